@@ -16,7 +16,7 @@
     constraints::OutlivesConstraint,
     dataflow::{calculate_borrows_out_of_scope_at_location, BorrowIndex, Borrows},
     facts::{AllFacts as PoloniusInput, RustcFacts},
-    location::{LocationTable, RichLocation},
+    location::{LocationIndex, LocationTable, RichLocation},
     nll::PoloniusOutput,
     place_ext::PlaceExt,
     places_conflict::{places_conflict, PlaceConflictBias},
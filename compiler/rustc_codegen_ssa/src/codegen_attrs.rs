@@ -338,6 +338,15 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, did: LocalDefId) -> CodegenFnAttrs {
                 }
             }
             sym::link_name => codegen_fn_attrs.link_name = attr.value_str(),
+            sym::codegen_unit => {
+                if let Some(val) = attr.value_str() {
+                    if val.as_str().is_empty() {
+                        tcx.sess.emit_err(errors::EmptyCodegenUnitName { span: attr.span });
+                    } else {
+                        codegen_fn_attrs.codegen_unit = Some(val);
+                    }
+                }
+            }
             sym::link_ordinal => {
                 link_ordinal_span = Some(attr.span);
                 if let ordinal @ Some(_) = check_link_ordinal(tcx, attr) {
@@ -575,6 +575,13 @@ pub struct ExpectedUsedSymbol {
     pub span: Span,
 }
 
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_empty_codegen_unit_name)]
+pub struct EmptyCodegenUnitName {
+    #[primary_span]
+    pub span: Span,
+}
+
 #[derive(Diagnostic)]
 #[diag(codegen_ssa_multiple_main_functions)]
 #[help]
@@ -212,7 +212,7 @@ fn do_call<Bx: BuilderMethods<'a, 'tcx>>(
                 unwind_block,
                 self.funclet(fx),
             );
-            if fx.mir[self.bb].is_cleanup {
+            if fx.mir[self.bb].is_cold {
                 bx.do_not_inline(invokeret);
             }
 
@@ -227,11 +227,11 @@ fn do_call<Bx: BuilderMethods<'a, 'tcx>>(
             MergingSucc::False
         } else {
             let llret = bx.call(fn_ty, fn_attrs, Some(&fn_abi), fn_ptr, &llargs, self.funclet(fx));
-            if fx.mir[self.bb].is_cleanup {
-                // Cleanup is always the cold path. Don't inline
-                // drop glue. Also, when there is a deeply-nested
-                // struct, there are "symmetry" issues that cause
-                // exponential inlining - see issue #41696.
+            if fx.mir[self.bb].is_cold {
+                // Don't inline calls on the cold path, which includes
+                // cleanup and its drop glue. Also, when there is a
+                // deeply-nested struct, there are "symmetry" issues
+                // that cause exponential inlining - see issue #41696.
                 bx.do_not_inline(llret);
             }
 
@@ -267,12 +267,30 @@ pub fn codegen_mir<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
     // So drop the builder of `start_llbb` to avoid having two at the same time.
     drop(start_bx);
 
+    // Create the backend blocks of the hot code first, so that cold blocks, like unwind paths and
+    // calls to `#[cold]` functions, are laid out at the end of the function. The blocks which may
+    // be merged into their predecessor are left to be created when they are reached.
+    for (bb, data) in traversal::reverse_postorder(&mir) {
+        if !data.is_cold && !is_merge_candidate(&mir, bb) {
+            fx.llbb(bb);
+        }
+    }
+
     // Codegen the body of each block using reverse postorder
     for (bb, _) in traversal::reverse_postorder(&mir) {
         fx.codegen_block(bb);
     }
 }
 
+/// Whether `bb` is the only successor of its only predecessor, in which case codegen may merge
+/// it into the backend block of its predecessor.
+fn is_merge_candidate(mir: &mir::Body<'_>, bb: mir::BasicBlock) -> bool {
+    match mir.basic_blocks.predecessors()[bb].as_slice() {
+        &[pred] => mir[pred].terminator().successors().count() == 1,
+        _ => false,
+    }
+}
+
 /// Produces, for each argument, a `Value` pointing at the
 /// argument's value. As arguments are places, these are always
 /// indirect.
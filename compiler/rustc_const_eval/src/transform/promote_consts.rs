@@ -710,6 +710,7 @@ fn new_block(&mut self) -> BasicBlock {
                 kind: TerminatorKind::Return,
             }),
             is_cleanup: false,
+            is_cold: false,
         })
     }
 
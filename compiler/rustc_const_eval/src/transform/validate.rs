@@ -126,16 +126,23 @@ impl<'a, 'tcx> CfgChecker<'a, 'tcx> {
     #[track_caller]
     fn fail(&self, location: Location, msg: impl AsRef<str>) {
         let span = self.body.source_info(location).span;
+        // Knowing which optimizations modified the body narrows down the pass at fault.
+        let applied_optimizations = if self.body.applied_optimizations.is_empty() {
+            String::new()
+        } else {
+            format!("\nthe body was modified by: {:?}", self.body.applied_optimizations)
+        };
         // We use `delay_span_bug` as we might see broken MIR when other errors have already
         // occurred.
         self.tcx.sess.diagnostic().delay_span_bug(
             span,
             format!(
-                "broken MIR in {:?} ({}) at {:?}:\n{}",
+                "broken MIR in {:?} ({}) at {:?}:\n{}{}",
                 self.body.source.instance,
                 self.when,
                 location,
-                msg.as_ref()
+                msg.as_ref(),
+                applied_optimizations,
             ),
         );
     }
@@ -390,6 +397,11 @@ fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
     }
 
     fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location) {
+        let block = &self.body.basic_blocks[location.block];
+        if block.is_cleanup && !block.is_cold {
+            self.fail(location, "cleanup block is not marked as cold");
+        }
+
         match &terminator.kind {
             TerminatorKind::Goto { target } => {
                 self.check_edge(location, *target, EdgeKind::Normal);
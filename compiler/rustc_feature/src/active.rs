@@ -376,6 +376,8 @@ pub fn set(&self, features: &mut Features, span: Span) {
     (active, closure_track_caller, "1.57.0", Some(87417), None),
     /// Allows to use the `#[cmse_nonsecure_entry]` attribute.
     (active, cmse_nonsecure_entry, "1.48.0", Some(75835), None),
+    /// Allows use of the `#[codegen_unit = "name"]` attribute to choose the codegen unit of an item.
+    (active, codegen_unit, "CURRENT_RUSTC_VERSION", None, None),
     /// Allows use of the `#[collapse_debuginfo]` attribute.
     (active, collapse_debuginfo, "1.65.0", Some(100758), None),
     /// Allows `async {}` expressions in const contexts.
@@ -427,6 +429,8 @@ pub fn set(&self, features: &mut Features, span: Span) {
     (active, doc_cfg_hide, "1.57.0", Some(43781), None),
     /// Allows `#[doc(masked)]`.
     (active, doc_masked, "1.21.0", Some(44027), None),
+    /// Allows `#![doc(smart_punctuation = false)]`.
+    (active, doc_smart_punctuation, "CURRENT_RUSTC_VERSION", None, None),
     /// Allows `dyn* Trait` objects.
     (incomplete, dyn_star, "1.65.0", Some(102425), None),
     // Uses generic effect parameters for ~const bounds
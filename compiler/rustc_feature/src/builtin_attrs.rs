@@ -483,6 +483,12 @@ pub struct BuiltinAttribute {
         experimental!(deprecated_safe),
     ),
 
+    // `#[codegen_unit = "name"]`
+    gated!(
+        codegen_unit, Normal, template!(NameValueStr: "name"), ErrorPreceding, @only_local: true,
+        experimental!(codegen_unit)
+    ),
+
     // `#[collapse_debuginfo]`
     gated!(
         collapse_debuginfo, Normal, template!(Word), WarnFollowing,
@@ -43,6 +43,9 @@ pub struct CodegenFnAttrs {
     /// The `#[repr(align(...))]` attribute. Indicates the value of which the function should be
     /// aligned to.
     pub alignment: Option<u32>,
+    /// The `#[codegen_unit = "..."]` attribute. Items with the same name are placed in the same
+    /// codegen unit by the partitioner.
+    pub codegen_unit: Option<Symbol>,
 }
 
 bitflags! {
@@ -123,6 +126,7 @@ pub const fn new() -> CodegenFnAttrs {
             no_sanitize: SanitizerSet::empty(),
             instruction_set: None,
             alignment: None,
+            codegen_unit: None,
         }
     }
 
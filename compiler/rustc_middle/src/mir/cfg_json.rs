@@ -0,0 +1,131 @@
+//! A machine-readable summary of the control-flow graph of a MIR body, written next to the
+//! `.mir` dumps when `-Z dump-mir-cfg-json` is passed.
+//!
+//! The summary is a JSON object of the form:
+//!
+//! ```json
+//! {
+//!   "def_path": "main",
+//!   "phase": "built",
+//!   "start": 0,
+//!   "blocks": [
+//!     {"id": 0, "terminator": "SwitchInt", "cleanup": false, "successors": [1, 2],
+//!      "immediate_dominator": null}
+//!   ],
+//!   "edges": [
+//!     {"source": 0, "target": 1, "kind": "normal", "label": "0"}
+//!   ],
+//!   "loop_headers": [1]
+//! }
+//! ```
+//!
+//! The `immediate_dominator` of the blocks form the dominator tree, rooted at the `start` block.
+//! Blocks which are not reachable from the start block don't have an immediate dominator either.
+//! The kind of an edge is `unwind` for the edges to cleanup blocks taken when unwinding,
+//! `imaginary` for the edges of `FalseEdge` terminators which are never taken, and `normal`
+//! otherwise. Its label is the one of the textual and graphviz dumps, like `otherwise`.
+//! The loop headers are the targets of the back edges: the edges whose target dominates their
+//! source, so only the headers of natural loops are listed.
+
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, TyCtxt};
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+/// Write the summary of the control-flow graph of `body` as JSON.
+pub fn write_mir_fn_cfg_json<'tcx, W>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    w: &mut W,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let dominators = body.basic_blocks.dominators();
+    // see notes on #41697 in `pretty.rs`
+    let def_path =
+        ty::print::with_forced_impl_filename_line!(tcx.def_path_str(body.source.def_id()));
+
+    writeln!(w, "{{")?;
+    writeln!(w, r#"  "def_path": {},"#, json_string(&def_path))?;
+    writeln!(w, r#"  "phase": {},"#, json_string(body.phase.name()))?;
+    writeln!(w, r#"  "start": {},"#, START_BLOCK.index())?;
+
+    writeln!(w, r#"  "blocks": ["#)?;
+    let mut edges = Vec::new();
+    let mut loop_headers = Vec::new();
+    for (bb, data) in body.basic_blocks.iter_enumerated() {
+        let terminator = data.terminator();
+        let successors: Vec<_> = terminator.successors().collect();
+        let labels = terminator.kind.fmt_successor_labels();
+        for (&target, label) in successors.iter().zip(labels) {
+            let kind = match &*label {
+                "unwind" => "unwind",
+                "imaginary" => "imaginary",
+                _ => "normal",
+            };
+            edges.push((bb, target, kind, label));
+            if dominators.is_reachable(bb)
+                && dominators.dominates(target, bb)
+                && !loop_headers.contains(&target)
+            {
+                loop_headers.push(target);
+            }
+        }
+
+        let immediate_dominator = match dominators.immediate_dominator(bb) {
+            Some(dom) => dom.index().to_string(),
+            None => "null".to_string(),
+        };
+        write!(
+            w,
+            r#"    {{"id": {}, "terminator": {}, "cleanup": {}, "successors": {}, "#,
+            bb.index(),
+            json_string(terminator.kind.name()),
+            data.is_cleanup,
+            json_list(successors.iter().map(|bb| bb.index())),
+        )?;
+        let separator = if bb.index() + 1 < body.basic_blocks.len() { "," } else { "" };
+        writeln!(w, r#""immediate_dominator": {immediate_dominator}}}{separator}"#)?;
+    }
+    writeln!(w, "  ],")?;
+
+    writeln!(w, r#"  "edges": ["#)?;
+    for (i, (source, target, kind, label)) in edges.iter().enumerate() {
+        writeln!(
+            w,
+            r#"    {{"source": {}, "target": {}, "kind": {}, "label": {}}}{}"#,
+            source.index(),
+            target.index(),
+            json_string(kind),
+            json_string(label),
+            if i + 1 < edges.len() { "," } else { "" },
+        )?;
+    }
+    writeln!(w, "  ],")?;
+
+    loop_headers.sort();
+    writeln!(w, r#"  "loop_headers": {}"#, json_list(loop_headers.iter().map(|bb| bb.index())))?;
+    writeln!(w, "}}")
+}
+
+fn json_list(items: impl Iterator<Item = usize>) -> String {
+    let items: Vec<_> = items.map(|item| item.to_string()).collect();
+    format!("[{}]", items.join(", "))
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str(r#"\""#),
+            '\\' => escaped.push_str(r"\\"),
+            '\n' => escaped.push_str(r"\n"),
+            c if c.is_control() => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
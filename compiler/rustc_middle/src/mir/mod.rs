@@ -45,6 +45,7 @@
 pub use basic_blocks::BasicBlocks;
 
 mod basic_blocks;
+pub mod cfg_json;
 mod consts;
 pub mod coverage;
 mod generic_graph;
@@ -63,6 +64,7 @@
 
 pub mod traversal;
 mod type_foldable;
+pub mod unreachable;
 pub mod visit;
 
 pub use self::generic_graph::graphviz_safe_def_name;
@@ -154,9 +156,24 @@ fn is_enabled(&self, _sess: &Session) -> bool {
 
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>);
 
+    /// Runs the pass like `run_pass`, and returns whether it may have changed `body`.
+    ///
+    /// This is used to iterate groups of passes until they stop changing the MIR. The default
+    /// implementation conservatively assumes that the pass always changes the body.
+    fn run_pass_reporting_changes(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) -> bool {
+        self.run_pass(tcx, body);
+        true
+    }
+
     fn is_mir_dump_enabled(&self) -> bool {
         true
     }
+
+    /// Returns `true` if this pass only runs other passes. Those are counted and dumped on their
+    /// own, so the group itself doesn't increase `Body::pass_count`.
+    fn is_group(&self) -> bool {
+        false
+    }
 }
 
 impl MirPhase {
@@ -177,45 +194,90 @@ pub fn phase_index(&self) -> usize {
         }
     }
 
-    /// Parses an `MirPhase` from a pair of strings. Panics if this isn't possible for any reason.
-    pub fn parse(dialect: String, phase: Option<String>) -> Self {
+    /// Parses an `MirPhase` from the names of a dialect and of one of its phases, like the ones
+    /// of the `#[custom_mir]` attribute. The names are case-insensitive, and the phase defaults
+    /// to the initial phase of the dialect.
+    pub fn parse(dialect: &str, phase: Option<&str>) -> Result<Self, MirPhaseParseError> {
         match &*dialect.to_ascii_lowercase() {
-            "built" => {
-                assert!(phase.is_none(), "Cannot specify a phase for `Built` MIR");
-                MirPhase::Built
-            }
-            "analysis" => Self::Analysis(AnalysisPhase::parse(phase)),
-            "runtime" => Self::Runtime(RuntimePhase::parse(phase)),
-            _ => bug!("Unknown MIR dialect: '{}'", dialect),
+            "built" => match phase {
+                Some(phase) => Err(MirPhaseParseError::PhaseOfBuilt(phase.to_string())),
+                None => Ok(MirPhase::Built),
+            },
+            "analysis" => Ok(Self::Analysis(AnalysisPhase::parse(phase)?)),
+            "runtime" => Ok(Self::Runtime(RuntimePhase::parse(phase)?)),
+            _ => Err(MirPhaseParseError::UnknownDialect(dialect.to_string())),
         }
     }
 }
 
 impl AnalysisPhase {
-    pub fn parse(phase: Option<String>) -> Self {
+    pub fn parse(phase: Option<&str>) -> Result<Self, MirPhaseParseError> {
         let Some(phase) = phase else {
-            return Self::Initial;
+            return Ok(Self::Initial);
         };
 
         match &*phase.to_ascii_lowercase() {
-            "initial" => Self::Initial,
-            "post_cleanup" | "post-cleanup" | "postcleanup" => Self::PostCleanup,
-            _ => bug!("Unknown analysis phase: '{}'", phase),
+            "initial" => Ok(Self::Initial),
+            "post_cleanup" | "post-cleanup" | "postcleanup" => Ok(Self::PostCleanup),
+            _ => Err(MirPhaseParseError::UnknownAnalysisPhase(phase.to_string())),
         }
     }
 }
 
 impl RuntimePhase {
-    pub fn parse(phase: Option<String>) -> Self {
+    pub fn parse(phase: Option<&str>) -> Result<Self, MirPhaseParseError> {
         let Some(phase) = phase else {
-            return Self::Initial;
+            return Ok(Self::Initial);
         };
 
         match &*phase.to_ascii_lowercase() {
-            "initial" => Self::Initial,
-            "post_cleanup" | "post-cleanup" | "postcleanup" => Self::PostCleanup,
-            "optimized" => Self::Optimized,
-            _ => bug!("Unknown runtime phase: '{}'", phase),
+            "initial" => Ok(Self::Initial),
+            "post_cleanup" | "post-cleanup" | "postcleanup" => Ok(Self::PostCleanup),
+            "optimized" => Ok(Self::Optimized),
+            _ => Err(MirPhaseParseError::UnknownRuntimePhase(phase.to_string())),
+        }
+    }
+}
+
+/// Why a [`MirPhase`] couldn't be parsed. It is displayed as a message listing the valid names,
+/// for the drivers which accept MIR phases from their users.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MirPhaseParseError {
+    /// The dialect isn't `built`, `analysis` or `runtime`.
+    UnknownDialect(String),
+    /// A phase was given for the `built` dialect, which doesn't have phases.
+    PhaseOfBuilt(String),
+    /// A phase was given without a dialect, which is then `built`.
+    PhaseWithoutDialect(String),
+    UnknownAnalysisPhase(String),
+    UnknownRuntimePhase(String),
+}
+
+impl fmt::Display for MirPhaseParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MirPhaseParseError::UnknownDialect(dialect) => write!(
+                f,
+                "unknown MIR dialect `{dialect}`, expected one of `built`, `analysis` or `runtime`"
+            ),
+            MirPhaseParseError::PhaseOfBuilt(phase) => write!(
+                f,
+                "cannot specify the phase `{phase}` for the `built` dialect, which has no phases"
+            ),
+            MirPhaseParseError::PhaseWithoutDialect(phase) => write!(
+                f,
+                "cannot specify the phase `{phase}` without a dialect, expected `analysis` or \
+                 `runtime`"
+            ),
+            MirPhaseParseError::UnknownAnalysisPhase(phase) => write!(
+                f,
+                "unknown analysis phase `{phase}`, expected one of `initial` or `post-cleanup`"
+            ),
+            MirPhaseParseError::UnknownRuntimePhase(phase) => write!(
+                f,
+                "unknown runtime phase `{phase}`, expected one of `initial`, `post-cleanup` or \
+                 `optimized`"
+            ),
         }
     }
 }
@@ -261,6 +323,29 @@ pub struct GeneratorInfo<'tcx> {
     pub generator_kind: GeneratorKind,
 }
 
+bitflags! {
+    /// The families of optimizations which changed a MIR body.
+    ///
+    /// This is encoded in the crate metadata along with the optimized MIR, so that the crates
+    /// inlining it know what it went through. A flag is only set when a pass of the family
+    /// actually modified the body, not merely when it ran.
+    #[derive(HashStable, TyEncodable, TyDecodable)]
+    pub struct AppliedOptimizations: u8 {
+        /// Calls were inlined into the body.
+        const INLINE                 = 1 << 0;
+        /// Operands were replaced by the constants they are known to evaluate to.
+        const CONST_PROP             = 1 << 1;
+        /// Locals were replaced by the locals or places they are copies of or refer to.
+        const COPY_PROP              = 1 << 2;
+        /// Locals were merged with the places they are moved to.
+        const DEST_PROP              = 1 << 3;
+        /// Aggregate locals were split into one local per field.
+        const SCALAR_REPLACEMENT     = 1 << 4;
+        /// Dead assignments were removed.
+        const DEAD_STORE_ELIMINATION = 1 << 5;
+    }
+}
+
 /// The lowered representation of a single function.
 #[derive(Clone, TyEncodable, TyDecodable, Debug, HashStable, TypeFoldable, TypeVisitable)]
 pub struct Body<'tcx> {
@@ -345,6 +430,14 @@ pub struct Body<'tcx> {
     pub injection_phase: Option<MirPhase>,
 
     pub tainted_by_errors: Option<ErrorGuaranteed>,
+
+    /// The families of optimizations which modified this body, including the ones which had
+    /// modified the bodies inlined into it.
+    pub applied_optimizations: AppliedOptimizations,
+
+    /// The unsafe operations of this body, as found by the MIR unsafety checker. This is only set
+    /// once the unsafety check has run, and only if the body contains unsafe operations.
+    pub unsafety_summary: Option<Box<UnsafetySummary>>,
 }
 
 impl<'tcx> Body<'tcx> {
@@ -392,6 +485,8 @@ pub fn new(
             is_polymorphic: false,
             injection_phase: None,
             tainted_by_errors,
+            applied_optimizations: AppliedOptimizations::empty(),
+            unsafety_summary: None,
         };
         body.is_polymorphic = body.has_non_region_param();
         body
@@ -420,6 +515,8 @@ pub fn new_cfg_only(basic_blocks: IndexVec<BasicBlock, BasicBlockData<'tcx>>) ->
             is_polymorphic: false,
             injection_phase: None,
             tainted_by_errors: None,
+            applied_optimizations: AppliedOptimizations::empty(),
+            unsafety_summary: None,
         };
         body.is_polymorphic = body.has_non_region_param();
         body
@@ -1217,11 +1314,20 @@ pub struct BasicBlockData<'tcx> {
     /// generated (particularly for MSVC cleanup). Unwind blocks must
     /// only branch to other unwind blocks.
     pub is_cleanup: bool,
+
+    /// If true, this block is unlikely to be executed: it lies on an unwind path, calls a
+    /// `#[cold]` function, or only leads to such blocks or to `unreachable`. Cleanup blocks are
+    /// always cold.
+    ///
+    /// This is computed once by the `MarkColdBlocks` pass, and blocks added afterwards must be
+    /// marked explicitly. It is used by the inliner, which doesn't inline calls in cold blocks,
+    /// and by codegen, which lays out cold blocks after the other ones.
+    pub is_cold: bool,
 }
 
 impl<'tcx> BasicBlockData<'tcx> {
     pub fn new(terminator: Option<Terminator<'tcx>>) -> BasicBlockData<'tcx> {
-        BasicBlockData { statements: vec![], terminator, is_cleanup: false }
+        BasicBlockData { statements: vec![], terminator, is_cleanup: false, is_cold: false }
     }
 
     /// Accessor for terminator.
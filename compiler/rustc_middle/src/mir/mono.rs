@@ -152,6 +152,17 @@ pub fn explicit_linkage(&self, tcx: TyCtxt<'tcx>) -> Option<Linkage> {
         codegen_fn_attrs.linkage
     }
 
+    /// Returns the name given by a `#[codegen_unit = "..."]` attribute on this item, if any.
+    pub fn codegen_unit_hint(&self, tcx: TyCtxt<'tcx>) -> Option<Symbol> {
+        let def_id = match *self {
+            MonoItem::Fn(Instance { def: InstanceDef::Item(def_id), .. }) => def_id,
+            MonoItem::Static(def_id) => def_id,
+            MonoItem::Fn(..) | MonoItem::GlobalAsm(..) => return None,
+        };
+
+        tcx.codegen_fn_attrs(def_id).codegen_unit
+    }
+
     /// Returns `true` if this instance is instantiable - whether it has no unsatisfied
     /// predicates.
     ///
@@ -261,6 +272,9 @@ pub struct MonoItemData {
 
     /// A cached copy of the result of `MonoItem::size_estimate`.
     pub size_estimate: usize,
+
+    /// A cached copy of the result of `MonoItem::codegen_unit_hint`, for root items.
+    pub codegen_unit_hint: Option<Symbol>,
 }
 
 /// Specifies the linkage type for a `MonoItem`.
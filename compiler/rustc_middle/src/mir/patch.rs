@@ -1,7 +1,10 @@
+#[cfg(test)]
+mod tests;
+
 use rustc_index::{Idx, IndexVec};
 use rustc_middle::mir::*;
 use rustc_middle::ty::Ty;
-use rustc_span::Span;
+use rustc_span::{Span, Symbol};
 
 /// This struct represents a patch to MIR, which can add
 /// new statements and basic blocks and patch over block
@@ -11,6 +14,7 @@ pub struct MirPatch<'tcx> {
     new_blocks: Vec<BasicBlockData<'tcx>>,
     new_statements: Vec<(Location, StatementKind<'tcx>)>,
     new_locals: Vec<LocalDecl<'tcx>>,
+    new_var_debug_info: Vec<VarDebugInfo<'tcx>>,
     resume_block: Option<BasicBlock>,
     // Only for unreachable in cleanup path.
     unreachable_cleanup_block: Option<BasicBlock>,
@@ -27,6 +31,7 @@ pub fn new(body: &Body<'tcx>) -> Self {
             new_blocks: vec![],
             new_statements: vec![],
             new_locals: vec![],
+            new_var_debug_info: vec![],
             next_local: body.local_decls.len(),
             resume_block: None,
             unreachable_cleanup_block: None,
@@ -76,6 +81,7 @@ pub fn resume_block(&mut self) -> BasicBlock {
                 kind: TerminatorKind::UnwindResume,
             }),
             is_cleanup: true,
+            is_cold: true,
         });
         self.resume_block = Some(bb);
         bb
@@ -93,6 +99,7 @@ pub fn unreachable_cleanup_block(&mut self) -> BasicBlock {
                 kind: TerminatorKind::Unreachable,
             }),
             is_cleanup: true,
+            is_cold: true,
         });
         self.unreachable_cleanup_block = Some(bb);
         bb
@@ -110,6 +117,7 @@ pub fn terminate_block(&mut self, reason: UnwindTerminateReason) -> BasicBlock {
                 kind: TerminatorKind::UnwindTerminate(reason),
             }),
             is_cleanup: true,
+            is_cold: true,
         });
         self.terminate_block = Some((bb, reason));
         bb
@@ -155,6 +163,34 @@ pub fn new_internal(&mut self, ty: Ty<'tcx>, span: Span) -> Local {
         Local::new(index)
     }
 
+    /// Adds a new local which is shown with the name `name` in debuggers, in the scope of
+    /// `source_info`. Unlike the temporaries of [`MirPatch::new_temp`], its value can be
+    /// inspected, which is what the temporaries added by instrumentation passes need.
+    pub fn new_user_visible_temp(
+        &mut self,
+        ty: Ty<'tcx>,
+        name: Symbol,
+        source_info: SourceInfo,
+    ) -> Local {
+        let local = self.new_temp(ty, source_info.span);
+        self.new_locals.last_mut().unwrap().source_info = source_info;
+        self.add_var_debug_info(local, name, source_info);
+        local
+    }
+
+    /// Makes `local` visible in debuggers with the name `name`, in the scope of `source_info`.
+    /// The local can be one of the body or one added by this patch.
+    pub fn add_var_debug_info(&mut self, local: Local, name: Symbol, source_info: SourceInfo) {
+        debug!("MirPatch: add_var_debug_info({:?}, {:?}, {:?})", local, name, source_info);
+        self.new_var_debug_info.push(VarDebugInfo {
+            name,
+            source_info,
+            composite: None,
+            value: VarDebugInfoContents::Place(local.into()),
+            argument_index: None,
+        });
+    }
+
     pub fn new_block(&mut self, data: BasicBlockData<'tcx>) -> BasicBlock {
         let block = BasicBlock::new(self.patch_map.len());
         debug!("MirPatch: new_block: {:?}: {:?}", block, data);
@@ -197,6 +233,7 @@ pub fn apply(self, body: &mut Body<'tcx>) {
         };
         bbs.extend(self.new_blocks);
         body.local_decls.extend(self.new_locals);
+        body.var_debug_info.extend(self.new_var_debug_info);
         for (src, patch) in self.patch_map.into_iter_enumerated() {
             if let Some(patch) = patch {
                 debug!("MirPatch: patching block {:?}", src);
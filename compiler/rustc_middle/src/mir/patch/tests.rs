@@ -0,0 +1,49 @@
+use super::MirPatch;
+use crate::mir::*;
+use rustc_index::IndexVec;
+use rustc_span::{create_default_session_globals_then, Symbol, DUMMY_SP};
+
+/// Creates a `Body` whose only block returns.
+fn returning_body<'tcx>() -> Body<'tcx> {
+    let source_info = SourceInfo::outermost(DUMMY_SP);
+    let mut blocks = IndexVec::new();
+    blocks.push(BasicBlockData {
+        statements: vec![],
+        terminator: Some(Terminator { source_info, kind: TerminatorKind::Return }),
+        is_cleanup: false,
+        is_cold: false,
+    });
+    Body::new_cfg_only(blocks)
+}
+
+#[test]
+fn var_debug_info_is_applied() {
+    create_default_session_globals_then(|| {
+        let mut body = returning_body();
+        let source_info = SourceInfo::outermost(DUMMY_SP);
+        let name = Symbol::intern("counter");
+
+        let mut patch = MirPatch::new(&body);
+        patch.add_var_debug_info(RETURN_PLACE, name, source_info);
+        // Also change the CFG, which takes another path in `apply`.
+        let unreachable = patch.new_block(BasicBlockData::new(Some(Terminator {
+            source_info,
+            kind: TerminatorKind::Unreachable,
+        })));
+        patch.patch_terminator(START_BLOCK, TerminatorKind::Goto { target: unreachable });
+        patch.apply(&mut body);
+
+        assert_eq!(body.basic_blocks.len(), 2);
+        let [debug_info] = &body.var_debug_info[..] else {
+            panic!("expected a single debug info, found {}", body.var_debug_info.len());
+        };
+        assert_eq!(debug_info.name, name);
+        assert_eq!(debug_info.source_info, source_info);
+        assert!(debug_info.composite.is_none());
+        assert!(debug_info.argument_index.is_none());
+        match &debug_info.value {
+            VarDebugInfoContents::Place(place) => assert_eq!(*place, Place::from(RETURN_PLACE)),
+            value => panic!("expected the place of the return local, found {value:?}"),
+        }
+    })
+}
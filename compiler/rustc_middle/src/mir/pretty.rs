@@ -4,6 +4,7 @@
 use std::io::{self, Write as _};
 use std::path::{Path, PathBuf};
 
+use super::cfg_json::write_mir_fn_cfg_json;
 use super::graphviz::write_mir_fn_graphviz;
 use super::spanview::write_mir_fn_spanview;
 use either::Either;
@@ -18,7 +19,8 @@
 use rustc_middle::mir::visit::Visitor;
 use rustc_middle::mir::*;
 use rustc_middle::ty::{self, TyCtxt};
-use rustc_target::abi::Size;
+use rustc_span::Symbol;
+use rustc_target::abi::{Size, VariantIdx};
 
 const INDENT: &str = "    ";
 /// Alignment for lining up comments following MIR statements
@@ -108,6 +110,25 @@ pub fn dump_enabled(tcx: TyCtxt<'_>, pass_name: &str, def_id: DefId) -> bool {
 // `def_path_str()` would otherwise trigger `type_of`, and this can
 // run while we are already attempting to evaluate `type_of`.
 
+fn write_unsafety_summary(
+    tcx: TyCtxt<'_>,
+    summary: &UnsafetySummary,
+    w: &mut dyn io::Write,
+) -> io::Result<()> {
+    writeln!(w, "/* unsafety_summary = [")?;
+    for op in &summary.operations {
+        let (description, _) = op.details.description_and_note();
+        write!(w, "{INDENT}{description}")?;
+        if let Some(callee) = op.callee {
+            // see notes on #41697 above
+            let callee = ty::print::with_forced_impl_filename_line!(tcx.def_path_str(callee));
+            write!(w, " `{callee}`")?;
+        }
+        writeln!(w, " at {},", tcx.sess.source_map().span_to_embeddable_string(op.span))?;
+    }
+    writeln!(w, "] */")
+}
+
 fn dump_matched_mir_node<'tcx, F>(
     tcx: TyCtxt<'tcx>,
     pass_num: bool,
@@ -133,6 +154,11 @@ fn dump_matched_mir_node<'tcx, F>(
         if let Some(ref layout) = body.generator_layout() {
             writeln!(file, "/* generator_layout = {layout:#?} */")?;
         }
+        if tcx.sess.opts.unstable_opts.dump_mir_unsafety
+            && let Some(ref summary) = body.unsafety_summary
+        {
+            write_unsafety_summary(tcx, summary, &mut file)?;
+        }
         writeln!(file)?;
         extra_data(PassWhere::BeforeCFG, &mut file)?;
         write_user_type_annotations(tcx, body, &mut file)?;
@@ -147,6 +173,14 @@ fn dump_matched_mir_node<'tcx, F>(
         };
     }
 
+    if tcx.sess.opts.unstable_opts.dump_mir_cfg_json {
+        let _: io::Result<()> = try {
+            let mut file =
+                create_dump_file(tcx, "cfg.json", pass_num, pass_name, disambiguator, body)?;
+            write_mir_fn_cfg_json(tcx, body, &mut file)?;
+        };
+    }
+
     if let Some(spanview) = tcx.sess.opts.unstable_opts.dump_mir_spanview {
         let _: io::Result<()> = try {
             let file_basename = dump_file_basename(tcx, pass_num, pass_name, disambiguator, body);
@@ -598,7 +632,13 @@ pub fn write_basic_block<'tcx, F>(
     let data = &body[block];
 
     // Basic block label at the top.
-    let cleanup_text = if data.is_cleanup { " (cleanup)" } else { "" };
+    let cleanup_text = if data.is_cleanup {
+        " (cleanup)"
+    } else if data.is_cold && tcx.sess.opts.unstable_opts.dump_mir_cold_blocks {
+        " (cold)"
+    } else {
+        ""
+    };
     writeln!(w, "{INDENT}{block:?}{cleanup_text}: {{")?;
 
     // List of statements in the middle.
@@ -630,7 +670,20 @@ pub fn write_basic_block<'tcx, F>(
 
     // Terminator at the bottom.
     extra_data(PassWhere::BeforeLocation(current_location), w)?;
-    let indented_terminator = format!("{0}{0}{1:?};", INDENT, data.terminator().kind);
+    let variant_names = if tcx.sess.opts.unstable_opts.mir_pretty_variant_names {
+        switch_variant_names(tcx, body, &data.terminator().kind)
+    } else {
+        None
+    };
+    let indented_terminator = match variant_names {
+        Some(variant_names) => {
+            format!(
+                "{INDENT}{INDENT}{};",
+                SwitchWithVariantNames(&data.terminator().kind, &variant_names)
+            )
+        }
+        None => format!("{0}{0}{1:?};", INDENT, data.terminator().kind),
+    };
     if tcx.sess.opts.unstable_opts.mir_include_spans {
         writeln!(
             w,
@@ -654,6 +707,67 @@ pub fn write_basic_block<'tcx, F>(
     writeln!(w, "{INDENT}}}")
 }
 
+/// If `kind` is a `SwitchInt` on the discriminant of an enum, returns the names of the variants
+/// corresponding to each of its values, in the order of its targets. The discriminant must be read
+/// into a local which is assigned only once in `body`.
+fn switch_variant_names<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    kind: &TerminatorKind<'tcx>,
+) -> Option<Vec<Option<Symbol>>> {
+    let TerminatorKind::SwitchInt { discr, targets } = kind else { return None };
+    let discr_local = discr.place()?.as_local()?;
+
+    let mut discr_rvalue = None;
+    for statement in body.basic_blocks.iter().flat_map(|data| &data.statements) {
+        if let StatementKind::Assign(box (place, rvalue)) = &statement.kind
+            && place.as_local() == Some(discr_local)
+        {
+            if discr_rvalue.is_some() {
+                return None;
+            }
+            discr_rvalue = Some(rvalue);
+        }
+    }
+    let Some(Rvalue::Discriminant(enum_place)) = discr_rvalue else { return None };
+    let ty::Adt(adt_def, _) = enum_place.ty(&body.local_decls, tcx).ty.kind() else { return None };
+    if !adt_def.is_enum() {
+        return None;
+    }
+
+    let discriminants: FxHashMap<u128, VariantIdx> =
+        adt_def.discriminants(tcx).map(|(variant, discr)| (discr.val, variant)).collect();
+    Some(
+        targets
+            .iter()
+            .map(|(value, _)| {
+                discriminants.get(&value).map(|&variant| adt_def.variant(variant).name)
+            })
+            .collect(),
+    )
+}
+
+/// Formats a `SwitchInt` terminator like its `Debug` implementation, with the names of the
+/// variants returned by [`switch_variant_names`] after the targets.
+struct SwitchWithVariantNames<'a, 'tcx>(&'a TerminatorKind<'tcx>, &'a [Option<Symbol>]);
+
+impl Display for SwitchWithVariantNames<'_, '_> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        let SwitchWithVariantNames(kind, variant_names) = *self;
+        let TerminatorKind::SwitchInt { targets, .. } = kind else { unreachable!() };
+        kind.fmt_head(fmt)?;
+        write!(fmt, " -> [")?;
+        for ((value, target), name) in targets.iter().zip(variant_names) {
+            write!(fmt, "{value}: {target:?}")?;
+            if let Some(name) = name {
+                write!(fmt, " /* {name} */")?;
+            }
+            write!(fmt, ", ")?;
+        }
+        write!(fmt, "otherwise: {:?}]", targets.otherwise())
+    }
+}
+
 impl Debug for Statement<'_> {
     fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         use self::StatementKind::*;
@@ -5,7 +5,7 @@
 use rustc_data_structures::unord::UnordSet;
 use rustc_errors::ErrorGuaranteed;
 use rustc_hir as hir;
-use rustc_hir::def_id::LocalDefId;
+use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_index::bit_set::BitMatrix;
 use rustc_index::{Idx, IndexVec};
 use rustc_span::symbol::Symbol;
@@ -127,6 +127,43 @@ pub struct UnsafetyCheckResult {
 
     /// This is `Some` iff the item is not a closure.
     pub unused_unsafes: Option<Vec<(hir::HirId, UnusedUnsafe)>>,
+
+    /// The unsafe operations of this function, whether they are allowed or not.
+    pub summary: UnsafetySummary,
+}
+
+/// An unsafe operation found by the unsafety checker.
+#[derive(Copy, Clone, PartialEq, TyEncodable, TyDecodable, HashStable, Debug)]
+pub struct UnsafeOperation {
+    pub details: UnsafetyViolationDetails,
+    pub span: Span,
+    /// The function called, for calls to unsafe functions and to functions with
+    /// `#[target_feature]`, when it is known statically.
+    pub callee: Option<DefId>,
+}
+
+/// The unsafe operations of a body, like dereferences of raw pointers, accesses to union fields,
+/// inline assembly and calls to unsafe functions. Unlike the violations of
+/// [`UnsafetyCheckResult`], this also contains the operations which are allowed because they are
+/// inside of an `unsafe` block or function, and doesn't include the operations of closures.
+#[derive(Clone, Debug, Default, TyEncodable, TyDecodable, HashStable)]
+pub struct UnsafetySummary {
+    /// The operations, in the order in which they were found, without duplicates.
+    pub operations: Vec<UnsafeOperation>,
+}
+
+impl UnsafetySummary {
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Returns the operations of the given kind.
+    pub fn operations_of(
+        &self,
+        details: UnsafetyViolationDetails,
+    ) -> impl Iterator<Item = &UnsafeOperation> + '_ {
+        self.operations.iter().filter(move |op| op.details == details)
+    }
 }
 
 rustc_index::newtype_index! {
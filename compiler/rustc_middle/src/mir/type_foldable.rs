@@ -8,6 +8,8 @@
 TrivialTypeTraversalImpls! {
     BlockTailInfo,
     MirPhase,
+    AppliedOptimizations,
+    UnsafetySummary,
     SourceInfo,
     FakeReadCause,
     RetagKind,
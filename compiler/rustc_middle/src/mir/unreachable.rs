@@ -0,0 +1,152 @@
+//! Propagation of `Unreachable` terminators to the blocks which can only reach them, for the MIR
+//! passes which need to know which blocks are unreachable.
+
+use rustc_ast::InlineAsmOptions;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_middle::mir::*;
+
+/// Which terminators stop the propagation of unreachability to their block.
+///
+/// `Goto` and `SwitchInt` terminators never do: a block which can only branch to unreachable
+/// blocks is unreachable too. Terminators with side effects, like `Assert`s, calls and drops,
+/// always do: an `Assert` whose target is unreachable must still panic when its condition
+/// doesn't hold, so its block stays reachable.
+#[derive(Copy, Clone, Debug)]
+pub struct UnreachableBarriers {
+    /// Whether `FalseEdge` and `FalseUnwind` terminators are kept even if their real target is
+    /// unreachable. Their imaginary edges are needed until borrowck has run.
+    pub false_edges: bool,
+    /// Whether `InlineAsm` terminators are kept even if their destination is unreachable.
+    /// Otherwise, such inline assembly is marked as diverging, as returning from it would be
+    /// undefined behavior.
+    pub inline_asm: bool,
+}
+
+impl UnreachableBarriers {
+    /// Keeps all the terminators which can be barriers, for the passes which also run on
+    /// unoptimized bodies, like `SimplifyCfg` and the generator lowering.
+    pub const ALL: UnreachableBarriers =
+        UnreachableBarriers { false_edges: true, inline_asm: true };
+
+    /// Barriers for bodies which have been borrowck'd, and in which `FalseEdge`s and
+    /// `FalseUnwind`s can be removed.
+    pub const OPTIMIZED: UnreachableBarriers =
+        UnreachableBarriers { false_edges: false, inline_asm: false };
+}
+
+/// The result of [`propagate_unreachable`].
+#[derive(Debug, Default)]
+pub struct UnreachableBlocks<'tcx> {
+    /// The blocks which are unreachable, either because their terminator is `Unreachable` or
+    /// because they can only reach unreachable blocks.
+    pub blocks: FxHashSet<BasicBlock>,
+    /// The new terminators of the blocks whose unreachable successors can be removed. The
+    /// terminators of the unreachable blocks are replaced by `Unreachable`.
+    pub replacements: FxHashMap<BasicBlock, TerminatorKind<'tcx>>,
+}
+
+/// Finds the unreachable blocks of `body`, through a post-order traversal of its blocks, and the
+/// terminators which can be simplified accordingly. The body isn't modified, so that passes can
+/// decide which replacements to apply.
+pub fn propagate_unreachable<'tcx>(
+    body: &Body<'tcx>,
+    barriers: UnreachableBarriers,
+) -> UnreachableBlocks<'tcx> {
+    let mut unreachable = UnreachableBlocks::default();
+
+    for (bb, bb_data) in traversal::postorder(body) {
+        let terminator = bb_data.terminator();
+        if terminator.kind == TerminatorKind::Unreachable {
+            unreachable.blocks.insert(bb);
+        } else {
+            let is_unreachable = |succ: BasicBlock| unreachable.blocks.contains(&succ);
+            let terminator_kind_opt = remove_successors(&terminator.kind, barriers, is_unreachable);
+
+            if let Some(terminator_kind) = terminator_kind_opt {
+                if terminator_kind == TerminatorKind::Unreachable {
+                    unreachable.blocks.insert(bb);
+                }
+                unreachable.replacements.insert(bb, terminator_kind);
+            }
+        }
+    }
+
+    unreachable
+}
+
+fn remove_successors<'tcx, F>(
+    terminator_kind: &TerminatorKind<'tcx>,
+    barriers: UnreachableBarriers,
+    is_unreachable: F,
+) -> Option<TerminatorKind<'tcx>>
+where
+    F: Fn(BasicBlock) -> bool,
+{
+    let terminator = match terminator_kind {
+        // This will unconditionally run into an unreachable and is therefore unreachable as well.
+        TerminatorKind::Goto { target } if is_unreachable(*target) => TerminatorKind::Unreachable,
+        TerminatorKind::FalseEdge { real_target, .. }
+        | TerminatorKind::FalseUnwind { real_target, .. }
+            if !barriers.false_edges && is_unreachable(*real_target) =>
+        {
+            TerminatorKind::Unreachable
+        }
+        TerminatorKind::InlineAsm {
+            template,
+            operands,
+            options,
+            line_spans,
+            destination: Some(destination),
+            unwind,
+        } if !barriers.inline_asm && is_unreachable(*destination) => TerminatorKind::InlineAsm {
+            template: *template,
+            operands: operands.clone(),
+            options: *options | InlineAsmOptions::NORETURN,
+            line_spans: *line_spans,
+            destination: None,
+            unwind: *unwind,
+        },
+        TerminatorKind::SwitchInt { targets, discr } => {
+            let otherwise = targets.otherwise();
+
+            // If all targets are unreachable, we can be unreachable as well.
+            if targets.all_targets().iter().all(|bb| is_unreachable(*bb)) {
+                TerminatorKind::Unreachable
+            } else if is_unreachable(otherwise) {
+                // If there are multiple targets, don't delete unreachable branches (like an unreachable otherwise)
+                // unless otherwise is unreachable, in which case deleting a normal branch causes it to be merged with
+                // the otherwise, keeping its unreachable.
+                // This looses information about reachability causing worse codegen.
+                // For example (see tests/codegen/match-optimizes-away.rs)
+                //
+                // pub enum Two { A, B }
+                // pub fn identity(x: Two) -> Two {
+                //     match x {
+                //         Two::A => Two::A,
+                //         Two::B => Two::B,
+                //     }
+                // }
+                //
+                // This generates a `switchInt() -> [0: 0, 1: 1, otherwise: unreachable]`, which allows us or LLVM to
+                // turn it into just `x` later. Without the unreachable, such a transformation would be illegal.
+                // If the otherwise branch is unreachable, we can delete all other unreachable targets, as they will
+                // still point to the unreachable and therefore not lose reachability information.
+                let reachable_iter = targets.iter().filter(|(_, bb)| !is_unreachable(*bb));
+
+                let new_targets = SwitchTargets::new(reachable_iter, otherwise);
+
+                // No unreachable branches were removed.
+                if new_targets.all_targets().len() == targets.all_targets().len() {
+                    return None;
+                }
+
+                TerminatorKind::SwitchInt { discr: discr.clone(), targets: new_targets }
+            } else {
+                // If the otherwise branch is reachable, we don't want to delete any unreachable branches.
+                return None;
+            }
+        }
+        _ => return None,
+    };
+    Some(terminator)
+}
@@ -38,7 +38,8 @@
 //!     let BasicBlockData {
 //!         statements,
 //!         terminator,
-//!         is_cleanup: _
+//!         is_cleanup: _,
+//!         is_cold: _,
 //!     } = *data;
 //!
 //!     for statement in statements {
@@ -298,7 +299,8 @@ fn super_basic_block_data(&mut self,
                 let BasicBlockData {
                     statements,
                     terminator,
-                    is_cleanup: _
+                    is_cleanup: _,
+                    is_cold: _,
                 } = data;
 
                 let mut index = 0;
@@ -874,6 +874,12 @@
         cache_on_disk_if { true }
     }
 
+    /// The unsafe operations of this `LocalDefId`, allowed or not, as found by the MIR unsafety
+    /// checker. This is always empty with `-Zthir-unsafeck`.
+    query unsafety_summary(key: LocalDefId) -> &'tcx mir::UnsafetySummary {
+        desc { |tcx| "summarizing the unsafe operations of `{}`", tcx.def_path_str(key) }
+    }
+
     /// Unsafety-check this `LocalDefId` with THIR unsafeck. This should be
     /// used with `-Zthir-unsafeck`.
     query thir_check_unsafety(key: LocalDefId) {
@@ -22,7 +22,9 @@ pub(crate) fn start_new_block(&mut self) -> BasicBlock {
 
     pub(crate) fn start_new_cleanup_block(&mut self) -> BasicBlock {
         let bb = self.start_new_block();
-        self.block_data_mut(bb).is_cleanup = true;
+        let block_data = self.block_data_mut(bb);
+        block_data.is_cleanup = true;
+        block_data.is_cold = true;
         bb
     }
 
@@ -74,7 +74,7 @@ pub(super) fn build_custom_mir<'tcx>(
             safety: Safety::Safe,
         }),
     });
-    body.injection_phase = Some(parse_attribute(attr));
+    body.injection_phase = Some(parse_attribute(tcx, attr));
 
     let mut pctxt = ParseCtxt {
         tcx,
@@ -100,10 +100,10 @@ pub(super) fn build_custom_mir<'tcx>(
     body
 }
 
-fn parse_attribute(attr: &Attribute) -> MirPhase {
+fn parse_attribute(tcx: TyCtxt<'_>, attr: &Attribute) -> MirPhase {
     let meta_items = attr.meta_item_list().unwrap();
-    let mut dialect: Option<String> = None;
-    let mut phase: Option<String> = None;
+    let mut dialect: Option<(String, Span)> = None;
+    let mut phase: Option<(String, Span)> = None;
 
     for nested in meta_items {
         let name = nested.name_or_empty();
@@ -111,11 +111,11 @@ fn parse_attribute(attr: &Attribute) -> MirPhase {
         match name.as_str() {
             "dialect" => {
                 assert!(dialect.is_none());
-                dialect = Some(value);
+                dialect = Some((value, nested.span()));
             }
             "phase" => {
                 assert!(phase.is_none());
-                phase = Some(value);
+                phase = Some((value, nested.span()));
             }
             other => {
                 span_bug!(
@@ -127,12 +127,22 @@ fn parse_attribute(attr: &Attribute) -> MirPhase {
         }
     }
 
-    let Some(dialect) = dialect else {
-        assert!(phase.is_none());
+    let Some((dialect, dialect_span)) = dialect else {
+        if let Some((phase, phase_span)) = phase {
+            let err = MirPhaseParseError::PhaseWithoutDialect(phase);
+            tcx.sess.diagnostic().span_fatal(phase_span, err.to_string());
+        }
         return MirPhase::Built;
     };
 
-    MirPhase::parse(dialect, phase)
+    let phase_name = phase.as_ref().map(|(phase, _)| phase.as_str());
+    MirPhase::parse(&dialect, phase_name).unwrap_or_else(|err| {
+        let span = match (&err, &phase) {
+            (MirPhaseParseError::UnknownDialect(_), _) | (_, None) => dialect_span,
+            (_, Some((_, phase_span))) => *phase_span,
+        };
+        tcx.sess.diagnostic().span_fatal(span, err.to_string())
+    })
 }
 
 struct ParseCtxt<'tcx, 'body> {
@@ -449,6 +449,7 @@ fn open_drop_for_adt(
                     kind: TerminatorKind::Unreachable,
                 }),
                 is_cleanup: self.unwind.is_cleanup(),
+                is_cold: self.unwind.is_cleanup(),
             });
         }
 
@@ -620,6 +621,7 @@ fn adt_switch_block(
                 },
             }),
             is_cleanup: unwind.is_cleanup(),
+            is_cold: unwind.is_cleanup(),
         };
         let switch_block = self.elaborator.patch().new_block(switch_block);
         self.drop_flag_test_block(switch_block, succ, unwind)
@@ -667,6 +669,7 @@ fn destructor_call_block(&mut self, (succ, unwind): (BasicBlock, Unwind)) -> Bas
                 source_info: self.source_info,
             }),
             is_cleanup: unwind.is_cleanup(),
+            is_cold: unwind.is_cleanup(),
         };
 
         let destructor_block = self.elaborator.patch().new_block(result);
@@ -717,6 +720,7 @@ fn drop_loop(
                 ),
             ],
             is_cleanup: unwind.is_cleanup(),
+            is_cold: unwind.is_cleanup(),
             terminator: Some(Terminator {
                 source_info: self.source_info,
                 // this gets overwritten by drop elaboration.
@@ -731,6 +735,7 @@ fn drop_loop(
                 Rvalue::BinaryOp(BinOp::Eq, Box::new((copy(Place::from(cur)), copy(len.into())))),
             )],
             is_cleanup: unwind.is_cleanup(),
+            is_cold: unwind.is_cleanup(),
             terminator: Some(Terminator {
                 source_info: self.source_info,
                 kind: TerminatorKind::if_(move_(can_go), succ, drop_block),
@@ -837,6 +842,7 @@ fn drop_loop_pair(&mut self, ety: Ty<'tcx>) -> BasicBlock {
                 self.assign(cur.into(), Rvalue::Use(zero)),
             ],
             is_cleanup: unwind.is_cleanup(),
+            is_cold: unwind.is_cleanup(),
             terminator: Some(Terminator {
                 source_info: self.source_info,
                 kind: TerminatorKind::Goto { target: loop_block },
@@ -965,6 +971,7 @@ fn new_block(&mut self, unwind: Unwind, k: TerminatorKind<'tcx>) -> BasicBlock {
             statements: vec![],
             terminator: Some(Terminator { source_info: self.source_info, kind: k }),
             is_cleanup: unwind.is_cleanup(),
+            is_cold: unwind.is_cleanup(),
         })
     }
 
@@ -25,6 +25,7 @@ fn mock_body<'tcx>() -> mir::Body<'tcx> {
             statements: std::iter::repeat(&nop).cloned().take(n).collect(),
             terminator: Some(mir::Terminator { source_info, kind }),
             is_cleanup: false,
+            is_cold: false,
         })
     };
 
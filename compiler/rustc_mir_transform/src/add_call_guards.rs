@@ -62,6 +62,7 @@ pub fn add_call_guards(&self, body: &mut Body<'_>) {
                     let call_guard = BasicBlockData {
                         statements: vec![],
                         is_cleanup: block.is_cleanup,
+                        is_cold: block.is_cold,
                         terminator: Some(Terminator {
                             source_info,
                             kind: TerminatorKind::Goto { target: *destination },
@@ -26,6 +26,11 @@ pub struct UnsafetyChecker<'a, 'tcx> {
 
     /// Used `unsafe` blocks in this function. This is used for the "unused_unsafe" lint.
     used_unsafe_blocks: UnordSet<HirId>,
+
+    /// The unsafe operations of this function, allowed or not.
+    summary: UnsafetySummary,
+    /// The function called by the terminator being visited, if any.
+    callee: Option<DefId>,
 }
 
 impl<'a, 'tcx> UnsafetyChecker<'a, 'tcx> {
@@ -43,6 +48,8 @@ fn new(
             tcx,
             param_env,
             used_unsafe_blocks: Default::default(),
+            summary: Default::default(),
+            callee: None,
         }
     }
 }
@@ -70,6 +77,7 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                 let func_ty = func.ty(self.body, self.tcx);
                 let func_id =
                     if let ty::FnDef(func_id, _) = func_ty.kind() { Some(func_id) } else { None };
+                self.callee = func_id.copied();
                 let sig = func_ty.fn_sig(self.tcx);
                 if let hir::Unsafety::Unsafe = sig.unsafety() {
                     self.require_unsafe(
@@ -81,6 +89,7 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                 if let Some(func_id) = func_id {
                     self.check_target_features(*func_id);
                 }
+                self.callee = None;
             }
 
             TerminatorKind::InlineAsm { .. } => self.require_unsafe(
@@ -264,6 +273,11 @@ fn require_unsafe(&mut self, kind: UnsafetyViolationKind, details: UnsafetyViola
         assert_ne!(kind, UnsafetyViolationKind::UnsafeFn);
 
         let source_info = self.source_info;
+        let operation = UnsafeOperation { details, span: source_info.span, callee: self.callee };
+        if !self.summary.operations.contains(&operation) {
+            self.summary.operations.push(operation);
+        }
+
         let lint_root = self.body.source_scopes[self.source_info.scope]
             .local_data
             .as_ref()
@@ -376,7 +390,7 @@ fn check_target_features(&mut self, func_did: DefId) {
 }
 
 pub(crate) fn provide(providers: &mut Providers) {
-    *providers = Providers { unsafety_check_result, ..*providers };
+    *providers = Providers { unsafety_check_result, unsafety_summary, ..*providers };
 }
 
 /// Context information for [`UnusedUnsafeVisitor`] traversal,
@@ -488,6 +502,7 @@ fn unsafety_check_result(tcx: TyCtxt<'_>, def: LocalDefId) -> &UnsafetyCheckResu
             violations: Vec::new(),
             used_unsafe_blocks: Default::default(),
             unused_unsafes: Some(Vec::new()),
+            summary: Default::default(),
         });
     }
 
@@ -503,9 +518,17 @@ fn unsafety_check_result(tcx: TyCtxt<'_>, def: LocalDefId) -> &UnsafetyCheckResu
         violations: checker.violations,
         used_unsafe_blocks: checker.used_unsafe_blocks,
         unused_unsafes,
+        summary: checker.summary,
     })
 }
 
+fn unsafety_summary(tcx: TyCtxt<'_>, def: LocalDefId) -> &UnsafetySummary {
+    if tcx.sess.opts.unstable_opts.thir_unsafeck {
+        return tcx.arena.alloc(UnsafetySummary::default());
+    }
+    &tcx.unsafety_check_result(def).summary
+}
+
 fn report_unused_unsafe(tcx: TyCtxt<'_>, kind: UnusedUnsafe, id: HirId) {
     let span = tcx.sess.source_map().guess_head_span(tcx.hir().span(id));
     let nested_parent = if let UnusedUnsafe::InUnsafeBlock(id) = kind {
@@ -107,8 +107,7 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
             optimization_finder.visit_basic_block_data(bb, data);
         }
 
-        let mut patch = optimization_finder.patch;
-        patch.visit_body_preserves_cfg(body);
+        optimization_finder.patch.apply(body);
 
         trace!("ConstProp done for {:?}", def_id);
     }
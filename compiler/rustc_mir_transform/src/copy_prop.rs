@@ -25,14 +25,19 @@ fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
         sess.mir_opt_level() >= 1
     }
 
-    #[instrument(level = "trace", skip(self, tcx, body))]
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        self.run_pass_reporting_changes(tcx, body);
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass_reporting_changes(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) -> bool {
         debug!(def_id = ?body.source.def_id());
-        propagate_ssa(tcx, body);
+        propagate_ssa(tcx, body)
     }
 }
 
-fn propagate_ssa<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+/// Returns whether `body` was modified.
+fn propagate_ssa<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) -> bool {
     let borrowed_locals = borrowed_locals(body);
     let ssa = SsaLocals::new(body);
 
@@ -48,18 +53,22 @@ fn propagate_ssa<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
 
     let any_replacement = ssa.copy_classes().iter_enumerated().any(|(l, &h)| l != h);
 
-    Replacer {
+    let mut replacer = Replacer {
         tcx,
         copy_classes: &ssa.copy_classes(),
         fully_moved,
         borrowed_locals,
         storage_to_remove,
-    }
-    .visit_body_preserves_cfg(body);
+        changed: false,
+    };
+    replacer.visit_body_preserves_cfg(body);
 
     if any_replacement {
         crate::simplify::remove_unused_definitions(body);
+        body.applied_optimizations |= AppliedOptimizations::COPY_PROP;
     }
+
+    any_replacement || replacer.changed
 }
 
 /// `SsaLocals` computed equivalence classes between locals considering copy/move assignments.
@@ -104,6 +113,8 @@ struct Replacer<'a, 'tcx> {
     storage_to_remove: BitSet<Local>,
     borrowed_locals: BitSet<Local>,
     copy_classes: &'a IndexSlice<Local, Local>,
+    /// Whether any local, operand or statement was modified.
+    changed: bool,
 }
 
 impl<'tcx> MutVisitor<'tcx> for Replacer<'_, 'tcx> {
@@ -119,13 +130,17 @@ fn visit_local(&mut self, local: &mut Local, ctxt: PlaceContext, _: Location) {
             // The local should have been marked as non-SSA.
             PlaceContext::MutatingUse(_) => assert_eq!(*local, new_local),
             // We access the value.
-            _ => *local = new_local,
+            _ => {
+                self.changed |= *local != new_local;
+                *local = new_local;
+            }
         }
     }
 
     fn visit_place(&mut self, place: &mut Place<'tcx>, ctxt: PlaceContext, loc: Location) {
         if let Some(new_projection) = self.process_projection(&place.projection, loc) {
             place.projection = self.tcx().mk_place_elems(&new_projection);
+            self.changed = true;
         }
 
         let observes_address = match ctxt {
@@ -158,6 +173,7 @@ fn visit_operand(&mut self, operand: &mut Operand<'tcx>, loc: Location) {
             && !self.fully_moved.contains(place.local)
         {
             *operand = Operand::Copy(place);
+            self.changed = true;
         }
         self.super_operand(operand, loc);
     }
@@ -168,6 +184,7 @@ fn visit_statement(&mut self, stmt: &mut Statement<'tcx>, loc: Location) {
             && self.storage_to_remove.contains(l)
         {
             stmt.make_nop();
+            self.changed = true;
             return
         }
 
@@ -179,6 +196,7 @@ fn visit_statement(&mut self, stmt: &mut Statement<'tcx>, loc: Location) {
             && lhs == rhs
         {
             stmt.make_nop();
+            self.changed = true;
         }
     }
 }
@@ -354,6 +354,7 @@ fn inject_edge_counter_basic_block(
             kind: TerminatorKind::Goto { target: to_bb },
         }),
         is_cleanup: false,
+        is_cold: false,
     });
     let edge_ref = mir_body[from_bb]
         .terminator_mut()
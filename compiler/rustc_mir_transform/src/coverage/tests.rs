@@ -76,6 +76,7 @@ fn push(&mut self, kind: TerminatorKind<'tcx>) -> BasicBlock {
                 kind,
             }),
             is_cleanup: false,
+            is_cold: false,
         })
     }
 
@@ -62,8 +62,8 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         // Collect results and patch the body afterwards.
         let mut visitor = Collector::new(tcx, &body.local_decls);
         debug_span!("collect").in_scope(|| results.visit_reachable_with(body, &mut visitor));
-        let mut patch = visitor.patch;
-        debug_span!("patch").in_scope(|| patch.visit_body_preserves_cfg(body));
+        let patch = visitor.patch;
+        debug_span!("patch").in_scope(|| patch.apply(body));
     }
 }
 
@@ -529,6 +529,15 @@ pub(crate) fn new(tcx: TyCtxt<'tcx>) -> Self {
         Self { tcx, before_effect: FxHashMap::default(), assignments: FxHashMap::default() }
     }
 
+    /// Applies the patch to `body`, recording that constants were propagated if there were any.
+    pub(crate) fn apply(mut self, body: &mut Body<'tcx>) {
+        if self.before_effect.is_empty() && self.assignments.is_empty() {
+            return;
+        }
+        self.visit_body_preserves_cfg(body);
+        body.applied_optimizations |= AppliedOptimizations::CONST_PROP;
+    }
+
     fn make_operand(&self, literal: ConstantKind<'tcx>) -> Operand<'tcx> {
         Operand::Constant(Box::new(Constant { span: DUMMY_SP, user_ty: None, literal }))
     }
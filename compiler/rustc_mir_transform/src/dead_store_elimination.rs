@@ -101,6 +101,7 @@ pub fn eliminate<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>, borrowed: &BitS
     if patch.is_empty() && call_operands_to_move.is_empty() {
         return;
     }
+    body.applied_optimizations |= AppliedOptimizations::DEAD_STORE_ELIMINATION;
 
     let bbs = body.basic_blocks.as_mut_preserves_cfg();
     for Location { block, statement_index } in patch {
@@ -115,7 +116,7 @@ pub fn eliminate<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>, borrowed: &BitS
         *arg = Operand::Move(place);
     }
 
-    crate::simplify::simplify_locals(body, tcx)
+    crate::simplify::simplify_locals(body, tcx);
 }
 
 pub struct DeadStoreElimination;
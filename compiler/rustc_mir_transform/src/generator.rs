@@ -64,6 +64,7 @@
 use rustc_index::bit_set::{BitMatrix, BitSet, GrowableBitSet};
 use rustc_index::{Idx, IndexVec};
 use rustc_middle::mir::dump_mir;
+use rustc_middle::mir::unreachable::UnreachableBarriers;
 use rustc_middle::mir::visit::{MutVisitor, PlaceContext, Visitor};
 use rustc_middle::mir::*;
 use rustc_middle::ty::InstanceDef;
@@ -1042,6 +1043,7 @@ fn insert_switch<'tcx>(
             statements: vec![assign],
             terminator: Some(Terminator { source_info, kind: switch }),
             is_cleanup: false,
+            is_cold: false,
         },
     );
 
@@ -1179,6 +1181,7 @@ fn insert_term_block<'tcx>(body: &mut Body<'tcx>, kind: TerminatorKind<'tcx>) ->
         statements: Vec::new(),
         terminator: Some(Terminator { source_info, kind }),
         is_cleanup: false,
+        is_cold: false,
     })
 }
 
@@ -1205,6 +1208,7 @@ fn insert_panic_block<'tcx>(
         statements: Vec::new(),
         terminator: Some(Terminator { source_info, kind: term }),
         is_cleanup: false,
+        is_cold: false,
     });
 
     assert_block
@@ -1281,6 +1285,7 @@ fn create_generator_resume_function<'tcx>(
             statements: vec![transform.set_discr(VariantIdx::new(POISONED), source_info)],
             terminator: Some(Terminator { source_info, kind: TerminatorKind::UnwindResume }),
             is_cleanup: true,
+            is_cold: true,
         });
 
         for (idx, block) in body.basic_blocks_mut().iter_enumerated_mut() {
@@ -1331,6 +1336,10 @@ fn create_generator_resume_function<'tcx>(
 
     insert_switch(body, cases, &transform, TerminatorKind::Unreachable);
 
+    // The code which can only lead to an unreachable state is unreachable too. The blocks which
+    // become dead are removed below.
+    simplify::propagate_unreachable_terminators(body, UnreachableBarriers::ALL);
+
     make_generator_state_argument_indirect(tcx, body);
     make_generator_state_argument_pinned(tcx, body);
 
@@ -1359,6 +1368,7 @@ fn insert_clean_drop(body: &mut Body<'_>) -> BasicBlock {
         statements: Vec::new(),
         terminator: Some(Terminator { source_info, kind: term }),
         is_cleanup: false,
+        is_cold: false,
     })
 }
 
@@ -1432,6 +1442,7 @@ fn create_cases<'tcx>(
                         kind: TerminatorKind::Goto { target },
                     }),
                     is_cleanup: false,
+                    is_cold: false,
                 });
 
                 (point.state, block)
@@ -127,7 +127,7 @@ fn process_blocks(&mut self, caller_body: &mut Body<'tcx>, blocks: Range<BasicBl
         let mut inlined_count = 0;
         for bb in blocks {
             let bb_data = &caller_body[bb];
-            if bb_data.is_cleanup {
+            if bb_data.is_cold {
                 continue;
             }
 
@@ -521,6 +521,10 @@ fn inline_call(
         callsite: &CallSite<'tcx>,
         mut callee_body: Body<'tcx>,
     ) {
+        // The optimizations which modified the callee now apply to part of the caller.
+        caller_body.applied_optimizations |=
+            AppliedOptimizations::INLINE | callee_body.applied_optimizations;
+
         let terminator = caller_body[callsite.block].terminator.take().unwrap();
         match terminator.kind {
             TerminatorKind::Call { args, destination, unwind, .. } => {
@@ -17,34 +17,40 @@ fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
     }
 
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        self.run_pass_reporting_changes(tcx, body);
+    }
+
+    fn run_pass_reporting_changes(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) -> bool {
         let ctx = InstSimplifyContext {
             tcx,
             local_decls: &body.local_decls,
             param_env: tcx.param_env_reveal_all_normalized(body.source.def_id()),
         };
+        let mut changed = false;
         for block in body.basic_blocks.as_mut() {
             for statement in block.statements.iter_mut() {
                 match statement.kind {
                     StatementKind::Assign(box (_place, ref mut rvalue)) => {
-                        ctx.simplify_bool_cmp(&statement.source_info, rvalue);
-                        ctx.simplify_ref_deref(&statement.source_info, rvalue);
-                        ctx.simplify_len(&statement.source_info, rvalue);
-                        ctx.simplify_cast(&statement.source_info, rvalue);
+                        changed |= ctx.simplify_bool_cmp(&statement.source_info, rvalue);
+                        changed |= ctx.simplify_ref_deref(&statement.source_info, rvalue);
+                        changed |= ctx.simplify_len(&statement.source_info, rvalue);
+                        changed |= ctx.simplify_cast(&statement.source_info, rvalue);
                     }
                     _ => {}
                 }
             }
 
-            ctx.simplify_primitive_clone(
+            changed |= ctx.simplify_primitive_clone(
                 &mut block.terminator.as_mut().unwrap(),
                 &mut block.statements,
             );
-            ctx.simplify_intrinsic_assert(
+            changed |= ctx.simplify_intrinsic_assert(
                 &mut block.terminator.as_mut().unwrap(),
                 &mut block.statements,
             );
-            simplify_duplicate_switch_targets(block.terminator.as_mut().unwrap());
+            changed |= simplify_duplicate_switch_targets(block.terminator.as_mut().unwrap());
         }
+        changed
     }
 }
 
@@ -54,6 +60,7 @@ struct InstSimplifyContext<'tcx, 'a> {
     param_env: ParamEnv<'tcx>,
 }
 
+/// The `simplify_*` methods return whether they modified the MIR.
 impl<'tcx> InstSimplifyContext<'tcx, '_> {
     fn should_simplify(&self, source_info: &SourceInfo, rvalue: &Rvalue<'tcx>) -> bool {
         self.tcx.consider_optimizing(|| {
@@ -62,7 +69,7 @@ fn should_simplify(&self, source_info: &SourceInfo, rvalue: &Rvalue<'tcx>) -> bo
     }
 
     /// Transform boolean comparisons into logical operations.
-    fn simplify_bool_cmp(&self, source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>) {
+    fn simplify_bool_cmp(&self, source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>) -> bool {
         match rvalue {
             Rvalue::BinaryOp(op @ (BinOp::Eq | BinOp::Ne), box (a, b)) => {
                 let new = match (op, self.try_eval_bool(a), self.try_eval_bool(b)) {
@@ -95,11 +102,13 @@ fn simplify_bool_cmp(&self, source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>)
 
                 if let Some(new) = new && self.should_simplify(source_info, rvalue) {
                     *rvalue = new;
+                    return true;
                 }
             }
 
             _ => {}
         }
+        false
     }
 
     fn try_eval_bool(&self, a: &Operand<'_>) -> Option<bool> {
@@ -108,46 +117,51 @@ fn try_eval_bool(&self, a: &Operand<'_>) -> Option<bool> {
     }
 
     /// Transform "&(*a)" ==> "a".
-    fn simplify_ref_deref(&self, source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>) {
+    fn simplify_ref_deref(&self, source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>) -> bool {
         if let Rvalue::Ref(_, _, place) = rvalue {
             if let Some((base, ProjectionElem::Deref)) = place.as_ref().last_projection() {
                 if rvalue.ty(self.local_decls, self.tcx) != base.ty(self.local_decls, self.tcx).ty {
-                    return;
+                    return false;
                 }
 
                 if !self.should_simplify(source_info, rvalue) {
-                    return;
+                    return false;
                 }
 
                 *rvalue = Rvalue::Use(Operand::Copy(Place {
                     local: base.local,
                     projection: self.tcx.mk_place_elems(base.projection),
                 }));
+                return true;
             }
         }
+        false
     }
 
     /// Transform "Len([_; N])" ==> "N".
-    fn simplify_len(&self, source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>) {
+    fn simplify_len(&self, source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>) -> bool {
         if let Rvalue::Len(ref place) = *rvalue {
             let place_ty = place.ty(self.local_decls, self.tcx).ty;
             if let ty::Array(_, len) = *place_ty.kind() {
                 if !self.should_simplify(source_info, rvalue) {
-                    return;
+                    return false;
                 }
 
                 let literal = ConstantKind::from_ty_const(len, self.tcx);
                 let constant = Constant { span: source_info.span, literal, user_ty: None };
                 *rvalue = Rvalue::Use(Operand::Constant(Box::new(constant)));
+                return true;
             }
         }
+        false
     }
 
-    fn simplify_cast(&self, _source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>) {
+    fn simplify_cast(&self, _source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>) -> bool {
         if let Rvalue::Cast(kind, operand, cast_ty) = rvalue {
             let operand_ty = operand.ty(self.local_decls, self.tcx);
             if operand_ty == *cast_ty {
                 *rvalue = Rvalue::Use(operand.clone());
+                return true;
             } else if *kind == CastKind::Transmute {
                 // Transmuting an integer to another integer is just a signedness cast
                 if let (ty::Int(int), ty::Uint(uint)) | (ty::Uint(uint), ty::Int(int)) = (operand_ty.kind(), cast_ty.kind())
@@ -159,7 +173,7 @@ fn simplify_cast(&self, _source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>) {
                     // (If `CastKind::Transmute` ever becomes *not* UB for mismatched sizes,
                     // then the width check is necessary for big-endian correctness.)
                     *kind = CastKind::IntToInt;
-                    return;
+                    return true;
                 }
 
                 // Transmuting a transparent struct/union to a field's type is a projection
@@ -175,52 +189,53 @@ fn simplify_cast(&self, _source_info: &SourceInfo, rvalue: &mut Rvalue<'tcx>) {
                             let place = place.project_deeper(&[ProjectionElem::Field(FieldIdx::from_usize(i), *cast_ty)], self.tcx);
                             let operand = if operand.is_move() { Operand::Move(place) } else { Operand::Copy(place) };
                             *rvalue = Rvalue::Use(operand);
-                            return;
+                            return true;
                         }
                     }
                 }
             }
         }
+        false
     }
 
     fn simplify_primitive_clone(
         &self,
         terminator: &mut Terminator<'tcx>,
         statements: &mut Vec<Statement<'tcx>>,
-    ) {
+    ) -> bool {
         let TerminatorKind::Call { func, args, destination, target, .. } = &mut terminator.kind
         else {
-            return;
+            return false;
         };
 
         // It's definitely not a clone if there are multiple arguments
         if args.len() != 1 {
-            return;
+            return false;
         }
 
-        let Some(destination_block) = *target else { return };
+        let Some(destination_block) = *target else { return false };
 
         // Only bother looking more if it's easy to know what we're calling
-        let Some((fn_def_id, fn_args)) = func.const_fn_def() else { return };
+        let Some((fn_def_id, fn_args)) = func.const_fn_def() else { return false };
 
         // Clone needs one subst, so we can cheaply rule out other stuff
         if fn_args.len() != 1 {
-            return;
+            return false;
         }
 
         // These types are easily available from locals, so check that before
         // doing DefId lookups to figure out what we're actually calling.
         let arg_ty = args[0].ty(self.local_decls, self.tcx);
 
-        let ty::Ref(_region, inner_ty, Mutability::Not) = *arg_ty.kind() else { return };
+        let ty::Ref(_region, inner_ty, Mutability::Not) = *arg_ty.kind() else { return false };
 
         if !inner_ty.is_trivially_pure_clone_copy() {
-            return;
+            return false;
         }
 
         let trait_def_id = self.tcx.trait_of_item(fn_def_id);
         if trait_def_id.is_none() || trait_def_id != self.tcx.lang_items().clone_trait() {
-            return;
+            return false;
         }
 
         if !self.tcx.consider_optimizing(|| {
@@ -230,10 +245,10 @@ fn simplify_primitive_clone(
                 terminator.source_info
             )
         }) {
-            return;
+            return false;
         }
 
-        let Some(arg_place) = args.pop().unwrap().place() else { return };
+        let Some(arg_place) = args[0].place() else { return false };
 
         statements.push(Statement {
             source_info: terminator.source_info,
@@ -245,40 +260,43 @@ fn simplify_primitive_clone(
             ))),
         });
         terminator.kind = TerminatorKind::Goto { target: destination_block };
+        true
     }
 
     fn simplify_intrinsic_assert(
         &self,
         terminator: &mut Terminator<'tcx>,
         _statements: &mut Vec<Statement<'tcx>>,
-    ) {
+    ) -> bool {
         let TerminatorKind::Call { func, target, .. } = &mut terminator.kind else {
-            return;
+            return false;
         };
         let Some(target_block) = target else {
-            return;
+            return false;
         };
         let func_ty = func.ty(self.local_decls, self.tcx);
         let Some((intrinsic_name, args)) = resolve_rust_intrinsic(self.tcx, func_ty) else {
-            return;
+            return false;
         };
         // The intrinsics we are interested in have one generic parameter
         if args.is_empty() {
-            return;
+            return false;
         }
         let ty = args.type_at(0);
 
         let known_is_valid = intrinsic_assert_panics(self.tcx, self.param_env, ty, intrinsic_name);
         match known_is_valid {
             // We don't know the layout or it's not validity assertion at all, don't touch it
-            None => {}
+            None => false,
             Some(true) => {
                 // If we know the assert panics, indicate to later opts that the call diverges
                 *target = None;
+                true
             }
             Some(false) => {
                 // If we know the assert does not panic, turn the call into a Goto
                 terminator.kind = TerminatorKind::Goto { target: *target_block };
+                true
             }
         }
     }
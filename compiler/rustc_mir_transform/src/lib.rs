@@ -81,6 +81,7 @@
 mod large_enums;
 mod lower_intrinsics;
 mod lower_slice_len;
+mod mark_cold_blocks;
 mod match_branches;
 mod multiple_return_terminators;
 mod normalize_array_len;
@@ -271,6 +272,11 @@ fn mir_const(tcx: TyCtxt<'_>, def: LocalDefId) -> &Steal<Body<'_>> {
 
     let mut body = tcx.mir_built(def).steal();
 
+    let summary = tcx.unsafety_summary(def);
+    if !summary.is_empty() {
+        body.unsafety_summary = Some(Box::new(summary.clone()));
+    }
+
     pass_manager::dump_mir_for_phase_change(tcx, &body);
 
     pm::run_passes(
@@ -532,6 +538,7 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
             &unreachable_prop::UnreachablePropagation,
             &uninhabited_enum_branching::UninhabitedEnumBranching,
             &o1(simplify::SimplifyCfg::AfterUninhabitedEnumBranching),
+            &mark_cold_blocks::MarkColdBlocks, // has to be done before inlining, which uses it
             &inline::Inline,
             &remove_storage_markers::RemoveStorageMarkers,
             &remove_zsts::RemoveZsts,
@@ -543,9 +550,16 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
             &match_branches::MatchBranchSimplification,
             // inst combine is after MatchBranchSimplification to clean up Ne(_1, false)
             &multiple_return_terminators::MultipleReturnTerminators,
-            &instsimplify::InstSimplify,
-            &simplify::SimplifyLocals::BeforeConstProp,
-            &copy_prop::CopyProp,
+            // These passes create opportunities for each other, so they can be iterated with
+            // `-Zmir-opt-fixpoint-fuel`.
+            &pm::Fixpoint {
+                name: "InstSimplifyCopyPropFixpoint",
+                passes: &[
+                    &instsimplify::InstSimplify,
+                    &simplify::SimplifyLocals::BeforeConstProp,
+                    &copy_prop::CopyProp,
+                ],
+            },
             // Perform `SeparateConstSwitch` after SSA-based analyses, as cloning blocks may
             // destroy the SSA property. It should still happen before const-propagation, so the
             // latter pass will leverage the created opportunities.
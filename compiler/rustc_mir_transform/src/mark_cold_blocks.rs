@@ -0,0 +1,63 @@
+use crate::MirPass;
+use rustc_index::IndexVec;
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+use rustc_middle::mir::*;
+use rustc_middle::ty::TyCtxt;
+
+/// Computes [`BasicBlockData::is_cold`]: a block is cold if it lies on an unwind path, if it calls
+/// a `#[cold]` function, or if all the blocks it leads to are cold or `unreachable`.
+///
+/// Unwind edges are ignored when looking at the blocks a terminator leads to: they always lead to
+/// cleanup blocks, which would otherwise make every call to a diverging function cold.
+///
+/// This has to run before inlining, which doesn't inline calls in cold blocks. The flag is then
+/// serialized with the optimized MIR, and later passes keep it up to date on the blocks they add.
+pub struct MarkColdBlocks;
+
+impl<'tcx> MirPass<'tcx> for MarkColdBlocks {
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        let basic_blocks = &body.basic_blocks;
+        let mut is_cold: IndexVec<BasicBlock, bool> = basic_blocks
+            .iter()
+            .map(|data| data.is_cleanup || calls_cold_fn(tcx, data.terminator()))
+            .collect();
+
+        // Propagate the coldness to the blocks which only lead to cold blocks. Successors are
+        // visited before their predecessors, so this only iterates again for loops.
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &bb in basic_blocks.reverse_postorder().iter().rev() {
+                if is_cold[bb] {
+                    continue;
+                }
+                let terminator = basic_blocks[bb].terminator();
+                let leads_to_cold = match terminator.kind {
+                    TerminatorKind::Unreachable => true,
+                    _ => {
+                        let unwind = terminator.unwind();
+                        let mut successors = terminator
+                            .successors()
+                            .filter(|&succ| unwind != Some(&UnwindAction::Cleanup(succ)))
+                            .peekable();
+                        successors.peek().is_some() && successors.all(|succ| is_cold[succ])
+                    }
+                };
+                if leads_to_cold {
+                    is_cold[bb] = true;
+                    changed = true;
+                }
+            }
+        }
+
+        for (bb, data) in body.basic_blocks.as_mut_preserves_cfg().iter_enumerated_mut() {
+            data.is_cold = is_cold[bb];
+        }
+    }
+}
+
+fn calls_cold_fn<'tcx>(tcx: TyCtxt<'tcx>, terminator: &Terminator<'tcx>) -> bool {
+    let TerminatorKind::Call { func, .. } = &terminator.kind else { return false };
+    let Some((def_id, _)) = func.const_fn_def() else { return false };
+    tcx.codegen_fn_attrs(def_id).flags.contains(CodegenFnAttrFlags::COLD)
+}
@@ -38,6 +38,11 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         self.0.run_lint(tcx, body)
     }
 
+    fn run_pass_reporting_changes(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) -> bool {
+        self.0.run_lint(tcx, body);
+        false
+    }
+
     fn is_mir_dump_enabled(&self) -> bool {
         false
     }
@@ -60,6 +65,53 @@ fn is_enabled(&self, sess: &Session) -> bool {
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         self.1.run_pass(tcx, body)
     }
+
+    fn run_pass_reporting_changes(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) -> bool {
+        self.1.run_pass_reporting_changes(tcx, body)
+    }
+}
+
+/// A group of passes which is run repeatedly, until it stops changing the MIR.
+///
+/// The number of iterations is bounded by `-Zmir-opt-fixpoint-fuel`. Without that flag, the passes
+/// only run once, as if they were listed directly in the pipeline. Each pass in the group can
+/// still be enabled or disabled on its own with `-Zmir-enable-passes`, and is dumped and counted
+/// separately. The iteration stops as soon as none of the passes reports a change, so the passes
+/// of a group should override `MirPass::run_pass_reporting_changes`.
+pub struct Fixpoint<'a, 'tcx> {
+    pub name: &'static str,
+    pub passes: &'a [&'a dyn MirPass<'tcx>],
+}
+
+impl<'tcx> MirPass<'tcx> for Fixpoint<'_, 'tcx> {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        self.run_pass_reporting_changes(tcx, body);
+    }
+
+    fn run_pass_reporting_changes(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) -> bool {
+        let fuel = tcx.sess.opts.unstable_opts.mir_opt_fixpoint_fuel.unwrap_or(1).max(1);
+        let mut changed = false;
+        for iteration in 1..=fuel {
+            if !run_passes_inner(tcx, body, self.passes, None, true) {
+                trace!(group = self.name, iteration, "reached a fixpoint");
+                break;
+            }
+            changed = true;
+        }
+        changed
+    }
+
+    fn is_mir_dump_enabled(&self) -> bool {
+        false
+    }
+
+    fn is_group(&self) -> bool {
+        true
+    }
 }
 
 /// Run the sequence of passes without validating the MIR after each pass. The MIR is still
@@ -83,18 +135,20 @@ pub fn run_passes<'tcx>(
     run_passes_inner(tcx, body, passes, phase_change, true);
 }
 
+/// Returns whether any of the passes reported a change to `body`.
 fn run_passes_inner<'tcx>(
     tcx: TyCtxt<'tcx>,
     body: &mut Body<'tcx>,
     passes: &[&dyn MirPass<'tcx>],
     phase_change: Option<MirPhase>,
     validate_each: bool,
-) {
+) -> bool {
     let validate = validate_each & tcx.sess.opts.unstable_opts.validate_mir & !body.should_skip();
     let overridden_passes = &tcx.sess.opts.unstable_opts.mir_enable_passes;
     trace!(?overridden_passes);
 
     let prof_arg = tcx.sess.prof.enabled().then(|| format!("{:?}", body.source.def_id()));
+    let mut changed = false;
 
     if !body.should_skip() {
         for pass in passes {
@@ -123,14 +177,14 @@ fn run_passes_inner<'tcx>(
                 validate_body(tcx, body, format!("before pass {name}"));
             }
 
-            if let Some(prof_arg) = &prof_arg {
+            changed |= if let Some(prof_arg) = &prof_arg {
                 tcx.sess
                     .prof
                     .generic_activity_with_arg(pass.profiler_name(), &**prof_arg)
-                    .run(|| pass.run_pass(tcx, body));
+                    .run(|| pass.run_pass_reporting_changes(tcx, body))
             } else {
-                pass.run_pass(tcx, body);
-            }
+                pass.run_pass_reporting_changes(tcx, body)
+            };
 
             if dump_enabled {
                 dump_mir_for_pass(tcx, body, &name, true);
@@ -139,7 +193,9 @@ fn run_passes_inner<'tcx>(
                 validate_body(tcx, body, format!("after pass {name}"));
             }
 
-            body.pass_count += 1;
+            if !pass.is_group() {
+                body.pass_count += 1;
+            }
         }
     }
 
@@ -158,6 +214,8 @@ fn run_passes_inner<'tcx>(
 
         body.pass_count = 1;
     }
+
+    changed
 }
 
 pub fn validate_body<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>, when: String) {
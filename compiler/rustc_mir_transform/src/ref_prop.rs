@@ -93,6 +93,7 @@ fn propagate_ssa<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) -> bool {
 
     if replacer.any_replacement {
         crate::simplify::remove_unused_definitions(body);
+        body.applied_optimizations |= AppliedOptimizations::COPY_PROP;
     }
 
     replacer.any_replacement
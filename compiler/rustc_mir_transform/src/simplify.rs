@@ -31,6 +31,9 @@
 use rustc_data_structures::fx::{FxHashSet, FxIndexSet};
 use rustc_index::{Idx, IndexSlice, IndexVec};
 use rustc_middle::mir::coverage::*;
+use rustc_middle::mir::unreachable::{
+    propagate_unreachable, UnreachableBarriers, UnreachableBlocks,
+};
 use rustc_middle::mir::visit::{MutVisitor, MutatingUseContext, PlaceContext, Visitor};
 use rustc_middle::mir::*;
 use rustc_middle::ty::TyCtxt;
@@ -80,6 +83,11 @@ fn name(&self) -> &'static str {
 
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         debug!("SimplifyCfg({:?}) - simplifying {:?}", self.name(), body.source);
+        if let SimplifyCfg::AfterUninhabitedEnumBranching = self {
+            // Now that the `otherwise` of the switches on enums is unreachable, their other
+            // unreachable targets can be removed.
+            propagate_unreachable_terminators(body, UnreachableBarriers::ALL);
+        }
         simplify_cfg(tcx, body);
     }
 }
@@ -279,7 +287,8 @@ fn strip_nops(&mut self) {
     }
 }
 
-pub fn simplify_duplicate_switch_targets(terminator: &mut Terminator<'_>) {
+/// Returns whether any target was removed.
+pub fn simplify_duplicate_switch_targets(terminator: &mut Terminator<'_>) -> bool {
     if let TerminatorKind::SwitchInt { targets, .. } = &mut terminator.kind {
         let otherwise = targets.otherwise();
         if targets.iter().any(|t| t.1 == otherwise) {
@@ -287,8 +296,22 @@ pub fn simplify_duplicate_switch_targets(terminator: &mut Terminator<'_>) {
                 targets.iter().filter(|t| t.1 != otherwise),
                 targets.otherwise(),
             );
+            return true;
         }
     }
+    false
+}
+
+/// Replaces the terminators of the blocks which can only reach `Unreachable` terminators, as found
+/// by [`propagate_unreachable`]. The blocks which are now dead are left for [`remove_dead_blocks`].
+pub fn propagate_unreachable_terminators<'tcx>(
+    body: &mut Body<'tcx>,
+    barriers: UnreachableBarriers,
+) {
+    let UnreachableBlocks { replacements, .. } = propagate_unreachable(body, barriers);
+    for (bb, terminator_kind) in replacements {
+        body.basic_blocks_mut()[bb].terminator_mut().kind = terminator_kind;
+    }
 }
 
 pub fn remove_duplicate_unreachable_blocks<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
@@ -474,8 +497,12 @@ fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
     }
 
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        self.run_pass_reporting_changes(tcx, body);
+    }
+
+    fn run_pass_reporting_changes(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) -> bool {
         trace!("running SimplifyLocals on {:?}", body.source);
-        simplify_locals(body, tcx);
+        simplify_locals(body, tcx)
     }
 }
 
@@ -491,7 +518,8 @@ pub fn remove_unused_definitions<'tcx>(body: &mut Body<'tcx>) {
     remove_unused_definitions_helper(&mut used_locals, body);
 }
 
-pub fn simplify_locals<'tcx>(body: &mut Body<'tcx>, tcx: TyCtxt<'tcx>) {
+/// Returns whether any statement or local was removed.
+pub fn simplify_locals<'tcx>(body: &mut Body<'tcx>, tcx: TyCtxt<'tcx>) -> bool {
     // First, we're going to get a count of *actual* uses for every `Local`.
     let mut used_locals = UsedLocals::new(body);
 
@@ -500,19 +528,22 @@ pub fn simplify_locals<'tcx>(body: &mut Body<'tcx>, tcx: TyCtxt<'tcx>) {
     // count. For example, if we removed `_2 = discriminant(_1)`, then we'll subtract one from
     // `use_counts[_1]`. That in turn might make `_1` unused, so we loop until we hit a
     // fixedpoint where there are no more unused locals.
-    remove_unused_definitions_helper(&mut used_locals, body);
+    let removed_statements = remove_unused_definitions_helper(&mut used_locals, body);
 
     // Finally, we'll actually do the work of shrinking `body.local_decls` and remapping the `Local`s.
     let map = make_local_map(&mut body.local_decls, &used_locals);
 
     // Only bother running the `LocalUpdater` if we actually found locals to remove.
-    if map.iter().any(Option::is_none) {
+    let removed_locals = map.iter().any(Option::is_none);
+    if removed_locals {
         // Update references to all vars and tmps now
         let mut updater = LocalUpdater { map, tcx };
         updater.visit_body_preserves_cfg(body);
 
         body.local_decls.shrink_to_fit();
     }
+
+    removed_statements || removed_locals
 }
 
 /// Construct the mapping while swapping out unused stuff out from the `vec`.
@@ -636,12 +667,14 @@ fn visit_local(&mut self, local: Local, _ctx: PlaceContext, _location: Location)
 }
 
 /// Removes unused definitions. Updates the used locals to reflect the changes made.
-fn remove_unused_definitions_helper(used_locals: &mut UsedLocals, body: &mut Body<'_>) {
+/// Returns whether any statement was removed.
+fn remove_unused_definitions_helper(used_locals: &mut UsedLocals, body: &mut Body<'_>) -> bool {
     // The use counts are updated as we remove the statements. A local might become unused
     // during the retain operation, leading to a temporary inconsistency (storage statements or
     // definitions referencing the local might remain). For correctness it is crucial that this
     // computation reaches a fixed point.
 
+    let mut removed = false;
     let mut modified = true;
     while modified {
         modified = false;
@@ -670,7 +703,10 @@ fn remove_unused_definitions_helper(used_locals: &mut UsedLocals, body: &mut Bod
                 keep
             });
         }
+        removed |= modified;
     }
+
+    removed
 }
 
 struct LocalUpdater<'tcx> {
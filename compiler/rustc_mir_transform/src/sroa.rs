@@ -250,6 +250,7 @@ fn replace_flattened_locals<'tcx>(
     visitor.expand_var_debug_info(&mut body.var_debug_info);
     let ReplacementVisitor { patch, all_dead_locals, .. } = visitor;
     patch.apply(body);
+    body.applied_optimizations |= AppliedOptimizations::SCALAR_REPLACEMENT;
     all_dead_locals
 }
 
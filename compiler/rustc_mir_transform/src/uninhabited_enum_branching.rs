@@ -91,6 +91,7 @@ fn ensure_otherwise_unreachable<'tcx>(
         kind: TerminatorKind::Unreachable,
     }));
     new_block.is_cleanup = bb.is_cleanup;
+    new_block.is_cold = bb.is_cold;
     Some(new_block)
 }
 
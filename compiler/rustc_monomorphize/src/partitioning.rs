@@ -222,16 +222,21 @@ fn place_mono_items<'tcx, I>(cx: &PartitioningCx<'_, 'tcx>, mono_items: I) -> Pl
 
         let characteristic_def_id = characteristic_def_id_of_mono_item(cx.tcx, mono_item);
         let is_volatile = is_incremental_build && mono_item.is_generic_fn(cx.tcx);
-
-        let cgu_name = match characteristic_def_id {
-            Some(def_id) => compute_codegen_unit_name(
+        let codegen_unit_hint = mono_item.codegen_unit_hint(cx.tcx);
+
+        // Items with a `#[codegen_unit]` attribute go in the CGU they name,
+        // regardless of the module they are defined in. Merging only ever
+        // moves whole CGUs, so they stay together afterwards.
+        let cgu_name = match (codegen_unit_hint, characteristic_def_id) {
+            (Some(hint), _) => hinted_cgu_name(cgu_name_builder, hint),
+            (None, Some(def_id)) => compute_codegen_unit_name(
                 cx.tcx,
                 cgu_name_builder,
                 def_id,
                 is_volatile,
                 cgu_name_cache,
             ),
-            None => fallback_cgu_name(cgu_name_builder),
+            (None, None) => fallback_cgu_name(cgu_name_builder),
         };
 
         let cgu = codegen_units.entry(cgu_name).or_insert_with(|| CodegenUnit::new(cgu_name));
@@ -248,8 +253,10 @@ fn place_mono_items<'tcx, I>(cx: &PartitioningCx<'_, 'tcx>, mono_items: I) -> Pl
         }
         let size_estimate = mono_item.size_estimate(cx.tcx);
 
-        cgu.items_mut()
-            .insert(mono_item, MonoItemData { inlined: false, linkage, visibility, size_estimate });
+        cgu.items_mut().insert(
+            mono_item,
+            MonoItemData { inlined: false, linkage, visibility, size_estimate, codegen_unit_hint },
+        );
 
         // Get all inlined items that are reachable from `mono_item` without
         // going via another root item. This includes drop-glue, functions from
@@ -268,6 +275,7 @@ fn place_mono_items<'tcx, I>(cx: &PartitioningCx<'_, 'tcx>, mono_items: I) -> Pl
                 linkage: Linkage::Internal,
                 visibility: Visibility::Default,
                 size_estimate: inlined_item.size_estimate(cx.tcx),
+                codegen_unit_hint: None,
             });
         }
     }
@@ -718,6 +726,10 @@ fn fallback_cgu_name(name_builder: &mut CodegenUnitNameBuilder<'_>) -> Symbol {
     name_builder.build_cgu_name(LOCAL_CRATE, &["fallback"], Some("cgu"))
 }
 
+fn hinted_cgu_name(name_builder: &mut CodegenUnitNameBuilder<'_>, hint: Symbol) -> Symbol {
+    name_builder.build_cgu_name(LOCAL_CRATE, &["hint"], Some(hint))
+}
+
 fn mono_item_linkage_and_visibility<'tcx>(
     tcx: TyCtxt<'tcx>,
     mono_item: &MonoItem<'tcx>,
@@ -1000,9 +1012,13 @@ fn debug_dump<'a, 'tcx: 'a>(tcx: TyCtxt<'tcx>, label: &str, cgus: &[CodegenUnit<
                 let symbol_hash = symbol_hash_start.map_or("<no hash>", |i| &symbol_name[i..]);
                 let kind = if !data.inlined { "root" } else { "inlined" };
                 let size = data.size_estimate;
+                let hint = match data.codegen_unit_hint {
+                    Some(hint) => format!(", hint: {hint}"),
+                    None => String::new(),
+                };
                 let _ = with_no_trimmed_paths!(writeln!(
                     s,
-                    "  - {item} [{linkage:?}] [{symbol_hash}] ({kind}, size: {size})"
+                    "  - {item} [{linkage:?}] [{symbol_hash}] ({kind}, size: {size}{hint})"
                 ));
             }
 
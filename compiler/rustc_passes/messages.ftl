@@ -97,6 +97,10 @@ passes_cold =
     .warn = {-passes_previously_accepted}
     .label = {passes_should_be_applied_to_fn.label}
 
+passes_codegen_unit =
+    `codegen_unit` attribute should be applied to functions or statics
+    .label = not a function or static
+
 passes_collapse_debuginfo =
     `collapse_debuginfo` attribute should be applied to macro definitions
     .label = not a macro definition
@@ -174,6 +174,7 @@ fn check_attributes(
                 sym::cmse_nonsecure_entry => {
                     self.check_cmse_nonsecure_entry(hir_id, attr, span, target)
                 }
+                sym::codegen_unit => self.check_codegen_unit(attr, span, target),
                 sym::collapse_debuginfo => self.check_collapse_debuginfo(attr, span, target),
                 sym::must_not_suspend => self.check_must_not_suspend(&attr, span, target),
                 sym::must_use => self.check_must_use(hir_id, &attr, target),
@@ -485,6 +486,22 @@ fn check_object_lifetime_default(&self, hir_id: HirId) {
         }
     }
 
+    /// Checks if `#[codegen_unit]` is applied to a function or a static.
+    fn check_codegen_unit(&self, attr: &Attribute, span: Span, target: Target) -> bool {
+        match target {
+            Target::Fn
+            | Target::Closure
+            | Target::Method(MethodKind::Trait { body: true } | MethodKind::Inherent)
+            | Target::Static => true,
+            _ => {
+                self.tcx
+                    .sess
+                    .emit_err(errors::CodegenUnit { attr_span: attr.span, defn_span: span });
+                false
+            }
+        }
+    }
+
     /// Checks if `#[collapse_debuginfo]` is applied to a macro.
     fn check_collapse_debuginfo(&self, attr: &Attribute, span: Span, target: Target) -> bool {
         match target {
@@ -1078,6 +1095,7 @@ fn check_doc_attrs(
                         | sym::issue_tracker_base_url
                         | sym::html_root_url
                         | sym::html_no_source
+                        | sym::smart_punctuation
                         | sym::test
                             if !self.check_attr_crate_level(attr, meta, hir_id) =>
                         {
@@ -1135,6 +1153,7 @@ fn check_doc_attrs(
                         | sym::notable_trait
                         | sym::passes
                         | sym::plugins
+                        | sym::smart_punctuation
                         | sym::fake_variadic => {}
 
                         sym::test => {
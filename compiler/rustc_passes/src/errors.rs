@@ -772,6 +772,15 @@ pub struct RustcLintOptDenyFieldAccess {
     pub span: Span,
 }
 
+#[derive(Diagnostic)]
+#[diag(passes_codegen_unit)]
+pub struct CodegenUnit {
+    #[primary_span]
+    pub attr_span: Span,
+    #[label]
+    pub defn_span: Span,
+}
+
 #[derive(Diagnostic)]
 #[diag(passes_collapse_debuginfo)]
 pub struct CollapseDebuginfo {
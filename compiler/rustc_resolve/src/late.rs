@@ -4433,12 +4433,14 @@ fn resolve_doc_links(&mut self, attrs: &[Attribute], maybe_exported: MaybeExport
             | ResolveDocLinks::All => {}
         }
 
-        if !attrs.iter().any(|attr| attr.may_have_doc_links()) {
+        if !attrs.iter().any(|attr| attr.may_have_doc_links())
+            && !(self.r.tcx.sess.opts.actually_rustdoc && rustdoc::has_doc_includes(attrs))
+        {
             return;
         }
 
         let mut need_traits_in_scope = false;
-        for path_str in rustdoc::attrs_to_preprocessed_links(attrs) {
+        for path_str in rustdoc::attrs_to_preprocessed_links(self.r.tcx.sess, attrs) {
             // Resolve all namespaces due to no disambiguator or for diagnostics.
             let mut any_resolved = false;
             let mut need_assoc = false;
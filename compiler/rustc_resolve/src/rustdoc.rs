@@ -3,11 +3,14 @@
 use rustc_ast::util::comments::beautify_doc_string;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_middle::ty::TyCtxt;
+use rustc_session::Session;
 use rustc_span::def_id::DefId;
+use rustc_span::source_map::SourceMap;
 use rustc_span::symbol::{kw, sym, Symbol};
-use rustc_span::{InnerSpan, Span, DUMMY_SP};
+use rustc_span::{FileName, InnerSpan, Span, DUMMY_SP};
 use std::ops::Range;
-use std::{cmp, mem};
+use std::path::{Path, PathBuf};
+use std::{cmp, fs, mem};
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum DocFragmentKind {
@@ -226,6 +229,111 @@ pub fn prepare_to_doc_link_resolution(
     res
 }
 
+/// The start of an mdBook-style `{{#include path}}` directive.
+const INCLUDE_DIRECTIVE: &str = "{{#include ";
+
+/// An `{{#include path}}` directive found in some Markdown.
+#[derive(Clone, Debug)]
+pub struct DocInclude {
+    /// The range of the directive in the Markdown.
+    pub directive: Range<usize>,
+    /// The file named by the directive.
+    pub file: PathBuf,
+    /// The range of the included content in the expanded Markdown, or why the file could not be
+    /// included. The directive is then kept as is.
+    pub result: Result<Range<usize>, String>,
+}
+
+/// Returns `true` if the docs of `attrs` contain `{{#include path}}` directives.
+pub fn has_doc_includes(attrs: &[ast::Attribute]) -> bool {
+    attrs.iter().any(|attr| attr.doc_str().is_some_and(|s| s.as_str().contains(INCLUDE_DIRECTIVE)))
+}
+
+/// Replaces the `{{#include path}}` directives of `md` by the content of the files they name,
+/// with paths relative to `base_dir`. Included files are not expanded recursively.
+pub fn expand_doc_includes(md: &str, base_dir: &Path) -> (String, Vec<DocInclude>) {
+    let mut text = String::with_capacity(md.len());
+    let mut includes = Vec::new();
+    let mut copied = 0;
+    let mut search_from = 0;
+    while let Some(start) = md[search_from..].find(INCLUDE_DIRECTIVE).map(|i| search_from + i) {
+        // Directives can't span several lines.
+        let line_end = md[start..].find('\n').map_or(md.len(), |i| start + i);
+        let Some(end) = md[start..line_end].find("}}").map(|i| start + i + 2) else {
+            search_from = line_end;
+            continue;
+        };
+        search_from = end;
+
+        let directive = start..end;
+        let path = md[start + INCLUDE_DIRECTIVE.len()..end - 2].trim();
+        text.push_str(&md[copied..start]);
+        copied = end;
+        let file = base_dir.join(path);
+        let content = if path.is_empty() {
+            Err("missing path".to_owned())
+        } else {
+            fs::read_to_string(&file).map_err(|err| err.to_string())
+        };
+        let result = match content {
+            Ok(content) => {
+                let included_start = text.len();
+                text.push_str(content.strip_suffix('\n').unwrap_or(&content));
+                Ok(included_start..text.len())
+            }
+            Err(err) => {
+                text.push_str(&md[directive.clone()]);
+                Err(format!("cannot include `{path}`: {err}"))
+            }
+        };
+        includes.push(DocInclude { directive, file, result });
+    }
+    text.push_str(&md[copied..]);
+
+    (text, includes)
+}
+
+/// Expands the `{{#include path}}` directives of `fragments`, with paths relative to the file
+/// each fragment comes from, and returns them with the span of their fragment.
+///
+/// Like the fragments of `#[doc = include_str!("path")]`, the fragments with included content
+/// become `RawDoc` fragments, as their content can't be mapped back to the source anymore.
+pub fn expand_fragment_includes(
+    source_map: &SourceMap,
+    fragments: &mut [DocFragment],
+) -> Vec<(Span, DocInclude)> {
+    let mut includes = Vec::new();
+    for fragment in fragments {
+        let doc = fragment.doc.as_str();
+        if !doc.contains(INCLUDE_DIRECTIVE) {
+            continue;
+        }
+        let FileName::Real(file) = source_map.span_to_filename(fragment.span) else {
+            continue;
+        };
+        let Some(base_dir) = file.local_path().and_then(Path::parent) else {
+            continue;
+        };
+        let (text, fragment_includes) = expand_doc_includes(doc, base_dir);
+        if fragment_includes.iter().any(|include| include.result.is_ok()) {
+            // `add_doc_fragment` removes the indent of the fragment from all its lines, including
+            // the ones of the included content.
+            let indent = " ".repeat(fragment.indent);
+            let mut doc = String::with_capacity(text.len());
+            for (i, line) in text.split_inclusive('\n').enumerate() {
+                if i > 0 && !line.trim().is_empty() {
+                    doc.push_str(&indent);
+                }
+                doc.push_str(line);
+            }
+            fragment.doc = Symbol::intern(&doc);
+            fragment.kind = DocFragmentKind::RawDoc;
+        }
+        includes.extend(fragment_includes.into_iter().map(|include| (fragment.span, include)));
+    }
+    includes
+}
+
 /// Options for rendering Markdown in the main body of documentation.
 pub fn main_body_opts() -> Options {
     Options::ENABLE_TABLES
@@ -390,8 +498,18 @@ pub fn may_be_doc_link(link_type: LinkType) -> bool {
 
 /// Simplified version of `preprocessed_markdown_links` from rustdoc.
 /// Must return at least the same links as it, but may add some more links on top of that.
-pub(crate) fn attrs_to_preprocessed_links(attrs: &[ast::Attribute]) -> Vec<Box<str>> {
-    let (doc_fragments, _) = attrs_to_doc_fragments(attrs.iter().map(|attr| (attr, None)), true);
+///
+/// In rustdoc, the `{{#include path}}` directives are expanded, since rustdoc expands them when
+/// `--markdown-includes` is passed.
+pub(crate) fn attrs_to_preprocessed_links(
+    sess: &Session,
+    attrs: &[ast::Attribute],
+) -> Vec<Box<str>> {
+    let (mut doc_fragments, _) =
+        attrs_to_doc_fragments(attrs.iter().map(|attr| (attr, None)), true);
+    if sess.opts.actually_rustdoc {
+        expand_fragment_includes(sess.source_map(), &mut doc_fragments);
+    }
     let doc = prepare_to_doc_link_resolution(&doc_fragments).into_values().next().unwrap();
 
     parse_links(&doc)
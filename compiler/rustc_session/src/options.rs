@@ -1470,6 +1470,13 @@ pub(crate) fn parse_dump_solver_proof_tree(
         `foo` matches all passes for functions whose name contains 'foo',
         `foo & ConstProp` only the 'ConstProp' pass for function names containing 'foo',
         `foo | bar` all passes for function names containing 'foo' or 'bar'."),
+    dump_mir_cfg_json: bool = (false, parse_bool, [UNTRACKED],
+        "in addition to `.mir` files, create `.cfg.json` files summarizing the control-flow \
+        graph: the successors of the blocks, the kinds of the edges, the dominator tree and the \
+        loop headers (default: no)"),
+    dump_mir_cold_blocks: bool = (false, parse_bool, [UNTRACKED],
+        "mark the blocks which are unlikely to be executed as `(cold)` in the `.mir` files \
+        (default: no)"),
     dump_mir_dataflow: bool = (false, parse_bool, [UNTRACKED],
         "in addition to `.mir` files, create graphviz `.dot` files with dataflow results \
         (default: no)"),
@@ -1484,6 +1491,8 @@ pub(crate) fn parse_dump_solver_proof_tree(
         all `statement`s (including terminators), only `terminator` spans, or \
         computed `block` spans (one span encompassing a block's terminator and \
         all statements)."),
+    dump_mir_unsafety: bool = (false, parse_bool, [UNTRACKED],
+        "include the unsafe operations of each body in the `.mir` files (default: no)"),
     dump_mono_stats: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
         parse_switch_with_opt_path, [UNTRACKED],
         "output statistics about monomorphization collection"),
@@ -1618,9 +1627,15 @@ pub(crate) fn parse_dump_solver_proof_tree(
     mir_keep_place_mention: bool = (false, parse_bool, [TRACKED],
         "keep place mention MIR statements, interpreted e.g., by miri; implies -Zmir-opt-level=0 \
         (default: no)"),
+    mir_opt_fixpoint_fuel: Option<usize> = (None, parse_opt_number, [TRACKED],
+        "run each group of MIR optimization passes until it stops changing the MIR, at most this \
+        many times (default: 1)"),
     #[rustc_lint_opt_deny_field_access("use `Session::mir_opt_level` instead of this field")]
     mir_opt_level: Option<usize> = (None, parse_opt_number, [TRACKED],
         "MIR optimization level (0-4; default: 1 in non optimized builds and 2 in optimized builds)"),
+    mir_pretty_variant_names: bool = (false, parse_bool, [UNTRACKED],
+        "annotate the targets of `switchInt` terminators with the names of the enum variants they \
+        are taken for in MIR dumps (default: no)"),
     move_size_limit: Option<usize> = (None, parse_opt_number, [TRACKED],
         "the size at which the `large_assignments` lint starts to be emitted"),
     mutable_noalias: bool = (true, parse_bool, [TRACKED],
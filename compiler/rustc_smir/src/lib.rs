@@ -17,6 +17,12 @@
 
 // Declare extern rustc_* crates to enable building this crate separately from the compiler.
 #[cfg(not(feature = "default"))]
+extern crate rustc_ast;
+#[cfg(not(feature = "default"))]
+extern crate rustc_ast_pretty;
+#[cfg(not(feature = "default"))]
+extern crate rustc_borrowck;
+#[cfg(not(feature = "default"))]
 extern crate rustc_hir;
 #[cfg(not(feature = "default"))]
 extern crate rustc_middle;
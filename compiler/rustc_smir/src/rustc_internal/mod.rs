@@ -3,19 +3,23 @@
 //! For that, we define APIs that will temporarily be public to 3P that exposes rustc internal APIs
 //! until stable MIR is complete.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::ops::{ControlFlow, Index};
+use std::path::Path;
 
 use crate::rustc_internal;
 use crate::stable_mir::CompilerError;
 use crate::{
-    rustc_smir::Tables,
+    rustc_smir::{borrowck, Tables},
     stable_mir::{self, with},
 };
+use rustc_borrowck::consumers::BodyWithBorrowckFacts;
 use rustc_driver::{Callbacks, Compilation, RunCompiler};
 use rustc_interface::{interface, Queries};
 use rustc_middle::mir::interpret::AllocId;
 use rustc_middle::ty::TyCtxt;
+use rustc_span::def_id::LocalDefIdMap;
 pub use rustc_span::def_id::{CrateNum, DefId};
 use rustc_span::Span;
 
@@ -73,6 +77,10 @@ pub fn impl_def(did: DefId) -> stable_mir::ty::ImplDef {
     with_tables(|t| t.impl_def(did))
 }
 
+pub fn static_def(did: DefId) -> stable_mir::ty::StaticDef {
+    with_tables(|t| t.static_def(did))
+}
+
 impl<'tcx> Index<stable_mir::DefId> for Tables<'tcx> {
     type Output = DefId;
 
@@ -135,6 +143,10 @@ pub fn impl_def(&mut self, did: DefId) -> stable_mir::ty::ImplDef {
         stable_mir::ty::ImplDef(self.create_def_id(did))
     }
 
+    pub fn static_def(&mut self, did: DefId) -> stable_mir::ty::StaticDef {
+        stable_mir::ty::StaticDef(self.create_def_id(did))
+    }
+
     pub fn prov(&mut self, aid: AllocId) -> stable_mir::ty::Prov {
         stable_mir::ty::Prov(self.create_alloc_id(aid))
     }
@@ -178,8 +190,28 @@ pub fn crate_num(item: &stable_mir::Crate) -> CrateNum {
 }
 
 pub fn run(tcx: TyCtxt<'_>, f: impl FnOnce()) {
+    run_with_borrowck_facts(tcx, Default::default(), f);
+}
+
+/// Like [`run`], keeping the borrowck facts collected by [`borrowck::collect_facts`] for the
+/// session.
+fn run_with_borrowck_facts<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    borrowck_facts: LocalDefIdMap<BodyWithBorrowckFacts<'tcx>>,
+    f: impl FnOnce(),
+) {
     crate::stable_mir::run(
-        Tables { tcx, def_ids: vec![], alloc_ids: vec![], spans: vec![], types: vec![] },
+        Tables {
+            tcx,
+            def_ids: vec![],
+            alloc_ids: vec![],
+            spans: vec![],
+            types: vec![],
+            param_envs: vec![],
+            instances: vec![],
+            const_allocs: HashMap::new(),
+            borrowck_facts,
+        },
         f,
     );
 }
@@ -212,6 +244,7 @@ pub struct StableMir<B = (), C = ()>
     args: Vec<String>,
     callback: fn(TyCtxt<'_>) -> ControlFlow<B, C>,
     result: Option<ControlFlow<B, C>>,
+    borrowck_facts: bool,
 }
 
 impl<B, C> StableMir<B, C>
@@ -221,7 +254,17 @@ impl<B, C> StableMir<B, C>
 {
     /// Creates a new `StableMir` instance, with given test_function and arguments.
     pub fn new(args: Vec<String>, callback: fn(TyCtxt<'_>) -> ControlFlow<B, C>) -> Self {
-        StableMir { args, callback, result: None }
+        StableMir { args, callback, result: None, borrowck_facts: false }
+    }
+
+    /// Keeps the results of the borrow checker for the bodies of the local crate, so that they
+    /// can be retrieved with [`CrateItem::borrowck_facts`](stable_mir::CrateItem::borrowck_facts).
+    ///
+    /// This borrow checks each body twice, so it makes the compilation slower. The callback is
+    /// called once the analysis succeeded, like without the facts.
+    pub fn with_borrowck_facts(mut self) -> Self {
+        self.borrowck_facts = true;
+        self
     }
 
     /// Runs the compiler against given target and tests it with `test_function`
@@ -236,6 +279,15 @@ pub fn run(&mut self) -> Result<C, CompilerError<B>> {
             (Err(_), _) => Err(CompilerError::ICE),
         }
     }
+
+    /// Whether the compilation continues after the callback.
+    fn compilation(&self) -> Compilation {
+        if self.result.as_ref().is_some_and(|val| val.is_continue()) {
+            Compilation::Continue
+        } else {
+            Compilation::Stop
+        }
+    }
 }
 
 impl<B, C> Callbacks for StableMir<B, C>
@@ -243,6 +295,28 @@ impl<B, C> Callbacks for StableMir<B, C>
     B: Send,
     C: Send,
 {
+    /// Collects the borrowck facts before the analysis steals the bodies, when they are kept.
+    /// The callback is then called from here, since the facts can't outlive this `TyCtxt` scope.
+    fn after_expansion<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        if !self.borrowck_facts {
+            return Compilation::Continue;
+        }
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            let facts = borrowck::collect_facts(tcx);
+            if tcx.analysis(()).is_err() {
+                return Compilation::Stop;
+            }
+            run_with_borrowck_facts(tcx, facts, || {
+                self.result = Some((self.callback)(tcx));
+            });
+            self.compilation()
+        })
+    }
+
     /// Called after analysis. Return value instructs the compiler whether to
     /// continue the compilation afterwards (defaults to `Compilation::Continue`)
     fn after_analysis<'tcx>(
@@ -250,15 +324,90 @@ fn after_analysis<'tcx>(
         _compiler: &interface::Compiler,
         queries: &'tcx Queries<'tcx>,
     ) -> Compilation {
+        if self.borrowck_facts {
+            // The callback was already called after the expansion.
+            return self.compilation();
+        }
         queries.global_ctxt().unwrap().enter(|tcx| {
             rustc_internal::run(tcx, || {
                 self.result = Some((self.callback)(tcx));
             });
-            if self.result.as_ref().is_some_and(|val| val.is_continue()) {
-                Compilation::Continue
-            } else {
-                Compilation::Stop
+            self.compilation()
+        })
+    }
+}
+
+/// An error reported by a tool run with [`run_with_tcx`], which is emitted as a compilation error.
+#[derive(Clone, Debug)]
+pub struct ToolError {
+    pub message: String,
+    /// The code the error points to, if any.
+    pub span: Option<stable_mir::ty::Span>,
+}
+
+/// Runs the compiler with the given command line arguments, and calls `callback` with the stable
+/// MIR context set once the crate was analyzed.
+///
+/// This is meant to be the whole `main` of a tool which replaces `rustc`, so that it can be run
+/// by cargo as `RUSTC_WRAPPER`, or as `RUSTC_WORKSPACE_WRAPPER` to only analyze the crates of the
+/// workspace. Cargo then passes the path of `rustc` as the first argument, which is skipped.
+/// The compilation continues after the callback, so that cargo gets the artifacts it expects, and
+/// the callback isn't called when the compiler only prints some information, like `--version`.
+///
+/// The errors returned by the callback are emitted as compilation errors, and make the
+/// compilation fail. Returns the exit code of the compiler, which should be the one of the tool:
+///
+/// ```ignore (needs a callback)
+/// fn main() {
+///     let args = std::env::args().collect();
+///     std::process::exit(rustc_internal::run_with_tcx(args, analyze));
+/// }
+/// ```
+pub fn run_with_tcx(
+    mut args: Vec<String>,
+    callback: fn(TyCtxt<'_>) -> Result<(), Vec<ToolError>>,
+) -> i32 {
+    if args.get(1).map(Path::new).and_then(Path::file_stem) == Some("rustc".as_ref()) {
+        args.remove(1);
+    }
+    let mut driver = Driver { callback };
+    rustc_driver::catch_with_exit_code(|| RunCompiler::new(&args, &mut driver).run())
+}
+
+struct Driver {
+    callback: fn(TyCtxt<'_>) -> Result<(), Vec<ToolError>>,
+}
+
+impl Callbacks for Driver {
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            let mut errors = vec![];
+            rustc_internal::run(tcx, || {
+                let Err(reported) = (self.callback)(tcx) else { return };
+                // The spans have to be converted while the tables are still alive.
+                errors = with_tables(|tables| {
+                    reported
+                        .iter()
+                        .map(|error| {
+                            (error.message.clone(), error.span.map(|sp| tables.spans[sp.0]))
+                        })
+                        .collect()
+                });
+            });
+            if errors.is_empty() {
+                return Compilation::Continue;
+            }
+            for (message, span) in errors {
+                match span {
+                    Some(span) => tcx.sess.span_err(span, message),
+                    None => tcx.sess.err(message),
+                };
             }
+            Compilation::Stop
         })
     }
 }
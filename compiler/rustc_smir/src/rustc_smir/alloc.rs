@@ -1,7 +1,8 @@
 use rustc_middle::mir::{
-    interpret::{alloc_range, AllocRange, Pointer},
+    interpret::{alloc_range, read_target_uint, AllocRange, Pointer, Scalar},
     ConstValue,
 };
+use rustc_target::abi::Size;
 
 use crate::{
     rustc_smir::{Stable, Tables},
@@ -80,7 +81,13 @@ pub fn new_allocation<'tcx>(
                 .layout_of(rustc_middle::ty::ParamEnv::reveal_all().and(ty))
                 .unwrap()
                 .size;
-            allocation_filter(&alloc.0, alloc_range(offset, ty_size), tables)
+            let allocation = allocation_filter(&alloc.0, alloc_range(offset, ty_size), tables);
+            // Remember where the value was read from, so that it can be printed without
+            // registering a new allocation, see `const_value`.
+            if let Some(value) = rustc_allocation(&allocation, tables) {
+                tables.const_allocs.insert(value, (alloc_id, offset));
+            }
+            allocation
         }
     }
 }
@@ -123,3 +130,58 @@ pub(super) fn allocation_filter<'tcx>(
         mutability: alloc.mutability.stable(tables),
     }
 }
+
+/// Rebuilds the value of a constant of type `ty` from its `Allocation`, to print it. Scalars and
+/// ZSTs are rebuilt from the allocation, while the other values are printed from the allocation
+/// of rustc they were read from by [`new_allocation`], as registering a new one would change the
+/// state of the compiler. Returns `None` if the layout of `ty` isn't known, if the allocation
+/// doesn't match it, or if the value doesn't come from rustc.
+pub(super) fn const_value<'tcx>(
+    ty: rustc_middle::ty::Ty<'tcx>,
+    alloc: &Allocation,
+    tables: &Tables<'tcx>,
+) -> Option<ConstValue<'tcx>> {
+    let tcx = tables.tcx;
+    let layout = tcx.layout_of(rustc_middle::ty::ParamEnv::reveal_all().and(ty)).ok()?;
+    if layout.is_zst() {
+        return Some(ConstValue::ZeroSized);
+    }
+    let allocation = rustc_allocation(alloc, tables)?;
+    let size = allocation.size();
+    if layout.abi.is_scalar() && layout.size == size {
+        let is_pointer = !alloc.provenance.ptrs.is_empty();
+        let scalar =
+            allocation.read_scalar(&tcx, alloc_range(Size::ZERO, size), is_pointer).ok()?;
+        return Some(ConstValue::Scalar(scalar));
+    }
+    let &(alloc_id, offset) = tables.const_allocs.get(&allocation)?;
+    Some(ConstValue::Indirect { alloc_id, offset })
+}
+
+/// Converts `alloc` back to an allocation of rustc, without registering it.
+fn rustc_allocation(
+    alloc: &Allocation,
+    tables: &Tables<'_>,
+) -> Option<rustc_middle::mir::interpret::Allocation> {
+    let tcx = tables.tcx;
+    let size = Size::from_bytes(alloc.bytes.len());
+    let align = rustc_target::abi::Align::from_bytes(alloc.align).ok()?;
+    let mut allocation = rustc_middle::mir::interpret::Allocation::uninit(size, align);
+    for (offset, byte) in alloc.bytes.iter().enumerate() {
+        if let Some(byte) = byte {
+            let range = alloc_range(Size::from_bytes(offset), Size::from_bytes(1));
+            allocation.write_scalar(&tcx, range, Scalar::from_u8(*byte)).ok()?;
+        }
+    }
+    // The bytes of a pointer hold its offset in the allocation it points to.
+    let pointer_size = tcx.data_layout.pointer_size;
+    for (offset, prov) in &alloc.provenance.ptrs {
+        let bytes = alloc.bytes.get(*offset..*offset + pointer_size.bytes_usize())?;
+        let bytes = bytes.iter().copied().collect::<Option<Vec<u8>>>()?;
+        let pointee_offset = read_target_uint(tcx.data_layout.endian, &bytes).ok()?;
+        let pointer = Pointer::new(tables.alloc_ids[prov.0 .0], Size::from_bytes(pointee_offset));
+        let range = alloc_range(Size::from_bytes(*offset), pointer_size);
+        allocation.write_scalar(&tcx, range, Scalar::from_pointer(pointer, &tcx)).ok()?;
+    }
+    Some(allocation)
+}
@@ -0,0 +1,157 @@
+//! Computes the borrowck facts of the bodies of the local crate, and converts them to
+//! [`stable_mir::mir::borrowck`].
+//!
+//! The bodies the facts are computed from are stolen once they are borrow checked, so the facts
+//! can't be computed on demand after the analysis. Instead, [`collect_facts`] computes the facts
+//! of every body before the analysis, and they are kept in the [`Tables`] of the session.
+
+use rustc_borrowck::consumers::{
+    self, BodyWithBorrowckFacts, ConsumerOptions, LocationIndex, LocationTable, RichLocation,
+};
+use rustc_middle::ty::TyCtxt;
+use rustc_span::def_id::LocalDefIdMap;
+
+use super::{Stable, Tables};
+use crate::stable_mir::mir::borrowck::{BorrowckFacts, Loan, MovePath, Point, RegionConstraint};
+use crate::stable_mir::{self, Error};
+
+/// Computes the borrowck facts of the bodies of the local crate. This has to be called before the
+/// analysis, which steals the bodies.
+///
+/// The bodies with type errors are skipped, as well as the constants and `const fn`s which were
+/// already evaluated while type checking the bodies before them, since they are stolen too.
+pub(crate) fn collect_facts(tcx: TyCtxt<'_>) -> LocalDefIdMap<BodyWithBorrowckFacts<'_>> {
+    let mut facts = LocalDefIdMap::default();
+    for def_id in tcx.hir().body_owners() {
+        if tcx.typeck(def_id).tainted_by_errors.is_some() {
+            continue;
+        }
+        let (body, _) = tcx.mir_promoted(def_id);
+        if body.is_stolen() || body.borrow().should_skip() {
+            continue;
+        }
+        let body_facts = consumers::get_body_with_borrowck_facts(
+            tcx,
+            def_id,
+            ConsumerOptions::PoloniusInputFacts,
+        );
+        facts.insert(def_id, body_facts);
+    }
+    facts
+}
+
+pub(super) fn borrowck_facts<'tcx>(
+    tables: &mut Tables<'tcx>,
+    def_id: stable_mir::DefId,
+) -> Result<BorrowckFacts, Error> {
+    let def_id = tables[def_id];
+    let Some(local_def_id) = def_id.as_local() else {
+        return Err(Error::new(format!(
+            "`{}` isn't part of the local crate, only its bodies are borrow checked",
+            tables.tcx.def_path_str(def_id)
+        )));
+    };
+    // The facts are taken out of the tables while they are converted, which needs the tables.
+    let Some(facts) = tables.borrowck_facts.remove(&local_def_id) else {
+        return Err(Error::new(format!(
+            "no borrowck facts were kept for `{}`, run the compiler with \
+             `StableMir::with_borrowck_facts` to keep them",
+            tables.tcx.def_path_str(def_id)
+        )));
+    };
+    let stable = stable_facts(tables, &facts);
+    tables.borrowck_facts.insert(local_def_id, facts);
+    Ok(stable)
+}
+
+fn stable_facts<'tcx>(
+    tables: &mut Tables<'tcx>,
+    facts: &BodyWithBorrowckFacts<'tcx>,
+) -> BorrowckFacts {
+    let location_table = facts.location_table.as_ref().unwrap();
+    let input = facts.input_facts.as_ref().unwrap();
+    let point = |index| stable_point(location_table, index);
+
+    let mut loans: Vec<_> = facts
+        .borrow_set
+        .location_map
+        .iter()
+        .map(|(&location, borrow)| Loan {
+            kind: borrow.kind.stable(tables),
+            borrowed_place: borrow.borrowed_place.stable(tables),
+            assigned_place: borrow.assigned_place.stable(tables),
+            region: borrow.region.as_usize(),
+            issued_at: point(location_table.mid_index(location)),
+            out_of_scope_at: Vec::new(),
+            killed_at: Vec::new(),
+            invalidated_at: Vec::new(),
+        })
+        .collect();
+    let out_of_scope = consumers::calculate_borrows_out_of_scope_at_location(
+        &facts.body,
+        &facts.region_inference_context,
+        &facts.borrow_set,
+    );
+    for (location, borrows) in out_of_scope {
+        let at = point(location_table.start_index(location));
+        for borrow in borrows {
+            loans[borrow.as_usize()].out_of_scope_at.push(at);
+        }
+    }
+    for &(loan, at) in &input.loan_killed_at {
+        loans[loan.as_usize()].killed_at.push(point(at));
+    }
+    for &(at, loan) in &input.loan_invalidated_at {
+        loans[loan.as_usize()].invalidated_at.push(point(at));
+    }
+
+    // Move paths are only known through the facts mentioning them.
+    let mut move_paths = Vec::new();
+    for &(child, parent) in &input.child_path {
+        move_path(&mut move_paths, child.as_usize()).parent = Some(parent.as_usize());
+    }
+    for &(path, local) in &input.path_is_var {
+        move_path(&mut move_paths, path.as_usize()).local = Some(local.as_usize());
+    }
+    for &(path, at) in &input.path_assigned_at_base {
+        move_path(&mut move_paths, path.as_usize()).assigned_at.push(point(at));
+    }
+    for &(path, at) in &input.path_moved_at_base {
+        move_path(&mut move_paths, path.as_usize()).moved_at.push(point(at));
+    }
+    for &(path, at) in &input.path_accessed_at_base {
+        move_path(&mut move_paths, path.as_usize()).accessed_at.push(point(at));
+    }
+
+    BorrowckFacts {
+        body: facts.body.stable(tables),
+        cfg_edges: input.cfg_edge.iter().map(|&(from, to)| (point(from), point(to))).collect(),
+        universal_regions: input.universal_region.iter().map(|region| region.as_usize()).collect(),
+        loans,
+        region_constraints: input
+            .subset_base
+            .iter()
+            .map(|&(sub, sup, at)| RegionConstraint {
+                sub: sub.as_usize(),
+                sup: sup.as_usize(),
+                point: point(at),
+            })
+            .collect(),
+        move_paths,
+    }
+}
+
+fn move_path(move_paths: &mut Vec<MovePath>, index: usize) -> &mut MovePath {
+    if move_paths.len() <= index {
+        move_paths.resize_with(index + 1, Default::default);
+    }
+    &mut move_paths[index]
+}
+
+fn stable_point(location_table: &LocationTable, index: LocationIndex) -> Point {
+    let (location, mid) = match location_table.to_location(index) {
+        RichLocation::Start(location) => (location, false),
+        RichLocation::Mid(location) => (location, true),
+    };
+    Point { block: location.block.as_usize(), statement_index: location.statement_index, mid }
+}
@@ -13,18 +13,51 @@
     FloatTy, GenericParamDef, IntTy, Movability, RigidTy, Span, TyKind, UintTy,
 };
 use crate::stable_mir::{self, CompilerError, Context};
+use rustc_ast as ast;
+use rustc_ast_pretty::pprust;
+use rustc_borrowck::consumers::BodyWithBorrowckFacts;
 use rustc_hir as hir;
-use rustc_middle::mir::interpret::{alloc_range, AllocId};
+use rustc_hir::def::DefKind;
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+use rustc_middle::middle::exported_symbols::SymbolExportLevel;
+use rustc_middle::mir::interpret::{
+    alloc_range, read_target_uint, AllocId, ErrorHandled, GlobalAlloc,
+};
+use rustc_middle::mir::mono::Linkage;
 use rustc_middle::mir::{self, ConstantKind};
-use rustc_middle::ty::{self, Ty, TyCtxt, Variance};
-use rustc_span::def_id::{CrateNum, DefId, LOCAL_CRATE};
-use rustc_span::ErrorGuaranteed;
-use rustc_target::abi::FieldIdx;
+use rustc_middle::traits::CodegenObligationError;
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::util::IntTypeExt;
+use rustc_middle::ty::{self, Ty, TyCtxt, TypeFoldable, TypeVisitableExt, Variance};
+use rustc_span::def_id::{CrateNum, DefId, LocalDefIdMap, LOCAL_CRATE};
+use rustc_span::{ErrorGuaranteed, Symbol};
+use rustc_target::abi::{FieldIdx, Size};
+use std::collections::HashMap;
 use tracing::debug;
 
 mod alloc;
+pub(crate) mod borrowck;
+
+/// The version of the stable MIR interface implemented by this compiler.
+///
+/// Bump this whenever `Context` or the stable MIR data structures change in a way that is not
+/// backward compatible, and update `stable_mir::SUPPORTED_INTERFACE_VERSIONS` accordingly:
+///
+/// 1. The initial version.
+/// 2. `Body::var_debug_info`.
+/// 3. `Statement` and `Terminator` are structs with a span.
+/// 4. The operands of inline assembly and the other opaque parts of statements, terminators and
+///    rvalues have typed counterparts.
+/// 5. `Layout` describes the fields and variants of the type, and is no longer `Copy`.
+/// 6. `ConstantKind` has the bound, placeholder, expression and error constants.
+/// 7. `Body::arg_count`.
+const INTERFACE_VERSION: u32 = 7;
 
 impl<'tcx> Context for Tables<'tcx> {
+    fn interface_version(&self) -> u32 {
+        INTERFACE_VERSION
+    }
+
     fn local_crate(&self) -> stable_mir::Crate {
         smir_crate(self.tcx, LOCAL_CRATE)
     }
@@ -48,6 +81,122 @@ fn span_of_an_item(&mut self, def_id: stable_mir::DefId) -> Span {
         self.tcx.def_span(self[def_id]).stable(self)
     }
 
+    fn get_filename(&mut self, span: &Span) -> stable_mir::ty::Filename {
+        let source_map = self.tcx.sess.source_map();
+        let filename = source_map.span_to_filename(self.spans[span.0]);
+        source_map.filename_for_diagnostics(&filename).to_string()
+    }
+
+    fn get_lines(&mut self, span: &Span) -> stable_mir::ty::LineInfo {
+        let source_map = self.tcx.sess.source_map();
+        let span = self.spans[span.0];
+        let lo = source_map.lookup_char_pos(span.lo());
+        let hi = source_map.lookup_char_pos(span.hi());
+        stable_mir::ty::LineInfo {
+            start_line: lo.line,
+            start_col: lo.col.to_usize() + 1,
+            end_line: hi.line,
+            end_col: hi.col.to_usize() + 1,
+        }
+    }
+
+    fn source_text(&mut self, span: &Span) -> Option<String> {
+        self.tcx.sess.source_map().span_to_snippet(self.spans[span.0]).ok()
+    }
+
+    fn linkage_info(&mut self, def_id: stable_mir::DefId) -> stable_mir::LinkageInfo {
+        let tcx = self.tcx;
+        let def_id = self[def_id];
+        if !tcx.def_kind(def_id).has_codegen_attrs() {
+            return stable_mir::LinkageInfo::default();
+        }
+        let attrs = tcx.codegen_fn_attrs(def_id);
+        let export_level = tcx
+            .reachable_non_generics(def_id.krate)
+            .get(&def_id)
+            .map(|info| info.level.stable(self));
+        let symbol_name = (!tcx.generics_of(def_id).requires_monomorphization(tcx))
+            .then(|| tcx.symbol_name(ty::Instance::mono(tcx, def_id)).name.to_string());
+        let used = if attrs.flags.contains(CodegenFnAttrFlags::USED_LINKER) {
+            Some(stable_mir::UsedBy::Linker)
+        } else if attrs.flags.contains(CodegenFnAttrFlags::USED) {
+            Some(stable_mir::UsedBy::Compiler)
+        } else {
+            None
+        };
+        stable_mir::LinkageInfo {
+            export_level,
+            symbol_name,
+            no_mangle: attrs.flags.contains(CodegenFnAttrFlags::NO_MANGLE),
+            export_name: attrs.export_name.map(|name| name.to_string()),
+            used,
+            linkage: attrs.linkage.map(|linkage| linkage.stable(self)),
+            link_section: attrs.link_section.map(|section| section.to_string()),
+        }
+    }
+
+    fn def_path(&mut self, def_id: stable_mir::DefId) -> String {
+        def_path(self.tcx, self[def_id])
+    }
+
+    fn item_attrs(&mut self, def_id: stable_mir::DefId) -> Vec<stable_mir::Attribute> {
+        let tcx = self.tcx;
+        tcx.get_attrs_unchecked(self[def_id])
+            .iter()
+            .filter_map(|attr| {
+                // Doc comments aren't `Normal` attributes.
+                let ast::AttrKind::Normal(normal) = &attr.kind else {
+                    return None;
+                };
+                let item = &normal.item;
+                let args = match &item.args {
+                    ast::AttrArgs::Empty => stable_mir::AttrArgs::Empty,
+                    ast::AttrArgs::Delimited(args) => {
+                        stable_mir::AttrArgs::Delimited(pprust::tts_to_string(&args.tokens))
+                    }
+                    ast::AttrArgs::Eq(_, ast::AttrArgsEq::Ast(expr)) => {
+                        stable_mir::AttrArgs::Eq(pprust::expr_to_string(expr))
+                    }
+                    ast::AttrArgs::Eq(_, ast::AttrArgsEq::Hir(lit)) => {
+                        stable_mir::AttrArgs::Eq(lit.as_token_lit().to_string())
+                    }
+                };
+                let path = item.path.segments.iter().map(|segment| segment.ident.to_string());
+                Some(stable_mir::Attribute {
+                    path: path.collect(),
+                    args,
+                    span: attr.span.stable(self),
+                })
+            })
+            .collect()
+    }
+
+    fn visibility(&mut self, def_id: stable_mir::DefId) -> stable_mir::Visibility {
+        let tcx = self.tcx;
+        let def_id = self[def_id];
+        let visibility = match (tcx.def_kind(def_id), def_id.as_local()) {
+            // The constants of array lengths and of `const` blocks aren't items, the visibility
+            // query doesn't know about them.
+            (DefKind::AnonConst | DefKind::InlineConst, Some(local)) => {
+                ty::Visibility::Restricted(tcx.parent_module_from_def_id(local).to_def_id())
+            }
+            _ => tcx.visibility(def_id),
+        };
+        match visibility {
+            ty::Visibility::Public => stable_mir::Visibility::Public,
+            ty::Visibility::Restricted(module) => {
+                stable_mir::Visibility::Restricted(def_path(tcx, module))
+            }
+        }
+    }
+
+    fn borrowck_facts(
+        &mut self,
+        def_id: stable_mir::DefId,
+    ) -> Result<stable_mir::mir::borrowck::BorrowckFacts, stable_mir::Error> {
+        borrowck::borrowck_facts(self, def_id)
+    }
+
     fn all_local_items(&mut self) -> stable_mir::CrateItems {
         self.tcx.mir_keys(()).iter().map(|item| self.crate_item(item.to_def_id())).collect()
     }
@@ -84,24 +233,103 @@ fn trait_impl(&mut self, impl_def: &stable_mir::ty::ImplDef) -> stable_mir::ty::
         impl_trait.stable(self)
     }
 
+    fn trait_impls_of(
+        &mut self,
+        trait_def: &stable_mir::ty::TraitDef,
+    ) -> stable_mir::ImplTraitDecls {
+        let def_id = self[trait_def.0];
+        self.tcx.all_impls(def_id).map(|impl_def_id| self.impl_def(impl_def_id)).collect()
+    }
+
+    fn implements_trait(
+        &mut self,
+        ty: stable_mir::ty::Ty,
+        trait_def: &stable_mir::ty::TraitDef,
+        args: &stable_mir::ty::GenericArgs,
+    ) -> Result<bool, stable_mir::Error> {
+        let tcx = self.tcx;
+        let def_id = self[trait_def.0];
+        let self_ty = self.rustc_ty(ty)?;
+        let args = self.rustc_args(args)?;
+        let expected = tcx.generics_of(def_id).count() - 1;
+        if args.len() != expected {
+            return Err(stable_mir::Error::new(format!(
+                "`{}` expects {expected} generic arguments besides `Self`, but {} were given",
+                tcx.def_path_str(def_id),
+                args.len()
+            )));
+        }
+        let trait_ref = ty::TraitRef::new(tcx, def_id, tcx.mk_args_trait(self_ty, args));
+        if trait_ref.has_param() {
+            return Err(stable_mir::Error::new(format!(
+                "`{trait_ref}` depends on generic parameters"
+            )));
+        }
+        let trait_ref = tcx.erase_regions(trait_ref);
+        match tcx.codegen_select_candidate((ty::ParamEnv::reveal_all(), trait_ref)) {
+            Ok(_) => Ok(true),
+            Err(
+                CodegenObligationError::Unimplemented | CodegenObligationError::FulfillmentError,
+            ) => Ok(false),
+            Err(CodegenObligationError::Ambiguity) => Err(stable_mir::Error::new(format!(
+                "the trait solver can't decide whether `{trait_ref}` holds"
+            ))),
+        }
+    }
+
+    fn associated_items(
+        &mut self,
+        trait_def: &stable_mir::ty::TraitDef,
+    ) -> Vec<stable_mir::ty::AssocItem> {
+        let tcx = self.tcx;
+        tcx.associated_items(self[trait_def.0])
+            .in_definition_order()
+            .filter(|item| !item.is_impl_trait_in_trait())
+            .map(|item| {
+                let kind = match item.kind {
+                    ty::AssocKind::Fn => stable_mir::ty::AssocKind::Fn {
+                        def: self.fn_def(item.def_id),
+                        has_self: item.fn_has_self_parameter,
+                    },
+                    ty::AssocKind::Const => {
+                        stable_mir::ty::AssocKind::Const(self.const_def(item.def_id))
+                    }
+                    ty::AssocKind::Type => {
+                        stable_mir::ty::AssocKind::Type(self.alias_def(item.def_id))
+                    }
+                };
+                stable_mir::ty::AssocItem {
+                    name: item.name.to_string(),
+                    kind,
+                    has_default: item.defaultness(tcx).has_value(),
+                }
+            })
+            .collect()
+    }
+
+    fn items_of_kind(&mut self, kind: stable_mir::ItemKind) -> stable_mir::CrateItems {
+        use stable_mir::ItemKind;
+        let tcx = self.tcx;
+        tcx.hir_crate_items(())
+            .definitions()
+            .filter(|&def_id| match (kind, tcx.def_kind(def_id)) {
+                (ItemKind::Fn, DefKind::Fn | DefKind::AssocFn) => {
+                    // Required methods of traits don't have a body.
+                    tcx.is_mir_available(def_id)
+                }
+                (ItemKind::Static, DefKind::Static(_))
+                | (ItemKind::Const, DefKind::Const | DefKind::AssocConst)
+                | (ItemKind::Impl, DefKind::Impl { .. })
+                | (ItemKind::Trait, DefKind::Trait) => true,
+                _ => false,
+            })
+            .map(|def_id| self.crate_item(def_id.to_def_id()))
+            .collect()
+    }
+
     fn mir_body(&mut self, item: stable_mir::DefId) -> stable_mir::mir::Body {
         let def_id = self[item];
-        let mir = self.tcx.instance_mir(ty::InstanceDef::Item(def_id));
-        stable_mir::mir::Body {
-            blocks: mir
-                .basic_blocks
-                .iter()
-                .map(|block| stable_mir::mir::BasicBlock {
-                    terminator: block.terminator().stable(self),
-                    statements: block
-                        .statements
-                        .iter()
-                        .map(|statement| statement.stable(self))
-                        .collect(),
-                })
-                .collect(),
-            locals: mir.local_decls.iter().map(|decl| self.intern_ty(decl.ty)).collect(),
-        }
+        self.tcx.instance_mir(ty::InstanceDef::Item(def_id)).stable(self)
     }
 
     fn rustc_tables(&mut self, f: &mut dyn FnMut(&mut Tables<'_>)) {
@@ -118,6 +346,535 @@ fn mk_ty(&mut self, kind: TyKind) -> stable_mir::ty::Ty {
         stable_mir::ty::Ty(n)
     }
 
+    fn ty_pretty(&mut self, ty: stable_mir::ty::Ty) -> String {
+        match &self.types[ty.0] {
+            MaybeStable::Rustc(ty) => with_no_trimmed_paths!(ty.to_string()),
+            MaybeStable::Stable(kind) => format!("{kind:?}"),
+        }
+    }
+
+    fn const_pretty(&mut self, cnst: &stable_mir::ty::Const) -> String {
+        let ty = match self.rustc_ty(cnst.ty) {
+            Ok(ty) => ty,
+            Err(_) => return format!("{:?}", cnst.literal),
+        };
+        match &cnst.literal {
+            stable_mir::ty::ConstantKind::Allocated(allocation) => {
+                match alloc::const_value(ty, allocation, self) {
+                    Some(val) => with_no_trimmed_paths!(ConstantKind::Val(val, ty).to_string()),
+                    None => format!("{allocation:?}: {}", with_no_trimmed_paths!(ty.to_string())),
+                }
+            }
+            stable_mir::ty::ConstantKind::Param(param) => param.name.clone(),
+            stable_mir::ty::ConstantKind::Error => "{const error}".to_owned(),
+            // Like the compiler, the constants which aren't evaluated are printed as `_`.
+            stable_mir::ty::ConstantKind::Unevaluated(_)
+            | stable_mir::ty::ConstantKind::Bound(..)
+            | stable_mir::ty::ConstantKind::Placeholder(_)
+            | stable_mir::ty::ConstantKind::Expr => "_".to_owned(),
+        }
+    }
+
+    fn param_env(&mut self, def_id: stable_mir::DefId) -> stable_mir::ty::ParamEnv {
+        let param_env = self.tcx.param_env(self[def_id]);
+        self.intern_param_env(param_env)
+    }
+
+    fn normalize_ty(
+        &mut self,
+        env: stable_mir::ty::ParamEnv,
+        ty: stable_mir::ty::Ty,
+    ) -> Result<stable_mir::ty::Ty, stable_mir::Error> {
+        let ty = self.rustc_ty(ty)?;
+        let ty = self.normalize(env, ty)?;
+        Ok(self.intern_ty(ty))
+    }
+
+    fn normalize_const(
+        &mut self,
+        env: stable_mir::ty::ParamEnv,
+        cnst: &stable_mir::ty::Const,
+    ) -> Result<stable_mir::ty::Const, stable_mir::Error> {
+        match self.eval_const(env, cnst) {
+            Err(stable_mir::ty::ConstEvalError::TooGeneric) => {}
+            result => return result.map_err(|err| stable_mir::Error::new(err.to_string())),
+        }
+        // The constants which can't be evaluated yet are only normalized.
+        let ty = self.rustc_ty(cnst.ty)?;
+        let ty = self.normalize(env, ty)?;
+        let stable_mir::ty::ConstantKind::Unevaluated(uv) = &cnst.literal else {
+            let literal = cnst.literal.clone();
+            return Ok(stable_mir::ty::Const { literal, ty: self.intern_ty(ty) });
+        };
+        let uv = self.rustc_unevaluated(env, uv)?;
+        Ok(ConstantKind::Unevaluated(uv, ty).stable(self))
+    }
+
+    fn eval_const(
+        &mut self,
+        env: stable_mir::ty::ParamEnv,
+        cnst: &stable_mir::ty::Const,
+    ) -> Result<stable_mir::ty::Const, stable_mir::ty::ConstEvalError> {
+        use stable_mir::ty::ConstEvalError;
+        let ty = self.rustc_ty(cnst.ty)?;
+        let ty = self.normalize(env, ty)?;
+        let uv = match &cnst.literal {
+            stable_mir::ty::ConstantKind::Allocated(_) => {
+                let literal = cnst.literal.clone();
+                return Ok(stable_mir::ty::Const { literal, ty: self.intern_ty(ty) });
+            }
+            stable_mir::ty::ConstantKind::Unevaluated(uv) => self.rustc_unevaluated(env, uv)?,
+            stable_mir::ty::ConstantKind::Param(_)
+            | stable_mir::ty::ConstantKind::Bound(..)
+            | stable_mir::ty::ConstantKind::Placeholder(_)
+            | stable_mir::ty::ConstantKind::Expr => return Err(ConstEvalError::TooGeneric),
+            stable_mir::ty::ConstantKind::Error => {
+                return Err(ConstEvalError::EvalError {
+                    span: None,
+                    message: "the constant failed to type-check".to_owned(),
+                });
+            }
+        };
+        match self.tcx.const_eval_resolve(self.param_envs[env.0], uv, None) {
+            Ok(val) => Ok(ConstantKind::Val(val, ty).stable(self)),
+            Err(ErrorHandled::TooGeneric(_)) => Err(ConstEvalError::TooGeneric),
+            Err(ErrorHandled::Reported(_, span)) => Err(ConstEvalError::EvalError {
+                span: (!span.is_dummy()).then(|| span.stable(self)),
+                message: format!(
+                    "failed to evaluate the constant `{}`",
+                    self.tcx.def_path_str(uv.def)
+                ),
+            }),
+        }
+    }
+
+    fn eval_target_usize(
+        &mut self,
+        cnst: &stable_mir::ty::Const,
+    ) -> Result<u64, stable_mir::Error> {
+        let ty = self.ty_kind(cnst.ty);
+        if !matches!(ty, TyKind::RigidTy(RigidTy::Uint(UintTy::Usize))) {
+            return Err(stable_mir::Error::new(format!(
+                "expected a constant of type `usize`, found one of type `{ty:?}`"
+            )));
+        }
+        let alloc = cnst.allocation()?;
+        let size = self.tcx.data_layout.pointer_size.bytes_usize();
+        if alloc.bytes.len() != size {
+            return Err(stable_mir::Error::new(format!(
+                "expected a constant of {size} bytes, found {} bytes",
+                alloc.bytes.len()
+            )));
+        }
+        Ok(self.read_scalar(alloc, 0, size)? as u64)
+    }
+
+    fn read_scalar(
+        &mut self,
+        alloc: &stable_mir::ty::Allocation,
+        offset: usize,
+        size: usize,
+    ) -> Result<u128, stable_mir::Error> {
+        if !(1..=16).contains(&size) {
+            return Err(stable_mir::Error::new(format!(
+                "expected a scalar of 1 to 16 bytes, found {size} bytes"
+            )));
+        }
+        let end = offset.saturating_add(size);
+        let bytes = alloc.bytes.get(offset..end).ok_or_else(|| {
+            stable_mir::Error::new(format!(
+                "the bytes {offset}..{end} are out of bounds of an allocation of {} bytes",
+                alloc.bytes.len()
+            ))
+        })?;
+        let pointer_size = self.tcx.data_layout.pointer_size.bytes_usize();
+        if alloc.provenance.ptrs.iter().any(|&(ptr, _)| ptr < end && offset < ptr + pointer_size) {
+            return Err(stable_mir::Error::new(format!(
+                "the bytes {offset}..{end} are part of a pointer"
+            )));
+        }
+        let bytes = bytes.iter().copied().collect::<Option<Vec<u8>>>().ok_or_else(|| {
+            stable_mir::Error::new(format!("the bytes {offset}..{end} are uninitialized"))
+        })?;
+        read_target_uint(self.tcx.data_layout.endian, &bytes)
+            .map_err(|err| stable_mir::Error::new(err.to_string()))
+    }
+
+    fn needs_drop(&mut self, ty: stable_mir::ty::Ty) -> Result<bool, stable_mir::Error> {
+        let ty = self.rustc_ty(ty)?;
+        Ok(ty.needs_drop(self.tcx, ty::ParamEnv::reveal_all()))
+    }
+
+    fn drop_glue_body(
+        &mut self,
+        ty: stable_mir::ty::Ty,
+    ) -> Result<Option<stable_mir::mir::Body>, stable_mir::Error> {
+        let ty = self.rustc_ty(ty)?;
+        if ty.has_param() {
+            return Err(stable_mir::Error::new(format!(
+                "the drop glue of `{ty}` depends on generic parameters"
+            )));
+        }
+        let instance = ty::Instance::resolve_drop_in_place(self.tcx, ty);
+        match instance.def {
+            // Types which don't need to be dropped have an empty drop glue, without a body.
+            ty::InstanceDef::DropGlue(_, None) => Ok(None),
+            def => Ok(Some(self.tcx.instance_mir(def).stable(self))),
+        }
+    }
+
+    fn field_drops(
+        &mut self,
+        ty: stable_mir::ty::Ty,
+    ) -> Result<Vec<stable_mir::ty::FieldDrop>, stable_mir::Error> {
+        let ty = self.rustc_ty(ty)?;
+        let tcx = self.tcx;
+        let fields: Vec<_> = match ty.kind() {
+            ty::Adt(adt_def, args) => adt_def
+                .variants()
+                .iter_enumerated()
+                .flat_map(|(variant_idx, variant)| {
+                    let variant_idx = adt_def.is_enum().then_some(variant_idx.as_usize());
+                    variant
+                        .fields
+                        .iter()
+                        .enumerate()
+                        .map(move |(field, def)| (variant_idx, field, def.ty(tcx, args)))
+                })
+                .collect(),
+            ty::Tuple(tys) => tys.iter().enumerate().map(|(field, ty)| (None, field, ty)).collect(),
+            ty::Closure(_, args) => args
+                .as_closure()
+                .upvar_tys()
+                .iter()
+                .enumerate()
+                .map(|(field, ty)| (None, field, ty))
+                .collect(),
+            _ => {
+                return Err(stable_mir::Error::new(format!(
+                    "expected an ADT, tuple or closure type, found `{ty}`"
+                )));
+            }
+        };
+        Ok(fields
+            .into_iter()
+            .map(|(variant, field, ty)| stable_mir::ty::FieldDrop {
+                variant,
+                field,
+                ty: self.intern_ty(ty),
+                needs_drop: ty.needs_drop(tcx, ty::ParamEnv::reveal_all()),
+            })
+            .collect())
+    }
+
+    fn ty_layout(
+        &mut self,
+        ty: stable_mir::ty::Ty,
+    ) -> Result<stable_mir::abi::Layout, stable_mir::Error> {
+        let ty = self.rustc_ty(ty)?;
+        let layout = self
+            .tcx
+            .layout_of(ty::ParamEnv::reveal_all().and(ty))
+            .map_err(|err| stable_mir::Error::new(err.to_string()))?;
+        Ok(layout.stable(self))
+    }
+
+    fn adt_kind(&mut self, def: stable_mir::ty::AdtDef) -> stable_mir::ty::AdtKind {
+        match self.tcx.adt_def(self[def.0]).adt_kind() {
+            ty::AdtKind::Struct => stable_mir::ty::AdtKind::Struct,
+            ty::AdtKind::Enum => stable_mir::ty::AdtKind::Enum,
+            ty::AdtKind::Union => stable_mir::ty::AdtKind::Union,
+        }
+    }
+
+    fn adt_variants(&mut self, def: stable_mir::ty::AdtDef) -> Vec<stable_mir::ty::VariantDef> {
+        let tcx = self.tcx;
+        tcx.adt_def(self[def.0])
+            .variants()
+            .iter()
+            .map(|variant| stable_mir::ty::VariantDef {
+                name: variant.name.to_string(),
+                fields: variant
+                    .fields
+                    .iter()
+                    .map(|field| stable_mir::ty::FieldDef {
+                        name: field.name.to_string(),
+                        ty: self.intern_ty(tcx.type_of(field.did).instantiate_identity()),
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+
+    fn adt_repr(&mut self, def: stable_mir::ty::AdtDef) -> stable_mir::ty::ReprOptions {
+        let tcx = self.tcx;
+        let repr = tcx.adt_def(self[def.0]).repr();
+        stable_mir::ty::ReprOptions {
+            int: repr.int.map(|int| self.intern_ty(int.to_ty(tcx))),
+            align: repr.align.map(|align| align.bytes()),
+            pack: repr.pack.map(|pack| pack.bytes()),
+            c: repr.c(),
+            transparent: repr.transparent(),
+            simd: repr.simd(),
+        }
+    }
+
+    fn adt_discriminant(
+        &mut self,
+        def: stable_mir::ty::AdtDef,
+        variant: VariantIdx,
+    ) -> Result<stable_mir::ty::Discr, stable_mir::Error> {
+        let tcx = self.tcx;
+        let adt_def = tcx.adt_def(self[def.0]);
+        if !adt_def.is_enum() {
+            return Err(stable_mir::Error::new(format!(
+                "expected an enum, found `{}`",
+                tcx.def_path_str(adt_def.did())
+            )));
+        }
+        if variant >= adt_def.variants().len() {
+            return Err(stable_mir::Error::new(format!(
+                "the enum `{}` has no variant {variant}",
+                tcx.def_path_str(adt_def.did())
+            )));
+        }
+        let discr = adt_def.discriminant_for_variant(tcx, variant.into());
+        Ok(stable_mir::ty::Discr { val: discr.val, ty: self.intern_ty(discr.ty) })
+    }
+
+    fn instantiate_ty(
+        &mut self,
+        ty: stable_mir::ty::Ty,
+        args: &stable_mir::ty::GenericArgs,
+    ) -> Result<stable_mir::ty::Ty, stable_mir::Error> {
+        let ty = self.rustc_ty(ty)?;
+        let args = self.rustc_args(args)?;
+        check_instantiation(ty, args)?;
+        Ok(self.intern_ty(ty::EarlyBinder::bind(ty).instantiate(self.tcx, args)))
+    }
+
+    fn fn_sig(&mut self, def: stable_mir::ty::FnDef) -> stable_mir::ty::PolyFnSig {
+        self.tcx.fn_sig(self[def.0]).instantiate_identity().stable(self)
+    }
+
+    fn opaque_hidden_type(
+        &mut self,
+        alias: &stable_mir::ty::AliasTy,
+    ) -> Result<stable_mir::ty::Ty, stable_mir::Error> {
+        let tcx = self.tcx;
+        let def_id = self[alias.def_id.0];
+        if tcx.def_kind(def_id) != DefKind::OpaqueTy {
+            return Err(stable_mir::Error::new(format!(
+                "`{}` is not an opaque type",
+                tcx.def_path_str(def_id)
+            )));
+        }
+        let args = self.rustc_args(&alias.args)?;
+        if args.len() != tcx.generics_of(def_id).count() {
+            return Err(stable_mir::Error::new(format!(
+                "expected {} generic arguments for `{}`, found {}",
+                tcx.generics_of(def_id).count(),
+                tcx.def_path_str(def_id),
+                args.len()
+            )));
+        }
+        let hidden = tcx.type_of(def_id);
+        if hidden.skip_binder().references_error() {
+            return Err(stable_mir::Error::new(format!(
+                "the hidden type of `{}` is unknown",
+                tcx.def_path_str(def_id)
+            )));
+        }
+        Ok(self.intern_ty(hidden.instantiate(tcx, args)))
+    }
+
+    fn closure_captures(
+        &mut self,
+        def_id: stable_mir::DefId,
+    ) -> Result<Vec<stable_mir::ty::CapturedPlace>, stable_mir::Error> {
+        let def_id = self[def_id];
+        let Some(local_def_id) = def_id.as_local() else {
+            return Err(stable_mir::Error::new(format!(
+                "the captures of `{}` are only known in the crate defining it",
+                self.tcx.def_path_str(def_id)
+            )));
+        };
+        let captures = self.tcx.closure_captures(local_def_id);
+        Ok(captures.iter().map(|capture| capture.stable(self)).collect())
+    }
+
+    fn generator_layout(
+        &mut self,
+        def: stable_mir::ty::GeneratorDef,
+    ) -> Result<stable_mir::ty::GeneratorLayout, stable_mir::Error> {
+        let tcx = self.tcx;
+        let def_id = self[def.0];
+        let layout = if tcx.is_mir_available(def_id) { tcx.generator_layout(def_id) } else { None };
+        let Some(layout) = layout else {
+            return Err(stable_mir::Error::new(format!(
+                "the layout of `{}` is not available",
+                tcx.def_path_str(def_id)
+            )));
+        };
+        Ok(stable_mir::ty::GeneratorLayout {
+            saved_locals: layout
+                .field_tys
+                .iter_enumerated()
+                .map(|(local, saved)| stable_mir::ty::GeneratorSavedLocal {
+                    ty: self.intern_ty(saved.ty),
+                    name: layout.field_names[local].map(|name| name.to_string()),
+                    span: saved.source_info.span.stable(self),
+                })
+                .collect(),
+            variant_fields: layout
+                .variant_fields
+                .iter()
+                .map(|fields| fields.iter().map(|local| local.as_usize()).collect())
+                .collect(),
+            variant_spans: layout
+                .variant_source_info
+                .iter()
+                .map(|source_info| source_info.span.stable(self))
+                .collect(),
+        })
+    }
+
+    fn resolve_instance(
+        &mut self,
+        def: stable_mir::ty::FnDef,
+        args: &stable_mir::ty::GenericArgs,
+    ) -> Result<stable_mir::mir::mono::Instance, stable_mir::Error> {
+        let def_id = self[def.0];
+        let args = self.rustc_args(args)?;
+        if args.has_param() {
+            return Err(stable_mir::Error::new(format!(
+                "the arguments `{args:?}` depend on generic parameters"
+            )));
+        }
+        match ty::Instance::resolve(self.tcx, ty::ParamEnv::reveal_all(), def_id, args) {
+            Ok(Some(instance)) => Ok(instance.stable(self)),
+            Ok(None) | Err(_) => Err(stable_mir::Error::new(format!(
+                "failed to resolve `{}` with the arguments `{args:?}`",
+                self.tcx.def_path_str(def_id)
+            ))),
+        }
+    }
+
+    fn resolve_call(
+        &mut self,
+        def: stable_mir::ty::FnDef,
+        args: &stable_mir::ty::GenericArgs,
+    ) -> Result<Option<stable_mir::ty::FnDef>, stable_mir::Error> {
+        let instance = self.resolve_instance(def, args)?;
+        match self.instances[instance.def.0].def {
+            ty::InstanceDef::Item(def_id)
+                if matches!(self.tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn) =>
+            {
+                Ok(Some(self.fn_def(def_id)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn instance_body(
+        &mut self,
+        def: stable_mir::mir::mono::InstanceDef,
+    ) -> Result<stable_mir::mir::Body, stable_mir::Error> {
+        let instance = self.instances[def.0];
+        let has_body = match instance.def {
+            ty::InstanceDef::Item(def_id) => self.tcx.is_mir_available(def_id),
+            ty::InstanceDef::Intrinsic(_) | ty::InstanceDef::Virtual(..) => false,
+            _ => true,
+        };
+        if !has_body {
+            return Err(stable_mir::Error::new(format!("`{instance}` doesn't have a body")));
+        }
+        let body = instance.subst_mir_and_normalize_erasing_regions(
+            self.tcx,
+            ty::ParamEnv::reveal_all(),
+            ty::EarlyBinder::bind(self.tcx.instance_mir(instance.def).clone()),
+        );
+        Ok(body.stable(self))
+    }
+
+    fn instance_ty(&mut self, def: stable_mir::mir::mono::InstanceDef) -> stable_mir::ty::Ty {
+        let instance = self.instances[def.0];
+        let ty = instance.ty(self.tcx, ty::ParamEnv::reveal_all());
+        self.intern_ty(ty)
+    }
+
+    fn instance_fn_abi(
+        &mut self,
+        def: stable_mir::mir::mono::InstanceDef,
+    ) -> Result<stable_mir::abi::FnAbi, stable_mir::Error> {
+        let instance = self.instances[def.0];
+        let fn_abi = self
+            .tcx
+            .fn_abi_of_instance(ty::ParamEnv::reveal_all().and((instance, ty::List::empty())))
+            .map_err(|_| {
+                stable_mir::Error::new(format!("failed to compute the ABI of `{instance}`"))
+            })?;
+        Ok(fn_abi.stable(self))
+    }
+
+    fn alloc_static(&mut self, alloc: stable_mir::AllocId) -> Option<stable_mir::CrateItem> {
+        match self.tcx.try_get_global_alloc(self.alloc_ids[alloc.0])? {
+            GlobalAlloc::Static(def_id) => Some(self.crate_item(def_id)),
+            GlobalAlloc::Function(_) | GlobalAlloc::VTable(..) | GlobalAlloc::Memory(_) => None,
+        }
+    }
+
+    fn static_is_mutable(&mut self, item: stable_mir::DefId) -> bool {
+        let def_id = self[item];
+        let ty = self.tcx.type_of(def_id).instantiate_identity();
+        self.tcx.is_mutable_static(def_id) || !ty.is_freeze(self.tcx, ty::ParamEnv::reveal_all())
+    }
+
+    fn static_of_item(&mut self, item: stable_mir::DefId) -> Option<stable_mir::ty::StaticDef> {
+        let def_id = self[item];
+        self.tcx.is_static(def_id).then(|| self.static_def(def_id))
+    }
+
+    fn static_ty(&mut self, def: stable_mir::ty::StaticDef) -> stable_mir::ty::Ty {
+        let ty = self.tcx.type_of(self[def.0]).instantiate_identity();
+        self.intern_ty(ty)
+    }
+
+    fn static_initializer_body(
+        &mut self,
+        def: stable_mir::ty::StaticDef,
+    ) -> Result<stable_mir::mir::Body, stable_mir::Error> {
+        let def_id = self[def.0];
+        if self.tcx.is_foreign_item(def_id) || !self.tcx.is_ctfe_mir_available(def_id) {
+            return Err(stable_mir::Error::new(format!(
+                "the static `{}` doesn't have a body",
+                self.tcx.def_path_str(def_id)
+            )));
+        }
+        Ok(self.tcx.mir_for_ctfe(def_id).stable(self))
+    }
+
+    fn static_allocation(
+        &mut self,
+        def: stable_mir::ty::StaticDef,
+    ) -> Result<stable_mir::ty::Allocation, stable_mir::Error> {
+        let tcx = self.tcx;
+        let def_id = self[def.0];
+        if tcx.is_foreign_item(def_id) {
+            return Err(stable_mir::Error::new(format!(
+                "the static `{}` is defined outside of Rust",
+                tcx.def_path_str(def_id)
+            )));
+        }
+        match tcx.eval_static_initializer(def_id) {
+            Ok(alloc) => Ok(alloc.inner().stable(self)),
+            Err(_) => Err(stable_mir::Error::new(format!(
+                "failed to evaluate the static `{}`",
+                tcx.def_path_str(def_id)
+            ))),
+        }
+    }
+
     fn generics_of(&mut self, def_id: stable_mir::DefId) -> stable_mir::ty::Generics {
         let def_id = self[def_id];
         let generics = self.tcx.generics_of(def_id);
@@ -189,6 +946,59 @@ pub struct Tables<'tcx> {
     pub alloc_ids: Vec<AllocId>,
     pub spans: Vec<rustc_span::Span>,
     pub types: Vec<MaybeStable<stable_mir::ty::TyKind, Ty<'tcx>>>,
+    pub param_envs: Vec<ty::ParamEnv<'tcx>>,
+    pub instances: Vec<ty::Instance<'tcx>>,
+    /// The allocations of rustc, and the offset in them, from which the values of the constants
+    /// which aren't scalars were read, keyed by the value converted back to an allocation of
+    /// rustc. They are used to print these constants.
+    pub(crate) const_allocs: HashMap<mir::interpret::Allocation, (AllocId, Size)>,
+    /// The borrowck facts of the local bodies, when they were collected.
+    pub(crate) borrowck_facts: LocalDefIdMap<BodyWithBorrowckFacts<'tcx>>,
+}
+
+/// The path of an item with the name of its crate, like `std::mem::{impl#0}::swap`.
+fn def_path(tcx: TyCtxt<'_>, def_id: DefId) -> String {
+    format!("{}{}", tcx.crate_name(def_id.krate), tcx.def_path(def_id).to_string_no_crate_verbose())
+}
+
+/// Checks that `args` has an argument of the right kind for each generic parameter mentioned by
+/// `ty`, as the compiler ICEs when instantiating it otherwise.
+fn check_instantiation<'tcx>(
+    ty: Ty<'tcx>,
+    args: ty::GenericArgsRef<'tcx>,
+) -> Result<(), stable_mir::Error> {
+    fn kind(arg: ty::GenericArg<'_>) -> &'static str {
+        match arg.unpack() {
+            ty::GenericArgKind::Lifetime(_) => "lifetime",
+            ty::GenericArgKind::Type(_) => "type",
+            ty::GenericArgKind::Const(_) => "constant",
+        }
+    }
+    for arg in ty.walk() {
+        let index = match arg.unpack() {
+            ty::GenericArgKind::Lifetime(region) => match *region {
+                ty::ReEarlyBound(param) => param.index,
+                _ => continue,
+            },
+            ty::GenericArgKind::Type(ty) => match *ty.kind() {
+                ty::Param(param) => param.index,
+                _ => continue,
+            },
+            ty::GenericArgKind::Const(cnst) => match cnst.kind() {
+                ty::ConstKind::Param(param) => param.index,
+                _ => continue,
+            },
+        };
+        let found = args.get(index as usize).map(|found| kind(*found));
+        if found != Some(kind(arg)) {
+            return Err(stable_mir::Error::new(format!(
+                "expected a {} argument for the generic parameter {index} of `{ty}`, found {}",
+                kind(arg),
+                found.map_or("none".to_owned(), |found| format!("a {found}"))
+            )));
+        }
+    }
+    Ok(())
 }
 
 impl<'tcx> Tables<'tcx> {
@@ -200,6 +1010,123 @@ fn intern_ty(&mut self, ty: Ty<'tcx>) -> stable_mir::ty::Ty {
         self.types.push(MaybeStable::Rustc(ty));
         stable_mir::ty::Ty(id)
     }
+
+    fn intern_param_env(&mut self, param_env: ty::ParamEnv<'tcx>) -> stable_mir::ty::ParamEnv {
+        if let Some(id) = self.param_envs.iter().position(|env| *env == param_env) {
+            return stable_mir::ty::ParamEnv(id);
+        }
+        let id = self.param_envs.len();
+        self.param_envs.push(param_env);
+        stable_mir::ty::ParamEnv(id)
+    }
+
+    fn instance_def(&mut self, instance: ty::Instance<'tcx>) -> stable_mir::mir::mono::InstanceDef {
+        if let Some(id) = self.instances.iter().position(|i| *i == instance) {
+            return stable_mir::mir::mono::InstanceDef(id);
+        }
+        let id = self.instances.len();
+        self.instances.push(instance);
+        stable_mir::mir::mono::InstanceDef(id)
+    }
+
+    /// Normalizes `value` in `env`, erasing its regions.
+    fn normalize<T: TypeFoldable<TyCtxt<'tcx>>>(
+        &self,
+        env: stable_mir::ty::ParamEnv,
+        value: T,
+    ) -> Result<T, stable_mir::Error> {
+        self.tcx.try_normalize_erasing_regions(self.param_envs[env.0], value).map_err(|err| {
+            stable_mir::Error::new(format!("failed to normalize `{}`", err.get_type_for_failure()))
+        })
+    }
+
+    /// Returns the rustc unevaluated constant of `uv`, with its arguments normalized in `env`.
+    fn rustc_unevaluated(
+        &self,
+        env: stable_mir::ty::ParamEnv,
+        uv: &stable_mir::ty::UnevaluatedConst,
+    ) -> Result<mir::UnevaluatedConst<'tcx>, stable_mir::Error> {
+        let args = self.rustc_args(&uv.args)?;
+        let args = self.normalize(env, args)?;
+        Ok(mir::UnevaluatedConst {
+            def: self[uv.def.0],
+            args,
+            promoted: uv.promoted.map(mir::Promoted::from_u32),
+        })
+    }
+
+    /// Returns the rustc generic arguments of `args`, with erased regions. Only the constants
+    /// which are generic parameters or evaluated scalars are supported.
+    fn rustc_args(
+        &self,
+        args: &stable_mir::ty::GenericArgs,
+    ) -> Result<ty::GenericArgsRef<'tcx>, stable_mir::Error> {
+        let tcx = self.tcx;
+        let args = args
+            .0
+            .iter()
+            .map(|arg| match arg {
+                stable_mir::ty::GenericArgKind::Lifetime(_) => Ok(tcx.lifetimes.re_erased.into()),
+                stable_mir::ty::GenericArgKind::Type(ty) => Ok(self.rustc_ty(*ty)?.into()),
+                stable_mir::ty::GenericArgKind::Const(cnst) => match &cnst.literal {
+                    stable_mir::ty::ConstantKind::Param(param) => {
+                        let param = ty::ParamConst::new(param.index, Symbol::intern(&param.name));
+                        Ok(ty::Const::new_param(tcx, param, self.rustc_ty(cnst.ty)?).into())
+                    }
+                    stable_mir::ty::ConstantKind::Allocated(alloc) => {
+                        Ok(self.rustc_scalar_const(alloc, self.rustc_ty(cnst.ty)?)?.into())
+                    }
+                    _ => Err(stable_mir::Error::new(
+                        "only constant arguments which are generic parameters or scalars are \
+                         supported"
+                            .to_owned(),
+                    )),
+                },
+            })
+            .collect::<Result<Vec<ty::GenericArg<'tcx>>, _>>()?;
+        Ok(tcx.mk_args(&args))
+    }
+
+    /// Returns the rustc constant of type `ty` whose value is `alloc`, like the value of a const
+    /// generic argument, which must be an integer, a `bool` or a `char`.
+    fn rustc_scalar_const(
+        &self,
+        alloc: &stable_mir::ty::Allocation,
+        ty: Ty<'tcx>,
+    ) -> Result<ty::Const<'tcx>, stable_mir::Error> {
+        if !ty.is_integral() && !ty.is_bool() && !ty.is_char() {
+            return Err(stable_mir::Error::new(format!(
+                "expected a constant argument of an integer, `bool` or `char` type, found one of \
+                 type `{ty}`"
+            )));
+        }
+        let ty = ty::ParamEnv::reveal_all().and(ty);
+        let size =
+            self.tcx.layout_of(ty).map_err(|err| stable_mir::Error::new(err.to_string()))?.size;
+        if alloc.bytes.len() as u64 != size.bytes() {
+            return Err(stable_mir::Error::new(format!(
+                "expected a constant argument of {} bytes, found {} bytes",
+                size.bytes(),
+                alloc.bytes.len()
+            )));
+        }
+        let bytes = alloc.bytes.iter().copied().collect::<Option<Vec<u8>>>().ok_or_else(|| {
+            stable_mir::Error::new("the constant argument is uninitialized".to_owned())
+        })?;
+        let bits = read_target_uint(self.tcx.data_layout.endian, &bytes)
+            .map_err(|err| stable_mir::Error::new(err.to_string()))?;
+        Ok(ty::Const::from_bits(self.tcx, bits, ty))
+    }
+
+    /// Returns the rustc type of `ty`, which fails if it was created by [`Context::mk_ty`].
+    fn rustc_ty(&self, ty: stable_mir::ty::Ty) -> Result<Ty<'tcx>, stable_mir::Error> {
+        match self.types[ty.0] {
+            MaybeStable::Rustc(ty) => Ok(ty),
+            MaybeStable::Stable(_) => {
+                Err(stable_mir::Error::new("the type was not created by the compiler".to_owned()))
+            }
+        }
+    }
 }
 
 /// Build a stable mir crate from a given crate number.
@@ -218,44 +1145,81 @@ pub(crate) trait Stable<'tcx> {
     fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T;
 }
 
+impl<'tcx> Stable<'tcx> for mir::Body<'tcx> {
+    type T = stable_mir::mir::Body;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        stable_mir::mir::Body {
+            blocks: self
+                .basic_blocks
+                .iter()
+                .map(|block| stable_mir::mir::BasicBlock {
+                    terminator: block.terminator().stable(tables),
+                    statements: block
+                        .statements
+                        .iter()
+                        .map(|statement| statement.stable(tables))
+                        .collect(),
+                })
+                .collect(),
+            locals: self.local_decls.iter().map(|decl| tables.intern_ty(decl.ty)).collect(),
+            arg_count: self.arg_count,
+            var_debug_info: self.var_debug_info.iter().map(|info| info.stable(tables)).collect(),
+            span: self.span.stable(tables),
+        }
+    }
+}
+
 impl<'tcx> Stable<'tcx> for mir::Statement<'tcx> {
     type T = stable_mir::mir::Statement;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        stable_mir::mir::Statement {
+            kind: self.kind.stable(tables),
+            span: self.source_info.span.stable(tables),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::StatementKind<'tcx> {
+    type T = stable_mir::mir::StatementKind;
     fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
         use rustc_middle::mir::StatementKind::*;
-        match &self.kind {
-            Assign(assign) => {
-                stable_mir::mir::Statement::Assign(assign.0.stable(tables), assign.1.stable(tables))
-            }
-            FakeRead(fake_read_place) => stable_mir::mir::Statement::FakeRead(
+        match self {
+            Assign(assign) => stable_mir::mir::StatementKind::Assign(
+                assign.0.stable(tables),
+                assign.1.stable(tables),
+            ),
+            FakeRead(fake_read_place) => stable_mir::mir::StatementKind::FakeRead(
                 fake_read_place.0.stable(tables),
                 fake_read_place.1.stable(tables),
             ),
             SetDiscriminant { place: plc, variant_index: idx } => {
-                stable_mir::mir::Statement::SetDiscriminant {
+                stable_mir::mir::StatementKind::SetDiscriminant {
                     place: plc.as_ref().stable(tables),
                     variant_index: idx.stable(tables),
                 }
             }
-            Deinit(place) => stable_mir::mir::Statement::Deinit(place.stable(tables)),
-            StorageLive(place) => stable_mir::mir::Statement::StorageLive(place.stable(tables)),
-            StorageDead(place) => stable_mir::mir::Statement::StorageDead(place.stable(tables)),
+            Deinit(place) => stable_mir::mir::StatementKind::Deinit(place.stable(tables)),
+            StorageLive(place) => stable_mir::mir::StatementKind::StorageLive(place.stable(tables)),
+            StorageDead(place) => stable_mir::mir::StatementKind::StorageDead(place.stable(tables)),
             Retag(retag, place) => {
-                stable_mir::mir::Statement::Retag(retag.stable(tables), place.stable(tables))
+                stable_mir::mir::StatementKind::Retag(retag.stable(tables), place.stable(tables))
+            }
+            PlaceMention(place) => {
+                stable_mir::mir::StatementKind::PlaceMention(place.stable(tables))
             }
-            PlaceMention(place) => stable_mir::mir::Statement::PlaceMention(place.stable(tables)),
             AscribeUserType(place_projection, variance) => {
-                stable_mir::mir::Statement::AscribeUserType {
+                stable_mir::mir::StatementKind::AscribeUserType {
                     place: place_projection.as_ref().0.stable(tables),
                     projections: place_projection.as_ref().1.stable(tables),
                     variance: variance.stable(tables),
                 }
             }
-            Coverage(coverage) => stable_mir::mir::Statement::Coverage(opaque(coverage)),
+            Coverage(coverage) => stable_mir::mir::StatementKind::Coverage(coverage.stable(tables)),
             Intrinsic(intrinstic) => {
-                stable_mir::mir::Statement::Intrinsic(intrinstic.stable(tables))
+                stable_mir::mir::StatementKind::Intrinsic(intrinstic.stable(tables))
             }
-            ConstEvalCounter => stable_mir::mir::Statement::ConstEvalCounter,
-            Nop => stable_mir::mir::Statement::Nop,
+            ConstEvalCounter => stable_mir::mir::StatementKind::ConstEvalCounter,
+            Nop => stable_mir::mir::StatementKind::Nop,
         }
     }
 }
@@ -339,6 +1303,62 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
     }
 }
 
+impl<'tcx> Stable<'tcx> for ty::CapturedPlace<'tcx> {
+    type T = stable_mir::ty::CapturedPlace;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        use rustc_middle::hir::place::ProjectionKind;
+        use stable_mir::mir::ProjectionElem;
+        let place = &self.place;
+        let mut projection = vec![];
+        for (index, proj) in place.projections.iter().enumerate() {
+            match proj.kind {
+                ProjectionKind::Deref => projection.push(ProjectionElem::Deref),
+                ProjectionKind::Field(field, variant) => {
+                    if place.ty_before_projection(index).is_enum() {
+                        projection.push(ProjectionElem::Downcast(variant.stable(tables)));
+                    }
+                    projection.push(ProjectionElem::Field(
+                        field.stable(tables),
+                        tables.intern_ty(proj.ty),
+                    ));
+                }
+                ProjectionKind::OpaqueCast => {
+                    projection.push(ProjectionElem::OpaqueCast(tables.intern_ty(proj.ty)));
+                }
+                // Captures are truncated before indexing, as the index isn't known when the
+                // closure is created.
+                ProjectionKind::Index | ProjectionKind::Subslice => {
+                    unreachable!("unexpected projection in a captured place: {place:?}")
+                }
+            }
+        }
+        stable_mir::ty::CapturedPlace {
+            var_name: self.var_ident.to_string(),
+            projection,
+            ty: tables.intern_ty(place.ty()),
+            kind: self.info.capture_kind.stable(tables),
+            mutability: self.mutability.stable(tables),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for ty::UpvarCapture {
+    type T = stable_mir::ty::CaptureKind;
+    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+        use stable_mir::mir::{BorrowKind, MutBorrowKind};
+        match self {
+            ty::UpvarCapture::ByValue => stable_mir::ty::CaptureKind::ByValue,
+            ty::UpvarCapture::ByRef(kind) => stable_mir::ty::CaptureKind::ByRef(match kind {
+                ty::BorrowKind::ImmBorrow => BorrowKind::Shared,
+                ty::BorrowKind::UniqueImmBorrow => {
+                    BorrowKind::Mut { kind: MutBorrowKind::ClosureCapture }
+                }
+                ty::BorrowKind::MutBorrow => BorrowKind::Mut { kind: MutBorrowKind::Default },
+            }),
+        }
+    }
+}
+
 impl<'tcx> Stable<'tcx> for mir::MutBorrowKind {
     type T = stable_mir::mir::MutBorrowKind;
     fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
@@ -506,20 +1526,87 @@ fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
 
 impl<'tcx> Stable<'tcx> for mir::FakeReadCause {
     type T = stable_mir::mir::FakeReadCause;
-    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
         use mir::FakeReadCause::*;
         match self {
             ForMatchGuard => stable_mir::mir::FakeReadCause::ForMatchGuard,
-            ForMatchedPlace(local_def_id) => {
-                stable_mir::mir::FakeReadCause::ForMatchedPlace(opaque(local_def_id))
-            }
+            ForMatchedPlace(closure) => stable_mir::mir::FakeReadCause::ForMatchedPlace(
+                closure.map(|def_id| tables.closure_def(def_id.to_def_id())),
+            ),
             ForGuardBinding => stable_mir::mir::FakeReadCause::ForGuardBinding,
-            ForLet(local_def_id) => stable_mir::mir::FakeReadCause::ForLet(opaque(local_def_id)),
+            ForLet(closure) => stable_mir::mir::FakeReadCause::ForLet(
+                closure.map(|def_id| tables.closure_def(def_id.to_def_id())),
+            ),
             ForIndex => stable_mir::mir::FakeReadCause::ForIndex,
         }
     }
 }
 
+impl<'tcx> Stable<'tcx> for mir::Coverage {
+    type T = stable_mir::mir::Coverage;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        stable_mir::mir::Coverage {
+            kind: self.kind.stable(tables),
+            code_region: self.code_region.as_ref().map(|region| region.stable(tables)),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::coverage::CoverageKind {
+    type T = stable_mir::mir::CoverageKind;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        use mir::coverage::CoverageKind::*;
+        match self {
+            Counter { function_source_hash, id } => stable_mir::mir::CoverageKind::Counter {
+                function_source_hash: *function_source_hash,
+                id: id.as_usize(),
+            },
+            Expression { id, lhs, op, rhs } => stable_mir::mir::CoverageKind::Expression {
+                id: id.as_usize(),
+                lhs: lhs.stable(tables),
+                op: op.stable(tables),
+                rhs: rhs.stable(tables),
+            },
+            Unreachable => stable_mir::mir::CoverageKind::Unreachable,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::coverage::Operand {
+    type T = stable_mir::mir::CovTerm;
+    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+        use mir::coverage::Operand::*;
+        match self {
+            Zero => stable_mir::mir::CovTerm::Zero,
+            Counter(id) => stable_mir::mir::CovTerm::Counter(id.as_usize()),
+            Expression(id) => stable_mir::mir::CovTerm::Expression(id.as_usize()),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::coverage::Op {
+    type T = stable_mir::mir::CovOp;
+    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+        match self {
+            mir::coverage::Op::Add => stable_mir::mir::CovOp::Add,
+            mir::coverage::Op::Subtract => stable_mir::mir::CovOp::Subtract,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::coverage::CodeRegion {
+    type T = stable_mir::mir::CodeRegion;
+    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+        stable_mir::mir::CodeRegion {
+            file_name: self.file_name.to_string(),
+            start_line: self.start_line,
+            start_col: self.start_col,
+            end_line: self.end_line,
+            end_col: self.end_col,
+        }
+    }
+}
+
 impl<'tcx> Stable<'tcx> for FieldIdx {
     type T = usize;
     fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
@@ -553,10 +1640,101 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
 
 impl<'tcx> Stable<'tcx> for mir::Place<'tcx> {
     type T = stable_mir::mir::Place;
-    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
         stable_mir::mir::Place {
             local: self.local.as_usize(),
-            projection: format!("{:?}", self.projection),
+            projection: self.projection.iter().map(|elem| elem.stable(tables)).collect(),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::PlaceElem<'tcx> {
+    type T = stable_mir::mir::ProjectionElem;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        use mir::ProjectionElem::*;
+        use stable_mir::mir::ProjectionElem;
+        match *self {
+            Deref => ProjectionElem::Deref,
+            Field(idx, ty) => ProjectionElem::Field(idx.stable(tables), tables.intern_ty(ty)),
+            Index(local) => ProjectionElem::Index(local.stable(tables)),
+            ConstantIndex { offset, min_length, from_end } => {
+                ProjectionElem::ConstantIndex { offset, min_length, from_end }
+            }
+            Subslice { from, to, from_end } => ProjectionElem::Subslice { from, to, from_end },
+            Downcast(_, variant) => ProjectionElem::Downcast(variant.stable(tables)),
+            OpaqueCast(ty) => ProjectionElem::OpaqueCast(tables.intern_ty(ty)),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::ProjectionKind {
+    type T = stable_mir::mir::ProjectionKind;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        use mir::ProjectionElem::*;
+        use stable_mir::mir::ProjectionElem;
+        match *self {
+            Deref => ProjectionElem::Deref,
+            Field(idx, ()) => ProjectionElem::Field(idx.stable(tables), ()),
+            Index(()) => ProjectionElem::Index(()),
+            ConstantIndex { offset, min_length, from_end } => {
+                ProjectionElem::ConstantIndex { offset, min_length, from_end }
+            }
+            Subslice { from, to, from_end } => ProjectionElem::Subslice { from, to, from_end },
+            Downcast(_, variant) => ProjectionElem::Downcast(variant.stable(tables)),
+            OpaqueCast(()) => ProjectionElem::OpaqueCast(()),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::VarDebugInfo<'tcx> {
+    type T = stable_mir::mir::VarDebugInfo;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        stable_mir::mir::VarDebugInfo {
+            name: self.name.to_string(),
+            source_info: self.source_info.stable(tables),
+            composite: self.composite.as_ref().map(|composite| composite.stable(tables)),
+            value: self.value.stable(tables),
+            argument_index: self.argument_index,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::SourceInfo {
+    type T = stable_mir::mir::SourceInfo;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        stable_mir::mir::SourceInfo { span: self.span.stable(tables), scope: self.scope.as_usize() }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::VarDebugInfoFragment<'tcx> {
+    type T = stable_mir::mir::VarDebugInfoFragment;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        stable_mir::mir::VarDebugInfoFragment {
+            ty: tables.intern_ty(self.ty),
+            projection: self
+                .projection
+                .iter()
+                .map(|elem| match elem {
+                    mir::ProjectionElem::Field(idx, ty) => {
+                        (idx.stable(tables), tables.intern_ty(*ty))
+                    }
+                    _ => unreachable!("unexpected projection in a debuginfo fragment: {elem:?}"),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::VarDebugInfoContents<'tcx> {
+    type T = stable_mir::mir::VarDebugInfoContents;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        match self {
+            mir::VarDebugInfoContents::Place(place) => {
+                stable_mir::mir::VarDebugInfoContents::Place(place.stable(tables))
+            }
+            mir::VarDebugInfoContents::Const(constant) => {
+                stable_mir::mir::VarDebugInfoContents::Const(constant.stable(tables))
+            }
         }
     }
 }
@@ -564,8 +1742,11 @@ fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
 impl<'tcx> Stable<'tcx> for mir::UserTypeProjection {
     type T = stable_mir::mir::UserTypeProjection;
 
-    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
-        UserTypeProjection { base: self.base.as_usize(), projection: format!("{:?}", self.projs) }
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        UserTypeProjection {
+            base: self.base.as_usize(),
+            projection: self.projs.iter().map(|elem| elem.stable(tables)).collect(),
+        }
     }
 }
 
@@ -784,31 +1965,96 @@ fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
 impl<'tcx> Stable<'tcx> for mir::InlineAsmOperand<'tcx> {
     type T = stable_mir::mir::InlineAsmOperand;
     fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
-        use rustc_middle::mir::InlineAsmOperand;
+        use rustc_middle::mir::InlineAsmOperand::*;
+        use stable_mir::mir::InlineAsmOperand;
+        match self {
+            In { reg, value } => {
+                InlineAsmOperand::In { reg: reg.stable(tables), value: value.stable(tables) }
+            }
+            Out { reg, late, place } => InlineAsmOperand::Out {
+                reg: reg.stable(tables),
+                late: *late,
+                place: place.map(|place| place.stable(tables)),
+            },
+            InOut { reg, late, in_value, out_place } => InlineAsmOperand::InOut {
+                reg: reg.stable(tables),
+                late: *late,
+                in_value: in_value.stable(tables),
+                out_place: out_place.map(|place| place.stable(tables)),
+            },
+            Const { value } => InlineAsmOperand::Const { value: value.stable(tables) },
+            SymFn { value } => InlineAsmOperand::SymFn { value: value.stable(tables) },
+            SymStatic { def_id } => InlineAsmOperand::SymStatic { def: tables.crate_item(*def_id) },
+        }
+    }
+}
 
-        let (in_value, out_place) = match self {
-            InlineAsmOperand::In { value, .. } => (Some(value.stable(tables)), None),
-            InlineAsmOperand::Out { place, .. } => (None, place.map(|place| place.stable(tables))),
-            InlineAsmOperand::InOut { in_value, out_place, .. } => {
-                (Some(in_value.stable(tables)), out_place.map(|place| place.stable(tables)))
+impl<'tcx> Stable<'tcx> for rustc_target::asm::InlineAsmRegOrRegClass {
+    type T = stable_mir::mir::InlineAsmRegOrRegClass;
+    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+        use rustc_target::asm::InlineAsmRegOrRegClass::*;
+        match self {
+            Reg(reg) => stable_mir::mir::InlineAsmRegOrRegClass::Reg(reg.name().to_string()),
+            RegClass(class) => {
+                stable_mir::mir::InlineAsmRegOrRegClass::RegClass(class.name().to_string())
             }
-            InlineAsmOperand::Const { .. }
-            | InlineAsmOperand::SymFn { .. }
-            | InlineAsmOperand::SymStatic { .. } => (None, None),
-        };
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_ast::InlineAsmTemplatePiece {
+    type T = stable_mir::mir::InlineAsmTemplatePiece;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        use rustc_ast::InlineAsmTemplatePiece::*;
+        match self {
+            String(string) => stable_mir::mir::InlineAsmTemplatePiece::String(string.clone()),
+            Placeholder { operand_idx, modifier, span } => {
+                stable_mir::mir::InlineAsmTemplatePiece::Placeholder {
+                    operand_idx: *operand_idx,
+                    modifier: *modifier,
+                    span: span.stable(tables),
+                }
+            }
+        }
+    }
+}
 
-        stable_mir::mir::InlineAsmOperand { in_value, out_place, raw_rpr: format!("{self:?}") }
+impl<'tcx> Stable<'tcx> for rustc_ast::InlineAsmOptions {
+    type T = stable_mir::mir::InlineAsmOptions;
+    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+        use rustc_ast::InlineAsmOptions;
+        stable_mir::mir::InlineAsmOptions {
+            pure: self.contains(InlineAsmOptions::PURE),
+            nomem: self.contains(InlineAsmOptions::NOMEM),
+            readonly: self.contains(InlineAsmOptions::READONLY),
+            preserves_flags: self.contains(InlineAsmOptions::PRESERVES_FLAGS),
+            noreturn: self.contains(InlineAsmOptions::NORETURN),
+            nostack: self.contains(InlineAsmOptions::NOSTACK),
+            att_syntax: self.contains(InlineAsmOptions::ATT_SYNTAX),
+            raw: self.contains(InlineAsmOptions::RAW),
+            may_unwind: self.contains(InlineAsmOptions::MAY_UNWIND),
+        }
     }
 }
 
 impl<'tcx> Stable<'tcx> for mir::Terminator<'tcx> {
     type T = stable_mir::mir::Terminator;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        stable_mir::mir::Terminator {
+            kind: self.kind.stable(tables),
+            span: self.source_info.span.stable(tables),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::TerminatorKind<'tcx> {
+    type T = stable_mir::mir::TerminatorKind;
     fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
         use rustc_middle::mir::TerminatorKind::*;
-        use stable_mir::mir::Terminator;
-        match &self.kind {
-            Goto { target } => Terminator::Goto { target: target.as_usize() },
-            SwitchInt { discr, targets } => Terminator::SwitchInt {
+        use stable_mir::mir::TerminatorKind;
+        match self {
+            Goto { target } => TerminatorKind::Goto { target: target.as_usize() },
+            SwitchInt { discr, targets } => TerminatorKind::SwitchInt {
                 discr: discr.stable(tables),
                 targets: targets
                     .iter()
@@ -819,17 +2065,17 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
                     .collect(),
                 otherwise: targets.otherwise().as_usize(),
             },
-            UnwindResume => Terminator::Resume,
-            UnwindTerminate(_) => Terminator::Abort,
-            Return => Terminator::Return,
-            Unreachable => Terminator::Unreachable,
-            Drop { place, target, unwind, replace: _ } => Terminator::Drop {
+            UnwindResume => TerminatorKind::Resume,
+            UnwindTerminate(_) => TerminatorKind::Abort,
+            Return => TerminatorKind::Return,
+            Unreachable => TerminatorKind::Unreachable,
+            Drop { place, target, unwind, replace: _ } => TerminatorKind::Drop {
                 place: place.stable(tables),
                 target: target.as_usize(),
                 unwind: unwind.stable(tables),
             },
             Call { func, args, destination, target, unwind, call_source: _, fn_span: _ } => {
-                Terminator::Call {
+                TerminatorKind::Call {
                     func: func.stable(tables),
                     args: args.iter().map(|arg| arg.stable(tables)).collect(),
                     destination: destination.stable(tables),
@@ -837,7 +2083,7 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
                     unwind: unwind.stable(tables),
                 }
             }
-            Assert { cond, expected, msg, target, unwind } => Terminator::Assert {
+            Assert { cond, expected, msg, target, unwind } => TerminatorKind::Assert {
                 cond: cond.stable(tables),
                 expected: *expected,
                 msg: msg.stable(tables),
@@ -845,16 +2091,30 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
                 unwind: unwind.stable(tables),
             },
             InlineAsm { template, operands, options, line_spans, destination, unwind } => {
-                Terminator::InlineAsm {
-                    template: format!("{template:?}"),
+                TerminatorKind::InlineAsm {
+                    template: template.iter().map(|piece| piece.stable(tables)).collect(),
                     operands: operands.iter().map(|operand| operand.stable(tables)).collect(),
-                    options: format!("{options:?}"),
-                    line_spans: format!("{line_spans:?}"),
+                    options: options.stable(tables),
+                    line_spans: line_spans.iter().map(|span| span.stable(tables)).collect(),
                     destination: destination.map(|d| d.as_usize()),
                     unwind: unwind.stable(tables),
                 }
             }
-            Yield { .. } | GeneratorDrop | FalseEdge { .. } | FalseUnwind { .. } => unreachable!(),
+            Yield { value, resume, resume_arg, drop } => TerminatorKind::Yield {
+                value: value.stable(tables),
+                resume: resume.as_usize(),
+                resume_arg: resume_arg.stable(tables),
+                drop: drop.map(|d| d.as_usize()),
+            },
+            GeneratorDrop => TerminatorKind::GeneratorDrop,
+            FalseEdge { real_target, imaginary_target } => TerminatorKind::FalseEdge {
+                real_target: real_target.as_usize(),
+                imaginary_target: imaginary_target.as_usize(),
+            },
+            FalseUnwind { real_target, unwind } => TerminatorKind::FalseUnwind {
+                real_target: real_target.as_usize(),
+                unwind: unwind.stable(tables),
+            },
         }
     }
 }
@@ -1154,10 +2414,17 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
                     ))
                 }
                 ty::ParamCt(param) => stable_mir::ty::ConstantKind::Param(param.stable(tables)),
-                ty::ErrorCt(_) => unreachable!(),
+                ty::ErrorCt(_) => stable_mir::ty::ConstantKind::Error,
                 ty::InferCt(_) => unreachable!(),
-                ty::BoundCt(_, _) => unimplemented!(),
-                ty::PlaceholderCt(_) => unimplemented!(),
+                ty::BoundCt(debruijn, var) => {
+                    stable_mir::ty::ConstantKind::Bound(debruijn.as_usize(), var.as_usize())
+                }
+                ty::PlaceholderCt(placeholder) => {
+                    stable_mir::ty::ConstantKind::Placeholder(stable_mir::ty::Placeholder {
+                        universe: placeholder.universe.as_usize(),
+                        bound: placeholder.bound.as_usize(),
+                    })
+                }
                 ty::Unevaluated(uv) => {
                     stable_mir::ty::ConstantKind::Unevaluated(stable_mir::ty::UnevaluatedConst {
                         def: tables.const_def(uv.def),
@@ -1165,7 +2432,7 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
                         promoted: None,
                     })
                 }
-                ty::ExprCt(_) => unimplemented!(),
+                ty::ExprCt(_) => stable_mir::ty::ConstantKind::Expr,
             },
             ty: tables.intern_ty(self.ty()),
         }
@@ -1508,9 +2775,43 @@ fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
 impl<'tcx> Stable<'tcx> for ty::Region<'tcx> {
     type T = stable_mir::ty::Region;
 
-    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
-        // FIXME: add a real implementation of stable regions
-        opaque(self)
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        use stable_mir::ty::RegionKind;
+        let kind = match **self {
+            ty::ReEarlyBound(region) => {
+                RegionKind::ReEarlyBound(stable_mir::ty::EarlyBoundRegion {
+                    def_id: tables.param_def(region.def_id),
+                    index: region.index,
+                    name: region.name.to_string(),
+                })
+            }
+            ty::ReLateBound(debruijn, region) => {
+                RegionKind::ReLateBound(debruijn.as_usize(), region.stable(tables))
+            }
+            ty::ReFree(region) => RegionKind::ReFree(stable_mir::ty::FreeRegion {
+                scope: tables.crate_item(region.scope),
+                bound_region: region.bound_region.stable(tables),
+            }),
+            ty::ReStatic => RegionKind::ReStatic,
+            ty::ReVar(vid) => RegionKind::ReVar(vid.as_usize()),
+            ty::RePlaceholder(placeholder) => {
+                RegionKind::RePlaceholder(stable_mir::ty::Placeholder {
+                    universe: placeholder.universe.as_usize(),
+                    bound: placeholder.bound.stable(tables),
+                })
+            }
+            ty::ReErased => RegionKind::ReErased,
+            ty::ReError(_) => RegionKind::ReError,
+        };
+        stable_mir::ty::Region { kind }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for ty::BoundRegion {
+    type T = stable_mir::ty::BoundRegion;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        stable_mir::ty::BoundRegion { var: self.var.as_usize(), kind: self.kind.stable(tables) }
     }
 }
 
@@ -1522,6 +2823,272 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
     }
 }
 
+impl<'tcx> Stable<'tcx> for SymbolExportLevel {
+    type T = stable_mir::SymbolExportLevel;
+
+    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+        match self {
+            SymbolExportLevel::C => stable_mir::SymbolExportLevel::C,
+            SymbolExportLevel::Rust => stable_mir::SymbolExportLevel::Rust,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for Linkage {
+    type T = stable_mir::Linkage;
+
+    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+        use stable_mir::Linkage as L;
+        match self {
+            Linkage::External => L::External,
+            Linkage::AvailableExternally => L::AvailableExternally,
+            Linkage::LinkOnceAny => L::LinkOnceAny,
+            Linkage::LinkOnceODR => L::LinkOnceODR,
+            Linkage::WeakAny => L::WeakAny,
+            Linkage::WeakODR => L::WeakODR,
+            Linkage::Appending => L::Appending,
+            Linkage::Internal => L::Internal,
+            Linkage::Private => L::Private,
+            Linkage::ExternalWeak => L::ExternalWeak,
+            Linkage::Common => L::Common,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for ty::Instance<'tcx> {
+    type T = stable_mir::mir::mono::Instance;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        use stable_mir::mir::mono::InstanceKind;
+        let kind = match self.def {
+            ty::InstanceDef::Item(_) => InstanceKind::Item,
+            ty::InstanceDef::Intrinsic(_) => InstanceKind::Intrinsic,
+            ty::InstanceDef::Virtual(..) => InstanceKind::Virtual,
+            ty::InstanceDef::VTableShim(_)
+            | ty::InstanceDef::ReifyShim(_)
+            | ty::InstanceDef::FnPtrAddrShim(..)
+            | ty::InstanceDef::ClosureOnceShim { .. }
+            | ty::InstanceDef::ThreadLocalShim(_)
+            | ty::InstanceDef::DropGlue(..)
+            | ty::InstanceDef::CloneShim(..)
+            | ty::InstanceDef::FnPtrShim(..) => InstanceKind::Shim,
+        };
+        stable_mir::mir::mono::Instance { kind, def: tables.instance_def(*self) }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_target::abi::call::FnAbi<'tcx, Ty<'tcx>> {
+    type T = stable_mir::abi::FnAbi;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        stable_mir::abi::FnAbi {
+            args: self.args.iter().map(|arg| arg.stable(tables)).collect(),
+            ret: self.ret.stable(tables),
+            c_variadic: self.c_variadic,
+            conv: self.conv.stable(tables),
+            can_unwind: self.can_unwind,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_target::abi::call::ArgAbi<'tcx, Ty<'tcx>> {
+    type T = stable_mir::abi::ArgAbi;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        stable_mir::abi::ArgAbi {
+            ty: tables.intern_ty(self.layout.ty),
+            layout: self.layout.stable(tables),
+            mode: self.mode.stable(tables),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_target::abi::TyAndLayout<'tcx, Ty<'tcx>> {
+    type T = stable_mir::abi::Layout;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        self.layout.0 .0.stable(tables)
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_target::abi::LayoutS {
+    type T = stable_mir::abi::Layout;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        stable_mir::abi::Layout {
+            size: self.size.bytes(),
+            align: self.align.abi.bytes(),
+            fields: self.fields.stable(tables),
+            variants: self.variants.stable(tables),
+            largest_niche: self.largest_niche.map(|niche| niche.stable(tables)),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_target::abi::FieldsShape {
+    type T = stable_mir::abi::FieldsShape;
+
+    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+        use rustc_target::abi::FieldsShape;
+        match self {
+            FieldsShape::Primitive => stable_mir::abi::FieldsShape::Primitive,
+            FieldsShape::Union(count) => stable_mir::abi::FieldsShape::Union(count.get()),
+            FieldsShape::Array { stride, count } => {
+                stable_mir::abi::FieldsShape::Array { stride: stride.bytes(), count: *count }
+            }
+            FieldsShape::Arbitrary { offsets, .. } => stable_mir::abi::FieldsShape::Arbitrary {
+                offsets: offsets.iter().map(|offset| offset.bytes()).collect(),
+            },
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_target::abi::Variants {
+    type T = stable_mir::abi::VariantsShape;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        use rustc_target::abi::Variants;
+        match self {
+            Variants::Single { index } => {
+                stable_mir::abi::VariantsShape::Single { index: index.stable(tables) }
+            }
+            Variants::Multiple { tag, tag_encoding, tag_field, variants } => {
+                stable_mir::abi::VariantsShape::Multiple {
+                    tag: tag.stable(tables),
+                    tag_encoding: tag_encoding.stable(tables),
+                    tag_field: *tag_field,
+                    variants: variants.iter().map(|variant| variant.stable(tables)).collect(),
+                }
+            }
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_target::abi::TagEncoding {
+    type T = stable_mir::abi::TagEncoding;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        use rustc_target::abi::TagEncoding;
+        match self {
+            TagEncoding::Direct => stable_mir::abi::TagEncoding::Direct,
+            TagEncoding::Niche { untagged_variant, niche_variants, niche_start } => {
+                stable_mir::abi::TagEncoding::Niche {
+                    untagged_variant: untagged_variant.stable(tables),
+                    niche_variants: niche_variants.start().stable(tables)
+                        ..=niche_variants.end().stable(tables),
+                    niche_start: *niche_start,
+                }
+            }
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_target::abi::Niche {
+    type T = stable_mir::abi::Niche;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        stable_mir::abi::Niche {
+            offset: self.offset.bytes(),
+            value: self.value.stable(tables),
+            valid_range: self.valid_range.stable(tables),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_target::abi::Scalar {
+    type T = stable_mir::abi::Scalar;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        use rustc_target::abi::Scalar;
+        match self {
+            Scalar::Initialized { value, valid_range } => stable_mir::abi::Scalar::Initialized {
+                value: value.stable(tables),
+                valid_range: valid_range.stable(tables),
+            },
+            Scalar::Union { value } => {
+                stable_mir::abi::Scalar::Union { value: value.stable(tables) }
+            }
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_target::abi::Primitive {
+    type T = stable_mir::abi::Primitive;
+
+    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+        use rustc_target::abi::Primitive;
+        match self {
+            Primitive::Int(integer, signed) => {
+                stable_mir::abi::Primitive::Int { size: integer.size().bytes(), signed: *signed }
+            }
+            Primitive::F32 => stable_mir::abi::Primitive::F32,
+            Primitive::F64 => stable_mir::abi::Primitive::F64,
+            Primitive::Pointer(_) => stable_mir::abi::Primitive::Pointer,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_target::abi::WrappingRange {
+    type T = stable_mir::abi::WrappingRange;
+
+    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+        stable_mir::abi::WrappingRange { start: self.start, end: self.end }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_target::abi::call::PassMode {
+    type T = stable_mir::abi::PassMode;
+
+    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+        use rustc_target::abi::call::PassMode;
+        match *self {
+            PassMode::Ignore => stable_mir::abi::PassMode::Ignore,
+            PassMode::Direct(_) => stable_mir::abi::PassMode::Direct,
+            PassMode::Pair(..) => stable_mir::abi::PassMode::Pair,
+            PassMode::Cast { pad_i32, .. } => stable_mir::abi::PassMode::Cast { pad_i32 },
+            PassMode::Indirect { on_stack, .. } => stable_mir::abi::PassMode::Indirect { on_stack },
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_target::abi::call::Conv {
+    type T = stable_mir::abi::CallConvention;
+
+    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+        use rustc_target::abi::call::{Conv, RiscvInterruptKind};
+        use stable_mir::abi::CallConvention;
+        match *self {
+            Conv::C => CallConvention::C,
+            Conv::Rust => CallConvention::Rust,
+            Conv::Cold => CallConvention::Cold,
+            Conv::PreserveMost => CallConvention::PreserveMost,
+            Conv::PreserveAll => CallConvention::PreserveAll,
+            Conv::ArmAapcs => CallConvention::ArmAapcs,
+            Conv::CCmseNonSecureCall => CallConvention::CCmseNonSecureCall,
+            Conv::Msp430Intr => CallConvention::Msp430Intr,
+            Conv::PtxKernel => CallConvention::PtxKernel,
+            Conv::X86Fastcall => CallConvention::X86Fastcall,
+            Conv::X86Intr => CallConvention::X86Intr,
+            Conv::X86Stdcall => CallConvention::X86Stdcall,
+            Conv::X86ThisCall => CallConvention::X86ThisCall,
+            Conv::X86VectorCall => CallConvention::X86VectorCall,
+            Conv::X86_64SysV => CallConvention::X86_64SysV,
+            Conv::X86_64Win64 => CallConvention::X86_64Win64,
+            Conv::AmdGpuKernel => CallConvention::AmdGpuKernel,
+            Conv::AvrInterrupt => CallConvention::AvrInterrupt,
+            Conv::AvrNonBlockingInterrupt => CallConvention::AvrNonBlockingInterrupt,
+            Conv::RiscvInterrupt { kind } => CallConvention::RiscvInterrupt {
+                kind: match kind {
+                    RiscvInterruptKind::Machine => stable_mir::abi::RiscvInterruptKind::Machine,
+                    RiscvInterruptKind::Supervisor => {
+                        stable_mir::abi::RiscvInterruptKind::Supervisor
+                    }
+                },
+            },
+        }
+    }
+}
+
 impl<T> From<ErrorGuaranteed> for CompilerError<T> {
     fn from(_error: ErrorGuaranteed) -> Self {
         CompilerError::CompilationFailed
@@ -0,0 +1,187 @@
+//! How functions are called and values are laid out in memory, once their types are known.
+
+use std::ops::RangeInclusive;
+
+use super::mir::VariantIdx;
+use super::ty::Ty;
+
+/// How a function is called at the machine level: how its arguments are passed and how its
+/// value is returned. See [`Instance::fn_abi`](super::mir::mono::Instance::fn_abi).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FnAbi {
+    /// The arguments of the function. For C-variadic functions, these are only the arguments
+    /// which are not passed to the variadic part.
+    pub args: Vec<ArgAbi>,
+    pub ret: ArgAbi,
+    /// Whether the function is a C-variadic function, like `printf`.
+    pub c_variadic: bool,
+    pub conv: CallConvention,
+    /// Whether the function can unwind.
+    pub can_unwind: bool,
+}
+
+/// How an argument or a return value is passed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArgAbi {
+    pub ty: Ty,
+    pub layout: Layout,
+    pub mode: PassMode,
+}
+
+/// How an argument or a return value is passed, without the attributes given to the backend.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PassMode {
+    /// Not passed at all, like values of zero-sized types.
+    Ignore,
+    /// Passed as a single immediate value, like an integer or a pointer.
+    Direct,
+    /// Passed as two immediate values, like the pointer and the length of a slice reference.
+    Pair,
+    /// Passed as one or more immediate values of another type, like the registers of the C ABI.
+    Cast { pad_i32: bool },
+    /// Passed behind a pointer to a copy of the value. If `on_stack` is true, the value is
+    /// copied on the stack by the caller instead, like with `byval` in LLVM.
+    Indirect { on_stack: bool },
+}
+
+/// The calling convention of a function.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallConvention {
+    C,
+    Rust,
+
+    Cold,
+    PreserveMost,
+    PreserveAll,
+
+    ArmAapcs,
+    CCmseNonSecureCall,
+
+    Msp430Intr,
+
+    PtxKernel,
+
+    X86Fastcall,
+    X86Intr,
+    X86Stdcall,
+    X86ThisCall,
+    X86VectorCall,
+
+    X86_64SysV,
+    X86_64Win64,
+
+    AmdGpuKernel,
+    AvrInterrupt,
+    AvrNonBlockingInterrupt,
+
+    RiscvInterrupt { kind: RiscvInterruptKind },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RiscvInterruptKind {
+    Machine,
+    Supervisor,
+}
+
+/// The layout of a type: its size and alignment, where its fields are, and how the variants of
+/// an enum are told apart. See [`Ty::layout`]. All the sizes and offsets are in bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Layout {
+    pub size: u64,
+    pub align: u64,
+    pub fields: FieldsShape,
+    pub variants: VariantsShape,
+    /// The largest range of values which are invalid for one of the scalars of the type, which
+    /// can be used to store the discriminant of an enum containing the type, like the null
+    /// pointer of references is used for `None` in `Option<&T>`.
+    pub largest_niche: Option<Niche>,
+}
+
+/// Where the fields of a type are.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldsShape {
+    /// A scalar type, like an integer or a pointer, which doesn't have fields.
+    Primitive,
+    /// All the fields of a union start at offset 0. This is the number of fields.
+    Union(usize),
+    /// The elements of an array, each `stride` bytes after the previous one.
+    Array { stride: u64, count: u64 },
+    /// The offsets of the fields of a struct, tuple, closure or enum variant, in the order in
+    /// which the fields are declared. The fields may be reordered in memory.
+    Arbitrary { offsets: Vec<u64> },
+}
+
+/// How the variants of a type are told apart.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VariantsShape {
+    /// The type is a struct, tuple or union, or an enum which has a single inhabited variant.
+    Single { index: VariantIdx },
+    /// The variants of an enum, told apart by its tag: the field `tag_field` of the enum.
+    Multiple { tag: Scalar, tag_encoding: TagEncoding, tag_field: usize, variants: Vec<Layout> },
+}
+
+/// How the discriminant of an enum is stored in its tag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TagEncoding {
+    /// The tag is the discriminant.
+    Direct,
+    /// The tag is a niche of the fields of the `untagged_variant`: the variants in
+    /// `niche_variants` are stored as `niche_start` plus the distance of their index to the start
+    /// of `niche_variants`, and any other value of the tag means the `untagged_variant`.
+    Niche {
+        untagged_variant: VariantIdx,
+        niche_variants: RangeInclusive<VariantIdx>,
+        niche_start: u128,
+    },
+}
+
+/// A range of invalid values for a scalar at a given offset of a type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Niche {
+    pub offset: u64,
+    pub value: Primitive,
+    /// The values which are valid for the scalar: the other values are the niche.
+    pub valid_range: WrappingRange,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scalar {
+    Initialized {
+        value: Primitive,
+        valid_range: WrappingRange,
+    },
+    /// A scalar of a union, which may be uninitialized or have any value.
+    Union {
+        value: Primitive,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Primitive {
+    /// An integer of `size` bytes.
+    Int {
+        size: u64,
+        signed: bool,
+    },
+    F32,
+    F64,
+    Pointer,
+}
+
+/// An inclusive range of values, which wraps around if `start` is greater than `end`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WrappingRange {
+    pub start: u128,
+    pub end: u128,
+}
+
+impl WrappingRange {
+    /// Returns whether `value` is in the range.
+    pub fn contains(&self, value: u128) -> bool {
+        if self.start <= self.end {
+            self.start <= value && value <= self.end
+        } else {
+            self.start <= value || value <= self.end
+        }
+    }
+}
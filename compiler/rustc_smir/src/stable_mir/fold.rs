@@ -4,8 +4,9 @@
 
 use super::ty::{
     Allocation, Binder, Const, ConstDef, ConstantKind, ExistentialPredicate, FnSig, GenericArgKind,
-    GenericArgs, Promoted, RigidTy, TermKind, Ty, TyKind, UnevaluatedConst,
+    GenericArgs, Promoted, Region, RigidTy, TermKind, TraitRef, Ty, TyKind, UnevaluatedConst,
 };
+use super::Error;
 
 pub trait Folder: Sized {
     type Break;
@@ -49,7 +50,11 @@ fn super_fold<V: Folder>(&self, folder: &mut V) -> ControlFlow<V::Break, Self> {
         match &mut this.literal {
             super::ty::ConstantKind::Allocated(alloc) => *alloc = alloc.fold(folder)?,
             super::ty::ConstantKind::Unevaluated(uv) => *uv = uv.fold(folder)?,
-            super::ty::ConstantKind::Param(_) => {}
+            super::ty::ConstantKind::Param(_)
+            | super::ty::ConstantKind::Bound(..)
+            | super::ty::ConstantKind::Placeholder(_)
+            | super::ty::ConstantKind::Expr
+            | super::ty::ConstantKind::Error => {}
         }
         this.ty = this.ty.fold(folder)?;
         ControlFlow::Continue(this)
@@ -62,6 +67,12 @@ fn super_fold<V: Folder>(&self, _folder: &mut V) -> ControlFlow<V::Break, Self>
     }
 }
 
+impl Foldable for Region {
+    fn super_fold<V: Folder>(&self, _folder: &mut V) -> ControlFlow<V::Break, Self> {
+        ControlFlow::Continue(self.clone())
+    }
+}
+
 impl Foldable for Allocation {
     fn super_fold<V: Folder>(&self, _folder: &mut V) -> ControlFlow<V::Break, Self> {
         ControlFlow::Continue(self.clone())
@@ -206,6 +217,12 @@ fn super_fold<V: Folder>(&self, folder: &mut V) -> ControlFlow<V::Break, Self> {
     }
 }
 
+impl Foldable for TraitRef {
+    fn super_fold<V: Folder>(&self, folder: &mut V) -> ControlFlow<V::Break, Self> {
+        ControlFlow::Continue(Self { def_id: self.def_id, args: self.args.fold(folder)? })
+    }
+}
+
 pub enum Never {}
 
 /// In order to instantiate a `Foldable`'s generic parameters with specific arguments,
@@ -228,3 +245,33 @@ fn fold_const(&mut self, c: &Const) -> ControlFlow<Self::Break, Const> {
         })
     }
 }
+
+/// Instantiates the generic parameters of a `Foldable` with the given arguments, see
+/// [`EarlyBinder::instantiate`](super::ty::EarlyBinder::instantiate). Unlike with `GenericArgs`,
+/// the types are instantiated by the compiler, so that they can still be used with the APIs which
+/// need types created by the compiler.
+pub(crate) struct Instantiate<'a>(pub(crate) &'a GenericArgs);
+
+impl Folder for Instantiate<'_> {
+    type Break = Error;
+
+    fn visit_ty(&mut self, ty: &Ty) -> ControlFlow<Self::Break, Ty> {
+        match ty.subst(self.0) {
+            Ok(ty) => ControlFlow::Continue(ty),
+            Err(error) => ControlFlow::Break(error),
+        }
+    }
+
+    fn fold_const(&mut self, c: &Const) -> ControlFlow<Self::Break, Const> {
+        let ConstantKind::Param(param) = &c.literal else {
+            return c.super_fold(self);
+        };
+        match self.0 .0.get(param.index as usize) {
+            Some(GenericArgKind::Const(arg)) => ControlFlow::Continue(arg.clone()),
+            _ => ControlFlow::Break(Error::new(format!(
+                "expected a constant argument for the generic parameter `{}`",
+                param.name
+            ))),
+        }
+    }
+}
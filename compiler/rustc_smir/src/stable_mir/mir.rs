@@ -1,3 +1,11 @@
 mod body;
+pub mod borrowck;
+pub mod dataflow;
+pub mod effects;
+pub mod mono;
+pub mod pattern;
+mod pretty;
+pub mod traversal;
+pub mod visit;
 
 pub use body::*;
@@ -1,13 +1,60 @@
-use crate::rustc_internal::Opaque;
+use crate::stable_mir::mir::traversal::{Dominators, Postorder, Preorder};
 use crate::stable_mir::ty::{
     AdtDef, ClosureDef, Const, GeneratorDef, GenericArgs, Movability, Region,
 };
-use crate::stable_mir::{self, ty::Ty, Span};
+use crate::stable_mir::{self, ty::Ty, Span, Symbol};
 
 #[derive(Clone, Debug)]
 pub struct Body {
     pub blocks: Vec<BasicBlock>,
+    /// The types of the locals: the return place `_0`, then the arguments, then the user
+    /// variables and the temporaries.
     pub locals: Vec<Ty>,
+    /// The number of arguments, which are the locals `_1` to `_{arg_count}`.
+    pub arg_count: usize,
+    /// Where the user variables can be found, which can be used to refer to places by the name
+    /// of the variable they belong to.
+    pub var_debug_info: Vec<VarDebugInfo>,
+    /// The span of the whole function or constant this body belongs to.
+    pub span: Span,
+}
+
+impl Body {
+    /// The predecessors of each block, in the order of the blocks. A block is only listed once
+    /// per predecessor, even if it is the target of several of its edges.
+    pub fn predecessors(&self) -> Vec<Vec<usize>> {
+        let mut predecessors: Vec<Vec<usize>> = vec![vec![]; self.blocks.len()];
+        for (index, block) in self.blocks.iter().enumerate() {
+            for successor in block.terminator.kind.successors() {
+                if predecessors[successor].last() != Some(&index) {
+                    predecessors[successor].push(index);
+                }
+            }
+        }
+        predecessors
+    }
+
+    pub fn dominators(&self) -> Dominators {
+        Dominators::new(self)
+    }
+
+    /// Visits the reachable blocks, each one before its successors except on the back edges.
+    pub fn preorder(&self) -> Preorder<'_> {
+        Preorder::new(self)
+    }
+
+    /// Visits the reachable blocks, each one after its successors except on the back edges.
+    pub fn postorder(&self) -> Postorder<'_> {
+        Postorder::new(self)
+    }
+
+    /// The reachable blocks in reverse postorder, in which each block comes after its
+    /// predecessors except on the back edges, which is the usual order of forward analyses.
+    pub fn reverse_postorder(&self) -> Vec<usize> {
+        let mut blocks: Vec<usize> = self.postorder().map(|(index, _)| index).collect();
+        blocks.reverse();
+        blocks
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -17,7 +64,13 @@ pub struct BasicBlock {
 }
 
 #[derive(Clone, Debug)]
-pub enum Terminator {
+pub struct Terminator {
+    pub kind: TerminatorKind,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug)]
+pub enum TerminatorKind {
     Goto {
         target: usize,
     },
@@ -49,24 +102,137 @@ pub enum Terminator {
         target: usize,
         unwind: UnwindAction,
     },
+    /// Suspends the generator, yielding `value`. Resumes at `resume` with the resume argument
+    /// written to `resume_arg`, or continues at `drop` if the generator is dropped.
+    Yield {
+        value: Operand,
+        resume: usize,
+        resume_arg: Place,
+        drop: Option<usize>,
+    },
     GeneratorDrop,
+    /// Continues at `real_target`. `imaginary_target` is an edge which is never taken, which only
+    /// exists for the borrow checker. This terminator is removed after borrow checking.
+    FalseEdge {
+        real_target: usize,
+        imaginary_target: usize,
+    },
+    /// Continues at `real_target`, with an edge to the unwind action which is never taken, which
+    /// only exists for the borrow checker, e.g. in loops. This terminator is removed after borrow
+    /// checking.
+    FalseUnwind {
+        real_target: usize,
+        unwind: UnwindAction,
+    },
     InlineAsm {
-        template: String,
+        template: Vec<InlineAsmTemplatePiece>,
         operands: Vec<InlineAsmOperand>,
-        options: String,
-        line_spans: String,
+        options: InlineAsmOptions,
+        line_spans: Vec<Span>,
         destination: Option<usize>,
         unwind: UnwindAction,
     },
 }
 
+impl TerminatorKind {
+    /// The blocks control can flow to after this terminator, including the unwind paths and the
+    /// edges which only exist for the borrow checker. A target may be repeated.
+    pub fn successors(&self) -> Vec<usize> {
+        let unwind = |unwind: &UnwindAction| match unwind {
+            UnwindAction::Cleanup(target) => Some(*target),
+            UnwindAction::Continue | UnwindAction::Unreachable | UnwindAction::Terminate => None,
+        };
+        match self {
+            TerminatorKind::Resume
+            | TerminatorKind::Abort
+            | TerminatorKind::Return
+            | TerminatorKind::Unreachable
+            | TerminatorKind::GeneratorDrop => vec![],
+            TerminatorKind::Goto { target } => vec![*target],
+            TerminatorKind::SwitchInt { targets, otherwise, .. } => {
+                targets.iter().map(|target| target.target).chain(Some(*otherwise)).collect()
+            }
+            TerminatorKind::Drop { target, unwind: action, .. }
+            | TerminatorKind::Assert { target, unwind: action, .. } => {
+                Some(*target).into_iter().chain(unwind(action)).collect()
+            }
+            TerminatorKind::Call { target, unwind: action, .. }
+            | TerminatorKind::InlineAsm { destination: target, unwind: action, .. } => {
+                target.iter().copied().chain(unwind(action)).collect()
+            }
+            TerminatorKind::Yield { resume, drop, .. } => {
+                Some(*resume).into_iter().chain(*drop).collect()
+            }
+            TerminatorKind::FalseEdge { real_target, imaginary_target } => {
+                vec![*real_target, *imaginary_target]
+            }
+            TerminatorKind::FalseUnwind { real_target, unwind: action } => {
+                Some(*real_target).into_iter().chain(unwind(action)).collect()
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum InlineAsmTemplatePiece {
+    String(String),
+    /// A placeholder for an operand, like `{0:e}`.
+    Placeholder {
+        operand_idx: usize,
+        modifier: Option<char>,
+        span: Span,
+    },
+}
+
+/// The options of an `asm!` block, like `options(nomem, nostack)`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InlineAsmOptions {
+    pub pure: bool,
+    pub nomem: bool,
+    pub readonly: bool,
+    pub preserves_flags: bool,
+    pub noreturn: bool,
+    pub nostack: bool,
+    pub att_syntax: bool,
+    pub raw: bool,
+    pub may_unwind: bool,
+}
+
 #[derive(Clone, Debug)]
-pub struct InlineAsmOperand {
-    pub in_value: Option<Operand>,
-    pub out_place: Option<Place>,
-    // This field has a raw debug representation of MIR's InlineAsmOperand.
-    // For now we care about place/operand + the rest in a debug format.
-    pub raw_rpr: String,
+pub enum InlineAsmOperand {
+    In {
+        reg: InlineAsmRegOrRegClass,
+        value: Operand,
+    },
+    Out {
+        reg: InlineAsmRegOrRegClass,
+        /// Whether the output may be allocated to the same register as an input.
+        late: bool,
+        place: Option<Place>,
+    },
+    InOut {
+        reg: InlineAsmRegOrRegClass,
+        late: bool,
+        in_value: Operand,
+        out_place: Option<Place>,
+    },
+    Const {
+        value: Constant,
+    },
+    SymFn {
+        value: Constant,
+    },
+    SymStatic {
+        def: stable_mir::CrateItem,
+    },
+}
+
+/// The register of an operand of an `asm!` block, like `"eax"`, or its register class, like
+/// `reg`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InlineAsmRegOrRegClass {
+    Reg(String),
+    RegClass(String),
 }
 
 #[derive(Clone, Debug)]
@@ -134,19 +300,62 @@ pub enum AsyncGeneratorKind {
     Fn,
 }
 
-pub(crate) type LocalDefId = Opaque;
-/// [`rustc_middle::mir::Coverage`] is heavily tied to internal details of the
-/// coverage implementation that are likely to change, and are unlikely to be
-/// useful to third-party tools for the foreseeable future.
-pub(crate) type Coverage = Opaque;
+/// A statement added by `-C instrument-coverage`.
+#[derive(Clone, Debug)]
+pub struct Coverage {
+    pub kind: CoverageKind,
+    /// The region of the source code this statement counts the executions of.
+    pub code_region: Option<CodeRegion>,
+}
+
+#[derive(Clone, Debug)]
+pub enum CoverageKind {
+    /// Increments the counter `id`.
+    Counter { function_source_hash: u64, id: CounterId },
+    /// Declares that the expression `id` is the sum or difference of two terms.
+    Expression { id: ExpressionId, lhs: CovTerm, op: CovOp, rhs: CovTerm },
+    /// Marks a code region which is never executed.
+    Unreachable,
+}
+
+pub type CounterId = usize;
+
+pub type ExpressionId = usize;
+
+/// A term of a coverage expression.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CovTerm {
+    Zero,
+    Counter(CounterId),
+    Expression(ExpressionId),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CovOp {
+    Add,
+    Subtract,
+}
+
+/// A region of a source file, with lines and columns starting at 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeRegion {
+    pub file_name: Symbol,
+    pub start_line: u32,
+    pub start_col: u32,
+    pub end_line: u32,
+    pub end_col: u32,
+}
 
 /// The FakeReadCause describes the type of pattern why a FakeRead statement exists.
+///
+/// The closures of `ForMatchedPlace` and `ForLet` are the closures whose body the statement
+/// is in, if any.
 #[derive(Clone, Debug)]
 pub enum FakeReadCause {
     ForMatchGuard,
-    ForMatchedPlace(LocalDefId),
+    ForMatchedPlace(Option<ClosureDef>),
     ForGuardBinding,
-    ForLet(LocalDefId),
+    ForLet(Option<ClosureDef>),
     ForIndex,
 }
 
@@ -181,7 +390,13 @@ pub enum NonDivergingIntrinsic {
 }
 
 #[derive(Clone, Debug)]
-pub enum Statement {
+pub struct Statement {
+    pub kind: StatementKind,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug)]
+pub enum StatementKind {
     Assign(Place, Rvalue),
     FakeRead(FakeReadCause, Place),
     SetDiscriminant { place: Place, variant_index: VariantIdx },
@@ -330,18 +545,47 @@ pub enum Operand {
 #[derive(Clone, Debug)]
 pub struct Place {
     pub local: Local,
-    pub projection: String,
+    /// The projections applied to `local`, from the outermost to the innermost, e.g. `(*_1).0`
+    /// is `[Deref, Field(0, _)]`.
+    pub projection: Vec<ProjectionElem>,
+}
+
+/// A projection of a place. The projections of the places of the body know the type of the
+/// fields and the locals used as indices, the ones of [`UserTypeProjection`] don't.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProjectionElem<V = Local, T = Ty> {
+    /// Dereferences the place, which must be a reference, a raw pointer or a `Box`.
+    Deref,
+    /// A field of a struct, a tuple, a union, a closure or of the variant of an enum selected
+    /// by a previous `Downcast`, with the type of the field.
+    Field(FieldIdx, T),
+    /// Indexes an array or a slice with the value of a local.
+    Index(V),
+    /// Indexes an array or a slice with a constant, like in the slice pattern `[_, x, ..]`. If
+    /// `from_end` is set, the index is `offset` elements from the end. The array or slice is at
+    /// least `min_length` elements long.
+    ConstantIndex { offset: u64, min_length: u64, from_end: bool },
+    /// Takes the subslice `from..to` of an array or a slice, like in the slice pattern
+    /// `[_, rest @ ..]`. If `from_end` is set, `to` is counted from the end.
+    Subslice { from: u64, to: u64, from_end: bool },
+    /// Selects a variant of an enum, whose fields can then be projected with `Field`.
+    Downcast(VariantIdx),
+    /// Casts an opaque type, like `impl Trait`, to its hidden type.
+    OpaqueCast(T),
 }
 
+/// A projection of a [`UserTypeProjection`].
+pub type ProjectionKind = ProjectionElem<(), ()>;
+
 #[derive(Clone, Debug)]
 pub struct UserTypeProjection {
     pub base: UserTypeAnnotationIndex,
-    pub projection: String,
+    pub projection: Vec<ProjectionKind>,
 }
 
 pub type Local = usize;
 
-type FieldIdx = usize;
+pub type FieldIdx = usize;
 
 /// The source-order index of a variant in a type.
 pub type VariantIdx = usize;
@@ -355,6 +599,44 @@ pub struct Constant {
     pub literal: Const,
 }
 
+/// Debug information pertaining to a user variable.
+#[derive(Clone, Debug)]
+pub struct VarDebugInfo {
+    /// The name of the variable.
+    pub name: Symbol,
+    pub source_info: SourceInfo,
+    /// If set, `value` only holds the part of the variable described by this fragment, and the
+    /// variable has several entries.
+    pub composite: Option<VarDebugInfoFragment>,
+    /// Where the data of the variable is to be found.
+    pub value: VarDebugInfoContents,
+    /// The argument number (starting from 1) if the variable is a function argument.
+    pub argument_index: Option<u16>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SourceInfo {
+    pub span: Span,
+    pub scope: SourceScope,
+}
+
+pub type SourceScope = usize;
+
+/// A part of a composite user variable.
+#[derive(Clone, Debug)]
+pub struct VarDebugInfoFragment {
+    /// The type of the whole variable.
+    pub ty: Ty,
+    /// The path to this fragment in the variable, as a list of fields and their types.
+    pub projection: Vec<(FieldIdx, Ty)>,
+}
+
+#[derive(Clone, Debug)]
+pub enum VarDebugInfoContents {
+    Place(Place),
+    Const(Constant),
+}
+
 #[derive(Clone, Debug)]
 pub struct SwitchTarget {
     pub value: u128,
@@ -0,0 +1,98 @@
+//! The results of the borrow checker for a body, in the form of the facts it gives to Polonius,
+//! so that verifiers can reuse the ownership analysis of rustc instead of re-deriving which
+//! places alias from the MIR.
+//!
+//! The facts are only kept for the bodies borrow checked while running the compiler with
+//! [`StableMir::with_borrowck_facts`], see [`CrateItem::borrowck_facts`].
+//!
+//! [`StableMir::with_borrowck_facts`]: crate::rustc_internal::StableMir::with_borrowck_facts
+//! [`CrateItem::borrowck_facts`]: crate::stable_mir::CrateItem::borrowck_facts
+
+use super::{Body, BorrowKind, Local, Place};
+
+/// The index of a region in the facts of a body, which Polonius calls an origin.
+pub type RegionId = usize;
+
+/// The index of a loan in [`BorrowckFacts::loans`].
+pub type LoanId = usize;
+
+/// The index of a move path in [`BorrowckFacts::move_paths`].
+pub type MovePathId = usize;
+
+/// A point in the control-flow graph of [`BorrowckFacts::body`]. Each statement and terminator
+/// has two points: its start, and its middle, where its effects take place.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub block: usize,
+    /// The index of the statement in the block. The terminator comes after the last statement.
+    pub statement_index: usize,
+    /// Whether this is the middle of the statement rather than its start.
+    pub mid: bool,
+}
+
+/// The borrow checking facts of a body.
+#[derive(Clone, Debug)]
+pub struct BorrowckFacts {
+    /// The body which was borrow checked, whose blocks and statements the points refer to. This
+    /// body is built before the optimizations, so it differs from [`CrateItem::body`].
+    ///
+    /// [`CrateItem::body`]: crate::stable_mir::CrateItem::body
+    pub body: Body,
+    /// The edges of the control-flow graph between points (`cfg_edge`).
+    pub cfg_edges: Vec<(Point, Point)>,
+    /// The regions which are parameters of the body, like the lifetimes of the arguments of a
+    /// function (`universal_region`).
+    pub universal_regions: Vec<RegionId>,
+    /// The borrows of the body.
+    pub loans: Vec<Loan>,
+    /// The constraints between regions found by type checking the body (`subset_base`).
+    pub region_constraints: Vec<RegionConstraint>,
+    /// The paths which are tracked for moves and initialization: locals and their fields.
+    pub move_paths: Vec<MovePath>,
+}
+
+/// A borrow of a place, created at a single point.
+#[derive(Clone, Debug)]
+pub struct Loan {
+    pub kind: BorrowKind,
+    /// The place which is borrowed.
+    pub borrowed_place: Place,
+    /// The place the reference is stored to.
+    pub assigned_place: Place,
+    /// The region of the reference.
+    pub region: RegionId,
+    /// Where the loan is created (`loan_issued_at`).
+    pub issued_at: Point,
+    /// Where the loan stops being live according to the region inference of rustc, which ends
+    /// the range of points where it can be invalidated.
+    pub out_of_scope_at: Vec<Point>,
+    /// Where the borrowed place is overwritten, which ends the loan (`loan_killed_at`).
+    pub killed_at: Vec<Point>,
+    /// Where an access to the borrowed place conflicts with the loan, which is an error if the
+    /// loan is live there (`loan_invalidated_at`).
+    pub invalidated_at: Vec<Point>,
+}
+
+/// The region `sub` must be included in `sup` at a point (`subset_base`), which comes from a
+/// `'sup: 'sub` constraint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegionConstraint {
+    pub sub: RegionId,
+    pub sup: RegionId,
+    pub point: Point,
+}
+
+/// A local or a field of a path, whose initialization is tracked.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MovePath {
+    /// The path this path is a field of (`child_path`).
+    pub parent: Option<MovePathId>,
+    /// The local of this path, if it is one (`path_is_var`).
+    pub local: Option<Local>,
+    /// Where the path is assigned (`path_assigned_at_base`).
+    pub assigned_at: Vec<Point>,
+    /// Where the path is moved out of (`path_moved_at_base`).
+    pub moved_at: Vec<Point>,
+    /// Where the path is accessed (`path_accessed_at_base`).
+    pub accessed_at: Vec<Point>,
+}
@@ -0,0 +1,398 @@
+//! A dataflow framework for the bodies of stable MIR, a minimal version of the one of rustc.
+//!
+//! An analysis gives the state at the start of the body and the effect of each statement and
+//! terminator on the state, and the framework iterates over the blocks until the state at the
+//! entry of each block reaches a fixpoint. The states are joined where control flow merges, so
+//! they must form a [`JoinSemiLattice`] whose height is finite.
+//!
+//! Most analyses are gen/kill problems over a set of indices, like the locals. They implement
+//! [`GenKillAnalysis`], for which the effect of each whole block is computed once before the
+//! fixpoint iteration. The other ones implement [`Analysis`] directly. For example, the locals
+//! which are live at a location can be found with the provided [`MaybeLiveLocals`]:
+//!
+//! ```ignore (needs a compiler context)
+//! use rustc_smir::stable_mir::mir::dataflow::{Analysis, MaybeLiveLocals};
+//!
+//! let mut results = MaybeLiveLocals.iterate_to_fixpoint(&body);
+//! let live: Vec<Local> = results.state_before(&body, location).iter().collect();
+//! ```
+
+mod liveness;
+
+pub use liveness::MaybeLiveLocals;
+
+use super::visit::Location;
+use super::{BasicBlock, Body, Statement, Terminator};
+use std::collections::VecDeque;
+
+/// A set of indices below its domain size.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitSet {
+    domain_size: usize,
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new_empty(domain_size: usize) -> BitSet {
+        BitSet { domain_size, words: vec![0; (domain_size + 63) / 64] }
+    }
+
+    pub fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+
+    pub fn contains(&self, elem: usize) -> bool {
+        assert!(elem < self.domain_size);
+        self.words[elem / 64] & (1 << (elem % 64)) != 0
+    }
+
+    /// Inserts `elem`, returning whether the set changed.
+    pub fn insert(&mut self, elem: usize) -> bool {
+        assert!(elem < self.domain_size);
+        let word = &mut self.words[elem / 64];
+        let old = *word;
+        *word |= 1 << (elem % 64);
+        *word != old
+    }
+
+    /// Removes `elem`, returning whether the set changed.
+    pub fn remove(&mut self, elem: usize) -> bool {
+        assert!(elem < self.domain_size);
+        let word = &mut self.words[elem / 64];
+        let old = *word;
+        *word &= !(1 << (elem % 64));
+        *word != old
+    }
+
+    /// Adds the elements of `other`, returning whether the set changed.
+    pub fn union(&mut self, other: &BitSet) -> bool {
+        self.update_words(other, |word, other| word | other)
+    }
+
+    /// Removes the elements of `other`, returning whether the set changed.
+    pub fn subtract(&mut self, other: &BitSet) -> bool {
+        self.update_words(other, |word, other| word & !other)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+
+    /// The elements of the set, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.domain_size).filter(|elem| self.contains(*elem))
+    }
+
+    fn update_words(&mut self, other: &BitSet, op: impl Fn(u64, u64) -> u64) -> bool {
+        assert_eq!(self.domain_size, other.domain_size);
+        let mut changed = false;
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            let new = op(*word, *other);
+            changed |= new != *word;
+            *word = new;
+        }
+        changed
+    }
+}
+
+/// The states of a dataflow analysis, where `join` computes the least upper bound of two states.
+pub trait JoinSemiLattice: Clone + Eq {
+    /// Joins `other` into `self`, returning whether `self` changed.
+    fn join(&mut self, other: &Self) -> bool;
+}
+
+/// Sets are joined by union, so the states can only grow during the fixpoint iteration.
+impl JoinSemiLattice for BitSet {
+    fn join(&mut self, other: &Self) -> bool {
+        self.union(other)
+    }
+}
+
+/// The direction in which the states flow through the control-flow graph.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// From the start of the body to its returns, like for the initialized locals.
+    Forward,
+    /// From the returns of the body to its start, like for the live locals.
+    Backward,
+}
+
+/// The domain of an analysis, shared by [`Analysis`] and [`GenKillAnalysis`].
+pub trait AnalysisDomain {
+    type Domain: JoinSemiLattice;
+
+    const DIRECTION: Direction;
+
+    /// The state at the entry of each block before the fixpoint iteration, which must be the
+    /// bottom of the lattice, e.g. the empty set.
+    fn bottom_value(&self, body: &Body) -> Self::Domain;
+
+    /// Sets the state at the start of the body, e.g. to mark the arguments as initialized. This
+    /// is only used by forward analyses.
+    fn initialize_start_block(&self, _body: &Body, _state: &mut Self::Domain) {}
+}
+
+/// A dataflow analysis, which gives the effect of each statement and terminator on the state.
+pub trait Analysis: AnalysisDomain {
+    fn apply_statement_effect(
+        &mut self,
+        state: &mut Self::Domain,
+        statement: &Statement,
+        location: Location,
+    );
+
+    /// Applies the effect of a terminator, which is the same on all its outgoing edges.
+    fn apply_terminator_effect(
+        &mut self,
+        state: &mut Self::Domain,
+        terminator: &Terminator,
+        location: Location,
+    );
+
+    /// Computes the state at the entry of each block of `body`.
+    fn iterate_to_fixpoint(self, body: &Body) -> Results<Self>
+    where
+        Self: Sized,
+    {
+        Engine { body, analysis: self, block_effects: None }.iterate_to_fixpoint()
+    }
+}
+
+/// A set which can be updated by adding (generating) and removing (killing) elements.
+pub trait GenKill {
+    fn gen(&mut self, elem: usize);
+
+    fn kill(&mut self, elem: usize);
+}
+
+impl GenKill for BitSet {
+    fn gen(&mut self, elem: usize) {
+        self.insert(elem);
+    }
+
+    fn kill(&mut self, elem: usize) {
+        self.remove(elem);
+    }
+}
+
+/// The combined effect of a sequence of statements on a [`BitSet`]: the elements they generate
+/// and the ones they kill, which are disjoint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GenKillSet {
+    gen: BitSet,
+    kill: BitSet,
+}
+
+impl GenKillSet {
+    /// The effect of no statement.
+    pub fn identity(domain_size: usize) -> GenKillSet {
+        GenKillSet { gen: BitSet::new_empty(domain_size), kill: BitSet::new_empty(domain_size) }
+    }
+
+    pub fn apply(&self, state: &mut BitSet) {
+        state.union(&self.gen);
+        state.subtract(&self.kill);
+    }
+}
+
+impl GenKill for GenKillSet {
+    fn gen(&mut self, elem: usize) {
+        self.gen.insert(elem);
+        self.kill.remove(elem);
+    }
+
+    fn kill(&mut self, elem: usize) {
+        self.kill.insert(elem);
+        self.gen.remove(elem);
+    }
+}
+
+/// An analysis whose statements and terminators add and remove elements of a set, independently
+/// of the elements of the set. Each gen/kill analysis is an [`Analysis`].
+pub trait GenKillAnalysis: AnalysisDomain<Domain = BitSet> {
+    fn statement_effect(
+        &mut self,
+        trans: &mut impl GenKill,
+        statement: &Statement,
+        location: Location,
+    );
+
+    fn terminator_effect(
+        &mut self,
+        trans: &mut impl GenKill,
+        terminator: &Terminator,
+        location: Location,
+    );
+}
+
+impl<A: GenKillAnalysis> Analysis for A {
+    fn apply_statement_effect(
+        &mut self,
+        state: &mut BitSet,
+        statement: &Statement,
+        location: Location,
+    ) {
+        self.statement_effect(state, statement, location);
+    }
+
+    fn apply_terminator_effect(
+        &mut self,
+        state: &mut BitSet,
+        terminator: &Terminator,
+        location: Location,
+    ) {
+        self.terminator_effect(state, terminator, location);
+    }
+
+    /// Computes the effect of each block once, instead of applying the effect of each of its
+    /// statements every time the block is visited.
+    fn iterate_to_fixpoint(mut self, body: &Body) -> Results<Self> {
+        let domain_size = self.bottom_value(body).domain_size();
+        let mut block_effects = vec![GenKillSet::identity(domain_size); body.blocks.len()];
+        for (index, block) in body.blocks.iter().enumerate() {
+            let trans = &mut block_effects[index];
+            for_each_location::<A>(block, index, |location| {
+                match block.statements.get(location.statement_index) {
+                    Some(statement) => self.statement_effect(trans, statement, location),
+                    None => self.terminator_effect(trans, &block.terminator, location),
+                }
+            });
+        }
+        let block_effects: Box<dyn Fn(usize, &mut BitSet)> =
+            Box::new(move |index, state| block_effects[index].apply(state));
+        Engine { body, analysis: self, block_effects: Some(block_effects) }.iterate_to_fixpoint()
+    }
+}
+
+/// The fixpoint of an analysis over a body.
+pub struct Results<A: Analysis> {
+    pub analysis: A,
+    /// The state at the entry of each block in the direction of the analysis.
+    entry_sets: Vec<A::Domain>,
+}
+
+impl<A: Analysis> Results<A> {
+    /// The state at the entry of `block` in the direction of the analysis: before its first
+    /// statement for forward analyses, after its terminator for backward ones.
+    pub fn entry_set(&self, block: usize) -> &A::Domain {
+        &self.entry_sets[block]
+    }
+
+    /// The state before the statement or terminator at `location` is executed.
+    pub fn state_before(&mut self, body: &Body, location: Location) -> A::Domain {
+        self.state_at(body, location, false)
+    }
+
+    /// The state after the statement or terminator at `location` is executed.
+    pub fn state_after(&mut self, body: &Body, location: Location) -> A::Domain {
+        self.state_at(body, location, true)
+    }
+
+    fn state_at(&mut self, body: &Body, location: Location, after: bool) -> A::Domain {
+        let block = &body.blocks[location.block];
+        let mut state = self.entry_sets[location.block].clone();
+        // The effects between the entry of the block and `location`, in the order they apply.
+        let statement_indices = match A::DIRECTION {
+            Direction::Forward if after => 0..location.statement_index + 1,
+            Direction::Forward => 0..location.statement_index,
+            Direction::Backward if after => {
+                location.statement_index + 1..block.statements.len() + 1
+            }
+            Direction::Backward => location.statement_index..block.statements.len() + 1,
+        };
+        for_each_location::<A>(block, location.block, |effect_location| {
+            if statement_indices.contains(&effect_location.statement_index) {
+                apply_effect(&mut self.analysis, &mut state, block, effect_location);
+            }
+        });
+        state
+    }
+}
+
+struct Engine<'a, A: Analysis> {
+    body: &'a Body,
+    analysis: A,
+    /// The effect of each whole block, if it is known in advance.
+    block_effects: Option<Box<dyn Fn(usize, &mut A::Domain)>>,
+}
+
+impl<A: Analysis> Engine<'_, A> {
+    fn iterate_to_fixpoint(mut self) -> Results<A> {
+        let blocks = &self.body.blocks;
+        let bottom = self.analysis.bottom_value(self.body);
+        let mut entry_sets = vec![bottom; blocks.len()];
+        let predecessors = self.body.predecessors();
+
+        // Visiting the blocks in the direction of the analysis first reaches the fixpoint faster.
+        // The blocks which aren't reachable keep the bottom value.
+        let mut reverse_postorder = self.body.reverse_postorder();
+        let mut worklist: VecDeque<usize> = match A::DIRECTION {
+            Direction::Forward => {
+                if let Some(start) = entry_sets.first_mut() {
+                    self.analysis.initialize_start_block(self.body, start);
+                }
+                reverse_postorder.into()
+            }
+            Direction::Backward => {
+                reverse_postorder.reverse();
+                reverse_postorder.into()
+            }
+        };
+        let mut in_worklist = vec![false; blocks.len()];
+        for index in &worklist {
+            in_worklist[*index] = true;
+        }
+
+        while let Some(index) = worklist.pop_front() {
+            in_worklist[index] = false;
+            let block = &blocks[index];
+            let mut state = entry_sets[index].clone();
+            match &self.block_effects {
+                Some(block_effects) => block_effects(index, &mut state),
+                None => for_each_location::<A>(block, index, |location| {
+                    apply_effect(&mut self.analysis, &mut state, block, location);
+                }),
+            }
+
+            let next = match A::DIRECTION {
+                Direction::Forward => block.terminator.kind.successors(),
+                Direction::Backward => predecessors[index].clone(),
+            };
+            for next in next {
+                if entry_sets[next].join(&state) && !in_worklist[next] {
+                    in_worklist[next] = true;
+                    worklist.push_back(next);
+                }
+            }
+        }
+
+        Results { analysis: self.analysis, entry_sets }
+    }
+}
+
+/// Calls `f` with the location of each statement of `block` and of its terminator, in the
+/// direction of the analysis.
+fn for_each_location<A: AnalysisDomain>(
+    block: &BasicBlock,
+    index: usize,
+    mut f: impl FnMut(Location),
+) {
+    let locations = (0..=block.statements.len())
+        .map(|statement_index| Location { block: index, statement_index });
+    match A::DIRECTION {
+        Direction::Forward => locations.for_each(&mut f),
+        Direction::Backward => locations.rev().for_each(&mut f),
+    }
+}
+
+fn apply_effect<A: Analysis>(
+    analysis: &mut A,
+    state: &mut A::Domain,
+    block: &BasicBlock,
+    location: Location,
+) {
+    match block.statements.get(location.statement_index) {
+        Some(statement) => analysis.apply_statement_effect(state, statement, location),
+        None => analysis.apply_terminator_effect(state, &block.terminator, location),
+    }
+}
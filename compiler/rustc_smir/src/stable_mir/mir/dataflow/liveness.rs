@@ -0,0 +1,124 @@
+use super::{AnalysisDomain, BitSet, Direction, GenKill, GenKillAnalysis};
+use crate::stable_mir::mir::visit::{Location, MirVisitor, PlaceContext};
+use crate::stable_mir::mir::{
+    Body, InlineAsmOperand, Local, Place, ProjectionElem, Statement, StatementKind, Terminator,
+    TerminatorKind,
+};
+
+/// The locals which may be read later before being overwritten, like the liveness analysis of
+/// rustc.
+///
+/// A local is used by any statement reading it, borrowing it, dropping it, or writing through it
+/// when it is a pointer, and it is defined by the assignments of the whole local and by
+/// `StorageLive` and `StorageDead`. Assigning to a field of a local neither uses nor defines
+/// it. The destination of a call is defined before its arguments are used, on all the outgoing
+/// edges of the call.
+pub struct MaybeLiveLocals;
+
+impl AnalysisDomain for MaybeLiveLocals {
+    type Domain = BitSet;
+
+    const DIRECTION: Direction = Direction::Backward;
+
+    fn bottom_value(&self, body: &Body) -> BitSet {
+        BitSet::new_empty(body.locals.len())
+    }
+}
+
+impl GenKillAnalysis for MaybeLiveLocals {
+    fn statement_effect(
+        &mut self,
+        trans: &mut impl GenKill,
+        statement: &Statement,
+        location: Location,
+    ) {
+        TransferFunction(trans).visit_statement(statement, location);
+    }
+
+    fn terminator_effect(
+        &mut self,
+        trans: &mut impl GenKill,
+        terminator: &Terminator,
+        location: Location,
+    ) {
+        TransferFunction(trans).visit_terminator(terminator, location);
+    }
+}
+
+/// Applies the effect of a statement in the backward order: the locals it defines are killed
+/// before the ones it uses are generated.
+struct TransferFunction<'a, T>(&'a mut T);
+
+impl<T: GenKill> TransferFunction<'_, T> {
+    fn visit_def(&mut self, place: &Place, location: Location) {
+        if place.projection.is_empty() {
+            self.0.kill(place.local);
+        } else if place.projection.contains(&ProjectionElem::Deref) {
+            self.visit_place(place, PlaceContext::MutatingUse, location);
+        } else {
+            for elem in &place.projection {
+                self.visit_projection_elem(elem, PlaceContext::MutatingUse, location);
+            }
+        }
+    }
+}
+
+impl<T: GenKill> MirVisitor for TransferFunction<'_, T> {
+    fn visit_statement(&mut self, statement: &Statement, location: Location) {
+        match &statement.kind {
+            StatementKind::Assign(place, rvalue) => {
+                self.visit_def(place, location);
+                self.visit_rvalue(rvalue, location);
+            }
+            StatementKind::StorageLive(local) | StatementKind::StorageDead(local) => {
+                self.0.kill(*local);
+            }
+            _ => self.super_statement(statement, location),
+        }
+    }
+
+    fn visit_terminator(&mut self, terminator: &Terminator, location: Location) {
+        match &terminator.kind {
+            TerminatorKind::Call { func, args, destination, .. } => {
+                self.visit_def(destination, location);
+                self.visit_operand(func, location);
+                for arg in args {
+                    self.visit_operand(arg, location);
+                }
+            }
+            TerminatorKind::Yield { value, resume_arg, .. } => {
+                self.visit_def(resume_arg, location);
+                self.visit_operand(value, location);
+            }
+            TerminatorKind::InlineAsm { operands, .. } => {
+                for operand in operands {
+                    match operand {
+                        InlineAsmOperand::Out { place: Some(place), .. }
+                        | InlineAsmOperand::InOut { out_place: Some(place), .. } => {
+                            self.visit_def(place, location);
+                        }
+                        _ => {}
+                    }
+                }
+                for operand in operands {
+                    match operand {
+                        InlineAsmOperand::In { value, .. }
+                        | InlineAsmOperand::InOut { in_value: value, .. } => {
+                            self.visit_operand(value, location);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            // The return place is read when returning.
+            TerminatorKind::Return => self.0.gen(0),
+            _ => self.super_terminator(terminator, location),
+        }
+    }
+
+    fn visit_local(&mut self, local: &Local, ptx: PlaceContext, _location: Location) {
+        if ptx != PlaceContext::NonUse {
+            self.0.gen(*local);
+        }
+    }
+}
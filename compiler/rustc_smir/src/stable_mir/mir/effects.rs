@@ -0,0 +1,287 @@
+//! A conservative summary of the side effects of a function, computed over the bodies of the
+//! function and of all the functions it may call, see [`Instance::effect_summary`].
+//!
+//! The call graph is built from the monomorphized bodies: each call to a function item is
+//! resolved to the instance it runs, and each `drop` to the drop glue of the dropped type. The
+//! effects of each function are then propagated to its callers until they reach a fixpoint, so
+//! recursive functions are supported. The code which can't be resolved this way is unknown, and
+//! is assumed to have all the effects:
+//!
+//! - calls through function pointers and trait objects;
+//! - foreign functions, and the functions of other crates whose MIR isn't available, which
+//!   includes most of the functions of the standard library which are neither generic nor
+//!   `#[inline]`;
+//! - the intrinsics calling the functions they are given, like `try`.
+//!
+//! The other intrinsics don't have any effect, and the functions of the global allocator only
+//! allocate.
+
+use std::collections::HashMap;
+
+use super::mono::{Instance, InstanceKind};
+use super::visit::{Location, MirVisitor};
+use super::{
+    Body, InlineAsmOperand, Operand, Place, ProjectionElem, Rvalue, Terminator, TerminatorKind,
+    UnwindAction,
+};
+use crate::stable_mir::ty::{Const, ConstantKind, RigidTy, Ty, TyKind};
+use crate::stable_mir::{with, CrateItem};
+
+/// The side effects a function may have, including the ones of the functions it calls.
+///
+/// Each flag is set when the effect may happen, so an unset flag means that the effect can't
+/// happen, while a set flag may be caused by code which is never run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EffectSummary {
+    /// Whether the function may read a static or a thread local, which is assumed as soon as
+    /// the function refers to it.
+    pub reads_globals: bool,
+    /// Whether the function may write to a static or a thread local, which is assumed as soon as
+    /// the function refers to a `static mut` or to a static with interior mutability.
+    pub writes_globals: bool,
+    /// Whether the function may call code whose body isn't known, in which case all the other
+    /// flags are set.
+    pub calls_unknown: bool,
+    /// Whether the function may unwind, i.e. panic without catching the panic. A panic which
+    /// aborts, like one in a function which can't unwind, doesn't count.
+    pub may_unwind: bool,
+    /// Whether the function may allocate memory with the global allocator.
+    pub may_allocate: bool,
+}
+
+impl EffectSummary {
+    /// The effects of unknown code.
+    const UNKNOWN: EffectSummary = EffectSummary {
+        reads_globals: true,
+        writes_globals: true,
+        calls_unknown: true,
+        may_unwind: true,
+        may_allocate: true,
+    };
+
+    /// Adds the effects of `other`, returning whether `self` changed.
+    fn join(&mut self, other: EffectSummary) -> bool {
+        let joined = EffectSummary {
+            reads_globals: self.reads_globals || other.reads_globals,
+            writes_globals: self.writes_globals || other.writes_globals,
+            calls_unknown: self.calls_unknown || other.calls_unknown,
+            may_unwind: self.may_unwind || other.may_unwind,
+            may_allocate: self.may_allocate || other.may_allocate,
+        };
+        let changed = joined != *self;
+        *self = joined;
+        changed
+    }
+}
+
+/// A function of the call graph.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Node {
+    Instance(Instance),
+    /// The drop glue of a type, which is run by the `drop` terminators.
+    DropGlue(Ty),
+}
+
+/// The maximum length of the chains of calls which are followed from the summarized function,
+/// like the default recursion limit of the monomorphization collector. A generic function calling
+/// itself with ever larger type arguments, e.g. `f::<T>` calling `f::<Vec<T>>`, has an infinite
+/// call graph, whose summary is unknown.
+const MAX_CALL_DEPTH: usize = 128;
+
+/// Computes the summary of the effects of `instance` and of the functions it may call.
+pub(super) fn summarize(instance: Instance) -> EffectSummary {
+    // The nodes of the call graph, with the length of the shortest chain of calls to them.
+    let mut nodes = vec![(Node::Instance(instance), 0)];
+    // The index of each node in `nodes`.
+    let mut indices = HashMap::from([(Node::Instance(instance), 0)]);
+    let mut summaries = Vec::new();
+    // The callees of each node, and whether their panics unwind into the caller.
+    let mut calls: Vec<Vec<(usize, bool)>> = Vec::new();
+    while let Some(&(node, depth)) = nodes.get(summaries.len()) {
+        let (summary, callees) = local_effects(node);
+        if depth == MAX_CALL_DEPTH && !callees.is_empty() {
+            return EffectSummary::UNKNOWN;
+        }
+        let callees = callees
+            .into_iter()
+            .map(|(callee, unwinds)| {
+                let index = *indices.entry(callee).or_insert_with(|| {
+                    nodes.push((callee, depth + 1));
+                    nodes.len() - 1
+                });
+                (index, unwinds)
+            })
+            .collect();
+        summaries.push(summary);
+        calls.push(callees);
+    }
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (caller, callees) in calls.iter().enumerate() {
+            for &(callee, unwinds) in callees {
+                let mut effects = summaries[callee];
+                effects.may_unwind &= unwinds;
+                changed |= summaries[caller].join(effects);
+            }
+        }
+    }
+    summaries[0]
+}
+
+/// The effects of the body of a function without the ones of its callees, and its callees.
+fn local_effects(node: Node) -> (EffectSummary, Vec<(Node, bool)>) {
+    let body = match node {
+        Node::Instance(instance) => {
+            if let Some(summary) = builtin_effects(instance) {
+                return (summary, vec![]);
+            }
+            instance.body()
+        }
+        Node::DropGlue(ty) => match ty.drop_glue_body() {
+            Ok(Some(body)) => Ok(body),
+            // The type doesn't need to be dropped.
+            Ok(None) => return (EffectSummary::default(), vec![]),
+            Err(error) => Err(error),
+        },
+    };
+    let Ok(body) = body else {
+        return (EffectSummary::UNKNOWN, vec![]);
+    };
+    let mut collector =
+        EffectCollector { body: &body, summary: EffectSummary::default(), callees: vec![] };
+    collector.visit_body(&body);
+    (collector.summary, collector.callees)
+}
+
+/// The effects of the functions without a body whose effects are known.
+fn builtin_effects(instance: Instance) -> Option<EffectSummary> {
+    let TyKind::RigidTy(RigidTy::FnDef(def, _)) = instance.ty().kind() else {
+        return None;
+    };
+    let path = def.name();
+    let name = path.rsplit("::").next().unwrap();
+    match (instance.kind, name) {
+        // These intrinsics call the functions they are given.
+        (InstanceKind::Intrinsic, "try" | "r#try" | "const_eval_select") => None,
+        (InstanceKind::Intrinsic, _) => Some(EffectSummary::default()),
+        (_, "__rust_alloc" | "__rust_alloc_zeroed" | "__rust_realloc") => {
+            Some(EffectSummary { may_allocate: true, ..EffectSummary::default() })
+        }
+        (_, "__rust_dealloc") => Some(EffectSummary::default()),
+        _ => None,
+    }
+}
+
+/// Whether a panic of the code run by a terminator unwinds into the function.
+fn unwinds(unwind: &UnwindAction) -> bool {
+    match unwind {
+        UnwindAction::Continue | UnwindAction::Cleanup(_) => true,
+        UnwindAction::Unreachable | UnwindAction::Terminate => false,
+    }
+}
+
+/// The type of a place, if its projections can be followed without the help of the compiler.
+fn place_ty(body: &Body, place: &Place) -> Option<Ty> {
+    place.projection.iter().try_fold(body.locals[place.local], |ty, elem| match (elem, ty.kind()) {
+        (
+            ProjectionElem::Deref,
+            TyKind::RigidTy(RigidTy::Ref(_, pointee, _) | RigidTy::RawPtr(pointee, _)),
+        ) => Some(pointee),
+        (ProjectionElem::Field(_, field_ty) | ProjectionElem::OpaqueCast(field_ty), _) => {
+            Some(*field_ty)
+        }
+        (
+            ProjectionElem::Index(_) | ProjectionElem::ConstantIndex { .. },
+            TyKind::RigidTy(RigidTy::Array(elem, _) | RigidTy::Slice(elem)),
+        ) => Some(elem),
+        (ProjectionElem::Subslice { .. }, TyKind::RigidTy(RigidTy::Slice(_)))
+        | (ProjectionElem::Downcast(_), _) => Some(ty),
+        _ => None,
+    })
+}
+
+/// Collects the effects and the callees of a body.
+struct EffectCollector<'a> {
+    body: &'a Body,
+    summary: EffectSummary,
+    callees: Vec<(Node, bool)>,
+}
+
+impl EffectCollector<'_> {
+    fn unknown(&mut self, unwinds: bool) {
+        self.summary.join(EffectSummary { may_unwind: unwinds, ..EffectSummary::UNKNOWN });
+    }
+
+    fn refer_to_static(&mut self, item: CrateItem) {
+        self.summary.reads_globals = true;
+        if with(|cx| cx.static_is_mutable(item.0)) {
+            self.summary.writes_globals = true;
+        }
+    }
+
+    fn call(&mut self, func: &Operand, unwinds: bool) {
+        let func_ty = match func {
+            Operand::Constant(constant) => Some(constant.literal.ty),
+            Operand::Copy(place) | Operand::Move(place) => place_ty(self.body, place),
+        };
+        let Some(TyKind::RigidTy(RigidTy::FnDef(def, args))) = func_ty.map(|ty| ty.kind()) else {
+            // A function pointer.
+            return self.unknown(unwinds);
+        };
+        match Instance::resolve(def, &args) {
+            Ok(instance) => self.callees.push((Node::Instance(instance), unwinds)),
+            Err(_) => self.unknown(unwinds),
+        }
+    }
+}
+
+impl MirVisitor for EffectCollector<'_> {
+    fn visit_terminator(&mut self, terminator: &Terminator, location: Location) {
+        match &terminator.kind {
+            TerminatorKind::Call { func, unwind, .. } => self.call(func, unwinds(unwind)),
+            TerminatorKind::Drop { place, unwind, .. } => match place_ty(self.body, place) {
+                Some(ty) => self.callees.push((Node::DropGlue(ty), unwinds(unwind))),
+                None => self.unknown(unwinds(unwind)),
+            },
+            // A failed assertion panics.
+            TerminatorKind::Assert { unwind, .. } => self.summary.may_unwind |= unwinds(unwind),
+            TerminatorKind::InlineAsm { operands, options, unwind, .. } => {
+                if !options.nomem {
+                    self.summary.reads_globals = true;
+                    self.summary.writes_globals |= !options.readonly;
+                }
+                self.summary.may_unwind |= options.may_unwind && unwinds(unwind);
+                for operand in operands {
+                    match operand {
+                        InlineAsmOperand::SymFn { .. } => self.unknown(unwinds(unwind)),
+                        InlineAsmOperand::SymStatic { def } => self.refer_to_static(*def),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+        self.super_terminator(terminator, location)
+    }
+
+    fn visit_rvalue(&mut self, rvalue: &Rvalue, location: Location) {
+        if let Rvalue::ThreadLocalRef(item) = rvalue {
+            self.refer_to_static(*item);
+        }
+        self.super_rvalue(rvalue, location)
+    }
+
+    fn visit_const(&mut self, constant: &Const, location: Location) {
+        // The statics are referred to by constants pointing to their memory.
+        if let ConstantKind::Allocated(allocation) = &constant.literal {
+            for (_, prov) in &allocation.provenance.ptrs {
+                if let Some(item) = with(|cx| cx.alloc_static(prov.0)) {
+                    self.refer_to_static(item);
+                }
+            }
+        }
+        self.super_const(constant, location)
+    }
+}
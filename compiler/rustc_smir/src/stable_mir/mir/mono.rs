@@ -0,0 +1,68 @@
+//! Monomorphized items: functions and shims instantiated with concrete generic arguments, as they
+//! are code generated.
+
+use crate::stable_mir::abi::FnAbi;
+use crate::stable_mir::mir::effects::{self, EffectSummary};
+use crate::stable_mir::mir::Body;
+use crate::stable_mir::ty::{FnDef, GenericArgs, Ty};
+use crate::stable_mir::{with, Error};
+
+/// A function or a shim instantiated with concrete generic arguments.
+///
+/// Unlike the bodies of [`CrateItem`](crate::stable_mir::CrateItem)s, which are polymorphic, the
+/// body of an instance only refers to concrete types, so its layouts and ABI are known.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Instance {
+    pub kind: InstanceKind,
+    pub def: InstanceDef,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InstanceKind {
+    /// A function with a body, like a user-defined function or a closure.
+    Item,
+    /// A compiler intrinsic, which doesn't have a body.
+    Intrinsic,
+    /// A call to a method of a trait object, through its vtable. It doesn't have a body.
+    Virtual,
+    /// A body generated by the compiler, like drop glue or the shim of a function pointer.
+    Shim,
+}
+
+/// A handle to an instance, which is only valid during the run of the compiler which produced it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct InstanceDef(pub(crate) usize);
+
+impl Instance {
+    /// Resolves the function `def` instantiated with `args` to the instance which is called,
+    /// e.g. to the method of the implementation of a trait for a call to a trait method.
+    ///
+    /// This fails if the arguments depend on generic parameters or if the function can't be
+    /// resolved, like a trait method for a type which doesn't implement the trait.
+    pub fn resolve(def: FnDef, args: &GenericArgs) -> Result<Instance, Error> {
+        with(|cx| cx.resolve_instance(def, args))
+    }
+
+    /// Returns the body of the instance, in which generic parameters are replaced by the
+    /// arguments of the instance. This fails for intrinsics and virtual calls, and for functions
+    /// of other crates whose MIR is not available.
+    pub fn body(&self) -> Result<Body, Error> {
+        with(|cx| cx.instance_body(self.def))
+    }
+
+    /// Returns the type of the instance, a function definition or closure type.
+    pub fn ty(&self) -> Ty {
+        with(|cx| cx.instance_ty(self.def))
+    }
+
+    /// Returns how the instance is called.
+    pub fn fn_abi(&self) -> Result<FnAbi, Error> {
+        with(|cx| cx.instance_fn_abi(self.def))
+    }
+
+    /// Returns a conservative summary of the side effects of the instance and of the functions
+    /// it may call, like whether it may write to a static or unwind.
+    pub fn effect_summary(&self) -> EffectSummary {
+        effects::summarize(*self)
+    }
+}
@@ -0,0 +1,171 @@
+//! Patterns matching the terminators of basic blocks, to find MIR idioms without writing nested
+//! matches over the statements and terminators of a body.
+//!
+//! Patterns are built from the functions of this module and combined with
+//! [`BlockPattern::then`], which matches a block and the block it continues to. For example,
+//! the blocks calling `Option::unwrap` can be found with:
+//!
+//! ```ignore (needs a compiler context)
+//! use rustc_smir::stable_mir::mir::pattern::{call, BlockPattern};
+//!
+//! let blocks = call().to("Option::<T>::unwrap").find(&body);
+//! ```
+//!
+//! And the matches over the result of a call, which switch on its discriminant right after the
+//! call returns, with:
+//!
+//! ```ignore (needs a compiler context)
+//! let blocks = call().to("Iterator::next").then(switch_int().on_discriminant()).find(&body);
+//! ```
+
+use super::{Body, Operand, Rvalue, StatementKind, TerminatorKind};
+use crate::stable_mir::ty::{RigidTy, TyKind};
+
+/// A pattern which a basic block of a body can match, usually through its terminator.
+pub trait BlockPattern {
+    /// Whether the block at index `block` of `body` matches this pattern.
+    fn matches(&self, body: &Body, block: usize) -> bool;
+
+    /// Returns the indices of the blocks of `body` which match this pattern.
+    fn find(&self, body: &Body) -> Vec<usize> {
+        (0..body.blocks.len()).filter(|&block| self.matches(body, block)).collect()
+    }
+
+    /// Matches the blocks which match this pattern and whose terminator continues to a block
+    /// matching `next`, when it doesn't unwind.
+    fn then<P: BlockPattern>(self, next: P) -> Then<Self, P>
+    where
+        Self: Sized,
+    {
+        Then { first: self, next }
+    }
+}
+
+impl<F: Fn(&Body, usize) -> bool> BlockPattern for F {
+    fn matches(&self, body: &Body, block: usize) -> bool {
+        self(body, block)
+    }
+}
+
+/// Matches the blocks whose terminator satisfies `predicate`.
+pub fn terminator(predicate: impl Fn(&TerminatorKind) -> bool) -> impl Fn(&Body, usize) -> bool {
+    move |body, block| predicate(&body.blocks[block].terminator.kind)
+}
+
+/// Matches the blocks ending with a call, see [`Call`].
+pub fn call() -> Call {
+    Call { callee: None, arg_count: None }
+}
+
+/// Matches the blocks ending with a `SwitchInt`, see [`SwitchInt`].
+pub fn switch_int() -> SwitchInt {
+    SwitchInt { on_discriminant: false }
+}
+
+/// Matches the blocks ending with a call. By default, any call matches.
+#[derive(Clone, Debug)]
+pub struct Call {
+    callee: Option<String>,
+    arg_count: Option<usize>,
+}
+
+impl Call {
+    /// Only matches the calls to the function whose path is `path`, or ends with `::path`.
+    /// Calls through function pointers never match.
+    pub fn to(mut self, path: &str) -> Self {
+        self.callee = Some(path.to_string());
+        self
+    }
+
+    /// Only matches the calls with `count` arguments.
+    pub fn with_arg_count(mut self, count: usize) -> Self {
+        self.arg_count = Some(count);
+        self
+    }
+}
+
+impl BlockPattern for Call {
+    fn matches(&self, body: &Body, block: usize) -> bool {
+        let TerminatorKind::Call { func, args, .. } = &body.blocks[block].terminator.kind else {
+            return false;
+        };
+        if self.arg_count.is_some_and(|count| count != args.len()) {
+            return false;
+        }
+        let Some(path) = &self.callee else {
+            return true;
+        };
+        let Operand::Constant(constant) = func else {
+            return false;
+        };
+        let TyKind::RigidTy(RigidTy::FnDef(def, _)) = constant.literal.ty.kind() else {
+            return false;
+        };
+        let name = def.name();
+        name == *path || name.ends_with(&format!("::{path}"))
+    }
+}
+
+/// Matches the blocks ending with a `SwitchInt`. By default, any `SwitchInt` matches.
+#[derive(Clone, Debug)]
+pub struct SwitchInt {
+    on_discriminant: bool,
+}
+
+impl SwitchInt {
+    /// Only matches the switches on the discriminant of an enum, read in the same block, as
+    /// generated for `match` and `if let` expressions.
+    pub fn on_discriminant(mut self) -> Self {
+        self.on_discriminant = true;
+        self
+    }
+}
+
+impl BlockPattern for SwitchInt {
+    fn matches(&self, body: &Body, block: usize) -> bool {
+        let block = &body.blocks[block];
+        let TerminatorKind::SwitchInt { discr, .. } = &block.terminator.kind else {
+            return false;
+        };
+        if !self.on_discriminant {
+            return true;
+        }
+        let (Operand::Copy(discr) | Operand::Move(discr)) = discr else {
+            return false;
+        };
+        block.statements.iter().any(|statement| {
+            matches!(
+                &statement.kind,
+                StatementKind::Assign(place, Rvalue::Discriminant(_))
+                    if place.local == discr.local && place.projection == discr.projection
+            )
+        })
+    }
+}
+
+/// Matches the blocks matching a pattern and continuing to a block which matches another one,
+/// see [`BlockPattern::then`].
+#[derive(Clone, Debug)]
+pub struct Then<A, B> {
+    first: A,
+    next: B,
+}
+
+impl<A: BlockPattern, B: BlockPattern> BlockPattern for Then<A, B> {
+    fn matches(&self, body: &Body, block: usize) -> bool {
+        if !self.first.matches(body, block) {
+            return false;
+        }
+        let next = match &body.blocks[block].terminator.kind {
+            TerminatorKind::Goto { target }
+            | TerminatorKind::Drop { target, .. }
+            | TerminatorKind::Assert { target, .. }
+            | TerminatorKind::Call { target: Some(target), .. }
+            | TerminatorKind::InlineAsm { destination: Some(target), .. }
+            | TerminatorKind::FalseEdge { real_target: target, .. }
+            | TerminatorKind::FalseUnwind { real_target: target, .. } => *target,
+            _ => return false,
+        };
+        self.next.matches(body, next)
+    }
+}
@@ -0,0 +1,683 @@
+//! Printing of bodies in the textual format of the MIR dumps of the compiler, like the ones
+//! written with `-Zdump-mir`, so that they can be read and compared with familiar syntax.
+//!
+//! The output closely follows the one of the compiler, except where the stable MIR doesn't keep
+//! enough information:
+//!
+//! - the locals are all declared with `let`, as their mutability isn't known, and the debug
+//!   information of the variables isn't nested in scopes;
+//! - the cleanup blocks aren't marked as such, and the reasons of the `abort` terminators and of
+//!   the `terminate` unwind actions are omitted;
+//! - downcasts are printed with the index of the variant, like `(_1 as variant#1)`, instead of
+//!   its name.
+//!
+//! ```ignore (needs a compiler context)
+//! let body = item.body();
+//! println!("{body}");
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+
+use super::{
+    AggregateKind, AssertMessage, BasicBlock, BinOp, Body, BorrowKind, Constant, CovOp,
+    CoverageKind, GeneratorKind, InlineAsmOperand, InlineAsmOptions, InlineAsmRegOrRegClass,
+    InlineAsmTemplatePiece, Mutability, NonDivergingIntrinsic, NullOp, Operand, Place,
+    ProjectionElem, RetagKind, Rvalue, Statement, StatementKind, TerminatorKind, UnwindAction,
+    VarDebugInfo, VarDebugInfoContents,
+};
+use crate::stable_mir::ty::{AdtKind, Const, GenericArgKind, GenericArgs, RigidTy, Span, TyKind};
+use crate::stable_mir::with;
+
+const INDENT: &str = "    ";
+
+impl Body {
+    /// Writes the body in the format of the MIR dumps of the compiler, as the body of the
+    /// function `name`, like `fn name(_1: u32) -> u32 { ... }`.
+    pub fn dump(&self, w: &mut dyn fmt::Write, name: &str) -> fmt::Result {
+        write!(w, "fn {name}(")?;
+        for (index, arg) in self.locals[1..=self.arg_count].iter().enumerate() {
+            if index > 0 {
+                w.write_str(", ")?;
+            }
+            write!(w, "_{}: {arg}", index + 1)?;
+        }
+        writeln!(w, ") -> {} {{", self.locals[0])?;
+
+        for var_debug_info in &self.var_debug_info {
+            writeln!(w, "{INDENT}debug {};", DebugInfo(var_debug_info))?;
+        }
+        for (local, ty) in self.locals.iter().enumerate() {
+            if (1..=self.arg_count).contains(&local) {
+                // The arguments are declared in the signature.
+                continue;
+            }
+            writeln!(w, "{INDENT}let _{local}: {ty};")?;
+        }
+
+        for (index, block) in self.blocks.iter().enumerate() {
+            writeln!(w)?;
+            write_basic_block(w, index, block)?;
+        }
+        writeln!(w, "}}")
+    }
+}
+
+/// Prints the body as the body of an anonymous function, see [`Body::dump`] to give it a name.
+impl Display for Body {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.dump(f, "")
+    }
+}
+
+fn write_basic_block(w: &mut dyn fmt::Write, index: usize, block: &BasicBlock) -> fmt::Result {
+    writeln!(w, "{INDENT}bb{index}: {{")?;
+    for statement in &block.statements {
+        writeln!(w, "{INDENT}{INDENT}{statement};")?;
+    }
+    writeln!(w, "{INDENT}{INDENT}{};", block.terminator.kind)?;
+    writeln!(w, "{INDENT}}}")
+}
+
+struct DebugInfo<'a>(&'a VarDebugInfo);
+
+impl Display for DebugInfo<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let info = self.0;
+        match &info.composite {
+            Some(fragment) => {
+                for _ in &fragment.projection {
+                    f.write_str("(")?;
+                }
+                write!(f, "({}: {})", info.name, fragment.ty)?;
+                for (field, ty) in &fragment.projection {
+                    write!(f, ".{field}: {ty})")?;
+                }
+            }
+            None => f.write_str(&info.name)?,
+        }
+        match &info.value {
+            VarDebugInfoContents::Place(place) => write!(f, " => {place}"),
+            VarDebugInfoContents::Const(constant) => write!(f, " => {constant}"),
+        }
+    }
+}
+
+impl Display for Statement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            StatementKind::Assign(place, rvalue) => write!(f, "{place} = {rvalue}"),
+            StatementKind::FakeRead(cause, place) => write!(f, "FakeRead({cause:?}, {place})"),
+            StatementKind::SetDiscriminant { place, variant_index } => {
+                write!(f, "discriminant({place}) = {variant_index}")
+            }
+            StatementKind::Deinit(place) => write!(f, "Deinit({place})"),
+            StatementKind::StorageLive(local) => write!(f, "StorageLive(_{local})"),
+            StatementKind::StorageDead(local) => write!(f, "StorageDead(_{local})"),
+            StatementKind::Retag(kind, place) => {
+                let kind = match kind {
+                    RetagKind::FnEntry => "[fn entry] ",
+                    RetagKind::TwoPhase => "[2phase] ",
+                    RetagKind::Raw => "[raw] ",
+                    RetagKind::Default => "",
+                };
+                write!(f, "Retag({kind}{place})")
+            }
+            StatementKind::PlaceMention(place) => write!(f, "PlaceMention({place})"),
+            StatementKind::AscribeUserType { place, projections, variance } => write!(
+                f,
+                "AscribeUserType({place}, {variance:?}, UserTypeProjection {{ base: UserType({}), \
+                 projs: {:?} }})",
+                projections.base, projections.projection
+            ),
+            StatementKind::Coverage(coverage) => {
+                f.write_str("Coverage::")?;
+                match &coverage.kind {
+                    CoverageKind::Counter { id, .. } => write!(f, "Counter({id})")?,
+                    CoverageKind::Expression { id, lhs, op, rhs } => {
+                        let op = match op {
+                            CovOp::Add => "+",
+                            CovOp::Subtract => "-",
+                        };
+                        write!(f, "Expression({id}) = {lhs:?} {op} {rhs:?}")?
+                    }
+                    CoverageKind::Unreachable => f.write_str("Unreachable")?,
+                }
+                match &coverage.code_region {
+                    Some(region) => write!(
+                        f,
+                        " for {}:{}:{} - {}:{}",
+                        region.file_name,
+                        region.start_line,
+                        region.start_col,
+                        region.end_line,
+                        region.end_col
+                    ),
+                    None => Ok(()),
+                }
+            }
+            StatementKind::Intrinsic(NonDivergingIntrinsic::Assume(operand)) => {
+                write!(f, "assume({operand})")
+            }
+            StatementKind::Intrinsic(NonDivergingIntrinsic::CopyNonOverlapping(copy)) => write!(
+                f,
+                "copy_nonoverlapping(dst = {}, src = {}, count = {})",
+                copy.dst, copy.src, copy.count
+            ),
+            StatementKind::ConstEvalCounter => f.write_str("ConstEvalCounter"),
+            StatementKind::Nop => f.write_str("nop"),
+        }
+    }
+}
+
+/// Prints the terminator followed by its successors, like `_0 = f(move _1) -> [return: bb1,
+/// unwind continue]`.
+impl Display for TerminatorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_head(f)?;
+        let successors = self.successors();
+        let labels = self.successor_labels();
+        let unwind = match self {
+            TerminatorKind::Drop { unwind, .. }
+            | TerminatorKind::Call { unwind, .. }
+            | TerminatorKind::Assert { unwind, .. }
+            | TerminatorKind::FalseUnwind { unwind, .. }
+            | TerminatorKind::InlineAsm { unwind, .. } => match unwind {
+                UnwindAction::Continue => Some("continue"),
+                UnwindAction::Unreachable => Some("unreachable"),
+                UnwindAction::Terminate => Some("terminate"),
+                // The cleanup block is one of the successors.
+                UnwindAction::Cleanup(_) => None,
+            },
+            _ => None,
+        };
+        match (successors.as_slice(), unwind) {
+            ([], None) => Ok(()),
+            ([], Some(unwind)) => write!(f, " -> unwind {unwind}"),
+            ([target], None) => write!(f, " -> bb{target}"),
+            _ => {
+                f.write_str(" -> [")?;
+                for (index, (label, target)) in labels.iter().zip(&successors).enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{label}: bb{target}")?;
+                }
+                if let Some(unwind) = unwind {
+                    write!(f, ", unwind {unwind}")?;
+                }
+                f.write_str("]")
+            }
+        }
+    }
+}
+
+impl TerminatorKind {
+    /// Writes the terminator without its successors.
+    fn fmt_head(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TerminatorKind::Goto { .. } => f.write_str("goto"),
+            TerminatorKind::SwitchInt { discr, .. } => write!(f, "switchInt({discr})"),
+            TerminatorKind::Resume => f.write_str("resume"),
+            TerminatorKind::Abort => f.write_str("abort"),
+            TerminatorKind::Return => f.write_str("return"),
+            TerminatorKind::Unreachable => f.write_str("unreachable"),
+            TerminatorKind::Drop { place, .. } => write!(f, "drop({place})"),
+            TerminatorKind::Call { func, args, destination, .. } => {
+                write!(f, "{destination} = {func}(")?;
+                write_separated(f, args)?;
+                f.write_str(")")
+            }
+            TerminatorKind::Assert { cond, expected, msg, .. } => {
+                let not = if *expected { "" } else { "!" };
+                write!(f, "assert({not}{cond}, ")?;
+                msg.fmt_assert_args(f)?;
+                f.write_str(")")
+            }
+            TerminatorKind::Yield { value, resume_arg, .. } => {
+                write!(f, "{resume_arg} = yield({value})")
+            }
+            TerminatorKind::GeneratorDrop => f.write_str("generator_drop"),
+            TerminatorKind::FalseEdge { .. } => f.write_str("falseEdge"),
+            TerminatorKind::FalseUnwind { .. } => f.write_str("falseUnwind"),
+            TerminatorKind::InlineAsm { template, operands, options, .. } => {
+                f.write_str("asm!(\"")?;
+                for piece in template {
+                    match piece {
+                        InlineAsmTemplatePiece::String(s) => {
+                            f.write_str(&s.replace('{', "{{").replace('}', "}}"))?
+                        }
+                        InlineAsmTemplatePiece::Placeholder { operand_idx, modifier, .. } => {
+                            match modifier {
+                                Some(modifier) => write!(f, "{{{operand_idx}:{modifier}}}")?,
+                                None => write!(f, "{{{operand_idx}}}")?,
+                            }
+                        }
+                    }
+                }
+                f.write_str("\"")?;
+                for operand in operands {
+                    f.write_str(", ")?;
+                    let late = |late: &bool| if *late { "late" } else { "" };
+                    match operand {
+                        InlineAsmOperand::In { reg, value } => write!(f, "in({reg}) {value}")?,
+                        InlineAsmOperand::Out { reg, late: is_late, place } => {
+                            write!(f, "{}out({reg}) ", late(is_late))?;
+                            write_optional_place(f, place)?;
+                        }
+                        InlineAsmOperand::InOut { reg, late: is_late, in_value, out_place } => {
+                            write!(f, "in{}out({reg}) {in_value} => ", late(is_late))?;
+                            write_optional_place(f, out_place)?;
+                        }
+                        InlineAsmOperand::Const { value } => write!(f, "const {value}")?,
+                        InlineAsmOperand::SymFn { value } => write!(f, "sym_fn {value}")?,
+                        InlineAsmOperand::SymStatic { def } => {
+                            write!(f, "sym_static {}", with(|cx| cx.name_of_def_id(def.0)))?
+                        }
+                    }
+                }
+                write!(f, ", options({}))", AsmOptions(options))
+            }
+        }
+    }
+
+    /// The labels of the edges to the blocks returned by [`TerminatorKind::successors`].
+    fn successor_labels(&self) -> Vec<String> {
+        let with_unwind = |label: &str, unwind: &UnwindAction| {
+            let mut labels = vec![label.to_owned()];
+            if let UnwindAction::Cleanup(_) = unwind {
+                labels.push("unwind".to_owned());
+            }
+            labels
+        };
+        match self {
+            TerminatorKind::Resume
+            | TerminatorKind::Abort
+            | TerminatorKind::Return
+            | TerminatorKind::Unreachable
+            | TerminatorKind::GeneratorDrop => vec![],
+            TerminatorKind::Goto { .. } => vec![String::new()],
+            TerminatorKind::SwitchInt { targets, .. } => targets
+                .iter()
+                .map(|target| target.value.to_string())
+                .chain(Some("otherwise".to_owned()))
+                .collect(),
+            TerminatorKind::Drop { unwind, .. } => with_unwind("return", unwind),
+            TerminatorKind::Assert { unwind, .. } => with_unwind("success", unwind),
+            TerminatorKind::FalseUnwind { unwind, .. } => with_unwind("real", unwind),
+            TerminatorKind::Call { target, unwind, .. }
+            | TerminatorKind::InlineAsm { destination: target, unwind, .. } => {
+                let mut labels = with_unwind("return", unwind);
+                if target.is_none() {
+                    labels.remove(0);
+                }
+                labels
+            }
+            TerminatorKind::Yield { drop, .. } => {
+                let mut labels = vec!["resume".to_owned()];
+                if drop.is_some() {
+                    labels.push("drop".to_owned());
+                }
+                labels
+            }
+            TerminatorKind::FalseEdge { .. } => vec!["real".to_owned(), "imaginary".to_owned()],
+        }
+    }
+}
+
+impl AssertMessage {
+    /// Writes the message of the assertion and the operands it is formatted with.
+    fn fmt_assert_args(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AssertMessage::BoundsCheck { len, index } => write!(
+                f,
+                "\"index out of bounds: the length is {{}} but the index is {{}}\", {len}, {index}"
+            ),
+            AssertMessage::OverflowNeg(op) => {
+                write!(f, "\"attempt to negate `{{}}`, which would overflow\", {op}")
+            }
+            AssertMessage::DivisionByZero(op) => {
+                write!(f, "\"attempt to divide `{{}}` by zero\", {op}")
+            }
+            AssertMessage::RemainderByZero(op) => write!(
+                f,
+                "\"attempt to calculate the remainder of `{{}}` with a divisor of zero\", {op}"
+            ),
+            AssertMessage::Overflow(BinOp::Shr, _, r) => {
+                write!(f, "\"attempt to shift right by `{{}}`, which would overflow\", {r}")
+            }
+            AssertMessage::Overflow(BinOp::Shl, _, r) => {
+                write!(f, "\"attempt to shift left by `{{}}`, which would overflow\", {r}")
+            }
+            AssertMessage::Overflow(BinOp::Rem, l, r) => write!(
+                f,
+                "\"attempt to compute the remainder of `{{}} % {{}}`, which would overflow\", {l}, \
+                 {r}"
+            ),
+            AssertMessage::Overflow(op, l, r) => {
+                let op = match op {
+                    BinOp::Add => "+",
+                    BinOp::Sub => "-",
+                    BinOp::Mul => "*",
+                    BinOp::Div => "/",
+                    _ => return write!(f, "\"attempt to compute `{op:?}` with overflow\""),
+                };
+                write!(f, "\"attempt to compute `{{}} {op} {{}}`, which would overflow\", {l}, {r}")
+            }
+            AssertMessage::MisalignedPointerDereference { required, found } => write!(
+                f,
+                "\"misaligned pointer dereference: address must be a multiple of {{}} but is \
+                 {{}}\", {required}, {found}"
+            ),
+            AssertMessage::ResumedAfterReturn(GeneratorKind::Gen) => {
+                f.write_str("\"generator resumed after completion\"")
+            }
+            AssertMessage::ResumedAfterReturn(GeneratorKind::Async(_)) => {
+                f.write_str("\"`async fn` resumed after completion\"")
+            }
+            AssertMessage::ResumedAfterPanic(GeneratorKind::Gen) => {
+                f.write_str("\"generator resumed after panicking\"")
+            }
+            AssertMessage::ResumedAfterPanic(GeneratorKind::Async(_)) => {
+                f.write_str("\"`async fn` resumed after panicking\"")
+            }
+        }
+    }
+}
+
+impl Display for Rvalue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Rvalue::AddressOf(mutability, place) => {
+                let kind = match mutability {
+                    Mutability::Not => "const",
+                    Mutability::Mut => "mut",
+                };
+                write!(f, "&raw {kind} {place}")
+            }
+            Rvalue::Aggregate(kind, operands) => write_aggregate(f, kind, operands),
+            Rvalue::BinaryOp(op, a, b) => write!(f, "{op:?}({a}, {b})"),
+            Rvalue::Cast(kind, operand, ty) => write!(f, "{operand} as {ty} ({kind:?})"),
+            Rvalue::CheckedBinaryOp(op, a, b) => write!(f, "Checked{op:?}({a}, {b})"),
+            Rvalue::CopyForDeref(place) => write!(f, "deref_copy {place}"),
+            Rvalue::Discriminant(place) => write!(f, "discriminant({place})"),
+            Rvalue::Len(place) => write!(f, "Len({place})"),
+            Rvalue::Ref(_, kind, place) => {
+                let kind = match kind {
+                    BorrowKind::Shared => "",
+                    BorrowKind::Shallow => "shallow ",
+                    BorrowKind::Mut { .. } => "mut ",
+                };
+                write!(f, "&{kind}{place}")
+            }
+            // The length of the array is printed without its type.
+            Rvalue::Repeat(operand, count) => match count.eval_target_usize() {
+                Ok(count) => write!(f, "[{operand}; {count}]"),
+                Err(_) => write!(f, "[{operand}; {count}]"),
+            },
+            Rvalue::ShallowInitBox(operand, ty) => write!(f, "ShallowInitBox({operand}, {ty})"),
+            Rvalue::ThreadLocalRef(item) => {
+                write!(f, "&/*tls*/ {}", with(|cx| cx.name_of_def_id(item.0)))
+            }
+            Rvalue::NullaryOp(op, ty) => match op {
+                NullOp::SizeOf => write!(f, "SizeOf({ty})"),
+                NullOp::AlignOf => write!(f, "AlignOf({ty})"),
+                NullOp::OffsetOf(fields) => write!(f, "OffsetOf({ty}, {fields:?})"),
+            },
+            Rvalue::UnaryOp(op, operand) => write!(f, "{op:?}({operand})"),
+            Rvalue::Use(operand) => write!(f, "{operand}"),
+        }
+    }
+}
+
+fn write_aggregate(
+    f: &mut Formatter<'_>,
+    kind: &AggregateKind,
+    operands: &[Operand],
+) -> fmt::Result {
+    match kind {
+        AggregateKind::Array(_) => {
+            f.write_str("[")?;
+            write_separated(f, operands)?;
+            f.write_str("]")
+        }
+        AggregateKind::Tuple => {
+            f.write_str("(")?;
+            write_separated(f, operands)?;
+            if operands.len() == 1 {
+                f.write_str(",")?;
+            }
+            f.write_str(")")
+        }
+        AggregateKind::Adt(def, variant, args, _, _) => {
+            let path = with(|cx| cx.name_of_def_id(def.0));
+            let variant = def.variants().swap_remove(*variant);
+            write!(f, "{path}{}", Turbofish(args))?;
+            if def.kind() == AdtKind::Enum {
+                write!(f, "::{}", variant.name)?;
+            }
+            match variant.fields.first() {
+                None => Ok(()),
+                // The fields of tuple structs and variants are named after their index.
+                Some(field) if field.name == "0" => {
+                    f.write_str("(")?;
+                    write_separated(f, operands)?;
+                    f.write_str(")")
+                }
+                Some(_) => {
+                    let names = variant.fields.iter().map(|field| &field.name);
+                    write_fields(f, names, operands)
+                }
+            }
+        }
+        AggregateKind::Closure(def, _) => {
+            write!(f, "[closure@{}]", SpanLocation(with(|cx| cx.span_of_an_item(def.0))))?;
+            // Like the compiler, the fields are named after the captured variables.
+            match def.captures() {
+                Ok(captures) if captures.len() == operands.len() => {
+                    write_fields(f, captures.iter().map(|capture| &capture.var_name), operands)
+                }
+                _ => write_fields(f, (0..operands.len()).map(|index| index.to_string()), operands),
+            }
+        }
+        AggregateKind::Generator(def, _, _) => {
+            write!(f, "[generator@{}]", SpanLocation(with(|cx| cx.span_of_an_item(def.0))))?;
+            write_fields(f, (0..operands.len()).map(|index| index.to_string()), operands)
+        }
+    }
+}
+
+/// Writes the fields of a struct-like aggregate, like ` { x: move _1, y: _2 }`.
+fn write_fields(
+    f: &mut Formatter<'_>,
+    names: impl Iterator<Item = impl Display>,
+    operands: &[Operand],
+) -> fmt::Result {
+    if operands.is_empty() {
+        return Ok(());
+    }
+    f.write_str(" { ")?;
+    for (index, (name, operand)) in names.zip(operands).enumerate() {
+        if index > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{name}: {operand}")?;
+    }
+    f.write_str(" }")
+}
+
+fn write_separated(f: &mut Formatter<'_>, items: &[impl Display]) -> fmt::Result {
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{item}")?;
+    }
+    Ok(())
+}
+
+fn write_optional_place(f: &mut Formatter<'_>, place: &Option<Place>) -> fmt::Result {
+    match place {
+        Some(place) => write!(f, "{place}"),
+        None => f.write_str("_"),
+    }
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Copy(place) => write!(f, "{place}"),
+            Operand::Move(place) => write!(f, "move {place}"),
+            Operand::Constant(constant) => write!(f, "{constant}"),
+        }
+    }
+}
+
+/// Prints the constant with the `const` keyword, except for functions which are printed as their
+/// path, like `std::mem::drop::<u8>`.
+impl Display for Constant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if !matches!(self.literal.ty.kind(), TyKind::RigidTy(RigidTy::FnDef(..))) {
+            f.write_str("const ")?;
+        }
+        write!(f, "{}", self.literal)
+    }
+}
+
+/// Prints the place like `(*(_1.0: &u8))`, with the type of each field.
+impl Display for Place {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for elem in self.projection.iter().rev() {
+            match elem {
+                ProjectionElem::OpaqueCast(_)
+                | ProjectionElem::Downcast(_)
+                | ProjectionElem::Field(..) => f.write_str("(")?,
+                ProjectionElem::Deref => f.write_str("(*")?,
+                ProjectionElem::Index(_)
+                | ProjectionElem::ConstantIndex { .. }
+                | ProjectionElem::Subslice { .. } => {}
+            }
+        }
+        write!(f, "_{}", self.local)?;
+        for elem in &self.projection {
+            match elem {
+                ProjectionElem::OpaqueCast(ty) => write!(f, " as {ty})")?,
+                ProjectionElem::Downcast(variant) => write!(f, " as variant#{variant})")?,
+                ProjectionElem::Deref => f.write_str(")")?,
+                ProjectionElem::Field(field, ty) => write!(f, ".{field}: {ty})")?,
+                ProjectionElem::Index(local) => write!(f, "[_{local}]")?,
+                ProjectionElem::ConstantIndex { offset, min_length, from_end } => {
+                    let minus = if *from_end { "-" } else { "" };
+                    write!(f, "[{minus}{offset} of {min_length}]")?
+                }
+                ProjectionElem::Subslice { from, to, from_end: true } if *to == 0 => {
+                    write!(f, "[{from}:]")?
+                }
+                ProjectionElem::Subslice { from, to, from_end: true } if *from == 0 => {
+                    write!(f, "[:-{to}]")?
+                }
+                ProjectionElem::Subslice { from, to, from_end: true } => {
+                    write!(f, "[{from}:-{to}]")?
+                }
+                ProjectionElem::Subslice { from, to, from_end: false } => {
+                    write!(f, "[{from}..{to}]")?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for InlineAsmRegOrRegClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            InlineAsmRegOrRegClass::Reg(reg) => write!(f, "\"{reg}\""),
+            InlineAsmRegOrRegClass::RegClass(class) => f.write_str(class),
+        }
+    }
+}
+
+/// Prints the options of an `asm!` block like the flags of the compiler, e.g. `NOMEM | NOSTACK`.
+struct AsmOptions<'a>(&'a InlineAsmOptions);
+
+impl Display for AsmOptions<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let options = self.0;
+        let flags = [
+            (options.pure, "PURE"),
+            (options.nomem, "NOMEM"),
+            (options.readonly, "READONLY"),
+            (options.preserves_flags, "PRESERVES_FLAGS"),
+            (options.noreturn, "NORETURN"),
+            (options.nostack, "NOSTACK"),
+            (options.att_syntax, "ATT_SYNTAX"),
+            (options.raw, "RAW"),
+            (options.may_unwind, "MAY_UNWIND"),
+        ];
+        let mut flags = flags.iter().filter(|(set, _)| *set).map(|(_, name)| *name).peekable();
+        if flags.peek().is_none() {
+            return f.write_str("(empty)");
+        }
+        for (index, flag) in flags.enumerate() {
+            if index > 0 {
+                f.write_str(" | ")?;
+            }
+            f.write_str(flag)?;
+        }
+        Ok(())
+    }
+}
+
+/// Prints the generic arguments of a path, like `::<u8, 3>`, without the lifetimes which are
+/// erased in bodies.
+struct Turbofish<'a>(&'a GenericArgs);
+
+impl Display for Turbofish<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for arg in &self.0 .0 {
+            let prefix = if first { "::<" } else { ", " };
+            match arg {
+                GenericArgKind::Lifetime(_) => continue,
+                GenericArgKind::Type(ty) => write!(f, "{prefix}{ty}")?,
+                GenericArgKind::Const(cnst) => write!(f, "{prefix}{}", ConstArg(cnst))?,
+            }
+            first = false;
+        }
+        if !first {
+            f.write_str(">")?;
+        }
+        Ok(())
+    }
+}
+
+/// Prints a constant generic argument without the suffix of its type, like `3`.
+struct ConstArg<'a>(&'a Const);
+
+impl Display for ConstArg<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.0.try_to_bits() {
+            Ok(bits) if matches!(self.0.ty.kind(), TyKind::RigidTy(RigidTy::Uint(_))) => {
+                write!(f, "{bits}")
+            }
+            _ => write!(f, "{}", self.0),
+        }
+    }
+}
+
+/// Prints the location of a span like in diagnostics, e.g. `src/lib.rs:3:13: 3:20`.
+struct SpanLocation(Span);
+
+impl Display for SpanLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let lines = self.0.get_lines();
+        write!(
+            f,
+            "{}:{}:{}: {}:{}",
+            self.0.get_filename(),
+            lines.start_line,
+            lines.start_col,
+            lines.end_line,
+            lines.end_col
+        )
+    }
+}
@@ -0,0 +1,187 @@
+//! Traversals of the control-flow graph of a body, and its dominator tree, like the ones of rustc.
+//!
+//! The traversals start at the first block of the body and follow all the edges of the graph,
+//! including the unwind paths, so they only visit the blocks which are reachable.
+
+use super::{BasicBlock, Body};
+
+/// A preorder traversal of a body, which visits each block before its successors, except for
+/// the back edges of the loops.
+pub struct Preorder<'a> {
+    body: &'a Body,
+    visited: Vec<bool>,
+    worklist: Vec<usize>,
+}
+
+impl<'a> Preorder<'a> {
+    pub(super) fn new(body: &'a Body) -> Self {
+        let worklist = if body.blocks.is_empty() { vec![] } else { vec![0] };
+        Preorder { body, visited: vec![false; body.blocks.len()], worklist }
+    }
+}
+
+impl<'a> Iterator for Preorder<'a> {
+    type Item = (usize, &'a BasicBlock);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(index) = self.worklist.pop() {
+            if std::mem::replace(&mut self.visited[index], true) {
+                continue;
+            }
+            let block = &self.body.blocks[index];
+            // Pushing the successors in reverse visits them in order.
+            let successors = block.terminator.kind.successors();
+            self.worklist.extend(successors.into_iter().rev().filter(|succ| !self.visited[*succ]));
+            return Some((index, block));
+        }
+        None
+    }
+}
+
+/// A postorder traversal of a body, which visits each block after its successors, except for
+/// the back edges of the loops.
+pub struct Postorder<'a> {
+    body: &'a Body,
+    visited: Vec<bool>,
+    /// The blocks being visited, with the successors which remain to be visited.
+    visit_stack: Vec<(usize, std::vec::IntoIter<usize>)>,
+}
+
+impl<'a> Postorder<'a> {
+    pub(super) fn new(body: &'a Body) -> Self {
+        let mut postorder =
+            Postorder { body, visited: vec![false; body.blocks.len()], visit_stack: vec![] };
+        if !body.blocks.is_empty() {
+            postorder.push(0);
+        }
+        postorder
+    }
+
+    fn push(&mut self, index: usize) {
+        self.visited[index] = true;
+        let successors = self.body.blocks[index].terminator.kind.successors();
+        self.visit_stack.push((index, successors.into_iter()));
+    }
+}
+
+impl<'a> Iterator for Postorder<'a> {
+    type Item = (usize, &'a BasicBlock);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (index, successors) = self.visit_stack.last_mut()?;
+            let index = *index;
+            match successors.find(|succ| !self.visited[*succ]) {
+                Some(successor) => self.push(successor),
+                None => {
+                    self.visit_stack.pop();
+                    return Some((index, &self.body.blocks[index]));
+                }
+            }
+        }
+    }
+}
+
+/// The dominator tree of a body: a block `a` dominates a block `b` if all the paths from the
+/// start of the body to `b` go through `a`.
+#[derive(Clone, Debug)]
+pub struct Dominators {
+    /// The immediate dominator of each block, which is the block itself for the first block and
+    /// `None` for the blocks which aren't reachable.
+    immediate_dominators: Vec<Option<usize>>,
+    /// The index of each reachable block in the reverse postorder, which is greater than the one
+    /// of its dominators.
+    rpo_index: Vec<usize>,
+}
+
+impl Dominators {
+    /// Computes the dominators with the algorithm of "A Simple, Fast Dominance Algorithm" by
+    /// Cooper, Harvey and Kennedy.
+    pub(super) fn new(body: &Body) -> Self {
+        let reverse_postorder = body.reverse_postorder();
+        let predecessors = body.predecessors();
+        let mut rpo_index = vec![usize::MAX; body.blocks.len()];
+        for (index, block) in reverse_postorder.iter().enumerate() {
+            rpo_index[*block] = index;
+        }
+
+        let mut immediate_dominators = vec![None; body.blocks.len()];
+        let Some(&start) = reverse_postorder.first() else {
+            return Dominators { immediate_dominators, rpo_index };
+        };
+        immediate_dominators[start] = Some(start);
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &block in &reverse_postorder[1..] {
+                let mut processed = predecessors[block]
+                    .iter()
+                    .copied()
+                    .filter(|pred| immediate_dominators[*pred].is_some());
+                // A block is visited after one of its predecessors in the reverse postorder.
+                let first = processed.next().unwrap();
+                let new_idom = processed.fold(first, |idom, pred| {
+                    intersect(&immediate_dominators, &rpo_index, idom, pred)
+                });
+                if immediate_dominators[block] != Some(new_idom) {
+                    immediate_dominators[block] = Some(new_idom);
+                    changed = true;
+                }
+            }
+        }
+        Dominators { immediate_dominators, rpo_index }
+    }
+
+    pub fn is_reachable(&self, block: usize) -> bool {
+        self.immediate_dominators[block].is_some()
+    }
+
+    /// The block which dominates `block` and is dominated by all its other dominators, or `None`
+    /// for the first block and the blocks which aren't reachable.
+    pub fn immediate_dominator(&self, block: usize) -> Option<usize> {
+        self.immediate_dominators[block].filter(|idom| *idom != block)
+    }
+
+    /// Whether `a` dominates `b`. Each reachable block dominates itself, and the blocks which
+    /// aren't reachable neither dominate nor are dominated.
+    pub fn dominates(&self, a: usize, b: usize) -> bool {
+        if !self.is_reachable(a) || !self.is_reachable(b) {
+            return false;
+        }
+        let mut current = b;
+        // The dominators of `b` come before it in the reverse postorder.
+        while self.rpo_index[current] > self.rpo_index[a] {
+            current = self.immediate_dominators[current].unwrap();
+        }
+        current == a
+    }
+
+    /// The dominators of `block`, from `block` itself to the first block of the body.
+    pub fn dominators(&self, block: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut next = self.immediate_dominators[block].map(|_| block);
+        std::iter::from_fn(move || {
+            let current = next?;
+            next = self.immediate_dominator(current);
+            Some(current)
+        })
+    }
+}
+
+/// Returns the closest common dominator of `a` and `b`, by walking up the dominator tree from
+/// the one which comes last in the reverse postorder.
+fn intersect(
+    immediate_dominators: &[Option<usize>],
+    rpo_index: &[usize],
+    mut a: usize,
+    mut b: usize,
+) -> usize {
+    while a != b {
+        while rpo_index[a] > rpo_index[b] {
+            a = immediate_dominators[a].unwrap();
+        }
+        while rpo_index[b] > rpo_index[a] {
+            b = immediate_dominators[b].unwrap();
+        }
+    }
+    a
+}
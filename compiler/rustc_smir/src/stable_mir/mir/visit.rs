@@ -0,0 +1,405 @@
+//! A visitor walking the bodies of stable MIR, like the MIR visitor of rustc.
+//!
+//! Each `visit_*` method of [`MirVisitor`] calls the matching `super_*` method by default, which
+//! walks the elements it contains. Visitors override the `visit_*` methods of the elements they
+//! are interested in, and call the `super_*` method to keep walking into them. For example, the
+//! locals which are assigned to can be collected with:
+//!
+//! ```ignore (needs a compiler context)
+//! use rustc_smir::stable_mir::mir::visit::{Location, MirVisitor, PlaceContext};
+//! use rustc_smir::stable_mir::mir::{Local, Place};
+//!
+//! struct AssignedLocals(Vec<Local>);
+//!
+//! impl MirVisitor for AssignedLocals {
+//!     fn visit_place(&mut self, place: &Place, ptx: PlaceContext, location: Location) {
+//!         if ptx == PlaceContext::MutatingUse {
+//!             self.0.push(place.local);
+//!         }
+//!         self.super_place(place, ptx, location)
+//!     }
+//! }
+//!
+//! let mut assigned = AssignedLocals(Vec::new());
+//! assigned.visit_body(&body);
+//! ```
+//!
+//! The `super_*` methods aren't meant to be overridden.
+
+use super::{
+    AggregateKind, AssertMessage, BasicBlock, Body, BorrowKind, Constant, InlineAsmOperand, Local,
+    Mutability, NonDivergingIntrinsic, Operand, Place, ProjectionElem, Rvalue, Statement,
+    StatementKind, Terminator, TerminatorKind, VarDebugInfo, VarDebugInfoContents,
+};
+use crate::stable_mir::ty::{Const, GenericArgKind, GenericArgs, Region, Ty};
+use crate::stable_mir::Span;
+
+/// The location of a statement in a body. The terminator of a block is at the index following
+/// its last statement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Location {
+    pub block: usize,
+    pub statement_index: usize,
+}
+
+impl Location {
+    /// The start of the body, which is the location given for the elements which aren't part of
+    /// a statement, like the debug info of the variables.
+    pub const START: Location = Location { block: 0, statement_index: 0 };
+}
+
+/// How a place is used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlaceContext {
+    /// The place is read, moved out of, or borrowed immutably.
+    NonMutatingUse,
+    /// The place is written to, borrowed mutably, or dropped.
+    MutatingUse,
+    /// The place is only mentioned, like in `StorageLive` or in the debug info of a variable.
+    NonUse,
+}
+
+/// Where a type appears.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TyContext {
+    /// The type of a local.
+    LocalDecl(Local),
+    /// The type of a fragment of a variable in the debug info.
+    VarDebugInfo,
+    /// A type in a statement or terminator.
+    Location(Location),
+}
+
+pub trait MirVisitor {
+    fn visit_body(&mut self, body: &Body) {
+        self.super_body(body)
+    }
+
+    fn visit_basic_block(&mut self, block: &BasicBlock, index: usize) {
+        self.super_basic_block(block, index)
+    }
+
+    fn visit_statement(&mut self, statement: &Statement, location: Location) {
+        self.super_statement(statement, location)
+    }
+
+    fn visit_terminator(&mut self, terminator: &Terminator, location: Location) {
+        self.super_terminator(terminator, location)
+    }
+
+    fn visit_assert_msg(&mut self, msg: &AssertMessage, location: Location) {
+        self.super_assert_msg(msg, location)
+    }
+
+    fn visit_rvalue(&mut self, rvalue: &Rvalue, location: Location) {
+        self.super_rvalue(rvalue, location)
+    }
+
+    fn visit_operand(&mut self, operand: &Operand, location: Location) {
+        self.super_operand(operand, location)
+    }
+
+    fn visit_place(&mut self, place: &Place, ptx: PlaceContext, location: Location) {
+        self.super_place(place, ptx, location)
+    }
+
+    fn visit_projection_elem(
+        &mut self,
+        elem: &ProjectionElem,
+        ptx: PlaceContext,
+        location: Location,
+    ) {
+        self.super_projection_elem(elem, ptx, location)
+    }
+
+    fn visit_local(&mut self, _local: &Local, _ptx: PlaceContext, _location: Location) {}
+
+    fn visit_local_decl(&mut self, local: Local, ty: &Ty) {
+        self.super_local_decl(local, ty)
+    }
+
+    fn visit_var_debug_info(&mut self, var_debug_info: &VarDebugInfo) {
+        self.super_var_debug_info(var_debug_info)
+    }
+
+    fn visit_constant(&mut self, constant: &Constant, location: Location) {
+        self.super_constant(constant, location)
+    }
+
+    fn visit_const(&mut self, constant: &Const, location: Location) {
+        self.super_const(constant, location)
+    }
+
+    fn visit_args(&mut self, args: &GenericArgs, location: Location) {
+        self.super_args(args, location)
+    }
+
+    /// Types are interned, their contents can be walked with a
+    /// [`Visitor`](crate::stable_mir::visitor::Visitor).
+    fn visit_ty(&mut self, _ty: &Ty, _context: TyContext) {}
+
+    fn visit_region(&mut self, _region: &Region, _location: Location) {}
+
+    fn visit_span(&mut self, _span: &Span) {}
+
+    fn super_body(&mut self, body: &Body) {
+        for (index, block) in body.blocks.iter().enumerate() {
+            self.visit_basic_block(block, index);
+        }
+        for (local, ty) in body.locals.iter().enumerate() {
+            self.visit_local_decl(local, ty);
+        }
+        for var_debug_info in &body.var_debug_info {
+            self.visit_var_debug_info(var_debug_info);
+        }
+        self.visit_span(&body.span);
+    }
+
+    fn super_basic_block(&mut self, block: &BasicBlock, index: usize) {
+        for (statement_index, statement) in block.statements.iter().enumerate() {
+            self.visit_statement(statement, Location { block: index, statement_index });
+        }
+        let location = Location { block: index, statement_index: block.statements.len() };
+        self.visit_terminator(&block.terminator, location);
+    }
+
+    fn super_statement(&mut self, statement: &Statement, location: Location) {
+        self.visit_span(&statement.span);
+        match &statement.kind {
+            StatementKind::Assign(place, rvalue) => {
+                self.visit_place(place, PlaceContext::MutatingUse, location);
+                self.visit_rvalue(rvalue, location);
+            }
+            StatementKind::FakeRead(_, place) => {
+                self.visit_place(place, PlaceContext::NonMutatingUse, location);
+            }
+            StatementKind::SetDiscriminant { place, .. }
+            | StatementKind::Deinit(place)
+            | StatementKind::Retag(_, place) => {
+                self.visit_place(place, PlaceContext::MutatingUse, location);
+            }
+            StatementKind::PlaceMention(place) | StatementKind::AscribeUserType { place, .. } => {
+                self.visit_place(place, PlaceContext::NonUse, location);
+            }
+            StatementKind::StorageLive(local) | StatementKind::StorageDead(local) => {
+                self.visit_local(local, PlaceContext::NonUse, location);
+            }
+            StatementKind::Intrinsic(NonDivergingIntrinsic::Assume(operand)) => {
+                self.visit_operand(operand, location);
+            }
+            StatementKind::Intrinsic(NonDivergingIntrinsic::CopyNonOverlapping(copy)) => {
+                self.visit_operand(&copy.src, location);
+                self.visit_operand(&copy.dst, location);
+                self.visit_operand(&copy.count, location);
+            }
+            StatementKind::Coverage(_) | StatementKind::ConstEvalCounter | StatementKind::Nop => {}
+        }
+    }
+
+    fn super_terminator(&mut self, terminator: &Terminator, location: Location) {
+        self.visit_span(&terminator.span);
+        match &terminator.kind {
+            TerminatorKind::Goto { .. }
+            | TerminatorKind::Resume
+            | TerminatorKind::Abort
+            | TerminatorKind::Return
+            | TerminatorKind::Unreachable
+            | TerminatorKind::GeneratorDrop
+            | TerminatorKind::FalseEdge { .. }
+            | TerminatorKind::FalseUnwind { .. } => {}
+            TerminatorKind::SwitchInt { discr, .. } => self.visit_operand(discr, location),
+            TerminatorKind::Drop { place, .. } => {
+                self.visit_place(place, PlaceContext::MutatingUse, location);
+            }
+            TerminatorKind::Call { func, args, destination, .. } => {
+                self.visit_operand(func, location);
+                for arg in args {
+                    self.visit_operand(arg, location);
+                }
+                self.visit_place(destination, PlaceContext::MutatingUse, location);
+            }
+            TerminatorKind::Assert { cond, msg, .. } => {
+                self.visit_operand(cond, location);
+                self.visit_assert_msg(msg, location);
+            }
+            TerminatorKind::Yield { value, resume_arg, .. } => {
+                self.visit_operand(value, location);
+                self.visit_place(resume_arg, PlaceContext::MutatingUse, location);
+            }
+            TerminatorKind::InlineAsm { operands, .. } => {
+                for operand in operands {
+                    match operand {
+                        InlineAsmOperand::In { value, .. } => self.visit_operand(value, location),
+                        InlineAsmOperand::Out { place, .. } => {
+                            if let Some(place) = place {
+                                self.visit_place(place, PlaceContext::MutatingUse, location);
+                            }
+                        }
+                        InlineAsmOperand::InOut { in_value, out_place, .. } => {
+                            self.visit_operand(in_value, location);
+                            if let Some(place) = out_place {
+                                self.visit_place(place, PlaceContext::MutatingUse, location);
+                            }
+                        }
+                        InlineAsmOperand::Const { value } | InlineAsmOperand::SymFn { value } => {
+                            self.visit_constant(value, location);
+                        }
+                        InlineAsmOperand::SymStatic { .. } => {}
+                    }
+                }
+            }
+        }
+    }
+
+    fn super_assert_msg(&mut self, msg: &AssertMessage, location: Location) {
+        match msg {
+            AssertMessage::BoundsCheck { len, index } => {
+                self.visit_operand(len, location);
+                self.visit_operand(index, location);
+            }
+            AssertMessage::Overflow(_, left, right) => {
+                self.visit_operand(left, location);
+                self.visit_operand(right, location);
+            }
+            AssertMessage::OverflowNeg(operand)
+            | AssertMessage::DivisionByZero(operand)
+            | AssertMessage::RemainderByZero(operand) => self.visit_operand(operand, location),
+            AssertMessage::ResumedAfterReturn(_) | AssertMessage::ResumedAfterPanic(_) => {}
+            AssertMessage::MisalignedPointerDereference { required, found } => {
+                self.visit_operand(required, location);
+                self.visit_operand(found, location);
+            }
+        }
+    }
+
+    fn super_rvalue(&mut self, rvalue: &Rvalue, location: Location) {
+        match rvalue {
+            Rvalue::AddressOf(mutability, place) => {
+                let ptx = match mutability {
+                    Mutability::Not => PlaceContext::NonMutatingUse,
+                    Mutability::Mut => PlaceContext::MutatingUse,
+                };
+                self.visit_place(place, ptx, location);
+            }
+            Rvalue::Aggregate(kind, operands) => {
+                match kind {
+                    AggregateKind::Array(ty) => {
+                        self.visit_ty(ty, TyContext::Location(location));
+                    }
+                    AggregateKind::Tuple => {}
+                    AggregateKind::Adt(_, _, args, _, _)
+                    | AggregateKind::Closure(_, args)
+                    | AggregateKind::Generator(_, args, _) => self.visit_args(args, location),
+                }
+                for operand in operands {
+                    self.visit_operand(operand, location);
+                }
+            }
+            Rvalue::BinaryOp(_, left, right) | Rvalue::CheckedBinaryOp(_, left, right) => {
+                self.visit_operand(left, location);
+                self.visit_operand(right, location);
+            }
+            Rvalue::Cast(_, operand, ty) | Rvalue::ShallowInitBox(operand, ty) => {
+                self.visit_operand(operand, location);
+                self.visit_ty(ty, TyContext::Location(location));
+            }
+            Rvalue::CopyForDeref(place) | Rvalue::Discriminant(place) | Rvalue::Len(place) => {
+                self.visit_place(place, PlaceContext::NonMutatingUse, location);
+            }
+            Rvalue::Ref(region, kind, place) => {
+                self.visit_region(region, location);
+                let ptx = match kind {
+                    BorrowKind::Shared | BorrowKind::Shallow => PlaceContext::NonMutatingUse,
+                    BorrowKind::Mut { .. } => PlaceContext::MutatingUse,
+                };
+                self.visit_place(place, ptx, location);
+            }
+            Rvalue::Repeat(operand, count) => {
+                self.visit_operand(operand, location);
+                self.visit_const(count, location);
+            }
+            Rvalue::ThreadLocalRef(_) => {}
+            Rvalue::NullaryOp(_, ty) => self.visit_ty(ty, TyContext::Location(location)),
+            Rvalue::UnaryOp(_, operand) | Rvalue::Use(operand) => {
+                self.visit_operand(operand, location);
+            }
+        }
+    }
+
+    fn super_operand(&mut self, operand: &Operand, location: Location) {
+        match operand {
+            Operand::Copy(place) | Operand::Move(place) => {
+                self.visit_place(place, PlaceContext::NonMutatingUse, location);
+            }
+            Operand::Constant(constant) => self.visit_constant(constant, location),
+        }
+    }
+
+    fn super_place(&mut self, place: &Place, ptx: PlaceContext, location: Location) {
+        self.visit_local(&place.local, ptx, location);
+        for elem in &place.projection {
+            self.visit_projection_elem(elem, ptx, location);
+        }
+    }
+
+    fn super_projection_elem(
+        &mut self,
+        elem: &ProjectionElem,
+        _ptx: PlaceContext,
+        location: Location,
+    ) {
+        match elem {
+            ProjectionElem::Field(_, ty) | ProjectionElem::OpaqueCast(ty) => {
+                self.visit_ty(ty, TyContext::Location(location));
+            }
+            // The index is read, whatever is done with the place.
+            ProjectionElem::Index(local) => {
+                self.visit_local(local, PlaceContext::NonMutatingUse, location);
+            }
+            ProjectionElem::Deref
+            | ProjectionElem::ConstantIndex { .. }
+            | ProjectionElem::Subslice { .. }
+            | ProjectionElem::Downcast(_) => {}
+        }
+    }
+
+    fn super_local_decl(&mut self, local: Local, ty: &Ty) {
+        self.visit_ty(ty, TyContext::LocalDecl(local));
+    }
+
+    fn super_var_debug_info(&mut self, var_debug_info: &VarDebugInfo) {
+        let location = Location::START;
+        self.visit_span(&var_debug_info.source_info.span);
+        if let Some(composite) = &var_debug_info.composite {
+            self.visit_ty(&composite.ty, TyContext::VarDebugInfo);
+            for (_, ty) in &composite.projection {
+                self.visit_ty(ty, TyContext::VarDebugInfo);
+            }
+        }
+        match &var_debug_info.value {
+            VarDebugInfoContents::Place(place) => {
+                self.visit_place(place, PlaceContext::NonUse, location);
+            }
+            VarDebugInfoContents::Const(constant) => self.visit_constant(constant, location),
+        }
+    }
+
+    fn super_constant(&mut self, constant: &Constant, location: Location) {
+        self.visit_span(&constant.span);
+        self.visit_const(&constant.literal, location);
+    }
+
+    fn super_const(&mut self, constant: &Const, location: Location) {
+        self.visit_ty(&constant.ty, TyContext::Location(location));
+    }
+
+    fn super_args(&mut self, args: &GenericArgs, location: Location) {
+        for arg in &args.0 {
+            match arg {
+                GenericArgKind::Lifetime(region) => self.visit_region(region, location),
+                GenericArgKind::Type(ty) => self.visit_ty(ty, TyContext::Location(location)),
+                GenericArgKind::Const(constant) => self.visit_const(constant, location),
+            }
+        }
+    }
+}
@@ -14,17 +14,36 @@
 use std::cell::Cell;
 use std::fmt;
 use std::fmt::Debug;
+use std::ops::RangeInclusive;
 
+use self::abi::{FnAbi, Layout};
+use self::mir::mono::{Instance, InstanceDef};
+use self::mir::VariantIdx;
 use self::ty::{
-    GenericPredicates, Generics, ImplDef, ImplTrait, Span, TraitDecl, TraitDef, Ty, TyKind,
+    AdtDef, AdtKind, AliasTy, Allocation, AssocItem, CapturedPlace, Const, ConstEvalError, Discr,
+    FieldDrop, Filename, FnDef, GeneratorDef, GeneratorLayout, GenericArgs, GenericPredicates,
+    Generics, ImplDef, ImplTrait, LineInfo, ParamEnv, PolyFnSig, ReprOptions, Span, StaticDef,
+    TraitDecl, TraitDef, Ty, TyKind, VariantDef,
 };
 use crate::rustc_smir::Tables;
 
+pub mod abi;
 pub mod fold;
 pub mod mir;
+pub mod serialize;
 pub mod ty;
 pub mod visitor;
 
+pub use self::serialize::{deserialize, serialize};
+
+/// The versions of the compiler interface that this version of `stable_mir` knows how to talk to,
+/// and whose snapshots can be loaded by [`deserialize`].
+///
+/// The compiler interface version is bumped by `rustc_smir` whenever a change to the `Context`
+/// trait or to the data structures it returns would make older consumers misbehave. It is
+/// checked when the context is installed by [`run`].
+pub const SUPPORTED_INTERFACE_VERSIONS: RangeInclusive<u32> = 7..=7;
+
 /// Use String for now but we should replace it.
 pub type Symbol = String;
 
@@ -71,6 +90,30 @@ pub enum CompilerError<T> {
     Skipped,
 }
 
+/// An error returned by the stable MIR APIs, like when a value doesn't have the expected kind.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Error(String);
+
+impl Error {
+    pub(crate) fn new(msg: String) -> Self {
+        Error(msg)
+    }
+}
+
+impl Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
 /// Holds information about a crate.
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Crate {
@@ -93,6 +136,163 @@ pub fn body(&self) -> mir::Body {
     pub fn span(&self) -> Span {
         with(|cx| cx.span_of_an_item(self.0))
     }
+
+    /// The path of the item as printed in diagnostics, like `std::mem::swap`.
+    pub fn name(&self) -> String {
+        with(|cx| cx.name_of_def_id(self.0))
+    }
+
+    /// The full path of the item, starting with the name of its crate, and with the
+    /// disambiguators of the items which don't have a name, like
+    /// `input::{impl#0}::new::{closure#0}`. Unlike the [`name`](Self::name), it is unique.
+    pub fn def_path(&self) -> String {
+        with(|cx| cx.def_path(self.0))
+    }
+
+    /// The attributes of the item, in the order they are written, except its doc comments.
+    ///
+    /// The items of other crates only have the attributes kept in the metadata of their crate.
+    pub fn attrs(&self) -> Vec<Attribute> {
+        with(|cx| cx.item_attrs(self.0))
+    }
+
+    /// Where the item can be named from.
+    pub fn visibility(&self) -> Visibility {
+        with(|cx| cx.visibility(self.0))
+    }
+
+    /// How the item is exposed to the linker: whether it is exported from the crate, and the
+    /// attributes controlling its symbol.
+    pub fn linkage_info(&self) -> LinkageInfo {
+        with(|cx| cx.linkage_info(self.0))
+    }
+
+    /// The results of the borrow checker for the body of the item.
+    ///
+    /// They are only available if the compiler was run with
+    /// [`StableMir::with_borrowck_facts`](crate::rustc_internal::StableMir::with_borrowck_facts),
+    /// which computes them for every body of the local crate before the analysis. The constants
+    /// and `const fn`s evaluated while type checking the bodies before them have no facts.
+    pub fn borrowck_facts(&self) -> Result<mir::borrowck::BorrowckFacts, Error> {
+        with(|cx| cx.borrowck_facts(self.0))
+    }
+
+    /// The environment of the item, in which the types of its signature and body can be
+    /// normalized. Opaque types, like `impl Trait`, are not revealed.
+    pub fn param_env(&self) -> ParamEnv {
+        with(|cx| cx.param_env(self.0))
+    }
+
+    /// The static this item is, if it is one, like the items of kind [`ItemKind::Static`].
+    pub fn static_def(&self) -> Option<StaticDef> {
+        with(|cx| cx.static_of_item(self.0))
+    }
+}
+
+/// How an item is exposed to the linker, see [`CrateItem::linkage_info`].
+///
+/// Items which don't have a symbol, like constants, have the default value: not exported and
+/// without any attribute.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LinkageInfo {
+    /// The level at which the item is exported from its crate, if it is. This is only known for
+    /// crates which are code generated: items of crates which are only checked are never exported.
+    pub export_level: Option<SymbolExportLevel>,
+    /// The name of the symbol of the item, if it has one: generic items don't have a symbol until
+    /// they are instantiated.
+    pub symbol_name: Option<Symbol>,
+    /// Whether the item has the `#[no_mangle]` attribute.
+    pub no_mangle: bool,
+    /// The symbol name given with `#[export_name = "..."]`.
+    pub export_name: Option<Symbol>,
+    /// Whether the item must be kept by the compiler or the linker (`#[used]`).
+    pub used: Option<UsedBy>,
+    /// The linkage given with `#[linkage = "..."]`.
+    pub linkage: Option<Linkage>,
+    /// The section given with `#[link_section = "..."]`.
+    pub link_section: Option<Symbol>,
+}
+
+/// The level at which a symbol is exported from a crate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SymbolExportLevel {
+    /// Exported from C dynamic libraries, like `#[no_mangle]` functions.
+    C,
+    /// Only exported to other Rust crates.
+    Rust,
+}
+
+/// Who a `#[used]` item is kept for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UsedBy {
+    /// `#[used(compiler)]`, and `#[used]` on most targets: the item is kept in the object file.
+    Compiler,
+    /// `#[used(linker)]`: the item is also kept by the linker.
+    Linker,
+}
+
+/// An attribute of an item, like `#[inline(always)]`, see [`CrateItem::attrs`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Attribute {
+    /// The segments of the path of the attribute, like `["inline"]`, or `["rustfmt", "skip"]`
+    /// for a tool attribute.
+    pub path: Vec<Symbol>,
+    pub args: AttrArgs,
+    pub span: Span,
+}
+
+/// The arguments of an [`Attribute`], as written.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AttrArgs {
+    /// `#[attr]`.
+    Empty,
+    /// `#[attr(...)]`, with the tokens between the delimiters, like `always` for
+    /// `#[inline(always)]`.
+    Delimited(String),
+    /// `#[attr = value]`, with the value, like `"name"` for `#[export_name = "name"]`.
+    Eq(String),
+}
+
+/// Where an item can be named from, see [`CrateItem::visibility`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Visibility {
+    Public,
+    /// The item can only be named from the module with the given path and its descendants, like
+    /// `input` for a `pub(crate)` item of the crate `input`, or the parent module of a private
+    /// item. The path is formatted like [`CrateItem::def_path`].
+    ///
+    /// The items which aren't declared with a visibility, like closures, are visible in their
+    /// module.
+    Restricted(String),
+}
+
+/// The linkage of a symbol, as given with `#[linkage = "..."]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Linkage {
+    External,
+    AvailableExternally,
+    LinkOnceAny,
+    LinkOnceODR,
+    WeakAny,
+    WeakODR,
+    Appending,
+    Internal,
+    Private,
+    ExternalWeak,
+    Common,
+}
+
+/// The kinds of items of the local crate which can be listed with [`items_of_kind`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ItemKind {
+    /// Functions and methods with a body.
+    Fn,
+    Static,
+    /// Constants and associated constants.
+    Const,
+    /// Inherent and trait implementations.
+    Impl,
+    Trait,
 }
 
 /// Return the function where execution starts if the current
@@ -138,7 +338,51 @@ pub fn trait_impl(trait_impl: &ImplDef) -> ImplTrait {
     with(|cx| cx.trait_impl(trait_impl))
 }
 
+/// Retrieve the implementations of a trait, from the local crate and from the external crates.
+pub fn trait_impls_of(trait_def: &TraitDef) -> ImplTraitDecls {
+    with(|cx| cx.trait_impls_of(trait_def))
+}
+
+/// Whether `ty` implements the trait, where `args` are the arguments of the generic parameters of
+/// the trait other than `Self`.
+///
+/// This fails if the type or the arguments depend on generic parameters, or if the trait solver
+/// can't decide.
+pub fn implements_trait(ty: Ty, trait_def: &TraitDef, args: &GenericArgs) -> Result<bool, Error> {
+    with(|cx| cx.implements_trait(ty, trait_def, args))
+}
+
+/// Retrieve the functions, constants and types declared in a trait, in the order of declaration.
+pub fn associated_items(trait_def: &TraitDef) -> Vec<AssocItem> {
+    with(|cx| cx.associated_items(trait_def))
+}
+
+/// Resolve a call to the function `def` instantiated with `args` to the function whose body is
+/// run, e.g. to the method of the implementation of a trait for a call to a trait method.
+///
+/// Returns `None` if the function is only known at runtime, like a method of a trait object, or if
+/// its body is generated by the compiler. See [`Instance::resolve`] for the errors.
+pub fn resolve_call(def: FnDef, args: &GenericArgs) -> Result<Option<FnDef>, Error> {
+    with(|cx| cx.resolve_call(def, args))
+}
+
+/// Retrieve the items of the local crate of the given kind. Only functions, statics and
+/// constants have a body.
+pub fn items_of_kind(kind: ItemKind) -> CrateItems {
+    with(|cx| cx.items_of_kind(kind))
+}
+
+/// Return the version of the interface implemented by the compiler, so that tools can report
+/// which compiler they are running against.
+pub fn compiler_interface_version() -> u32 {
+    with(|cx| cx.interface_version())
+}
+
 pub trait Context {
+    /// The version of the interface implemented by this context.
+    /// See [`SUPPORTED_INTERFACE_VERSIONS`].
+    fn interface_version(&self) -> u32;
+
     fn entry_fn(&mut self) -> Option<CrateItem>;
     /// Retrieve all items of the local crate that have a MIR associated with them.
     fn all_local_items(&mut self) -> CrateItems;
@@ -147,6 +391,19 @@ pub trait Context {
     fn trait_decl(&mut self, trait_def: &TraitDef) -> TraitDecl;
     fn all_trait_impls(&mut self) -> ImplTraitDecls;
     fn trait_impl(&mut self, trait_impl: &ImplDef) -> ImplTrait;
+    /// Retrieve the implementations of a trait in all crates.
+    fn trait_impls_of(&mut self, trait_def: &TraitDef) -> ImplTraitDecls;
+    /// Whether a type implements a trait, according to the trait solver.
+    fn implements_trait(
+        &mut self,
+        ty: Ty,
+        trait_def: &TraitDef,
+        args: &GenericArgs,
+    ) -> Result<bool, Error>;
+    /// Retrieve the items declared in a trait.
+    fn associated_items(&mut self, trait_def: &TraitDef) -> Vec<AssocItem>;
+    /// Retrieve the items of the local crate of the given kind.
+    fn items_of_kind(&mut self, kind: ItemKind) -> CrateItems;
     fn generics_of(&mut self, def_id: DefId) -> Generics;
     fn predicates_of(&mut self, def_id: DefId) -> GenericPredicates;
     fn explicit_predicates_of(&mut self, def_id: DefId) -> GenericPredicates;
@@ -164,12 +421,139 @@ pub trait Context {
     /// `Span` of an item
     fn span_of_an_item(&mut self, def_id: DefId) -> Span;
 
+    /// The name of the file a span points into.
+    fn get_filename(&mut self, span: &Span) -> Filename;
+
+    /// The lines and columns a span starts and ends at.
+    fn get_lines(&mut self, span: &Span) -> LineInfo;
+
+    /// The source code a span points at.
+    fn source_text(&mut self, span: &Span) -> Option<String>;
+
+    /// How an item is exposed to the linker.
+    fn linkage_info(&mut self, def_id: DefId) -> LinkageInfo;
+
+    /// The full path of an item, with the name of its crate and the disambiguators of the items
+    /// without a name.
+    fn def_path(&mut self, def_id: DefId) -> String;
+
+    /// The attributes of an item, without its doc comments.
+    fn item_attrs(&mut self, def_id: DefId) -> Vec<Attribute>;
+
+    /// Where an item can be named from.
+    fn visibility(&mut self, def_id: DefId) -> Visibility;
+
+    /// The results of the borrow checker for the body of an item.
+    fn borrowck_facts(&mut self, def_id: DefId) -> Result<mir::borrowck::BorrowckFacts, Error>;
+
     /// Obtain the representation of a type.
     fn ty_kind(&mut self, ty: Ty) -> TyKind;
 
     /// Create a new `Ty` from scratch without information from rustc.
     fn mk_ty(&mut self, kind: TyKind) -> Ty;
 
+    /// The type as printed in the MIR dumps of the compiler, with full paths.
+    fn ty_pretty(&mut self, ty: Ty) -> String;
+
+    /// The value of the constant as printed in the MIR dumps of the compiler.
+    fn const_pretty(&mut self, cnst: &Const) -> String;
+
+    /// The environment of an item.
+    fn param_env(&mut self, def_id: DefId) -> ParamEnv;
+
+    /// Normalize a type in the given environment.
+    fn normalize_ty(&mut self, env: ParamEnv, ty: Ty) -> Result<Ty, Error>;
+
+    /// Normalize and evaluate a constant in the given environment.
+    fn normalize_const(&mut self, env: ParamEnv, cnst: &Const) -> Result<Const, Error>;
+
+    /// Normalize and evaluate a constant in the given environment, failing if it can't be
+    /// evaluated.
+    fn eval_const(&mut self, env: ParamEnv, cnst: &Const) -> Result<Const, ConstEvalError>;
+
+    /// Evaluate a constant of type `usize`, like the length of an array type.
+    fn eval_target_usize(&mut self, cnst: &Const) -> Result<u64, Error>;
+
+    /// Read an integer of `size` bytes at `offset` in an allocation.
+    fn read_scalar(
+        &mut self,
+        alloc: &Allocation,
+        offset: usize,
+        size: usize,
+    ) -> Result<u128, Error>;
+
+    /// Whether values of the given type need to be dropped.
+    fn needs_drop(&mut self, ty: Ty) -> Result<bool, Error>;
+
+    /// The body of the drop glue of the given type, if it needs to be dropped.
+    fn drop_glue_body(&mut self, ty: Ty) -> Result<Option<mir::Body>, Error>;
+
+    /// The fields of the given type, in drop order, and whether they need to be dropped.
+    fn field_drops(&mut self, ty: Ty) -> Result<Vec<FieldDrop>, Error>;
+
+    /// The layout of a type.
+    fn ty_layout(&mut self, ty: Ty) -> Result<Layout, Error>;
+
+    /// Whether an ADT is a struct, an enum or a union.
+    fn adt_kind(&mut self, def: AdtDef) -> AdtKind;
+
+    /// The variants of an ADT and their fields.
+    fn adt_variants(&mut self, def: AdtDef) -> Vec<VariantDef>;
+
+    /// The `#[repr]` attributes of an ADT.
+    fn adt_repr(&mut self, def: AdtDef) -> ReprOptions;
+
+    /// The discriminant of a variant of an enum.
+    fn adt_discriminant(&mut self, def: AdtDef, variant: VariantIdx) -> Result<Discr, Error>;
+
+    /// Instantiate the generic parameters of a type with the given arguments.
+    fn instantiate_ty(&mut self, ty: Ty, args: &GenericArgs) -> Result<Ty, Error>;
+
+    /// The signature of a function.
+    fn fn_sig(&mut self, def: FnDef) -> PolyFnSig;
+
+    /// The hidden type of an opaque type.
+    fn opaque_hidden_type(&mut self, alias: &AliasTy) -> Result<Ty, Error>;
+
+    /// The places captured by a closure or a generator.
+    fn closure_captures(&mut self, def_id: DefId) -> Result<Vec<CapturedPlace>, Error>;
+
+    /// The locals saved in the state of a generator.
+    fn generator_layout(&mut self, def: GeneratorDef) -> Result<GeneratorLayout, Error>;
+
+    /// Resolve a function instantiated with the given arguments to an instance.
+    fn resolve_instance(&mut self, def: FnDef, args: &GenericArgs) -> Result<Instance, Error>;
+
+    /// Resolve a call to a function to the function whose body is run, if it is known statically.
+    fn resolve_call(&mut self, def: FnDef, args: &GenericArgs) -> Result<Option<FnDef>, Error>;
+
+    /// The monomorphized body of an instance.
+    fn instance_body(&mut self, def: InstanceDef) -> Result<mir::Body, Error>;
+
+    /// The type of an instance.
+    fn instance_ty(&mut self, def: InstanceDef) -> Ty;
+
+    /// How an instance is called.
+    fn instance_fn_abi(&mut self, def: InstanceDef) -> Result<FnAbi, Error>;
+
+    /// The static item an allocation is the memory of, if it is one.
+    fn alloc_static(&mut self, alloc: AllocId) -> Option<CrateItem>;
+
+    /// Whether a static can be written to: a `static mut`, or a static with interior mutability.
+    fn static_is_mutable(&mut self, item: DefId) -> bool;
+
+    /// The static an item is, if it is one.
+    fn static_of_item(&mut self, item: DefId) -> Option<StaticDef>;
+
+    /// The type of a static.
+    fn static_ty(&mut self, def: StaticDef) -> Ty;
+
+    /// The body computing the initial value of a static.
+    fn static_initializer_body(&mut self, def: StaticDef) -> Result<mir::Body, Error>;
+
+    /// Evaluate the initial value of a static.
+    fn static_allocation(&mut self, def: StaticDef) -> Result<Allocation, Error>;
+
     /// HACK: Until we have fully stable consumers, we need an escape hatch
     /// to get `DefId`s out of `CrateItem`s.
     fn rustc_tables(&mut self, f: &mut dyn FnMut(&mut Tables<'_>));
@@ -179,8 +563,15 @@ pub trait Context {
 // datastructures and stable MIR datastructures
 scoped_thread_local! (static TLV: Cell<*mut ()>);
 
+/// Installs `context` while `f` runs, so that the functions of this module can be called.
+///
+/// Panics if the context implements a version of the interface which is not supported.
 pub fn run(mut context: impl Context, f: impl FnOnce()) {
     assert!(!TLV.is_set());
+    let version = context.interface_version();
+    if !SUPPORTED_INTERFACE_VERSIONS.contains(&version) {
+        unsupported_interface_version(version);
+    }
     fn g<'a>(mut context: &mut (dyn Context + 'a), f: impl FnOnce()) {
         let ptr: *mut () = &mut context as *mut &mut _ as _;
         TLV.set(&Cell::new(ptr), || {
@@ -200,3 +591,15 @@ pub(crate) fn with<R>(f: impl FnOnce(&mut dyn Context) -> R) -> R {
         f(unsafe { *(ptr as *mut &mut dyn Context) })
     })
 }
+
+#[cold]
+#[inline(never)]
+fn unsupported_interface_version(version: u32) -> ! {
+    panic!(
+        "the compiler implements version {version} of the stable MIR interface, but this version \
+         of `stable_mir` only supports versions {} to {}; use a `stable_mir` release matching \
+         the compiler",
+        SUPPORTED_INTERFACE_VERSIONS.start(),
+        SUPPORTED_INTERFACE_VERSIONS.end(),
+    )
+}
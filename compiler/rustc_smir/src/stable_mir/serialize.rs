@@ -0,0 +1,780 @@
+//! Saving the MIR of crate items to bytes, so it can be analyzed without running the compiler.
+//!
+//! [`serialize`] writes the bodies of a list of items, along with the types, definitions and
+//! spans they refer to, and [`deserialize`] loads them back as a [`Snapshot`]. A snapshot
+//! starts with a header recording the version of its format and the version of the compiler
+//! interface which produced it, which are checked when it is loaded.
+//!
+//! The handles of a snapshot, like [`Ty`], [`DefId`] and [`Span`], are indices into the tables of
+//! the snapshot: they must be resolved with the methods of [`Snapshot`], not with their own
+//! methods, which query the compiler and would return information about unrelated values.
+
+use std::collections::HashMap;
+use std::mem;
+
+use super::mir::*;
+use super::ty::*;
+use super::{
+    compiler_interface_version, local_crate, with, AllocId, CrateItem, DefId, Error, Symbol,
+    SUPPORTED_INTERFACE_VERSIONS,
+};
+
+/// The bytes every snapshot starts with.
+const MAGIC: &[u8; 4] = b"SMIR";
+
+/// The version of the format of snapshots, which is bumped whenever the encoding of one of the
+/// data structures changes. Snapshots in another format are rejected by [`deserialize`].
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 3;
+
+/// The MIR of a list of items, loaded with [`deserialize`].
+#[derive(Clone)]
+pub struct Snapshot {
+    /// The name of the crate the items were taken from.
+    pub crate_name: Symbol,
+    /// The version of the compiler interface which produced the snapshot.
+    pub interface_version: u32,
+    pub items: Vec<ItemSnapshot>,
+    types: Vec<TyKind>,
+    def_paths: Vec<String>,
+    spans: Vec<String>,
+}
+
+/// An item of a [`Snapshot`].
+#[derive(Clone)]
+pub struct ItemSnapshot {
+    /// The path of the item, like `std::option::Option::<T>::unwrap`.
+    pub name: String,
+    pub span: Span,
+    pub body: Body,
+}
+
+impl Snapshot {
+    /// The kind of a type of the snapshot.
+    pub fn ty_kind(&self, ty: Ty) -> &TyKind {
+        &self.types[ty.0]
+    }
+
+    /// The path of a definition of the snapshot.
+    pub fn def_path(&self, def_id: DefId) -> &str {
+        &self.def_paths[def_id.0]
+    }
+
+    /// The path of a function of the snapshot, like the callee of a `Call` terminator.
+    pub fn fn_path(&self, def: FnDef) -> &str {
+        self.def_path(def.0)
+    }
+
+    /// The path of an item of the snapshot, like the static of a `ThreadLocalRef`.
+    pub fn item_path(&self, item: &CrateItem) -> &str {
+        self.def_path(item.0)
+    }
+
+    /// The location of a span of the snapshot in the source code, like `src/lib.rs:3:5: 3:12`.
+    pub fn span(&self, span: Span) -> &str {
+        &self.spans[span.0]
+    }
+}
+
+/// Saves the bodies of `items`, with the types, definitions and spans they refer to.
+pub fn serialize(items: &[CrateItem]) -> Vec<u8> {
+    let mut e = Encoder::default();
+    items.len().encode(&mut e);
+    for item in items {
+        with(|cx| cx.name_of_def_id(item.0)).encode(&mut e);
+        item.span().encode(&mut e);
+        item.body().encode(&mut e);
+    }
+    let items = mem::take(&mut e.data);
+
+    // The kinds of the types can refer to more types, which are added to the table while it is
+    // being encoded.
+    let mut index = 0;
+    while let Some(&ty) = e.types.items.get(index) {
+        ty.kind().encode(&mut e);
+        index += 1;
+    }
+    let types = mem::take(&mut e.data);
+
+    let def_paths: Vec<_> =
+        e.def_ids.items.iter().map(|&def_id| with(|cx| cx.name_of_def_id(def_id))).collect();
+    let spans: Vec<_> = e.spans.items.iter().map(|span| format!("{span:?}")).collect();
+
+    e.data.extend_from_slice(MAGIC);
+    e.data.extend_from_slice(&SNAPSHOT_FORMAT_VERSION.to_le_bytes());
+    e.data.extend_from_slice(&compiler_interface_version().to_le_bytes());
+    local_crate().name.encode(&mut e);
+    e.types.items.len().encode(&mut e);
+    def_paths.len().encode(&mut e);
+    spans.len().encode(&mut e);
+    for string in def_paths.iter().chain(&spans) {
+        string.encode(&mut e);
+    }
+    e.data.extend(types);
+    e.data.extend(items);
+    e.data
+}
+
+/// Loads a snapshot saved by [`serialize`]. This fails if the bytes are not a snapshot, or if
+/// the snapshot was saved in another format or by a compiler implementing a version of the
+/// interface which this version of `stable_mir` doesn't support.
+pub fn deserialize(bytes: &[u8]) -> Result<Snapshot, Error> {
+    let mut d = Decoder { data: bytes, position: 0, num_types: 0, num_def_ids: 0, num_spans: 0 };
+    if d.read_bytes(MAGIC.len())? != MAGIC {
+        return Err(Error::new("not a stable MIR snapshot".to_string()));
+    }
+    let format_version = d.read_u32_le()?;
+    if format_version != SNAPSHOT_FORMAT_VERSION {
+        return Err(Error::new(format!(
+            "the snapshot is in version {format_version} of the format, but this version of \
+             `stable_mir` only supports version {SNAPSHOT_FORMAT_VERSION}"
+        )));
+    }
+    let interface_version = d.read_u32_le()?;
+    if !SUPPORTED_INTERFACE_VERSIONS.contains(&interface_version) {
+        return Err(Error::new(format!(
+            "the snapshot was produced by a compiler implementing version {interface_version} of \
+             the stable MIR interface, but this version of `stable_mir` only supports versions \
+             {} to {}",
+            SUPPORTED_INTERFACE_VERSIONS.start(),
+            SUPPORTED_INTERFACE_VERSIONS.end(),
+        )));
+    }
+    let crate_name = String::decode(&mut d)?;
+    d.num_types = usize::decode(&mut d)?;
+    d.num_def_ids = usize::decode(&mut d)?;
+    d.num_spans = usize::decode(&mut d)?;
+
+    let def_paths = (0..d.num_def_ids).map(|_| String::decode(&mut d)).collect::<Result<_, _>>()?;
+    let spans = (0..d.num_spans).map(|_| String::decode(&mut d)).collect::<Result<_, _>>()?;
+    let types = (0..d.num_types).map(|_| TyKind::decode(&mut d)).collect::<Result<_, _>>()?;
+    let items = Vec::decode(&mut d)?;
+    if d.position != bytes.len() {
+        return Err(d.error("unexpected data after the end of the snapshot".to_string()));
+    }
+    Ok(Snapshot { crate_name, interface_version, items, types, def_paths, spans })
+}
+
+/// Maps the handles of the compiler to consecutive indices in a snapshot.
+struct Interner<T> {
+    items: Vec<T>,
+    indices: HashMap<usize, usize>,
+}
+
+impl<T> Default for Interner<T> {
+    fn default() -> Self {
+        Interner { items: Vec::new(), indices: HashMap::new() }
+    }
+}
+
+impl<T> Interner<T> {
+    fn intern(&mut self, key: usize, item: T) -> usize {
+        *self.indices.entry(key).or_insert_with(|| {
+            self.items.push(item);
+            self.items.len() - 1
+        })
+    }
+}
+
+#[derive(Default)]
+struct Encoder {
+    data: Vec<u8>,
+    types: Interner<Ty>,
+    def_ids: Interner<DefId>,
+    spans: Interner<Span>,
+    alloc_ids: Interner<AllocId>,
+}
+
+impl Encoder {
+    /// Writes an unsigned integer in the LEB128 encoding.
+    fn write_leb128(&mut self, mut value: u128) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                self.data.push(byte);
+                return;
+            }
+            self.data.push(byte | 0x80);
+        }
+    }
+}
+
+struct Decoder<'a> {
+    data: &'a [u8],
+    position: usize,
+    num_types: usize,
+    num_def_ids: usize,
+    num_spans: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn error(&self, msg: String) -> Error {
+        Error::new(format!("invalid stable MIR snapshot at byte {}: {msg}", self.position))
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let Some(bytes) = self.data.get(self.position..self.position.saturating_add(len)) else {
+            return Err(self.error("unexpected end of the snapshot".to_string()));
+        };
+        self.position += len;
+        Ok(bytes)
+    }
+
+    fn read_u32_le(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_le_bytes(self.read_bytes(4)?.try_into().unwrap()))
+    }
+
+    fn read_leb128(&mut self) -> Result<u128, Error> {
+        let mut value = 0;
+        let mut shift = 0;
+        loop {
+            if shift >= u128::BITS {
+                return Err(self.error("integer too large".to_string()));
+            }
+            let byte = self.read_bytes(1)?[0];
+            value |= u128::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+
+    /// Reads an index into a table of `len` elements.
+    fn read_index(&mut self, len: usize, table: &str) -> Result<usize, Error> {
+        let index = usize::decode(self)?;
+        if index >= len {
+            return Err(self.error(format!("{table} {index} out of range, there are only {len}")));
+        }
+        Ok(index)
+    }
+}
+
+trait Encodable {
+    fn encode(&self, e: &mut Encoder);
+}
+
+trait Decodable: Sized {
+    fn decode(d: &mut Decoder<'_>) -> Result<Self, Error>;
+}
+
+macro_rules! impl_integers {
+    ($($ty:ty),*) => {$(
+        impl Encodable for $ty {
+            fn encode(&self, e: &mut Encoder) {
+                e.write_leb128(*self as u128)
+            }
+        }
+
+        impl Decodable for $ty {
+            fn decode(d: &mut Decoder<'_>) -> Result<Self, Error> {
+                let value = d.read_leb128()?;
+                <$ty>::try_from(value).map_err(|_| {
+                    d.error(format!("{value} is out of the range of `{}`", stringify!($ty)))
+                })
+            }
+        }
+    )*};
+}
+
+impl_integers!(u8, u16, u32, u64, u128, usize);
+
+impl Encodable for bool {
+    fn encode(&self, e: &mut Encoder) {
+        e.data.push(*self as u8)
+    }
+}
+
+impl Decodable for bool {
+    fn decode(d: &mut Decoder<'_>) -> Result<Self, Error> {
+        match d.read_bytes(1)?[0] {
+            0 => Ok(false),
+            1 => Ok(true),
+            byte => Err(d.error(format!("invalid boolean {byte}"))),
+        }
+    }
+}
+
+impl Encodable for char {
+    fn encode(&self, e: &mut Encoder) {
+        (*self as u32).encode(e)
+    }
+}
+
+impl Decodable for char {
+    fn decode(d: &mut Decoder<'_>) -> Result<Self, Error> {
+        let value = u32::decode(d)?;
+        char::from_u32(value).ok_or_else(|| d.error(format!("invalid character {value}")))
+    }
+}
+
+impl Encodable for String {
+    fn encode(&self, e: &mut Encoder) {
+        self.len().encode(e);
+        e.data.extend_from_slice(self.as_bytes());
+    }
+}
+
+impl Decodable for String {
+    fn decode(d: &mut Decoder<'_>) -> Result<Self, Error> {
+        let len = usize::decode(d)?;
+        let bytes = d.read_bytes(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| d.error("invalid UTF-8 string".to_string()))
+    }
+}
+
+impl Encodable for () {
+    fn encode(&self, _e: &mut Encoder) {}
+}
+
+impl Decodable for () {
+    fn decode(_d: &mut Decoder<'_>) -> Result<Self, Error> {
+        Ok(())
+    }
+}
+
+impl<A: Encodable, B: Encodable> Encodable for (A, B) {
+    fn encode(&self, e: &mut Encoder) {
+        self.0.encode(e);
+        self.1.encode(e);
+    }
+}
+
+impl<A: Decodable, B: Decodable> Decodable for (A, B) {
+    fn decode(d: &mut Decoder<'_>) -> Result<Self, Error> {
+        Ok((A::decode(d)?, B::decode(d)?))
+    }
+}
+
+impl<T: Encodable> Encodable for Option<T> {
+    fn encode(&self, e: &mut Encoder) {
+        match self {
+            None => e.data.push(0),
+            Some(value) => {
+                e.data.push(1);
+                value.encode(e);
+            }
+        }
+    }
+}
+
+impl<T: Decodable> Decodable for Option<T> {
+    fn decode(d: &mut Decoder<'_>) -> Result<Self, Error> {
+        match d.read_bytes(1)?[0] {
+            0 => Ok(None),
+            1 => Ok(Some(T::decode(d)?)),
+            byte => Err(d.error(format!("invalid option {byte}"))),
+        }
+    }
+}
+
+impl<T: Encodable> Encodable for Vec<T> {
+    fn encode(&self, e: &mut Encoder) {
+        self.len().encode(e);
+        for element in self {
+            element.encode(e);
+        }
+    }
+}
+
+impl<T: Decodable> Decodable for Vec<T> {
+    fn decode(d: &mut Decoder<'_>) -> Result<Self, Error> {
+        let len = usize::decode(d)?;
+        // Don't trust the length to preallocate: each element takes at least one byte.
+        let mut elements = Vec::with_capacity(len.min(d.data.len() - d.position));
+        for _ in 0..len {
+            elements.push(T::decode(d)?);
+        }
+        Ok(elements)
+    }
+}
+
+impl Encodable for Ty {
+    fn encode(&self, e: &mut Encoder) {
+        e.types.intern(self.0, *self).encode(e)
+    }
+}
+
+impl Decodable for Ty {
+    fn decode(d: &mut Decoder<'_>) -> Result<Self, Error> {
+        Ok(Ty(d.read_index(d.num_types, "type")?))
+    }
+}
+
+impl Encodable for DefId {
+    fn encode(&self, e: &mut Encoder) {
+        e.def_ids.intern(self.0, *self).encode(e)
+    }
+}
+
+impl Decodable for DefId {
+    fn decode(d: &mut Decoder<'_>) -> Result<Self, Error> {
+        Ok(DefId(d.read_index(d.num_def_ids, "definition")?))
+    }
+}
+
+impl Encodable for Span {
+    fn encode(&self, e: &mut Encoder) {
+        e.spans.intern(self.0, *self).encode(e)
+    }
+}
+
+impl Decodable for Span {
+    fn decode(d: &mut Decoder<'_>) -> Result<Self, Error> {
+        Ok(Span(d.read_index(d.num_spans, "span")?))
+    }
+}
+
+impl Encodable for AllocId {
+    fn encode(&self, e: &mut Encoder) {
+        e.alloc_ids.intern(self.0, *self).encode(e)
+    }
+}
+
+impl Decodable for AllocId {
+    fn decode(d: &mut Decoder<'_>) -> Result<Self, Error> {
+        Ok(AllocId(usize::decode(d)?))
+    }
+}
+
+/// Implements the traits for structs, by encoding their fields in order. Tuple structs list
+/// their fields by index, like `FnDef { 0 }`.
+macro_rules! impl_structs {
+    ($($name:ident $(<$($param:ident),+>)? { $($field:tt),* $(,)? })*) => {$(
+        impl$(<$($param: Encodable),+>)? Encodable for $name$(<$($param),+>)? {
+            fn encode(&self, e: &mut Encoder) {
+                $(self.$field.encode(e);)*
+            }
+        }
+
+        impl$(<$($param: Decodable),+>)? Decodable for $name$(<$($param),+>)? {
+            fn decode(d: &mut Decoder<'_>) -> Result<Self, Error> {
+                Ok($name { $($field: Decodable::decode(d)?),* })
+            }
+        }
+    )*};
+}
+
+/// Implements the traits for enums, by encoding the index of the variant followed by its
+/// fields. The fields of tuple variants are given names to bind them, like `Ref(r, ty, m)`.
+macro_rules! impl_enums {
+    ($($name:ident $(<$($param:ident),+>)? {
+        $($variant:ident $(($($tuple:ident),+))? $({ $($field:ident),+ })?),* $(,)?
+    })*) => {$(
+        impl$(<$($param: Encodable),+>)? Encodable for $name$(<$($param),+>)? {
+            #[allow(unused_assignments)]
+            fn encode(&self, e: &mut Encoder) {
+                let mut tag = 0u32;
+                $(
+                    if let $name::$variant $(($($tuple),+))? $({ $($field),+ })? = self {
+                        tag.encode(e);
+                        $($($tuple.encode(e);)+)?
+                        $($($field.encode(e);)+)?
+                        return;
+                    }
+                    tag += 1;
+                )*
+            }
+        }
+
+        impl$(<$($param: Decodable),+>)? Decodable for $name$(<$($param),+>)? {
+            #[allow(unused_assignments)]
+            fn decode(d: &mut Decoder<'_>) -> Result<Self, Error> {
+                let tag = u32::decode(d)?;
+                let mut index = 0u32;
+                $(
+                    if tag == index {
+                        return Ok($name::$variant
+                            $(($({
+                                let $tuple = Decodable::decode(d)?;
+                                $tuple
+                            }),+))?
+                            $({ $($field: Decodable::decode(d)?),+ })?);
+                    }
+                    index += 1;
+                )*
+                Err(d.error(format!("invalid variant {tag} of `{}`", stringify!($name))))
+            }
+        }
+    )*};
+}
+
+impl_structs! {
+    ItemSnapshot { name, span, body }
+
+    CrateItem { 0 }
+    ForeignDef { 0 }
+    FnDef { 0 }
+    ClosureDef { 0 }
+    GeneratorDef { 0 }
+    ParamDef { 0 }
+    BrNamedDef { 0 }
+    AdtDef { 0 }
+    AliasDef { 0 }
+    TraitDef { 0 }
+    ConstDef { 0 }
+    Prov { 0 }
+    GenericArgs { 0 }
+
+    Body { blocks, locals, arg_count, var_debug_info, span }
+    BasicBlock { statements, terminator }
+    Terminator { kind, span }
+    InlineAsmOptions {
+        pure, nomem, readonly, preserves_flags, noreturn, nostack, att_syntax, raw, may_unwind
+    }
+    Coverage { kind, code_region }
+    CodeRegion { file_name, start_line, start_col, end_line, end_col }
+    CopyNonOverlapping { src, dst, count }
+    Statement { kind, span }
+    Place { local, projection }
+    UserTypeProjection { base, projection }
+    Constant { span, user_ty, literal }
+    VarDebugInfo { name, source_info, composite, value, argument_index }
+    SourceInfo { span, scope }
+    VarDebugInfoFragment { ty, projection }
+    SwitchTarget { value, target }
+
+    Const { literal, ty }
+    Region { kind }
+    EarlyBoundRegion { def_id, index, name }
+    BoundRegion { var, kind }
+    FreeRegion { scope, bound_region }
+    Placeholder<T> { universe, bound }
+    AliasTy { def_id, args }
+    FnSig { inputs_and_output, c_variadic, unsafety, abi }
+    Binder<T> { value, bound_vars }
+    ExistentialTraitRef { def_id, generic_args }
+    ExistentialProjection { def_id, generic_args, term }
+    ParamTy { index, name }
+    BoundTy { var, kind }
+    ProvenanceMap { ptrs }
+    Allocation { bytes, provenance, align, mutability }
+    ParamConst { index, name }
+    UnevaluatedConst { def, args, promoted }
+}
+
+impl_enums! {
+    TerminatorKind {
+        Goto { target },
+        SwitchInt { discr, targets, otherwise },
+        Resume,
+        Abort,
+        Return,
+        Unreachable,
+        Drop { place, target, unwind },
+        Call { func, args, destination, target, unwind },
+        Assert { cond, expected, msg, target, unwind },
+        Yield { value, resume, resume_arg, drop },
+        GeneratorDrop,
+        FalseEdge { real_target, imaginary_target },
+        FalseUnwind { real_target, unwind },
+        InlineAsm { template, operands, options, line_spans, destination, unwind },
+    }
+    InlineAsmTemplatePiece {
+        String(s),
+        Placeholder { operand_idx, modifier, span },
+    }
+    InlineAsmOperand {
+        In { reg, value },
+        Out { reg, late, place },
+        InOut { reg, late, in_value, out_place },
+        Const { value },
+        SymFn { value },
+        SymStatic { def },
+    }
+    InlineAsmRegOrRegClass { Reg(r), RegClass(c) }
+    UnwindAction { Continue, Unreachable, Terminate, Cleanup(bb) }
+    AssertMessage {
+        BoundsCheck { len, index },
+        Overflow(op, l, r),
+        OverflowNeg(op),
+        DivisionByZero(op),
+        RemainderByZero(op),
+        ResumedAfterReturn(kind),
+        ResumedAfterPanic(kind),
+        MisalignedPointerDereference { required, found },
+    }
+    BinOp {
+        Add, AddUnchecked, Sub, SubUnchecked, Mul, MulUnchecked, Div, Rem, BitXor, BitAnd, BitOr,
+        Shl, ShlUnchecked, Shr, ShrUnchecked, Eq, Lt, Le, Ne, Ge, Gt, Offset,
+    }
+    UnOp { Not, Neg }
+    GeneratorKind { Async(kind), Gen }
+    AsyncGeneratorKind { Block, Closure, Fn }
+    CoverageKind {
+        Counter { function_source_hash, id },
+        Expression { id, lhs, op, rhs },
+        Unreachable,
+    }
+    CovTerm { Zero, Counter(id), Expression(id) }
+    CovOp { Add, Subtract }
+    FakeReadCause {
+        ForMatchGuard,
+        ForMatchedPlace(closure),
+        ForGuardBinding,
+        ForLet(closure),
+        ForIndex,
+    }
+    RetagKind { FnEntry, TwoPhase, Raw, Default }
+    Variance { Covariant, Invariant, Contravariant, Bivariant }
+    NonDivergingIntrinsic { Assume(op), CopyNonOverlapping(copy) }
+    StatementKind {
+        Assign(place, rvalue),
+        FakeRead(cause, place),
+        SetDiscriminant { place, variant_index },
+        Deinit(place),
+        StorageLive(local),
+        StorageDead(local),
+        Retag(kind, place),
+        PlaceMention(place),
+        AscribeUserType { place, projections, variance },
+        Coverage(coverage),
+        Intrinsic(intrinsic),
+        ConstEvalCounter,
+        Nop,
+    }
+    Rvalue {
+        AddressOf(mutability, place),
+        Aggregate(kind, operands),
+        BinaryOp(op, l, r),
+        Cast(kind, op, ty),
+        CheckedBinaryOp(op, l, r),
+        CopyForDeref(place),
+        Discriminant(place),
+        Len(place),
+        Ref(region, kind, place),
+        Repeat(op, len),
+        ShallowInitBox(op, ty),
+        ThreadLocalRef(item),
+        NullaryOp(op, ty),
+        UnaryOp(op, operand),
+        Use(op),
+    }
+    AggregateKind {
+        Array(ty),
+        Tuple,
+        Adt(def, variant, args, user_ty, field),
+        Closure(def, args),
+        Generator(def, args, movability),
+    }
+    Operand { Copy(place), Move(place), Constant(constant) }
+    ProjectionElem<V, T> {
+        Deref,
+        Field(field, ty),
+        Index(local),
+        ConstantIndex { offset, min_length, from_end },
+        Subslice { from, to, from_end },
+        Downcast(variant),
+        OpaqueCast(ty),
+    }
+    VarDebugInfoContents { Place(place), Const(constant) }
+    BorrowKind { Shared, Shallow, Mut { kind } }
+    MutBorrowKind { Default, TwoPhaseBorrow, ClosureCapture }
+    Mutability { Not, Mut }
+    Safety { Unsafe, Normal }
+    PointerCoercion {
+        ReifyFnPointer,
+        UnsafeFnPointer,
+        ClosureFnPointer(safety),
+        MutToConstPointer,
+        ArrayToPointer,
+        Unsize,
+    }
+    CastKind {
+        PointerExposeAddress,
+        PointerFromExposedAddress,
+        PointerCoercion(coercion),
+        DynStar,
+        IntToInt,
+        FloatToInt,
+        FloatToFloat,
+        IntToFloat,
+        PtrToPtr,
+        FnPtrToPtr,
+        Transmute,
+    }
+    NullOp { SizeOf, AlignOf, OffsetOf(fields) }
+
+    RegionKind {
+        ReEarlyBound(region),
+        ReLateBound(index, region),
+        ReFree(region),
+        ReStatic,
+        ReVar(vid),
+        RePlaceholder(placeholder),
+        ReErased,
+        ReError,
+    }
+    TyKind { RigidTy(ty), Alias(kind, alias), Param(param), Bound(index, bound) }
+    RigidTy {
+        Bool,
+        Char,
+        Int(int),
+        Uint(uint),
+        Float(float),
+        Adt(def, args),
+        Foreign(def),
+        Str,
+        Array(ty, len),
+        Slice(ty),
+        RawPtr(ty, mutability),
+        Ref(region, ty, mutability),
+        FnDef(def, args),
+        FnPtr(sig),
+        Closure(def, args),
+        Generator(def, args, movability),
+        Dynamic(predicates, region, kind),
+        Never,
+        Tuple(fields),
+    }
+    IntTy { Isize, I8, I16, I32, I64, I128 }
+    UintTy { Usize, U8, U16, U32, U64, U128 }
+    FloatTy { F32, F64 }
+    Movability { Static, Movable }
+    GenericArgKind { Lifetime(region), Type(ty), Const(cnst) }
+    TermKind { Type(ty), Const(cnst) }
+    AliasKind { Projection, Inherent, Opaque, Weak }
+    Abi {
+        Rust,
+        C { unwind },
+        Cdecl { unwind },
+        Stdcall { unwind },
+        Fastcall { unwind },
+        Vectorcall { unwind },
+        Thiscall { unwind },
+        Aapcs { unwind },
+        Win64 { unwind },
+        SysV64 { unwind },
+        PtxKernel,
+        Msp430Interrupt,
+        X86Interrupt,
+        AmdGpuKernel,
+        EfiApi,
+        AvrInterrupt,
+        AvrNonBlockingInterrupt,
+        CCmseNonSecureCall,
+        Wasm,
+        System { unwind },
+        RustIntrinsic,
+        RustCall,
+        PlatformIntrinsic,
+        Unadjusted,
+        RustCold,
+        RiscvInterruptM,
+        RiscvInterruptS,
+    }
+    BoundVariableKind { Ty(kind), Region(kind), Const }
+    BoundTyKind { Anon, Param(def, name) }
+    BoundRegionKind { BrAnon(span), BrNamed(def, name), BrEnv }
+    DynKind { Dyn, DynStar }
+    ExistentialPredicate { Trait(trait_ref), Projection(projection), AutoTrait(def) }
+    ConstantKind {
+        Allocated(alloc),
+        Unevaluated(uneval),
+        Param(param),
+        Bound(index, var),
+        Placeholder(placeholder),
+        Expr,
+        Error,
+    }
+}
@@ -1,12 +1,17 @@
 use super::{
+    abi::Layout,
     mir::Safety,
-    mir::{Body, Mutability},
-    with, AllocId, DefId,
+    mir::{Body, BorrowKind, Mutability, ProjectionElem, VariantIdx},
+    with, AllocId, CrateItem, DefId, Error, Symbol,
 };
 use crate::rustc_internal::Opaque;
+use crate::stable_mir::fold::{Foldable, Instantiate};
 use std::fmt::{self, Debug, Formatter};
+use std::ops::ControlFlow;
 
-#[derive(Copy, Clone)]
+/// A type. The types which come from rustc are interned: two equal types have the same index.
+/// The types created with `Context::mk_ty` aren't, so they always get a new index.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Ty(pub usize);
 
 impl Debug for Ty {
@@ -15,10 +20,89 @@ fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
     }
 }
 
+/// Prints the type like the compiler does in its MIR dumps, e.g. `std::option::Option<u8>`.
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&with(|context| context.ty_pretty(*self)))
+    }
+}
+
 impl Ty {
     pub fn kind(&self) -> TyKind {
         with(|context| context.ty_kind(*self))
     }
+
+    /// Instantiates the generic parameters mentioned by this type with `args`, like the arguments
+    /// of the item the type was given by. This fails if `args` doesn't have an argument of the
+    /// right kind for one of the parameters.
+    pub fn subst(&self, args: &GenericArgs) -> Result<Ty, Error> {
+        with(|context| context.instantiate_ty(*self, args))
+    }
+
+    /// Returns the signature of this function pointer type.
+    pub fn fn_ptr_sig(&self) -> Result<PolyFnSig, Error> {
+        match self.kind() {
+            TyKind::RigidTy(RigidTy::FnPtr(sig)) => Ok(sig),
+            kind => Err(Error::new(format!("expected a function pointer type, found `{kind:?}`"))),
+        }
+    }
+
+    /// Returns the types of the fields of this tuple type.
+    pub fn tuple_fields(&self) -> Result<Vec<Ty>, Error> {
+        match self.kind() {
+            TyKind::RigidTy(RigidTy::Tuple(fields)) => Ok(fields),
+            kind => Err(Error::new(format!("expected a tuple type, found `{kind:?}`"))),
+        }
+    }
+
+    /// Returns the length of this array type. This fails if the length depends on a generic
+    /// parameter or could not be evaluated.
+    pub fn array_len(&self) -> Result<u64, Error> {
+        match self.kind() {
+            TyKind::RigidTy(RigidTy::Array(_, len)) => len.eval_target_usize(),
+            kind => Err(Error::new(format!("expected an array type, found `{kind:?}`"))),
+        }
+    }
+
+    /// Returns whether values of this type need to be dropped, i.e. whether the type has drop
+    /// glue. Types depending on generic parameters conservatively need to be dropped.
+    pub fn needs_drop(&self) -> Result<bool, Error> {
+        with(|context| context.needs_drop(*self))
+    }
+
+    /// Returns the layout of this type, as laid out in memory by the compiler: its size and
+    /// alignment, the offsets of its fields and the encoding of its discriminant. This fails if
+    /// the type depends on generic parameters or is too big.
+    pub fn layout(&self) -> Result<Layout, Error> {
+        with(|context| context.ty_layout(*self))
+    }
+
+    /// Returns the body of the drop glue of this type, i.e. the shim called by
+    /// `std::ptr::drop_in_place`, or `None` if the type doesn't need to be dropped. This fails if
+    /// the type depends on generic parameters.
+    pub fn drop_glue_body(&self) -> Result<Option<Body>, Error> {
+        with(|context| context.drop_glue_body(*self))
+    }
+
+    /// Returns the fields of this ADT, tuple or closure type, in the order in which they are
+    /// dropped, along with whether they need to be dropped.
+    ///
+    /// The fields are dropped after the `Drop` implementation of the type, if any, has run. For
+    /// enums, only the fields of the active variant are dropped.
+    pub fn field_drops(&self) -> Result<Vec<FieldDrop>, Error> {
+        with(|context| context.field_drops(*self))
+    }
+}
+
+/// A field of a type, as returned by [`Ty::field_drops`].
+#[derive(Clone, Debug)]
+pub struct FieldDrop {
+    /// The variant containing the field, if the type is an enum.
+    pub variant: Option<VariantIdx>,
+    /// The index of the field in its variant, tuple or list of captured variables.
+    pub field: usize,
+    pub ty: Ty,
+    pub needs_drop: bool,
 }
 
 impl From<TyKind> for Ty {
@@ -33,8 +117,199 @@ pub struct Const {
     pub ty: Ty,
 }
 
+/// Prints the value of the constant like the compiler does in its MIR dumps, e.g. `5_i32` or
+/// `"text"`. Constants which aren't evaluated are printed as `_`.
+impl fmt::Display for Const {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&with(|context| context.const_pretty(self)))
+    }
+}
+
+impl Const {
+    /// Returns the value of this constant of type `usize`.
+    pub fn eval_target_usize(&self) -> Result<u64, Error> {
+        with(|context| context.eval_target_usize(self))
+    }
+
+    /// Returns the bits of the value of this constant, like the value of an integer or the
+    /// discriminant of a field-less enum, in the byte order of the target.
+    ///
+    /// Fails if the value isn't known, or doesn't fit in 128 bits, or contains a pointer.
+    pub fn try_to_bits(&self) -> Result<u128, Error> {
+        let alloc = self.allocation()?;
+        alloc.read_scalar(0, alloc.bytes.len())
+    }
+
+    /// Returns the allocation holding the value of this constant, if it is known.
+    pub(crate) fn allocation(&self) -> Result<&Allocation, Error> {
+        match &self.literal {
+            ConstantKind::Allocated(alloc) => Ok(alloc),
+            ConstantKind::Param(param) => {
+                Err(Error::new(format!("the constant is the generic parameter `{}`", param.name)))
+            }
+            ConstantKind::Unevaluated(_) => {
+                Err(Error::new("the constant has not been evaluated".to_owned()))
+            }
+            ConstantKind::Bound(..) | ConstantKind::Placeholder(_) | ConstantKind::Expr => {
+                Err(Error::new("the constant depends on generic parameters".to_owned()))
+            }
+            ConstantKind::Error => Err(Error::new("the constant failed to type-check".to_owned())),
+        }
+    }
+
+    /// Returns whether the value of this constant is known, can be evaluated with
+    /// [`ParamEnv::eval_const`], or depends on generic parameters.
+    pub fn state(&self) -> ConstState {
+        match self.literal {
+            ConstantKind::Allocated(_) => ConstState::Known,
+            ConstantKind::Unevaluated(_) => ConstState::Unevaluated,
+            ConstantKind::Param(_)
+            | ConstantKind::Bound(..)
+            | ConstantKind::Placeholder(_)
+            | ConstantKind::Expr => ConstState::Parameterized,
+            ConstantKind::Error => ConstState::Error,
+        }
+    }
+}
+
+/// How far a constant is evaluated, see [`Const::state`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConstState {
+    /// The value of the constant is known: it is [`ConstantKind::Allocated`].
+    Known,
+    /// The constant refers to a constant item, like an associated constant, which can be
+    /// evaluated with [`ParamEnv::eval_const`]. The evaluation fails with
+    /// [`ConstEvalError::TooGeneric`] if the item depends on generic parameters which aren't
+    /// known in the environment.
+    Unevaluated,
+    /// The constant is a generic parameter or depends on one: it can only be evaluated once it is
+    /// instantiated.
+    Parameterized,
+    /// The constant failed to type-check or to be evaluated, and the compiler reported an error.
+    Error,
+}
+
+/// The reason why [`ParamEnv::eval_const`] failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConstEvalError {
+    /// The constant depends on generic parameters which aren't known in the environment.
+    TooGeneric,
+    /// Evaluating the constant failed, like when it overflows or panics, and the compiler
+    /// reported an error at `span`, if it is known.
+    EvalError { span: Option<Span>, message: String },
+    /// The constant couldn't be normalized in the environment, or wasn't created by the compiler.
+    Invalid(Error),
+}
+
+impl From<Error> for ConstEvalError {
+    fn from(error: Error) -> Self {
+        ConstEvalError::Invalid(error)
+    }
+}
+
+impl fmt::Display for ConstEvalError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ConstEvalError::TooGeneric => f.write_str("the constant depends on generic parameters"),
+            ConstEvalError::EvalError { message, .. } => f.write_str(message),
+            ConstEvalError::Invalid(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+/// The environment in which types are reasoned about: the where clauses in scope in an item,
+/// which are needed to resolve its associated types. See [`CrateItem::param_env`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ParamEnv(pub(crate) usize);
+
+impl ParamEnv {
+    /// Normalizes `ty` in this environment: associated types, like `<T as Iterator>::Item`, are
+    /// replaced by the type they resolve to, and the regions are erased.
+    ///
+    /// Projections which can't be resolved, because they depend on a generic parameter without a
+    /// where clause constraining them, stay [`TyKind::Alias`]es. This fails if normalization
+    /// itself fails, e.g. because a trait isn't implemented.
+    pub fn normalize(&self, ty: Ty) -> Result<Ty, Error> {
+        with(|context| context.normalize_ty(*self, ty))
+    }
+
+    /// Normalizes the type of `cnst` in this environment and evaluates it if it is unevaluated,
+    /// like an associated constant. Constants depending on generic parameters are left
+    /// unevaluated.
+    pub fn normalize_const(&self, cnst: &Const) -> Result<Const, Error> {
+        with(|context| context.normalize_const(*self, cnst))
+    }
+
+    /// Normalizes the type of `cnst` in this environment and evaluates it. Unlike
+    /// [`ParamEnv::normalize_const`], the returned constant is always
+    /// [`ConstantKind::Allocated`]: constants which can't be evaluated are errors.
+    pub fn eval_const(&self, cnst: &Const) -> Result<Const, ConstEvalError> {
+        with(|context| context.eval_const(*self, cnst))
+    }
+}
+
 type Ident = Opaque;
-pub(crate) type Region = Opaque;
+
+#[derive(Clone, Debug)]
+pub struct Region {
+    pub kind: RegionKind,
+}
+
+#[derive(Clone, Debug)]
+pub enum RegionKind {
+    /// A lifetime parameter of an item, like `'a` in `fn f<'a>(x: &'a u8)`.
+    ReEarlyBound(EarlyBoundRegion),
+    /// A lifetime bound by a binder, like the one of a `for<'a>` bound or of a function pointer.
+    ReLateBound(DebruijnIndex, BoundRegion),
+    /// A lifetime parameter or bound lifetime seen from inside the item which binds it.
+    ReFree(FreeRegion),
+    ReStatic,
+    /// A region variable of the borrow checker, which are the regions of the body of
+    /// [`BorrowckFacts`]: this is the [`RegionId`] the facts use.
+    ///
+    /// [`BorrowckFacts`]: crate::stable_mir::mir::borrowck::BorrowckFacts
+    /// [`RegionId`]: crate::stable_mir::mir::borrowck::RegionId
+    ReVar(RegionVid),
+    RePlaceholder(Placeholder<BoundRegion>),
+    /// A region which was erased, like all the regions of the optimized MIR.
+    ReErased,
+    ReError,
+}
+
+pub type DebruijnIndex = usize;
+
+pub type BoundVar = usize;
+
+pub type UniverseIndex = usize;
+
+pub type RegionVid = usize;
+
+#[derive(Clone, Debug)]
+pub struct EarlyBoundRegion {
+    pub def_id: ParamDef,
+    pub index: u32,
+    pub name: Symbol,
+}
+
+#[derive(Clone, Debug)]
+pub struct BoundRegion {
+    pub var: BoundVar,
+    pub kind: BoundRegionKind,
+}
+
+#[derive(Clone, Debug)]
+pub struct FreeRegion {
+    /// The item whose body the region is free in.
+    pub scope: CrateItem,
+    pub bound_region: BoundRegionKind,
+}
+
+#[derive(Clone, Debug)]
+pub struct Placeholder<T> {
+    pub universe: UniverseIndex,
+    pub bound: T,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Span(pub(crate) usize);
 
@@ -46,6 +321,35 @@ fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
     }
 }
 
+impl Span {
+    /// The name of the file the span points into, as it is displayed in diagnostics.
+    pub fn get_filename(&self) -> Filename {
+        with(|cx| cx.get_filename(self))
+    }
+
+    /// The lines and columns the span starts and ends at.
+    pub fn get_lines(&self) -> LineInfo {
+        with(|cx| cx.get_lines(self))
+    }
+
+    /// The source code the span points at, if the source of its file is available.
+    pub fn source_text(&self) -> Option<String> {
+        with(|cx| cx.source_text(self))
+    }
+}
+
+pub type Filename = String;
+
+/// The position of a [`Span`] in its file. Lines and columns start at 1, and the end column
+/// is the one right after the last character of the span.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LineInfo {
+    pub start_line: usize,
+    pub start_col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+}
+
 #[derive(Clone, Debug)]
 pub enum TyKind {
     RigidTy(RigidTy),
@@ -119,14 +423,98 @@ impl FnDef {
     pub fn body(&self) -> Body {
         with(|ctx| ctx.mir_body(self.0))
     }
+
+    /// The path of the function, like `std::option::Option::<T>::unwrap`.
+    pub fn name(&self) -> String {
+        with(|ctx| ctx.name_of_def_id(self.0))
+    }
+
+    /// The signature of the function, in terms of its own generic parameters. The return type of
+    /// a function returning `impl Trait` is an opaque [`TyKind::Alias`], see
+    /// [`AliasTy::hidden_type`].
+    pub fn fn_sig(&self) -> PolyFnSig {
+        with(|ctx| ctx.fn_sig(*self))
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct ClosureDef(pub(crate) DefId);
 
+impl ClosureDef {
+    /// The places captured by the closure, in the order of the fields of its environment, like
+    /// the upvar types of [`RigidTy::Closure`]. This fails for closures of other crates.
+    pub fn captures(&self) -> Result<Vec<CapturedPlace>, Error> {
+        with(|cx| cx.closure_captures(self.0))
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct GeneratorDef(pub(crate) DefId);
 
+impl GeneratorDef {
+    /// The places captured by the generator, which are stored before the saved locals of its
+    /// [`layout`](Self::layout). This fails for generators of other crates.
+    pub fn captures(&self) -> Result<Vec<CapturedPlace>, Error> {
+        with(|cx| cx.closure_captures(self.0))
+    }
+
+    /// The locals of the generator which are saved in its state across suspension points, and
+    /// which of them each variant of the state stores. The types of the saved locals refer to the
+    /// generic parameters of the item defining the generator.
+    pub fn layout(&self) -> Result<GeneratorLayout, Error> {
+        with(|cx| cx.generator_layout(*self))
+    }
+}
+
+/// A place of the enclosing function captured by a closure or a generator.
+#[derive(Clone, Debug)]
+pub struct CapturedPlace {
+    /// The name of the captured variable.
+    pub var_name: Symbol,
+    /// The projections applied to the variable when only a part of it is captured, e.g.
+    /// `[Field(1, _)]` if the closure only uses `x.1`.
+    pub projection: Vec<ProjectionElem>,
+    /// The type of the captured place, which is the type of the field of the environment if the
+    /// place is captured by value, or the type it refers to otherwise.
+    pub ty: Ty,
+    pub kind: CaptureKind,
+    /// Whether the captured place can be mutated.
+    pub mutability: Mutability,
+}
+
+#[derive(Clone, Debug)]
+pub enum CaptureKind {
+    ByValue,
+    /// The place is captured by a reference. A closure which mutates a place behind a mutable
+    /// reference, but only captures the reference, borrows it uniquely with
+    /// [`MutBorrowKind::ClosureCapture`].
+    ///
+    /// [`MutBorrowKind::ClosureCapture`]: crate::stable_mir::mir::MutBorrowKind::ClosureCapture
+    ByRef(BorrowKind),
+}
+
+/// The state of a generator, see [`GeneratorDef::layout`].
+#[derive(Clone, Debug)]
+pub struct GeneratorLayout {
+    /// The locals saved across suspension points.
+    pub saved_locals: Vec<GeneratorSavedLocal>,
+    /// For each variant of the state, the indices in `saved_locals` of the locals it stores. The
+    /// first three variants are the unresumed, returned and poisoned states, which don't store
+    /// any local. The other ones are the suspension points.
+    pub variant_fields: Vec<Vec<usize>>,
+    /// For each variant of the state, the span of the suspension point, like a `yield` or an
+    /// `.await`, which created it.
+    pub variant_spans: Vec<Span>,
+}
+
+#[derive(Clone, Debug)]
+pub struct GeneratorSavedLocal {
+    pub ty: Ty,
+    /// The name of the variable, if the local is a user variable.
+    pub name: Option<Symbol>,
+    pub span: Span,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct ParamDef(pub(crate) DefId);
 
@@ -136,6 +524,94 @@ pub fn body(&self) -> Body {
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct AdtDef(pub(crate) DefId);
 
+impl AdtDef {
+    /// Whether the ADT is a struct, an enum or a union.
+    pub fn kind(&self) -> AdtKind {
+        with(|cx| cx.adt_kind(*self))
+    }
+
+    /// The variants of the ADT, in declaration order. Structs and unions have a single variant.
+    pub fn variants(&self) -> Vec<VariantDef> {
+        with(|cx| cx.adt_variants(*self))
+    }
+
+    /// The fields of a struct or union. Enums have no fields of their own, the fields of their
+    /// variants are listed by [`AdtDef::variants`].
+    pub fn fields(&self) -> Vec<FieldDef> {
+        match self.kind() {
+            AdtKind::Enum => vec![],
+            AdtKind::Struct | AdtKind::Union => {
+                self.variants().pop().map(|variant| variant.fields).unwrap_or_default()
+            }
+        }
+    }
+
+    /// The `#[repr]` attributes of the ADT.
+    pub fn repr(&self) -> ReprOptions {
+        with(|cx| cx.adt_repr(*self))
+    }
+
+    /// The discriminant of a variant of an enum, given explicitly or implicitly.
+    pub fn discriminant_for_variant(&self, variant: VariantIdx) -> Result<Discr, Error> {
+        with(|cx| cx.adt_discriminant(*self, variant))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AdtKind {
+    Struct,
+    Enum,
+    Union,
+}
+
+/// A variant of an ADT, see [`AdtDef::variants`].
+#[derive(Clone, Debug)]
+pub struct VariantDef {
+    pub name: Symbol,
+    pub fields: Vec<FieldDef>,
+}
+
+/// A field of a variant of an ADT.
+#[derive(Clone, Debug)]
+pub struct FieldDef {
+    /// The name of the field, which is its index for the fields of tuple structs and variants.
+    pub name: Symbol,
+    /// The type of the field, which mentions the generic parameters of the ADT. See
+    /// [`FieldDef::ty_with_args`] for its type in an instance of the ADT.
+    pub ty: Ty,
+}
+
+impl FieldDef {
+    /// The type of the field in the ADT instantiated with `args`, like the arguments of
+    /// [`RigidTy::Adt`].
+    pub fn ty_with_args(&self, args: &GenericArgs) -> Result<Ty, Error> {
+        with(|cx| cx.instantiate_ty(self.ty, args))
+    }
+}
+
+/// The `#[repr]` attributes of an ADT, see [`AdtDef::repr`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReprOptions {
+    /// The type of the discriminant given by an attribute like `#[repr(u8)]`.
+    pub int: Option<Ty>,
+    /// The alignment given by `#[repr(align(N))]`, in bytes.
+    pub align: Option<u64>,
+    /// The maximum alignment of the fields given by `#[repr(packed(N))]`, in bytes.
+    pub pack: Option<u64>,
+    pub c: bool,
+    pub transparent: bool,
+    pub simd: bool,
+}
+
+/// The discriminant of a variant of an enum, see [`AdtDef::discriminant_for_variant`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Discr {
+    /// The bits of the value, truncated to the size of `ty`: `-1i8` is `0xff`.
+    pub val: u128,
+    /// The type of the discriminant, which is `isize` unless the enum has a `#[repr]` attribute.
+    pub ty: Ty,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct AliasDef(pub(crate) DefId);
 
@@ -148,6 +624,30 @@ pub fn body(&self) -> Body {
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct ConstDef(pub(crate) DefId);
 
+/// A static item, including the statics of `extern` blocks and thread locals.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct StaticDef(pub(crate) DefId);
+
+impl StaticDef {
+    /// The type of the static.
+    pub fn ty(&self) -> Ty {
+        with(|cx| cx.static_ty(*self))
+    }
+
+    /// The body computing the initial value of the static, which is evaluated at compile time.
+    /// This fails for the statics of `extern` blocks, which don't have one.
+    pub fn initializer_body(&self) -> Result<Body, Error> {
+        with(|cx| cx.static_initializer_body(*self))
+    }
+
+    /// The memory of the static when the program starts, which is the result of its
+    /// [`initializer_body`](Self::initializer_body). The pointers it contains are listed in its
+    /// provenance. This fails for the statics of `extern` blocks.
+    pub fn allocation(&self) -> Result<Allocation, Error> {
+        with(|cx| cx.static_allocation(*self))
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct ImplDef(pub(crate) DefId);
 
@@ -219,6 +719,18 @@ pub struct AliasTy {
     pub args: GenericArgs,
 }
 
+impl AliasTy {
+    /// The type hidden behind an opaque type, like the type actually returned by a function
+    /// returning `impl Trait` or the type a `type Alias = impl Trait` is defined as, instantiated
+    /// with the arguments of the alias.
+    ///
+    /// This fails if the alias isn't an opaque type or if its hidden type is unknown, e.g. because
+    /// type checking the items defining it failed. The hidden type may itself contain opaque types.
+    pub fn hidden_type(&self) -> Result<Ty, Error> {
+        with(|cx| cx.opaque_hidden_type(self))
+    }
+}
+
 pub type PolyFnSig = Binder<FnSig>;
 
 #[derive(Clone, Debug)]
@@ -271,6 +783,18 @@ pub struct EarlyBinder<T> {
     pub value: T,
 }
 
+impl<T: Foldable> EarlyBinder<T> {
+    /// Instantiates the generic parameters of the value with `args`, like the arguments of the
+    /// item the value was given by. The types are instantiated by the compiler, see
+    /// [`Ty::subst`], so this fails like it does.
+    pub fn instantiate(self, args: &GenericArgs) -> Result<T, Error> {
+        match self.value.fold(&mut Instantiate(args)) {
+            ControlFlow::Continue(value) => Ok(value),
+            ControlFlow::Break(error) => Err(error),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum BoundVariableKind {
     Ty(BoundTyKind),
@@ -354,11 +878,29 @@ pub struct Allocation {
     pub mutability: Mutability,
 }
 
+impl Allocation {
+    /// Reads the integer of `size` bytes starting at `offset`, in the byte order of the target.
+    ///
+    /// Fails if the bytes are out of bounds, uninitialized, or part of a pointer, or if `size`
+    /// isn't between 1 and 16.
+    pub fn read_scalar(&self, offset: Size, size: Size) -> Result<u128, Error> {
+        with(|cx| cx.read_scalar(self, offset, size))
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum ConstantKind {
     Allocated(Allocation),
     Unevaluated(UnevaluatedConst),
     Param(ParamConst),
+    /// A constant bound by a binder.
+    Bound(DebruijnIndex, BoundVar),
+    Placeholder(Placeholder<BoundVar>),
+    /// An expression of generic constants, like `N + 1` with `generic_const_exprs`, which can't
+    /// be inspected yet.
+    Expr,
+    /// A constant which failed to type-check or to be evaluated.
+    Error,
 }
 
 #[derive(Clone, Debug)]
@@ -410,6 +952,26 @@ pub fn explicit_predicates_of(&self) -> GenericPredicates {
     }
 }
 
+/// An item declared in a trait, see [`crate::stable_mir::associated_items`].
+#[derive(Clone, Debug)]
+pub struct AssocItem {
+    pub name: Symbol,
+    pub kind: AssocKind,
+    /// Whether the trait provides a default body or value for the item.
+    pub has_default: bool,
+}
+
+#[derive(Clone, Debug)]
+pub enum AssocKind {
+    /// A method if `has_self` is true, an associated function otherwise.
+    Fn {
+        def: FnDef,
+        has_self: bool,
+    },
+    Const(ConstDef),
+    Type(AliasDef),
+}
+
 pub type ImplTrait = EarlyBinder<TraitRef>;
 
 #[derive(Clone, Debug)]
@@ -4,7 +4,7 @@
 
 use super::ty::{
     Allocation, Binder, Const, ConstDef, ExistentialPredicate, FnSig, GenericArgKind, GenericArgs,
-    Promoted, RigidTy, TermKind, Ty, UnevaluatedConst,
+    Promoted, Region, RigidTy, TermKind, Ty, UnevaluatedConst,
 };
 
 pub trait Visitor: Sized {
@@ -47,7 +47,11 @@ fn super_visit<V: Visitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
         match &self.literal {
             super::ty::ConstantKind::Allocated(alloc) => alloc.visit(visitor)?,
             super::ty::ConstantKind::Unevaluated(uv) => uv.visit(visitor)?,
-            super::ty::ConstantKind::Param(_) => {}
+            super::ty::ConstantKind::Param(_)
+            | super::ty::ConstantKind::Bound(..)
+            | super::ty::ConstantKind::Placeholder(_)
+            | super::ty::ConstantKind::Expr
+            | super::ty::ConstantKind::Error => {}
         }
         self.ty.visit(visitor)
     }
@@ -59,6 +63,12 @@ fn super_visit<V: Visitor>(&self, _visitor: &mut V) -> ControlFlow<V::Break> {
     }
 }
 
+impl Visitable for Region {
+    fn super_visit<V: Visitor>(&self, _visitor: &mut V) -> ControlFlow<V::Break> {
+        ControlFlow::Continue(())
+    }
+}
+
 impl Visitable for Allocation {
     fn super_visit<V: Visitor>(&self, _visitor: &mut V) -> ControlFlow<V::Break> {
         ControlFlow::Continue(())
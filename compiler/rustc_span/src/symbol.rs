@@ -502,6 +502,7 @@
         cmp_min,
         cmpxchg16b_target_feature,
         cmse_nonsecure_entry,
+        codegen_unit,
         coerce_unsized,
         cold,
         collapse_debuginfo,
@@ -653,6 +654,7 @@
         doc_masked,
         doc_notable_trait,
         doc_primitive,
+        doc_smart_punctuation,
         doc_spotlight,
         doctest,
         document_private_items,
@@ -1480,6 +1482,7 @@
         slice_len_fn,
         slice_patterns,
         slicing_syntax,
+        smart_punctuation,
         soft,
         specialization,
         speed,
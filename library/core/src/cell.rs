@@ -242,6 +242,8 @@
 use crate::mem;
 use crate::ops::{CoerceUnsized, Deref, DerefMut, DispatchFromDyn};
 use crate::ptr::{self, NonNull};
+#[cfg(target_has_atomic_load_store = "ptr")]
+use crate::sync::atomic::{self, AtomicPtr};
 
 mod lazy;
 mod once;
@@ -770,6 +772,35 @@ fn panic_already_mutably_borrowed(err: BorrowError) -> ! {
     panic!("already mutably borrowed: {:?}", err)
 }
 
+/// Hook called with the address of a `RefCell` and whether the borrow is
+/// mutable every time a `RefCell` is borrowed, or null. It is set by the
+/// unwind safety diagnostic mode of `std`, which cannot be reached from `core`.
+#[cfg(target_has_atomic_load_store = "ptr")]
+static BORROW_HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Sets the hook called when a `RefCell` is borrowed, used by
+/// `std::panic::unwind_safe_assert_in_tests`.
+#[doc(hidden)]
+#[unstable(feature = "unwind_safe_assert_in_tests", issue = "none")]
+#[cfg(target_has_atomic_load_store = "ptr")]
+pub fn set_borrow_hook(hook: Option<fn(*const (), bool)>) {
+    let hook = hook.map_or(ptr::null_mut(), |hook| hook as *mut ());
+    BORROW_HOOK.store(hook, atomic::Ordering::Release);
+}
+
+#[inline]
+fn borrowed(cell: *const (), exclusive: bool) {
+    #[cfg(target_has_atomic_load_store = "ptr")]
+    {
+        let hook = BORROW_HOOK.load(atomic::Ordering::Acquire);
+        if !hook.is_null() {
+            // SAFETY: only `fn(*const (), bool)` pointers are stored in `BORROW_HOOK`.
+            let hook = unsafe { mem::transmute::<*mut (), fn(*const (), bool)>(hook) };
+            hook(cell, exclusive);
+        }
+    }
+}
+
 // Positive values represent the number of `Ref` active. Negative values
 // represent the number of `RefMut` active. Multiple `RefMut`s can only be
 // active at a time if they refer to distinct, nonoverlapping components of a
@@ -996,6 +1027,8 @@ pub fn try_borrow(&self) -> Result<Ref<'_, T>, BorrowError> {
                     }
                 }
 
+                borrowed(self as *const Self as *const (), false);
+
                 // SAFETY: `BorrowRef` ensures that there is only immutable access
                 // to the value while borrowed.
                 let value = unsafe { NonNull::new_unchecked(self.value.get()) };
@@ -1086,6 +1119,8 @@ pub fn try_borrow_mut(&self) -> Result<RefMut<'_, T>, BorrowMutError> {
                     self.borrowed_at.set(Some(crate::panic::Location::caller()));
                 }
 
+                borrowed(self as *const Self as *const (), true);
+
                 // SAFETY: `BorrowRefMut` guarantees unique access.
                 let value = unsafe { NonNull::new_unchecked(self.value.get()) };
                 Ok(RefMut { value, borrow: b, marker: PhantomData })
@@ -2,16 +2,21 @@
 
 #![stable(feature = "core_panic_info", since = "1.41.0")]
 
+mod assert_failure;
 mod location;
 mod panic_info;
 mod unwind_safe;
 
 use crate::any::Any;
 
+#[unstable(feature = "panic_assert_formatter", issue = "none")]
+pub use self::assert_failure::AssertFailure;
 #[stable(feature = "panic_hooks", since = "1.10.0")]
 pub use self::location::Location;
 #[stable(feature = "panic_hooks", since = "1.10.0")]
 pub use self::panic_info::PanicInfo;
+#[unstable(feature = "panic_payload_kind", issue = "none")]
+pub use self::panic_info::PanicPayloadKind;
 #[stable(feature = "catch_unwind", since = "1.9.0")]
 pub use self::unwind_safe::{AssertUnwindSafe, RefUnwindSafe, UnwindSafe};
 
@@ -112,4 +117,7 @@ pub unsafe trait BoxMeUp {
 
     /// Just borrow the contents.
     fn get(&mut self) -> &(dyn Any + Send);
+
+    /// How the contents were created, as reported by [`PanicInfo::payload_kind`].
+    fn kind(&self) -> PanicPayloadKind;
 }
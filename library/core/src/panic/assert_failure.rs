@@ -0,0 +1,87 @@
+use crate::fmt;
+use crate::panicking::AssertKind;
+
+/// A failed assertion of [`assert_eq!`] or [`assert_ne!`], before it is
+/// turned into the message of the panic.
+///
+/// It is passed to the formatter registered with [`set_assert_formatter`],
+/// which renders the message of the panic instead of the [`Display`]
+/// implementation of this struct, which is the default message.
+///
+/// [`set_assert_formatter`]: ../../std/panic/fn.set_assert_formatter.html
+/// [`Display`]: fmt::Display
+#[unstable(feature = "panic_assert_formatter", issue = "none")]
+#[derive(Debug)]
+pub struct AssertFailure<'a> {
+    kind: AssertKind,
+    left: &'a dyn fmt::Debug,
+    right: &'a dyn fmt::Debug,
+    args: Option<fmt::Arguments<'a>>,
+}
+
+impl<'a> AssertFailure<'a> {
+    pub(crate) fn new(
+        kind: AssertKind,
+        left: &'a dyn fmt::Debug,
+        right: &'a dyn fmt::Debug,
+        args: Option<fmt::Arguments<'a>>,
+    ) -> Self {
+        AssertFailure { kind, left, right, args }
+    }
+
+    /// Returns the operator of the assertion: `"=="` for `assert_eq!`, `"!="`
+    /// for `assert_ne!` and `"matches"` for `assert_matches!`.
+    #[must_use]
+    #[unstable(feature = "panic_assert_formatter", issue = "none")]
+    pub fn op(&self) -> &'static str {
+        match self.kind {
+            AssertKind::Eq => "==",
+            AssertKind::Ne => "!=",
+            AssertKind::Match => "matches",
+        }
+    }
+
+    /// Returns the left-hand side of the assertion.
+    #[must_use]
+    #[unstable(feature = "panic_assert_formatter", issue = "none")]
+    pub fn left(&self) -> &'a dyn fmt::Debug {
+        self.left
+    }
+
+    /// Returns the right-hand side of the assertion.
+    #[must_use]
+    #[unstable(feature = "panic_assert_formatter", issue = "none")]
+    pub fn right(&self) -> &'a dyn fmt::Debug {
+        self.right
+    }
+
+    /// Returns the custom message passed to the assertion, if any, like
+    /// `"ids differ"` for `assert_eq!(a, b, "ids differ")`.
+    #[must_use]
+    #[unstable(feature = "panic_assert_formatter", issue = "none")]
+    pub fn message(&self) -> Option<&fmt::Arguments<'a>> {
+        self.args.as_ref()
+    }
+}
+
+#[unstable(feature = "panic_assert_formatter", issue = "none")]
+impl fmt::Display for AssertFailure<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = self.op();
+        let (left, right) = (self.left, self.right);
+        match self.args {
+            Some(args) => write!(
+                f,
+                r#"assertion `left {op} right` failed: {args}
+  left: {left:?}
+ right: {right:?}"#
+            ),
+            None => write!(
+                f,
+                r#"assertion `left {op} right` failed
+  left: {left:?}
+ right: {right:?}"#
+            ),
+        }
+    }
+}
@@ -1,6 +1,7 @@
-use crate::any::Any;
+use crate::any::{Any, TypeId};
+use crate::cell::Cell;
 use crate::fmt;
-use crate::panic::Location;
+use crate::panic::{AssertFailure, Location};
 
 /// A struct providing information about a panic.
 ///
@@ -25,10 +26,13 @@
 #[derive(Debug)]
 pub struct PanicInfo<'a> {
     payload: &'a (dyn Any + Send),
+    payload_kind: PanicPayloadKind,
     message: Option<&'a fmt::Arguments<'a>>,
     location: &'a Location<'a>,
     can_unwind: bool,
     force_no_backtrace: bool,
+    abort_requested: Cell<bool>,
+    assert_failure: Option<&'a AssertFailure<'a>>,
 }
 
 impl<'a> PanicInfo<'a> {
@@ -46,7 +50,16 @@ pub fn internal_constructor(
         force_no_backtrace: bool,
     ) -> Self {
         struct NoPayload;
-        PanicInfo { location, message, payload: &NoPayload, can_unwind, force_no_backtrace }
+        PanicInfo {
+            location,
+            message,
+            payload: &NoPayload,
+            payload_kind: PanicPayloadKind::Any(TypeId::of::<NoPayload>()),
+            can_unwind,
+            force_no_backtrace,
+            abort_requested: Cell::new(false),
+            assert_failure: None,
+        }
     }
 
     #[unstable(
@@ -60,6 +73,41 @@ pub fn set_payload(&mut self, info: &'a (dyn Any + Send)) {
         self.payload = info;
     }
 
+    #[unstable(
+        feature = "panic_internals",
+        reason = "internal details of the implementation of the `panic!` and related macros",
+        issue = "none"
+    )]
+    #[doc(hidden)]
+    #[inline]
+    pub fn set_payload_kind(&mut self, kind: PanicPayloadKind) {
+        self.payload_kind = kind;
+    }
+
+    #[unstable(
+        feature = "panic_internals",
+        reason = "internal details of the implementation of the `panic!` and related macros",
+        issue = "none"
+    )]
+    #[doc(hidden)]
+    #[inline]
+    pub fn set_assert_failure(&mut self, failure: &'a AssertFailure<'a>) {
+        self.assert_failure = Some(failure);
+    }
+
+    /// Returns the failed assertion this panic comes from, for `assert_eq!`
+    /// and `assert_ne!`. The message is its default rendering.
+    #[unstable(
+        feature = "panic_internals",
+        reason = "internal details of the implementation of the `panic!` and related macros",
+        issue = "none"
+    )]
+    #[doc(hidden)]
+    #[inline]
+    pub fn assert_failure(&self) -> Option<&'a AssertFailure<'a>> {
+        self.assert_failure
+    }
+
     /// Returns the payload associated with the panic.
     ///
     /// This will commonly, but not always, be a `&'static str` or [`String`].
@@ -87,6 +135,37 @@ pub fn payload(&self) -> &(dyn Any + Send) {
         self.payload
     }
 
+    /// Returns how the payload of the panic was created.
+    ///
+    /// This tells apart panics whose payload is a message, either a
+    /// `&'static str` or a [`String`] formatted from the arguments of `panic!`,
+    /// from panics raised with an arbitrary payload by [`panic_any`], without
+    /// having to try downcasting the payload to each type.
+    ///
+    /// [`String`]: ../../std/string/struct.String.html
+    /// [`panic_any`]: ../../std/panic/fn.panic_any.html
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// #![feature(panic_payload_kind)]
+    /// use std::panic::{self, PanicPayloadKind};
+    ///
+    /// panic::set_hook(Box::new(|panic_info| match panic_info.payload_kind() {
+    ///     PanicPayloadKind::StaticStr | PanicPayloadKind::FormattedString => {
+    ///         println!("panic occurred: {panic_info}");
+    ///     }
+    ///     _ => println!("panic occurred with a custom payload"),
+    /// }));
+    ///
+    /// panic::panic_any(42);
+    /// ```
+    #[must_use]
+    #[unstable(feature = "panic_payload_kind", issue = "none")]
+    pub fn payload_kind(&self) -> PanicPayloadKind {
+        self.payload_kind
+    }
+
     /// If the `panic!` macro from the `core` crate (not from `std`)
     /// was used with a formatting string and some additional arguments,
     /// returns that message ready to be used for example with [`fmt::write`]
@@ -154,6 +233,69 @@ pub fn can_unwind(&self) -> bool {
     pub fn force_no_backtrace(&self) -> bool {
         self.force_no_backtrace
     }
+
+    /// Requests that the process be aborted once the panic hook returns,
+    /// instead of unwinding the stack.
+    ///
+    /// This allows a panic hook to enforce abort-on-panic for some classes of
+    /// panics at runtime, even when the panic could otherwise unwind. The
+    /// request is only honored for panics that run the panic hook; it has no
+    /// effect on panics resumed with [`resume_unwind`], which don't.
+    ///
+    /// [`resume_unwind`]: ../../std/panic/fn.resume_unwind.html
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(panic_request_abort)]
+    /// use std::panic;
+    ///
+    /// panic::set_hook(Box::new(|panic_info| {
+    ///     if let Some(s) = panic_info.payload().downcast_ref::<&str>() {
+    ///         if s.starts_with("invariant violated") {
+    ///             panic_info.request_abort();
+    ///         }
+    ///     }
+    /// }));
+    ///
+    /// // This will abort the process rather than being caught.
+    /// let _ = panic::catch_unwind(|| {
+    ///     panic!("invariant violated: queue is empty");
+    /// });
+    /// ```
+    #[unstable(feature = "panic_request_abort", issue = "none")]
+    pub fn request_abort(&self) {
+        self.abort_requested.set(true);
+    }
+
+    /// Returns whether [`request_abort`] has been called on this `PanicInfo`.
+    ///
+    /// [`request_abort`]: PanicInfo::request_abort
+    #[must_use]
+    #[unstable(feature = "panic_request_abort", issue = "none")]
+    pub fn abort_requested(&self) -> bool {
+        self.abort_requested.get()
+    }
+}
+
+/// How the payload of a panic was created, as returned by
+/// [`PanicInfo::payload_kind`].
+#[unstable(feature = "panic_payload_kind", issue = "none")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PanicPayloadKind {
+    /// The payload is a `&'static str`, like for `panic!("message")`.
+    StaticStr,
+    /// The payload is a [`String`] formatted from the arguments of `panic!`,
+    /// like for `panic!("{x}")`.
+    ///
+    /// [`String`]: ../../std/string/struct.String.html
+    FormattedString,
+    /// The payload is an arbitrary value of the given type, like for
+    /// [`panic_any`].
+    ///
+    /// [`panic_any`]: ../../std/panic/fn.panic_any.html
+    Any(TypeId),
 }
 
 #[stable(feature = "panic_hook_display", since = "1.26.0")]
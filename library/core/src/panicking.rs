@@ -27,7 +27,7 @@
 )]
 
 use crate::fmt;
-use crate::panic::{Location, PanicInfo};
+use crate::panic::{AssertFailure, Location, PanicInfo};
 
 #[cfg(feature = "panic_immediate_abort")]
 const _: () = assert!(cfg!(panic = "abort"), "panic_immediate_abort requires -C panic=abort");
@@ -50,6 +50,19 @@
 #[rustc_do_not_const_check] // hooked by const-eval
 #[rustc_const_unstable(feature = "core_panic", issue = "none")]
 pub const fn panic_fmt(fmt: fmt::Arguments<'_>) -> ! {
+    let pi = PanicInfo::internal_constructor(
+        Some(&fmt),
+        Location::caller(),
+        /* can_unwind */ true,
+        /* force_no_backtrace */ false,
+    );
+    panic_with_info(&pi)
+}
+
+/// Hands `pi` over to the `#[panic_handler]`. Shared by `panic_fmt` and the
+/// panics which give more information than the message to the handler.
+#[inline(always)]
+fn panic_with_info(pi: &PanicInfo<'_>) -> ! {
     if cfg!(feature = "panic_immediate_abort") {
         super::intrinsics::abort()
     }
@@ -61,15 +74,8 @@ pub const fn panic_fmt(fmt: fmt::Arguments<'_>) -> ! {
         fn panic_impl(pi: &PanicInfo<'_>) -> !;
     }
 
-    let pi = PanicInfo::internal_constructor(
-        Some(&fmt),
-        Location::caller(),
-        /* can_unwind */ true,
-        /* force_no_backtrace */ false,
-    );
-
     // SAFETY: `panic_impl` is defined in safe Rust code and thus is safe to call.
-    unsafe { panic_impl(&pi) }
+    unsafe { panic_impl(pi) }
 }
 
 /// Like `panic_fmt`, but for non-unwinding panics.
@@ -299,22 +305,22 @@ fn assert_failed_inner(
     right: &dyn fmt::Debug,
     args: Option<fmt::Arguments<'_>>,
 ) -> ! {
-    let op = match kind {
-        AssertKind::Eq => "==",
-        AssertKind::Ne => "!=",
-        AssertKind::Match => "matches",
-    };
+    let failure = AssertFailure::new(kind, left, right, args);
+    panic_assert_failed(format_args!("{failure}"), &failure)
+}
 
-    match args {
-        Some(args) => panic!(
-            r#"assertion `left {op} right` failed: {args}
-  left: {left:?}
- right: {right:?}"#
-        ),
-        None => panic!(
-            r#"assertion `left {op} right` failed
-  left: {left:?}
- right: {right:?}"#
-        ),
-    }
+/// Like `panic_fmt`, but also gives the failed assertion to the panic handler,
+/// so that the message can be rendered by the assert formatter of std.
+#[cfg_attr(not(feature = "panic_immediate_abort"), inline(never), cold)]
+#[cfg_attr(feature = "panic_immediate_abort", inline)]
+#[track_caller]
+fn panic_assert_failed(fmt: fmt::Arguments<'_>, failure: &AssertFailure<'_>) -> ! {
+    let mut pi = PanicInfo::internal_constructor(
+        Some(&fmt),
+        Location::caller(),
+        /* can_unwind */ true,
+        /* force_no_backtrace */ false,
+    );
+    pi.set_assert_failure(failure);
+    panic_with_info(&pi)
 }
@@ -993,6 +993,47 @@ pub fn set_output_capture(sink: Option<LocalStream>) -> Option<LocalStream> {
     OUTPUT_CAPTURE.with(move |slot| slot.replace(sink))
 }
 
+/// Returns the thread-local output capture buffer, if output is being captured
+/// on this thread.
+///
+/// Custom panic hooks can write their message into this buffer, like the
+/// default hook does, so that it is shown together with the rest of the
+/// output of the test that panicked. The buffer must not be locked while
+/// using the print macros, since they write into it too.
+#[unstable(
+    feature = "internal_output_capture",
+    reason = "this function is meant for use in test harnesses \
+        and may disappear in the future",
+    issue = "none"
+)]
+#[doc(hidden)]
+pub fn output_capture() -> Option<LocalStream> {
+    if !OUTPUT_CAPTURE_USED.load(Ordering::Relaxed) {
+        return None;
+    }
+    OUTPUT_CAPTURE
+        .try_with(|slot| {
+            let sink = slot.take();
+            slot.set(sink.clone());
+            sink
+        })
+        .ok()
+        .flatten()
+}
+
+/// Returns whether the output of the print macros and of the default panic
+/// hook is being captured on this thread.
+#[unstable(
+    feature = "internal_output_capture",
+    reason = "this function is meant for use in test harnesses \
+        and may disappear in the future",
+    issue = "none"
+)]
+#[doc(hidden)]
+pub fn is_output_captured() -> bool {
+    output_capture().is_some()
+}
+
 /// Write `args` to the capture buffer if enabled and possible, or `global_s`
 /// otherwise. `label` identifies the stream in a panic message.
 ///
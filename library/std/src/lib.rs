@@ -325,9 +325,11 @@
 #![feature(maybe_uninit_uninit_array)]
 #![feature(maybe_uninit_write_slice)]
 #![feature(offset_of)]
+#![feature(panic_assert_formatter)]
 #![feature(panic_can_unwind)]
 #![feature(panic_info_message)]
 #![feature(panic_internals)]
+#![feature(panic_request_abort)]
 #![feature(pointer_byte_offsets)]
 #![feature(pointer_is_aligned)]
 #![feature(portable_simd)]
@@ -340,6 +342,7 @@
 #![feature(std_internals)]
 #![feature(str_internals)]
 #![feature(strict_provenance)]
+#![feature(unwind_safe_assert_in_tests)]
 // tidy-alphabetical-end
 //
 // Library features (alloc):
@@ -42,9 +42,52 @@
 #[unstable(feature = "panic_update_hook", issue = "92649")]
 pub use crate::panicking::update_hook;
 
+#[unstable(feature = "panic_assert_formatter", issue = "none")]
+pub use crate::panicking::{set_assert_formatter, take_assert_formatter};
+
+pub(crate) mod backtrace_location;
+pub(crate) mod buffered_output;
+pub(crate) mod context;
+pub(crate) mod propagation;
+pub(crate) mod stats;
+pub(crate) mod thread_local_dtor;
+pub(crate) mod timings;
+pub(crate) mod unrecoverable;
+pub(crate) mod unwind_safety;
+
+#[unstable(feature = "unwind_safe_assert_in_tests", issue = "none")]
+pub use self::unwind_safety::unwind_safe_assert_in_tests;
+
+#[unstable(feature = "panic_buffer", issue = "none")]
+pub use self::buffered_output::take_buffered_panics;
+
+#[unstable(feature = "panic_backtrace_location", issue = "none")]
+pub use self::backtrace_location::{caller_from_frame, set_backtrace_location_fallback};
+
+#[unstable(feature = "panic_context", issue = "none")]
+pub use self::context::{push_context, ContextGuard};
+
+#[unstable(feature = "panic_propagation", issue = "none")]
+pub use self::propagation::PanicPayload;
+
+#[unstable(feature = "panic_stats", issue = "none")]
+pub use self::stats::{stats, stats_by_thread_name, PanicStats, ThreadPanicStats};
+
+#[unstable(feature = "panic_timings", issue = "none")]
+pub use self::timings::{unwind_timings, UnwindTimings};
+
+#[unstable(feature = "panic_on_unrecoverable", issue = "none")]
+pub use self::unrecoverable::{on_unrecoverable, UnrecoverablePanic, UnrecoverableReason};
+
 #[stable(feature = "panic_hooks", since = "1.10.0")]
 pub use core::panic::{Location, PanicInfo};
 
+#[unstable(feature = "panic_payload_kind", issue = "none")]
+pub use core::panic::PanicPayloadKind;
+
+#[unstable(feature = "panic_assert_formatter", issue = "none")]
+pub use core::panic::AssertFailure;
+
 #[stable(feature = "catch_unwind", since = "1.9.0")]
 pub use core::panic::{AssertUnwindSafe, RefUnwindSafe, UnwindSafe};
 
@@ -63,6 +106,40 @@ pub fn panic_any<M: 'static + Any + Send>(msg: M) -> ! {
     crate::panicking::begin_panic(msg);
 }
 
+/// Returns the payload of a panic as a string slice, if it is one.
+///
+/// Panics raised through the [`panic!`] macro carry either a `&'static str`
+/// or a [`String`] payload, depending on whether the message needed any
+/// formatting. This function handles both cases, returning `None` for payloads
+/// of any other type (such as those passed to [`panic_any`]).
+///
+/// # Examples
+///
+/// ```
+/// #![feature(panic_payload_as_str)]
+/// use std::panic;
+///
+/// let err = panic::catch_unwind(|| panic!("static message")).unwrap_err();
+/// assert_eq!(panic::payload_as_str(&*err), Some("static message"));
+///
+/// let err = panic::catch_unwind(|| panic!("formatted {}", 42)).unwrap_err();
+/// assert_eq!(panic::payload_as_str(&*err), Some("formatted 42"));
+///
+/// let err = panic::catch_unwind(|| panic::panic_any(42_u32)).unwrap_err();
+/// assert_eq!(panic::payload_as_str(&*err), None);
+/// ```
+#[unstable(feature = "panic_payload_as_str", issue = "none")]
+#[must_use]
+pub fn payload_as_str(payload: &dyn Any) -> Option<&str> {
+    if let Some(s) = payload.downcast_ref::<&'static str>() {
+        Some(s)
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        Some(s.as_str())
+    } else {
+        None
+    }
+}
+
 #[stable(feature = "catch_unwind", since = "1.9.0")]
 impl<T: ?Sized> UnwindSafe for Mutex<T> {}
 #[stable(feature = "catch_unwind", since = "1.9.0")]
@@ -169,6 +246,7 @@ pub fn catch_unwind<F: FnOnce() -> R + UnwindSafe, R>(f: F) -> Result<R> {
 /// ```
 #[stable(feature = "resume_unwind", since = "1.9.0")]
 pub fn resume_unwind(payload: Box<dyn Any + Send>) -> ! {
+    propagation::record_location(None);
     panicking::rust_panic_without_hook(payload)
 }
 
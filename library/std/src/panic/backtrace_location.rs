@@ -0,0 +1,131 @@
+//! Fallback for the location of panics which are reported inside the
+//! standard library.
+//!
+//! Code which isn't `#[track_caller]`, like FFI callbacks and closures called
+//! by the standard library, can't pass its location to the panic machinery, so
+//! such panics are reported at a location in the standard library. When the
+//! fallback is enabled with [`set_backtrace_location_fallback`], the panic is
+//! instead reported at the topmost frame of the backtrace which is not in the
+//! standard library.
+
+use crate::backtrace_rs;
+use crate::panic::Location;
+use crate::sync::atomic::{AtomicBool, Ordering};
+use crate::sync::{Mutex, PoisonError};
+use crate::sys_common::backtrace::lock;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The file names of the locations returned by [`caller_from_frame`], which
+/// are leaked so that the locations can be `'static`. There is one entry per
+/// source file, so this stays small.
+static FILES: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+/// Enables or disables reporting panics at a location found in the backtrace
+/// when they are raised inside the standard library.
+///
+/// Panics raised by code which isn't `#[track_caller]` are reported at the
+/// location of the code which called `panic!`. When that code is part of the
+/// standard library, like when a panic escapes from an FFI callback, the
+/// reported location is a file like `library/std/src/panicking.rs`, which
+/// doesn't help finding the cause of the panic. With this fallback enabled,
+/// such panics are reported at the topmost frame of the backtrace which isn't
+/// in the standard library, as returned by [`caller_from_frame`].
+///
+/// Resolving the backtrace is slow, so this only affects panics which would
+/// otherwise be reported in the standard library. The fallback also requires
+/// debug information; without it, the original location is kept.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(panic_backtrace_location)]
+/// use std::panic;
+///
+/// panic::set_backtrace_location_fallback(true);
+/// ```
+#[unstable(feature = "panic_backtrace_location", issue = "none")]
+pub fn set_backtrace_location_fallback(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns the location of the topmost frame of the current backtrace which
+/// is not in the standard library.
+///
+/// This is the location of the user code which is running, even when it
+/// isn't `#[track_caller]`. `None` is returned if backtraces aren't supported
+/// or if no such frame has debug information.
+///
+/// Unlike [`Location::caller`], this resolves the backtrace at runtime, which
+/// is slow.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(panic_backtrace_location)]
+/// use std::panic;
+///
+/// if let Some(location) = panic::caller_from_frame() {
+///     println!("running at {location}");
+/// }
+/// ```
+#[unstable(feature = "panic_backtrace_location", issue = "none")]
+pub fn caller_from_frame() -> Option<Location<'static>> {
+    // Like when printing backtraces, std's own unit tests don't link the
+    // symbolization code.
+    if cfg!(test) {
+        return None;
+    }
+
+    let mut found = None;
+    let _lock = lock();
+    // SAFETY: the backtrace lock is held.
+    unsafe {
+        backtrace_rs::trace_unsynchronized(|frame| {
+            backtrace_rs::resolve_frame_unsynchronized(frame, |symbol| {
+                if found.is_some() {
+                    return;
+                }
+                let (Some(file), Some(line)) = (symbol.filename_raw(), symbol.lineno()) else {
+                    return;
+                };
+                let file = file.to_str_lossy();
+                if !is_in_std(&file) {
+                    found = Some((intern(&file), line, symbol.colno().unwrap_or(0)));
+                }
+            });
+            found.is_none()
+        });
+    }
+    let (file, line, col) = found?;
+    Some(Location::internal_constructor(file, line, col))
+}
+
+/// Called with the location of a panic before running the panic hook. Returns
+/// the location to report instead, if the fallback is enabled and applies.
+pub(crate) fn fallback_location(location: &Location<'_>) -> Option<Location<'static>> {
+    if !ENABLED.load(Ordering::Relaxed) || !is_in_std(location.file()) {
+        return None;
+    }
+    caller_from_frame()
+}
+
+/// Returns whether `file` is a source file of the standard library, either in
+/// a build of the Rust repository (`library/std/src/...`) or in a
+/// distributed toolchain (`/rustc/<hash>/library/std/src/...`).
+fn is_in_std(file: &str) -> bool {
+    let file = file.replace('\\', "/");
+    ["library/std/src/", "library/core/src/", "library/alloc/src/"]
+        .iter()
+        .any(|dir| file.starts_with(dir) || file.contains(&format!("/{dir}")))
+}
+
+pub(super) fn intern(file: &str) -> &'static str {
+    let mut files = FILES.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(interned) = files.iter().find(|interned| **interned == file) {
+        return interned;
+    }
+    let interned: &'static str = Box::leak(file.into());
+    files.push(interned);
+    interned
+}
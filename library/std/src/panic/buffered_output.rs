@@ -0,0 +1,88 @@
+//! Destination of the output of the default panic hook when the process has
+//! no panic output, like a Windows GUI application without a console or a
+//! daemon which closed its standard error.
+//!
+//! The output is kept in memory until it is retrieved with
+//! [`take_buffered_panics`]. Only the last [`CAPACITY`] bytes are kept, so that
+//! a process panicking repeatedly doesn't grow without bounds.
+
+use crate::collections::VecDeque;
+use crate::io;
+use crate::sync::{Mutex, PoisonError, TryLockError};
+
+/// The number of bytes of panic output which are kept.
+const CAPACITY: usize = 16 * 1024;
+
+/// Whether the panic output can be missing only at times on this platform, like
+/// when standard error was closed. The platforms which never have a panic
+/// output, like wasm32-unknown-unknown, don't buffer anything, so that the
+/// panic messages aren't formatted for nothing.
+pub(crate) const ENABLED: bool = cfg!(any(unix, windows));
+
+static BUFFER: Mutex<VecDeque<u8>> = Mutex::new(VecDeque::new());
+
+/// Writer appending to the ring buffer, used by the default panic hook.
+pub(crate) struct PanicBuffer;
+
+impl io::Write for PanicBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut buffer = match BUFFER.try_lock() {
+            Ok(buffer) => buffer,
+            Err(TryLockError::Poisoned(err)) => err.into_inner(),
+            // This thread panicked while the buffer was locked, waiting for the
+            // lock could deadlock. The output is lost, like when it can't be
+            // written to standard error.
+            Err(TryLockError::WouldBlock) => return Ok(buf.len()),
+        };
+        let buf_tail = &buf[buf.len().saturating_sub(CAPACITY)..];
+        let excess = (buffer.len() + buf_tail.len()).saturating_sub(CAPACITY);
+        buffer.drain(..excess);
+        buffer.extend(buf_tail);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Takes the panic messages which couldn't be printed because the process has
+/// no panic output.
+///
+/// When standard error is unavailable, like in Windows GUI applications which
+/// aren't attached to a console or in daemons which closed it, the default
+/// panic hook writes its output to an in-memory buffer instead of dropping it.
+/// This function empties the buffer and returns its content, so that the
+/// application can display it or write it to a log. An empty string is
+/// returned if nothing was buffered.
+///
+/// Only the last 16 KiB of output are kept. If older output was dropped, the
+/// returned text may start with a partial message, and with a replacement
+/// character if a UTF-8 sequence was cut. Custom panic hooks set with
+/// [`set_hook`] don't write to this buffer. Nothing is buffered on the
+/// platforms which never have a standard error, like wasm32-unknown-unknown.
+///
+/// [`set_hook`]: crate::panic::set_hook
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(panic_buffer)]
+/// use std::panic;
+///
+/// let result = panic::catch_unwind(|| {
+///     panic!("the window was closed unexpectedly");
+/// });
+/// if result.is_err() {
+///     let messages = panic::take_buffered_panics();
+///     if !messages.is_empty() {
+///         // Show `messages` to the user, e.g. in a message box.
+///     }
+/// }
+/// ```
+#[unstable(feature = "panic_buffer", issue = "none")]
+pub fn take_buffered_panics() -> String {
+    let mut buffer = BUFFER.lock().unwrap_or_else(PoisonError::into_inner);
+    let bytes = Vec::from(crate::mem::take(&mut *buffer));
+    String::from_utf8_lossy(&bytes).into_owned()
+}
@@ -0,0 +1,105 @@
+//! Breadcrumbs describing what a thread is doing, which the default panic
+//! hook prints beneath the panic message.
+//!
+//! Each thread has its own stack of contexts: [`push_context`] pushes one,
+//! and dropping the returned [`ContextGuard`] pops it. Only the
+//! [`MAX_PRINTED`] most recent contexts are printed, so that deep recursion
+//! doesn't bury the panic message.
+
+use crate::borrow::Cow;
+use crate::cell::RefCell;
+use crate::fmt;
+use crate::io::Write;
+use crate::marker::PhantomData;
+
+/// The number of contexts printed by the default panic hook. The older ones
+/// are only counted.
+const MAX_PRINTED: usize = 8;
+
+thread_local! {
+    static CONTEXTS: RefCell<Vec<Cow<'static, str>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Guard returned by [`push_context`], which pops the context when dropped.
+#[unstable(feature = "panic_context", issue = "none")]
+#[must_use = "the context is popped as soon as the guard is dropped"]
+pub struct ContextGuard {
+    /// The number of contexts of the thread before this one was pushed.
+    depth: usize,
+    /// The context can only be popped from the stack of the thread which
+    /// pushed it.
+    _not_send: PhantomData<*const ()>,
+}
+
+#[unstable(feature = "panic_context", issue = "none")]
+impl fmt::Debug for ContextGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContextGuard").finish_non_exhaustive()
+    }
+}
+
+#[unstable(feature = "panic_context", issue = "none")]
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        // Truncating rather than popping also removes the contexts pushed
+        // after this one whose guard was leaked. The thread-local may already
+        // be destroyed if the guard is dropped while the thread exits.
+        let _ = CONTEXTS.try_with(|contexts| contexts.borrow_mut().truncate(self.depth));
+    }
+}
+
+/// Pushes a description of what the current thread is doing, which the
+/// default panic hook prints beneath the message of the panics raised until
+/// the returned guard is dropped.
+///
+/// The contexts are printed most recent first, and only the most recent
+/// ones are printed if there are many. Custom panic hooks don't print them.
+///
+/// # Examples
+///
+/// ```should_panic
+/// #![feature(panic_context)]
+/// use std::panic;
+///
+/// fn parse_port(port: &str) -> u16 {
+///     let _guard = panic::push_context(format!("parsing port `{port}`"));
+///     port.parse().unwrap()
+/// }
+///
+/// let _guard = panic::push_context("parsing config");
+/// assert_eq!(parse_port("8080"), 8080);
+/// // Prints the panic message, followed by:
+/// //
+/// // context:
+/// //   parsing port `http`
+/// //   parsing config
+/// parse_port("http");
+/// ```
+#[unstable(feature = "panic_context", issue = "none")]
+pub fn push_context(context: impl Into<Cow<'static, str>>) -> ContextGuard {
+    let context = context.into();
+    let depth = CONTEXTS.with(|contexts| {
+        let mut contexts = contexts.borrow_mut();
+        contexts.push(context);
+        contexts.len() - 1
+    });
+    ContextGuard { depth, _not_send: PhantomData }
+}
+
+/// Writes the contexts of the current thread for the default panic hook.
+pub(crate) fn write(err: &mut dyn Write) {
+    let _ = CONTEXTS.try_with(|contexts| {
+        // The thread may have panicked while pushing or popping a context.
+        let Ok(contexts) = contexts.try_borrow() else { return };
+        if contexts.is_empty() {
+            return;
+        }
+        let _ = writeln!(err, "context:");
+        for context in contexts.iter().rev().take(MAX_PRINTED) {
+            let _ = writeln!(err, "  {context}");
+        }
+        if contexts.len() > MAX_PRINTED {
+            let _ = writeln!(err, "  ... and {} more", contexts.len() - MAX_PRINTED);
+        }
+    });
+}
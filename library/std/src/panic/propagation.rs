@@ -0,0 +1,156 @@
+//! Propagation of panics between threads or tasks, like from a task run by an
+//! async executor to the code awaiting its join handle.
+//!
+//! [`catch_unwind`] only returns the payload of a panic, so resuming it with
+//! [`resume_unwind`] loses the location where the panic was raised.
+//! [`PanicPayload`] also keeps the location, and restores it when the panic is
+//! resumed so that it can be propagated again.
+//!
+//! [`catch_unwind`]: crate::panic::catch_unwind
+//! [`resume_unwind`]: crate::panic::resume_unwind
+
+use crate::any::Any;
+use crate::cell::Cell;
+use crate::fmt;
+use crate::panic::{self, Location, UnwindSafe};
+use crate::panicking;
+
+thread_local! {
+    /// The number of [`PanicPayload::catch`] calls running on this thread.
+    /// Locations are only recorded when it isn't zero.
+    static CATCHING: Cell<usize> = const { Cell::new(0) };
+    /// The location of the last panic raised or resumed on this thread while
+    /// [`CATCHING`] isn't zero.
+    static LAST_LOCATION: Cell<Option<Location<'static>>> = const { Cell::new(None) };
+}
+
+/// A panic caught by [`PanicPayload::catch`], which can be resumed later,
+/// possibly on another thread, with [`resume_in_place`].
+///
+/// Unlike the payload returned by [`catch_unwind`], this also keeps the
+/// location at which the panic was raised. This lets async executors and
+/// thread pools propagate the panics of their tasks to the code waiting for
+/// their result without losing where they come from: the panic can be caught
+/// and resumed any number of times and keeps its original location.
+///
+/// [`resume_in_place`]: PanicPayload::resume_in_place
+/// [`catch_unwind`]: crate::panic::catch_unwind
+///
+/// # Examples
+///
+/// ```
+/// #![feature(panic_propagation)]
+/// use std::panic::{AssertUnwindSafe, PanicPayload};
+/// use std::thread;
+///
+/// let task = thread::spawn(|| PanicPayload::catch(|| panic!("task failed")));
+/// let panic = task.join().unwrap().unwrap_err();
+///
+/// assert_eq!(panic.payload().downcast_ref::<&str>(), Some(&"task failed"));
+/// assert_eq!(panic.location().unwrap().file(), file!());
+///
+/// // Propagates the panic of the task to the current thread.
+/// let result = PanicPayload::catch(AssertUnwindSafe(|| panic.resume_in_place()));
+/// assert_eq!(result.unwrap_err().location().unwrap().file(), file!());
+/// ```
+#[unstable(feature = "panic_propagation", issue = "none")]
+pub struct PanicPayload {
+    payload: Box<dyn Any + Send>,
+    location: Option<Location<'static>>,
+}
+
+impl PanicPayload {
+    /// Invokes a closure, capturing the cause of an unwinding panic if one
+    /// occurs.
+    ///
+    /// This is [`catch_unwind`], except that the location of the panic is
+    /// also returned, see [`location`]. Panics resumed with
+    /// [`resume_in_place`] keep their original location.
+    ///
+    /// [`catch_unwind`]: crate::panic::catch_unwind
+    /// [`location`]: PanicPayload::location
+    /// [`resume_in_place`]: PanicPayload::resume_in_place
+    #[unstable(feature = "panic_propagation", issue = "none")]
+    pub fn catch<F: FnOnce() -> R + UnwindSafe, R>(f: F) -> Result<R, PanicPayload> {
+        struct Catching;
+
+        impl Drop for Catching {
+            fn drop(&mut self) {
+                CATCHING.with(|catching| catching.set(catching.get() - 1));
+            }
+        }
+
+        CATCHING.with(|catching| catching.set(catching.get() + 1));
+        let catching = Catching;
+        let result = panic::catch_unwind(f);
+        drop(catching);
+        result.map_err(|payload| PanicPayload {
+            payload,
+            location: LAST_LOCATION.with(|location| location.take()),
+        })
+    }
+
+    /// Returns the payload of the panic, usually a `&'static str` or a
+    /// `String`.
+    #[unstable(feature = "panic_propagation", issue = "none")]
+    pub fn payload(&self) -> &(dyn Any + Send) {
+        &*self.payload
+    }
+
+    /// Returns the location at which the panic was raised.
+    ///
+    /// This is the location reported to the panic hook. `None` is returned
+    /// for panics raised with [`resume_unwind`], which have no location.
+    ///
+    /// [`resume_unwind`]: crate::panic::resume_unwind
+    #[unstable(feature = "panic_propagation", issue = "none")]
+    pub fn location(&self) -> Option<&Location<'static>> {
+        self.location.as_ref()
+    }
+
+    /// Returns the payload of the panic, to be passed to
+    /// [`resume_unwind`](crate::panic::resume_unwind) for example.
+    #[unstable(feature = "panic_propagation", issue = "none")]
+    pub fn into_payload(self) -> Box<dyn Any + Send> {
+        self.payload
+    }
+
+    /// Resumes unwinding with the original payload of the panic.
+    ///
+    /// Like [`resume_unwind`], this doesn't invoke the panic hook: the panic
+    /// was already reported when it was first raised. If the panic is caught
+    /// again by [`PanicPayload::catch`], its location is the original one.
+    ///
+    /// [`resume_unwind`]: crate::panic::resume_unwind
+    #[unstable(feature = "panic_propagation", issue = "none")]
+    pub fn resume_in_place(self) -> ! {
+        record_location(self.location.as_ref());
+        panicking::rust_panic_without_hook(self.payload)
+    }
+}
+
+#[unstable(feature = "panic_propagation", issue = "none")]
+impl fmt::Debug for PanicPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("PanicPayload");
+        if let Some(message) = self.payload.downcast_ref::<&str>() {
+            s.field("message", message);
+        } else if let Some(message) = self.payload.downcast_ref::<String>() {
+            s.field("message", message);
+        }
+        s.field("location", &self.location).finish_non_exhaustive()
+    }
+}
+
+/// Called with the location of each panic before running the panic hook, and
+/// with `None` when a panic is resumed without a location.
+pub(crate) fn record_location(location: Option<&Location<'_>>) {
+    if CATCHING.with(|catching| catching.get()) == 0 {
+        return;
+    }
+    let location = location.map(|location| {
+        let file = super::backtrace_location::intern(location.file());
+        Location::internal_constructor(file, location.line(), location.column())
+    });
+    LAST_LOCATION.with(|last| last.set(location));
+}
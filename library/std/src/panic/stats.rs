@@ -0,0 +1,153 @@
+//! Process-wide panic counters, returned by [`stats`], and the same counters
+//! for each thread name, returned by [`stats_by_thread_name`].
+
+use crate::panicking::panic_count;
+use crate::sync::{Mutex, PoisonError};
+use crate::sys_common::thread_info;
+
+/// The counters of the threads of each name, in the order the names were first
+/// seen. Threads without a name share the entry whose name is `None`.
+static BY_THREAD_NAME: Mutex<Vec<ThreadPanicStats>> = Mutex::new(Vec::new());
+
+/// A snapshot of the process-wide panic counters, as returned by [`stats`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[unstable(feature = "panic_stats", issue = "none")]
+#[non_exhaustive]
+pub struct PanicStats {
+    /// The number of panics raised since the process started.
+    ///
+    /// This counts every panic that ran the panic hook. Unwinds restarted with
+    /// [`resume_unwind`] are not counted again.
+    ///
+    /// [`resume_unwind`]: crate::panic::resume_unwind
+    pub total: usize,
+    /// The number of panics which are currently unwinding, summed over all
+    /// threads.
+    pub unwinding: usize,
+    /// The number of panics raised by the current thread which are currently
+    /// unwinding.
+    ///
+    /// This is larger than one if a destructor run during unwinding panicked
+    /// again and that panic was caught.
+    pub unwinding_on_current_thread: usize,
+    /// The number of panics which didn't end the process: the panics caught by
+    /// [`catch_unwind`], and the panics which ended the thread they were raised
+    /// by, other than the main thread.
+    ///
+    /// [`catch_unwind`]: crate::panic::catch_unwind
+    pub aborts_avoided: usize,
+}
+
+/// The panic counters of the threads with a given name, as returned by
+/// [`stats_by_thread_name`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[unstable(feature = "panic_stats", issue = "none")]
+#[non_exhaustive]
+pub struct ThreadPanicStats {
+    /// The name of the threads, or `None` for the threads without a name.
+    pub name: Option<String>,
+    /// The number of panics raised by these threads, like [`PanicStats::total`].
+    pub total: usize,
+    /// The number of panics raised by these threads which didn't end the
+    /// process, like [`PanicStats::aborts_avoided`].
+    pub aborts_avoided: usize,
+}
+
+/// Returns a snapshot of the process-wide panic counters.
+///
+/// This is intended for long-running processes that want to export panic
+/// metrics without having to install a custom panic hook. The counters are
+/// updated with relaxed atomic operations, so a snapshot taken while other
+/// threads are panicking may be slightly out of date.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(panic_stats)]
+/// use std::panic;
+///
+/// let before = panic::stats();
+/// let _ = panic::catch_unwind(|| panic!("oops"));
+/// let after = panic::stats();
+///
+/// assert!(after.total > before.total);
+/// assert!(after.aborts_avoided > before.aborts_avoided);
+/// assert_eq!(after.unwinding_on_current_thread, 0);
+/// ```
+#[unstable(feature = "panic_stats", issue = "none")]
+#[must_use]
+pub fn stats() -> PanicStats {
+    PanicStats {
+        total: panic_count::get_total_count(),
+        unwinding: panic_count::get_global_count(),
+        unwinding_on_current_thread: panic_count::get_count(),
+        aborts_avoided: panic_count::get_aborts_avoided_count(),
+    }
+}
+
+/// Returns the panic counters of the threads of each name which panicked, in
+/// the order of their first panic.
+///
+/// This is like [`stats`], for servers whose threads are named after their
+/// role, like `"worker"` or `"scheduler"`. The threads with the same name share
+/// their counters. Panics which abort the process right away, like a panic in
+/// the panic hook, are not counted.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(panic_stats)]
+/// use std::{panic, thread};
+///
+/// thread::Builder::new()
+///     .name("worker".to_string())
+///     .spawn(|| panic!("oops"))
+///     .unwrap()
+///     .join()
+///     .unwrap_err();
+///
+/// let worker = panic::stats_by_thread_name()
+///     .into_iter()
+///     .find(|stats| stats.name.as_deref() == Some("worker"))
+///     .unwrap();
+/// assert_eq!(worker.total, 1);
+/// assert_eq!(worker.aborts_avoided, 1);
+/// ```
+#[unstable(feature = "panic_stats", issue = "none")]
+#[must_use]
+pub fn stats_by_thread_name() -> Vec<ThreadPanicStats> {
+    BY_THREAD_NAME.lock().unwrap_or_else(PoisonError::into_inner).clone()
+}
+
+/// Called when a panic runs the panic hook.
+pub(crate) fn record_panic() {
+    update_current_thread(|stats| stats.total += 1);
+}
+
+/// Called when a panic is caught.
+pub(crate) fn record_caught() {
+    panic_count::abort_avoided();
+    update_current_thread(|stats| stats.aborts_avoided += 1);
+}
+
+/// Called when the runtime caught a panic which reached the top of the main
+/// thread, and which ends the process.
+pub(crate) fn record_panic_ended_process() {
+    panic_count::abort_not_avoided();
+    update_current_thread(|stats| stats.aborts_avoided -= 1);
+}
+
+fn update_current_thread(update: impl FnOnce(&mut ThreadPanicStats)) {
+    let thread = thread_info::current_thread();
+    let name = thread.as_ref().and_then(|thread| thread.name());
+    let mut by_name = BY_THREAD_NAME.lock().unwrap_or_else(PoisonError::into_inner);
+    let index = match by_name.iter().position(|stats| stats.name.as_deref() == name) {
+        Some(index) => index,
+        None => {
+            let name = name.map(String::from);
+            by_name.push(ThreadPanicStats { name, total: 0, aborts_avoided: 0 });
+            by_name.len() - 1
+        }
+    };
+    update(&mut by_name[index]);
+}
@@ -54,3 +54,24 @@ fn baz<T: UnwindSafe>() {
         assert::<Arc<AssertUnwindSafe<T>>>();
     }
 }
+
+#[test]
+fn panic_buffer_keeps_last_output() {
+    use crate::io::Write;
+    use crate::panic::buffered_output::PanicBuffer;
+    use crate::panic::take_buffered_panics;
+
+    assert_eq!(take_buffered_panics(), "");
+    PanicBuffer.write_all(b"first\n").unwrap();
+    PanicBuffer.write_all(b"second\n").unwrap();
+    assert_eq!(take_buffered_panics(), "first\nsecond\n");
+    assert_eq!(take_buffered_panics(), "");
+
+    // Only the last 16 KiB are kept.
+    PanicBuffer.write_all(&[b'a'; 10 * 1024]).unwrap();
+    PanicBuffer.write_all(&[b'b'; 10 * 1024]).unwrap();
+    let output = take_buffered_panics();
+    assert_eq!(output.len(), 16 * 1024);
+    assert!(output.starts_with(&"a".repeat(6 * 1024)));
+    assert!(output.ends_with(&"b".repeat(10 * 1024)));
+}
@@ -0,0 +1,53 @@
+//! Tracking of the destructors of thread-locals run by the current thread, so
+//! that a panic raised by one of them explains why the process is about to
+//! abort. A panic which is caught inside the destructor doesn't abort, so the
+//! note is only printed once the abort is certain.
+
+use crate::any::type_name;
+use crate::cell::Cell;
+use crate::sys_common::thread_info;
+
+thread_local! {
+    /// The type of the thread-local whose destructor the current thread is
+    /// running, if any.
+    static RUNNING_DTOR: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+/// Marks the current thread as running the destructor of a thread-local of
+/// type `T` until the returned guard is dropped.
+pub(crate) fn running_dtor<T>() -> RunningDtor {
+    let previous = RUNNING_DTOR.try_with(|dtor| dtor.replace(Some(type_name::<T>())));
+    RunningDtor { previous: previous.ok().flatten() }
+}
+
+pub(crate) struct RunningDtor {
+    previous: Option<&'static str>,
+}
+
+impl Drop for RunningDtor {
+    fn drop(&mut self) {
+        let _ = RUNNING_DTOR.try_with(|dtor| dtor.set(self.previous));
+    }
+}
+
+/// Prints a note if the panic being raised, which aborts the process without
+/// unwinding for the given reason, comes from the destructor of a thread-local.
+pub(crate) fn print_abort_note(reason: &str) {
+    let Ok(Some(ty)) = RUNNING_DTOR.try_with(Cell::get) else { return };
+    print_note(ty, reason);
+}
+
+/// Prints a note before aborting the process because a panic unwound out of
+/// the destructor of a thread-local of type `T`.
+pub(crate) fn print_unwound_note<T>() {
+    print_note(type_name::<T>(), "the panic unwound out of the destructor");
+}
+
+fn print_note(ty: &str, reason: &str) {
+    let thread = thread_info::current_thread();
+    let name = thread.as_ref().and_then(|t| t.name()).unwrap_or("<unnamed>");
+    rtprintpanic!(
+        "note: the panic was raised by the destructor of a thread-local of type `{ty}` while \
+         thread '{name}' was exiting. {reason}, so the process will abort.\n"
+    );
+}
@@ -0,0 +1,138 @@
+//! Measurement of the time spent in panic hooks and unwinding, to help
+//! latency-sensitive programs find out how much panics caught with
+//! [`catch_unwind`] cost them.
+//!
+//! The measurements are only made when the `RUST_PANIC_TIMINGS` environment
+//! variable is set to a value other than `0` when the first panic is raised,
+//! as reading the clock isn't free.
+//!
+//! [`catch_unwind`]: crate::panic::catch_unwind
+
+use crate::cell::Cell;
+use crate::sync::atomic::{AtomicU8, Ordering};
+use crate::time::{Duration, Instant};
+
+/// Whether the measurements are enabled: `0` if `RUST_PANIC_TIMINGS` wasn't
+/// read yet, `1` if they are disabled and `2` if they are enabled.
+static ENABLED: AtomicU8 = AtomicU8::new(0);
+
+thread_local! {
+    static TIMINGS: Cell<UnwindTimings> = const { Cell::new(UnwindTimings::ZERO) };
+    /// The time at which the panic being unwound on this thread started
+    /// unwinding, if the measurements are enabled.
+    static UNWIND_START: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// The time spent by the current thread in panic hooks and unwinding, as
+/// returned by [`unwind_timings`].
+#[unstable(feature = "panic_timings", issue = "none")]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct UnwindTimings {
+    /// The number of panics which ran the panic hook.
+    pub hooks: u64,
+    /// The total time spent running the panic hook, including printing the
+    /// panic message and backtrace with the default hook.
+    pub hook_time: Duration,
+    /// The number of panics whose unwinding was caught, by [`catch_unwind`]
+    /// for example.
+    ///
+    /// [`catch_unwind`]: crate::panic::catch_unwind
+    pub unwinds: u64,
+    /// The total time spent unwinding, from the end of the panic hook (or the
+    /// call to [`resume_unwind`]) until the panic was caught. This includes
+    /// running the destructors of the unwound frames.
+    ///
+    /// [`resume_unwind`]: crate::panic::resume_unwind
+    pub unwind_time: Duration,
+}
+
+impl UnwindTimings {
+    const ZERO: UnwindTimings = UnwindTimings {
+        hooks: 0,
+        hook_time: Duration::ZERO,
+        unwinds: 0,
+        unwind_time: Duration::ZERO,
+    };
+}
+
+/// Returns the time spent by the current thread in panic hooks and unwinding
+/// since it started.
+///
+/// The measurements are only made when the `RUST_PANIC_TIMINGS` environment
+/// variable is set to a value other than `0` when the first panic of the
+/// process is raised. Otherwise, the returned timings are always zero.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(panic_timings)]
+/// use std::panic;
+///
+/// let _ = panic::catch_unwind(|| panic!("oops"));
+/// let timings = panic::unwind_timings();
+/// if timings.unwinds > 0 {
+///     println!("spent {:?} unwinding", timings.unwind_time);
+/// }
+/// ```
+#[unstable(feature = "panic_timings", issue = "none")]
+pub fn unwind_timings() -> UnwindTimings {
+    TIMINGS.try_with(Cell::get).unwrap_or_default()
+}
+
+fn enabled() -> bool {
+    match ENABLED.load(Ordering::Relaxed) {
+        1 => false,
+        2 => true,
+        _ => {
+            let enabled = crate::env::var_os("RUST_PANIC_TIMINGS").is_some_and(|x| &x != "0");
+            ENABLED.store(if enabled { 2 } else { 1 }, Ordering::Relaxed);
+            enabled
+        }
+    }
+}
+
+fn update(f: impl FnOnce(&mut UnwindTimings)) {
+    // The thread-local may already be destroyed if the thread panics while
+    // exiting.
+    let _ = TIMINGS.try_with(|timings| {
+        let mut t = timings.get();
+        f(&mut t);
+        timings.set(t);
+    });
+}
+
+/// Called before running the panic hook. The returned value is passed to
+/// [`hook_finished`].
+pub(crate) fn hook_started() -> Option<Instant> {
+    enabled().then(Instant::now)
+}
+
+/// Called after running the panic hook.
+pub(crate) fn hook_finished(start: Option<Instant>) {
+    if let Some(start) = start {
+        let elapsed = start.elapsed();
+        update(|t| {
+            t.hooks += 1;
+            t.hook_time += elapsed;
+        });
+    }
+}
+
+/// Called right before unwinding starts.
+pub(crate) fn unwinding_started() {
+    if enabled() {
+        let _ = UNWIND_START.try_with(|start| start.set(Some(Instant::now())));
+    }
+}
+
+/// Called when unwinding is caught.
+pub(crate) fn unwinding_finished() {
+    if let Ok(Some(start)) = UNWIND_START.try_with(Cell::take) {
+        let elapsed = start.elapsed();
+        update(|t| {
+            t.unwinds += 1;
+            t.unwind_time += elapsed;
+        });
+    }
+}
@@ -0,0 +1,142 @@
+//! A callback for the panics which abort the process instead of unwinding,
+//! for embedders which need to record something before the process dies.
+//!
+//! The panic hook also runs for the panics which are caught, and it isn't run
+//! at all for some of the aborting ones, like a panic inside the hook itself.
+//! The callback set with [`on_unrecoverable`] is only called right before a
+//! panic aborts the process, and calling it doesn't allocate.
+
+use crate::panic::Location;
+use crate::ptr;
+use crate::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+
+/// The callback set with [`on_unrecoverable`], as a `fn(&UnrecoverablePanic<'_>)`,
+/// or null if there is none.
+static CALLBACK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Whether the callback is running, so that a panic inside it aborts without
+/// calling it again.
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Why a panic aborts the process, as returned by [`UnrecoverablePanic::reason`].
+#[unstable(feature = "panic_on_unrecoverable", issue = "none")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum UnrecoverableReason {
+    /// The panic hook panicked, or a panic was raised while another one was
+    /// being processed.
+    PanicInHook,
+    /// The panic was raised after [`always_abort`] was called.
+    ///
+    /// [`always_abort`]: crate::panic::always_abort
+    AlwaysAbort,
+    /// The panic can't unwind, because it was raised while running the
+    /// destructors of another panic or in a function which can't unwind, like
+    /// an `extern "C"` function.
+    CannotUnwind,
+    /// The panic hook asked for the panic to abort with
+    /// [`PanicInfo::request_abort`].
+    ///
+    /// [`PanicInfo::request_abort`]: crate::panic::PanicInfo::request_abort
+    AbortRequested,
+}
+
+/// A panic which is about to abort the process, as passed to the callback set
+/// with [`on_unrecoverable`].
+#[unstable(feature = "panic_on_unrecoverable", issue = "none")]
+#[derive(Debug)]
+pub struct UnrecoverablePanic<'a> {
+    reason: UnrecoverableReason,
+    location: &'a Location<'a>,
+    message: Option<&'a str>,
+}
+
+impl<'a> UnrecoverablePanic<'a> {
+    /// Why the panic aborts the process.
+    #[unstable(feature = "panic_on_unrecoverable", issue = "none")]
+    pub fn reason(&self) -> UnrecoverableReason {
+        self.reason
+    }
+
+    /// The location at which the panic was raised.
+    #[unstable(feature = "panic_on_unrecoverable", issue = "none")]
+    pub fn location(&self) -> &Location<'_> {
+        self.location
+    }
+
+    /// The message of the panic, if it is a string literal which doesn't need
+    /// to be formatted, like the one of `panic!("literal")`.
+    #[unstable(feature = "panic_on_unrecoverable", issue = "none")]
+    pub fn message(&self) -> Option<&str> {
+        self.message
+    }
+}
+
+/// Sets a callback which is called right before a panic aborts the process,
+/// replacing the previous one.
+///
+/// Unlike the panic hook set with [`set_hook`], the callback isn't called for
+/// the panics which unwind, and it is called for the panics which abort:
+/// when the panic hook panics, when [`always_abort`] was called, when the
+/// panic can't unwind and when the hook requested an abort. It is called after
+/// the panic message is printed, and the process aborts when it returns.
+///
+/// The callback is not called for the panics which abort because the program
+/// is built with `-C panic=abort`: these panics are handed to the panic
+/// runtime like the ones which unwind, and it aborts the process without
+/// returning to the standard library. Only the panic hook runs for them.
+///
+/// Calling the callback doesn't allocate. However, the callback may be called
+/// at any point of the program, possibly while a lock of the allocator or of
+/// the standard output is held by the panicking thread, so it should restrict
+/// itself to operations which are async-signal-safe, like writing to a file
+/// descriptor. In particular, [`eprintln!`] and [`stderr`] take a lock, so
+/// the example below writes to the file descriptor of the standard error
+/// directly. If the callback panics, the process aborts without calling it
+/// again.
+///
+/// [`set_hook`]: crate::panic::set_hook
+/// [`always_abort`]: crate::panic::always_abort
+/// [`eprintln!`]: crate::eprintln
+/// [`stderr`]: crate::io::stderr
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(panic_on_unrecoverable)]
+/// # #[cfg(unix)] {
+/// use std::fs::File;
+/// use std::io::Write;
+/// use std::mem::ManuallyDrop;
+/// use std::os::fd::FromRawFd;
+/// use std::panic;
+///
+/// panic::on_unrecoverable(|info| {
+///     // SAFETY: the standard error stays open, and the file is never dropped
+///     // so it isn't closed.
+///     let mut stderr = ManuallyDrop::new(unsafe { File::from_raw_fd(2) });
+///     if let Some(message) = info.message() {
+///         let _ = stderr.write_all(b"aborting because of: ");
+///         let _ = stderr.write_all(message.as_bytes());
+///         let _ = stderr.write_all(b"\n");
+///     }
+/// });
+/// # }
+/// ```
+#[unstable(feature = "panic_on_unrecoverable", issue = "none")]
+pub fn on_unrecoverable(callback: fn(&UnrecoverablePanic<'_>)) {
+    CALLBACK.store(callback as *mut (), Ordering::Release);
+}
+
+/// Calls the callback set with [`on_unrecoverable`], if there is one, for a
+/// panic which is about to abort.
+pub(crate) fn notify(reason: UnrecoverableReason, location: &Location<'_>, message: Option<&str>) {
+    let callback = CALLBACK.load(Ordering::Acquire);
+    if callback.is_null() || RUNNING.swap(true, Ordering::Relaxed) {
+        return;
+    }
+    // SAFETY: the only pointers stored in `CALLBACK` are callbacks of this type.
+    let callback =
+        unsafe { crate::mem::transmute::<*mut (), fn(&UnrecoverablePanic<'_>)>(callback) };
+    callback(&UnrecoverablePanic { reason, location, message });
+}
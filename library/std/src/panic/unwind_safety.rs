@@ -0,0 +1,191 @@
+//! Diagnostic mode detecting state which is observed after a caught panic.
+//!
+//! When enabled through [`unwind_safe_assert_in_tests`], every `catch_unwind`
+//! scope gets an id, and locking a [`Mutex`], write-locking a [`RwLock`] or
+//! mutably borrowing a [`RefCell`] inside a scope tags the lock with the
+//! innermost active scope. When a panic is caught, the locks tagged with the
+//! scope that panicked are marked as tainted, and acquiring a tainted lock
+//! afterwards prints a warning: the panic may have interrupted an update of the
+//! protected data after the guard was released, which lock poisoning cannot
+//! detect, and which `RefCell` doesn't even try to.
+//!
+//! Tracking is per-thread and keyed by the address of the lock, so a lock
+//! which is dropped and whose memory is reused may be reported spuriously.
+//!
+//! The lock types of `std::sync` call [`lock_acquired`] directly. `RefCell` is
+//! defined in `core`, which cannot reach the thread-local state of this module,
+//! so its borrows are reported through a hook which is only set while the mode
+//! is enabled.
+//!
+//! [`Mutex`]: crate::sync::Mutex
+//! [`RwLock`]: crate::sync::RwLock
+//! [`RefCell`]: crate::cell::RefCell
+
+use crate::cell::Cell;
+use crate::collections::HashMap;
+use crate::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Copy)]
+enum Tag {
+    /// Modified inside the `catch_unwind` scope with this id, which hasn't
+    /// returned yet.
+    Scope(u64),
+    /// Modified inside a `catch_unwind` scope that caught a panic.
+    Tainted,
+}
+
+#[derive(Default)]
+struct State {
+    /// Ids of the `catch_unwind` scopes active on this thread, innermost last.
+    scopes: Vec<u64>,
+    next_scope: u64,
+    locks: HashMap<usize, Tag>,
+}
+
+thread_local! {
+    // Not a `RefCell`, whose borrows are themselves tracked.
+    static STATE: Cell<State> = Cell::new(State::default());
+}
+
+/// Runs `f` on the state of this thread, if it is still available. The state
+/// is taken out of its cell while `f` runs, so a lock acquired by `f` itself,
+/// like when printing the warning, sees an empty state instead of re-entering
+/// it.
+fn with_state(f: impl FnOnce(&mut State)) -> bool {
+    STATE
+        .try_with(|cell| {
+            let mut state = cell.take();
+            f(&mut state);
+            cell.set(state);
+        })
+        .is_ok()
+}
+
+/// Enables or disables the unwind safety diagnostic mode.
+///
+/// This mode is meant to be turned on in tests. It helps finding logical
+/// unwind safety violations which were silenced with [`AssertUnwindSafe`]:
+/// when a [`Mutex`] is locked, a [`RwLock`] is write-locked or a [`RefCell`]
+/// is mutably borrowed inside a [`catch_unwind`] closure that panics, and the
+/// same value is locked or borrowed again after the panic was caught, a
+/// warning is printed to the panic output.
+///
+/// Values are only tracked within a single thread. Tracking has a cost for
+/// every lock acquisition and every borrow, so this should not be enabled in
+/// production.
+///
+/// [`AssertUnwindSafe`]: crate::panic::AssertUnwindSafe
+/// [`Mutex`]: crate::sync::Mutex
+/// [`RwLock`]: crate::sync::RwLock
+/// [`RefCell`]: crate::cell::RefCell
+/// [`catch_unwind`]: crate::panic::catch_unwind
+///
+/// # Examples
+///
+/// ```
+/// #![feature(unwind_safe_assert_in_tests)]
+/// use std::panic::{self, AssertUnwindSafe};
+/// use std::sync::Mutex;
+///
+/// panic::unwind_safe_assert_in_tests(true);
+///
+/// let balance = Mutex::new(100);
+/// let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+///     *balance.lock().unwrap() -= 30;
+///     // The guard was released, so `balance` is not poisoned...
+///     panic!("failed to credit the other account");
+/// }));
+///
+/// // ...but this prints a warning, since the panic interrupted the transfer.
+/// let _ = *balance.lock().unwrap();
+/// # panic::unwind_safe_assert_in_tests(false);
+/// ```
+#[unstable(feature = "unwind_safe_assert_in_tests", issue = "none")]
+pub fn unwind_safe_assert_in_tests(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+    #[cfg(target_has_atomic_load_store = "ptr")]
+    crate::cell::set_borrow_hook(enabled.then_some(refcell_borrowed as fn(*const (), bool)));
+}
+
+#[inline]
+fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Called when entering a `catch_unwind` scope. Returns whether the scope is
+/// being tracked, in which case `exit_scope` must be called when leaving it.
+#[inline]
+pub(crate) fn enter_scope() -> bool {
+    if !is_enabled() {
+        return false;
+    }
+    with_state(|state| {
+        let id = state.next_scope;
+        state.next_scope += 1;
+        state.scopes.push(id);
+    })
+}
+
+/// Called when leaving a `catch_unwind` scope entered while tracking was
+/// enabled.
+#[cold]
+pub(crate) fn exit_scope(panicked: bool) {
+    with_state(|state| {
+        let Some(id) = state.scopes.pop() else { return };
+        let parent = state.scopes.last().copied();
+        state.locks.retain(|_, tag| match *tag {
+            Tag::Scope(scope) if scope == id => {
+                // Locks modified in a scope that returned normally are now
+                // considered modified by the enclosing scope, if any.
+                *tag = if panicked {
+                    Tag::Tainted
+                } else if let Some(parent) = parent {
+                    Tag::Scope(parent)
+                } else {
+                    return false;
+                };
+                true
+            }
+            _ => true,
+        });
+    });
+}
+
+/// Called by the lock types when they are acquired.
+#[inline]
+pub(crate) fn lock_acquired(addr: *const (), kind: &'static str, exclusive: bool) {
+    if is_enabled() {
+        lock_acquired_slow(addr, kind, exclusive);
+    }
+}
+
+/// The hook set in `core` while tracking is enabled, called when a `RefCell`
+/// is borrowed.
+#[cfg(target_has_atomic_load_store = "ptr")]
+fn refcell_borrowed(addr: *const (), exclusive: bool) {
+    lock_acquired_slow(addr, "RefCell", exclusive);
+}
+
+#[cold]
+#[inline(never)]
+fn lock_acquired_slow(addr: *const (), kind: &'static str, exclusive: bool) {
+    with_state(|state| {
+        let current = state.scopes.last().copied();
+        let key = addr as usize;
+        if let Some(Tag::Tainted) = state.locks.get(&key) {
+            // Only report each lock once per caught panic.
+            state.locks.remove(&key);
+            rtprintpanic!(
+                "warning: {kind} at {addr:p} is used after being modified inside a \
+                 `catch_unwind` closure that panicked\n\
+                 note: the panic may have left the protected data in an inconsistent state, \
+                 see `std::panic::UnwindSafe`\n",
+            );
+        }
+        if exclusive && let Some(scope) = current {
+            state.locks.insert(key, Tag::Scope(scope));
+        }
+    });
+}
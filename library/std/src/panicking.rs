@@ -9,16 +9,19 @@
 
 #![deny(unsafe_op_in_unsafe_fn)]
 
+use crate::panic::buffered_output::{self, PanicBuffer};
 use crate::panic::BacktraceStyle;
-use core::panic::{BoxMeUp, Location, PanicInfo};
+use crate::panic::unrecoverable::{self, UnrecoverableReason};
+use core::panic::{AssertFailure, BoxMeUp, Location, PanicInfo, PanicPayloadKind};
 
-use crate::any::Any;
+use crate::any::{Any, TypeId};
 use crate::fmt;
 use crate::intrinsics;
 use crate::mem::{self, ManuallyDrop};
 use crate::process;
-use crate::sync::atomic::{AtomicBool, Ordering};
-use crate::sync::{PoisonError, RwLock};
+use crate::ptr;
+use crate::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+use crate::sync::{Arc, Mutex, PoisonError};
 use crate::sys::stdio::panic_output;
 use crate::sys_common::backtrace;
 use crate::sys_common::thread_info;
@@ -69,29 +72,105 @@ extern "C" fn __rust_foreign_exception() -> ! {
     rtabort!("Rust cannot catch foreign exceptions");
 }
 
-enum Hook {
-    Default,
-    Custom(Box<dyn Fn(&PanicInfo<'_>) + 'static + Sync + Send>),
+type CustomHook = Box<dyn Fn(&PanicInfo<'_>) + 'static + Sync + Send>;
+
+/// A registered panic hook, or assert formatter.
+///
+/// A panicking thread must never wait for a thread replacing the hook, so the
+/// hook isn't behind a lock. Instead, the custom hook is stored in an `Arc`
+/// whose pointer is published atomically, and readers clone that `Arc` before
+/// calling it. A null pointer means that the default hook is registered.
+///
+/// The only thing left to synchronize is a writer freeing the hook it replaced
+/// while a reader is between loading the pointer and incrementing the
+/// reference count. Readers announce that they are in this window by
+/// incrementing the counter of the current generation, and writers start a new
+/// generation after swapping the pointer and wait for the counter of the
+/// previous one to go down to zero. As the generations alternate between two
+/// counters, a writer only ever waits for the readers which already started. A
+/// reader retries if a new generation started while it was registering itself,
+/// so it never blocks, but can be delayed by a writer replacing the hook over
+/// and over.
+struct HookCell<T> {
+    hook: AtomicPtr<T>,
+    /// Which of `readers` new readers should increment.
+    generation: AtomicUsize,
+    readers: [AtomicUsize; 2],
+    /// Held by the functions replacing the hook, like `set_hook`, `take_hook`
+    /// and `update_hook`, so that they are still mutually exclusive.
+    write_lock: Mutex<()>,
 }
 
-impl Hook {
-    #[inline]
-    fn into_box(self) -> Box<dyn Fn(&PanicInfo<'_>) + 'static + Sync + Send> {
-        match self {
-            Hook::Default => Box::new(default_hook),
-            Hook::Custom(hook) => hook,
+/// The registered panic hook.
+static HOOK: HookCell<CustomHook> = HookCell::new();
+
+impl<T> HookCell<T> {
+    const fn new() -> Self {
+        HookCell {
+            hook: AtomicPtr::new(ptr::null_mut()),
+            generation: AtomicUsize::new(0),
+            readers: [AtomicUsize::new(0), AtomicUsize::new(0)],
+            write_lock: Mutex::new(()),
         }
     }
-}
 
-impl Default for Hook {
-    #[inline]
-    fn default() -> Hook {
-        Hook::Default
+    /// Returns the current hook, or `None` if it is the default hook. This
+    /// never waits for a writer.
+    fn load(&self) -> Option<Arc<T>> {
+        let readers = loop {
+            let generation = self.generation.load(Ordering::SeqCst);
+            let readers = &self.readers[generation & 1];
+            readers.fetch_add(1, Ordering::SeqCst);
+            // If a writer started a new generation in the meantime, it might
+            // not have seen us: it could free the hook we are about to load.
+            if self.generation.load(Ordering::SeqCst) == generation {
+                break readers;
+            }
+            readers.fetch_sub(1, Ordering::SeqCst);
+        };
+        let hook = self.hook.load(Ordering::SeqCst);
+        let hook = if hook.is_null() {
+            None
+        } else {
+            // SAFETY: `hook` comes from `Arc::into_raw` and the writer which
+            // replaced it (if any) waits for us to be done before dropping
+            // its reference.
+            unsafe {
+                Arc::increment_strong_count(hook);
+                Some(Arc::from_raw(hook))
+            }
+        };
+        readers.fetch_sub(1, Ordering::SeqCst);
+        hook
+    }
+
+    /// Replaces the current hook, returning the previous one. `write_lock`
+    /// must be held.
+    fn swap(&self, new: Option<Arc<T>>) -> Option<Arc<T>> {
+        let new = new.map_or(ptr::null_mut(), |hook| Arc::into_raw(hook).cast_mut());
+        let old = self.hook.swap(new, Ordering::SeqCst);
+        // Readers which load the generation from now on will see the new hook,
+        // only wait for the ones which may have loaded the old one.
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst);
+        let readers = &self.readers[generation & 1];
+        while readers.load(Ordering::SeqCst) != 0 {
+            thread::yield_now();
+        }
+        // SAFETY: `old` comes from `Arc::into_raw` and no reader can still be
+        // about to increment its reference count.
+        (!old.is_null()).then(|| unsafe { Arc::from_raw(old) })
     }
 }
 
-static HOOK: RwLock<Hook> = RwLock::new(Hook::Default);
+/// Turns a hook which was registered back into a `Box`.
+fn into_box(hook: Option<Arc<CustomHook>>) -> CustomHook {
+    match hook.map(Arc::try_unwrap) {
+        None => Box::new(default_hook),
+        Some(Ok(hook)) => hook,
+        // A panicking thread is still running it.
+        Some(Err(hook)) => Box::new(move |info: &PanicInfo<'_>| hook(info)),
+    }
+}
 
 /// Registers a custom panic hook, replacing the previously registered hook.
 ///
@@ -135,10 +214,9 @@ pub fn set_hook(hook: Box<dyn Fn(&PanicInfo<'_>) + 'static + Sync + Send>) {
         panic!("cannot modify the panic hook from a panicking thread");
     }
 
-    let new = Hook::Custom(hook);
-    let mut hook = HOOK.write().unwrap_or_else(PoisonError::into_inner);
-    let old = mem::replace(&mut *hook, new);
-    drop(hook);
+    let lock = HOOK.write_lock.lock().unwrap_or_else(PoisonError::into_inner);
+    let old = HOOK.swap(Some(Arc::new(hook)));
+    drop(lock);
     // Only drop the old hook after releasing the lock to avoid deadlocking
     // if its destructor panics.
     drop(old);
@@ -179,11 +257,11 @@ pub fn take_hook() -> Box<dyn Fn(&PanicInfo<'_>) + 'static + Sync + Send> {
         panic!("cannot modify the panic hook from a panicking thread");
     }
 
-    let mut hook = HOOK.write().unwrap_or_else(PoisonError::into_inner);
-    let old_hook = mem::take(&mut *hook);
-    drop(hook);
+    let lock = HOOK.write_lock.lock().unwrap_or_else(PoisonError::into_inner);
+    let old_hook = HOOK.swap(None);
+    drop(lock);
 
-    old_hook.into_box()
+    into_box(old_hook)
 }
 
 /// Atomic combination of [`take_hook`] and [`set_hook`]. Use this to replace the panic handler with
@@ -229,9 +307,105 @@ pub fn update_hook<F>(hook_fn: F)
         panic!("cannot modify the panic hook from a panicking thread");
     }
 
-    let mut hook = HOOK.write().unwrap_or_else(PoisonError::into_inner);
-    let prev = mem::take(&mut *hook).into_box();
-    *hook = Hook::Custom(Box::new(move |info| hook_fn(&prev, info)));
+    let lock = HOOK.write_lock.lock().unwrap_or_else(PoisonError::into_inner);
+    // The previous hook stays registered until the new one replaces it, so
+    // that panics happening meanwhile still run it.
+    let prev = into_box(HOOK.load());
+    let new: CustomHook = Box::new(move |info| hook_fn(&prev, info));
+    let old = HOOK.swap(Some(Arc::new(new)));
+    drop(lock);
+    drop(old);
+}
+
+type AssertFormatter =
+    Box<dyn Fn(&AssertFailure<'_>, &mut fmt::Formatter<'_>) -> fmt::Result + 'static + Sync + Send>;
+
+/// The registered assert formatter, if any.
+static ASSERT_FORMATTER: HookCell<AssertFormatter> = HookCell::new();
+
+/// Registers a formatter for the messages of the panics of failed
+/// assertions, replacing the previously registered formatter.
+///
+/// The messages of the panics of [`assert_eq!`], [`assert_ne!`] and
+/// `assert_matches!` are rendered by the formatter when they are created,
+/// before the panic hook runs, instead of the default `left`/`right` message.
+/// This lets test frameworks render structured diffs of the two sides
+/// without replacing the assertion macros.
+///
+/// The formatter is given the failed [`AssertFailure`], whose [`Display`]
+/// implementation is the default message. It must not panic: a panic while
+/// formatting the message of a panic aborts the process.
+///
+/// The assert formatter is a global resource, like the panic hook.
+///
+/// [`Display`]: fmt::Display
+///
+/// # Panics
+///
+/// Panics if called from a panicking thread.
+///
+/// # Examples
+///
+/// ```should_panic
+/// #![feature(panic_assert_formatter)]
+/// use std::panic;
+///
+/// panic::set_assert_formatter(Box::new(|failure, f| {
+///     writeln!(f, "values differ")?;
+///     writeln!(f, "- {:?}", failure.left())?;
+///     write!(f, "+ {:?}", failure.right())
+/// }));
+///
+/// assert_eq!(1 + 1, 3);
+/// ```
+#[unstable(feature = "panic_assert_formatter", issue = "none")]
+pub fn set_assert_formatter(
+    formatter: Box<
+        dyn Fn(&AssertFailure<'_>, &mut fmt::Formatter<'_>) -> fmt::Result + 'static + Sync + Send,
+    >,
+) {
+    if thread::panicking() {
+        panic!("cannot modify the assert formatter from a panicking thread");
+    }
+
+    let lock = ASSERT_FORMATTER.write_lock.lock().unwrap_or_else(PoisonError::into_inner);
+    let old = ASSERT_FORMATTER.swap(Some(Arc::new(formatter)));
+    drop(lock);
+    // Only drop the old formatter after releasing the lock to avoid
+    // deadlocking if its destructor panics.
+    drop(old);
+}
+
+/// Unregisters the current assert formatter and returns it, if one was
+/// registered. The default messages are used again.
+///
+/// *See also the function [`set_assert_formatter`].*
+///
+/// # Panics
+///
+/// Panics if called from a panicking thread.
+#[must_use]
+#[unstable(feature = "panic_assert_formatter", issue = "none")]
+pub fn take_assert_formatter() -> Option<
+    Box<dyn Fn(&AssertFailure<'_>, &mut fmt::Formatter<'_>) -> fmt::Result + 'static + Sync + Send>,
+> {
+    if thread::panicking() {
+        panic!("cannot modify the assert formatter from a panicking thread");
+    }
+
+    let lock = ASSERT_FORMATTER.write_lock.lock().unwrap_or_else(PoisonError::into_inner);
+    let old = ASSERT_FORMATTER.swap(None);
+    drop(lock);
+
+    old.map(|formatter| match Arc::try_unwrap(formatter) {
+        Ok(formatter) => formatter,
+        // A panicking thread is still running it.
+        Err(formatter) => {
+            Box::new(move |failure: &AssertFailure<'_>, f: &mut fmt::Formatter<'_>| {
+                formatter(failure, f)
+            })
+        }
+    })
 }
 
 /// The default panic handler.
@@ -249,18 +423,13 @@ fn default_hook(info: &PanicInfo<'_>) {
     // The current implementation always returns `Some`.
     let location = info.location().unwrap();
 
-    let msg = match info.payload().downcast_ref::<&'static str>() {
-        Some(s) => *s,
-        None => match info.payload().downcast_ref::<String>() {
-            Some(s) => &s[..],
-            None => "Box<dyn Any>",
-        },
-    };
+    let msg = crate::panic::payload_as_str(info.payload()).unwrap_or("Box<dyn Any>");
     let thread = thread_info::current_thread();
     let name = thread.as_ref().and_then(|t| t.name()).unwrap_or("<unnamed>");
 
     let write = |err: &mut dyn crate::io::Write| {
         let _ = writeln!(err, "thread '{name}' panicked at {location}:\n{msg}");
+        crate::panic::context::write(err);
 
         static FIRST_PANIC: AtomicBool = AtomicBool::new(true);
 
@@ -290,6 +459,8 @@ fn default_hook(info: &PanicInfo<'_>) {
         set_output_capture(Some(local));
     } else if let Some(mut out) = panic_output() {
         write(&mut out);
+    } else if buffered_output::ENABLED {
+        write(&mut PanicBuffer);
     }
 }
 
@@ -298,6 +469,8 @@ fn default_hook(info: &PanicInfo<'_>) {
 #[unstable(feature = "update_panic_count", issue = "none")]
 pub mod panic_count {
     use crate::cell::Cell;
+    use crate::panic::Location;
+    use crate::ptr;
     use crate::sync::atomic::{AtomicUsize, Ordering};
 
     pub const ALWAYS_ABORT_FLAG: usize = 1 << (usize::BITS - 1);
@@ -315,6 +488,18 @@ pub enum MustAbort {
         static LOCAL_PANIC_COUNT: Cell<(usize, bool)> = const { Cell::new((0, false)) }
     }
 
+    // Location of the panic whose hook is currently being executed on this
+    // thread, or null. This is only used to explain which panic was being
+    // processed when a panic hook itself panics. The pointer is only set by
+    // `HookLocationGuard`, which clears it before the location goes out of
+    // scope.
+    thread_local! {
+        static HOOK_PANIC_LOCATION: Cell<*const Location<'static>> =
+            const { Cell::new(ptr::null()) }
+        // Message of the same panic, if it has no arguments to format.
+        static HOOK_PANIC_MESSAGE: Cell<Option<&'static str>> = const { Cell::new(None) }
+    }
+
     // Sum of panic counts from all threads. The purpose of this is to have
     // a fast path in `count_is_zero` (which is used by `panicking`). In any particular
     // thread, if that thread currently views `GLOBAL_PANIC_COUNT` as being zero,
@@ -342,6 +527,12 @@ pub enum MustAbort {
     // panicking thread consumes at least 2 bytes of address space.
     static GLOBAL_PANIC_COUNT: AtomicUsize = AtomicUsize::new(0);
 
+    // Number of panics that ran the panic hook since the process started, and
+    // number of panics that were caught without ending the process. They are
+    // only used to report statistics through `panic::stats`.
+    static TOTAL_PANIC_COUNT: AtomicUsize = AtomicUsize::new(0);
+    static ABORTS_AVOIDED_COUNT: AtomicUsize = AtomicUsize::new(0);
+
     // Increases the global and local panic count, and returns whether an
     // immediate abort is required.
     //
@@ -349,6 +540,9 @@ pub enum MustAbort {
     // hook is currently executing.
     pub fn increase(run_panic_hook: bool) -> Option<MustAbort> {
         let global_count = GLOBAL_PANIC_COUNT.fetch_add(1, Ordering::Relaxed);
+        if run_panic_hook {
+            TOTAL_PANIC_COUNT.fetch_add(1, Ordering::Relaxed);
+        }
         if global_count & ALWAYS_ABORT_FLAG != 0 {
             return Some(MustAbort::AlwaysAbort);
         }
@@ -363,6 +557,40 @@ pub fn increase(run_panic_hook: bool) -> Option<MustAbort> {
         })
     }
 
+    /// Records `location` and `message` as the ones of the panic whose hook is
+    /// running, until the returned guard is dropped.
+    pub fn running_panic_hook(
+        location: &Location<'_>,
+        message: Option<&'static str>,
+    ) -> HookLocationGuard {
+        let location = location as *const Location<'_> as *const Location<'static>;
+        HOOK_PANIC_LOCATION.with(|l| l.set(location));
+        HOOK_PANIC_MESSAGE.with(|m| m.set(message));
+        HookLocationGuard(())
+    }
+
+    pub struct HookLocationGuard(());
+
+    impl Drop for HookLocationGuard {
+        fn drop(&mut self) {
+            HOOK_PANIC_LOCATION.with(|l| l.set(ptr::null()));
+            HOOK_PANIC_MESSAGE.with(|m| m.set(None));
+        }
+    }
+
+    /// Calls `f` with the location of the panic whose hook is currently
+    /// running on this thread, if any, and with its message if it has no
+    /// arguments.
+    pub fn with_panic_hook_location<R>(
+        f: impl FnOnce(Option<&Location<'_>>, Option<&'static str>) -> R,
+    ) -> R {
+        let location = HOOK_PANIC_LOCATION.with(|l| l.get());
+        let message = HOOK_PANIC_MESSAGE.with(|m| m.get());
+        // SAFETY: the pointer is either null or was set by `running_panic_hook`
+        // from a reference which outlives the `HookLocationGuard` that clears it.
+        f(unsafe { location.as_ref() }, message)
+    }
+
     pub fn finished_panic_hook() {
         LOCAL_PANIC_COUNT.with(|c| {
             let (count, _) = c.get();
@@ -378,6 +606,16 @@ pub fn decrease() {
         });
     }
 
+    pub fn abort_avoided() {
+        ABORTS_AVOIDED_COUNT.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // Undoes `abort_avoided` for a panic which was caught, but by the runtime
+    // which then ends the process.
+    pub fn abort_not_avoided() {
+        ABORTS_AVOIDED_COUNT.fetch_sub(1, Ordering::Relaxed);
+    }
+
     pub fn set_always_abort() {
         GLOBAL_PANIC_COUNT.fetch_or(ALWAYS_ABORT_FLAG, Ordering::Relaxed);
     }
@@ -388,6 +626,22 @@ pub fn get_count() -> usize {
         LOCAL_PANIC_COUNT.with(|c| c.get().0)
     }
 
+    // Disregards ALWAYS_ABORT_FLAG
+    #[must_use]
+    pub fn get_global_count() -> usize {
+        GLOBAL_PANIC_COUNT.load(Ordering::Relaxed) & !ALWAYS_ABORT_FLAG
+    }
+
+    #[must_use]
+    pub fn get_total_count() -> usize {
+        TOTAL_PANIC_COUNT.load(Ordering::Relaxed)
+    }
+
+    #[must_use]
+    pub fn get_aborts_avoided_count() -> usize {
+        ABORTS_AVOIDED_COUNT.load(Ordering::Relaxed)
+    }
+
     // Disregards ALWAYS_ABORT_FLAG
     #[must_use]
     #[inline]
@@ -455,6 +709,7 @@ union Data<F, R> {
     let mut data = Data { f: ManuallyDrop::new(f) };
 
     let data_ptr = &mut data as *mut _ as *mut u8;
+    let track_unwind_safety = crate::panic::unwind_safety::enter_scope();
     // SAFETY:
     //
     // Access to the union's fields: this is `std` and we know that the `r#try`
@@ -465,11 +720,15 @@ union Data<F, R> {
     // - `do_catch`, the second argument, can be called with the `data_ptr` as well.
     // See their safety preconditions for more information
     unsafe {
-        return if intrinsics::r#try(do_call::<F, R>, data_ptr, do_catch::<F, R>) == 0 {
+        let result = if intrinsics::r#try(do_call::<F, R>, data_ptr, do_catch::<F, R>) == 0 {
             Ok(ManuallyDrop::into_inner(data.r))
         } else {
             Err(ManuallyDrop::into_inner(data.p))
         };
+        if track_unwind_safety {
+            crate::panic::unwind_safety::exit_scope(result.is_err());
+        }
+        return result;
     }
 
     // We consider unwinding to be rare, so mark this function as cold. However,
@@ -484,6 +743,8 @@ unsafe fn cleanup(payload: *mut u8) -> Box<dyn Any + Send + 'static> {
         // without undefined behavior.
         let obj = unsafe { Box::from_raw(__rust_panic_cleanup(payload)) };
         panic_count::decrease();
+        crate::panic::stats::record_caught();
+        crate::panic::timings::unwinding_finished();
         obj
     }
 
@@ -578,6 +839,10 @@ fn take_box(&mut self) -> *mut (dyn Any + Send) {
         fn get(&mut self) -> &(dyn Any + Send) {
             self.fill()
         }
+
+        fn kind(&self) -> PanicPayloadKind {
+            PanicPayloadKind::FormattedString
+        }
     }
 
     struct StrPanicPayload(&'static str);
@@ -590,17 +855,32 @@ fn take_box(&mut self) -> *mut (dyn Any + Send) {
         fn get(&mut self) -> &(dyn Any + Send) {
             &self.0
         }
+
+        fn kind(&self) -> PanicPayloadKind {
+            PanicPayloadKind::StaticStr
+        }
     }
 
-    let loc = info.location().unwrap(); // The current implementation always returns Some
-    let msg = info.message().unwrap(); // The current implementation always returns Some
-    crate::sys_common::backtrace::__rust_end_short_backtrace(move || {
+    /// The message of a failed assertion, rendered by the assert formatter.
+    struct FormattedAssertFailure<'a> {
+        failure: &'a AssertFailure<'a>,
+        formatter: &'a AssertFormatter,
+    }
+
+    impl fmt::Display for FormattedAssertFailure<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            (self.formatter)(self.failure, f)
+        }
+    }
+
+    fn panic_with_message(msg: &fmt::Arguments<'_>, info: &PanicInfo<'_>) -> ! {
         // FIXME: can we just pass `info` along rather than taking it apart here, only to have
         // `rust_panic_with_hook` construct a new `PanicInfo`?
-        if let Some(msg) = msg.as_str() {
+        let loc = info.location().unwrap(); // The current implementation always returns Some
+        if let Some(s) = msg.as_str() {
             rust_panic_with_hook(
-                &mut StrPanicPayload(msg),
-                info.message(),
+                &mut StrPanicPayload(s),
+                Some(msg),
                 loc,
                 info.can_unwind(),
                 info.force_no_backtrace(),
@@ -608,12 +888,28 @@ fn get(&mut self) -> &(dyn Any + Send) {
         } else {
             rust_panic_with_hook(
                 &mut PanicPayload::new(msg),
-                info.message(),
+                Some(msg),
                 loc,
                 info.can_unwind(),
                 info.force_no_backtrace(),
             );
         }
+    }
+
+    let msg = info.message().unwrap(); // The current implementation always returns Some
+
+    // The payload of a failed assertion is created from the message of the
+    // assert formatter, if one is registered. The formatter is only loaded
+    // for assertions.
+    let assert_formatter =
+        info.assert_failure().and_then(|f| ASSERT_FORMATTER.load().map(|h| (f, h)));
+    crate::sys_common::backtrace::__rust_end_short_backtrace(move || {
+        if let Some((failure, formatter)) = &assert_formatter {
+            let formatted = FormattedAssertFailure { failure, formatter };
+            panic_with_message(&format_args!("{formatted}"), info)
+        } else {
+            panic_with_message(msg, info)
+        }
     })
 }
 
@@ -675,6 +971,15 @@ fn get(&mut self) -> &(dyn Any + Send) {
                 None => process::abort(),
             }
         }
+
+        fn kind(&self) -> PanicPayloadKind {
+            // `panic!("message")` in the 2015 and 2018 editions of std ends up here too.
+            if TypeId::of::<A>() == TypeId::of::<&'static str>() {
+                PanicPayloadKind::StaticStr
+            } else {
+                PanicPayloadKind::Any(TypeId::of::<A>())
+            }
+        }
     }
 }
 
@@ -694,11 +999,31 @@ fn rust_panic_with_hook(
 
     // Check if we need to abort immediately.
     if let Some(must_abort) = must_abort {
-        match must_abort {
+        let reason = match must_abort {
             panic_count::MustAbort::PanicInHook => {
-                // Don't try to print the message in this case
-                // - perhaps that is causing the recursive panics.
-                rtprintpanic!("thread panicked while processing panic. aborting.\n");
+                // Don't try to format the message in this case - perhaps that
+                // is causing the recursive panics. Locations and messages
+                // without arguments are safe to print.
+                rtprintpanic!("thread panicked at {location}:\n");
+                if let Some(message) = message.and_then(|m| m.as_str()) {
+                    rtprintpanic!("{message}\n");
+                }
+                panic_count::with_panic_hook_location(|outer, outer_message| {
+                    match (outer, outer_message) {
+                        (Some(outer), Some(outer_message)) => rtprintpanic!(
+                            "thread panicked while running the panic hook for the panic at \
+                             {outer}:\n{outer_message}\naborting.\n"
+                        ),
+                        (Some(outer), None) => rtprintpanic!(
+                            "thread panicked while running the panic hook for the panic at \
+                             {outer}. aborting.\n"
+                        ),
+                        (None, _) => {
+                            rtprintpanic!("thread panicked while processing panic. aborting.\n")
+                        }
+                    }
+                });
+                UnrecoverableReason::PanicInHook
             }
             panic_count::MustAbort::AlwaysAbort => {
                 // Unfortunately, this does not print a backtrace, because creating
@@ -710,32 +1035,55 @@ fn rust_panic_with_hook(
                     force_no_backtrace,
                 );
                 rtprintpanic!("{panicinfo}\npanicked after panic::always_abort(), aborting.\n");
+                UnrecoverableReason::AlwaysAbort
             }
-        }
+        };
+        unrecoverable::notify(reason, location, message.and_then(|m| m.as_str()));
         crate::sys::abort_internal();
     }
 
+    crate::panic::stats::record_panic();
+
+    // Panics raised by code which isn't `#[track_caller]` may be reported
+    // inside std, in which case the backtrace can tell where they come from.
+    let fallback_location = crate::panic::backtrace_location::fallback_location(location);
+    let location = fallback_location.as_ref().unwrap_or(location);
+    crate::panic::propagation::record_location(Some(location));
+
     let mut info =
         PanicInfo::internal_constructor(message, location, can_unwind, force_no_backtrace);
-    let hook = HOOK.read().unwrap_or_else(PoisonError::into_inner);
-    match *hook {
+    let hook_location = panic_count::running_panic_hook(location, message.and_then(|m| m.as_str()));
+    let hook_start = crate::panic::timings::hook_started();
+    let hook = HOOK.load();
+    match hook.as_deref() {
         // Some platforms (like wasm) know that printing to stderr won't ever actually
         // print anything, and if that's the case we can skip the default
         // hook. Since string formatting happens lazily when calling `payload`
         // methods, this means we avoid formatting the string at all!
         // (The panic runtime might still call `payload.take_box()` though and trigger
-        // formatting.)
-        Hook::Default if panic_output().is_none() => {}
-        Hook::Default => {
+        // formatting.) Where the panic output is only missing at times, the default
+        // hook buffers its output for `take_buffered_panics` instead.
+        None if panic_output().is_none() && !buffered_output::ENABLED => {}
+        None => {
             info.set_payload(payload.get());
+            info.set_payload_kind(payload.kind());
             default_hook(&info);
         }
-        Hook::Custom(ref hook) => {
+        Some(hook) => {
             info.set_payload(payload.get());
+            info.set_payload_kind(payload.kind());
             hook(&info);
         }
     };
     drop(hook);
+    drop(hook_location);
+    crate::panic::timings::hook_finished(hook_start);
+    let abort_requested = info.abort_requested();
+    if !can_unwind {
+        crate::panic::thread_local_dtor::print_abort_note("the panic cannot unwind");
+    } else if abort_requested {
+        crate::panic::thread_local_dtor::print_abort_note("the panic hook requested an abort");
+    }
 
     // Indicate that we have finished executing the panic hook. After this point
     // it is fine if there is a panic while executing destructors, as long as it
@@ -747,9 +1095,20 @@ fn rust_panic_with_hook(
         // through a nounwind function (e.g. extern "C") then we cannot continue
         // unwinding and have to abort immediately.
         rtprintpanic!("thread caused non-unwinding panic. aborting.\n");
+        let message = message.and_then(|m| m.as_str());
+        unrecoverable::notify(UnrecoverableReason::CannotUnwind, location, message);
         crate::sys::abort_internal();
     }
 
+    if abort_requested {
+        // The panic hook asked for this panic to abort even though it could unwind.
+        rtprintpanic!("panic hook requested an abort. aborting.\n");
+        let message = message.and_then(|m| m.as_str());
+        unrecoverable::notify(UnrecoverableReason::AbortRequested, location, message);
+        crate::sys::abort_internal();
+    }
+
+    crate::panic::timings::unwinding_started();
     rust_panic(payload)
 }
 
@@ -768,8 +1127,13 @@ fn take_box(&mut self) -> *mut (dyn Any + Send) {
         fn get(&mut self) -> &(dyn Any + Send) {
             &*self.0
         }
+
+        fn kind(&self) -> PanicPayloadKind {
+            PanicPayloadKind::Any((*self.0).type_id())
+        }
     }
 
+    crate::panic::timings::unwinding_started();
     rust_panic(&mut RewrapBox(payload))
 }
 
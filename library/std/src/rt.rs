@@ -145,11 +145,17 @@ fn lang_start_internal(
     // user code from `main` or, more nefariously, as described in e.g. issue #86030.
     // SAFETY: Only called once during runtime initialization.
     panic::catch_unwind(move || unsafe { init(argc, argv, sigpipe) }).map_err(rt_abort)?;
-    let ret_code = panic::catch_unwind(move || panic::catch_unwind(main).unwrap_or(101) as isize)
-        .map_err(move |e| {
-            mem::forget(e);
-            rtabort!("drop of the panic payload panicked");
-        });
+    let ret_code = panic::catch_unwind(move || {
+        // A panic reaching the top of the main thread ends the process, even though it is caught.
+        panic::catch_unwind(main).unwrap_or_else(|_| {
+            crate::panic::stats::record_panic_ended_process();
+            101
+        }) as isize
+    })
+    .map_err(move |e| {
+        mem::forget(e);
+        rtabort!("drop of the panic payload panicked");
+    });
     panic::catch_unwind(cleanup).map_err(rt_abort)?;
     ret_code
 }
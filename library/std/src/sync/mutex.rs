@@ -500,6 +500,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 
 impl<'mutex, T: ?Sized> MutexGuard<'mutex, T> {
     unsafe fn new(lock: &'mutex Mutex<T>) -> LockResult<MutexGuard<'mutex, T>> {
+        crate::panic::unwind_safety::lock_acquired(lock as *const _ as *const (), "Mutex", true);
         poison::map_result(lock.poison.guard(), |guard| MutexGuard { lock, poison: guard })
     }
 }
@@ -234,10 +234,12 @@ unsafe fn try_register_dtor(&self) -> bool {
     // Wrap the call in a catch to ensure unwinding is caught in the event
     // a panic takes place in a destructor.
     if let Err(_) = panic::catch_unwind(panic::AssertUnwindSafe(|| unsafe {
+        let _running = panic::thread_local_dtor::running_dtor::<T>();
         let value = (*ptr).inner.take();
         (*ptr).dtor_state.set(DtorState::RunningOrHasRun);
         drop(value);
     })) {
+        panic::thread_local_dtor::print_unwound_note::<T>();
         rtabort!("thread local panicked on drop");
     }
 }
@@ -177,9 +177,11 @@ unsafe fn try_initialize(&'static self, init: impl FnOnce() -> T) -> Option<&'st
         let ptr = Box::from_raw(ptr as *mut Value<T>);
         let key = ptr.key;
         key.os.set(ptr::invalid_mut(1));
+        let _running = panic::thread_local_dtor::running_dtor::<T>();
         drop(ptr);
         key.os.set(ptr::null_mut());
     }) {
+        panic::thread_local_dtor::print_unwound_note::<T>();
         rtabort!("thread local panicked on drop");
     }
 }
@@ -94,6 +94,17 @@ pub fn is_ebadf(err: &io::Error) -> bool {
 
 pub const STDIN_BUF_SIZE: usize = crate::sys_common::io::DEFAULT_BUF_SIZE;
 
+#[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "vita")))]
+pub fn panic_output() -> Option<impl io::Write> {
+    // If standard error was closed, like in some daemons, the default panic
+    // hook buffers its output instead.
+    // SAFETY: `F_GETFD` only reads the flags of the descriptor, and fails with
+    // `EBADF` if it isn't open.
+    let is_open = unsafe { libc::fcntl(libc::STDERR_FILENO, libc::F_GETFD) } != -1;
+    is_open.then(Stderr::new)
+}
+
+#[cfg(any(target_os = "espidf", target_os = "horizon", target_os = "vita"))]
 pub fn panic_output() -> Option<impl io::Write> {
     Some(Stderr::new())
 }
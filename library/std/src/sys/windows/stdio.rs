@@ -457,5 +457,7 @@ pub fn is_ebadf(err: &io::Error) -> bool {
 }
 
 pub fn panic_output() -> Option<impl io::Write> {
-    Some(Stderr::new())
+    // GUI applications which aren't attached to a console have no standard
+    // error handle, the default panic hook buffers its output instead.
+    get_handle(c::STD_ERROR_HANDLE).ok().map(|_| Stderr::new())
 }
@@ -20,6 +20,7 @@
 #![feature(staged_api)]
 #![feature(process_exitcode_internals)]
 #![feature(panic_can_unwind)]
+#![feature(panic_payload_as_str)]
 #![feature(test)]
 #![allow(internal_features)]
 
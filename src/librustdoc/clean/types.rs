@@ -350,6 +350,34 @@ pub(crate) fn rustc_span(def_id: DefId, tcx: TyCtxt<'_>) -> Span {
     ))
 }
 
+/// Returns the path of `def_id` and the URL of the lines of its source, which the examples link to
+/// with `--generate-link-to-definition`.
+fn example_source_link(def_id: DefId, cx: &Context<'_>) -> Option<(String, String)> {
+    if !cx.shared.generate_link_to_definition || !def_id.is_local() {
+        return None;
+    }
+    let path = example_path(def_id, cx.tcx())?;
+    let href = cx.href_from_span(rustc_span(def_id, cx.tcx()), true)?;
+    Some((path, href))
+}
+
+/// Returns the path an example names `def_id` with, like `my_crate::Widget::new`. The associated
+/// items of trait impls don't have one, since they are named through the trait.
+fn example_path(def_id: DefId, tcx: TyCtxt<'_>) -> Option<String> {
+    let name = tcx.opt_item_name(def_id)?;
+    let parent = tcx.opt_parent(def_id)?;
+    let parent_path = match tcx.def_kind(parent) {
+        DefKind::Impl { of_trait: false } => {
+            let self_ty = tcx.type_of(parent).instantiate_identity().ty_adt_def()?;
+            example_path(self_ty.did(), tcx)?
+        }
+        DefKind::Impl { of_trait: true } => return None,
+        _ if parent.is_crate_root() => tcx.crate_name(parent.krate).to_string(),
+        _ => example_path(parent, tcx)?,
+    };
+    Some(format!("{parent_path}::{name}"))
+}
+
 fn is_field_vis_inherited(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
     let parent = tcx.parent(def_id);
     match tcx.def_kind(parent) {
@@ -464,11 +492,16 @@ pub(crate) fn links(&self, cx: &Context<'_>) -> Vec<RenderedLink> {
                     if let Some(ref fragment) = *fragment {
                         fragment.render(&mut href, cx.tcx())
                     }
+                    let target = match fragment {
+                        Some(UrlFragment::Item(def_id)) => *def_id,
+                        _ => *id,
+                    };
                     Some(RenderedLink {
                         original_text: s.clone(),
                         new_text: link_text.clone(),
                         tooltip: link_tooltip(*id, fragment, cx),
                         href,
+                        source: example_source_link(target, cx),
                     })
                 } else {
                     None
@@ -493,6 +526,7 @@ pub(crate) fn link_names(&self, cache: &Cache) -> Vec<RenderedLink> {
                 new_text: link_text.clone(),
                 href: String::new(),
                 tooltip: String::new(),
+                source: None,
             })
             .collect()
     }
@@ -1105,6 +1139,10 @@ pub struct RenderedLink {
     pub(crate) href: String,
     /// The tooltip.
     pub(crate) tooltip: String,
+    /// The path of the item the link points to and the line-anchored URL of its source, if it is
+    /// an item of the local crate and `--generate-link-to-definition` is passed. The paths
+    /// starting with this one in the examples of the docs link to its source.
+    pub(crate) source: Option<(String, String)>,
 }
 
 /// The attributes on an [`Item`], including attributes like `#[derive(...)]` and `#[inline]`,
@@ -33,6 +33,14 @@ pub(crate) fn krate(cx: &mut DocContext<'_>) -> Crate {
     // understood by rustdoc.
     let mut module = clean_doc_module(&module, cx);
 
+    cx.cache.smart_punctuation = !module.attrs.lists(sym::doc).any(|attr| {
+        attr.has_name(sym::smart_punctuation)
+            && attr
+                .meta_item()
+                .and_then(|meta| meta.name_value_literal())
+                .is_some_and(|lit| matches!(lit.kind, ast::LitKind::Bool(false)))
+    });
+
     match *module.kind {
         ItemKind::ModuleItem(ref module) => {
             for it in &module.items {
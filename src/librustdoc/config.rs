@@ -134,6 +134,11 @@ pub(crate) struct Options {
     /// Whether to run the `calculate-doc-coverage` pass, which counts the number of public items
     /// with and without documentation.
     pub(crate) show_coverage: bool,
+    /// Whether to expand the `{{#include path}}` directives found in the documentation.
+    ///
+    /// Note: this field is duplicated in `RenderOptions` because it's also needed when running
+    /// doctests.
+    pub(crate) markdown_includes: bool,
 
     // Options that alter generated documentation pages
     /// Crate version to note on the sidebar of generated docs.
@@ -198,6 +203,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             .field("test_run_directory", &self.test_run_directory)
             .field("persist_doctests", &self.persist_doctests)
             .field("show_coverage", &self.show_coverage)
+            .field("markdown_includes", &self.markdown_includes)
             .field("crate_version", &self.crate_version)
             .field("runtool", &self.runtool)
             .field("runtool_args", &self.runtool_args)
@@ -281,6 +287,45 @@ pub(crate) struct RenderOptions {
     pub(crate) no_emit_shared: bool,
     /// If `true`, HTML source code pages won't be generated.
     pub(crate) html_no_source: bool,
+    /// If `true`, external links found in the documentation are checked over the network and a
+    /// report of the dead ones is printed.
+    pub(crate) check_external_links: bool,
+    /// URL of the mermaid.js script to include in the pages to render `mermaid` code blocks as
+    /// diagrams.
+    pub(crate) mermaid_script: Option<String>,
+    /// Path of the JSON file into which the external URLs found in the documentation are written.
+    pub(crate) external_url_manifest: Option<PathBuf>,
+    /// Directory of the documentation of the standard library which the anchors of the links to
+    /// `doc.rust-lang.org` are checked against. Defaults to the one installed in the sysroot.
+    pub(crate) std_docs_dir: Option<PathBuf>,
+    /// If `true`, the `{{#include path}}` directives found in the documentation are replaced by
+    /// the content of the files they name.
+    pub(crate) markdown_includes: bool,
+    /// If `true`, `$...$` and `$$...$$` regions of the documentation are tagged as math.
+    pub(crate) enable_math: bool,
+    /// Base URL of the KaTeX distribution whose script and stylesheet are included in the pages
+    /// to render the math regions.
+    pub(crate) katex_url: Option<String>,
+    /// If `true`, the hidden lines of the examples are rendered behind a toggle instead of being
+    /// removed.
+    pub(crate) expandable_hidden_lines: bool,
+    /// If `true`, the targets the examples are ignored on with `ignore-<target>` are shown. This
+    /// is the same flag as [`Options::enable_per_target_ignores`], which applies to doctests.
+    pub(crate) enable_per_target_ignores: bool,
+    /// If `true`, the text of autolinks to API documentation on docs.rs and doc.rust-lang.org is
+    /// replaced with the path of the item they point to.
+    pub(crate) smart_link_titles: bool,
+    /// If `true`, the headings deeper than `<h6>` once offset are rendered as
+    /// `<div role="heading">` with their level, instead of being rendered as `<h6>`.
+    pub(crate) nest_deep_headings: bool,
+    /// Directory the relative image links of the documentation are resolved in. The images they
+    /// point to are copied to the output directory.
+    pub(crate) doc_assets_dir: Option<PathBuf>,
+    /// If `true`, the JSON output includes a structured representation of the docs of each item.
+    pub(crate) json_structured_docs: bool,
+    /// If `true`, the JSON output of `--show-coverage` includes how complete the docs of each
+    /// item are.
+    pub(crate) show_coverage_details: bool,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -379,6 +424,8 @@ fn println_condition(condition: Condition) {
                     WhenDocumentPrivate => println!("  (when --document-private-items)"),
                     WhenNotDocumentPrivate => println!("  (when not --document-private-items)"),
                     WhenNotDocumentHidden => println!("  (when not --document-hidden-items)"),
+                    WhenCheckExternalLinks => println!("  (when --check-external-links)"),
+                    WhenMarkdownIncludes => println!("  (when --markdown-includes)"),
                 }
             }
 
@@ -607,7 +654,20 @@ fn println_condition(condition: Condition) {
 
         let edition = config::parse_crate_edition(handler, matches);
 
-        let mut id_map = html::markdown::IdMap::new();
+        let slug_style = match matches.opt_str("heading-slug-style") {
+            None => html::markdown::SlugStyle::default(),
+            Some(style) => match style.parse() {
+                Ok(style) => style,
+                Err(()) => {
+                    diag.struct_err(format!("unknown heading slug style `{style}`"))
+                        .help("valid styles are `rustdoc` and `github`")
+                        .emit();
+                    return Err(1);
+                }
+            },
+        };
+
+        let mut id_map = html::markdown::IdMap::with_slug_style(slug_style);
         let Some(external_html) = ExternalHtml::load(
             &matches.opt_strs("html-in-header"),
             &matches.opt_strs("html-before-content"),
@@ -707,6 +767,20 @@ fn println_condition(condition: Condition) {
         let extern_html_root_takes_precedence =
             matches.opt_present("extern-html-root-takes-precedence");
         let html_no_source = matches.opt_present("html-no-source");
+        let check_external_links = matches.opt_present("check-external-links");
+        let mermaid_script = matches.opt_str("mermaid-script");
+        let external_url_manifest = matches.opt_str("external-url-manifest").map(PathBuf::from);
+        let std_docs_dir = matches.opt_str("std-docs-dir").map(PathBuf::from);
+        let markdown_includes = matches.opt_present("markdown-includes");
+        let enable_math = matches.opt_present("enable-math");
+        let katex_url =
+            matches.opt_str("enable-math").map(|url| url.trim_end_matches('/').to_owned());
+        let expandable_hidden_lines = matches.opt_present("expandable-hidden-lines");
+        let smart_link_titles = matches.opt_present("smart-link-titles");
+        let nest_deep_headings = matches.opt_present("nest-deep-headings");
+        let doc_assets_dir = matches.opt_str("doc-assets-dir").map(PathBuf::from);
+        let json_structured_docs = matches.opt_present("json-structured-docs");
+        let show_coverage_details = matches.opt_present("show-coverage-details");
 
         if generate_link_to_definition && (show_coverage || output_format != OutputFormat::Html) {
             diag.struct_err(
@@ -716,6 +790,15 @@ fn println_condition(condition: Condition) {
             return Err(1);
         }
 
+        if show_coverage_details && !(show_coverage && output_format.is_json()) {
+            diag.struct_err(
+                "--show-coverage-details option can only be used with --show-coverage and the \
+                JSON output format",
+            )
+            .emit();
+            return Err(1);
+        }
+
         let scrape_examples_options = ScrapeExamplesOptions::new(matches, &diag)?;
         let with_examples = matches.opt_strs("with-examples");
         let call_locations = crate::scrape_examples::load_call_locations(with_examples, &diag)?;
@@ -747,6 +830,7 @@ fn println_condition(condition: Condition) {
             should_test,
             test_args,
             show_coverage,
+            markdown_includes,
             crate_version,
             test_run_directory,
             persist_doctests,
@@ -792,6 +876,20 @@ fn println_condition(condition: Condition) {
             call_locations,
             no_emit_shared: false,
             html_no_source,
+            check_external_links,
+            mermaid_script,
+            external_url_manifest,
+            std_docs_dir,
+            markdown_includes,
+            enable_math,
+            katex_url,
+            expandable_hidden_lines,
+            enable_per_target_ignores,
+            smart_link_titles,
+            nest_deep_headings,
+            doc_assets_dir,
+            json_structured_docs,
+            show_coverage_details,
         };
         Ok((options, render_options))
     }
@@ -430,6 +430,8 @@ fn report_deprecated_attr(name: &str, diag: &rustc_errors::Handler, sp: Span) {
             WhenDocumentPrivate => ctxt.render_options.document_private,
             WhenNotDocumentPrivate => !ctxt.render_options.document_private,
             WhenNotDocumentHidden => !ctxt.render_options.document_hidden,
+            WhenCheckExternalLinks => ctxt.render_options.check_external_links,
+            WhenMarkdownIncludes => ctxt.render_options.markdown_includes,
         };
         if run {
             debug!("running pass {}", p.pass.name);
@@ -10,7 +10,7 @@
 use rustc_middle::ty::TyCtxt;
 use rustc_parse::maybe_new_parser_from_source_str;
 use rustc_parse::parser::attr::InnerAttrPolicy;
-use rustc_resolve::rustdoc::span_of_fragments;
+use rustc_resolve::rustdoc::{expand_fragment_includes, span_of_fragments};
 use rustc_session::config::{self, CrateType, ErrorOutputType};
 use rustc_session::parse::ParseSess;
 use rustc_session::{lint, EarlyErrorHandler, Session};
@@ -32,7 +32,7 @@
 
 use crate::clean::{types::AttributesExt, Attributes};
 use crate::config::Options as RustdocOptions;
-use crate::html::markdown::{self, ErrorCodes, Ignore, LangString};
+use crate::html::markdown::{self, ErrorCodes, Ignore, IncludedMarkdown, LangString};
 use crate::lint::init_lints;
 
 /// Options that apply to all doctests in a crate or Markdown file (for `rustdoc foo.md`).
@@ -1041,16 +1041,23 @@ fn add_test(&mut self, test: String, config: LangString, line: usize) {
             )
         };
 
+        let missing_features = config
+            .required_features
+            .iter()
+            .any(|feature| !is_feature_enabled(&self.rustdoc_options.cfgs, feature));
+
         debug!("creating test {name}: {test}");
         self.tests.push(test::TestDescAndFn {
             desc: test::TestDesc {
                 name: test::DynTestName(name),
-                ignore: match config.ignore {
-                    Ignore::All => true,
-                    Ignore::None => false,
-                    Ignore::Some(ref ignores) => ignores.iter().any(|s| target_str.contains(s)),
-                },
-                ignore_message: None,
+                ignore: missing_features
+                    || match config.ignore {
+                        Ignore::All => true,
+                        Ignore::None => false,
+                        Ignore::Some(ref ignores) => ignores.iter().any(|s| target_str.contains(s)),
+                    },
+                ignore_message: missing_features
+                    .then_some("requires features which are not enabled (`required-features`)"),
                 source_file: "",
                 start_line: 0,
                 start_col: 0,
@@ -1194,6 +1201,39 @@ fn register_header(&mut self, name: &str, level: u32) {
     }
 }
 
+/// Collects the doctests of markdown in which `{{#include}}` directives were expanded, reporting
+/// the tests coming from included files at the line of their directive.
+pub(crate) struct IncludedTests<'a, 'md> {
+    pub(crate) collector: &'a mut Collector,
+    pub(crate) included: &'a IncludedMarkdown<'md>,
+}
+
+impl Tester for IncludedTests<'_, '_> {
+    fn add_test(&mut self, test: String, config: LangString, line: usize) {
+        // `line` is one-based, and relative to the line returned by `get_line`.
+        let start = self.collector.get_line();
+        let line = start + self.included.original_line(line - start - 1) + 1;
+        self.collector.add_test(test, config, line);
+    }
+
+    fn get_line(&self) -> usize {
+        self.collector.get_line()
+    }
+
+    fn register_header(&mut self, name: &str, level: u32) {
+        self.collector.register_header(name, level);
+    }
+}
+
+/// Returns whether `feature` was enabled with `--cfg 'feature="..."'`, like cargo does.
+fn is_feature_enabled(cfgs: &[String], feature: &str) -> bool {
+    cfgs.iter().any(|cfg| {
+        cfg.split_once('=').is_some_and(|(name, value)| {
+            name.trim() == "feature" && value.trim().trim_matches('"') == feature
+        })
+    })
+}
+
 struct HirCollector<'a, 'hir, 'tcx> {
     sess: &'a Session,
     collector: &'a mut Collector,
@@ -1224,7 +1264,15 @@ fn visit_testable<F: FnOnce(&mut Self)>(
 
         // The collapse-docs pass won't combine sugared/raw doc attributes, or included files with
         // anything else, this will combine them for us.
-        let attrs = Attributes::from_ast(ast_attrs);
+        let mut attrs = Attributes::from_ast(ast_attrs);
+        if self.collector.rustdoc_options.markdown_includes {
+            let includes = expand_fragment_includes(self.sess.source_map(), &mut attrs.doc_strings);
+            for (span, include) in includes {
+                if let Err(err) = include.result {
+                    self.sess.span_err(span, err);
+                }
+            }
+        }
         if let Some(doc) = attrs.opt_doc_value() {
             // Use the outermost invocation, so that doctest names come from where the docs were written.
             let span = ast_attrs
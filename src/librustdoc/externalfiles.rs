@@ -48,6 +48,14 @@ pub(crate) fn load(
                 heading_offset: HeadingOffset::H2,
                 // For external files, it'll be disabled until the feature is enabled by default.
                 custom_code_classes_in_docs: false,
+                enable_per_target_ignores: false,
+                code_block_id_prefix: None,
+                math: false,
+                expandable_hidden_lines: false,
+                smart_link_titles: false,
+                nest_deep_headings: false,
+                smart_punctuation: true,
+                doc_assets: None,
             }
             .into_string()
         );
@@ -65,6 +73,14 @@ pub(crate) fn load(
                 heading_offset: HeadingOffset::H2,
                 // For external files, it'll be disabled until the feature is enabled by default.
                 custom_code_classes_in_docs: false,
+                enable_per_target_ignores: false,
+                code_block_id_prefix: None,
+                math: false,
+                expandable_hidden_lines: false,
+                smart_link_titles: false,
+                nest_deep_headings: false,
+                smart_punctuation: true,
+                doc_assets: None,
             }
             .into_string()
         );
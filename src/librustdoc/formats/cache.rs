@@ -95,6 +95,11 @@ pub(crate) struct Cache {
     /// [doc_masked]: https://doc.rust-lang.org/nightly/unstable-book/language-features/doc-masked.html
     pub(crate) masked_crates: FxHashSet<CrateNum>,
 
+    /// Whether quotes and dashes are replaced by their typographic forms when rendering the
+    /// documentation. Crates documenting command-line syntax can disable it with
+    /// `#![doc(smart_punctuation = false)]` so that `--option` isn't rendered as `–option`.
+    pub(crate) smart_punctuation: bool,
+
     // Private fields only used when initially crawling a crate to build a cache
     stack: Vec<Symbol>,
     parent_stack: Vec<ParentStackItem>,
@@ -141,7 +146,7 @@ struct CacheBuilder<'a, 'tcx> {
 
 impl Cache {
     pub(crate) fn new(document_private: bool, document_hidden: bool) -> Self {
-        Cache { document_private, document_hidden, ..Cache::default() }
+        Cache { document_private, document_hidden, smart_punctuation: true, ..Cache::default() }
     }
 
     /// Populates the `Cache` with more data. The returned `Crate` will be missing some data that was
@@ -335,8 +340,11 @@ fn fold_item(&mut self, item: clean::Item) -> Option<clean::Item> {
                     // which should not be indexed. The crate-item itself is
                     // inserted later on when serializing the search-index.
                     if item.item_id.as_def_id().map_or(false, |idx| !idx.is_crate_root()) {
-                        let desc =
-                            short_markdown_summary(&item.doc_value(), &item.link_names(self.cache));
+                        let desc = short_markdown_summary(
+                            &item.doc_value(),
+                            &item.link_names(self.cache),
+                            self.cache.smart_punctuation,
+                        );
                         let ty = item.type_();
                         if ty != ItemType::StructField
                             || u16::from_str_radix(s.as_str(), 10).is_err()
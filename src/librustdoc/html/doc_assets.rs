@@ -0,0 +1,140 @@
+//! The images of the documentation which are copied to the output directory, see
+//! `--doc-assets-dir`.
+//!
+//! Relative image links, like `![The layout](diagrams/layout.png)`, are resolved in the assets
+//! directory, and the images they point to are copied to `<crate>/doc-assets/` in the output
+//! directory. Their dimensions are added to the `<img>` tags, so that the page doesn't move
+//! around while they load.
+
+use std::cell::RefCell;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rustc_data_structures::fx::FxIndexMap;
+
+use crate::docfs::PathError;
+use crate::error::Error;
+use crate::html::render::SharedContext;
+
+/// The width and height of an image, in pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ImageSize {
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+}
+
+/// An image of the assets directory linked to by the docs.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct DocImage {
+    /// The URL of the copy of the image, relative to the root of the documentation.
+    pub(crate) url: String,
+    /// The size of the image, if its format is known.
+    pub(crate) size: Option<ImageSize>,
+}
+
+pub struct DocAssets {
+    dir: PathBuf,
+    /// The URL of the directory the images are copied to, relative to the root of the
+    /// documentation, like `foo/doc-assets/`.
+    url_prefix: String,
+    /// The images which were linked to, by their path relative to `dir`, and their size.
+    images: RefCell<FxIndexMap<String, Option<ImageSize>>>,
+}
+
+impl DocAssets {
+    pub(crate) fn new(dir: PathBuf, crate_name: &str) -> DocAssets {
+        DocAssets {
+            dir,
+            url_prefix: format!("{crate_name}/doc-assets/"),
+            images: RefCell::new(FxIndexMap::default()),
+        }
+    }
+
+    /// Returns the image `link` points to, if it is a relative link to a file of the assets
+    /// directory. The image is then copied by [`DocAssets::copy_images`].
+    pub(crate) fn image(&self, link: &str) -> Option<DocImage> {
+        let path = relative_path(link)?;
+        let cached = self.images.borrow().get(&path).copied();
+        let size = match cached {
+            Some(size) => size,
+            None => {
+                let bytes = fs::read(self.dir.join(&path)).ok()?;
+                let size = image_size(&bytes);
+                self.images.borrow_mut().insert(path.clone(), size);
+                size
+            }
+        };
+        Some(DocImage { url: format!("{}{path}", self.url_prefix), size })
+    }
+
+    /// Copies the images which were linked to into the documentation in `dst`.
+    pub(crate) fn copy_images(&self, shared: &SharedContext<'_>, dst: &Path) -> Result<(), Error> {
+        for path in self.images.borrow().keys() {
+            let src = self.dir.join(path);
+            let contents = fs::read(&src).map_err(|err| Error::new(err, &src))?;
+            let dst = dst.join(&self.url_prefix).join(path);
+            if let Some(parent) = dst.parent() {
+                shared.ensure_dir(parent)?;
+            }
+            shared.fs.write(dst, contents)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the path of the file a relative link points to, without `.` components, or `None` if
+/// it isn't a relative link or goes up with `..`.
+fn relative_path(link: &str) -> Option<String> {
+    let path = link.split(['?', '#']).next()?;
+    let is_url = path.split_once(':').is_some_and(|(scheme, _)| !scheme.contains('/'));
+    if path.is_empty() || path.starts_with('/') || path.starts_with('\\') || is_url {
+        return None;
+    }
+    let mut components = Vec::new();
+    for component in path.split(['/', '\\']) {
+        match component {
+            "" | "." => {}
+            ".." => return None,
+            component => components.push(component),
+        }
+    }
+    (!components.is_empty()).then(|| components.join("/"))
+}
+
+/// Reads the size of a PNG, GIF or JPEG image from its header.
+pub(crate) fn image_size(bytes: &[u8]) -> Option<ImageSize> {
+    let u16_be = |i: usize| Some(u16::from_be_bytes(bytes.get(i..i + 2)?.try_into().ok()?));
+    let u32_be = |i: usize| Some(u32::from_be_bytes(bytes.get(i..i + 4)?.try_into().ok()?));
+    let u16_le = |i: usize| Some(u16::from_le_bytes(bytes.get(i..i + 2)?.try_into().ok()?));
+
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") && bytes.get(12..16) == Some(b"IHDR".as_slice()) {
+        return Some(ImageSize { width: u32_be(16)?, height: u32_be(20)? });
+    }
+    if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        return Some(ImageSize { width: u16_le(6)?.into(), height: u16_le(8)?.into() });
+    }
+    if bytes.starts_with(b"\xff\xd8") {
+        // The size is in the first "start of frame" segment.
+        let mut i = 2;
+        loop {
+            if *bytes.get(i)? != 0xff {
+                return None;
+            }
+            let marker = *bytes.get(i + 1)?;
+            match marker {
+                // Fill bytes.
+                0xff => i += 1,
+                // Markers without a segment.
+                0x01 | 0xd0..=0xd7 => i += 2,
+                // The start of frame markers, except the ones used for other segments.
+                0xc0..=0xcf if !matches!(marker, 0xc4 | 0xc8 | 0xcc) => {
+                    let height = u16_be(i + 5)?.into();
+                    let width = u16_be(i + 7)?.into();
+                    return Some(ImageSize { width, height });
+                }
+                _ => i += 2 + usize::from(u16_be(i + 2)?),
+            }
+        }
+    }
+    None
+}
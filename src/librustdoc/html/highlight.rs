@@ -20,6 +20,10 @@
 
 use super::format::{self, Buffer};
 
+mod languages;
+
+pub(crate) use self::languages::highlight_non_rust;
+
 /// This type is needed in case we want to render links on items to allow to go to their definition.
 pub(crate) struct HrefContext<'a, 'tcx> {
     pub(crate) context: &'a Context<'tcx>,
@@ -32,37 +36,89 @@ pub(crate) struct HrefContext<'a, 'tcx> {
     pub(crate) current_href: String,
 }
 
+/// Links from the identifiers of an example to the source of the items the docs link to, keyed by
+/// the path of the items, like `my_crate::Widget`.
+pub(crate) type ExampleLinks<'a> = FxHashMap<&'a str, &'a str>;
+
 /// Decorations are represented as a map from CSS class to vector of character ranges.
 /// Each range will be wrapped in a span with that class.
 #[derive(Default)]
 pub(crate) struct DecorationInfo(pub(crate) FxHashMap<&'static str, Vec<(u32, u32)>>);
 
 #[derive(Eq, PartialEq, Clone, Copy)]
-pub(crate) enum Tooltip {
-    Ignore,
+pub(crate) enum Tooltip<'a> {
+    /// The example is not tested. If targets are given, it is only ignored on them.
+    Ignore(&'a [String]),
     CompileFail,
     ShouldPanic,
     Edition(Edition),
     None,
 }
 
-/// Highlights `src` as an inline example, returning the HTML output.
+/// An attribute of a code example which changes how it is tested, shown as a label on the example.
+#[derive(Eq, PartialEq, Clone, Copy)]
+pub(crate) enum Badge<'a> {
+    /// The example is not tested. If targets are given, it is only ignored on them.
+    Ignore(&'a [String]),
+    CompileFail,
+    NoRun,
+    ShouldPanic,
+    Edition(Edition),
+}
+
+impl Badge<'_> {
+    fn class(self) -> &'static str {
+        match self {
+            Badge::Ignore(_) => "ignore",
+            Badge::CompileFail => "compile_fail",
+            Badge::NoRun => "no_run",
+            Badge::ShouldPanic => "should_panic",
+            Badge::Edition(_) => "edition",
+        }
+    }
+
+    fn label(self) -> String {
+        match self {
+            Badge::Ignore([]) => "ignored".to_owned(),
+            Badge::Ignore(targets) => format!("ignored on {}", targets.join(", ")),
+            Badge::CompileFail => "compile_fail".to_owned(),
+            Badge::NoRun => "no_run".to_owned(),
+            Badge::ShouldPanic => "should_panic".to_owned(),
+            Badge::Edition(edition) => format!("edition {edition}"),
+        }
+    }
+}
+
+/// Highlights `src` as an inline example, returning the HTML output. `code` is the text copied
+/// by the copy button of the example, including its hidden lines.
 pub(crate) fn render_example_with_highlighting(
     src: &str,
+    code: &str,
     out: &mut Buffer,
-    tooltip: Tooltip,
+    tooltip: Tooltip<'_>,
+    badges: &[Badge<'_>],
     playground_button: Option<&str>,
     extra_classes: &[String],
+    decoration_info: Option<DecorationInfo>,
+    id: Option<&str>,
+    example_links: Option<&ExampleLinks<'_>>,
 ) {
-    write_header(out, "rust-example-rendered", None, tooltip, extra_classes);
-    write_code(out, src, None, None);
+    write_header(out, "rust-example-rendered", None, tooltip, badges, extra_classes, id, code);
+    write_code(out, src, None, decoration_info, example_links);
     write_footer(out, playground_button);
 }
 
+/// Returns the link to a code example with the given ID, shown when hovering it.
+pub(crate) fn example_anchor(id: &str) -> String {
+    format!(
+        "<a href=\"#{id}\" class=\"anchor example-anchor\" title=\"Link to this example\">§</a>"
+    )
+}
+
 /// Highlights `src` as an item-decl, returning the HTML output.
 pub(crate) fn render_item_decl_with_highlighting(src: &str, out: &mut Buffer) {
     write!(out, "<pre class=\"rust item-decl\">");
-    write_code(out, src, None, None);
+    write_code(out, src, None, None, None);
     write!(out, "</pre>");
 }
 
@@ -70,37 +126,54 @@ fn write_header(
     out: &mut Buffer,
     class: &str,
     extra_content: Option<Buffer>,
-    tooltip: Tooltip,
+    tooltip: Tooltip<'_>,
+    badges: &[Badge<'_>],
     extra_classes: &[String],
+    id: Option<&str>,
+    code: &str,
 ) {
     write!(
         out,
-        "<div class=\"example-wrap{}\">",
+        "<div class=\"example-wrap{}\" data-code=\"{}\"",
         match tooltip {
-            Tooltip::Ignore => " ignore",
+            Tooltip::Ignore(_) => " ignore",
             Tooltip::CompileFail => " compile_fail",
             Tooltip::ShouldPanic => " should_panic",
             Tooltip::Edition(_) => " edition",
             Tooltip::None => "",
         },
+        Escape(code),
     );
+    match id {
+        Some(id) => write!(out, " id=\"{id}\">{}", example_anchor(id)),
+        None => write!(out, ">"),
+    }
 
     if tooltip != Tooltip::None {
-        let edition_code;
-        write!(
-            out,
-            "<a href=\"#\" class=\"tooltip\" title=\"{}\">ⓘ</a>",
-            match tooltip {
-                Tooltip::Ignore => "This example is not tested",
-                Tooltip::CompileFail => "This example deliberately fails to compile",
-                Tooltip::ShouldPanic => "This example panics",
-                Tooltip::Edition(edition) => {
-                    edition_code = format!("This example runs with edition {edition}");
-                    &edition_code
-                }
-                Tooltip::None => unreachable!(),
-            },
-        );
+        let title = match tooltip {
+            Tooltip::Ignore([]) => "This example is not tested".to_owned(),
+            Tooltip::Ignore(targets) => {
+                format!("This example is not tested on {}", targets.join(", "))
+            }
+            Tooltip::CompileFail => "This example deliberately fails to compile".to_owned(),
+            Tooltip::ShouldPanic => "This example panics".to_owned(),
+            Tooltip::Edition(edition) => format!("This example runs with edition {edition}"),
+            Tooltip::None => unreachable!(),
+        };
+        write!(out, "<a href=\"#\" class=\"tooltip\" title=\"{}\">ⓘ</a>", Escape(&title));
+    }
+
+    if !badges.is_empty() {
+        write!(out, "<div class=\"example-badges\">");
+        for badge in badges {
+            write!(
+                out,
+                "<span class=\"example-badge {}\">{}</span>",
+                badge.class(),
+                Escape(&badge.label()),
+            );
+        }
+        write!(out, "</div>");
     }
 
     if let Some(extra) = extra_content {
@@ -157,6 +230,7 @@ struct TokenHandler<'a, 'tcx, F: Write> {
     /// used to generate links.
     pending_elems: Vec<(&'a str, Option<Class>)>,
     href_context: Option<HrefContext<'a, 'tcx>>,
+    example_links: Option<&'a ExampleLinks<'a>>,
 }
 
 impl<'a, 'tcx, F: Write> TokenHandler<'a, 'tcx, F> {
@@ -189,7 +263,9 @@ fn write_pending_elems(&mut self, current_class: Option<Class>) -> bool {
             can_merge(current_class, Some(*parent_class), "")
         {
             for (text, class) in self.pending_elems.iter() {
-                string(self.out, Escape(text), *class, &self.href_context, false);
+                if !example_link(self.out, text, *class, self.example_links) {
+                    string(self.out, Escape(text), *class, &self.href_context, false);
+                }
             }
         } else {
             // We only want to "open" the tag ourselves if we have more than one pending and if the
@@ -200,7 +276,9 @@ fn write_pending_elems(&mut self, current_class: Option<Class>) -> bool {
                 None
             };
             for (text, class) in self.pending_elems.iter() {
-                string(self.out, Escape(text), *class, &self.href_context, close_tag.is_none());
+                if !example_link(self.out, text, *class, self.example_links) {
+                    string(self.out, Escape(text), *class, &self.href_context, close_tag.is_none());
+                }
             }
             if let Some(close_tag) = close_tag {
                 exit_span(self.out, close_tag);
@@ -238,6 +316,7 @@ pub(super) fn write_code(
     src: &str,
     href_context: Option<HrefContext<'_, '_>>,
     decoration_info: Option<DecorationInfo>,
+    example_links: Option<&ExampleLinks<'_>>,
 ) {
     // This replace allows to fix how the code source with DOS backline characters is displayed.
     let src = src.replace("\r\n", "\n");
@@ -248,6 +327,7 @@ pub(super) fn write_code(
         current_class: None,
         pending_elems: Vec::new(),
         href_context,
+        example_links,
     };
 
     Classifier::new(
@@ -496,21 +576,25 @@ fn next(&mut self) -> Option<Self::Item> {
 /// Custom spans inserted into the source. Eg --scrape-examples uses this to highlight function calls
 struct Decorations {
     starts: Vec<(u32, &'static str)>,
-    ends: Vec<u32>,
+    /// The `(start, end)` of each decoration, sorted by end.
+    ends: Vec<(u32, u32)>,
 }
 
 impl Decorations {
     fn new(info: DecorationInfo) -> Self {
-        // Extract tuples (start, end, kind) into separate sequences of (start, kind) and (end).
+        // Extract tuples (start, end, kind) into separate sequences of (start, kind) and
+        // (start, end).
         let (mut starts, mut ends): (Vec<_>, Vec<_>) = info
             .0
             .into_iter()
-            .flat_map(|(kind, ranges)| ranges.into_iter().map(move |(lo, hi)| ((lo, kind), hi)))
+            .flat_map(|(kind, ranges)| {
+                ranges.into_iter().map(move |(lo, hi)| ((lo, kind), (lo, hi)))
+            })
             .unzip();
 
         // Sort the sequences in document order.
         starts.sort_by_key(|(lo, _)| *lo);
-        ends.sort();
+        ends.sort_by_key(|(_, hi)| *hi);
 
         Decorations { starts, ends }
     }
@@ -626,12 +710,22 @@ fn highlight(mut self, sink: &mut dyn FnMut(Highlight<'src>)) {
         loop {
             if let Some(decs) = self.decorations.as_mut() {
                 let byte_pos = self.byte_pos;
+                // The decorations ending here are closed before the ones starting here are
+                // opened, so that adjacent decorations don't get nested. Empty decorations are
+                // closed right after being opened.
+                let n_ends =
+                    decs.ends.iter().filter(|(lo, hi)| byte_pos >= *hi && *lo < *hi).count();
+                decs.ends.retain(|(lo, hi)| byte_pos < *hi || *lo == *hi);
+                for _ in 0..n_ends {
+                    sink(Highlight::ExitSpan);
+                }
+
                 let n_starts = decs.starts.iter().filter(|(i, _)| byte_pos >= *i).count();
                 for (_, kind) in decs.starts.drain(0..n_starts) {
                     sink(Highlight::EnterSpan { class: Class::Decoration(kind) });
                 }
 
-                let n_ends = decs.ends.iter().filter(|i| byte_pos >= **i).count();
+                let n_ends = decs.ends.iter().filter(|(_, hi)| byte_pos >= *hi).count();
                 for _ in decs.ends.drain(0..n_ends) {
                     sink(Highlight::ExitSpan);
                 }
@@ -895,6 +989,38 @@ fn enter_span(
     )
 }
 
+/// Writes `text` as a link to the source of the item it names if it is a path found in
+/// `example_links`, returning whether it did. If only the start of the path is found, like
+/// `my_crate::Widget` in `my_crate::Widget::default`, only this start is linked.
+fn example_link(
+    out: &mut impl Write,
+    text: &str,
+    klass: Option<Class>,
+    example_links: Option<&ExampleLinks<'_>>,
+) -> bool {
+    let (Some(example_links), Some(Class::Ident(_))) = (example_links, klass) else {
+        return false;
+    };
+    // The starts of the path are tried from the shortest to the longest, the last found one wins.
+    let Some((path, href)) = text
+        .match_indices("::")
+        .map(|(pos, _)| &text[..pos])
+        .chain([text])
+        .filter_map(|path| Some((path, *example_links.get(path)?)))
+        .last()
+    else {
+        return false;
+    };
+    write!(
+        out,
+        "<a href=\"{href}\" title=\"View the source of `{path}`\">{}</a>{}",
+        Escape(path),
+        Escape(&text[path.len()..]),
+    )
+    .unwrap();
+    true
+}
+
 /// Called at the end of a span of highlighted text.
 fn exit_span(out: &mut impl Write, closing_tag: &str) {
     out.write_str(closing_tag).unwrap();
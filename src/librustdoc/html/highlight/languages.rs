@@ -0,0 +1,166 @@
+//! Lightweight highlighting of the code blocks written in a few common languages other than
+//! Rust, like the `Cargo.toml` and shell snippets found in crate documentation.
+//!
+//! Unlike Rust code, these blocks are not lexed properly: each language is described by a small
+//! [`Grammar`] listing its comments, strings and keywords, which is enough to make the blocks
+//! readable. The spans use the same CSS classes as the Rust highlighting, so that the themes
+//! apply to them.
+
+use std::fmt::Write;
+
+use crate::html::escape::Escape;
+
+/// The syntax elements of a language which get highlighted.
+struct Grammar {
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+    keywords: &'static [&'static str],
+    /// Words highlighted like `true` and `false` in Rust.
+    literals: &'static [&'static str],
+    /// Lines starting with this string are highlighted as attributes, like C preprocessor
+    /// directives and TOML table headers.
+    directive: Option<&'static str>,
+}
+
+const TOML: Grammar = Grammar {
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: &['"', '\''],
+    keywords: &[],
+    literals: &["true", "false", "inf", "nan"],
+    directive: Some("["),
+};
+
+const JSON: Grammar = Grammar {
+    line_comments: &[],
+    block_comment: None,
+    quotes: &['"'],
+    keywords: &[],
+    literals: &["true", "false", "null"],
+    directive: None,
+};
+
+const SHELL: Grammar = Grammar {
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: &['"', '\''],
+    keywords: &[
+        "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if",
+        "in", "local", "return", "then", "until", "while",
+    ],
+    literals: &["true", "false"],
+    directive: None,
+};
+
+const C: Grammar = Grammar {
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\''],
+    keywords: &[
+        "auto", "break", "case", "char", "const", "continue", "default", "do", "double", "else",
+        "enum", "extern", "float", "for", "goto", "if", "inline", "int", "long", "register",
+        "restrict", "return", "short", "signed", "sizeof", "static", "struct", "switch", "typedef",
+        "union", "unsigned", "void", "volatile", "while",
+    ],
+    literals: &["true", "false", "NULL"],
+    directive: Some("#"),
+};
+
+impl Grammar {
+    fn for_lang(lang: &str) -> Option<&'static Grammar> {
+        match lang {
+            "toml" => Some(&TOML),
+            "json" => Some(&JSON),
+            "sh" | "bash" | "shell" => Some(&SHELL),
+            "c" | "h" => Some(&C),
+            _ => None,
+        }
+    }
+
+    /// Returns the length of the token `src` starts with, and its CSS class if it is highlighted.
+    /// `prev` is the character before the token, if any.
+    fn next_token(&self, src: &str, prev: Option<char>) -> (usize, Option<&'static str>) {
+        let line_len = src.find('\n').unwrap_or(src.len());
+        let at_line_start = prev.map_or(true, |c| c == '\n');
+        if at_line_start && let Some(directive) = self.directive {
+            let indent = src[..line_len].len() - src[..line_len].trim_start().len();
+            if src[indent..].starts_with(directive) {
+                return (line_len, Some("attr"));
+            }
+        }
+        // `#` also appears inside words, like in shell variables.
+        let after_space = prev.map_or(true, char::is_whitespace);
+        if self
+            .line_comments
+            .iter()
+            .any(|start| src.starts_with(start) && (after_space || *start != "#"))
+        {
+            return (line_len, Some("comment"));
+        }
+        if let Some((start, end)) = self.block_comment
+            && src.starts_with(start)
+        {
+            let len =
+                src[start.len()..].find(end).map_or(src.len(), |i| start.len() + i + end.len());
+            return (len, Some("comment"));
+        }
+
+        let c = src.chars().next().unwrap();
+        let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+        if self.quotes.contains(&c) {
+            return (string_len(src, c), Some("string"));
+        }
+        if prev.is_some_and(is_word_char) || !is_word_char(c) {
+            return (c.len_utf8(), None);
+        }
+        let len = src.find(|c| !is_word_char(c) && c != '.').unwrap_or(src.len());
+        if c.is_ascii_digit() {
+            return (len, Some("number"));
+        }
+        // Dots are only part of numbers.
+        let len = src.find(|c| !is_word_char(c)).unwrap_or(src.len());
+        let word = &src[..len];
+        let class = if self.keywords.contains(&word) {
+            Some("kw")
+        } else if self.literals.contains(&word) {
+            Some("bool-val")
+        } else {
+            None
+        };
+        (len, class)
+    }
+}
+
+/// Returns the length of the string literal `src` starts with, including its `quote`s. Unclosed
+/// strings run until the end of `src`.
+fn string_len(src: &str, quote: char) -> usize {
+    let mut chars = src.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        if c == '\\' {
+            chars.next();
+        } else if c == quote {
+            return i + c.len_utf8();
+        }
+    }
+    src.len()
+}
+
+/// Highlights `src`, written in `lang`. Returns `None` if the language is not supported, in which
+/// case the code should be displayed as is.
+pub(crate) fn highlight_non_rust(lang: &str, src: &str) -> Option<String> {
+    let grammar = Grammar::for_lang(&lang.to_ascii_lowercase())?;
+    let mut out = String::with_capacity(src.len() * 3 / 2);
+    let mut pos = 0;
+    while pos < src.len() {
+        let prev = src[..pos].chars().next_back();
+        let (len, class) = grammar.next_token(&src[pos..], prev);
+        let text = Escape(&src[pos..pos + len]);
+        match class {
+            Some(class) => write!(out, "<span class=\"{class}\">{text}</span>").unwrap(),
+            None => write!(out, "{text}").unwrap(),
+        }
+        pos += len;
+    }
+    Some(out)
+}
@@ -1,4 +1,4 @@
-use super::{write_code, DecorationInfo};
+use super::{highlight_non_rust, write_code, DecorationInfo};
 use crate::html::format::Buffer;
 use expect_test::expect_file;
 use rustc_data_structures::fx::FxHashMap;
@@ -22,7 +22,7 @@ fn test_html_highlighting() {
         let src = include_str!("fixtures/sample.rs");
         let html = {
             let mut out = Buffer::new();
-            write_code(&mut out, src, None, None);
+            write_code(&mut out, src, None, None, None);
             format!("{STYLE}<pre><code>{}</code></pre>\n", out.into_inner())
         };
         expect_file!["fixtures/sample.html"].assert_eq(&html);
@@ -36,7 +36,7 @@ fn test_dos_backline() {
     println!(\"foo\");\r\n\
 }\r\n";
         let mut html = Buffer::new();
-        write_code(&mut html, src, None, None);
+        write_code(&mut html, src, None, None, None);
         expect_file!["fixtures/dos_line.html"].assert_eq(&html.into_inner());
     });
 }
@@ -50,7 +50,7 @@ fn test_keyword_highlight() {
 let y = Self::whatever;";
 
         let mut html = Buffer::new();
-        write_code(&mut html, src, None, None);
+        write_code(&mut html, src, None, None, None);
         expect_file!["fixtures/highlight.html"].assert_eq(&html.into_inner());
     });
 }
@@ -60,7 +60,7 @@ fn test_union_highlighting() {
     create_default_session_globals_then(|| {
         let src = include_str!("fixtures/union.rs");
         let mut html = Buffer::new();
-        write_code(&mut html, src, None, None);
+        write_code(&mut html, src, None, None, None);
         expect_file!["fixtures/union.html"].assert_eq(&html.into_inner());
     });
 }
@@ -77,7 +77,66 @@ fn test_decorations() {
         decorations.insert("example2", vec![(22, 32)]);
 
         let mut html = Buffer::new();
-        write_code(&mut html, src, None, Some(DecorationInfo(decorations)));
+        write_code(&mut html, src, None, Some(DecorationInfo(decorations)), None);
         expect_file!["fixtures/decorations.html"].assert_eq(&html.into_inner());
     });
 }
+
+#[test]
+fn test_example_links() {
+    create_default_session_globals_then(|| {
+        let src = "let w = foo::Widget::new();\nfoo::Widget::default();\nfoo::other::new(&w);";
+        let mut example_links = FxHashMap::default();
+        example_links.insert("foo::Widget", "../src/foo/lib.rs.html#1");
+        example_links.insert("foo::Widget::new", "../src/foo/lib.rs.html#3-5");
+
+        let mut html = Buffer::new();
+        write_code(&mut html, src, None, None, Some(&example_links));
+        let html = html.into_inner();
+        assert!(html.contains(
+            "<a href=\"../src/foo/lib.rs.html#3-5\" \
+             title=\"View the source of `foo::Widget::new`\">foo::Widget::new</a>"
+        ));
+        assert!(html.contains(
+            "<a href=\"../src/foo/lib.rs.html#1\" \
+             title=\"View the source of `foo::Widget`\">foo::Widget</a>::default"
+        ));
+        assert!(!html.contains("foo::other::new</a>"));
+    });
+}
+
+#[test]
+fn test_non_rust_highlighting() {
+    fn t(lang: &str, src: &str, expect: &str) {
+        assert_eq!(highlight_non_rust(lang, src).as_deref(), Some(expect), "{lang}: {src}");
+    }
+
+    t(
+        "toml",
+        "[dependencies]\nfoo = { version = \"1.0\", default-features = false } # pinned\n",
+        "<span class=\"attr\">[dependencies]</span>\nfoo = { version = <span class=\"string\">\
+         &quot;1.0&quot;</span>, default-features = <span class=\"bool-val\">false</span> } \
+         <span class=\"comment\"># pinned</span>\n",
+    );
+    t(
+        "json",
+        "{\"retries\": 3, \"proxy\": null}",
+        "{<span class=\"string\">&quot;retries&quot;</span>: <span class=\"number\">3</span>, \
+         <span class=\"string\">&quot;proxy&quot;</span>: <span class=\"bool-val\">null</span>}",
+    );
+    t(
+        "sh",
+        "if [ -n \"$1\" ]; then echo ${#1}; fi # done",
+        "<span class=\"kw\">if</span> [ -n <span class=\"string\">&quot;$1&quot;</span> ]; \
+         <span class=\"kw\">then</span> echo ${#<span class=\"number\">1</span>}; \
+         <span class=\"kw\">fi</span> \
+         <span class=\"comment\"># done</span>",
+    );
+    t(
+        "C",
+        "#include <stdio.h>\nint x2 = 0x1f; /* a\nb */",
+        "<span class=\"attr\">#include &lt;stdio.h&gt;</span>\n<span class=\"kw\">int</span> x2 = \
+         <span class=\"number\">0x1f</span>; <span class=\"comment\">/* a\nb */</span>",
+    );
+    assert_eq!(highlight_non_rust("text", "if x"), None);
+}
@@ -22,6 +22,10 @@ pub(crate) struct Layout {
     pub(crate) css_file_extension: Option<PathBuf>,
     /// If true, then scrape-examples.js will be included in the output HTML file
     pub(crate) scrape_examples_extension: bool,
+    /// URL of the mermaid.js script rendering the `mermaid` code blocks, if any.
+    pub(crate) mermaid_script: Option<String>,
+    /// Base URL of the KaTeX distribution rendering the math regions, if any.
+    pub(crate) katex_url: Option<String>,
 }
 
 pub(crate) struct Page<'a> {
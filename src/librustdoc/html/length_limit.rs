@@ -13,17 +13,21 @@
 /// * tags are closed in the reverse order of when they were opened (i.e., the correct HTML order),
 /// * no tags are left empty (e.g., `<em></em>`) due to the length limit being reached,
 /// * all text is escaped.
+///
+/// It can also generate plain text, see [`Self::new_plain_text()`].
 #[derive(Debug)]
 pub(super) struct HtmlWithLimit {
     buf: String,
     len: usize,
     limit: usize,
-    /// A list of tags that have been requested to be opened via [`Self::open_tag()`]
-    /// but have not actually been pushed to `buf` yet. This ensures that tags are not
-    /// left empty (e.g., `<em></em>`) due to the length limit being reached.
-    queued_tags: Vec<&'static str>,
+    /// A list of tags (and their rendered attributes) that have been requested to be opened via
+    /// [`Self::open_tag()`] but have not actually been pushed to `buf` yet. This ensures that tags
+    /// are not left empty (e.g., `<em></em>`) due to the length limit being reached.
+    queued_tags: Vec<(&'static str, String)>,
     /// A list of all tags that have been opened but not yet closed.
     unclosed_tags: Vec<&'static str>,
+    /// If `true`, the tags are ignored and the text is not escaped.
+    plain_text: bool,
 }
 
 impl HtmlWithLimit {
@@ -45,9 +49,15 @@ pub(super) fn new(length_limit: usize) -> Self {
             limit: length_limit,
             unclosed_tags: Vec::new(),
             queued_tags: Vec::new(),
+            plain_text: false,
         }
     }
 
+    /// Create a new buffer generating plain text instead of HTML, with a limit of `length_limit`.
+    pub(super) fn new_plain_text(length_limit: usize) -> Self {
+        Self { plain_text: true, ..Self::new(length_limit) }
+    }
+
     /// Finish using the buffer and get the written output.
     /// This function will close all unclosed tags for you.
     pub(super) fn finish(mut self) -> String {
@@ -65,7 +75,11 @@ pub(super) fn push(&mut self, text: &str) -> ControlFlow<(), ()> {
         }
 
         self.flush_queue();
-        write!(self.buf, "{}", Escape(text)).unwrap();
+        if self.plain_text {
+            self.buf.push_str(text);
+        } else {
+            write!(self.buf, "{}", Escape(text)).unwrap();
+        }
         self.len += text.len();
 
         ControlFlow::Continue(())
@@ -73,18 +87,40 @@ pub(super) fn push(&mut self, text: &str) -> ControlFlow<(), ()> {
 
     /// Open an HTML tag.
     ///
-    /// **Note:** HTML attributes have not yet been implemented.
     /// This function will panic if called with a non-alphabetic `tag_name`.
     pub(super) fn open_tag(&mut self, tag_name: &'static str) {
-        assert!(
-            tag_name.chars().all(|c| ('a'..='z').contains(&c)),
-            "tag_name contained non-alphabetic chars: {tag_name:?}",
-        );
-        self.queued_tags.push(tag_name);
+        self.open_tag_with_attr(tag_name, None);
+    }
+
+    /// Open an HTML tag with an attribute, like `href` for `<a>`. The value of the attribute
+    /// is escaped.
+    ///
+    /// This function will panic if called with a non-alphabetic `tag_name` or attribute name.
+    pub(super) fn open_tag_with_attr(
+        &mut self,
+        tag_name: &'static str,
+        attr: Option<(&'static str, &str)>,
+    ) {
+        let is_alphabetic = |name: &str| name.chars().all(|c| ('a'..='z').contains(&c));
+        assert!(is_alphabetic(tag_name), "tag_name contained non-alphabetic chars: {tag_name:?}");
+        let attrs = match attr {
+            Some((name, value)) => {
+                assert!(is_alphabetic(name), "attribute contained non-alphabetic chars: {name:?}");
+                format!(" {name}=\"{}\"", Escape(value))
+            }
+            None => String::new(),
+        };
+        if !self.plain_text {
+            self.queued_tags.push((tag_name, attrs));
+        }
     }
 
     /// Close the most recently opened HTML tag.
     pub(super) fn close_tag(&mut self) {
+        // If nothing was written in the tag, it was never written either.
+        if self.queued_tags.pop().is_some() {
+            return;
+        }
         match self.unclosed_tags.pop() {
             // Close the most recently opened tag.
             Some(tag_name) => write!(self.buf, "</{tag_name}>").unwrap(),
@@ -99,8 +135,8 @@ pub(super) fn close_tag(&mut self) {
 
     /// Write all queued tags and add them to the `unclosed_tags` list.
     fn flush_queue(&mut self) {
-        for tag_name in self.queued_tags.drain(..) {
-            write!(self.buf, "<{tag_name}>").unwrap();
+        for (tag_name, attrs) in self.queued_tags.drain(..) {
+            write!(self.buf, "<{tag_name}{attrs}>").unwrap();
 
             self.unclosed_tags.push(tag_name);
         }
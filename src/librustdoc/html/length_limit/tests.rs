@@ -118,3 +118,39 @@ fn close_too_many() {
     buf.close_tag();
     assert_eq!(buf.finish(), "<p>Hello</p>");
 }
+
+#[test]
+fn empty_tag_is_dropped() {
+    let mut buf = HtmlWithLimit::new(60);
+    buf.open_tag("p");
+    buf.push("Hello");
+    buf.open_tag("em");
+    buf.close_tag();
+    buf.push(" world");
+    buf.close_tag();
+    assert_eq!(buf.finish(), "<p>Hello world</p>");
+}
+
+#[test]
+fn tag_with_attr() {
+    let mut buf = HtmlWithLimit::new(60);
+    buf.open_tag_with_attr("a", Some(("href", "https://example.com/?a=1&b=\"2\"")));
+    buf.push("link");
+    buf.close_tag();
+    assert_eq!(buf.finish(), "<a href=\"https://example.com/?a=1&amp;b=&quot;2&quot;\">link</a>");
+}
+
+#[test]
+fn plain_text() {
+    let mut buf = HtmlWithLimit::new_plain_text(12);
+    buf.push("Hello ");
+    buf.open_tag("em");
+    buf.push("<world>");
+    buf.close_tag();
+    assert_eq!(buf.finish(), "Hello ");
+    let mut buf = HtmlWithLimit::new_plain_text(60);
+    buf.open_tag("em");
+    buf.push("<world> & co");
+    buf.close_tag();
+    assert_eq!(buf.finish(), "<world> & co");
+}
@@ -21,30 +21,41 @@
 //!     playground: &None,
 //!     heading_offset: HeadingOffset::H2,
 //!     custom_code_classes_in_docs: true,
+//!     enable_per_target_ignores: false,
+//!     code_block_id_prefix: None,
+//!     math: false,
+//!     expandable_hidden_lines: false,
+//!     smart_link_titles: false,
+//!     nest_deep_headings: false,
+//!     smart_punctuation: true,
+//!     doc_assets: None,
 //! };
 //! let html = md.into_string();
 //! // ... something using html
 //! ```
 
-use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_errors::{DiagnosticMessage, SubdiagnosticMessage};
 use rustc_hir::def_id::DefId;
 use rustc_middle::ty::TyCtxt;
 pub(crate) use rustc_resolve::rustdoc::main_body_opts;
-use rustc_resolve::rustdoc::may_be_doc_link;
+use rustc_resolve::rustdoc::{expand_doc_includes, may_be_doc_link, DocInclude};
 use rustc_span::edition::Edition;
 use rustc_span::{Span, Symbol};
 
 use once_cell::sync::Lazy;
 use std::borrow::Cow;
+use std::cell::RefCell;
 use std::collections::VecDeque;
 use std::fmt::Write;
-use std::iter::Peekable;
-use std::ops::{ControlFlow, Range};
-use std::str::{self, CharIndices};
+use std::iter::{self, Peekable};
+use std::ops::{ControlFlow, Range, RangeInclusive};
+use std::path::Path;
+use std::str::{self, CharIndices, FromStr};
 
 use crate::clean::RenderedLink;
 use crate::doctest;
+use crate::html::doc_assets::DocAssets;
 use crate::html::escape::Escape;
 use crate::html::format::Buffer;
 use crate::html::highlight;
@@ -53,7 +64,8 @@
 use crate::html::toc::TocBuilder;
 
 use pulldown_cmark::{
-    html, BrokenLink, CodeBlockKind, CowStr, Event, LinkType, OffsetIter, Options, Parser, Tag,
+    html, Alignment, BrokenLink, CodeBlockKind, CowStr, Event, HeadingLevel, LinkType, OffsetIter,
+    Options, Parser, Tag,
 };
 
 #[cfg(test)]
@@ -62,12 +74,22 @@
 const MAX_HEADER_LEVEL: u32 = 6;
 
 /// Options for rendering Markdown in summaries (e.g., in search results).
-pub(crate) fn summary_opts() -> Options {
-    Options::ENABLE_TABLES
+pub(crate) fn summary_opts(smart_punctuation: bool) -> Options {
+    let mut opts = Options::ENABLE_TABLES
         | Options::ENABLE_FOOTNOTES
         | Options::ENABLE_STRIKETHROUGH
-        | Options::ENABLE_TASKLISTS
-        | Options::ENABLE_SMART_PUNCTUATION
+        | Options::ENABLE_TASKLISTS;
+    opts.set(Options::ENABLE_SMART_PUNCTUATION, smart_punctuation);
+    opts
+}
+
+/// Options for rendering Markdown in the main body of documentation, like [`main_body_opts`]
+/// but without smart punctuation if the crate disabled it with
+/// `#![doc(smart_punctuation = false)]`.
+pub(crate) fn render_opts(smart_punctuation: bool) -> Options {
+    let mut opts = main_body_opts();
+    opts.set(Options::ENABLE_SMART_PUNCTUATION, smart_punctuation);
+    opts
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -98,6 +120,29 @@ pub struct Markdown<'a> {
     pub heading_offset: HeadingOffset,
     /// `true` if the `custom_code_classes_in_docs` feature is enabled.
     pub custom_code_classes_in_docs: bool,
+    /// `true` if the `ignore-<target>` attributes of the examples are taken into account
+    /// (`--enable-per-target-ignores`), to show the targets the examples are ignored on.
+    pub enable_per_target_ignores: bool,
+    /// If set, every code block gets an ID starting with this prefix (usually the item the docs
+    /// belong to) and a link to itself.
+    pub code_block_id_prefix: Option<&'a str>,
+    /// `true` if `$...$` and `$$...$$` are rendered as math (`--enable-math`).
+    pub math: bool,
+    /// `true` if the hidden lines of Rust code blocks are rendered behind a toggle instead of
+    /// being removed (`--expandable-hidden-lines`).
+    pub expandable_hidden_lines: bool,
+    /// `true` if the text of autolinks to API documentation is replaced with the path of the item
+    /// they point to (`--smart-link-titles`).
+    pub smart_link_titles: bool,
+    /// `true` if the headings which would be deeper than `<h6>` once offset are rendered as
+    /// `<div role="heading">` with their actual level, instead of as `<h6>`
+    /// (`--nest-deep-headings`).
+    pub nest_deep_headings: bool,
+    /// `true` if quotes and dashes are replaced by their typographic forms, like `--` by `–`.
+    /// This is disabled by `#![doc(smart_punctuation = false)]`.
+    pub smart_punctuation: bool,
+    /// The assets directory the relative image links are resolved in (`--doc-assets-dir`).
+    pub doc_assets: Option<DocAssetLinks<'a>>,
 }
 /// A struct like `Markdown` that renders the markdown with a table of contents.
 pub(crate) struct MarkdownWithToc<'a> {
@@ -108,12 +153,34 @@ pub(crate) struct MarkdownWithToc<'a> {
     pub(crate) playground: &'a Option<Playground>,
     /// `true` if the `custom_code_classes_in_docs` feature is enabled.
     pub(crate) custom_code_classes_in_docs: bool,
+    /// `true` if the `ignore-<target>` attributes are taken into account, see
+    /// [`Markdown::enable_per_target_ignores`].
+    pub(crate) enable_per_target_ignores: bool,
+    /// `true` if `$...$` and `$$...$$` are rendered as math (`--enable-math`).
+    pub(crate) math: bool,
+    /// `true` if the hidden lines of Rust code blocks are rendered behind a toggle.
+    pub(crate) expandable_hidden_lines: bool,
+    /// `true` if the text of autolinks to API documentation is replaced with an item path.
+    pub(crate) smart_link_titles: bool,
+    /// `true` if the headings deeper than `<h6>` keep their level, see
+    /// [`Markdown::nest_deep_headings`].
+    pub(crate) nest_deep_headings: bool,
+    /// Offset at which we render headings, like [`Markdown::heading_offset`]. The numbering of
+    /// the headings doesn't depend on it: `# something` is always numbered as a top-level section.
+    pub(crate) heading_offset: HeadingOffset,
+    /// `true` if the headings are numbered (`1`, `1.1`, `1.2`...), both in the rendered headings
+    /// and in the table of contents.
+    pub(crate) number_headings: bool,
+    /// `true` if quotes and dashes are replaced by their typographic forms.
+    pub(crate) smart_punctuation: bool,
 }
 /// A tuple struct like `Markdown` that renders the markdown escaping HTML tags
-/// and includes no paragraph tags.
-pub(crate) struct MarkdownItemInfo<'a>(pub(crate) &'a str, pub(crate) &'a mut IdMap);
-/// A tuple struct like `Markdown` that renders only the first paragraph.
-pub(crate) struct MarkdownSummaryLine<'a>(pub &'a str, pub &'a [RenderedLink]);
+/// and includes no paragraph tags. The boolean enables smart punctuation, like
+/// [`Markdown::smart_punctuation`].
+pub(crate) struct MarkdownItemInfo<'a>(pub(crate) &'a str, pub(crate) &'a mut IdMap, pub bool);
+/// A tuple struct like `Markdown` that renders only the first paragraph. The boolean enables
+/// smart punctuation, like [`Markdown::smart_punctuation`].
+pub(crate) struct MarkdownSummaryLine<'a>(pub &'a str, pub &'a [RenderedLink], pub bool);
 
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum ErrorCodes {
@@ -194,6 +261,50 @@ fn slugify(c: char) -> Option<char> {
     }
 }
 
+/// How the ids of headings are generated from their text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SlugStyle {
+    /// Only ASCII letters are lowercased and ASCII whitespace becomes `-`.
+    ///
+    /// "Über uns!" -> "Über-uns"
+    #[default]
+    Rustdoc,
+    /// The slugs generated by GitHub when rendering markdown files, so that `#section` links
+    /// copied from a README keep working: all letters are lowercased and only spaces become `-`.
+    ///
+    /// "Über uns!" -> "über-uns"
+    GitHub,
+}
+
+impl SlugStyle {
+    fn push_slug(self, slug: &mut String, text: &str) {
+        match self {
+            SlugStyle::Rustdoc => slug.extend(text.chars().filter_map(slugify)),
+            SlugStyle::GitHub => {
+                for c in text.chars() {
+                    if c == ' ' || c == '-' {
+                        slug.push('-');
+                    } else if c.is_alphanumeric() || c == '_' {
+                        slug.extend(c.to_lowercase());
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for SlugStyle {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rustdoc" => Ok(SlugStyle::Rustdoc),
+            "github" => Ok(SlugStyle::GitHub),
+            _ => Err(()),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Playground {
     pub crate_name: Option<Symbol>,
@@ -209,6 +320,11 @@ struct CodeBlocks<'p, 'a, I: Iterator<Item = Event<'a>>> {
     // optional crate name and the URL.
     playground: &'p Option<Playground>,
     custom_code_classes_in_docs: bool,
+    enable_per_target_ignores: bool,
+    expandable_hidden_lines: bool,
+    anchors: Option<CodeBlockAnchors<'p>>,
+    /// The identifiers naming the items the docs link to, which link to the source of the items.
+    example_links: highlight::ExampleLinks<'p>,
 }
 
 impl<'p, 'a, I: Iterator<Item = Event<'a>>> CodeBlocks<'p, 'a, I> {
@@ -218,6 +334,10 @@ fn new(
         edition: Edition,
         playground: &'p Option<Playground>,
         custom_code_classes_in_docs: bool,
+        enable_per_target_ignores: bool,
+        expandable_hidden_lines: bool,
+        anchors: Option<CodeBlockAnchors<'p>>,
+        links: &'p [RenderedLink],
     ) -> Self {
         CodeBlocks {
             inner: iter,
@@ -225,10 +345,52 @@ fn new(
             edition,
             playground,
             custom_code_classes_in_docs,
+            enable_per_target_ignores,
+            expandable_hidden_lines,
+            anchors,
+            example_links: example_links(links),
         }
     }
 }
 
+/// Returns the paths of the items the docs link to with the URL of their source, see
+/// [`RenderedLink::source`]. The paths of several items, like a module and a function with the
+/// same name, are left out as they are ambiguous.
+fn example_links(links: &[RenderedLink]) -> highlight::ExampleLinks<'_> {
+    let mut example_links = FxHashMap::default();
+    let mut ambiguous = Vec::new();
+    for (path, href) in links.iter().filter_map(|link| link.source.as_ref()) {
+        match example_links.insert(path.as_str(), href.as_str()) {
+            Some(other) if other != href => ambiguous.push(path.as_str()),
+            _ => {}
+        }
+    }
+    for path in ambiguous {
+        example_links.remove(path);
+    }
+    example_links
+}
+
+/// Allocates the IDs of the code blocks of a docblock, so that each of them can be linked to.
+struct CodeBlockAnchors<'ids> {
+    ids: &'ids RefCell<&'ids mut IdMap>,
+    prefix: &'ids str,
+    count: usize,
+}
+
+impl<'ids> CodeBlockAnchors<'ids> {
+    fn new(ids: &'ids RefCell<&'ids mut IdMap>, prefix: &'ids str) -> Self {
+        CodeBlockAnchors { ids, prefix, count: 0 }
+    }
+
+    /// Returns the ID of the next code block, which is derived from its position in the docblock
+    /// so that it stays the same as long as the code blocks before it don't change.
+    fn next_id(&mut self) -> String {
+        self.count += 1;
+        self.ids.borrow_mut().derive(format!("{}.example-{}", self.prefix, self.count))
+    }
+}
+
 impl<'a, I: Iterator<Item = Event<'a>>> Iterator for CodeBlocks<'_, 'a, I> {
     type Item = Event<'a>;
 
@@ -236,11 +398,17 @@ fn next(&mut self) -> Option<Self::Item> {
         let event = self.inner.next();
         let compile_fail;
         let should_panic;
+        let no_run;
         let ignore;
         let edition;
         let Some(Event::Start(Tag::CodeBlock(kind))) = event else {
             return event;
         };
+        let id = self.anchors.as_mut().map(|anchors| anchors.next_id());
+        let (id_attr, anchor) = match id {
+            Some(ref id) => (format!(" id=\"{id}\""), highlight::example_anchor(id)),
+            None => (String::new(), String::new()),
+        };
 
         let mut original_text = String::new();
         for event in &mut self.inner {
@@ -258,26 +426,44 @@ fn next(&mut self) -> Option<Self::Item> {
                 let parse_result = LangString::parse_without_check(
                     lang,
                     self.check_error_codes,
-                    false,
+                    self.enable_per_target_ignores,
                     self.custom_code_classes_in_docs,
                 );
                 if !parse_result.rust {
                     let added_classes = parse_result.added_classes;
-                    let lang_string = if let Some(lang) = parse_result.unknown.first() {
+                    if parse_result.unknown.first().is_some_and(|lang| lang == "mermaid") {
+                        // The diagram source is kept as is, it is rendered on the client side by
+                        // the script given with `--mermaid-script`, which looks for this class.
+                        return Some(Event::Html(
+                            format!(
+                                "<div class=\"example-wrap mermaid-wrap\"{id_attr}>{anchor}\
+                                     <pre class=\"mermaid{whitespace}{added_classes}\">{text}</pre>\
+                                 </div>",
+                                whitespace = if added_classes.is_empty() { "" } else { " " },
+                                added_classes = added_classes.join(" "),
+                                text = Escape(&original_text),
+                            )
+                            .into(),
+                        ));
+                    }
+                    let lang = parse_result.unknown.first();
+                    let lang_string = if let Some(lang) = lang {
                         format!("language-{}", lang)
                     } else {
                         String::new()
                     };
+                    let text = lang
+                        .and_then(|lang| highlight::highlight_non_rust(lang, &original_text))
+                        .unwrap_or_else(|| Escape(&original_text).to_string());
                     let whitespace = if added_classes.is_empty() { "" } else { " " };
                     return Some(Event::Html(
                         format!(
-                            "<div class=\"example-wrap\">\
+                            "<div class=\"example-wrap\"{id_attr}>{anchor}\
                                  <pre class=\"{lang_string}{whitespace}{added_classes}\">\
                                      <code>{text}</code>\
                                  </pre>\
                              </div>",
                             added_classes = added_classes.join(" "),
-                            text = Escape(&original_text),
                         )
                         .into(),
                     ));
@@ -288,13 +474,37 @@ fn next(&mut self) -> Option<Self::Item> {
         };
 
         let added_classes = parse_result.added_classes;
-        let lines = original_text.lines().filter_map(|l| map_line(l).for_html());
-        let text = lines.intersperse("\n".into()).collect::<String>();
+        // The example as displayed without its hidden lines.
+        let code = original_text
+            .lines()
+            .filter_map(|l| map_line(l).for_html())
+            .intersperse("\n".into())
+            .collect::<String>();
+        // The whole example, copied by the copy button so that it can be run like the playground
+        // link does: the hidden lines are kept, without their `# ` marker.
+        let copied_code = original_text
+            .lines()
+            .map(|l| map_line(l).for_code())
+            .intersperse("\n".into())
+            .collect::<String>();
+        let (text, decoration_info) = if self.expandable_hidden_lines {
+            text_with_hidden_lines(&original_text, &parse_result.highlighted_lines)
+        } else {
+            let decoration_info =
+                highlighted_lines_decorations(&code, &parse_result.highlighted_lines);
+            (code.clone(), decoration_info)
+        };
+        let has_hidden_lines = decoration_info
+            .as_ref()
+            .is_some_and(|decorations| decorations.0.contains_key("hidden-line"));
 
         compile_fail = parse_result.compile_fail;
         should_panic = parse_result.should_panic;
+        no_run = parse_result.no_run;
         ignore = parse_result.ignore;
         edition = parse_result.edition;
+        let playground_channel = parse_result.playground_channel;
+        let playground_mode = parse_result.playground_mode;
 
         let explicit_edition = edition.is_some();
         let edition = edition.unwrap_or(self.edition);
@@ -305,26 +515,32 @@ fn next(&mut self) -> Option<Self::Item> {
             if url.is_empty() {
                 return None;
             }
-            let test = original_text
-                .lines()
-                .map(|l| map_line(l).for_code())
-                .intersperse("\n".into())
-                .collect::<String>();
+            let test = &copied_code;
             let krate = krate.as_ref().map(|s| s.as_str());
             let (test, _, _) =
-                doctest::make_test(&test, krate, false, &Default::default(), edition, None);
-            let channel = if test.contains("#![feature(") { "&amp;version=nightly" } else { "" };
+                doctest::make_test(test, krate, false, &Default::default(), edition, None);
+            // Without a `channel` attribute, examples using unstable features are run on nightly.
+            let channel = match &playground_channel {
+                Some(channel) => format!("&amp;version={channel}"),
+                None if test.contains("#![feature(") => "&amp;version=nightly".to_owned(),
+                None => String::new(),
+            };
+            let mode = match &playground_mode {
+                Some(mode) => format!("&amp;mode={mode}"),
+                None => String::new(),
+            };
 
             let test_escaped = small_url_encode(test);
             Some(format!(
                 "<a class=\"test-arrow\" \
                     target=\"_blank\" \
-                    href=\"{url}?code={test_escaped}{channel}&amp;edition={edition}\">Run</a>",
+                    href=\"{url}?code={test_escaped}{channel}{mode}&amp;edition={edition}\">\
+                 Run</a>",
             ))
         });
 
         let tooltip = if ignore != Ignore::None {
-            highlight::Tooltip::Ignore
+            highlight::Tooltip::Ignore(ignore.targets())
         } else if compile_fail {
             highlight::Tooltip::CompileFail
         } else if should_panic {
@@ -335,6 +551,32 @@ fn next(&mut self) -> Option<Self::Item> {
             highlight::Tooltip::None
         };
 
+        let mut badges = Vec::new();
+        if ignore != Ignore::None {
+            badges.push(highlight::Badge::Ignore(ignore.targets()));
+        }
+        if compile_fail {
+            badges.push(highlight::Badge::CompileFail);
+        } else if no_run {
+            // `compile_fail` examples are never run either.
+            badges.push(highlight::Badge::NoRun);
+        }
+        if should_panic {
+            badges.push(highlight::Badge::ShouldPanic);
+        }
+        if explicit_edition {
+            badges.push(highlight::Badge::Edition(edition));
+        }
+
+        // The toggle revealing the hidden lines goes next to the playground button.
+        let buttons = if has_hidden_lines {
+            let toggle =
+                "<button class=\"hidden-lines-toggle\" title=\"Show hidden lines\">⋯</button>";
+            Some(playground_button.unwrap_or_default() + toggle)
+        } else {
+            playground_button
+        };
+
         // insert newline to clearly separate it from the
         // previous block so we can shorten the html output
         let mut s = Buffer::new();
@@ -342,25 +584,124 @@ fn next(&mut self) -> Option<Self::Item> {
 
         highlight::render_example_with_highlighting(
             &text,
+            &copied_code,
             &mut s,
             tooltip,
-            playground_button.as_deref(),
+            &badges,
+            buttons.as_deref(),
             &added_classes,
+            decoration_info,
+            id.as_deref(),
+            Some(&self.example_links).filter(|example_links| !example_links.is_empty()),
         );
         Some(Event::Html(s.into_inner().into()))
     }
 }
 
+/// Returns the decorations wrapping the given lines of `text` (1-based, as displayed, so hidden
+/// lines are not counted) in a `<span class="hl">`, if there are any.
+fn highlighted_lines_decorations(
+    text: &str,
+    highlighted_lines: &[RangeInclusive<usize>],
+) -> Option<highlight::DecorationInfo> {
+    if highlighted_lines.is_empty() {
+        return None;
+    }
+    // Byte range of each line, without the trailing newline.
+    let mut pos = 0;
+    let line_ranges: Vec<(u32, u32)> = text
+        .split('\n')
+        .map(|line| {
+            let range = (pos as u32, (pos + line.len()) as u32);
+            pos += line.len() + 1;
+            range
+        })
+        .collect();
+    let ranges: Vec<(u32, u32)> = highlighted_lines
+        .iter()
+        .filter(|lines| *lines.start() <= line_ranges.len())
+        .map(|lines| {
+            let last = (*lines.end()).min(line_ranges.len());
+            (line_ranges[*lines.start() - 1].0, line_ranges[last - 1].1)
+        })
+        .collect();
+    if ranges.is_empty() {
+        return None;
+    }
+    let mut decorations = FxHashMap::default();
+    decorations.insert("hl", ranges);
+    Some(highlight::DecorationInfo(decorations))
+}
+
+/// Returns the text of a Rust code block including its hidden lines, and its decorations: the
+/// hidden lines are wrapped (along with their line break) in a `<span class="hidden-line">`, and
+/// the lines given with `hl_lines` (not counting the hidden lines) in a `<span class="hl">`.
+fn text_with_hidden_lines(
+    original_text: &str,
+    highlighted_lines: &[RangeInclusive<usize>],
+) -> (String, Option<highlight::DecorationInfo>) {
+    let mut text = String::with_capacity(original_text.len());
+    // Byte range of each shown line, without the trailing newline.
+    let mut shown_lines: Vec<(u32, u32)> = Vec::new();
+    let mut hidden_ranges: Vec<(u32, u32)> = Vec::new();
+    for (i, line) in original_text.lines().enumerate() {
+        if i > 0 {
+            text.push('\n');
+        }
+        let start = text.len() as u32;
+        match map_line(line) {
+            Line::Shown(line) => {
+                text.push_str(&line);
+                shown_lines.push((start, text.len() as u32));
+            }
+            Line::Hidden(line) => {
+                text.push_str(line);
+                // The line break after a hidden line is hidden with it, and consecutive hidden
+                // lines share a single span.
+                let end = text.len() as u32 + 1;
+                match hidden_ranges.last_mut() {
+                    Some(range) if range.1 == start => range.1 = end,
+                    _ => hidden_ranges.push((start, end)),
+                }
+            }
+        }
+    }
+    // The last line has no line break to hide, so the one before it is hidden instead.
+    if let Some(range) = hidden_ranges.last_mut()
+        && range.1 as usize > text.len()
+    {
+        range.1 = text.len() as u32;
+        range.0 = range.0.saturating_sub(1);
+    }
+
+    let mut decorations = FxHashMap::default();
+    let hl_ranges: Vec<(u32, u32)> = highlighted_lines
+        .iter()
+        .filter(|lines| *lines.start() <= shown_lines.len())
+        .map(|lines| {
+            let last = (*lines.end()).min(shown_lines.len());
+            (shown_lines[*lines.start() - 1].0, shown_lines[last - 1].1)
+        })
+        .collect();
+    if !hl_ranges.is_empty() {
+        decorations.insert("hl", hl_ranges);
+    }
+    if !hidden_ranges.is_empty() {
+        decorations.insert("hidden-line", hidden_ranges);
+    }
+    let decorations = (!decorations.is_empty()).then_some(highlight::DecorationInfo(decorations));
+    (text, decorations)
+}
+
 /// Make headings links with anchor IDs and build up TOC.
 struct LinkReplacer<'a, I: Iterator<Item = Event<'a>>> {
     inner: I,
-    links: &'a [RenderedLink],
-    shortcut_link: Option<&'a RenderedLink>,
+    rewriter: LinkRewriter<'a>,
 }
 
 impl<'a, I: Iterator<Item = Event<'a>>> LinkReplacer<'a, I> {
     fn new(iter: I, links: &'a [RenderedLink]) -> Self {
-        LinkReplacer { inner: iter, links, shortcut_link: None }
+        LinkReplacer { inner: iter, rewriter: LinkRewriter::new(links) }
     }
 }
 
@@ -368,18 +709,35 @@ impl<'a, I: Iterator<Item = Event<'a>>> Iterator for LinkReplacer<'a, I> {
     type Item = Event<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let mut event = self.inner.next();
+        let mut event = self.inner.next()?;
+        self.rewriter.rewrite(&mut event);
+        Some(event)
+    }
+}
+
+/// Rewrites the events of intra-doc links for [`LinkReplacer`], one event at a time so that it
+/// can also be used on events with their range, see [`doc_events`].
+struct LinkRewriter<'a> {
+    links: &'a [RenderedLink],
+    shortcut_link: Option<&'a RenderedLink>,
+}
+
+impl<'a> LinkRewriter<'a> {
+    fn new(links: &'a [RenderedLink]) -> Self {
+        LinkRewriter { links, shortcut_link: None }
+    }
 
+    fn rewrite(&mut self, event: &mut Event<'a>) {
         // Replace intra-doc links and remove disambiguators from shortcut links (`[fn@f]`).
-        match &mut event {
+        match event {
             // This is a shortcut link that was resolved by the broken_link_callback: `[fn@f]`
             // Remove any disambiguator.
-            Some(Event::Start(Tag::Link(
+            Event::Start(Tag::Link(
                 // [fn@f] or [fn@f][]
                 LinkType::ShortcutUnknown | LinkType::CollapsedUnknown,
                 dest,
                 title,
-            ))) => {
+            )) => {
                 debug!("saw start of shortcut link to {dest} with title {title}");
                 // If this is a shortcut link, it was resolved by the broken_link_callback.
                 // So the URL will already be updated properly.
@@ -396,11 +754,11 @@ fn next(&mut self) -> Option<Self::Item> {
                 }
             }
             // Now that we're done with the shortcut link, don't replace any more text.
-            Some(Event::End(Tag::Link(
+            Event::End(Tag::Link(
                 LinkType::ShortcutUnknown | LinkType::CollapsedUnknown,
                 dest,
                 _,
-            ))) => {
+            )) => {
                 debug!("saw end of shortcut link to {dest}");
                 if self.links.iter().any(|link| *link.href == **dest) {
                     assert!(self.shortcut_link.is_some(), "saw closing link without opening tag");
@@ -409,7 +767,7 @@ fn next(&mut self) -> Option<Self::Item> {
             }
             // Handle backticks in inline code blocks, but only if we're in the middle of a shortcut link.
             // [`fn@f`]
-            Some(Event::Code(text)) => {
+            Event::Code(text) => {
                 trace!("saw code {text}");
                 if let Some(link) = self.shortcut_link {
                     // NOTE: this only replaces if the code block is the *entire* text.
@@ -432,7 +790,7 @@ fn next(&mut self) -> Option<Self::Item> {
             }
             // Replace plain text in links, but only in the middle of a shortcut link.
             // [fn@f]
-            Some(Event::Text(text)) => {
+            Event::Text(text) => {
                 trace!("saw text {text}");
                 if let Some(link) = self.shortcut_link {
                     // NOTE: same limitations as `Event::Code`
@@ -448,7 +806,7 @@ fn next(&mut self) -> Option<Self::Item> {
             }
             // If this is a link, but not a shortcut link,
             // replace the URL, since the broken_link_callback was not called.
-            Some(Event::Start(Tag::Link(_, dest, title))) => {
+            Event::Start(Tag::Link(_, dest, title)) => {
                 if let Some(link) = self.links.iter().find(|&link| *link.original_text == **dest) {
                     *dest = CowStr::Borrowed(link.href.as_ref());
                     if title.is_empty() && !link.tooltip.is_empty() {
@@ -459,21 +817,106 @@ fn next(&mut self) -> Option<Self::Item> {
             // Anything else couldn't have been a valid Rust path, so no need to replace the text.
             _ => {}
         }
-
-        // Yield the modified event
-        event
     }
 }
 
 /// Wrap HTML tables into `<div>` to prevent having the doc blocks width being too big.
+///
+/// The alignment of the columns is rendered as an `align-*` class on their cells, and a paragraph
+/// starting with `Table: ` right after a table becomes its `<caption>`:
+///
+/// ```markdown
+/// | Name  | Size |
+/// |-------|-----:|
+/// | `Foo` |    8 |
+///
+/// Table: The size of the types, in bytes.
+/// ```
 struct TableWrapper<'a, I: Iterator<Item = Event<'a>>> {
     inner: I,
     stored_events: VecDeque<Event<'a>>,
+    /// The event read after a table which isn't its caption, like the start of another table. It
+    /// is handled like the events of `inner` once the stored events are yielded.
+    next_event: Option<Event<'a>>,
 }
 
 impl<'a, I: Iterator<Item = Event<'a>>> TableWrapper<'a, I> {
     fn new(iter: I) -> Self {
-        Self { inner: iter, stored_events: VecDeque::new() }
+        Self { inner: iter, stored_events: VecDeque::new(), next_event: None }
+    }
+
+    /// Called after a table start event: stores the events of the table, with the classes of its
+    /// aligned cells, and returns its caption if it has one.
+    fn read_table(&mut self, alignments: &[Alignment]) -> Option<String> {
+        let mut in_head = false;
+        let mut cell_index = 0;
+        for event in self.inner.by_ref() {
+            let event = match event {
+                Event::Start(Tag::TableHead) => {
+                    in_head = true;
+                    cell_index = 0;
+                    event
+                }
+                Event::End(Tag::TableHead) => {
+                    in_head = false;
+                    event
+                }
+                Event::Start(Tag::TableRow) => {
+                    cell_index = 0;
+                    event
+                }
+                Event::Start(Tag::TableCell) => {
+                    let class = match alignments.get(cell_index) {
+                        Some(Alignment::Left) => "align-left",
+                        Some(Alignment::Center) => "align-center",
+                        Some(Alignment::Right) => "align-right",
+                        Some(Alignment::None) | None => "",
+                    };
+                    if class.is_empty() {
+                        event
+                    } else {
+                        let tag = if in_head { "th" } else { "td" };
+                        Event::Html(format!("<{tag} class=\"{class}\">").into())
+                    }
+                }
+                Event::End(Tag::TableCell) => {
+                    cell_index += 1;
+                    event
+                }
+                Event::End(Tag::Table(_)) => {
+                    self.stored_events.push_back(event);
+                    self.stored_events.push_back(Event::Html(CowStr::Borrowed("</div>")));
+                    break;
+                }
+                event => event,
+            };
+            self.stored_events.push_back(event);
+        }
+
+        // Tables cannot be interrupted by a paragraph, so the caption is always the paragraph
+        // after the table.
+        let start = self.inner.next()?;
+        if !matches!(start, Event::Start(Tag::Paragraph)) {
+            self.next_event = Some(start);
+            return None;
+        }
+        let first = self.inner.next()?;
+        let text = match &first {
+            Event::Text(text) => {
+                text.strip_prefix("Table:").map(|text| text.trim_start().to_owned())
+            }
+            _ => None,
+        };
+        let Some(text) = text else {
+            self.stored_events.extend([start, first]);
+            return None;
+        };
+        let events = iter::once(Event::Text(text.into())).chain(
+            self.inner.by_ref().take_while(|event| !matches!(event, Event::End(Tag::Paragraph))),
+        );
+        let mut caption = String::new();
+        html::push_html(&mut caption, events);
+        Some(caption)
     }
 }
 
@@ -485,16 +928,967 @@ fn next(&mut self) -> Option<Self::Item> {
             return Some(first);
         }
 
-        let event = self.inner.next()?;
+        let event = match self.next_event.take() {
+            Some(event) => event,
+            None => self.inner.next()?,
+        };
 
         Some(match event {
-            Event::Start(Tag::Table(t)) => {
-                self.stored_events.push_back(Event::Start(Tag::Table(t)));
+            Event::Start(Tag::Table(alignments)) => {
+                // The caption has to be the first child of the table.
+                if let Some(caption) = self.read_table(&alignments) {
+                    let caption = format!("<caption>{caption}</caption>");
+                    self.stored_events.push_front(Event::Html(caption.into()));
+                }
+                self.stored_events.push_front(Event::Start(Tag::Table(alignments)));
                 Event::Html(CowStr::Borrowed("<div>"))
             }
-            Event::End(Tag::Table(t)) => {
-                self.stored_events.push_back(Event::Html(CowStr::Borrowed("</div>")));
-                Event::End(Tag::Table(t))
+            e => e,
+        })
+    }
+}
+
+/// Replaces the text of autolinks to the API documentation of a crate with the path of the item
+/// they point to (`--smart-link-titles`): `<https://doc.rust-lang.org/std/vec/struct.Vec.html>`
+/// is rendered as [`std::vec::Vec`](https://doc.rust-lang.org/std/vec/struct.Vec.html).
+///
+/// Only links to `docs.rs` and to the standard library crates on `doc.rust-lang.org` are
+/// rewritten, see [`autolink_item_path`].
+struct SmartAutolinks<'a, I: Iterator<Item = Event<'a>>> {
+    inner: I,
+    enabled: bool,
+    stored_events: VecDeque<Event<'a>>,
+}
+
+impl<'a, I: Iterator<Item = Event<'a>>> SmartAutolinks<'a, I> {
+    fn new(iter: I, enabled: bool) -> Self {
+        Self { inner: iter, enabled, stored_events: VecDeque::new() }
+    }
+}
+
+impl<'a, I: Iterator<Item = Event<'a>>> Iterator for SmartAutolinks<'a, I> {
+    type Item = Event<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(event) = self.stored_events.pop_front() {
+            return Some(event);
+        }
+
+        let event = self.inner.next()?;
+        if self.enabled
+            && let Event::Start(Tag::Link(LinkType::Autolink, dest, _)) = &event
+            && let Some(path) = autolink_item_path(dest)
+        {
+            // The text of an autolink is the URL itself, replace it with the path.
+            for inner in self.inner.by_ref() {
+                if let Event::End(Tag::Link(..)) = inner {
+                    self.stored_events.push_back(Event::Code(path.into()));
+                    self.stored_events.push_back(inner);
+                    break;
+                }
+            }
+        }
+        Some(event)
+    }
+}
+
+/// Returns the path of the item documented at `url`, if it is a page of the API documentation of
+/// a crate on `docs.rs` (`https://docs.rs/regex/latest/regex/struct.Regex.html`) or of one of the
+/// standard library crates on `doc.rust-lang.org` (`https://doc.rust-lang.org/std/index.html`,
+/// optionally with a channel or version like `https://doc.rust-lang.org/1.70.0/std/`).
+///
+/// Fragments pointing to an associated item or a field are part of the path, so
+/// `https://doc.rust-lang.org/std/vec/struct.Vec.html#method.push` gives `std::vec::Vec::push`.
+fn autolink_item_path(url: &str) -> Option<String> {
+    let (url, fragment) = match url.split_once('#') {
+        Some((url, fragment)) => (url, Some(fragment)),
+        None => (url, None),
+    };
+    let url = url.split('?').next().unwrap_or(url);
+
+    let mut segments: Vec<&str>;
+    if let Some(rest) = url.strip_prefix("https://doc.rust-lang.org/") {
+        segments = rest.split('/').collect();
+        if let Some(&channel) = segments.first()
+            && (matches!(channel, "stable" | "beta" | "nightly")
+                || channel.starts_with(|c: char| c.is_ascii_digit()))
+        {
+            segments.remove(0);
+        }
+        if !matches!(segments.first(), Some(&("std" | "core" | "alloc" | "proc_macro" | "test"))) {
+            return None;
+        }
+    } else if let Some(rest) = url.strip_prefix("https://docs.rs/") {
+        segments = rest.split('/').collect();
+        // `https://docs.rs/crate/...` are the pages about the crate itself.
+        let krate = match segments.first() {
+            None | Some(&("" | "crate")) => return None,
+            Some(krate) => krate.replace('-', "_"),
+        };
+        // Skip the crate name and the version, what's left is the path to the documentation page.
+        segments.drain(..segments.len().min(2));
+        if segments.iter().all(|segment| segment.is_empty()) {
+            return Some(krate);
+        }
+    } else {
+        return None;
+    }
+
+    let is_ident = |s: &str| {
+        !s.is_empty()
+            && !s.starts_with(|c: char| c.is_ascii_digit())
+            && s.chars().all(|c| c.is_alphanumeric() || c == '_')
+    };
+    let mut path = Vec::new();
+    let mut item = None;
+    for (i, segment) in segments.iter().enumerate() {
+        if let Some(page) = segment.strip_suffix(".html") {
+            // Only the last segment can be a page.
+            if i != segments.len() - 1 {
+                return None;
+            }
+            if page != "index" {
+                let (kind, name) = page.split_once('.')?;
+                if !is_ident(name) {
+                    return None;
+                }
+                item = Some((kind, name));
+            }
+        } else if is_ident(segment) {
+            path.push(*segment);
+        } else if !segment.is_empty() || i != segments.len() - 1 {
+            // Only a trailing slash is allowed.
+            return None;
+        }
+    }
+    if path.is_empty() {
+        return None;
+    }
+
+    let mut path = path.join("::");
+    match item {
+        None => return Some(path),
+        // Primitive types are not reachable through the path of the crate documenting them.
+        Some(("primitive", name)) => path = name.to_owned(),
+        Some(("macro", name)) => return Some(format!("{path}::{name}!")),
+        Some((
+            "struct" | "enum" | "union" | "trait" | "traitalias" | "fn" | "type" | "constant"
+            | "static" | "attr" | "derive",
+            name,
+        )) => {
+            path.push_str("::");
+            path.push_str(name);
+        }
+        Some(_) => return None,
+    }
+    const MEMBER_KINDS: &[&str] =
+        &["method", "tymethod", "associatedconstant", "associatedtype", "variant", "structfield"];
+    if let Some((kind, name)) = fragment.and_then(|fragment| fragment.split_once('.'))
+        && MEMBER_KINDS.contains(&kind)
+        && is_ident(name)
+    {
+        path.push_str("::");
+        path.push_str(name);
+    }
+    Some(path)
+}
+
+/// The assets directory the relative image links are resolved in, and the path from the page
+/// being rendered to the root of the documentation.
+#[derive(Clone, Copy)]
+pub struct DocAssetLinks<'a> {
+    pub assets: &'a DocAssets,
+    pub root_path: &'a str,
+}
+
+/// Renders the images whose link points to a file of the assets directory (`--doc-assets-dir`)
+/// as an `<img>` pointing to the copy of the file in the output, with the size of the image so
+/// that the page doesn't move around while it loads.
+struct DocImages<'a, I: Iterator<Item = Event<'a>>> {
+    inner: I,
+    links: Option<DocAssetLinks<'a>>,
+}
+
+impl<'a, I: Iterator<Item = Event<'a>>> DocImages<'a, I> {
+    fn new(iter: I, links: Option<DocAssetLinks<'a>>) -> Self {
+        Self { inner: iter, links }
+    }
+}
+
+impl<'a, I: Iterator<Item = Event<'a>>> Iterator for DocImages<'a, I> {
+    type Item = Event<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let event = self.inner.next()?;
+        let Some(links) = self.links else { return Some(event) };
+        let Event::Start(Tag::Image(_, ref dest, ref title)) = event else { return Some(event) };
+        let Some(image) = links.assets.image(dest) else { return Some(event) };
+
+        // The alt text is the plain text of the content of the image, which can contain nested
+        // images.
+        let mut alt = String::new();
+        let mut depth = 1;
+        for inner in self.inner.by_ref() {
+            match inner {
+                Event::Start(Tag::Image(..)) => depth += 1,
+                Event::End(Tag::Image(..)) => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                Event::Text(text) | Event::Code(text) => alt.push_str(&text),
+                Event::SoftBreak | Event::HardBreak => alt.push(' '),
+                _ => {}
+            }
+        }
+
+        let mut html = format!(
+            "<img src=\"{root}{url}\" alt=\"{alt}\"",
+            root = links.root_path,
+            url = Escape(&image.url),
+            alt = Escape(&alt),
+        );
+        if !title.is_empty() {
+            write!(html, " title=\"{}\"", Escape(title)).unwrap();
+        }
+        if let Some(size) = image.size {
+            write!(html, " width=\"{}\" height=\"{}\"", size.width, size.height).unwrap();
+        }
+        html.push('>');
+        Some(Event::Html(html.into()))
+    }
+}
+
+/// Replaces emoji shortcodes like `:warning:` with the emoji they stand for (⚠️), as GitHub does
+/// in READMEs which are often included in the crate documentation.
+///
+/// Only the shortcodes of [`EMOJI_SHORTCODES`] are replaced, and only when they are not directly
+/// preceded or followed by a letter, a digit or a colon, so that paths like `a::x::b` are kept.
+/// Code is never changed.
+struct EmojiShortcodes<'a, I: Iterator<Item = Event<'a>>> {
+    inner: I,
+    /// An event read after a run of text events, to emit after it.
+    stored_event: Option<Event<'a>>,
+    in_code_block: bool,
+}
+
+impl<'a, I: Iterator<Item = Event<'a>>> EmojiShortcodes<'a, I> {
+    fn new(iter: I) -> Self {
+        Self { inner: iter, stored_event: None, in_code_block: false }
+    }
+}
+
+impl<'a, I: Iterator<Item = Event<'a>>> Iterator for EmojiShortcodes<'a, I> {
+    type Item = Event<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let event = match self.stored_event.take() {
+            Some(event) => event,
+            None => self.inner.next()?,
+        };
+        let mut text = match event {
+            Event::Text(text) if !self.in_code_block => text,
+            Event::Start(Tag::CodeBlock(_)) => {
+                self.in_code_block = true;
+                return Some(event);
+            }
+            Event::End(Tag::CodeBlock(_)) => {
+                self.in_code_block = false;
+                return Some(event);
+            }
+            event => return Some(event),
+        };
+        // The parser can split text around characters like `_`, which are common in shortcodes.
+        for event in self.inner.by_ref() {
+            match event {
+                Event::Text(next) => text = format!("{text}{next}").into(),
+                event => {
+                    self.stored_event = Some(event);
+                    break;
+                }
+            }
+        }
+        match replace_emoji_shortcodes(&text) {
+            Some(replaced) => Some(Event::Text(replaced.into())),
+            None => Some(Event::Text(text)),
+        }
+    }
+}
+
+/// The shortcodes replaced by [`EmojiShortcodes`], sorted by name.
+static EMOJI_SHORTCODES: &[(&str, &str)] = &[
+    ("+1", "\u{1f44d}"),
+    ("-1", "\u{1f44e}"),
+    ("arrow_right", "\u{27a1}\u{fe0f}"),
+    ("bangbang", "\u{203c}\u{fe0f}"),
+    ("book", "\u{1f4d6}"),
+    ("books", "\u{1f4da}"),
+    ("boom", "\u{1f4a5}"),
+    ("bug", "\u{1f41b}"),
+    ("bulb", "\u{1f4a1}"),
+    ("construction", "\u{1f6a7}"),
+    ("crab", "\u{1f980}"),
+    ("exclamation", "\u{2757}"),
+    ("eyes", "\u{1f440}"),
+    ("fire", "\u{1f525}"),
+    ("gear", "\u{2699}\u{fe0f}"),
+    ("heart", "\u{2764}\u{fe0f}"),
+    ("heavy_check_mark", "\u{2714}\u{fe0f}"),
+    ("heavy_minus_sign", "\u{2796}"),
+    ("heavy_plus_sign", "\u{2795}"),
+    ("information_source", "\u{2139}\u{fe0f}"),
+    ("link", "\u{1f517}"),
+    ("lock", "\u{1f512}"),
+    ("mag", "\u{1f50d}"),
+    ("memo", "\u{1f4dd}"),
+    ("no_entry", "\u{26d4}"),
+    ("no_entry_sign", "\u{1f6ab}"),
+    ("package", "\u{1f4e6}"),
+    ("pushpin", "\u{1f4cc}"),
+    ("question", "\u{2753}"),
+    ("rocket", "\u{1f680}"),
+    ("sparkles", "\u{2728}"),
+    ("star", "\u{2b50}"),
+    ("stop_sign", "\u{1f6d1}"),
+    ("tada", "\u{1f389}"),
+    ("thumbsdown", "\u{1f44e}"),
+    ("thumbsup", "\u{1f44d}"),
+    ("warning", "\u{26a0}\u{fe0f}"),
+    ("white_check_mark", "\u{2705}"),
+    ("wrench", "\u{1f527}"),
+    ("x", "\u{274c}"),
+    ("zap", "\u{26a1}"),
+];
+
+/// Returns `text` with its emoji shortcodes replaced, or `None` if it has none.
+fn replace_emoji_shortcodes(text: &str) -> Option<String> {
+    let is_boundary = |c: Option<char>| c.map_or(true, |c| !c.is_alphanumeric() && c != ':');
+    let mut out = String::new();
+    // The end of the text which was already copied to `out`.
+    let mut copied = 0;
+    let mut search = 0;
+    while let Some(start) = text[search..].find(':').map(|i| search + i) {
+        search = start + 1;
+        let Some(len) = text[start + 1..].find(':') else { break };
+        let end = start + 1 + len;
+        let name = &text[start + 1..end];
+        if !is_boundary(text[..start].chars().next_back())
+            || !is_boundary(text[end + 1..].chars().next())
+        {
+            continue;
+        }
+        let Ok(index) = EMOJI_SHORTCODES.binary_search_by_key(&name, |&(name, _)| name) else {
+            continue;
+        };
+        out.push_str(&text[copied..start]);
+        out.push_str(EMOJI_SHORTCODES[index].1);
+        copied = end + 1;
+        search = end + 1;
+    }
+    if copied == 0 {
+        return None;
+    }
+    out.push_str(&text[copied..]);
+    Some(out)
+}
+
+/// Renders the items of task lists (`- [x] done`) with a disabled checkbox, and without a bullet.
+struct TaskLists<'a, I: Iterator<Item = Event<'a>>> {
+    inner: I,
+    stored_events: VecDeque<Event<'a>>,
+}
+
+impl<'a, I: Iterator<Item = Event<'a>>> TaskLists<'a, I> {
+    fn new(iter: I) -> Self {
+        TaskLists { inner: iter, stored_events: VecDeque::new() }
+    }
+}
+
+fn task_list_checkbox(checked: bool) -> String {
+    let checked_attr = if checked { " checked" } else { "" };
+    format!(
+        "<input type=\"checkbox\" class=\"task-list-checkbox\" disabled{checked_attr} \
+            aria-checked=\"{checked}\">",
+    )
+}
+
+impl<'a, I: Iterator<Item = Event<'a>>> Iterator for TaskLists<'a, I> {
+    type Item = Event<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(event) = self.stored_events.pop_front() {
+            return Some(event);
+        }
+        let event = self.inner.next()?;
+        if !matches!(event, Event::Start(Tag::Item)) {
+            return Some(event);
+        }
+        // The marker is the first event of the item, or of its first paragraph in loose lists.
+        let mut next = self.inner.next();
+        if matches!(next, Some(Event::Start(Tag::Paragraph))) {
+            self.stored_events.push_back(next.take().unwrap());
+            next = self.inner.next();
+        }
+        match next {
+            Some(Event::TaskListMarker(checked)) => {
+                self.stored_events.push_back(Event::Html(task_list_checkbox(checked).into()));
+                Some(Event::Html("<li class=\"task-list-item\">".into()))
+            }
+            next => {
+                self.stored_events.extend(next);
+                Some(event)
+            }
+        }
+    }
+}
+
+/// Renders paragraphs made of terms, each followed by one or more lines starting with `: `, as
+/// definition lists:
+///
+/// ```markdown
+/// Borrow checker
+/// : The part of the compiler which checks that references are valid.
+/// ```
+///
+/// Each term gets an ID derived from its text, so that it can be linked to like a heading.
+/// Definition lists which are only separated by blank lines are merged.
+struct DefinitionLists<'a, 'ids, I: Iterator<Item = SpannedEvent<'a>>> {
+    inner: I,
+    ids: &'ids RefCell<&'ids mut IdMap>,
+    stored_events: VecDeque<SpannedEvent<'a>>,
+    /// Whether the last paragraph was rendered as a definition list, whose `</dl>` is only
+    /// emitted once we know that the next block isn't another one.
+    in_list: bool,
+}
+
+impl<'a, 'ids, I: Iterator<Item = SpannedEvent<'a>>> DefinitionLists<'a, 'ids, I> {
+    fn new(iter: I, ids: &'ids RefCell<&'ids mut IdMap>) -> Self {
+        DefinitionLists { inner: iter, ids, stored_events: VecDeque::new(), in_list: false }
+    }
+
+    /// Renders the content of a paragraph as the entries of a definition list, or returns `None`
+    /// if the paragraph isn't one: each of its lines must be a term or a definition, and every
+    /// term must be followed by at least one definition.
+    fn render_entries(&self, content: &[SpannedEvent<'a>]) -> Option<Vec<SpannedEvent<'a>>> {
+        let lines: Vec<_> = content
+            .split(|(event, _)| matches!(event, Event::SoftBreak | Event::HardBreak))
+            .map(|line| (line, definition_text(line)))
+            .collect();
+        if lines.first()?.1.is_some()
+            || lines.last()?.1.is_none()
+            || lines.windows(2).any(|w| w[0].1.is_none() && w[1].1.is_none())
+        {
+            return None;
+        }
+
+        let mut events = Vec::new();
+        for (line, definition) in lines {
+            if let Some(text) = definition {
+                events.push((Event::Html("<dd>".into()), 0..0));
+                if !text.is_empty() {
+                    events.push((Event::Text(text.to_owned().into()), line[0].1.clone()));
+                }
+                events.extend(line[1..].iter().cloned());
+                events.push((Event::Html("</dd>".into()), 0..0));
+            } else {
+                let mut ids = self.ids.borrow_mut();
+                let slug_style = ids.slug_style();
+                let mut id = String::new();
+                for (event, _) in line {
+                    if let Event::Text(text) | Event::Code(text) = event {
+                        slug_style.push_slug(&mut id, text);
+                    }
+                }
+                let id = ids.derive(id);
+                let start = format!(
+                    "<dt id=\"{id}\">\
+                        <a href=\"#{id}\" class=\"anchor\" title=\"Link to this term\">§</a>",
+                );
+                events.push((Event::Html(start.into()), 0..0));
+                events.extend(line.iter().cloned());
+                events.push((Event::Html("</dt>".into()), 0..0));
+            }
+        }
+        Some(events)
+    }
+}
+
+/// Returns the text of the definition on `line` without its `: ` marker, if it is one.
+fn definition_text<'s>(line: &'s [SpannedEvent<'_>]) -> Option<&'s str> {
+    let Some((Event::Text(text), _)) = line.first() else { return None };
+    let rest = text.strip_prefix(':')?;
+    (rest.is_empty() || rest.starts_with([' ', '\t'])).then(|| rest.trim_start())
+}
+
+impl<'a, 'ids, I: Iterator<Item = SpannedEvent<'a>>> Iterator for DefinitionLists<'a, 'ids, I> {
+    type Item = SpannedEvent<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(event) = self.stored_events.pop_front() {
+            return Some(event);
+        }
+        let start = match self.inner.next() {
+            Some(start @ (Event::Start(Tag::Paragraph), _)) => start,
+            event if self.in_list => {
+                self.in_list = false;
+                self.stored_events.extend(event);
+                return Some((Event::Html("</dl>".into()), 0..0));
+            }
+            event => return event,
+        };
+
+        let mut content = Vec::new();
+        let mut end = None;
+        for event in &mut self.inner {
+            if let Event::End(Tag::Paragraph) = event.0 {
+                end = Some(event);
+                break;
+            }
+            content.push(event);
+        }
+        match self.render_entries(&content) {
+            Some(entries) => {
+                if !self.in_list {
+                    self.in_list = true;
+                    self.stored_events.push_back((Event::Html("<dl>".into()), 0..0));
+                }
+                self.stored_events.extend(entries);
+            }
+            None => {
+                if self.in_list {
+                    self.in_list = false;
+                    self.stored_events.push_back((Event::Html("</dl>".into()), 0..0));
+                }
+                self.stored_events.push_back(start);
+                self.stored_events.extend(content);
+                self.stored_events.extend(end);
+            }
+        }
+        self.next()
+    }
+}
+
+/// Renders block quotes starting with a `[!details Title]` line as collapsed `<details>` sections:
+///
+/// ```markdown
+/// > [!details All the error codes]
+/// > * `E1`: ...
+/// ```
+struct CollapsibleSections<'a, I: Iterator<Item = Event<'a>>> {
+    inner: I,
+    stored_events: VecDeque<Event<'a>>,
+    /// For each block quote we're in, whether it's rendered as a `<details>` section.
+    block_quotes: Vec<bool>,
+}
+
+impl<'a, I: Iterator<Item = Event<'a>>> CollapsibleSections<'a, I> {
+    fn new(iter: I) -> Self {
+        Self { inner: iter, stored_events: VecDeque::new(), block_quotes: Vec::new() }
+    }
+
+    /// Called after a block quote start event: reads the beginning of its first paragraph and
+    /// returns the `<summary>` of the section if it is a `[!details Title]` line. Otherwise, the
+    /// consumed events are stored to be emitted as is.
+    fn parse_summary(&mut self) -> Option<String> {
+        let first_line = BlockQuoteFirstLine::read(&mut self.inner, |event| event);
+        let title = first_line.text.as_deref().and_then(|text| {
+            let title = text.strip_prefix("[!details")?.strip_suffix(']')?;
+            // A space is needed after `details`, `[!details]` would be a (broken) intra-doc link.
+            title.starts_with(' ').then(|| title.trim())
+        });
+        let Some(title) = title else {
+            self.stored_events.extend(first_line.into_events());
+            return None;
+        };
+        let summary = format!("<details><summary>{}</summary>", Escape(title));
+        // If the paragraph has more lines, they are the beginning of the section content.
+        if !first_line.ends_paragraph {
+            self.stored_events.push_back(Event::Start(Tag::Paragraph));
+        }
+        Some(summary)
+    }
+}
+
+/// The events at the beginning of a block quote, up to the end of the first line of its first
+/// paragraph, used to recognize the block quotes which are rendered differently.
+struct BlockQuoteFirstLine<T> {
+    events: Vec<T>,
+    /// The event ending the first line.
+    line_end: Option<T>,
+    /// The content of the first line, if it is only made of text.
+    text: Option<String>,
+    /// Whether the first line is the whole first paragraph.
+    ends_paragraph: bool,
+}
+
+impl<T> BlockQuoteFirstLine<T> {
+    /// Reads the first line of a block quote from `iter`, right after the block quote start event.
+    /// `event` returns the markdown event of an item of `iter`.
+    fn read<'a>(iter: &mut impl Iterator<Item = T>, event: impl Fn(&T) -> &Event<'a>) -> Self {
+        let mut first_line = BlockQuoteFirstLine {
+            events: Vec::new(),
+            line_end: None,
+            text: None,
+            ends_paragraph: false,
+        };
+        let Some(start) = iter.next() else { return first_line };
+        let is_paragraph = matches!(event(&start), Event::Start(Tag::Paragraph));
+        first_line.events.push(start);
+        if !is_paragraph {
+            return first_line;
+        }
+        let mut text = String::new();
+        for item in iter {
+            match event(&item) {
+                Event::Text(s) => text.push_str(s),
+                Event::SoftBreak | Event::HardBreak => {
+                    first_line.line_end = Some(item);
+                    first_line.text = Some(text);
+                    return first_line;
+                }
+                Event::End(Tag::Paragraph) => {
+                    first_line.line_end = Some(item);
+                    first_line.text = Some(text);
+                    first_line.ends_paragraph = true;
+                    return first_line;
+                }
+                _ => {
+                    first_line.events.push(item);
+                    return first_line;
+                }
+            }
+            first_line.events.push(item);
+        }
+        first_line
+    }
+
+    /// Returns the events which were read, to emit them unchanged.
+    fn into_events(self) -> impl Iterator<Item = T> {
+        self.events.into_iter().chain(self.line_end)
+    }
+}
+
+/// Returns the CSS class and the title of the GitHub-style admonition (or "alert") marked with
+/// `[{marker}]`, like `[!NOTE]`.
+pub(crate) fn admonition_kind(marker: &str) -> Option<(&'static str, &'static str)> {
+    let kind = marker.strip_prefix('!')?;
+    [
+        ("note", "Note"),
+        ("tip", "Tip"),
+        ("important", "Important"),
+        ("warning", "Warning"),
+        ("caution", "Caution"),
+    ]
+    .into_iter()
+    .find(|(class, _)| kind.eq_ignore_ascii_case(class))
+}
+
+/// Renders `$...$` and `$$...$$` math regions as `<span class="math">` elements containing the
+/// escaped TeX source, so that a script like KaTeX can render them. Display math (`$$`) also gets
+/// the `display` class.
+///
+/// The TeX source is taken from the markdown source, since the parser would otherwise interpret
+/// characters like `*` and `_` in it. Like in Pandoc, an inline region must start with a
+/// non-space character and end with a non-space character not followed by a digit, so that
+/// prices like `$5 and $10` aren't math. A `$` preceded by a backslash or inside code is never a
+/// delimiter, and a region left open at the end of its block is rendered as is.
+struct MathFilter<'a, I: Iterator<Item = SpannedEvent<'a>>> {
+    inner: I,
+    md: &'a str,
+    enabled: bool,
+    stored_events: VecDeque<SpannedEvent<'a>>,
+    in_code_block: bool,
+    open: Option<OpenMath<'a>>,
+}
+
+/// A math region whose closing delimiter hasn't been found yet.
+struct OpenMath<'a> {
+    /// The offset of the opening delimiter.
+    start: usize,
+    display: bool,
+    /// The events since the opening delimiter, emitted as is if the region is never closed.
+    events: Vec<SpannedEvent<'a>>,
+    /// The number of inline tags (like emphasis) started in the region and not ended yet.
+    depth: usize,
+}
+
+impl<'a, I: Iterator<Item = SpannedEvent<'a>>> MathFilter<'a, I> {
+    fn new(iter: I, md: &'a str, enabled: bool) -> Self {
+        Self {
+            inner: iter,
+            md,
+            enabled,
+            stored_events: VecDeque::new(),
+            in_code_block: false,
+            open: None,
+        }
+    }
+
+    /// Looks for math delimiters in the text at `range`, whose source is its content.
+    fn scan_text(&mut self, range: Range<usize>) {
+        let md = self.md;
+        // The start of the text which hasn't been emitted yet.
+        let mut start = range.start;
+        let mut pos = range.start;
+        while let Some(offset) = md[pos..range.end].find('$') {
+            pos += offset;
+            let is_escaped = md[..pos].ends_with('\\');
+            match &self.open {
+                None => {
+                    let display = md[pos..range.end].starts_with("$$");
+                    let can_open =
+                        display || md[pos + 1..].starts_with(|c: char| !c.is_whitespace());
+                    if is_escaped || !can_open {
+                        pos += 1;
+                        continue;
+                    }
+                    self.push_text(start..pos);
+                    self.open =
+                        Some(OpenMath { start: pos, display, events: Vec::new(), depth: 0 });
+                    start = pos;
+                    pos += if display { 2 } else { 1 };
+                }
+                Some(open) => {
+                    let delim_len = if open.display { 2 } else { 1 };
+                    let content_start = open.start + delim_len;
+                    let can_close = if open.display {
+                        md[pos..range.end].starts_with("$$")
+                    } else {
+                        !md[..pos].ends_with(char::is_whitespace)
+                            && !md[pos + 1..].starts_with(|c: char| c.is_ascii_digit())
+                    };
+                    if is_escaped || !can_close || pos <= content_start {
+                        pos += 1;
+                        continue;
+                    }
+                    if open.depth > 0 {
+                        // The region would end in the middle of an emphasis or a link.
+                        self.push_text(start..pos);
+                        self.flush();
+                        start = pos;
+                        pos += 1;
+                        continue;
+                    }
+                    let class = if open.display { "math display" } else { "math" };
+                    let html = format!(
+                        "<span class=\"{class}\">{}</span>",
+                        Escape(&md[content_start..pos])
+                    );
+                    let open = self.open.take().unwrap();
+                    pos += delim_len;
+                    self.stored_events.push_back((Event::Html(html.into()), open.start..pos));
+                    start = pos;
+                }
+            }
+        }
+        self.push_text(start..range.end);
+    }
+
+    /// Emits the text at `range`, or adds it to the open math region.
+    fn push_text(&mut self, range: Range<usize>) {
+        if !range.is_empty() {
+            let event = (Event::Text(CowStr::Borrowed(&self.md[range.clone()])), range);
+            self.push(event);
+        }
+    }
+
+    fn push(&mut self, event: SpannedEvent<'a>) {
+        match &mut self.open {
+            Some(open) => open.events.push(event),
+            None => self.stored_events.push_back(event),
+        }
+    }
+
+    /// Gives up on the open math region, emitting its content as is.
+    fn flush(&mut self) {
+        if let Some(open) = self.open.take() {
+            self.stored_events.extend(open.events);
+        }
+    }
+}
+
+impl<'a, I: Iterator<Item = SpannedEvent<'a>>> Iterator for MathFilter<'a, I> {
+    type Item = SpannedEvent<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.enabled {
+            return self.inner.next();
+        }
+        loop {
+            if let Some(event) = self.stored_events.pop_front() {
+                return Some(event);
+            }
+            let Some((event, range)) = self.inner.next() else {
+                self.flush();
+                return self.stored_events.pop_front();
+            };
+            let is_inline_tag = |tag: &Tag<'_>| {
+                matches!(
+                    tag,
+                    Tag::Emphasis
+                        | Tag::Strong
+                        | Tag::Strikethrough
+                        | Tag::Link(..)
+                        | Tag::Image(..)
+                )
+            };
+            match event {
+                Event::Text(ref text)
+                    if !self.in_code_block && **text == self.md[range.clone()] =>
+                {
+                    self.scan_text(range);
+                }
+                Event::Start(ref tag) if is_inline_tag(tag) => {
+                    if let Some(open) = &mut self.open {
+                        open.depth += 1;
+                    }
+                    self.push((event, range));
+                }
+                Event::End(ref tag) if is_inline_tag(tag) => {
+                    match &mut self.open {
+                        Some(open) if open.depth > 0 => open.depth -= 1,
+                        // The region started inside this tag, so it can't end after it.
+                        _ => self.flush(),
+                    }
+                    self.push((event, range));
+                }
+                Event::Start(_) | Event::End(_) => {
+                    // Math regions can't span several blocks.
+                    self.flush();
+                    if let Event::Start(Tag::CodeBlock(_)) = event {
+                        self.in_code_block = true;
+                    } else if let Event::End(Tag::CodeBlock(_)) = event {
+                        self.in_code_block = false;
+                    }
+                    self.push((event, range));
+                }
+                _ => self.push((event, range)),
+            }
+        }
+    }
+}
+
+/// Renders block quotes starting with a `[!NOTE]`, `[!WARNING]`, etc line as admonitions, like
+/// GitHub does. The admonition title is emitted as a heading, so that it gets an ID and a TOC
+/// entry from [`HeadingLinks`].
+struct Admonitions<'a, I: Iterator<Item = SpannedEvent<'a>>> {
+    inner: I,
+    stored_events: VecDeque<SpannedEvent<'a>>,
+    /// For each block quote we're in, whether it's rendered as an admonition.
+    block_quotes: Vec<bool>,
+    /// The level of the last heading, the admonition titles are one level below it.
+    section_level: Option<HeadingLevel>,
+}
+
+impl<'a, I: Iterator<Item = SpannedEvent<'a>>> Admonitions<'a, I> {
+    fn new(iter: I) -> Self {
+        Self {
+            inner: iter,
+            stored_events: VecDeque::new(),
+            block_quotes: Vec::new(),
+            section_level: None,
+        }
+    }
+
+    /// Called after a block quote start event: returns the HTML starting the admonition if the
+    /// block quote is one. Otherwise, the consumed events are stored to be emitted as is.
+    fn parse_admonition(&mut self) -> Option<String> {
+        let first_line = BlockQuoteFirstLine::read(&mut self.inner, |(event, _)| event);
+        let kind = first_line.text.as_deref().and_then(|text| {
+            admonition_kind(text.trim().strip_prefix('[')?.strip_suffix(']')?)
+        });
+        let Some((class, title)) = kind else {
+            self.stored_events.extend(first_line.into_events());
+            return None;
+        };
+
+        let level = match self.section_level {
+            None => HeadingLevel::H1,
+            Some(level) => HeadingLevel::try_from(level as usize + 1).unwrap_or(HeadingLevel::H6),
+        };
+        self.stored_events.extend([
+            (Event::Start(Tag::Heading(level, None, Vec::new())), 0..0),
+            (Event::Text(title.into()), 0..0),
+            (Event::End(Tag::Heading(level, None, Vec::new())), 0..0),
+        ]);
+        if !first_line.ends_paragraph {
+            self.stored_events.push_back((Event::Start(Tag::Paragraph), 0..0));
+        }
+        Some(format!("<div class=\"admonition {class}\">"))
+    }
+}
+
+impl<'a, I: Iterator<Item = SpannedEvent<'a>>> Iterator for Admonitions<'a, I> {
+    type Item = SpannedEvent<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (event, range) = match self.stored_events.pop_front() {
+            // Like in `CollapsibleSections`, stored events starting or ending a block quote are
+            // the last stored event and need to be handled. The other ones (including the
+            // admonition titles) don't change the current section.
+            Some((event @ (Event::Start(Tag::BlockQuote) | Event::End(Tag::BlockQuote)), range)) => {
+                (event, range)
+            }
+            Some(event) => return Some(event),
+            None => self.inner.next()?,
+        };
+
+        Some(match event {
+            Event::Start(Tag::BlockQuote) => match self.parse_admonition() {
+                Some(start) => {
+                    self.block_quotes.push(true);
+                    (Event::Html(start.into()), range)
+                }
+                None => {
+                    self.block_quotes.push(false);
+                    (event, range)
+                }
+            },
+            Event::End(Tag::BlockQuote) => {
+                if self.block_quotes.pop() == Some(true) {
+                    (Event::Html(CowStr::Borrowed("</div>")), range)
+                } else {
+                    (event, range)
+                }
+            }
+            Event::Start(Tag::Heading(level, ..)) => {
+                self.section_level = Some(level);
+                (event, range)
+            }
+            event => (event, range),
+        })
+    }
+}
+
+impl<'a, I: Iterator<Item = Event<'a>>> Iterator for CollapsibleSections<'a, I> {
+    type Item = Event<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The stored events still need to be checked, since they can start or end a block quote.
+        // When they do, they are the last stored event.
+        let event = match self.stored_events.pop_front() {
+            Some(event) => event,
+            None => self.inner.next()?,
+        };
+
+        Some(match event {
+            Event::Start(Tag::BlockQuote) => match self.parse_summary() {
+                Some(summary) => {
+                    self.block_quotes.push(true);
+                    Event::Html(summary.into())
+                }
+                None => {
+                    self.block_quotes.push(false);
+                    event
+                }
+            },
+            Event::End(Tag::BlockQuote) => {
+                if self.block_quotes.pop() == Some(true) {
+                    Event::Html(CowStr::Borrowed("</details>"))
+                } else {
+                    event
+                }
             }
             e => e,
         })
@@ -508,18 +1902,33 @@ struct HeadingLinks<'a, 'b, 'ids, I> {
     inner: I,
     toc: Option<&'b mut TocBuilder>,
     buf: VecDeque<SpannedEvent<'a>>,
-    id_map: &'ids mut IdMap,
+    id_map: &'ids RefCell<&'ids mut IdMap>,
     heading_offset: HeadingOffset,
+    /// If `true`, the headings deeper than `<h6>` once offset are rendered as
+    /// `<div role="heading">` instead of being clamped to `<h6>`.
+    nest_deep_headings: bool,
+    /// If `true`, the section numbers computed by `toc` are prepended to the headings.
+    number_headings: bool,
 }
 
 impl<'a, 'b, 'ids, I> HeadingLinks<'a, 'b, 'ids, I> {
     fn new(
         iter: I,
         toc: Option<&'b mut TocBuilder>,
-        ids: &'ids mut IdMap,
+        ids: &'ids RefCell<&'ids mut IdMap>,
         heading_offset: HeadingOffset,
+        nest_deep_headings: bool,
+        number_headings: bool,
     ) -> Self {
-        HeadingLinks { inner: iter, toc, buf: VecDeque::new(), id_map: ids, heading_offset }
+        HeadingLinks {
+            inner: iter,
+            toc,
+            buf: VecDeque::new(),
+            id_map: ids,
+            heading_offset,
+            nest_deep_headings,
+            number_headings,
+        }
     }
 }
 
@@ -534,36 +1943,52 @@ fn next(&mut self) -> Option<Self::Item> {
         }
 
         let event = self.inner.next();
-        if let Some((Event::Start(Tag::Heading(level, _, _)), _)) = event {
+        if let Some((Event::Start(Tag::Heading(level, _, _)), ref range)) = event {
+            let range = range.clone();
+            let slug_style = self.id_map.borrow().slug_style;
             let mut id = String::new();
             for event in &mut self.inner {
                 match &event.0 {
                     Event::End(Tag::Heading(..)) => break,
                     Event::Start(Tag::Link(_, _, _)) | Event::End(Tag::Link(..)) => {}
                     Event::Text(text) | Event::Code(text) => {
-                        id.extend(text.chars().filter_map(slugify));
+                        slug_style.push_slug(&mut id, text);
                         self.buf.push_back(event);
                     }
                     _ => self.buf.push_back(event),
                 }
             }
-            let id = self.id_map.derive(id);
+            let id = self.id_map.borrow_mut().derive_heading(id, range.clone());
 
             if let Some(ref mut builder) = self.toc {
                 let mut html_header = String::new();
                 html::push_html(&mut html_header, self.buf.iter().map(|(ev, _)| ev.clone()));
+                // The sections are numbered according to the level of the Markdown heading, not
+                // the offset one, so that the top-level headings are always numbered `1`, `2`...
                 let sec = builder.push(level as u32, html_header, id.clone());
-                self.buf.push_front((Event::Html(format!("{sec} ").into()), 0..0));
+                if self.number_headings {
+                    self.buf.push_front((Event::Html(format!("{sec} ").into()), 0..0));
+                }
             }
 
-            let level =
-                std::cmp::min(level as u32 + (self.heading_offset as u32), MAX_HEADER_LEVEL);
-            self.buf.push_back((Event::Html(format!("</a></h{level}>").into()), 0..0));
-
-            let start_tags = format!(
-                "<h{level} id=\"{id}\">\
-                    <a href=\"#{id}\">",
-            );
+            let level = level as u32 + (self.heading_offset as u32);
+            let start_tags = if level > MAX_HEADER_LEVEL && self.nest_deep_headings {
+                self.buf.push_back((Event::Html("</a></div>".into()), 0..0));
+                format!(
+                    "<div role=\"heading\" aria-level=\"{level}\" id=\"{id}\">\
+                        <a href=\"#{id}\">",
+                )
+            } else {
+                if level >= MAX_HEADER_LEVEL {
+                    self.id_map.borrow_mut().record_h6_heading(range, level);
+                }
+                let level = std::cmp::min(level, MAX_HEADER_LEVEL);
+                self.buf.push_back((Event::Html(format!("</a></h{level}>").into()), 0..0));
+                format!(
+                    "<h{level} id=\"{id}\">\
+                        <a href=\"#{id}\">",
+                )
+            };
             return Some((Event::Html(start_tags.into()), 0..0));
         }
         event
@@ -576,11 +2001,47 @@ struct SummaryLine<'a, I: Iterator<Item = Event<'a>>> {
     started: bool,
     depth: u32,
     skipped_tags: u32,
+    /// The remaining events of the summary of a table, see [`SummaryLine::table_summary`].
+    table_summary: VecDeque<Event<'a>>,
 }
 
 impl<'a, I: Iterator<Item = Event<'a>>> SummaryLine<'a, I> {
     fn new(iter: I) -> Self {
-        SummaryLine { inner: iter, started: false, depth: 0, skipped_tags: 0 }
+        SummaryLine {
+            inner: iter,
+            started: false,
+            depth: 0,
+            skipped_tags: 0,
+            table_summary: VecDeque::new(),
+        }
+    }
+
+    /// Tables are dropped from summaries, so if the documentation starts with one, the text of
+    /// its header row is used as the summary instead, with the cells separated by `|`.
+    ///
+    /// This is called once the start of the table has been consumed.
+    fn table_summary(&mut self) -> Option<Event<'a>> {
+        let mut cells = 0;
+        for event in self.inner.by_ref() {
+            match event {
+                Event::End(Tag::TableHead) => break,
+                Event::Start(Tag::TableCell) => {
+                    if cells > 0 {
+                        self.table_summary.push_back(Event::Text(" | ".into()));
+                    }
+                    cells += 1;
+                }
+                Event::Start(ref tag) | Event::End(ref tag) if !check_if_allowed_tag(tag) => {}
+                Event::Start(_) | Event::End(_) | Event::Text(_) | Event::Code(_) => {
+                    self.table_summary.push_back(event)
+                }
+                _ => {}
+            }
+        }
+        // The rest of the table is skipped.
+        self.skipped_tags += 1;
+        self.table_summary.push_back(Event::End(Tag::Paragraph));
+        Some(Event::Start(Tag::Paragraph))
     }
 }
 
@@ -612,15 +2073,20 @@ impl<'a, I: Iterator<Item = Event<'a>>> Iterator for SummaryLine<'a, I> {
     type Item = Event<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(event) = self.table_summary.pop_front() {
+            return Some(event);
+        }
         if self.started && self.depth == 0 {
             return None;
         }
+        let is_first = !self.started;
         if !self.started {
             self.started = true;
         }
         if let Some(event) = self.inner.next() {
             let mut is_start = true;
             let is_allowed_tag = match event {
+                Event::Start(Tag::Table(_)) if is_first => return self.table_summary(),
                 Event::Start(ref c) => {
                     if is_forbidden_tag(c) {
                         self.skipped_tags += 1;
@@ -642,6 +2108,8 @@ fn next(&mut self) -> Option<Self::Item> {
                     self.skipped_tags += 1;
                     false
                 }
+                // The checkboxes of task lists are dropped, but the text of the item is kept.
+                Event::TaskListMarker(_) => return self.next(),
                 _ => true,
             };
             if !is_allowed_tag {
@@ -661,78 +2129,225 @@ fn next(&mut self) -> Option<Self::Item> {
     }
 }
 
-/// Moves all footnote definitions to the end and add back links to the
-/// references.
-struct Footnotes<'a, I> {
+/// Moves the footnote definitions to the end of the docs, where they are numbered in the order
+/// in which they are referenced.
+///
+/// The IDs of the footnotes are derived from their label, like `fn-label`, so that they can be
+/// linked to with `[text](#fn-label)`. References to footnotes which aren't defined in the docs
+/// being rendered link to the footnote with the same label defined by the docs of an item shown
+/// before on the same page, if any.
+struct Footnotes<'a, 'ids, I> {
     inner: I,
-    footnotes: FxHashMap<String, (Vec<Event<'a>>, u16)>,
+    ids: &'ids RefCell<&'ids mut IdMap>,
+    /// The events of the docs, without the footnote definitions, once they have all been read.
+    events: Option<VecDeque<SpannedEvent<'a>>>,
+    /// The content of the footnote definitions which weren't referenced yet.
+    definitions: FxHashMap<String, Vec<Event<'a>>>,
+    footnotes: FxHashMap<String, FootnoteDef<'a>>,
 }
 
-impl<'a, I> Footnotes<'a, I> {
-    fn new(iter: I) -> Self {
-        Footnotes { inner: iter, footnotes: FxHashMap::default() }
+struct FootnoteDef<'a> {
+    /// `None` if the footnote isn't defined in the docs.
+    content: Option<Vec<Event<'a>>>,
+    /// The number of the footnote, shown in its references.
+    number: u16,
+    /// The label of the footnote, in the form used in IDs.
+    slug: String,
+    /// The ID of the footnote, like `fn-label`.
+    id: String,
+    /// The IDs of the references to the footnote, which the footnote links back to.
+    reference_ids: Vec<String>,
+}
+
+impl<'a, 'ids, I: Iterator<Item = SpannedEvent<'a>>> Footnotes<'a, 'ids, I> {
+    fn new(iter: I, ids: &'ids RefCell<&'ids mut IdMap>) -> Self {
+        Footnotes {
+            inner: iter,
+            ids,
+            events: None,
+            definitions: FxHashMap::default(),
+            footnotes: FxHashMap::default(),
+        }
     }
 
-    fn get_entry(&mut self, key: &str) -> &mut (Vec<Event<'a>>, u16) {
-        let new_id = self.footnotes.len() + 1;
-        let key = key.to_owned();
-        self.footnotes.entry(key).or_insert((Vec::new(), new_id as u16))
+    /// Reads all the events, so that the footnote definitions are known before the references to
+    /// them are rendered.
+    fn read_definitions(&mut self) -> VecDeque<SpannedEvent<'a>> {
+        let mut events = VecDeque::new();
+        while let Some(event) = self.inner.next() {
+            let def = match event {
+                (Event::Start(Tag::FootnoteDefinition(def)), _) => def,
+                event => {
+                    events.push_back(event);
+                    continue;
+                }
+            };
+            let mut content = Vec::new();
+            for (event, _) in &mut self.inner {
+                if let Event::End(Tag::FootnoteDefinition(..)) = event {
+                    break;
+                }
+                content.push(event);
+            }
+            self.definitions.entry(def.into_string()).or_insert(content);
+        }
+        events
+    }
+
+    fn get_entry(&mut self, label: &str) -> &mut FootnoteDef<'a> {
+        let number = self.footnotes.len() as u16 + 1;
+        let content = self.definitions.remove(label);
+        let ids = self.ids;
+        self.footnotes.entry(label.to_owned()).or_insert_with(|| {
+            let mut ids = ids.borrow_mut();
+            let mut slug = String::new();
+            ids.slug_style().push_slug(&mut slug, label);
+            let id = ids.derive(format!("fn-{slug}"));
+            FootnoteDef { content, number, slug, id, reference_ids: Vec::new() }
+        })
+    }
+
+    /// Renders all the footnotes, including the ones which were defined but not referenced.
+    fn render_footnotes(&mut self) -> String {
+        let mut unreferenced: Vec<_> = self.definitions.keys().cloned().collect();
+        unreferenced.sort();
+        for label in unreferenced {
+            self.get_entry(&label);
+        }
+
+        let mut v: Vec<_> = self.footnotes.drain().collect();
+        v.sort_by_key(|(_, footnote)| footnote.number);
+        let mut ids = self.ids.borrow_mut();
+        let mut ret = String::from("<div class=\"footnotes\"><hr><ol>");
+        for (label, FootnoteDef { content, number, id, reference_ids, .. }) in v {
+            write!(ret, "<li id=\"{id}\">").unwrap();
+            let mut content = content.unwrap_or_default();
+            let mut is_paragraph = false;
+            if let Some(&Event::End(Tag::Paragraph)) = content.last() {
+                content.pop();
+                is_paragraph = true;
+            }
+            html::push_html(&mut ret, content.into_iter());
+            for (i, reference_id) in reference_ids.iter().enumerate() {
+                if i == 0 {
+                    write!(ret, "&nbsp;<a href=\"#{reference_id}\">↩</a>").unwrap();
+                } else {
+                    let n = i + 1;
+                    write!(
+                        ret,
+                        " <a href=\"#{reference_id}\" aria-label=\"Back to reference {n}\">\
+                            ↩<sup>{n}</sup>\
+                         </a>",
+                    )
+                    .unwrap();
+                }
+            }
+            if is_paragraph {
+                ret.push_str("</p>");
+            }
+            ret.push_str("</li>");
+            ids.footnotes.entry(label).or_insert(PageFootnote { id, number });
+        }
+        ret.push_str("</ol></div>");
+        ret
     }
 }
 
-impl<'a, I: Iterator<Item = SpannedEvent<'a>>> Iterator for Footnotes<'a, I> {
+/// Returns the ID of the `n`th reference (starting from 1) to the footnote whose label is `slug`.
+/// The first one doesn't have a suffix, so that it stays the same when more references are added.
+fn footnote_reference_id(slug: &str, n: usize) -> String {
+    if n <= 1 { format!("fnref-{slug}") } else { format!("fnref-{slug}-{n}") }
+}
+
+impl<'a, 'ids, I: Iterator<Item = SpannedEvent<'a>>> Iterator for Footnotes<'a, 'ids, I> {
     type Item = SpannedEvent<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            match self.inner.next() {
-                Some((Event::FootnoteReference(ref reference), range)) => {
-                    let entry = self.get_entry(reference);
-                    let reference = format!(
-                        "<sup id=\"fnref{0}\"><a href=\"#fn{0}\">{0}</a></sup>",
-                        (*entry).1
-                    );
-                    return Some((Event::Html(reference.into()), range));
-                }
-                Some((Event::Start(Tag::FootnoteDefinition(def)), _)) => {
-                    let mut content = Vec::new();
-                    for (event, _) in &mut self.inner {
-                        if let Event::End(Tag::FootnoteDefinition(..)) = event {
-                            break;
-                        }
-                        content.push(event);
-                    }
-                    let entry = self.get_entry(&def);
-                    (*entry).0 = content;
-                }
-                Some(e) => return Some(e),
-                None => {
-                    if !self.footnotes.is_empty() {
-                        let mut v: Vec<_> = self.footnotes.drain().map(|(_, x)| x).collect();
-                        v.sort_by(|a, b| a.1.cmp(&b.1));
-                        let mut ret = String::from("<div class=\"footnotes\"><hr><ol>");
-                        for (mut content, id) in v {
-                            write!(ret, "<li id=\"fn{id}\">").unwrap();
-                            let mut is_paragraph = false;
-                            if let Some(&Event::End(Tag::Paragraph)) = content.last() {
-                                content.pop();
-                                is_paragraph = true;
-                            }
-                            html::push_html(&mut ret, content.into_iter());
-                            write!(ret, "&nbsp;<a href=\"#fnref{id}\">↩</a>").unwrap();
-                            if is_paragraph {
-                                ret.push_str("</p>");
-                            }
-                            ret.push_str("</li>");
-                        }
-                        ret.push_str("</ol></div>");
-                        return Some((Event::Html(ret.into()), 0..0));
-                    } else {
-                        return None;
-                    }
+        if self.events.is_none() {
+            self.events = Some(self.read_definitions());
+        }
+        match self.events.as_mut().unwrap().pop_front() {
+            Some((Event::FootnoteReference(ref reference), range)) => {
+                if !self.definitions.contains_key(&**reference)
+                    && !self.footnotes.contains_key(&**reference)
+                    && let Some(PageFootnote { id, number }) =
+                        self.ids.borrow().footnotes.get(&**reference)
+                {
+                    // The footnote is defined by another item of the page.
+                    let reference = format!("<sup><a href=\"#{id}\">{number}</a></sup>");
+                    return Some((Event::Html(reference.into()), range));
                 }
+                let ids = self.ids;
+                let entry = self.get_entry(reference);
+                let candidate = footnote_reference_id(&entry.slug, entry.reference_ids.len() + 1);
+                let reference_id = ids.borrow_mut().derive(candidate);
+                let reference = format!(
+                    "<sup id=\"{reference_id}\"><a href=\"#{id}\">{number}</a></sup>",
+                    id = entry.id,
+                    number = entry.number,
+                );
+                entry.reference_ids.push(reference_id);
+                Some((Event::Html(reference.into()), range))
+            }
+            Some(e) => Some(e),
+            None if !self.footnotes.is_empty() || !self.definitions.is_empty() => {
+                Some((Event::Html(self.render_footnotes().into()), 0..0))
+            }
+            None => None,
+        }
+    }
+}
+
+/// Markdown in which the `{{#include path}}` directives were replaced by the content of the files
+/// they name, see [`expand_doc_includes`]. This is used for the Markdown files given to rustdoc:
+/// the directives of the docs of a crate are expanded by the `expand-markdown-includes` pass.
+pub(crate) struct IncludedMarkdown<'a> {
+    original: &'a str,
+    pub(crate) text: String,
+    /// For each expanded directive, its range in the original markdown and the range of the
+    /// included content in `text`, in order.
+    includes: Vec<(Range<usize>, Range<usize>)>,
+    /// The directives which could not be expanded and were kept as is, with the reason.
+    pub(crate) errors: Vec<(Range<usize>, String)>,
+}
+
+impl<'a> IncludedMarkdown<'a> {
+    /// Expands the directives of `md`, with paths relative to `base_dir`.
+    pub(crate) fn expand(md: &'a str, base_dir: &Path) -> IncludedMarkdown<'a> {
+        let (text, directives) = expand_doc_includes(md, base_dir);
+        let mut includes = Vec::new();
+        let mut errors = Vec::new();
+        for DocInclude { directive, result, .. } in directives {
+            match result {
+                Ok(included) => includes.push((directive, included)),
+                Err(err) => errors.push((directive, err)),
+            }
+        }
+        IncludedMarkdown { original: md, text, includes, errors }
+    }
+
+    /// Maps an offset of the expanded markdown back to the original markdown. Offsets inside
+    /// included content are mapped to the start or the end of its directive, depending on
+    /// `is_start`.
+    fn original_offset(&self, offset: usize, is_start: bool) -> usize {
+        let mut original = offset;
+        for (directive, included) in &self.includes {
+            if offset <= included.start {
+                break;
+            }
+            if offset < included.end {
+                return if is_start { directive.start } else { directive.end };
             }
+            original = offset - included.end + directive.end;
         }
+        original
+    }
+
+    /// Maps a (zero-based) line of the expanded markdown to the line of the original markdown it
+    /// comes from. Lines of included content are mapped to the line of their directive.
+    pub(crate) fn original_line(&self, line: usize) -> usize {
+        let offset = self.text.split_inclusive('\n').take(line).map(str::len).sum();
+        self.original[..self.original_offset(offset, true)].matches('\n').count()
     }
 }
 
@@ -765,9 +2380,9 @@ pub(crate) fn find_codes<T: doctest::Tester>(
     custom_code_classes_in_docs: bool,
 ) {
     let mut parser = Parser::new(doc).into_offset_iter();
-    let mut prev_offset = 0;
-    let mut nb_lines = 0;
-    let mut register_header = None;
+    let span_map = DocTestSpanMap::new(doc, tests.get_line());
+    // The level and the text of the heading being parsed, which is registered once it ends.
+    let mut heading: Option<(u32, String)> = None;
     while let Some((event, offset)) = parser.next() {
         match event {
             Event::Start(Tag::CodeBlock(kind)) => {
@@ -802,30 +2417,63 @@ pub(crate) fn find_codes<T: doctest::Tester>(
                     .collect::<Vec<Cow<'_, str>>>()
                     .join("\n");
 
-                nb_lines += doc[prev_offset..offset.start].lines().count();
-                // If there are characters between the preceding line ending and
-                // this code block, `str::lines` will return an additional line,
-                // which we subtract here.
-                if nb_lines != 0 && !&doc[prev_offset..offset.start].ends_with('\n') {
-                    nb_lines -= 1;
-                }
-                let line = tests.get_line() + nb_lines + 1;
-                tests.add_test(text, block_info, line);
-                prev_offset = offset.start;
+                tests.add_test(text, block_info, span_map.file_line(offset.start));
             }
             Event::Start(Tag::Heading(level, _, _)) => {
-                register_header = Some(level as u32);
+                heading = Some((level as u32, String::new()));
+            }
+            // Headings like ``## Using `Vec` `` are made of several events.
+            Event::Text(ref s) | Event::Code(ref s) => {
+                if let Some((_, text)) = &mut heading {
+                    text.push_str(s);
+                }
             }
-            Event::Text(ref s) if register_header.is_some() => {
-                let level = register_header.unwrap();
-                tests.register_header(s, level);
-                register_header = None;
+            Event::End(Tag::Heading(..)) => {
+                if let Some((level, text)) = heading.take() {
+                    tests.register_header(&text, level);
+                }
             }
             _ => {}
         }
     }
 }
 
+/// Maps byte offsets in documentation to the lines they are on, both relative to the start of the
+/// documentation and in the source file it comes from.
+///
+/// Line endings are recognized like in Markdown: `\n`, `\r\n` and `\r` all end a line.
+pub(crate) struct DocTestSpanMap {
+    /// The byte offsets at which the lines of the documentation start.
+    line_starts: Vec<usize>,
+    /// The 0-based line of the source file on which the documentation starts, as returned by
+    /// [`doctest::Tester::get_line`].
+    first_line: usize,
+}
+
+impl DocTestSpanMap {
+    pub(crate) fn new(doc: &str, first_line: usize) -> Self {
+        let bytes = doc.as_bytes();
+        let line_starts = iter::once(0)
+            .chain(bytes.iter().enumerate().filter_map(|(i, &b)| match b {
+                b'\n' => Some(i + 1),
+                b'\r' if bytes.get(i + 1) != Some(&b'\n') => Some(i + 1),
+                _ => None,
+            }))
+            .collect();
+        DocTestSpanMap { line_starts, first_line }
+    }
+
+    /// Returns the 0-based line of the documentation on which `offset` is.
+    pub(crate) fn doc_line(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|&start| start <= offset) - 1
+    }
+
+    /// Returns the 1-based line of the source file on which `offset` is.
+    pub(crate) fn file_line(&self, offset: usize) -> usize {
+        self.first_line + self.doc_line(offset) + 1
+    }
+}
+
 pub(crate) struct ExtraInfo<'tcx> {
     def_id: DefId,
     sp: Span,
@@ -878,6 +2526,17 @@ pub(crate) struct LangString {
     pub(crate) error_codes: Vec<String>,
     pub(crate) edition: Option<Edition>,
     pub(crate) added_classes: Vec<String>,
+    /// Lines to highlight when rendering the code block, from the `hl_lines` attribute.
+    pub(crate) highlighted_lines: Vec<RangeInclusive<usize>>,
+    /// Cargo features which must be enabled to run the doctest, from the `required-features`
+    /// attribute.
+    pub(crate) required_features: Vec<String>,
+    /// The channel the playground runs the example on (`stable`, `beta` or `nightly`), from the
+    /// `channel` attribute.
+    pub(crate) playground_channel: Option<String>,
+    /// The build mode the playground runs the example in (`debug` or `release`), from the `mode`
+    /// attribute.
+    pub(crate) playground_mode: Option<String>,
     pub(crate) unknown: Vec<String>,
 }
 
@@ -888,6 +2547,17 @@ pub(crate) enum Ignore {
     Some(Vec<String>),
 }
 
+impl Ignore {
+    /// Returns the targets the example is ignored on, from its `ignore-<target>` attributes. This
+    /// is empty if the example is ignored on all targets, or on none.
+    pub(crate) fn targets(&self) -> &[String] {
+        match self {
+            Ignore::Some(targets) => targets,
+            Ignore::All | Ignore::None => &[],
+        }
+    }
+}
+
 /// This is the parser for fenced codeblocks attributes. It implements the following eBNF:
 ///
 /// ```eBNF
@@ -1176,6 +2846,21 @@ fn next(&mut self) -> Option<Self::Item> {
     }
 }
 
+/// Parses the value of the `hl_lines` code block attribute, a comma-separated list of 1-based line
+/// numbers (`3`) or inclusive line ranges (`3-5`).
+fn parse_line_ranges(value: &str) -> Option<Vec<RangeInclusive<usize>>> {
+    value
+        .split(',')
+        .map(|range| {
+            let range = range.trim();
+            let (start, end) = range.split_once('-').unwrap_or((range, range));
+            let start = start.trim().parse::<usize>().ok()?;
+            let end = end.trim().parse::<usize>().ok()?;
+            (start >= 1 && start <= end).then_some(start..=end)
+        })
+        .collect()
+}
+
 fn tokens(string: &str) -> impl Iterator<Item = LangStringToken<'_>> {
     // Pandoc, which Rust once used for generating documentation,
     // expects lang strings to be surrounded by `{}` and for each token
@@ -1212,6 +2897,10 @@ fn default() -> Self {
             error_codes: Vec::new(),
             edition: None,
             added_classes: Vec::new(),
+            highlighted_lines: Vec::new(),
+            required_features: Vec::new(),
+            playground_channel: None,
+            playground_mode: None,
             unknown: Vec::new(),
         }
     }
@@ -1353,6 +3042,44 @@ fn parse(
                         if custom_code_classes_in_docs {
                             if key == "class" {
                                 data.added_classes.push(value.to_owned());
+                            } else if key == "hl_lines" {
+                                if let Some(lines) = parse_line_ranges(value) {
+                                    data.highlighted_lines.extend(lines);
+                                } else if let Some(extra) = extra {
+                                    extra.error_invalid_codeblock_attr(format!(
+                                        "invalid `hl_lines` value `{value}`: expected a \
+                                         comma-separated list of line numbers or ranges like `3-5`"
+                                    ));
+                                }
+                            } else if key == "required-features" {
+                                data.required_features.extend(
+                                    value
+                                        .split(',')
+                                        .map(str::trim)
+                                        .filter(|feature| !feature.is_empty())
+                                        .map(str::to_owned),
+                                );
+                            } else if key == "channel" || key == "mode" {
+                                let (expected, field) = if key == "channel" {
+                                    (
+                                        &["stable", "beta", "nightly"][..],
+                                        &mut data.playground_channel,
+                                    )
+                                } else {
+                                    (&["debug", "release"][..], &mut data.playground_mode)
+                                };
+                                if expected.contains(&value) {
+                                    *field = Some(value.to_owned());
+                                } else if let Some(extra) = extra {
+                                    extra.error_invalid_codeblock_attr(format!(
+                                        "invalid `{key}` value `{value}`: expected one of {}",
+                                        expected
+                                            .iter()
+                                            .map(|value| format!("`{value}`"))
+                                            .collect::<Vec<_>>()
+                                            .join(", ")
+                                    ));
+                                }
                             } else if let Some(extra) = extra {
                                 extra.error_invalid_codeblock_attr(format!(
                                     "unsupported attribute `{key}`"
@@ -1397,6 +3124,14 @@ pub fn into_string(self) -> String {
             playground,
             heading_offset,
             custom_code_classes_in_docs,
+            enable_per_target_ignores,
+            code_block_id_prefix,
+            math,
+            expandable_hidden_lines,
+            smart_link_titles,
+            nest_deep_headings,
+            smart_punctuation,
+            doc_assets,
         } = self;
 
         // This is actually common enough to special-case
@@ -1410,22 +3145,149 @@ pub fn into_string(self) -> String {
                 .map(|link| (link.href.as_str().into(), link.tooltip.as_str().into()))
         };
 
-        let p = Parser::new_with_broken_link_callback(md, main_body_opts(), Some(&mut replacer));
+        let opts = render_opts(smart_punctuation);
+        let p = Parser::new_with_broken_link_callback(md, opts, Some(&mut replacer));
         let p = p.into_offset_iter();
 
         let mut s = String::with_capacity(md.len() * 3 / 2);
 
-        let p = HeadingLinks::new(p, None, ids, heading_offset);
-        let p = Footnotes::new(p);
+        // Both the headings and the code blocks need IDs.
+        let ids = RefCell::new(ids);
+        let anchors = code_block_id_prefix.map(|prefix| CodeBlockAnchors::new(&ids, prefix));
+
+        let p = MathFilter::new(p, md, math);
+        let p = Admonitions::new(p);
+        let p = HeadingLinks::new(p, None, &ids, heading_offset, nest_deep_headings, false);
+        let p = Footnotes::new(p, &ids);
+        let p = DefinitionLists::new(p, &ids);
         let p = LinkReplacer::new(p.map(|(ev, _)| ev), links);
+        let p = DocImages::new(p, doc_assets);
+        let p = SmartAutolinks::new(p, smart_link_titles);
+        let p = EmojiShortcodes::new(p);
+        let p = TaskLists::new(p);
         let p = TableWrapper::new(p);
-        let p = CodeBlocks::new(p, codes, edition, playground, custom_code_classes_in_docs);
+        let p = CollapsibleSections::new(p);
+        let p = CodeBlocks::new(
+            p,
+            codes,
+            edition,
+            playground,
+            custom_code_classes_in_docs,
+            enable_per_target_ignores,
+            expandable_hidden_lines,
+            anchors,
+            links,
+        );
         html::push_html(&mut s, p);
 
         s
     }
 }
 
+/// An event of the docs returned by [`doc_events`].
+pub(crate) struct DocEvent<'a> {
+    pub(crate) event: Event<'a>,
+    /// The byte range of the event in the Markdown.
+    pub(crate) range: Range<usize>,
+    /// The ID of the heading, if the event starts one.
+    pub(crate) heading_id: Option<String>,
+}
+
+/// Parses the Markdown of some docs like [`Markdown`] does, for the outputs which need the
+/// structure of the docs rather than HTML, like the JSON one:
+///
+/// - the intra-doc links are resolved with `links` and rewritten like by [`LinkReplacer`];
+/// - the headings are given the ID derived from their text by [`HeadingLinks`] with `ids`;
+/// - the emoji shortcodes are replaced like by [`EmojiShortcodes`].
+pub(crate) fn doc_events<'a>(
+    md: &'a str,
+    links: &'a [RenderedLink],
+    ids: &mut IdMap,
+    smart_punctuation: bool,
+) -> Vec<DocEvent<'a>> {
+    let mut replacer = |broken_link: BrokenLink<'_>| {
+        links
+            .iter()
+            .find(|link| &*link.original_text == &*broken_link.reference)
+            .map(|link| (link.href.as_str().into(), link.tooltip.as_str().into()))
+    };
+    let opts = render_opts(smart_punctuation);
+    let p = Parser::new_with_broken_link_callback(md, opts, Some(&mut replacer));
+
+    let mut rewriter = LinkRewriter::new(links);
+    let mut events = Vec::new();
+    // The ID and the events of the heading being read, which are kept until its ID is known.
+    let mut heading: Option<(String, Vec<DocEvent<'a>>)> = None;
+    for (mut event, range) in p.into_offset_iter() {
+        match &mut heading {
+            None => {
+                rewriter.rewrite(&mut event);
+                let starts_heading = matches!(event, Event::Start(Tag::Heading(..)));
+                let event = DocEvent { event, range, heading_id: None };
+                if starts_heading {
+                    heading = Some((String::new(), vec![event]));
+                } else {
+                    events.push(event);
+                }
+            }
+            Some((id, heading_events)) => {
+                // Like in `HeadingLinks`, the ID is derived from the text before it is rewritten.
+                if let Event::Text(text) | Event::Code(text) = &event {
+                    ids.slug_style.push_slug(id, text);
+                }
+                rewriter.rewrite(&mut event);
+                let ends_heading = matches!(event, Event::End(Tag::Heading(..)));
+                heading_events.push(DocEvent { event, range, heading_id: None });
+                if ends_heading {
+                    let (id, mut heading_events) = heading.take().unwrap();
+                    let start = &mut heading_events[0];
+                    start.heading_id = Some(ids.derive_heading(id, start.range.clone()));
+                    events.extend(heading_events);
+                }
+            }
+        }
+    }
+
+    // Like in `EmojiShortcodes`, the runs of text are merged before the shortcodes are replaced,
+    // since the parser can split text around characters like `_`.
+    let mut merged: Vec<DocEvent<'a>> = Vec::with_capacity(events.len());
+    let mut in_code_block = false;
+    for doc_event in events {
+        match &doc_event.event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(Tag::CodeBlock(_)) => in_code_block = false,
+            Event::Text(text) if !in_code_block => {
+                if let Some(DocEvent { event: Event::Text(prev), range, .. }) = merged.last_mut() {
+                    *prev = format!("{prev}{text}").into();
+                    range.end = doc_event.range.end;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        merged.push(doc_event);
+    }
+    for DocEvent { event, .. } in &mut merged {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(Tag::CodeBlock(_)) => in_code_block = false,
+            Event::Text(text) if !in_code_block => {
+                if let Some(replaced) = replace_emoji_shortcodes(text) {
+                    *text = replaced.into();
+                }
+            }
+            _ => {}
+        }
+    }
+    merged
+}
+
+/// Returns whether a code block with the given lang string is rendered and tested as Rust code.
+pub(crate) fn is_rust_code_block(lang_string: &str, custom_code_classes_in_docs: bool) -> bool {
+    LangString::parse_without_check(lang_string, ErrorCodes::No, false, custom_code_classes_in_docs)
+        .rust
+}
+
 impl MarkdownWithToc<'_> {
     pub(crate) fn into_string(self) -> String {
         let MarkdownWithToc {
@@ -1435,35 +3297,68 @@ pub(crate) fn into_string(self) -> String {
             edition,
             playground,
             custom_code_classes_in_docs,
+            enable_per_target_ignores,
+            math,
+            expandable_hidden_lines,
+            smart_link_titles,
+            nest_deep_headings,
+            heading_offset,
+            number_headings,
+            smart_punctuation,
         } = self;
 
-        let p = Parser::new_ext(md, main_body_opts()).into_offset_iter();
+        let p = Parser::new_ext(md, render_opts(smart_punctuation)).into_offset_iter();
 
         let mut s = String::with_capacity(md.len() * 3 / 2);
 
         let mut toc = TocBuilder::new();
 
         {
-            let p = HeadingLinks::new(p, Some(&mut toc), ids, HeadingOffset::H1);
-            let p = Footnotes::new(p);
-            let p = TableWrapper::new(p.map(|(ev, _)| ev));
-            let p = CodeBlocks::new(p, codes, edition, playground, custom_code_classes_in_docs);
+            let ids = RefCell::new(ids);
+            let p = MathFilter::new(p, md, math);
+            let p = Admonitions::new(p);
+            let p = HeadingLinks::new(
+                p,
+                Some(&mut toc),
+                &ids,
+                heading_offset,
+                nest_deep_headings,
+                number_headings,
+            );
+            let p = Footnotes::new(p, &ids);
+            let p = DefinitionLists::new(p, &ids);
+            let p = SmartAutolinks::new(p.map(|(ev, _)| ev), smart_link_titles);
+            let p = EmojiShortcodes::new(p);
+            let p = TaskLists::new(p);
+            let p = TableWrapper::new(p);
+            let p = CollapsibleSections::new(p);
+            let p = CodeBlocks::new(
+                p,
+                codes,
+                edition,
+                playground,
+                custom_code_classes_in_docs,
+                enable_per_target_ignores,
+                expandable_hidden_lines,
+                None,
+                &[],
+            );
             html::push_html(&mut s, p);
         }
 
-        format!("<nav id=\"TOC\">{toc}</nav>{s}", toc = toc.into_toc().print())
+        format!("<nav id=\"TOC\">{toc}</nav>{s}", toc = toc.into_toc().print(number_headings))
     }
 }
 
 impl MarkdownItemInfo<'_> {
     pub(crate) fn into_string(self) -> String {
-        let MarkdownItemInfo(md, ids) = self;
+        let MarkdownItemInfo(md, ids, smart_punctuation) = self;
 
         // This is actually common enough to special-case
         if md.is_empty() {
             return String::new();
         }
-        let p = Parser::new_ext(md, main_body_opts()).into_offset_iter();
+        let p = Parser::new_ext(md, render_opts(smart_punctuation)).into_offset_iter();
 
         // Treat inline HTML as plain text.
         let p = p.map(|event| match event.0 {
@@ -1473,8 +3368,9 @@ pub(crate) fn into_string(self) -> String {
 
         let mut s = String::with_capacity(md.len() * 3 / 2);
 
-        let p = HeadingLinks::new(p, None, ids, HeadingOffset::H1);
-        let p = Footnotes::new(p);
+        let ids = RefCell::new(ids);
+        let p = HeadingLinks::new(p, None, &ids, HeadingOffset::H1, false, false);
+        let p = Footnotes::new(p, &ids);
         let p = TableWrapper::new(p.map(|(ev, _)| ev));
         let p = p.filter(|event| {
             !matches!(event, Event::Start(Tag::Paragraph) | Event::End(Tag::Paragraph))
@@ -1487,7 +3383,7 @@ pub(crate) fn into_string(self) -> String {
 
 impl MarkdownSummaryLine<'_> {
     pub(crate) fn into_string_with_has_more_content(self) -> (String, bool) {
-        let MarkdownSummaryLine(md, links) = self;
+        let MarkdownSummaryLine(md, links, smart_punctuation) = self;
         // This is actually common enough to special-case
         if md.is_empty() {
             return (String::new(), false);
@@ -1500,13 +3396,14 @@ pub(crate) fn into_string_with_has_more_content(self) -> (String, bool) {
                 .map(|link| (link.href.as_str().into(), link.tooltip.as_str().into()))
         };
 
-        let p = Parser::new_with_broken_link_callback(md, summary_opts(), Some(&mut replacer))
-            .peekable();
+        let opts = summary_opts(smart_punctuation);
+        let p = Parser::new_with_broken_link_callback(md, opts, Some(&mut replacer)).peekable();
         let mut summary = SummaryLine::new(p);
 
         let mut s = String::new();
 
-        let without_paragraphs = LinkReplacer::new(&mut summary, links).filter(|event| {
+        let p = EmojiShortcodes::new(LinkReplacer::new(&mut summary, links));
+        let without_paragraphs = p.filter(|event| {
             !matches!(event, Event::Start(Tag::Paragraph) | Event::End(Tag::Paragraph))
         });
 
@@ -1523,23 +3420,86 @@ pub(crate) fn into_string(self) -> String {
     }
 }
 
-/// Renders a subset of Markdown in the first paragraph of the provided Markdown.
-///
-/// - *Italics*, **bold**, and `inline code` styles **are** rendered.
-/// - Headings and links are stripped (though the text *is* rendered).
-/// - HTML, code blocks, and everything else are ignored.
+/// The format of the summaries generated by [`summarize`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SummaryFormat {
+    /// A subset of HTML: emphasis, strong emphasis, inline code and links.
+    Html,
+    /// Plain text, without any markup.
+    PlainText,
+}
+
+/// How [`summarize`] renders links.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SummaryLinks {
+    /// Only the text of the links is kept.
+    Text,
+    /// The links are rendered as `<a>` elements. Plain text summaries only contain their text.
+    Anchors,
+}
+
+/// How [`summarize`] renders inline code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SummaryCode {
+    /// The code is marked as such: it is put in a `<code>` element in HTML summaries, and
+    /// surrounded by backticks in plain text summaries.
+    Marked,
+    /// Only the text of the code is kept.
+    Text,
+}
+
+/// The options of [`summarize`]. The default options render an HTML summary without a length
+/// limit, where only the text of the links is kept.
+#[derive(Clone, Copy)]
+pub struct SummaryOptions<'a> {
+    pub format: SummaryFormat,
+    /// The maximum length of the text of the summary, markup excluded. If the summary had to be
+    /// shortened, an ellipsis (`…`) is added after it.
+    pub length_limit: Option<usize>,
+    pub links: SummaryLinks,
+    pub code: SummaryCode,
+    /// The intra-doc links of the Markdown, which are resolved to their `href`.
+    pub link_names: &'a [RenderedLink],
+    /// Whether quotes and dashes are replaced by their typographic forms, like `--` by `–`.
+    pub smart_punctuation: bool,
+}
+
+impl Default for SummaryOptions<'_> {
+    fn default() -> Self {
+        SummaryOptions {
+            format: SummaryFormat::Html,
+            length_limit: None,
+            links: SummaryLinks::Text,
+            code: SummaryCode::Marked,
+            link_names: &[],
+            smart_punctuation: true,
+        }
+    }
+}
+
+/// Where the events of a summary are written, see [`write_summary`].
+trait SummaryWriter {
+    /// Appends `text`, or returns [`ControlFlow::Break`] if it doesn't fit in the length limit.
+    fn push(&mut self, text: &str) -> ControlFlow<()>;
+    /// Appends inline code, or returns [`ControlFlow::Break`] if it doesn't fit in the length
+    /// limit.
+    fn push_code(&mut self, code: &str) -> ControlFlow<()>;
+    /// Called for the inline tags, like emphasis and links, which the summary goes through.
+    fn start_tag(&mut self, tag: &Tag<'_>);
+    fn end_tag(&mut self, tag: &Tag<'_>);
+}
+
+/// Writes the summary of the provided Markdown, its first paragraph or heading, to `writer`.
+/// Returns whether the summary had to be shortened to fit in the length limit of `writer`.
 ///
-/// Returns a tuple of the rendered HTML string and whether the output was shortened
-/// due to the provided `length_limit`.
-fn markdown_summary_with_limit(
+/// If the Markdown starts with a table, the text of its header row is written instead, with the
+/// cells separated by `|`. HTML, code blocks, and everything else are ignored.
+fn write_summary(
     md: &str,
     link_names: &[RenderedLink],
-    length_limit: usize,
-) -> (String, bool) {
-    if md.is_empty() {
-        return (String::new(), false);
-    }
-
+    smart_punctuation: bool,
+    writer: &mut impl SummaryWriter,
+) -> bool {
     let mut replacer = |broken_link: BrokenLink<'_>| {
         link_names
             .iter()
@@ -1547,106 +3507,286 @@ fn markdown_summary_with_limit(
             .map(|link| (link.href.as_str().into(), link.tooltip.as_str().into()))
     };
 
-    let p = Parser::new_with_broken_link_callback(md, summary_opts(), Some(&mut replacer));
-    let mut p = LinkReplacer::new(p, link_names);
+    let opts = summary_opts(smart_punctuation);
+    let p = Parser::new_with_broken_link_callback(md, opts, Some(&mut replacer));
+    let mut p = EmojiShortcodes::new(LinkReplacer::new(p, link_names));
 
-    let mut buf = HtmlWithLimit::new(length_limit);
     let mut stopped_early = false;
+    let mut table_cells = 0;
     p.try_for_each(|event| {
         match &event {
             Event::Text(text) => {
-                let r =
-                    text.split_inclusive(char::is_whitespace).try_for_each(|word| buf.push(word));
+                let r = text
+                    .split_inclusive(char::is_whitespace)
+                    .try_for_each(|word| writer.push(word));
                 if r.is_break() {
                     stopped_early = true;
                 }
                 return r;
             }
             Event::Code(code) => {
-                buf.open_tag("code");
-                let r = buf.push(code);
+                let r = writer.push_code(code);
                 if r.is_break() {
                     stopped_early = true;
-                } else {
-                    buf.close_tag();
                 }
                 return r;
             }
             Event::Start(tag) => match tag {
-                Tag::Emphasis => buf.open_tag("em"),
-                Tag::Strong => buf.open_tag("strong"),
                 Tag::CodeBlock(..) => return ControlFlow::Break(()),
-                _ => {}
+                Tag::TableCell => {
+                    if table_cells > 0 {
+                        writer.push(" | ")?;
+                    }
+                    table_cells += 1;
+                }
+                tag => writer.start_tag(tag),
             },
             Event::End(tag) => match tag {
-                Tag::Emphasis | Tag::Strong => buf.close_tag(),
-                Tag::Paragraph | Tag::Heading(..) => return ControlFlow::Break(()),
-                _ => {}
+                Tag::Paragraph | Tag::Heading(..) | Tag::TableHead => {
+                    return ControlFlow::Break(());
+                }
+                tag => writer.end_tag(tag),
             },
-            Event::HardBreak | Event::SoftBreak => buf.push(" ")?,
+            Event::HardBreak | Event::SoftBreak => writer.push(" ")?,
             _ => {}
         };
         ControlFlow::Continue(())
     });
+    stopped_early
+}
 
-    (buf.finish(), stopped_early)
+/// Writes the summaries of [`summarize`].
+struct SummaryBuf<'a> {
+    buf: HtmlWithLimit,
+    options: SummaryOptions<'a>,
 }
 
-/// Renders a shortened first paragraph of the given Markdown as a subset of Markdown,
-/// making it suitable for contexts like the search index.
+impl SummaryWriter for SummaryBuf<'_> {
+    fn push(&mut self, text: &str) -> ControlFlow<()> {
+        self.buf.push(text)
+    }
+
+    fn push_code(&mut self, code: &str) -> ControlFlow<()> {
+        match (self.options.code, self.options.format) {
+            (SummaryCode::Marked, SummaryFormat::Html) => {
+                self.buf.open_tag("code");
+                let r = self.buf.push(code);
+                self.buf.close_tag();
+                r
+            }
+            (SummaryCode::Marked, SummaryFormat::PlainText) => self.buf.push(&format!("`{code}`")),
+            (SummaryCode::Text, _) => self.buf.push(code),
+        }
+    }
+
+    fn start_tag(&mut self, tag: &Tag<'_>) {
+        match tag {
+            Tag::Emphasis => self.buf.open_tag("em"),
+            Tag::Strong => self.buf.open_tag("strong"),
+            Tag::Link(_, dest, _) if self.options.links == SummaryLinks::Anchors => {
+                self.buf.open_tag_with_attr("a", Some(("href", dest)))
+            }
+            _ => {}
+        }
+    }
+
+    fn end_tag(&mut self, tag: &Tag<'_>) {
+        match tag {
+            Tag::Emphasis | Tag::Strong => self.buf.close_tag(),
+            Tag::Link(..) if self.options.links == SummaryLinks::Anchors => self.buf.close_tag(),
+            _ => {}
+        }
+    }
+}
+
+/// Renders a summary of the provided Markdown: its first paragraph or heading.
 ///
-/// Will shorten to 59 or 60 characters, including an ellipsis (…) if it was shortened.
+/// - Emphasis, strong emphasis, inline code and links are rendered according to `options`.
+/// - Headings are stripped (though the text *is* rendered).
+/// - If the Markdown starts with a table, the text of its header row is rendered instead, with the
+///   cells separated by `|`.
+/// - HTML, code blocks, and everything else are ignored.
 ///
-/// See [`markdown_summary_with_limit`] for details about what is rendered and what is not.
-pub(crate) fn short_markdown_summary(markdown: &str, link_names: &[RenderedLink]) -> String {
-    let (mut s, was_shortened) = markdown_summary_with_limit(markdown, link_names, 59);
+/// This is how rustdoc summarizes the documentation of the items in the search index and the
+/// module pages, so that tools displaying rustdoc's summaries elsewhere can render the same ones.
+pub fn summarize(md: &str, options: &SummaryOptions<'_>) -> String {
+    if md.is_empty() {
+        return String::new();
+    }
+
+    let length_limit = options.length_limit.unwrap_or(usize::MAX);
+    let buf = match options.format {
+        SummaryFormat::Html => HtmlWithLimit::new(length_limit),
+        SummaryFormat::PlainText => HtmlWithLimit::new_plain_text(length_limit),
+    };
+    let mut writer = SummaryBuf { buf, options: *options };
+    let stopped_early =
+        write_summary(md, options.link_names, options.smart_punctuation, &mut writer);
 
-    if was_shortened {
+    let mut s = writer.buf.finish();
+    if stopped_early {
         s.push('…');
     }
-
     s
 }
 
+/// Renders a shortened first paragraph of the given Markdown as a subset of Markdown,
+/// making it suitable for contexts like the search index.
+///
+/// Will shorten to 59 or 60 characters, including an ellipsis (…) if it was shortened.
+///
+/// See [`summarize`] for details about what is rendered and what is not.
+pub(crate) fn short_markdown_summary(
+    markdown: &str,
+    link_names: &[RenderedLink],
+    smart_punctuation: bool,
+) -> String {
+    summarize(
+        markdown,
+        &SummaryOptions {
+            length_limit: Some(59),
+            link_names,
+            smart_punctuation,
+            ..Default::default()
+        },
+    )
+}
+
 /// Renders the first paragraph of the provided markdown as plain text.
 /// Useful for alt-text.
 ///
 /// - Headings, links, and formatting are stripped.
 /// - Inline code is rendered as-is, surrounded by backticks.
 /// - HTML and code blocks are ignored.
-pub(crate) fn plain_text_summary(md: &str, link_names: &[RenderedLink]) -> String {
-    if md.is_empty() {
-        return String::new();
-    }
+pub(crate) fn plain_text_summary(
+    md: &str,
+    link_names: &[RenderedLink],
+    smart_punctuation: bool,
+) -> String {
+    summarize(
+        md,
+        &SummaryOptions {
+            format: SummaryFormat::PlainText,
+            link_names,
+            smart_punctuation,
+            ..Default::default()
+        },
+    )
+}
+
+/// The style of a [`SummarySpan`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SummarySpanStyle {
+    Text,
+    /// Inline code, like `` `Vec<T>` ``.
+    Code,
+}
 
-    let mut s = String::with_capacity(md.len() * 3 / 2);
+/// A run of text of a summary with the same style and link, see [`summarize_spans`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SummarySpan {
+    pub text: String,
+    pub style: SummarySpanStyle,
+    /// The destination of the link the text is part of. Intra-doc links are resolved to their
+    /// `href`.
+    pub target: Option<String>,
+}
 
-    let mut replacer = |broken_link: BrokenLink<'_>| {
-        link_names
-            .iter()
-            .find(|link| &*link.original_text == &*broken_link.reference)
-            .map(|link| (link.href.as_str().into(), link.tooltip.as_str().into()))
-    };
+/// Collects the spans of [`summarize_spans`].
+struct SummarySpans {
+    spans: Vec<SummarySpan>,
+    len: usize,
+    limit: usize,
+    /// The destination of the link we're in.
+    target: Option<String>,
+}
 
-    let p = Parser::new_with_broken_link_callback(md, summary_opts(), Some(&mut replacer));
+impl SummarySpans {
+    /// Appends `text` to the spans, or returns [`ControlFlow::Break`] if it doesn't fit in the
+    /// length limit.
+    fn push_styled(&mut self, text: &str, style: SummarySpanStyle) -> ControlFlow<()> {
+        if self.len + text.len() > self.limit {
+            return ControlFlow::Break(());
+        }
+        self.len += text.len();
+        self.push_unlimited(text, style);
+        ControlFlow::Continue(())
+    }
 
-    for event in p {
-        match &event {
-            Event::Text(text) => s.push_str(text),
-            Event::Code(code) => {
-                s.push('`');
-                s.push_str(code);
-                s.push('`');
+    fn push_unlimited(&mut self, text: &str, style: SummarySpanStyle) {
+        match self.spans.last_mut() {
+            Some(last) if last.style == style && last.target == self.target => {
+                last.text.push_str(text)
             }
-            Event::HardBreak | Event::SoftBreak => s.push(' '),
-            Event::Start(Tag::CodeBlock(..)) => break,
-            Event::End(Tag::Paragraph) => break,
-            Event::End(Tag::Heading(..)) => break,
-            _ => (),
+            _ => self.spans.push(SummarySpan {
+                text: text.to_owned(),
+                style,
+                target: self.target.clone(),
+            }),
         }
     }
+}
 
-    s
+impl SummaryWriter for SummarySpans {
+    fn push(&mut self, text: &str) -> ControlFlow<()> {
+        self.push_styled(text, SummarySpanStyle::Text)
+    }
+
+    fn push_code(&mut self, code: &str) -> ControlFlow<()> {
+        self.push_styled(code, SummarySpanStyle::Code)
+    }
+
+    fn start_tag(&mut self, tag: &Tag<'_>) {
+        if let Tag::Link(_, dest, _) = tag {
+            self.target = Some(dest.to_string());
+        }
+    }
+
+    fn end_tag(&mut self, tag: &Tag<'_>) {
+        if let Tag::Link(..) = tag {
+            self.target = None;
+        }
+    }
+}
+
+/// Summarizes the provided Markdown like [`summarize`], but as a list of spans keeping the inline
+/// code and the link destinations, instead of as a string. Consecutive spans have a different
+/// style or link. Emphasis is stripped.
+///
+/// This lets the tools showing the summaries, like IDEs in their hovers, render the code and
+/// links of the summaries with their own markup.
+pub fn summarize_spans(
+    md: &str,
+    link_names: &[RenderedLink],
+    length_limit: Option<usize>,
+    smart_punctuation: bool,
+) -> Vec<SummarySpan> {
+    if md.is_empty() {
+        return Vec::new();
+    }
+
+    let mut spans = SummarySpans {
+        spans: Vec::new(),
+        len: 0,
+        limit: length_limit.unwrap_or(usize::MAX),
+        target: None,
+    };
+    let stopped_early = write_summary(md, link_names, smart_punctuation, &mut spans);
+
+    if stopped_early {
+        spans.target = None;
+        spans.push_unlimited("…", SummarySpanStyle::Text);
+    }
+    spans.spans
+}
+
+/// Like [`short_markdown_summary`], but returns the summary as spans, see [`summarize_spans`].
+pub fn short_markdown_summary_spans(
+    markdown: &str,
+    link_names: &[RenderedLink],
+    smart_punctuation: bool,
+) -> Vec<SummarySpan> {
+    summarize_spans(markdown, link_names, Some(59), smart_punctuation)
 }
 
 #[derive(Debug)]
@@ -1654,6 +3794,9 @@ pub(crate) struct MarkdownLink {
     pub kind: LinkType,
     pub link: String,
     pub display_text: Option<String>,
+    /// The range of the display text in the markdown, from the start of its first text or code
+    /// span to the end of its last one, used to suggest fixes to both the text and destination.
+    pub display_text_range: Option<Range<usize>>,
     pub range: MarkdownLinkRange,
 }
 
@@ -1815,7 +3958,7 @@ pub(crate) fn markdown_links<'md, R>(
                     LinkType::Autolink | LinkType::Email => unreachable!(),
                 };
 
-                let display_text = if matches!(
+                let (display_text, display_text_range) = if matches!(
                     link_type,
                     LinkType::Inline
                         | LinkType::ReferenceUnknown
@@ -1825,13 +3968,14 @@ pub(crate) fn markdown_links<'md, R>(
                 ) {
                     collect_link_data(&mut event_iter)
                 } else {
-                    None
+                    (None, None)
                 };
 
                 if let Some(link) = preprocess_link(MarkdownLink {
                     kind: link_type,
                     link: dest.into_string(),
                     display_text,
+                    display_text_range,
                     range,
                 }) {
                     links.push(link);
@@ -1844,26 +3988,31 @@ pub(crate) fn markdown_links<'md, R>(
     links
 }
 
-/// Collects additional data of link.
+/// Collects additional data of link: its display text and the range of the display text.
 fn collect_link_data<'input, 'callback>(
     event_iter: &mut OffsetIter<'input, 'callback>,
-) -> Option<String> {
+) -> (Option<String>, Option<Range<usize>>) {
     let mut display_text: Option<String> = None;
-    let mut append_text = |text: CowStr<'_>| {
+    let mut display_text_range: Option<Range<usize>> = None;
+    let mut append_text = |text: CowStr<'_>, span: Range<usize>| {
         if let Some(display_text) = &mut display_text {
             display_text.push_str(&text);
         } else {
             display_text = Some(text.to_string());
         }
+        match &mut display_text_range {
+            Some(range) => range.end = span.end,
+            None => display_text_range = Some(span),
+        }
     };
 
-    while let Some((event, _span)) = event_iter.next() {
+    while let Some((event, span)) = event_iter.next() {
         match event {
             Event::Text(text) => {
-                append_text(text);
+                append_text(text, span);
             }
             Event::Code(code) => {
-                append_text(code);
+                append_text(code, span);
             }
             Event::End(_) => {
                 break;
@@ -1872,7 +4021,90 @@ fn collect_link_data<'input, 'callback>(
         }
     }
 
-    display_text
+    (display_text, display_text_range)
+}
+
+/// The outline of some documentation, used by `--show-coverage-details` to tell whether the
+/// docs of an item are complete.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct DocOutline {
+    /// The text of the headings, in the order they appear.
+    pub(crate) headings: Vec<String>,
+    /// The number of characters of the summary, the first paragraph of the docs as plain text.
+    pub(crate) summary_len: usize,
+}
+
+impl DocOutline {
+    /// Returns `true` if one of the headings is `name`, like `Errors`, ignoring the case.
+    pub(crate) fn has_section(&self, name: &str) -> bool {
+        self.headings.iter().any(|heading| heading.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Returns the headings of the markdown, and the length of its summary.
+pub(crate) fn doc_outline(md: &str) -> DocOutline {
+    let mut headings = vec![];
+    let mut heading: Option<String> = None;
+    for event in Parser::new_ext(md, main_body_opts()) {
+        match event {
+            Event::Start(Tag::Heading(..)) => heading = Some(String::new()),
+            Event::End(Tag::Heading(..)) => {
+                headings.extend(heading.take().map(|heading| heading.trim().to_owned()))
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some(heading) = &mut heading {
+                    heading.push_str(&text);
+                }
+            }
+            _ => {}
+        }
+    }
+    let summary_len = plain_text_summary(md, &[], false).chars().count();
+    DocOutline { headings, summary_len }
+}
+
+/// A link to a section of a page of the standard library docs on `doc.rust-lang.org`, like
+/// `https://doc.rust-lang.org/std/string/struct.String.html#method.push_str`.
+#[derive(Debug)]
+pub(crate) struct StdDocAnchorLink {
+    /// The path of the page from the root of the docs, like `std/string/struct.String.html`.
+    pub(crate) page: String,
+    /// The ID of the section, like `method.push_str`.
+    pub(crate) anchor: String,
+    pub(crate) range: MarkdownLinkRange,
+}
+
+/// Returns the links of the markdown to a section of a page of the standard library docs, see
+/// [`std_doc_anchor`].
+pub(crate) fn std_doc_anchor_links(md: &str) -> Vec<StdDocAnchorLink> {
+    markdown_links(md, |link| {
+        let (page, anchor) = std_doc_anchor(&link.link)?;
+        Some(StdDocAnchorLink {
+            page: page.to_owned(),
+            anchor: anchor.to_owned(),
+            range: link.range,
+        })
+    })
+}
+
+/// Splits a URL to a section of a page of the docs of the standard library crates on
+/// `doc.rust-lang.org` into the path of the page and the ID of the section. The URL can name a
+/// channel, like `https://doc.rust-lang.org/nightly/std/`, but URLs naming a version, like
+/// `https://doc.rust-lang.org/1.70.0/std/`, are ignored: the pages they point to don't change.
+fn std_doc_anchor(url: &str) -> Option<(&str, &str)> {
+    let (path, anchor) = url.strip_prefix("https://doc.rust-lang.org/")?.split_once('#')?;
+    let path = ["stable/", "beta/", "nightly/"]
+        .iter()
+        .find_map(|channel| path.strip_prefix(channel))
+        .unwrap_or(path);
+    let krate = path.split('/').next()?;
+    if !matches!(krate, "std" | "core" | "alloc" | "proc_macro" | "test")
+        || !path.ends_with(".html")
+        || anchor.is_empty()
+    {
+        return None;
+    }
+    Some((path, anchor))
 }
 
 #[derive(Debug)]
@@ -1980,6 +4212,57 @@ pub(crate) fn rust_code_blocks(
 #[derive(Clone, Default, Debug)]
 pub struct IdMap {
     map: FxHashMap<Cow<'static, str>, usize>,
+    slug_style: SlugStyle,
+    /// The problems found with the headings, when they are being collected (see
+    /// [`IdMap::collect_heading_diagnostics`]).
+    heading_diagnostics: Option<HeadingDiagnostics>,
+    /// The footnotes defined by the docs rendered so far, by label. The first definition of a
+    /// label is used by the references to it from the docs which don't define it.
+    footnotes: FxHashMap<String, PageFootnote>,
+}
+
+/// A footnote of the page, see [`IdMap::footnotes`].
+#[derive(Clone, Debug)]
+struct PageFootnote {
+    id: String,
+    /// The number shown in the references to the footnote.
+    number: u16,
+}
+
+/// The problems found with the headings of the Markdown being rendered, which are reported by
+/// lints.
+#[derive(Clone, Default, Debug)]
+pub(crate) struct HeadingDiagnostics {
+    /// The headings which were given another ID than the one derived from their text.
+    pub(crate) id_collisions: Vec<HeadingIdCollision>,
+    /// The headings which are rendered at the same level as their parent heading.
+    pub(crate) collapsed_headings: Vec<CollapsedHeading>,
+    /// The levels, once offset, of the headings rendered as `<h6>`, to only report the headings
+    /// deeper than `<h6>` which are collapsed onto a heading of a lower level.
+    h6_levels: FxHashSet<u32>,
+    /// The IDs given to the headings of the Markdown being rendered, to tell the collisions
+    /// between its own headings from the ones with the docs of the other items of the page.
+    heading_ids: FxHashSet<String>,
+}
+
+/// A heading whose ID was already in use on the page, so it was given another one.
+#[derive(Clone, Debug)]
+pub(crate) struct HeadingIdCollision {
+    /// The range of the heading in the Markdown source.
+    pub(crate) range: Range<usize>,
+    /// The ID derived from the text of the heading.
+    pub(crate) original_id: String,
+    /// The ID given to the heading instead, like `examples-1`.
+    pub(crate) id: String,
+}
+
+/// A heading which would be deeper than `<h6>` once offset, so it was rendered as `<h6>`.
+#[derive(Clone, Debug)]
+pub(crate) struct CollapsedHeading {
+    /// The range of the heading in the Markdown source.
+    pub(crate) range: Range<usize>,
+    /// The level the heading would have without the clamping, like `7`.
+    pub(crate) level: u32,
 }
 
 // The map is pre-initialized and cloned each time to avoid reinitializing it repeatedly.
@@ -2038,7 +4321,20 @@ fn init_id_map() -> FxHashMap<Cow<'static, str>, usize> {
 
 impl IdMap {
     pub fn new() -> Self {
-        IdMap { map: DEFAULT_ID_MAP.clone() }
+        Self::with_slug_style(SlugStyle::default())
+    }
+
+    pub fn with_slug_style(slug_style: SlugStyle) -> Self {
+        IdMap {
+            map: DEFAULT_ID_MAP.clone(),
+            slug_style,
+            heading_diagnostics: None,
+            footnotes: FxHashMap::default(),
+        }
+    }
+
+    pub(crate) fn slug_style(&self) -> SlugStyle {
+        self.slug_style
     }
 
     pub(crate) fn derive<S: AsRef<str> + ToString>(&mut self, candidate: S) -> String {
@@ -2054,4 +4350,53 @@ pub(crate) fn derive<S: AsRef<str> + ToString>(&mut self, candidate: S) -> Strin
         self.map.insert(id.clone().into(), 1);
         id
     }
+
+    /// Like [`IdMap::derive`], for the heading at `range` in the Markdown being rendered. If
+    /// diagnostics are being collected, the heading is recorded when its ID is changed because of
+    /// another heading of the same Markdown or of an ID reserved by rustdoc. The docs of the other
+    /// items of the page, like the `# Examples` section of every method, don't count.
+    fn derive_heading(&mut self, candidate: String, range: Range<usize>) -> String {
+        let id = self.derive(&candidate);
+        if let Some(diagnostics) = &mut self.heading_diagnostics {
+            if id != candidate
+                && (diagnostics.heading_ids.contains(&candidate)
+                    || DEFAULT_ID_MAP.contains_key(candidate.as_str()))
+            {
+                let collision =
+                    HeadingIdCollision { range, original_id: candidate.clone(), id: id.clone() };
+                diagnostics.id_collisions.push(collision);
+            }
+            diagnostics.heading_ids.insert(candidate);
+            diagnostics.heading_ids.insert(id.clone());
+        }
+        id
+    }
+
+    /// Records that the heading at `range` is rendered as `<h6>` while its level is `level`, if
+    /// diagnostics are being collected.
+    fn record_h6_heading(&mut self, range: Range<usize>, level: u32) {
+        if let Some(diagnostics) = &mut self.heading_diagnostics {
+            diagnostics.h6_levels.insert(level);
+            if level > MAX_HEADER_LEVEL {
+                diagnostics.collapsed_headings.push(CollapsedHeading { range, level });
+            }
+        }
+    }
+
+    /// Starts recording the problems found with the headings, like IDs colliding with an ID
+    /// already in use, until [`IdMap::take_heading_diagnostics`] is called.
+    pub(crate) fn collect_heading_diagnostics(&mut self) {
+        self.heading_diagnostics = Some(HeadingDiagnostics::default());
+    }
+
+    /// Stops recording the problems found with the headings and returns the ones recorded since
+    /// [`IdMap::collect_heading_diagnostics`] was called.
+    pub(crate) fn take_heading_diagnostics(&mut self) -> HeadingDiagnostics {
+        let mut diagnostics = self.heading_diagnostics.take().unwrap_or_default();
+        // A heading clamped to `<h6>` is only rendered at the same level as its parent heading if
+        // a heading of a lower level was rendered as `<h6>` too.
+        let min_h6_level = diagnostics.h6_levels.iter().copied().min();
+        diagnostics.collapsed_headings.retain(|heading| Some(heading.level) > min_h6_level);
+        diagnostics
+    }
 }
@@ -1,7 +1,13 @@
-use super::{find_testable_code, plain_text_summary, short_markdown_summary};
+use super::replace_emoji_shortcodes;
+use super::short_markdown_summary;
+use super::{autolink_item_path, doc_outline, find_testable_code};
+use super::{plain_text_summary, DocOutline};
+use super::{short_markdown_summary_spans, SummarySpan, SummarySpanStyle};
+use super::{summarize, SummaryCode, SummaryFormat, SummaryLinks, SummaryOptions};
 use super::{
-    ErrorCodes, HeadingOffset, IdMap, Ignore, LangString, LangStringToken, Markdown,
-    MarkdownItemInfo, TagIterator,
+    DocTestSpanMap, ErrorCodes, HeadingOffset, IdMap, Ignore, IncludedMarkdown, LangString,
+    LangStringToken, Markdown, MarkdownItemInfo, MarkdownSummaryLine, MarkdownWithToc, SlugStyle,
+    TagIterator,
 };
 use rustc_span::edition::{Edition, DEFAULT_EDITION};
 
@@ -262,6 +268,37 @@ fn t(lg: LangString) {
     });
     // error
     t(LangString { original: r#"{class=f"irst"}"#.into(), rust: true, ..Default::default() });
+    t(LangString {
+        original: "{hl_lines=3-5}".into(),
+        highlighted_lines: vec![3..=5],
+        rust: true,
+        ..Default::default()
+    });
+    t(LangString {
+        original: r#"rust,{hl_lines="1, 4-6",hl_lines=8}"#.into(),
+        highlighted_lines: vec![1..=1, 4..=6, 8..=8],
+        rust: true,
+        ..Default::default()
+    });
+    // error
+    t(LangString { original: "{hl_lines=5-3}".into(), rust: true, ..Default::default() });
+    // error
+    t(LangString { original: "{hl_lines=0}".into(), rust: true, ..Default::default() });
+    t(LangString {
+        original: r#"rust,{required-features="std, alloc",required-features=serde}"#.into(),
+        required_features: vec!["std".into(), "alloc".into(), "serde".into()],
+        rust: true,
+        ..Default::default()
+    });
+    t(LangString {
+        original: "{channel=nightly mode=release}".into(),
+        playground_channel: Some("nightly".into()),
+        playground_mode: Some("release".into()),
+        rust: true,
+        ..Default::default()
+    });
+    // error
+    t(LangString { original: "{channel=unstable}".into(), rust: true, ..Default::default() });
 }
 
 #[test]
@@ -306,6 +343,14 @@ fn t(input: &str, expect: &str) {
             playground: &None,
             heading_offset: HeadingOffset::H2,
             custom_code_classes_in_docs: true,
+            enable_per_target_ignores: false,
+            code_block_id_prefix: None,
+            math: false,
+            expandable_hidden_lines: false,
+            smart_link_titles: false,
+            nest_deep_headings: false,
+            smart_punctuation: true,
+            doc_assets: None,
         }
         .into_string();
         assert_eq!(output, expect, "original: {}", input);
@@ -333,6 +378,104 @@ fn t(input: &str, expect: &str) {
     );
 }
 
+#[test]
+fn test_header_github_slugs() {
+    fn t(input: &str, expect: &str) {
+        let mut map = IdMap::with_slug_style(SlugStyle::GitHub);
+        let output = Markdown {
+            content: input,
+            links: &[],
+            ids: &mut map,
+            error_codes: ErrorCodes::Yes,
+            edition: DEFAULT_EDITION,
+            playground: &None,
+            heading_offset: HeadingOffset::H2,
+            custom_code_classes_in_docs: true,
+            enable_per_target_ignores: false,
+            code_block_id_prefix: None,
+            math: false,
+            expandable_hidden_lines: false,
+            smart_link_titles: false,
+            nest_deep_headings: false,
+            smart_punctuation: true,
+            doc_assets: None,
+        }
+        .into_string();
+        assert_eq!(output, expect, "original: {}", input);
+    }
+
+    t("# Foo bar", "<h2 id=\"foo-bar\"><a href=\"#foo-bar\">Foo bar</a></h2>");
+    t("# Über uns", "<h2 id=\"über-uns\"><a href=\"#über-uns\">Über uns</a></h2>");
+    t(
+        "#### **Foo?** & \\*bar?!*  _`baz`_ ❤ #qux",
+        "<h5 id=\"foo--bar--baz--qux\">\
+             <a href=\"#foo--bar--baz--qux\"><strong>Foo?</strong> &amp; *bar?!*  \
+             <em><code>baz</code></em> ❤ #qux</a>\
+         </h5>",
+    );
+}
+
+#[test]
+fn test_deep_headers() {
+    fn t(input: &str, nest_deep_headings: bool, expect: &str, collapsed: &[u32]) {
+        let mut map = IdMap::new();
+        map.collect_heading_diagnostics();
+        let output = Markdown {
+            content: input,
+            links: &[],
+            ids: &mut map,
+            error_codes: ErrorCodes::Yes,
+            edition: DEFAULT_EDITION,
+            playground: &None,
+            heading_offset: HeadingOffset::H5,
+            custom_code_classes_in_docs: true,
+            enable_per_target_ignores: false,
+            code_block_id_prefix: None,
+            math: false,
+            expandable_hidden_lines: false,
+            smart_link_titles: false,
+            nest_deep_headings,
+            smart_punctuation: true,
+            doc_assets: None,
+        }
+        .into_string();
+        assert_eq!(output, expect, "original: {}", input);
+        let levels: Vec<_> =
+            map.take_heading_diagnostics().collapsed_headings.iter().map(|h| h.level).collect();
+        assert_eq!(levels, collapsed, "original: {}", input);
+    }
+
+    t("## Foo", false, "<h6 id=\"foo\"><a href=\"#foo\">Foo</a></h6>", &[]);
+    t("### Foo", false, "<h6 id=\"foo\"><a href=\"#foo\">Foo</a></h6>", &[]);
+    t(
+        "### Foo\n\n### Bar",
+        false,
+        "<h6 id=\"foo\"><a href=\"#foo\">Foo</a></h6>\
+         <h6 id=\"bar\"><a href=\"#bar\">Bar</a></h6>",
+        &[],
+    );
+    t(
+        "### Foo\n\n#### Bar",
+        false,
+        "<h6 id=\"foo\"><a href=\"#foo\">Foo</a></h6>\
+         <h6 id=\"bar\"><a href=\"#bar\">Bar</a></h6>",
+        &[8],
+    );
+    t(
+        "## Foo\n\n#### Bar",
+        false,
+        "<h6 id=\"foo\"><a href=\"#foo\">Foo</a></h6>\
+         <h6 id=\"bar\"><a href=\"#bar\">Bar</a></h6>",
+        &[8],
+    );
+    t(
+        "### Foo",
+        true,
+        "<div role=\"heading\" aria-level=\"7\" id=\"foo\"><a href=\"#foo\">Foo</a></div>",
+        &[],
+    );
+}
+
 #[test]
 fn test_header_ids_multiple_blocks() {
     let mut map = IdMap::new();
@@ -346,6 +489,14 @@ fn t(map: &mut IdMap, input: &str, expect: &str) {
             playground: &None,
             heading_offset: HeadingOffset::H2,
             custom_code_classes_in_docs: true,
+            enable_per_target_ignores: false,
+            code_block_id_prefix: None,
+            math: false,
+            expandable_hidden_lines: false,
+            smart_link_titles: false,
+            nest_deep_headings: false,
+            smart_punctuation: true,
+            doc_assets: None,
         }
         .into_string();
         assert_eq!(output, expect, "original: {}", input);
@@ -359,10 +510,78 @@ fn t(map: &mut IdMap, input: &str, expect: &str) {
     t(&mut map, "# Panics", "<h2 id=\"panics-1\"><a href=\"#panics-1\">Panics</a></h2>");
 }
 
+#[test]
+fn test_markdown_with_toc() {
+    fn t(input: &str, heading_offset: HeadingOffset, number_headings: bool, expect: &[&str]) {
+        let mut map = IdMap::new();
+        let output = MarkdownWithToc {
+            content: input,
+            ids: &mut map,
+            error_codes: ErrorCodes::Yes,
+            edition: DEFAULT_EDITION,
+            playground: &None,
+            custom_code_classes_in_docs: true,
+            enable_per_target_ignores: false,
+            math: false,
+            expandable_hidden_lines: false,
+            smart_link_titles: false,
+            nest_deep_headings: false,
+            heading_offset,
+            number_headings,
+            smart_punctuation: true,
+        }
+        .into_string();
+        for expect in expect {
+            assert!(output.contains(expect), "original: {}\noutput: {}", input, output);
+        }
+    }
+
+    let input = "# Foo\n## Bar\n## Baz\n# Qux";
+    t(
+        input,
+        HeadingOffset::H1,
+        true,
+        &[
+            "<li><a href=\"#foo\">1 Foo</a>",
+            "<li><a href=\"#bar\">1.1 Bar</a>",
+            "<li><a href=\"#baz\">1.2 Baz</a>",
+            "<li><a href=\"#qux\">2 Qux</a>",
+            "<h1 id=\"foo\"><a href=\"#foo\">1 Foo</a></h1>",
+            "<h2 id=\"baz\"><a href=\"#baz\">1.2 Baz</a></h2>",
+        ],
+    );
+    t(
+        input,
+        HeadingOffset::H1,
+        false,
+        &["<li><a href=\"#bar\">Bar</a>", "<h2 id=\"bar\"><a href=\"#bar\">Bar</a></h2>"],
+    );
+    // The numbering follows the Markdown levels, not the rendered ones.
+    t(
+        input,
+        HeadingOffset::H3,
+        true,
+        &[
+            "<h3 id=\"foo\"><a href=\"#foo\">1 Foo</a></h3>",
+            "<h4 id=\"bar\"><a href=\"#bar\">1.1 Bar</a></h4>",
+            "<li><a href=\"#qux\">2 Qux</a>",
+        ],
+    );
+    t(
+        "##### Foo\n###### Bar",
+        HeadingOffset::H3,
+        true,
+        &[
+            "<h6 id=\"foo\"><a href=\"#foo\">0.0.0.0.1 Foo</a></h6>",
+            "<h6 id=\"bar\"><a href=\"#bar\">0.0.0.0.1.1 Bar</a></h6>",
+        ],
+    );
+}
+
 #[test]
 fn test_short_markdown_summary() {
     fn t(input: &str, expect: &str) {
-        let output = short_markdown_summary(input, &[][..]);
+        let output = short_markdown_summary(input, &[][..], true);
         assert_eq!(output, expect, "original: {}", input);
     }
 
@@ -391,6 +610,8 @@ fn t(input: &str, expect: &str) {
     t("# top header\n\nfollowed by a paragraph", "top header");
     t("## header", "header");
     t("first paragraph\n\nsecond paragraph", "first paragraph");
+    t("| Name | *Value* |\n|------|-------|\n| a | b |", "Name | <em>Value</em>");
+    t("first paragraph\n\n| a | b |\n|---|---|", "first paragraph");
     t("```\nfn main() {}\n```", "");
     t("<div>hello</div>", "");
     t(
@@ -402,7 +623,7 @@ fn t(input: &str, expect: &str) {
 #[test]
 fn test_plain_text_summary() {
     fn t(input: &str, expect: &str) {
-        let output = plain_text_summary(input, &[]);
+        let output = plain_text_summary(input, &[], true);
         assert_eq!(output, expect, "original: {}", input);
     }
 
@@ -428,11 +649,116 @@ fn t(input: &str, expect: &str) {
     );
 }
 
+#[test]
+fn test_summarize() {
+    fn t(input: &str, options: SummaryOptions<'_>, expect: &str) {
+        let output = summarize(input, &options);
+        assert_eq!(output, expect, "original: {}", input);
+    }
+
+    let anchors = SummaryOptions { links: SummaryLinks::Anchors, ..Default::default() };
+    t(
+        "hello [Rust](https://www.rust-lang.org) :)",
+        anchors,
+        "hello <a href=\"https://www.rust-lang.org\">Rust</a> :)",
+    );
+    t(
+        "[a & b](https://example.com/?a&b)",
+        anchors,
+        "<a href=\"https://example.com/?a&amp;b\">a &amp; b</a>",
+    );
+    t("[](https://www.rust-lang.org) empty", anchors, " empty");
+    t(
+        "hello [Rust](https://www.rust-lang.org)",
+        SummaryOptions { format: SummaryFormat::PlainText, ..anchors },
+        "hello Rust",
+    );
+
+    let code_text = SummaryOptions { code: SummaryCode::Text, ..Default::default() };
+    t("code `Vec<T>`", code_text, "code Vec&lt;T&gt;");
+    t(
+        "code `Vec<T>`",
+        SummaryOptions { format: SummaryFormat::PlainText, ..code_text },
+        "code Vec<T>",
+    );
+
+    t(
+        "a long paragraph with <b>lots</b> of words",
+        SummaryOptions {
+            format: SummaryFormat::PlainText,
+            length_limit: Some(14),
+            ..Default::default()
+        },
+        "a long …",
+    );
+    t(
+        "*very* long",
+        SummaryOptions { length_limit: Some(4), ..Default::default() },
+        "<em>very</em>…",
+    );
+
+    let plain_text = SummaryOptions { format: SummaryFormat::PlainText, ..Default::default() };
+    t("run with --verbose for \"all\"", plain_text, "run with –verbose for “all”");
+    t(
+        "run with --verbose for \"all\"",
+        SummaryOptions { smart_punctuation: false, ..plain_text },
+        "run with --verbose for \"all\"",
+    );
+}
+
+#[test]
+fn test_short_markdown_summary_spans() {
+    fn t(input: &str, expect: &[(&str, SummarySpanStyle, Option<&str>)]) {
+        let output = short_markdown_summary_spans(input, &[][..], true);
+        let expect: Vec<_> = expect
+            .iter()
+            .map(|&(text, style, target)| SummarySpan {
+                text: text.to_owned(),
+                style,
+                target: target.map(str::to_owned),
+            })
+            .collect();
+        assert_eq!(output, expect, "original: {}", input);
+    }
+
+    use SummarySpanStyle::{Code, Text};
+    t("", &[]);
+    t("*italic* and **bold**", &[("italic and bold", Text, None)]);
+    t(
+        "Returns a [`Vec`](https://doc.rust-lang.org/std/vec/struct.Vec.html) of `u8`.",
+        &[
+            ("Returns a ", Text, None),
+            ("Vec", Code, Some("https://doc.rust-lang.org/std/vec/struct.Vec.html")),
+            (" of ", Text, None),
+            ("u8", Code, None),
+            (".", Text, None),
+        ],
+    );
+    t(
+        "hello [Rust **lang**](https://www.rust-lang.org)\n:)",
+        &[
+            ("hello ", Text, None),
+            ("Rust lang", Text, Some("https://www.rust-lang.org")),
+            (" :)", Text, None),
+        ],
+    );
+    t("# top header\n\nfollowed by a paragraph", &[("top header", Text, None)]);
+    t("| `a` | b |\n|---|---|", &[("a", Code, None), (" | b", Text, None)]);
+    t(
+        "a very long first paragraph. it has lots of words, and [a link](https://example.com) at the end.",
+        &[
+            ("a very long first paragraph. it has lots of words, and ", Text, None),
+            ("a ", Text, Some("https://example.com")),
+            ("…", Text, None),
+        ],
+    );
+}
+
 #[test]
 fn test_markdown_html_escape() {
     fn t(input: &str, expect: &str) {
         let mut idmap = IdMap::new();
-        let output = MarkdownItemInfo(input, &mut idmap).into_string();
+        let output = MarkdownItemInfo(input, &mut idmap, true).into_string();
         assert_eq!(output, expect, "original: {}", input);
     }
 
@@ -461,6 +787,19 @@ fn add_test(&mut self, _test: String, _config: LangString, line: usize) {
     t("\n ```rust\n```", &[2]);
     t("```rust\n```\n```rust\n```", &[1, 3]);
     t("```rust\n```\n ```rust\n```", &[1, 3]);
+    t("text\r\n\r\n```rust\r\n```\r\n```rust\r\n```", &[3, 5]);
+    t("text\r\r```rust\r```", &[3]);
+    t("- item\n\n  ```rust\n  let x = 1;\n  ```\n\n> quote\n>\n> ```rust\n> ```", &[3, 9]);
+}
+
+#[test]
+fn test_doc_test_span_map() {
+    let doc = "a\nb\r\nc\rd";
+    let map = DocTestSpanMap::new(doc, 10);
+    let lines = (0..=doc.len()).map(|offset| map.doc_line(offset)).collect::<Vec<_>>();
+    assert_eq!(lines, [0, 0, 1, 1, 1, 2, 2, 3, 3]);
+    assert_eq!(map.file_line(0), 11);
+    assert_eq!(map.file_line(doc.len()), 14);
 }
 
 #[test]
@@ -476,6 +815,14 @@ fn t(input: &str, expect: &str) {
             playground: &None,
             heading_offset: HeadingOffset::H2,
             custom_code_classes_in_docs: true,
+            enable_per_target_ignores: false,
+            code_block_id_prefix: None,
+            math: false,
+            expandable_hidden_lines: false,
+            smart_link_titles: false,
+            nest_deep_headings: false,
+            smart_punctuation: true,
+            doc_assets: None,
         }
         .into_string();
         assert_eq!(output, expect, "original: {}", input);
@@ -508,3 +855,775 @@ fn t(input: &str, expect: &str) {
 </code></pre></div>",
     );
 }
+
+#[test]
+fn test_footnote_references() {
+    let mut map = IdMap::new();
+    let output = Markdown {
+        content: "a[^1] b[^1] c[^2]\n\n[^1]: one\n[^2]: two",
+        links: &[],
+        ids: &mut map,
+        error_codes: ErrorCodes::Yes,
+        edition: DEFAULT_EDITION,
+        playground: &None,
+        heading_offset: HeadingOffset::H2,
+        custom_code_classes_in_docs: true,
+        enable_per_target_ignores: false,
+        code_block_id_prefix: None,
+        math: false,
+        expandable_hidden_lines: false,
+        smart_link_titles: false,
+        nest_deep_headings: false,
+        smart_punctuation: true,
+        doc_assets: None,
+    }
+    .into_string();
+    for expect in [
+        "a<sup id=\"fnref-1\"><a href=\"#fn-1\">1</a></sup>",
+        "b<sup id=\"fnref-1-2\"><a href=\"#fn-1\">1</a></sup>",
+        "c<sup id=\"fnref-2\"><a href=\"#fn-2\">2</a></sup>",
+        "<li id=\"fn-1\"><p>one&nbsp;<a href=\"#fnref-1\">↩</a> \
+         <a href=\"#fnref-1-2\" aria-label=\"Back to reference 2\">↩<sup>2</sup></a></p></li>",
+        "<li id=\"fn-2\"><p>two&nbsp;<a href=\"#fnref-2\">↩</a></p></li>",
+    ] {
+        assert!(output.contains(expect), "expected: {}\noutput: {}", expect, output);
+    }
+}
+
+#[test]
+fn test_footnote_ids() {
+    let mut map = IdMap::new();
+    fn t(map: &mut IdMap, input: &str, expect: &[&str]) {
+        let output = Markdown {
+            content: input,
+            links: &[],
+            ids: map,
+            error_codes: ErrorCodes::Yes,
+            edition: DEFAULT_EDITION,
+            playground: &None,
+            heading_offset: HeadingOffset::H2,
+            custom_code_classes_in_docs: true,
+            enable_per_target_ignores: false,
+            code_block_id_prefix: None,
+            math: false,
+            expandable_hidden_lines: false,
+            smart_link_titles: false,
+            nest_deep_headings: false,
+            smart_punctuation: true,
+            doc_assets: None,
+        }
+        .into_string();
+        for expect in expect {
+            assert!(output.contains(expect), "expected: {}\noutput: {}", expect, output);
+        }
+    }
+
+    // Footnotes are numbered in the order they are referenced, and can be linked to by label.
+    t(
+        &mut map,
+        "a[^Second Note] b[^first] [see](#fn-first)\n\n[^first]: one\n[^Second Note]: two\n\
+         [^unused]: three",
+        &[
+            "a<sup id=\"fnref-second-note\"><a href=\"#fn-second-note\">1</a></sup>",
+            "b<sup id=\"fnref-first\"><a href=\"#fn-first\">2</a></sup>",
+            "<a href=\"#fn-first\">see</a>",
+            "<li id=\"fn-second-note\"><p>two",
+            "<li id=\"fn-unused\"><p>three</p></li>",
+        ],
+    );
+    // Labels reused by other docs of the page get other IDs, and footnotes which aren't defined
+    // link to the ones defined before on the page.
+    t(
+        &mut map,
+        "c[^first] d[^unused]\n\n[^first]: four",
+        &[
+            "c<sup id=\"fnref-first-1\"><a href=\"#fn-first-1\">1</a></sup>",
+            "d<sup><a href=\"#fn-unused\">3</a></sup>",
+            "<li id=\"fn-first-1\"><p>four",
+        ],
+    );
+}
+
+#[test]
+fn test_admonitions() {
+    fn t(input: &str, expect: &[&str], not_expect: &[&str]) {
+        let mut map = IdMap::new();
+        let output = Markdown {
+            content: input,
+            links: &[],
+            ids: &mut map,
+            error_codes: ErrorCodes::Yes,
+            edition: DEFAULT_EDITION,
+            playground: &None,
+            heading_offset: HeadingOffset::H2,
+            custom_code_classes_in_docs: true,
+            enable_per_target_ignores: false,
+            code_block_id_prefix: None,
+            math: false,
+            expandable_hidden_lines: false,
+            smart_link_titles: false,
+            nest_deep_headings: false,
+            smart_punctuation: true,
+            doc_assets: None,
+        }
+        .into_string();
+        for expect in expect {
+            assert!(output.contains(expect), "original: {}\noutput: {}", input, output);
+        }
+        for not_expect in not_expect {
+            assert!(!output.contains(not_expect), "original: {}\noutput: {}", input, output);
+        }
+    }
+
+    t(
+        "> [!NOTE]\n> Useful information.",
+        &[
+            "<div class=\"admonition note\"><h2 id=\"note\"><a href=\"#note\">Note</a></h2>",
+            "<p>Useful information.</p>\n</div>",
+        ],
+        &["blockquote", "[!NOTE]"],
+    );
+    // The title is a level below the current section, and gets a unique ID.
+    t(
+        "# Safety\n\n> [!warning]\n>\n> Don't.\n\n> [!Warning]\n> Really.",
+        &[
+            "<div class=\"admonition warning\"><h3 id=\"warning\">",
+            "<div class=\"admonition warning\"><h3 id=\"warning-1\">",
+            "<p>Don’t.</p>\n</div>",
+        ],
+        &["blockquote"],
+    );
+    // Other block quotes are left untouched.
+    t("> [!FOO]\n> text", &["<blockquote>"], &["admonition"]);
+    t("> [!NOTE] text", &["<blockquote>"], &["admonition"]);
+    t("> text\n> [!NOTE]", &["<blockquote>"], &["admonition"]);
+}
+
+#[test]
+fn test_tables() {
+    fn t(input: &str, expect: &[&str], not_expect: &[&str]) {
+        let mut map = IdMap::new();
+        let output = Markdown {
+            content: input,
+            links: &[],
+            ids: &mut map,
+            error_codes: ErrorCodes::Yes,
+            edition: DEFAULT_EDITION,
+            playground: &None,
+            heading_offset: HeadingOffset::H2,
+            custom_code_classes_in_docs: true,
+            enable_per_target_ignores: false,
+            code_block_id_prefix: None,
+            math: false,
+            expandable_hidden_lines: false,
+            smart_link_titles: false,
+            nest_deep_headings: false,
+            smart_punctuation: true,
+            doc_assets: None,
+        }
+        .into_string();
+        for expect in expect {
+            assert!(output.contains(expect), "original: {}\noutput: {}", input, output);
+        }
+        for not_expect in not_expect {
+            assert!(!output.contains(not_expect), "original: {}\noutput: {}", input, output);
+        }
+    }
+
+    t(
+        "| a | b | c |\n|:--|:-:|---|\n| 1 | 2 | 3 |\n\nTable: Some *numbers*",
+        &[
+            "<div><table><caption>Some <em>numbers</em></caption><thead>",
+            "<th class=\"align-left\">a</th><th class=\"align-center\">b</th><th>c</th>",
+            "<td class=\"align-left\">1</td><td class=\"align-center\">2</td><td>3</td>",
+            "</table>\n</div>",
+        ],
+        &["Table:", "style="],
+    );
+    t("| a |\n|--:|\n| 1 |\n\nNot a caption.", &["<p>Not a caption.</p>"], &["<caption>"]);
+    t("| a |\n|---|\n| 1 |", &["<table><thead>", "</table>\n</div>"], &["<caption>"]);
+    t(
+        "| a |\n|---|\n| 1 |\n\n| b |\n|--:|\n| 2 |\n\nTable: The second one",
+        &[
+            "</table>\n</div><div><table><caption>The second one</caption><thead>",
+            "<th class=\"align-right\">b</th>",
+            "<td class=\"align-right\">2</td>",
+        ],
+        &["Table:"],
+    );
+}
+
+#[test]
+fn test_task_lists() {
+    fn t(input: &str, expect: &[&str]) {
+        let mut map = IdMap::new();
+        let output = Markdown {
+            content: input,
+            links: &[],
+            ids: &mut map,
+            error_codes: ErrorCodes::Yes,
+            edition: DEFAULT_EDITION,
+            playground: &None,
+            heading_offset: HeadingOffset::H2,
+            custom_code_classes_in_docs: true,
+            enable_per_target_ignores: false,
+            code_block_id_prefix: None,
+            math: false,
+            expandable_hidden_lines: false,
+            smart_link_titles: false,
+            nest_deep_headings: false,
+            smart_punctuation: true,
+            doc_assets: None,
+        }
+        .into_string();
+        for expect in expect {
+            assert!(output.contains(expect), "original: {}\noutput: {}", input, output);
+        }
+    }
+
+    t(
+        "- [ ] todo\n- [x] done\n- plain",
+        &[
+            "<li class=\"task-list-item\">\
+             <input type=\"checkbox\" class=\"task-list-checkbox\" disabled \
+             aria-checked=\"false\">todo</li>",
+            "<li class=\"task-list-item\">\
+             <input type=\"checkbox\" class=\"task-list-checkbox\" disabled checked \
+             aria-checked=\"true\">done</li>",
+            "<li>plain</li>",
+        ],
+    );
+    t(
+        "- [x] done\n\n- [ ] todo",
+        &["<li class=\"task-list-item\">", "disabled checked aria-checked=\"true\">", "done</p>"],
+    );
+
+    // The summary keeps the text of the items, without the checkboxes.
+    let (summary, _) =
+        MarkdownSummaryLine("- [x] done", &[], true).into_string_with_has_more_content();
+    assert_eq!(summary, "done");
+}
+
+#[test]
+fn test_definition_lists() {
+    fn t(input: &str, expect: &str) {
+        let mut map = IdMap::new();
+        let output = Markdown {
+            content: input,
+            links: &[],
+            ids: &mut map,
+            error_codes: ErrorCodes::Yes,
+            edition: DEFAULT_EDITION,
+            playground: &None,
+            heading_offset: HeadingOffset::H2,
+            custom_code_classes_in_docs: true,
+            enable_per_target_ignores: false,
+            code_block_id_prefix: None,
+            math: false,
+            expandable_hidden_lines: false,
+            smart_link_titles: false,
+            nest_deep_headings: false,
+            smart_punctuation: true,
+            doc_assets: None,
+        }
+        .into_string();
+        assert_eq!(output, expect, "original: {}", input);
+    }
+
+    t(
+        "Borrow checker\n: Checks *references*.\n: Runs on MIR.\n\n`Drop` glue\n: Drops values.",
+        "<dl><dt id=\"borrow-checker\">\
+         <a href=\"#borrow-checker\" class=\"anchor\" title=\"Link to this term\">§</a>\
+         Borrow checker</dt><dd>Checks <em>references</em>.</dd><dd>Runs on MIR.</dd>\
+         <dt id=\"drop-glue\">\
+         <a href=\"#drop-glue\" class=\"anchor\" title=\"Link to this term\">§</a>\
+         <code>Drop</code> glue</dt><dd>Drops values.</dd></dl>",
+    );
+    // Terms with the same text get different IDs.
+    t(
+        "Term\n: One.\n\nTerm\n: Two.",
+        "<dl><dt id=\"term\">\
+         <a href=\"#term\" class=\"anchor\" title=\"Link to this term\">§</a>Term</dt>\
+         <dd>One.</dd><dt id=\"term-1\">\
+         <a href=\"#term-1\" class=\"anchor\" title=\"Link to this term\">§</a>Term</dt>\
+         <dd>Two.</dd></dl>",
+    );
+    t(
+        "Term\n: One.\n\nText",
+        "<dl><dt id=\"term\">\
+         <a href=\"#term\" class=\"anchor\" title=\"Link to this term\">§</a>Term</dt>\
+         <dd>One.</dd></dl>\n<p>Text</p>\n",
+    );
+    // Every term needs a definition.
+    t("One\nTwo\n: Definition.", "<p>One\nTwo\n: Definition.</p>\n");
+    t("Term\n: Definition.\nOther term", "<p>Term\n: Definition.\nOther term</p>\n");
+    t(": Definition.", "<p>: Definition.</p>\n");
+    t("Time\n:12:00", "<p>Time\n:12:00</p>\n");
+}
+
+#[test]
+fn test_collapsible_sections() {
+    fn t(input: &str, expect: &[&str], not_expect: &[&str]) {
+        let mut map = IdMap::new();
+        let output = Markdown {
+            content: input,
+            links: &[],
+            ids: &mut map,
+            error_codes: ErrorCodes::Yes,
+            edition: DEFAULT_EDITION,
+            playground: &None,
+            heading_offset: HeadingOffset::H2,
+            custom_code_classes_in_docs: true,
+            enable_per_target_ignores: false,
+            code_block_id_prefix: None,
+            math: false,
+            expandable_hidden_lines: false,
+            smart_link_titles: false,
+            nest_deep_headings: false,
+            smart_punctuation: true,
+            doc_assets: None,
+        }
+        .into_string();
+        for expect in expect {
+            assert!(output.contains(expect), "original: {}\noutput: {}", input, output);
+        }
+        for not_expect in not_expect {
+            assert!(!output.contains(not_expect), "original: {}\noutput: {}", input, output);
+        }
+    }
+
+    t(
+        "> [!details Error codes]\n> * `E1`: bad\n> * `E2`: worse\n\nafter",
+        &[
+            "<details><summary>Error codes</summary>",
+            "<li><code>E2</code>: worse</li>\n</ul>\n</details>",
+        ],
+        &["blockquote", "[!details", "<p>after</p>\n</details>"],
+    );
+    t(
+        "> [!details A & B]\n> First line.\n>\n> Second paragraph.",
+        &[
+            "<details><summary>A &amp; B</summary>",
+            "<p>First line.</p>",
+            "<p>Second paragraph.</p>\n</details>",
+        ],
+        &["blockquote"],
+    );
+    t(
+        "> [!details Outer]\n> > [!details Inner]\n> > text\n>\n> > quote",
+        &[
+            "<summary>Outer</summary>",
+            "<summary>Inner</summary>",
+            "<blockquote>\n<p>quote</p>\n</blockquote>\n</details>",
+        ],
+        &[],
+    );
+    // Regular block quotes are left untouched.
+    t("> [!details]\n> text", &["<blockquote>"], &["<details>"]);
+    t("> [!note Title]\n> text", &["<blockquote>"], &["<details>"]);
+    t("> > nested", &["<blockquote>\n<blockquote>"], &["<details>"]);
+}
+
+#[test]
+fn test_mermaid_code_block() {
+    fn t(input: &str, expect: &str) {
+        let mut map = IdMap::new();
+        let output = Markdown {
+            content: input,
+            links: &[],
+            ids: &mut map,
+            error_codes: ErrorCodes::Yes,
+            edition: DEFAULT_EDITION,
+            playground: &None,
+            heading_offset: HeadingOffset::H2,
+            custom_code_classes_in_docs: true,
+            enable_per_target_ignores: false,
+            code_block_id_prefix: None,
+            math: false,
+            expandable_hidden_lines: false,
+            smart_link_titles: false,
+            nest_deep_headings: false,
+            smart_punctuation: true,
+            doc_assets: None,
+        }
+        .into_string();
+        assert_eq!(output, expect, "original: {}", input);
+    }
+
+    t(
+        "```mermaid\ngraph LR\n    A --> B\n```",
+        "<div class=\"example-wrap mermaid-wrap\"><pre class=\"mermaid\">graph LR\n    \
+         A --&gt; B\n</pre></div>",
+    );
+    t(
+        "```mermaid,{.wide}\ngraph LR\n```",
+        "<div class=\"example-wrap mermaid-wrap\"><pre class=\"mermaid wide\">graph LR\n\
+         </pre></div>",
+    );
+}
+
+#[test]
+fn test_code_block_anchors() {
+    fn t(map: &mut IdMap, input: &str, expect: &[&str]) {
+        let output = Markdown {
+            content: input,
+            links: &[],
+            ids: map,
+            error_codes: ErrorCodes::Yes,
+            edition: DEFAULT_EDITION,
+            playground: &None,
+            heading_offset: HeadingOffset::H2,
+            custom_code_classes_in_docs: true,
+            enable_per_target_ignores: false,
+            code_block_id_prefix: Some("fn.foo"),
+            math: false,
+            expandable_hidden_lines: false,
+            smart_link_titles: false,
+            nest_deep_headings: false,
+            smart_punctuation: true,
+            doc_assets: None,
+        }
+        .into_string();
+        for expect in expect {
+            assert!(output.contains(expect), "original: {}\noutput: {}", input, output);
+        }
+    }
+
+    let mut map = IdMap::new();
+    t(
+        &mut map,
+        "# Examples\n```\nfoo();\n```\n```text\nbar\n```",
+        &[
+            "<h2 id=\"examples\">",
+            "<div class=\"example-wrap\" data-code=\"foo();\" id=\"fn.foo.example-1\">\
+             <a href=\"#fn.foo.example-1\" class=\"anchor example-anchor\" \
+             title=\"Link to this example\">§</a>",
+            "<div class=\"example-wrap\" id=\"fn.foo.example-2\">\
+             <a href=\"#fn.foo.example-2\" class=\"anchor example-anchor\" \
+             title=\"Link to this example\">§</a><pre class=\"language-text\">",
+        ],
+    );
+    // The same item documented twice on a page doesn't reuse the IDs.
+    t(&mut map, "```\nfoo();\n```", &["id=\"fn.foo.example-1-1\""]);
+}
+
+#[test]
+fn test_highlighted_lines() {
+    fn t(input: &str, expect: &str) {
+        let mut map = IdMap::new();
+        let output = Markdown {
+            content: input,
+            links: &[],
+            ids: &mut map,
+            error_codes: ErrorCodes::Yes,
+            edition: DEFAULT_EDITION,
+            playground: &None,
+            heading_offset: HeadingOffset::H2,
+            custom_code_classes_in_docs: true,
+            enable_per_target_ignores: false,
+            code_block_id_prefix: None,
+            math: false,
+            expandable_hidden_lines: false,
+            smart_link_titles: false,
+            nest_deep_headings: false,
+            smart_punctuation: true,
+            doc_assets: None,
+        }
+        .into_string();
+        assert!(output.contains(expect), "original: {}\noutput: {}", input, output);
+    }
+
+    t("```{hl_lines=2}\na;\nb;\nc;\n```", "a;\n<span class=\"hl\">b;");
+    t("```{hl_lines=1-2}\n# hidden;\na;\nb;\nc;\n```", "<span class=\"hl\">a;\nb;");
+    // Out of range lines are ignored.
+    t("```{hl_lines=\"3-9\"}\na;\nb;\nc;\n```", "b;\n<span class=\"hl\">c;</span>");
+}
+
+#[test]
+fn test_expandable_hidden_lines() {
+    fn t(input: &str, expect: &[&str], not_expect: &[&str]) {
+        let mut map = IdMap::new();
+        let output = Markdown {
+            content: input,
+            links: &[],
+            ids: &mut map,
+            error_codes: ErrorCodes::Yes,
+            edition: DEFAULT_EDITION,
+            playground: &None,
+            heading_offset: HeadingOffset::H2,
+            custom_code_classes_in_docs: true,
+            enable_per_target_ignores: false,
+            code_block_id_prefix: None,
+            math: false,
+            expandable_hidden_lines: true,
+            smart_link_titles: false,
+            nest_deep_headings: false,
+            smart_punctuation: true,
+            doc_assets: None,
+        }
+        .into_string();
+        for expect in expect {
+            assert!(output.contains(expect), "original: {}\noutput: {}", input, output);
+        }
+        for not_expect in not_expect {
+            assert!(!output.contains(not_expect), "original: {}\noutput: {}", input, output);
+        }
+    }
+
+    t(
+        "```\n# x;\na;\n# y;\n#   z;\nb;\n# w;\n```",
+        &[
+            "<span class=\"hidden-line\">x;\n</span>a;",
+            "<span class=\"hidden-line\">y;\n  z;\n</span>b;",
+            "b;<span class=\"hidden-line\">\nw;</span>",
+            "<button class=\"hidden-lines-toggle\"",
+        ],
+        &[],
+    );
+    // The highlighted lines don't count the hidden ones.
+    t(
+        "```{hl_lines=1}\n# x;\na;\nb;\n```",
+        &["<span class=\"hidden-line\">x;\n</span><span class=\"hl\">a;</span>"],
+        &[],
+    );
+    t("```\na;\n## b;\n```", &["# b;"], &["hidden-line", "hidden-lines-toggle"]);
+    t("```text\n# a\n```", &["# a"], &["hidden-line", "hidden-lines-toggle"]);
+}
+
+#[test]
+fn test_code_block_copied_code() {
+    fn t(input: &str, expandable_hidden_lines: bool, expect: &str) {
+        let mut map = IdMap::new();
+        let output = Markdown {
+            content: input,
+            links: &[],
+            ids: &mut map,
+            error_codes: ErrorCodes::Yes,
+            edition: DEFAULT_EDITION,
+            playground: &None,
+            heading_offset: HeadingOffset::H2,
+            custom_code_classes_in_docs: true,
+            enable_per_target_ignores: false,
+            code_block_id_prefix: None,
+            math: false,
+            expandable_hidden_lines,
+            smart_link_titles: false,
+            nest_deep_headings: false,
+            smart_punctuation: true,
+            doc_assets: None,
+        }
+        .into_string();
+        assert!(output.contains(expect), "original: {}\noutput: {}", input, output);
+    }
+
+    // The hidden lines are copied without their `# ` marker, so the example can be run.
+    t(
+        "```\n# x;\nlet a = \"<b>\";\n## y;\n```",
+        false,
+        "data-code=\"x;\nlet a = &quot;&lt;b&gt;&quot;;\n# y;\"",
+    );
+    t("```\n# let x = 1;\na;\n# y;\n```", true, "data-code=\"let x = 1;\na;\ny;\"");
+}
+
+#[test]
+fn test_autolink_item_path() {
+    fn t(url: &str, expect: Option<&str>) {
+        assert_eq!(autolink_item_path(url).as_deref(), expect, "url: {url}");
+    }
+
+    t("https://doc.rust-lang.org/std/vec/struct.Vec.html", Some("std::vec::Vec"));
+    t("https://doc.rust-lang.org/stable/std/vec/struct.Vec.html", Some("std::vec::Vec"));
+    t("https://doc.rust-lang.org/1.70.0/core/option/index.html", Some("core::option"));
+    t("https://doc.rust-lang.org/nightly/alloc/", Some("alloc"));
+    t("https://doc.rust-lang.org/std/vec/struct.Vec.html#method.push", Some("std::vec::Vec::push"));
+    t(
+        "https://doc.rust-lang.org/std/option/enum.Option.html#variant.Some",
+        Some("std::option::Option::Some"),
+    );
+    t("https://doc.rust-lang.org/std/vec/struct.Vec.html#examples", Some("std::vec::Vec"));
+    t("https://doc.rust-lang.org/std/macro.vec.html", Some("std::vec!"));
+    t("https://doc.rust-lang.org/std/primitive.u8.html#method.max", Some("u8::max"));
+    t("https://doc.rust-lang.org/std/keyword.match.html", None);
+    t("https://doc.rust-lang.org/book/ch01-00-getting-started.html", None);
+    t("https://doc.rust-lang.org/stable/", None);
+    t("https://docs.rs/regex/latest/regex/struct.Regex.html", Some("regex::Regex"));
+    t("https://docs.rs/serde-json/1.0.0/serde_json/", Some("serde_json"));
+    t("https://docs.rs/serde-json", Some("serde_json"));
+    t("https://docs.rs/regex/latest/regex/bytes/index.html?search=x", Some("regex::bytes"));
+    t("https://docs.rs/crate/regex/latest", None);
+    t("https://docs.rs/regex/latest/regex/all.html/extra", None);
+    t("https://example.com/std/vec/struct.Vec.html", None);
+}
+
+#[test]
+fn test_smart_link_titles() {
+    fn t(input: &str, smart_link_titles: bool, expect: &str) {
+        let mut map = IdMap::new();
+        let output = Markdown {
+            content: input,
+            links: &[],
+            ids: &mut map,
+            error_codes: ErrorCodes::Yes,
+            edition: DEFAULT_EDITION,
+            playground: &None,
+            heading_offset: HeadingOffset::H2,
+            custom_code_classes_in_docs: true,
+            enable_per_target_ignores: false,
+            code_block_id_prefix: None,
+            math: false,
+            expandable_hidden_lines: false,
+            smart_link_titles,
+            nest_deep_headings: false,
+            smart_punctuation: true,
+            doc_assets: None,
+        }
+        .into_string();
+        assert_eq!(output, expect, "original: {}", input);
+    }
+
+    let url = "https://doc.rust-lang.org/std/vec/struct.Vec.html";
+    t(
+        &format!("See <{url}>."),
+        true,
+        &format!("<p>See <a href=\"{url}\"><code>std::vec::Vec</code></a>.</p>\n"),
+    );
+    t(&format!("See <{url}>."), false, &format!("<p>See <a href=\"{url}\">{url}</a>.</p>\n"));
+    // Links with an explicit text are kept as is.
+    t(&format!("[vectors]({url})"), true, &format!("<p><a href=\"{url}\">vectors</a></p>\n"));
+    t(
+        "<https://example.com/>",
+        true,
+        "<p><a href=\"https://example.com/\">https://example.com/</a></p>\n",
+    );
+}
+
+#[test]
+fn test_replace_emoji_shortcodes() {
+    fn t(input: &str, expect: Option<&str>) {
+        assert_eq!(replace_emoji_shortcodes(input).as_deref(), expect, "original: {}", input);
+    }
+
+    t(":warning: not thread-safe", Some("\u{26a0}\u{fe0f} not thread-safe"));
+    t("done :heavy_check_mark:", Some("done \u{2714}\u{fe0f}"));
+    t(":+1::tada:", None);
+    t(":crab: and :rocket:", Some("\u{1f980} and \u{1f680}"));
+    t("no shortcode", None);
+    t(":unknown:", None);
+    t("a::warning::b", None);
+    t("x:warning:", None);
+    t(":warning:s", None);
+}
+
+#[test]
+fn test_emoji_shortcodes() {
+    fn t(input: &str, expect: &str) {
+        let mut map = IdMap::new();
+        let output = Markdown {
+            content: input,
+            links: &[],
+            ids: &mut map,
+            error_codes: ErrorCodes::Yes,
+            edition: DEFAULT_EDITION,
+            playground: &None,
+            heading_offset: HeadingOffset::H2,
+            custom_code_classes_in_docs: true,
+            enable_per_target_ignores: false,
+            code_block_id_prefix: None,
+            math: false,
+            expandable_hidden_lines: false,
+            smart_link_titles: false,
+            nest_deep_headings: false,
+            smart_punctuation: true,
+            doc_assets: None,
+        }
+        .into_string();
+        assert_eq!(output, expect, "original: {}", input);
+    }
+
+    t(":warning: unsafe_fn", "<p>\u{26a0}\u{fe0f} unsafe_fn</p>\n");
+    t("`:warning:`", "<p><code>:warning:</code></p>\n");
+    t("Use `a::b`::warning:", "<p>Use <code>a::b</code>::warning:</p>\n");
+}
+
+#[test]
+fn test_math() {
+    fn t(input: &str, expect: &str) {
+        let mut map = IdMap::new();
+        let output = Markdown {
+            content: input,
+            links: &[],
+            ids: &mut map,
+            error_codes: ErrorCodes::Yes,
+            edition: DEFAULT_EDITION,
+            playground: &None,
+            heading_offset: HeadingOffset::H2,
+            custom_code_classes_in_docs: true,
+            enable_per_target_ignores: false,
+            code_block_id_prefix: None,
+            math: true,
+            expandable_hidden_lines: false,
+            smart_link_titles: false,
+            nest_deep_headings: false,
+            smart_punctuation: true,
+            doc_assets: None,
+        }
+        .into_string();
+        assert_eq!(output, expect, "original: {}", input);
+    }
+
+    t("$a_1 * b_2$", "<p><span class=\"math\">a_1 * b_2</span></p>\n");
+    t("so $$x < y$$.", "<p>so <span class=\"math display\">x &lt; y</span>.</p>\n");
+    t(
+        "$a*b*c$ and $d$",
+        "<p><span class=\"math\">a*b*c</span> and <span class=\"math\">d</span></p>\n",
+    );
+    t("it costs $5 or $10", "<p>it costs $5 or $10</p>\n");
+    t("$ a$ and $b $", "<p>$ a$ and $b $</p>\n");
+    t("`$a$` and \\$b$", "<p><code>$a$</code> and $b$</p>\n");
+    // Math regions don't span several blocks, or end in the middle of an emphasis.
+    t("$a\n\nb$", "<p>$a</p>\n<p>b$</p>\n");
+    t("*$a* b$", "<p><em>$a</em> b$</p>\n");
+    t(
+        "```text\n$a$\n```",
+        "<div class=\"example-wrap\"><pre class=\"language-text\"><code>$a$\n</code></pre></div>",
+    );
+}
+
+#[test]
+fn test_doc_outline() {
+    fn t(input: &str, headings: &[&str], summary_len: usize) {
+        let headings = headings.iter().map(|heading| heading.to_string()).collect();
+        assert_eq!(doc_outline(input), DocOutline { headings, summary_len }, "original: {input}");
+    }
+
+    t("", &[], 0);
+    t("Parses a *number*.\n\nMore details.", &[], 16);
+    t("Parses.\n\n# Errors\n\nFails.\n\n## The `Err` variant", &["Errors", "The Err variant"], 7);
+    t("# Examples\n\n```\n# fn main() {}\n```", &["Examples"], 8);
+
+    assert!(doc_outline("Reads.\n\n# errors\n\nFails.").has_section("Errors"));
+    assert!(!doc_outline("Reads.\n\nErrors are returned.").has_section("Errors"));
+}
+
+#[test]
+fn test_included_markdown() {
+    let dir = tempfile::Builder::new().prefix("rustdoc-includes").tempdir().unwrap();
+    std::fs::write(dir.path().join("errors.md"), "# Errors\n\nFails.\n").unwrap();
+
+    let md = "Docs.\n\n{{#include errors.md}}\n\n{{#include missing.md}} end";
+    let included = IncludedMarkdown::expand(md, dir.path());
+    assert_eq!(included.text, "Docs.\n\n# Errors\n\nFails.\n\n{{#include missing.md}} end");
+    assert_eq!(included.errors.len(), 1);
+    assert_eq!(&md[included.errors[0].0.clone()], "{{#include missing.md}}");
+
+    // Before, inside and after the included content.
+    assert_eq!(included.original_offset(5, true), 5);
+    assert_eq!(included.original_offset(9, true), 7);
+    assert_eq!(included.original_offset(9, false), 29);
+    assert_eq!(included.original_offset(25, true), 31);
+    // Lines of the included content are mapped to the line of the directive.
+    assert_eq!(included.original_line(0), 0);
+    assert_eq!(included.original_line(2), 2);
+    assert_eq!(included.original_line(4), 2);
+    assert_eq!(included.original_line(6), 4);
+}
@@ -1,3 +1,4 @@
+pub(crate) mod doc_assets;
 pub(crate) mod escape;
 pub(crate) mod format;
 pub(crate) mod highlight;
@@ -24,13 +24,14 @@
     sidebar::{sidebar_module_like, Sidebar},
     AllTypes, LinkFromSrc, StylePath,
 };
-use crate::clean::{self, types::ExternalLocation, ExternalCrate, TypeAliasItem};
+use crate::clean::{self, types::ExternalLocation, ExternalCrate, ItemId, TypeAliasItem};
 use crate::config::{ModuleSorting, RenderOptions};
 use crate::docfs::{DocFS, PathError};
 use crate::error::Error;
 use crate::formats::cache::Cache;
 use crate::formats::item_type::ItemType;
 use crate::formats::{self, FormatRenderer};
+use crate::html::doc_assets::DocAssets;
 use crate::html::escape::Escape;
 use crate::html::format::{join_with_double_colon, Buffer};
 use crate::html::markdown::{self, plain_text_summary, ErrorCodes, IdMap};
@@ -96,6 +97,18 @@ pub(crate) struct SharedContext<'tcx> {
     pub(crate) local_sources: FxHashMap<PathBuf, String>,
     /// Show the memory layout of types in the docs.
     pub(super) show_type_layout: bool,
+    /// Whether to render `$...$` and `$$...$$` as math.
+    pub(super) math: bool,
+    /// Whether to keep the hidden lines of the examples, behind a toggle.
+    pub(super) expandable_hidden_lines: bool,
+    /// Whether to show the targets the examples are ignored on with `ignore-<target>`.
+    pub(super) enable_per_target_ignores: bool,
+    /// Whether to replace the text of autolinks to API documentation with item paths.
+    pub(super) smart_link_titles: bool,
+    /// Whether to keep the level of the headings deeper than `<h6>`.
+    pub(super) nest_deep_headings: bool,
+    /// The images linked to by the docs, which are copied to the output directory.
+    pub(super) doc_assets: Option<DocAssets>,
     /// The base-URL of the issue tracker for when an item has been tagged with
     /// an issue number.
     pub(super) issue_tracker_base_url: Option<String>,
@@ -125,7 +138,14 @@ pub(crate) struct SharedContext<'tcx> {
     /// to `Some(...)`, it'll store redirections and then generate a JSON file at the top level of
     /// the crate.
     redirections: Option<RefCell<FxHashMap<String, String>>>,
-
+    /// The items whose headings were already checked by the heading lints, so that the lints
+    /// aren't emitted again when their docs are rendered on another page, like the docs of the
+    /// methods of a trait on the pages of its implementors.
+    pub(super) heading_diagnostics_reported: RefCell<FxHashSet<ItemId>>,
+
+    /// Whether to generate links to the definitions of items in the source code pages, and from
+    /// the identifiers of the examples to the source of the items the docs link to.
+    pub(crate) generate_link_to_definition: bool,
     /// Correspondence map used to link types used in the source code pages to allow to click on
     /// links to jump to the type's definition.
     pub(crate) span_correspondence_map: FxHashMap<rustc_span::Span, LinkFromSrc>,
@@ -248,7 +268,9 @@ fn render_item(&mut self, it: &clean::Item, is_module: bool) -> String {
         };
         title.push_str(" - Rust");
         let tyname = it.type_();
-        let desc = plain_text_summary(&it.doc_value(), &it.link_names(&self.cache()));
+        let cache = self.cache();
+        let desc =
+            plain_text_summary(&it.doc_value(), &it.link_names(cache), cache.smart_punctuation);
         let desc = if !desc.is_empty() {
             desc
         } else if it.is_crate() {
@@ -513,6 +535,14 @@ fn init(
             call_locations,
             no_emit_shared,
             html_no_source,
+            mermaid_script,
+            enable_math,
+            katex_url,
+            expandable_hidden_lines,
+            enable_per_target_ignores,
+            smart_link_titles,
+            nest_deep_headings,
+            doc_assets_dir,
             ..
         } = options;
 
@@ -536,6 +566,8 @@ fn init(
             krate: krate.name(tcx).to_string(),
             css_file_extension: extension_css,
             scrape_examples_extension: !call_locations.is_empty(),
+            mermaid_script,
+            katex_url,
         };
         let mut issue_tracker_base_url = None;
         let mut include_sources = !html_no_source;
@@ -592,7 +624,15 @@ fn init(
             all: RefCell::new(AllTypes::new()),
             errors: receiver,
             redirections: if generate_redirect_map { Some(Default::default()) } else { None },
+            heading_diagnostics_reported: Default::default(),
             show_type_layout,
+            math: enable_math,
+            expandable_hidden_lines,
+            enable_per_target_ignores,
+            smart_link_titles,
+            nest_deep_headings,
+            doc_assets: doc_assets_dir.map(|dir| DocAssets::new(dir, krate.name(tcx).as_str())),
+            generate_link_to_definition,
             span_correspondence_map: matches,
             cache,
             call_locations,
@@ -636,7 +676,7 @@ fn make_child_renderer(&self) -> Self {
             dst: self.dst.clone(),
             render_redirect_pages: self.render_redirect_pages,
             deref_id_map: Default::default(),
-            id_map: IdMap::new(),
+            id_map: IdMap::with_slug_style(self.id_map.slug_style()),
             shared: Rc::clone(&self.shared),
             include_sources: self.include_sources,
             types_with_notable_traits: FxHashSet::default(),
@@ -792,6 +832,10 @@ fn after_krate(&mut self) -> Result<(), Error> {
             shared.fs.write(redirect_map_path, paths)?;
         }
 
+        if let Some(ref doc_assets) = shared.doc_assets {
+            doc_assets.copy_images(&shared, &self.dst)?;
+        }
+
         // No need for it anymore.
         drop(shared);
 
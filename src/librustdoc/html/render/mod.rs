@@ -55,6 +55,7 @@
 use rustc_hir::Mutability;
 use rustc_middle::middle::stability;
 use rustc_middle::ty::TyCtxt;
+use rustc_resolve::rustdoc::source_span_for_markdown_range;
 use rustc_span::{
     symbol::{sym, Symbol},
     BytePos, FileName, RealFileName,
@@ -63,6 +64,7 @@
 use serde::{Serialize, Serializer};
 
 use crate::clean::{self, ItemId, RenderedLink, SelfTy};
+use crate::core::DocContext;
 use crate::error::Error;
 use crate::formats::cache::Cache;
 use crate::formats::item_type::ItemType;
@@ -75,7 +77,8 @@
 };
 use crate::html::highlight;
 use crate::html::markdown::{
-    HeadingOffset, IdMap, Markdown, MarkdownItemInfo, MarkdownSummaryLine,
+    CollapsedHeading, DocAssetLinks, HeadingDiagnostics, HeadingIdCollision, HeadingOffset, IdMap,
+    Markdown, MarkdownItemInfo, MarkdownSummaryLine,
 };
 use crate::html::sources;
 use crate::html::static_files::SCRAPE_EXAMPLES_HELP_MD;
@@ -416,6 +419,14 @@ fn scrape_examples_help(shared: &SharedContext<'_>) -> String {
             playground: &shared.playground,
             heading_offset: HeadingOffset::H1,
             custom_code_classes_in_docs: false,
+            enable_per_target_ignores: false,
+            code_block_id_prefix: None,
+            math: false,
+            expandable_hidden_lines: false,
+            smart_link_titles: false,
+            nest_deep_headings: false,
+            smart_punctuation: true,
+            doc_assets: None,
         }
         .into_string()
     )
@@ -441,33 +452,98 @@ fn document<'a, 'cx: 'a>(
     })
 }
 
-/// Render md_text as markdown.
+/// Render md_text, the docs of `item`, as markdown.
 fn render_markdown<'a, 'cx: 'a>(
     cx: &'a mut Context<'cx>,
+    item: &'a clean::Item,
     md_text: &'a str,
     links: Vec<RenderedLink>,
     heading_offset: HeadingOffset,
+    code_block_id_prefix: Option<String>,
 ) -> impl fmt::Display + 'a + Captures<'cx> {
     display_fn(move |f| {
         let custom_code_classes_in_docs = cx.tcx().features().custom_code_classes_in_docs;
-        write!(
-            f,
-            "<div class=\"docblock\">{}</div>",
-            Markdown {
-                content: md_text,
-                links: &links,
-                ids: &mut cx.id_map,
-                error_codes: cx.shared.codes,
-                edition: cx.shared.edition(),
-                playground: &cx.shared.playground,
-                heading_offset,
-                custom_code_classes_in_docs,
-            }
-            .into_string()
-        )
+        let root_path = cx.root_path();
+        cx.id_map.collect_heading_diagnostics();
+        let docs = Markdown {
+            content: md_text,
+            links: &links,
+            ids: &mut cx.id_map,
+            error_codes: cx.shared.codes,
+            edition: cx.shared.edition(),
+            playground: &cx.shared.playground,
+            heading_offset,
+            custom_code_classes_in_docs,
+            enable_per_target_ignores: cx.shared.enable_per_target_ignores,
+            code_block_id_prefix: code_block_id_prefix.as_deref(),
+            math: cx.shared.math,
+            expandable_hidden_lines: cx.shared.expandable_hidden_lines,
+            smart_link_titles: cx.shared.smart_link_titles,
+            nest_deep_headings: cx.shared.nest_deep_headings,
+            smart_punctuation: cx.shared.cache.smart_punctuation,
+            doc_assets: cx
+                .shared
+                .doc_assets
+                .as_ref()
+                .map(|assets| DocAssetLinks { assets, root_path: &root_path }),
+        }
+        .into_string();
+        let diagnostics = cx.id_map.take_heading_diagnostics();
+        if cx.shared.heading_diagnostics_reported.borrow_mut().insert(item.item_id) {
+            report_heading_diagnostics(cx.tcx(), item, md_text, diagnostics);
+        }
+        write!(f, "<div class=\"docblock\">{docs}</div>")
     })
 }
 
+/// Emits the lints about the headings of the docs of `item`:
+///
+/// * `heading_id_collisions` for the headings which were given another ID than the one derived
+///   from their text, because it was already used by another heading of these docs or by
+///   rustdoc itself.
+/// * `collapsed_heading_levels` for the headings rendered as `<h6>` because they would be deeper
+///   once offset.
+fn report_heading_diagnostics(
+    tcx: TyCtxt<'_>,
+    item: &clean::Item,
+    md_text: &str,
+    diagnostics: HeadingDiagnostics,
+) {
+    let HeadingDiagnostics { id_collisions, collapsed_headings, .. } = diagnostics;
+    if id_collisions.is_empty() && collapsed_headings.is_empty() {
+        return;
+    }
+    let Some(hir_id) = DocContext::as_local_hir_id(tcx, item.item_id) else {
+        return;
+    };
+    // The range can't be mapped back to the source if the docs include other files.
+    let span = |range: &_| {
+        source_span_for_markdown_range(tcx, md_text, range, &item.attrs.doc_strings)
+            .unwrap_or_else(|| item.attr_span(tcx))
+    };
+    for HeadingIdCollision { range, original_id, id } in id_collisions {
+        let sp = span(&range);
+        let msg = format!("the ID `{original_id}` of this heading is already used on this page");
+        tcx.struct_span_lint_hir(crate::lint::HEADING_ID_COLLISIONS, hir_id, sp, msg, |lint| {
+            lint.note(format!(
+                "the heading was given the ID `{id}` instead, so links to `#{original_id}` lead \
+                 elsewhere"
+            ))
+        });
+    }
+    for CollapsedHeading { range, level } in collapsed_headings {
+        let sp = span(&range);
+        let msg = "this heading is rendered at the same level as its parent heading";
+        tcx.struct_span_lint_hir(crate::lint::COLLAPSED_HEADING_LEVELS, hir_id, sp, msg, |lint| {
+            lint.note(format!(
+                "it would be rendered as an `<h{level}>` heading, which doesn't exist, so it is \
+                 rendered as an `<h6>` heading instead"
+            ))
+            .help("use a higher level heading, or `--nest-deep-headings` to keep its level")
+        });
+    }
+}
+
 /// Writes a documentation block containing only the first paragraph of the documentation. If the
 /// docs are longer, a "Read more" link is appended to the end.
 fn document_short<'a, 'cx: 'a>(
@@ -485,7 +561,8 @@ fn document_short<'a, 'cx: 'a>(
         let s = item.doc_value();
         if !s.is_empty() {
             let (mut summary_html, has_more_content) =
-                MarkdownSummaryLine(&s, &item.links(cx)).into_string_with_has_more_content();
+                MarkdownSummaryLine(&s, &item.links(cx), cx.cache().smart_punctuation)
+                    .into_string_with_has_more_content();
 
             if has_more_content {
                 let link = format!(" <a{}>Read more</a>", assoc_href_attr(item, link, cx));
@@ -528,17 +605,20 @@ fn document_full_inner<'a, 'cx: 'a>(
     display_fn(move |f| {
         if let Some(s) = item.opt_doc_value() {
             debug!("Doc block: =====\n{s}\n=====");
+            // The code examples are linked to as `{item type}.{item name}.example-{index}`.
+            let code_block_id_prefix = item.name.map(|name| format!("{}.{name}", item.type_()));
+            let links = item.links(cx);
+            let docs = render_markdown(cx, item, &s, links, heading_offset, code_block_id_prefix);
             if is_collapsible {
                 write!(
                     f,
                     "<details class=\"toggle top-doc\" open>\
                      <summary class=\"hideme\">\
                         <span>Expand description</span>\
-                     </summary>{}</details>",
-                    render_markdown(cx, &s, item.links(cx), heading_offset)
+                     </summary>{docs}</details>",
                 )?;
             } else {
-                write!(f, "{}", render_markdown(cx, &s, item.links(cx), heading_offset))?;
+                write!(f, "{docs}")?;
             }
         }
 
@@ -637,7 +717,7 @@ fn short_item_info(
 
         if let Some(note) = note {
             let note = note.as_str();
-            let html = MarkdownItemInfo(note, &mut cx.id_map);
+            let html = MarkdownItemInfo(note, &mut cx.id_map, cx.shared.cache.smart_punctuation);
             message.push_str(": ");
             message.push_str(&html.into_string());
         }
@@ -1793,6 +1873,7 @@ fn render_default_items(
                 );
             }
             let custom_code_classes_in_docs = cx.tcx().features().custom_code_classes_in_docs;
+            let root_path = cx.root_path();
             write!(
                 w,
                 "<div class=\"docblock\">{}</div>",
@@ -1805,6 +1886,18 @@ fn render_default_items(
                     playground: &cx.shared.playground,
                     heading_offset: HeadingOffset::H4,
                     custom_code_classes_in_docs,
+                    enable_per_target_ignores: cx.shared.enable_per_target_ignores,
+                    code_block_id_prefix: None,
+                    math: cx.shared.math,
+                    expandable_hidden_lines: cx.shared.expandable_hidden_lines,
+                    smart_link_titles: cx.shared.smart_link_titles,
+                    nest_deep_headings: cx.shared.nest_deep_headings,
+                    smart_punctuation: cx.shared.cache.smart_punctuation,
+                    doc_assets: cx
+                        .shared
+                        .doc_assets
+                        .as_ref()
+                        .map(|assets| DocAssetLinks { assets, root_path: &root_path }),
                 }
                 .into_string()
             );
@@ -527,8 +527,12 @@ fn cmp(
                 };
 
                 w.write_str(ITEM_TABLE_ROW_OPEN);
-                let docs =
-                    MarkdownSummaryLine(&myitem.doc_value(), &myitem.links(cx)).into_string();
+                let docs = MarkdownSummaryLine(
+                    &myitem.doc_value(),
+                    &myitem.links(cx),
+                    cx.cache().smart_punctuation,
+                )
+                .into_string();
                 let (docs_before, docs_after) = if docs.is_empty() {
                     ("", "")
                 } else {
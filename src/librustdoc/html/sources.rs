@@ -325,6 +325,7 @@ struct Source<Code: std::fmt::Display> {
             s,
             Some(highlight::HrefContext { context, file_span, root_path, current_href }),
             Some(decoration_info),
+            None,
         );
         Ok(())
     });
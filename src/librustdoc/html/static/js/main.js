@@ -1213,6 +1213,48 @@ href="https://doc.rust-lang.org/${channel}/rustdoc/how-to-read-rustdoc.html\
         });
     }
 
+    // The toggles added by `--expandable-hidden-lines`.
+    onEachLazy(document.querySelectorAll(".hidden-lines-toggle"), button => {
+        button.onclick = () => {
+            const wrapper = button.parentElement;
+            if (hasClass(wrapper, "expanded")) {
+                removeClass(wrapper, "expanded");
+                button.title = "Show hidden lines";
+            } else {
+                addClass(wrapper, "expanded");
+                button.title = "Hide hidden lines";
+            }
+        };
+    });
+
+    // The copy buttons of the Rust examples, which copy the code stored in their `data-code`
+    // attribute rather than their text, which lacks the hidden lines needed to run them.
+    onEachLazy(document.querySelectorAll(".example-wrap[data-code]"), wrapper => {
+        const button = document.createElement("button");
+        button.className = "copy-code";
+        button.title = "Copy code to clipboard";
+        button.textContent = "⎘";
+        button.onclick = () => {
+            navigator.clipboard.writeText(wrapper.getAttribute("data-code")).then(() => {
+                button.textContent = "✓";
+                window.setTimeout(() => {
+                    button.textContent = "⎘";
+                }, 1000);
+            });
+        };
+        wrapper.appendChild(button);
+    });
+
+    // Render the math regions tagged by `--enable-math`, if the KaTeX script was loaded.
+    if (window.katex) {
+        onEachLazy(document.querySelectorAll(".docblock .math"), el => {
+            window.katex.render(el.textContent, el, {
+                displayMode: hasClass(el, "display"),
+                throwOnError: false,
+            });
+        });
+    }
+
     setMobileTopbar();
     addSidebarItems();
     addSidebarCrates();
@@ -1,3 +1,4 @@
+use crate::html::doc_assets::{image_size, ImageSize};
 use crate::html::format::href_relative_parts;
 use rustc_span::{sym, Symbol};
 
@@ -48,3 +49,23 @@ fn href_relative_parts_root() {
     let fqp = &[sym::std];
     assert_relative_path(&[sym::std], relative_to_fqp, fqp);
 }
+
+#[test]
+fn image_size_gif() {
+    let gif = b"GIF89a\x40\x01\xf0\x00\x80\x00\x00";
+    assert_eq!(image_size(gif), Some(ImageSize { width: 320, height: 240 }));
+}
+
+#[test]
+fn image_size_jpeg() {
+    // An APP0 segment, then a DHT segment using one of the excluded markers, then the frame.
+    let jpeg = b"\xff\xd8\xff\xe0\x00\x04JF\xff\xc4\x00\x02\xff\xc0\x00\x11\x08\x01\xe0\x02\x80";
+    assert_eq!(image_size(jpeg), Some(ImageSize { width: 640, height: 480 }));
+}
+
+#[test]
+fn image_size_unknown() {
+    assert_eq!(image_size(b"<svg></svg>"), None);
+    assert_eq!(image_size(b"\x89PNG\r\n\x1a\n"), None);
+    assert_eq!(image_size(b"\xff\xd8\xff\xe0\x00\x10"), None);
+}
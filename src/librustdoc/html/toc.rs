@@ -162,27 +162,27 @@ pub(crate) fn push(&mut self, level: u32, name: String, id: String) -> &str {
 }
 
 impl Toc {
-    fn print_inner(&self, v: &mut String) {
+    fn print_inner(&self, v: &mut String, numbered: bool) {
         use std::fmt::Write as _;
 
         v.push_str("<ul>");
         for entry in &self.entries {
             // recursively format this table of contents
-            let _ = write!(
-                v,
-                "\n<li><a href=\"#{id}\">{num} {name}</a>",
-                id = entry.id,
-                num = entry.sec_number,
-                name = entry.name
-            );
-            entry.children.print_inner(&mut *v);
+            let _ = write!(v, "\n<li><a href=\"#{id}\">", id = entry.id);
+            if numbered {
+                let _ = write!(v, "{num} ", num = entry.sec_number);
+            }
+            let _ = write!(v, "{name}</a>", name = entry.name);
+            entry.children.print_inner(&mut *v, numbered);
             v.push_str("</li>");
         }
         v.push_str("</ul>");
     }
-    pub(crate) fn print(&self) -> String {
+    /// Renders the table of contents. If `numbered` is `true`, the section numbers are prepended
+    /// to the entries.
+    pub(crate) fn print(&self, numbered: bool) -> String {
         let mut v = String::new();
-        self.print_inner(&mut v);
+        self.print_inner(&mut v, numbered);
         v
     }
 }
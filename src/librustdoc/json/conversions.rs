@@ -18,6 +18,8 @@
 
 use crate::clean::{self, ItemId};
 use crate::formats::item_type::ItemType;
+use crate::html::markdown::IdMap;
+use crate::json::docs::{link_names, structured_docs, summary_spans};
 use crate::json::JsonRenderer;
 use crate::passes::collect_intra_doc_links::UrlFragment;
 
@@ -41,6 +43,27 @@ pub(super) fn convert_item(&self, item: clean::Item) -> Option<Item> {
             })
             .collect();
         let docs = item.opt_doc_value();
+        let (structured_docs, summary) = match &docs {
+            Some(docs) if self.structured_docs => {
+                let item_links = self.cache.intra_doc_links.get(&item.item_id);
+                let link_names = link_names(item_links.into_iter().flatten());
+                let smart_punctuation = self.cache.smart_punctuation;
+                // The IDs of the headings are derived like on a page of their own.
+                let mut ids = IdMap::with_slug_style(self.slug_style);
+                let structured_docs = structured_docs(
+                    docs,
+                    &link_names,
+                    &links,
+                    &mut ids,
+                    smart_punctuation,
+                    self.tcx.features().custom_code_classes_in_docs,
+                );
+                let summary = summary_spans(docs, &link_names, &links, smart_punctuation);
+                (Some(structured_docs), Some(summary))
+            }
+            None if self.structured_docs => (Some(Vec::new()), Some(Vec::new())),
+            _ => (None, None),
+        };
         let attrs = item.attributes(self.tcx, true);
         let span = item.span(self.tcx);
         let visibility = item.visibility(self.tcx);
@@ -74,6 +97,8 @@ pub(super) fn convert_item(&self, item: clean::Item) -> Option<Item> {
             deprecation: deprecation.map(from_deprecation),
             inner,
             links,
+            structured_docs,
+            summary,
         })
     }
 
@@ -0,0 +1,213 @@
+//! Conversion of the documentation of items into the simplified structure emitted with
+//! `--json-structured-docs`.
+
+use pulldown_cmark::{CodeBlockKind, Event, Tag};
+use rustc_data_structures::fx::FxHashMap;
+use rustdoc_json_types::{DocBlock, DocBlockKind, DocInline, Id, SummarySpan};
+
+use crate::clean::{ItemLink, RenderedLink};
+use crate::html::markdown::{
+    doc_events, is_rust_code_block, short_markdown_summary_spans, DocEvent, IdMap, SummarySpanStyle,
+};
+
+/// An inline element whose content is still being collected.
+enum Frame {
+    Emphasis,
+    Strong,
+    Strikethrough,
+    Link { destination: String },
+}
+
+/// A block whose content is still being collected.
+enum OpenBlock {
+    Heading { level: u8, id: String },
+    Paragraph,
+    CodeBlock(Option<String>),
+}
+
+struct DocBuilder<'a> {
+    links: &'a FxHashMap<String, Id>,
+    custom_code_classes_in_docs: bool,
+    blocks: Vec<DocBlock>,
+    open: Option<(OpenBlock, usize)>,
+    /// The content of the open block, followed by the content of each open inline element.
+    content: Vec<Vec<DocInline>>,
+    frames: Vec<Frame>,
+    code: String,
+}
+
+impl<'a> DocBuilder<'a> {
+    fn start_block(&mut self, block: OpenBlock, start: usize) {
+        self.open = Some((block, start));
+        self.content = vec![Vec::new()];
+        self.frames.clear();
+        self.code.clear();
+    }
+
+    fn end_block(&mut self, end: usize) {
+        let Some((block, start)) = self.open.take() else { return };
+        // Inline elements cannot span blocks, but close them anyway in case of unbalanced events.
+        while !self.frames.is_empty() {
+            self.end_inline();
+        }
+        let content = self.content.pop().unwrap_or_default();
+        let kind = match block {
+            OpenBlock::Heading { level, id } => DocBlockKind::Heading { level, id, content },
+            OpenBlock::Paragraph => DocBlockKind::Paragraph(content),
+            OpenBlock::CodeBlock(lang_string) => {
+                // Indented code blocks are Rust code.
+                let rust = lang_string.as_deref().map_or(true, |lang_string| {
+                    is_rust_code_block(lang_string, self.custom_code_classes_in_docs)
+                });
+                let code = std::mem::take(&mut self.code);
+                DocBlockKind::CodeBlock { lang_string, rust, code }
+            }
+        };
+        self.blocks.push(DocBlock { kind, range: (start, end) });
+    }
+
+    /// Appends an inline element to the innermost open element. Text outside of any block, like
+    /// the text of tight list items, is collected in a paragraph of its own.
+    fn push_inline(&mut self, inline: DocInline, start: usize) {
+        if self.open.is_none() {
+            self.start_block(OpenBlock::Paragraph, start);
+        }
+        let content = self.content.last_mut().unwrap();
+        match (content.last_mut(), inline) {
+            (Some(DocInline::Text(prev)), DocInline::Text(text)) => prev.push_str(&text),
+            (_, inline) => content.push(inline),
+        }
+    }
+
+    fn start_inline(&mut self, frame: Frame, start: usize) {
+        if self.open.is_none() {
+            self.start_block(OpenBlock::Paragraph, start);
+        }
+        self.frames.push(frame);
+        self.content.push(Vec::new());
+    }
+
+    fn end_inline(&mut self) {
+        let (Some(frame), Some(content)) = (self.frames.pop(), self.content.pop()) else { return };
+        let inline = match frame {
+            Frame::Emphasis => DocInline::Emphasis(content),
+            Frame::Strong => DocInline::Strong(content),
+            Frame::Strikethrough => DocInline::Strikethrough(content),
+            Frame::Link { destination } => {
+                let id = self.links.get(&destination).cloned();
+                DocInline::Link { content, destination, id }
+            }
+        };
+        self.content.last_mut().unwrap().push(inline);
+    }
+}
+
+/// Converts `doc` into a list of [`DocBlock`]s, from the events the HTML output is rendered from.
+/// `link_names` are the intra-doc links of the item, see [`link_names`], and `links` the items they
+/// resolve to, as emitted in [`Item::links`](rustdoc_json_types::Item::links).
+pub(super) fn structured_docs(
+    doc: &str,
+    link_names: &[RenderedLink],
+    links: &FxHashMap<String, Id>,
+    ids: &mut IdMap,
+    smart_punctuation: bool,
+    custom_code_classes_in_docs: bool,
+) -> Vec<DocBlock> {
+    let mut builder = DocBuilder {
+        links,
+        custom_code_classes_in_docs,
+        blocks: Vec::new(),
+        open: None,
+        content: Vec::new(),
+        frames: Vec::new(),
+        code: String::new(),
+    };
+    for DocEvent { event, range, heading_id } in doc_events(doc, link_names, ids, smart_punctuation)
+    {
+        match event {
+            Event::Start(Tag::Heading(level, ..)) => {
+                builder.end_block(range.start);
+                let id = heading_id.unwrap_or_default();
+                builder.start_block(OpenBlock::Heading { level: level as u8, id }, range.start);
+            }
+            Event::Start(Tag::Paragraph) => {
+                builder.end_block(range.start);
+                builder.start_block(OpenBlock::Paragraph, range.start);
+            }
+            Event::Start(Tag::CodeBlock(kind)) => {
+                builder.end_block(range.start);
+                let lang_string = match kind {
+                    CodeBlockKind::Fenced(lang_string) => Some(lang_string.into_string()),
+                    CodeBlockKind::Indented => None,
+                };
+                builder.start_block(OpenBlock::CodeBlock(lang_string), range.start);
+            }
+            Event::End(Tag::Heading(..) | Tag::Paragraph | Tag::CodeBlock(_)) => {
+                builder.end_block(range.end)
+            }
+            // Containers are flattened: only the blocks they contain are emitted.
+            Event::Start(Tag::Item | Tag::TableCell) => builder.end_block(range.start),
+            Event::End(Tag::Item | Tag::TableCell) => builder.end_block(range.end),
+            Event::Start(Tag::Emphasis) => builder.start_inline(Frame::Emphasis, range.start),
+            Event::Start(Tag::Strong) => builder.start_inline(Frame::Strong, range.start),
+            Event::Start(Tag::Strikethrough) => {
+                builder.start_inline(Frame::Strikethrough, range.start)
+            }
+            Event::Start(Tag::Link(_, destination, _)) => builder
+                .start_inline(Frame::Link { destination: destination.into_string() }, range.start),
+            Event::End(Tag::Emphasis | Tag::Strong | Tag::Strikethrough | Tag::Link(..)) => {
+                builder.end_inline()
+            }
+            Event::Text(text) => {
+                if matches!(builder.open, Some((OpenBlock::CodeBlock(_), _))) {
+                    builder.code.push_str(&text);
+                } else {
+                    builder.push_inline(DocInline::Text(text.into_string()), range.start);
+                }
+            }
+            Event::Code(code) => {
+                builder.push_inline(DocInline::Code(code.into_string()), range.start)
+            }
+            Event::SoftBreak => builder.push_inline(DocInline::Text(" ".into()), range.start),
+            Event::HardBreak => builder.push_inline(DocInline::LineBreak, range.start),
+            // Images, raw HTML, footnotes, rules and task list markers are not represented.
+            _ => {}
+        }
+    }
+    builder.end_block(doc.len());
+    builder.blocks
+}
+
+/// Converts the summary of `doc` into a list of [`SummarySpan`]s. `link_names` and `links` are the
+/// intra-doc links of the item, as in [`structured_docs`].
+pub(super) fn summary_spans(
+    doc: &str,
+    link_names: &[RenderedLink],
+    links: &FxHashMap<String, Id>,
+    smart_punctuation: bool,
+) -> Vec<SummarySpan> {
+    short_markdown_summary_spans(doc, link_names, smart_punctuation)
+        .into_iter()
+        .map(|span| SummarySpan {
+            text: span.text,
+            code: span.style == SummarySpanStyle::Code,
+            id: span.target.as_ref().and_then(|target| links.get(target).cloned()),
+            destination: span.target,
+        })
+        .collect()
+}
+
+/// Returns the intra-doc links of an item as they are given to the Markdown renderer. Their
+/// `href` is the link as written, which is how [`Item::links`](rustdoc_json_types::Item::links)
+/// is keyed.
+pub(super) fn link_names<'a>(item_links: impl Iterator<Item = &'a ItemLink>) -> Vec<RenderedLink> {
+    item_links
+        .map(|link| RenderedLink {
+            original_text: link.link.clone(),
+            new_text: link.link_text.clone(),
+            href: link.link.to_string(),
+            tooltip: String::new(),
+            source: None,
+        })
+        .collect()
+}
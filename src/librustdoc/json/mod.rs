@@ -5,6 +5,7 @@
 //! docs for usage and details.
 
 mod conversions;
+mod docs;
 mod import_finder;
 
 use std::cell::RefCell;
@@ -28,6 +29,7 @@
 use crate::error::Error;
 use crate::formats::cache::Cache;
 use crate::formats::FormatRenderer;
+use crate::html::markdown::SlugStyle;
 use crate::json::conversions::{id_from_item, id_from_item_default, IntoWithTcx};
 use crate::{clean, try_err};
 
@@ -41,6 +43,10 @@ pub(crate) struct JsonRenderer<'tcx> {
     out_path: PathBuf,
     cache: Rc<Cache>,
     imported_items: DefIdSet,
+    /// Whether to emit the [`structured_docs`](types::Item::structured_docs) of items.
+    structured_docs: bool,
+    /// How the IDs of the headings of the structured docs are derived from their text.
+    slug_style: SlugStyle,
 }
 
 impl<'tcx> JsonRenderer<'tcx> {
@@ -124,6 +130,8 @@ fn init(
                 out_path: options.output,
                 cache: Rc::new(cache),
                 imported_items,
+                structured_docs: options.json_structured_docs,
+                slug_style: options.id_map.slug_style(),
             },
             krate,
         ))
@@ -525,6 +525,13 @@ fn opts() -> Vec<RustcOptGroup> {
                 "calculate percentage of public items with documentation",
             )
         }),
+        unstable("show-coverage-details", |o| {
+            o.optflag(
+                "",
+                "show-coverage-details",
+                "Include how complete the docs of each item are in the output of --show-coverage",
+            )
+        }),
         unstable("enable-per-target-ignores", |o| {
             o.optflagmulti(
                 "",
@@ -659,6 +666,99 @@ fn opts() -> Vec<RustcOptGroup> {
         unstable("html-no-source", |o| {
             o.optflag("", "html-no-source", "Disable HTML source code pages generation")
         }),
+        unstable("check-external-links", |o| {
+            o.optflag(
+                "",
+                "check-external-links",
+                "Check that external links in the documentation are reachable (uses the network)",
+            )
+        }),
+        unstable("markdown-includes", |o| {
+            o.optflag(
+                "",
+                "markdown-includes",
+                "Replace `{{#include path}}` directives in the docs with the content of the file",
+            )
+        }),
+        unstable("mermaid-script", |o| {
+            o.optopt(
+                "",
+                "mermaid-script",
+                "URL of the mermaid.js script used to render the `mermaid` code blocks as diagrams",
+                "URL",
+            )
+        }),
+        unstable("enable-math", |o| {
+            o.optflagopt(
+                "",
+                "enable-math",
+                "Render `$...$` and `$$...$$` in the docs as math, with the KaTeX distribution \
+                 found at KATEX_URL if given",
+                "KATEX_URL",
+            )
+        }),
+        unstable("expandable-hidden-lines", |o| {
+            o.optflag(
+                "",
+                "expandable-hidden-lines",
+                "Keep the hidden lines of the examples, behind a button revealing them",
+            )
+        }),
+        unstable("external-url-manifest", |o| {
+            o.optopt(
+                "",
+                "external-url-manifest",
+                "Write the external URLs found in the documentation to a JSON file",
+                "PATH",
+            )
+        }),
+        unstable("std-docs-dir", |o| {
+            o.optopt(
+                "",
+                "std-docs-dir",
+                "Directory of the standard library docs to check the anchors of links to \
+                 doc.rust-lang.org against",
+                "PATH",
+            )
+        }),
+        unstable("smart-link-titles", |o| {
+            o.optflag(
+                "",
+                "smart-link-titles",
+                "Show the path of the item instead of the URL in autolinks to API documentation",
+            )
+        }),
+        unstable("nest-deep-headings", |o| {
+            o.optflag(
+                "",
+                "nest-deep-headings",
+                "Render the headings deeper than <h6> with their level instead of as <h6>",
+            )
+        }),
+        unstable("doc-assets-dir", |o| {
+            o.optopt(
+                "",
+                "doc-assets-dir",
+                "Directory to resolve the relative image links of the docs in, and to copy \
+                 the images from",
+                "PATH",
+            )
+        }),
+        unstable("json-structured-docs", |o| {
+            o.optflag(
+                "",
+                "json-structured-docs",
+                "Include a structured representation of the docs of each item in the JSON output",
+            )
+        }),
+        unstable("heading-slug-style", |o| {
+            o.optopt(
+                "",
+                "heading-slug-style",
+                "How the ids of headings are generated: `rustdoc` (the default) or `github`",
+                "rustdoc|github",
+            )
+        }),
     ]
 }
 
@@ -196,6 +196,86 @@ macro_rules! declare_rustdoc_lint {
     "detects redundant explicit links in doc comments"
 }
 
+declare_rustdoc_lint! {
+    /// The `lazy_list_continuation` lint detects list items continued on lines
+    /// which are not indented, which makes them look like separate paragraphs.
+    /// This is a `rustdoc` only lint, see the documentation in the [rustdoc book].
+    ///
+    /// [rustdoc book]: ../../../rustdoc/lints.html#lazy_list_continuation
+    LAZY_LIST_CONTINUATION,
+    Allow,
+    "detects list items continued on lines which are not indented"
+}
+
+declare_rustdoc_lint! {
+    /// The `malformed_external_urls` lint detects external URLs in links which
+    /// use an unknown scheme or are malformed. This is a `rustdoc` only lint,
+    /// see the documentation in the [rustdoc book].
+    ///
+    /// [rustdoc book]: ../../../rustdoc/lints.html#malformed_external_urls
+    MALFORMED_EXTERNAL_URLS,
+    Allow,
+    "detects malformed external URLs in doc comments"
+}
+
+declare_rustdoc_lint! {
+    /// The `heading_id_collisions` lint detects headings which can't be given the ID derived from
+    /// their text because it is already used by another heading of the same docs or by rustdoc,
+    /// so that links to them with this ID lead to another section. This is a `rustdoc` only lint, see the documentation in the
+    /// [rustdoc book].
+    ///
+    /// [rustdoc book]: ../../../rustdoc/lints.html#heading_id_collisions
+    HEADING_ID_COLLISIONS,
+    Allow,
+    "detects headings whose ID collides with another ID of the page"
+}
+
+declare_rustdoc_lint! {
+    /// The `collapsed_heading_levels` lint detects headings which are rendered at the same level
+    /// as their parent heading, because they would be deeper than `<h6>` once offset by the
+    /// level at which the docs are shown. This is a `rustdoc` only lint, see the documentation in
+    /// the [rustdoc book].
+    ///
+    /// [rustdoc book]: ../../../rustdoc/lints.html#collapsed_heading_levels
+    COLLAPSED_HEADING_LEVELS,
+    Allow,
+    "detects headings rendered at the same level as their parent heading"
+}
+
+declare_rustdoc_lint! {
+    /// The `missing_image_alt_text` lint detects images in the documentation which have no alt
+    /// text, so that nothing describes them to screen readers or when they fail to load. This is
+    /// a `rustdoc` only lint, see the documentation in the [rustdoc book].
+    ///
+    /// [rustdoc book]: ../../../rustdoc/lints.html#missing_image_alt_text
+    MISSING_IMAGE_ALT_TEXT,
+    Allow,
+    "detects images without alt text in doc comments"
+}
+
+declare_rustdoc_lint! {
+    /// The `broken_std_doc_anchors` lint detects links to a section of the standard library docs
+    /// on `doc.rust-lang.org` which doesn't exist in the local copy of these docs, like links to
+    /// methods which were renamed. This is a `rustdoc` only lint, see the documentation in the
+    /// [rustdoc book].
+    ///
+    /// [rustdoc book]: ../../../rustdoc/lints.html#broken_std_doc_anchors
+    BROKEN_STD_DOC_ANCHORS,
+    Allow,
+    "detects links to sections of the standard library docs which don't exist"
+}
+
+declare_rustdoc_lint! {
+    /// The `unreachable_external_links` lint detects external links in the documentation which
+    /// can't be reached anymore. It is only checked when `--check-external-links` is passed. This
+    /// is a `rustdoc` only lint, see the documentation in the [rustdoc book].
+    ///
+    /// [rustdoc book]: ../../../rustdoc/lints.html#unreachable_external_links
+    UNREACHABLE_EXTERNAL_LINKS,
+    Warn,
+    "detects external links which can't be reached"
+}
+
 pub(crate) static RUSTDOC_LINTS: Lazy<Vec<&'static Lint>> = Lazy::new(|| {
     vec![
         BROKEN_INTRA_DOC_LINKS,
@@ -209,6 +289,13 @@ macro_rules! declare_rustdoc_lint {
         MISSING_CRATE_LEVEL_DOCS,
         UNESCAPED_BACKTICKS,
         REDUNDANT_EXPLICIT_LINKS,
+        LAZY_LIST_CONTINUATION,
+        MALFORMED_EXTERNAL_URLS,
+        HEADING_ID_COLLISIONS,
+        COLLAPSED_HEADING_LEVELS,
+        MISSING_IMAGE_ALT_TEXT,
+        BROKEN_STD_DOC_ANCHORS,
+        UNREACHABLE_EXTERNAL_LINKS,
     ]
 });
 
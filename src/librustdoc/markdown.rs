@@ -7,11 +7,12 @@
 use rustc_span::source_map::DUMMY_SP;
 
 use crate::config::{Options, RenderOptions};
-use crate::doctest::{Collector, GlobalTestOptions};
+use crate::doctest::{Collector, GlobalTestOptions, IncludedTests};
 use crate::html::escape::Escape;
 use crate::html::markdown;
 use crate::html::markdown::{
-    find_testable_code, ErrorCodes, HeadingOffset, IdMap, Markdown, MarkdownWithToc,
+    find_testable_code, ErrorCodes, HeadingOffset, IdMap, IncludedMarkdown, Markdown,
+    MarkdownWithToc,
 };
 
 /// Separate any lines at the start of the file that begin with `# ` or `%`.
@@ -71,7 +72,7 @@ pub(crate) fn render<P: AsRef<Path>>(
     }
     let title = metadata[0];
 
-    let mut ids = IdMap::new();
+    let mut ids = IdMap::with_slug_style(options.id_map.slug_style());
     let error_codes = ErrorCodes::from(options.unstable_features.is_nightly_build());
     let text = if !options.markdown_no_toc {
         MarkdownWithToc {
@@ -82,6 +83,14 @@ pub(crate) fn render<P: AsRef<Path>>(
             playground: &playground,
             // For markdown files, it'll be disabled until the feature is enabled by default.
             custom_code_classes_in_docs: false,
+            enable_per_target_ignores: options.enable_per_target_ignores,
+            math: options.enable_math,
+            expandable_hidden_lines: options.expandable_hidden_lines,
+            smart_link_titles: options.smart_link_titles,
+            nest_deep_headings: options.nest_deep_headings,
+            heading_offset: HeadingOffset::H1,
+            number_headings: true,
+            smart_punctuation: true,
         }
         .into_string()
     } else {
@@ -95,6 +104,14 @@ pub(crate) fn render<P: AsRef<Path>>(
             heading_offset: HeadingOffset::H1,
             // For markdown files, it'll be disabled until the feature is enabled by default.
             custom_code_classes_in_docs: false,
+            enable_per_target_ignores: options.enable_per_target_ignores,
+            code_block_id_prefix: None,
+            math: options.enable_math,
+            expandable_hidden_lines: options.expandable_hidden_lines,
+            smart_link_titles: options.smart_link_titles,
+            nest_deep_headings: options.nest_deep_headings,
+            smart_punctuation: true,
+            doc_assets: None,
         }
         .into_string()
     };
@@ -146,6 +163,19 @@ pub(crate) fn test(options: Options) -> Result<(), String> {
         .map_err(|err| format!("{input}: {err}", input = options.input.display()))?;
     let mut opts = GlobalTestOptions::default();
     opts.no_crate_inject = true;
+    // Included files are relative to the markdown file, like the files included by the
+    // documentation of a crate are relative to its source file.
+    let included = options.markdown_includes.then(|| {
+        let base_dir = options.input.parent().unwrap_or(Path::new(""));
+        IncludedMarkdown::expand(&input_str, base_dir)
+    });
+    if let Some((_, error)) = included.as_ref().and_then(|included| included.errors.first()) {
+        return Err(format!("{input}: {error}", input = options.input.display()));
+    }
+    // Standalone Markdown files have no crate attributes, so the attributes of code blocks, like
+    // `required-features`, are enabled with `-Z crate-attr=feature(custom_code_classes_in_docs)`.
+    let custom_code_classes_in_docs =
+        crate_attr_enables_feature(&options, "custom_code_classes_in_docs");
     let mut collector = Collector::new(
         options.input.display().to_string(),
         options.clone(),
@@ -157,17 +187,37 @@ pub(crate) fn test(options: Options) -> Result<(), String> {
     );
     collector.set_position(DUMMY_SP);
     let codes = ErrorCodes::from(options.unstable_features.is_nightly_build());
-
-    // For markdown files, custom code classes will be disabled until the feature is enabled by default.
-    find_testable_code(
-        &input_str,
-        &mut collector,
-        codes,
-        options.enable_per_target_ignores,
-        None,
-        false,
-    );
+    let enable_per_target_ignores = options.enable_per_target_ignores;
+
+    match &included {
+        Some(included) => find_testable_code(
+            &included.text,
+            &mut IncludedTests { collector: &mut collector, included },
+            codes,
+            enable_per_target_ignores,
+            None,
+            custom_code_classes_in_docs,
+        ),
+        None => find_testable_code(
+            &input_str,
+            &mut collector,
+            codes,
+            enable_per_target_ignores,
+            None,
+            custom_code_classes_in_docs,
+        ),
+    }
 
     crate::doctest::run_tests(options.test_args, options.nocapture, collector.tests);
     Ok(())
 }
+
+/// Returns whether `feature` is enabled with `-Z crate-attr=feature(...)`.
+fn crate_attr_enables_feature(options: &Options, feature: &str) -> bool {
+    options.unstable_opts.crate_attr.iter().any(|attr| {
+        attr.trim()
+            .strip_prefix("feature(")
+            .and_then(|features| features.strip_suffix(')'))
+            .is_some_and(|features| features.split(',').any(|name| name.trim() == feature))
+    })
+}
@@ -1,15 +1,16 @@
 //! Calculates information used for the --show-coverage flag.
 use crate::clean;
 use crate::core::DocContext;
-use crate::html::markdown::{find_testable_code, ErrorCodes};
+use crate::html::markdown::{doc_outline, find_testable_code, ErrorCodes};
 use crate::passes::check_doc_test_visibility::{should_have_doc_example, Tests};
 use crate::passes::Pass;
 use crate::visit::DocVisitor;
 use rustc_hir as hir;
 use rustc_lint::builtin::MISSING_DOCS;
 use rustc_middle::lint::LintLevelSource;
+use rustc_middle::ty;
 use rustc_session::lint;
-use rustc_span::FileName;
+use rustc_span::{sym, FileName};
 use serde::Serialize;
 
 use std::collections::BTreeMap;
@@ -22,7 +23,8 @@
 };
 
 fn calculate_doc_coverage(krate: clean::Crate, ctx: &mut DocContext<'_>) -> clean::Crate {
-    let mut calc = CoverageCalculator { items: Default::default(), ctx };
+    let mut calc =
+        CoverageCalculator { items: Default::default(), details: Default::default(), ctx };
     calc.visit_crate(&krate);
 
     calc.print_results();
@@ -100,8 +102,50 @@ fn add_assign(&mut self, rhs: Self) {
     }
 }
 
+/// How complete the documentation of an item is, emitted with `--show-coverage-details`.
+#[derive(Serialize, Debug)]
+struct ItemDetails {
+    /// The path of the item, like `foo::Bar::new`.
+    name: String,
+    kind: &'static str,
+    line: usize,
+    has_docs: bool,
+    has_example: bool,
+    /// Whether the item is expected to have an example, see [`should_have_doc_example`].
+    needs_example: bool,
+    /// Whether the docs have an `# Errors` section, for the functions returning a `Result`.
+    has_errors_section: Option<bool>,
+    /// The number of characters of the summary line of the docs.
+    summary_len: usize,
+    /// The fraction of the criteria above which apply to the item and are met, between 0 and 1.
+    score: f64,
+}
+
+impl ItemDetails {
+    fn compute_score(&mut self) {
+        let criteria = [
+            Some(self.has_docs),
+            (self.has_example || self.needs_example).then_some(self.has_example),
+            self.has_errors_section,
+        ];
+        let applicable = criteria.iter().flatten().count();
+        let met = criteria.iter().flatten().filter(|&&met| met).count();
+        self.score = met as f64 / applicable as f64;
+    }
+}
+
+/// The counts of a file, followed by the details of each of its items.
+#[derive(Serialize)]
+struct FileDetails<'a> {
+    #[serde(flatten)]
+    count: &'a ItemCount,
+    items: &'a [ItemDetails],
+}
+
 struct CoverageCalculator<'a, 'b> {
     items: BTreeMap<FileName, ItemCount>,
+    /// The details of the items of each file, only collected with `--show-coverage-details`.
+    details: BTreeMap<FileName, Vec<ItemDetails>>,
     ctx: &'a mut DocContext<'b>,
 }
 
@@ -117,6 +161,19 @@ fn limit_filename_len(filename: String) -> String {
 
 impl<'a, 'b> CoverageCalculator<'a, 'b> {
     fn to_json(&self) -> String {
+        if self.ctx.render_options.show_coverage_details {
+            return serde_json::to_string(
+                &self
+                    .items
+                    .iter()
+                    .map(|(k, count)| {
+                        let items = self.details.get(k).map_or(&[][..], |items| &items[..]);
+                        (k.prefer_local().to_string(), FileDetails { count, items })
+                    })
+                    .collect::<BTreeMap<String, FileDetails<'_>>>(),
+            )
+            .expect("failed to convert JSON data to string");
+        }
         serde_json::to_string(
             &self
                 .items
@@ -182,6 +239,34 @@ fn print_table_record(
         );
         print_table_line();
     }
+
+    fn item_path(&self, i: &clean::Item) -> String {
+        match i.item_id.as_def_id() {
+            Some(def_id) if !def_id.is_crate_root() => self.ctx.tcx.def_path_str(def_id),
+            _ => i.name.map(|name| name.to_string()).unwrap_or_default(),
+        }
+    }
+
+    /// Returns `true` if the item is a function returning a `Result`, whose docs should explain
+    /// when it fails in an `# Errors` section. The type aliases of `Result`, like `io::Result`,
+    /// are resolved through the signature of the function.
+    fn returns_result(&self, i: &clean::Item) -> bool {
+        if !matches!(
+            *i.kind,
+            clean::FunctionItem(_)
+                | clean::MethodItem(..)
+                | clean::TyMethodItem(_)
+                | clean::ForeignFunctionItem(_)
+        ) {
+            return false;
+        }
+        let Some(def_id) = i.item_id.as_def_id() else { return false };
+        let tcx = self.ctx.tcx;
+        match tcx.fn_sig(def_id).instantiate_identity().skip_binder().output().kind() {
+            ty::Adt(adt, _) => tcx.is_diagnostic_item(sym::Result, adt.did()),
+            _ => false,
+        }
+    }
 }
 
 impl<'a, 'b> DocVisitor for CoverageCalculator<'a, 'b> {
@@ -218,6 +303,7 @@ fn visit_item(&mut self, i: &clean::Item) {
                 );
 
                 let has_doc_example = tests.found_tests != 0;
+                let should_have_doc_example = should_have_doc_example(self.ctx, i);
                 let hir_id = DocContext::as_local_hir_id(self.ctx.tcx, i.item_id).unwrap();
                 let (level, source) = self.ctx.tcx.lint_level_at_node(MISSING_DOCS, hir_id);
 
@@ -259,10 +345,30 @@ fn visit_item(&mut self, i: &clean::Item) {
                 if let Some(span) = i.span(self.ctx.tcx) {
                     let filename = span.filename(self.ctx.sess());
                     debug!("counting {:?} {:?} in {filename:?}", i.type_(), i.name);
+                    if self.ctx.render_options.show_coverage_details
+                        && (has_docs || should_have_docs)
+                    {
+                        let outline = doc_outline(&i.doc_value());
+                        let mut details = ItemDetails {
+                            name: self.item_path(i),
+                            kind: i.type_().as_str(),
+                            line: span.lo(self.ctx.sess()).line,
+                            has_docs,
+                            has_example: has_doc_example,
+                            needs_example: should_have_doc_example,
+                            has_errors_section: self
+                                .returns_result(i)
+                                .then(|| outline.has_section("Errors")),
+                            summary_len: outline.summary_len,
+                            score: 0.,
+                        };
+                        details.compute_score();
+                        self.details.entry(filename.clone()).or_default().push(details);
+                    }
                     self.items.entry(filename).or_default().count_item(
                         has_docs,
                         has_doc_example,
-                        should_have_doc_example(self.ctx, i),
+                        should_have_doc_example,
                         should_have_docs,
                     );
                 }
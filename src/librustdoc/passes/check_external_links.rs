@@ -0,0 +1,201 @@
+//! Checks that the external `http` and `https` links found in the documentation are still
+//! reachable.
+//!
+//! This pass is only run when `--check-external-links` is passed, since it needs network access.
+//! The links are collected while visiting the items, and checked once all items have been
+//! visited. Dead links are reported with the `unreachable_external_links` lint, while the links
+//! which could not be checked in time are counted in a note.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::HirId;
+use rustc_resolve::rustdoc::source_span_for_markdown_range;
+use rustc_span::Span;
+
+use crate::clean::*;
+use crate::core::DocContext;
+use crate::html::markdown::markdown_links;
+use crate::passes::lint::malformed_external_urls::url_scheme;
+use crate::passes::Pass;
+use crate::visit::DocVisitor;
+
+pub(crate) const CHECK_EXTERNAL_LINKS: Pass = Pass {
+    name: "check-external-links",
+    run: check_external_links,
+    description: "checks that external links in the documentation are reachable",
+};
+
+/// How long to wait for a host to accept a connection or to answer a request.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long checking all the links may take. The links which are left once it is over are not
+/// checked. Resolving the host names isn't covered by the timeouts, so this is only a best effort.
+const TOTAL_TIMEOUT: Duration = Duration::from_secs(60);
+
+fn check_external_links(krate: Crate, cx: &mut DocContext<'_>) -> Crate {
+    let mut collector = LinkCollector { cx, links: Vec::new() };
+    collector.visit_crate(&krate);
+    let links = collector.links;
+    report(cx, &links);
+    krate
+}
+
+struct Link {
+    url: String,
+    hir_id: HirId,
+    span: Span,
+}
+
+struct LinkCollector<'a, 'tcx> {
+    cx: &'a DocContext<'tcx>,
+    links: Vec<Link>,
+}
+
+impl<'a, 'tcx> DocVisitor for LinkCollector<'a, 'tcx> {
+    fn visit_item(&mut self, item: &Item) {
+        let tcx = self.cx.tcx;
+        // Only the links of the local docs can be reported.
+        if let Some(hir_id) = DocContext::as_local_hir_id(tcx, item.item_id) {
+            let dox = item.doc_value();
+            let links = markdown_links(&dox, |link| {
+                let scheme = url_scheme(&link.link)?;
+                (scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https"))
+                    .then_some(link)
+            });
+            for link in links {
+                let span = source_span_for_markdown_range(
+                    tcx,
+                    &dox,
+                    link.range.inner_range(),
+                    &item.attrs.doc_strings,
+                )
+                .unwrap_or_else(|| item.attr_span(tcx));
+                self.links.push(Link { url: link.link, hir_id, span });
+            }
+        }
+
+        self.visit_item_recur(item)
+    }
+}
+
+enum Status {
+    Reachable,
+    Dead(String),
+    /// There was no time left to check the link.
+    Unchecked,
+}
+
+/// Checks every distinct URL of `links` once, and reports the dead and unchecked links.
+fn report(cx: &DocContext<'_>, links: &[Link]) {
+    let tcx = cx.tcx;
+    let deadline = Instant::now() + TOTAL_TIMEOUT;
+    let mut statuses = FxHashMap::default();
+    let mut out_of_time = 0;
+    for Link { url, hir_id, span } in links {
+        let status = statuses.entry(url.as_str()).or_insert_with(|| {
+            debug!("checking external link {url}");
+            probe(url, deadline)
+        });
+        match status {
+            Status::Reachable => {}
+            Status::Dead(reason) => {
+                tcx.struct_span_lint_hir(
+                    crate::lint::UNREACHABLE_EXTERNAL_LINKS,
+                    *hir_id,
+                    *span,
+                    format!("unreachable external link `{url}`"),
+                    |lint| lint.note(reason.clone()),
+                );
+            }
+            Status::Unchecked => out_of_time += 1,
+        }
+    }
+
+    if out_of_time > 0 {
+        tcx.sess
+            .struct_note_without_error(format!("{out_of_time} link(s) were not checked"))
+            .note(format!(
+                "checking the external links is stopped after {} seconds",
+                TOTAL_TIMEOUT.as_secs()
+            ))
+            .emit();
+    }
+}
+
+/// Checks whether `url` can be reached before `deadline`.
+///
+/// Plain `http` URLs are requested with `HEAD` and are considered dead if the server answers with
+/// an error status. Since rustdoc doesn't ship a TLS implementation, `https` URLs are only
+/// considered dead if their host can't be resolved or doesn't accept connections.
+fn probe(url: &str, deadline: Instant) -> Status {
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return Status::Dead("missing URL scheme".to_owned());
+    };
+    let timeout = deadline.saturating_duration_since(Instant::now()).min(TIMEOUT);
+    if timeout.is_zero() {
+        return Status::Unchecked;
+    }
+    match request(rest, scheme.eq_ignore_ascii_case("https"), timeout) {
+        Ok(()) => Status::Reachable,
+        // The host may only have been too slow for what was left of the total time.
+        Err(_) if timeout < TIMEOUT => Status::Unchecked,
+        Err(reason) => Status::Dead(reason),
+    }
+}
+
+/// Requests the URL whose scheme is stripped in `rest`, waiting at most `timeout` for each step
+/// of the request. For `https` URLs, this only connects to the host; other URLs are requested
+/// with `HEAD`.
+fn request(rest: &str, https: bool, timeout: Duration) -> Result<(), String> {
+    let (authority, path) = match rest.find(|c| c == '/' || c == '?' || c == '#') {
+        Some(pos) => rest.split_at(pos),
+        None => (rest, "/"),
+    };
+    // The fragment is never sent to the server.
+    let path = path.split('#').next().unwrap_or_default();
+    let path = if path.starts_with('/') { path.to_owned() } else { format!("/{path}") };
+    let host_and_port = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+    let explicit_port = host_and_port
+        .rsplit_once(':')
+        .and_then(|(host, port)| Some((host, port.parse::<u16>().ok()?)));
+    let (host, port) = explicit_port.unwrap_or((host_and_port, if https { 443 } else { 80 }));
+    if host.is_empty() {
+        return Err("missing host".to_owned());
+    }
+
+    let addr = (host.trim_start_matches('[').trim_end_matches(']'), port)
+        .to_socket_addrs()
+        .map_err(|err| format!("cannot resolve `{host}`: {err}"))?
+        .next()
+        .ok_or_else(|| format!("`{host}` does not resolve to any address"))?;
+    let mut stream = TcpStream::connect_timeout(&addr, timeout)
+        .map_err(|err| format!("cannot connect to `{host}`: {err}"))?;
+    if https {
+        return Ok(());
+    }
+
+    let io_err = |err: std::io::Error| format!("request failed: {err}");
+    stream.set_read_timeout(Some(timeout)).map_err(io_err)?;
+    stream.set_write_timeout(Some(timeout)).map_err(io_err)?;
+    write!(
+        stream,
+        "HEAD {path} HTTP/1.1\r\nHost: {host_and_port}\r\nUser-Agent: rustdoc\r\nConnection: close\r\n\r\n",
+    )
+    .map_err(io_err)?;
+    let mut status_line = String::new();
+    BufReader::new(stream).read_line(&mut status_line).map_err(io_err)?;
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse::<u16>().ok())
+        .ok_or_else(|| format!("invalid HTTP response: `{}`", status_line.trim_end()))?;
+    // Some servers don't implement `HEAD` but would still serve the page.
+    if status >= 400 && status != 405 {
+        Err(format!("server responded with status {status}"))
+    } else {
+        Ok(())
+    }
+}
@@ -20,6 +20,7 @@
     source_span_for_markdown_range, strip_generics_from_path, MalformedGenerics,
 };
 use rustc_session::lint::Lint;
+use rustc_span::edit_distance::find_best_match_for_name;
 use rustc_span::hygiene::MacroKind;
 use rustc_span::symbol::{sym, Ident, Symbol};
 use rustc_span::BytePos;
@@ -33,7 +34,7 @@
 use crate::clean::{self, utils::find_nearest_parent_module};
 use crate::clean::{Crate, Item, ItemLink, PrimitiveType};
 use crate::core::DocContext;
-use crate::html::markdown::{markdown_links, MarkdownLink, MarkdownLinkRange};
+use crate::html::markdown::{admonition_kind, markdown_links, MarkdownLink, MarkdownLinkRange};
 use crate::lint::{BROKEN_INTRA_DOC_LINKS, PRIVATE_INTRA_DOC_LINKS};
 use crate::passes::Pass;
 use crate::visit::DocVisitor;
@@ -251,6 +252,7 @@ struct DiagnosticInfo<'a> {
     dox: &'a str,
     ori_link: &'a str,
     link_range: MarkdownLinkRange,
+    display_text_range: Option<Range<usize>>,
 }
 
 struct LinkCollector<'a, 'tcx> {
@@ -703,6 +705,48 @@ fn resolve_associated_item(
             _ => Vec::new(),
         }
     }
+
+    /// Returns the names of the items which can follow `parent` in a path, or of the items of the
+    /// module `module_id` if no part of the path resolved, to suggest similarly named items for
+    /// unresolved links.
+    fn similar_name_candidates(&self, parent: Option<Res>, module_id: DefId) -> Vec<Symbol> {
+        let tcx = self.cx.tcx;
+
+        match parent {
+            None | Some(Res::Def(DefKind::Mod, _)) => {
+                let module = parent.and_then(|res| res.def_id(tcx)).unwrap_or(module_id);
+                let children = match module.as_local() {
+                    Some(module) => tcx.module_children_local(module),
+                    None => tcx.module_children(module),
+                };
+                children
+                    .iter()
+                    .filter(|child| module.is_local() || child.vis.is_public())
+                    .map(|child| child.ident.name)
+                    .collect()
+            }
+            Some(Res::Def(DefKind::Struct | DefKind::Union | DefKind::Enum, did)) => {
+                let mut names = Vec::new();
+                if let ty::Adt(def, _) = tcx.type_of(did).instantiate_identity().kind() {
+                    if def.is_enum() {
+                        names.extend(def.variants().iter().map(|variant| variant.name));
+                    } else {
+                        names.extend(def.non_enum_variant().fields.iter().map(|field| field.name));
+                    }
+                }
+                for &imp in tcx.inherent_impls(did) {
+                    names.extend(
+                        tcx.associated_items(imp).in_definition_order().map(|item| item.name),
+                    );
+                }
+                names
+            }
+            Some(Res::Def(DefKind::Trait, did)) => {
+                tcx.associated_items(did).in_definition_order().map(|item| item.name).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
 }
 
 fn full_res(tcx: TyCtxt<'_>, (base, assoc_item): (Res, Option<DefId>)) -> Res {
@@ -889,6 +933,11 @@ fn preprocess_link(
         return None;
     }
 
+    // `[!NOTE]` and the like start an admonition.
+    if admonition_kind(&ori_link.link).is_some() {
+        return None;
+    }
+
     // Bail early for real links.
     if ori_link.link.contains('/') {
         return None;
@@ -1022,6 +1071,7 @@ fn resolve_link(
             dox,
             ori_link: &ori_link.link,
             link_range: ori_link.range.clone(),
+            display_text_range: ori_link.display_text_range.clone(),
         };
         let PreprocessingInfo { path_str, disambiguator, extra_fragment, link_text } =
             pp_link.as_ref().map_err(|err| err.report(self.cx, diag_info.clone())).ok()?;
@@ -1689,7 +1739,7 @@ fn report_diagnostic(
     tcx: TyCtxt<'_>,
     lint: &'static Lint,
     msg: impl Into<DiagnosticMessage> + Display,
-    DiagnosticInfo { item, ori_link: _, dox, link_range }: &DiagnosticInfo<'_>,
+    DiagnosticInfo { item, dox, link_range, .. }: &DiagnosticInfo<'_>,
     decorate: impl FnOnce(&mut Diagnostic, Option<rustc_span::Span>, MarkdownLinkRange),
 ) {
     let Some(hir_id) = DocContext::as_local_hir_id(tcx, item.item_id) else {
@@ -1837,6 +1887,27 @@ fn split(path: &str) -> Option<(&str, &str)> {
                         *unresolved = end.into();
                     }
 
+                    // Suggest an item with a similar name if the last segment of the path is the
+                    // one which didn't resolve. Very short names are usually placeholders.
+                    if unresolved.len() >= 3
+                        && let Some(prefix) = path_str.strip_suffix(&**unresolved)
+                        && let Some(similar) = find_best_match_for_name(
+                            &collector.similar_name_candidates(*partial_res, module_id),
+                            Symbol::intern(unresolved),
+                            None,
+                        )
+                    {
+                        suggest_similar_path(
+                            tcx,
+                            diag,
+                            &diag_info,
+                            path_str,
+                            &format!("{prefix}{similar}"),
+                            sp,
+                            &link_range,
+                        );
+                    }
+
                     let last_found_module = match *partial_res {
                         Some(Res::Def(DefKind::Mod, id)) => Some(id),
                         None => Some(module_id),
@@ -2172,6 +2243,59 @@ fn suggest_disambiguator(
     }
 }
 
+/// Suggests replacing the unresolved `path_str` of a link with `similar_path`, in its destination
+/// and in its display text if it's the same path, like in ``[`Vec::psh`](Vec::psh)``.
+fn suggest_similar_path(
+    tcx: TyCtxt<'_>,
+    diag: &mut Diagnostic,
+    diag_info: &DiagnosticInfo<'_>,
+    path_str: &str,
+    similar_path: &str,
+    sp: Option<rustc_span::Span>,
+    link_range: &MarkdownLinkRange,
+) {
+    let help = "an item with a similar name exists";
+
+    let ori_link = match link_range {
+        MarkdownLinkRange::Destination(range) => Some(&diag_info.dox[range.clone()]),
+        MarkdownLinkRange::WholeLink(_) => None,
+    };
+    let (Some(sp), Some(ori_link)) = (sp, ori_link.filter(|link| link.contains(path_str))) else {
+        diag.help(format!("{help}: `[{similar_path}]`"));
+        return;
+    };
+
+    let mut suggestions = vec![(sp, ori_link.replacen(path_str, similar_path, 1))];
+    // For shortcut links, the display text is the destination, which is already replaced.
+    let dest_range = link_range.inner_range();
+    if let Some(range) = &diag_info.display_text_range
+        && (range.end <= dest_range.start || dest_range.end <= range.start)
+    {
+        let text = &diag_info.dox[range.clone()];
+        let trimmed = text.trim_start_matches(['`', ' ']);
+        let start = range.start + text.len() - trimmed.len();
+        let trimmed = trimmed.trim_end_matches(['`', ' ']);
+        let range = start..start + trimmed.len();
+        if trimmed == path_str
+            && let Some(text_sp) = source_span_for_markdown_range(
+                tcx,
+                diag_info.dox,
+                &range,
+                &diag_info.item.attrs.doc_strings,
+            )
+        {
+            suggestions.push((text_sp, similar_path.to_string()));
+        }
+    }
+
+    if suggestions.len() > 1 {
+        diag.multipart_suggestion(help, suggestions, Applicability::MaybeIncorrect);
+    } else {
+        let (sp, suggestion) = suggestions.pop().unwrap();
+        diag.span_suggestion_verbose(sp, help, suggestion, Applicability::MaybeIncorrect);
+    }
+}
+
 /// Report a link from a public item to a private one.
 fn privacy_error(cx: &DocContext<'_>, diag_info: &DiagnosticInfo<'_>, path_str: &str) {
     let sym;
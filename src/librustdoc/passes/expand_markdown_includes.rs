@@ -0,0 +1,50 @@
+//! Replaces the `{{#include path}}` directives of the documentation by the content of the files
+//! they name, so that the other passes and the renderers see the included Markdown.
+//!
+//! This pass is only run when `--markdown-includes` is passed. The links of the included files
+//! are resolved like the other links, since the resolver expands the directives too.
+
+use rustc_resolve::rustdoc::expand_fragment_includes;
+use rustc_span::Symbol;
+
+use crate::clean::{Crate, Item};
+use crate::core::DocContext;
+use crate::fold::DocFolder;
+use crate::passes::Pass;
+
+pub(crate) const EXPAND_MARKDOWN_INCLUDES: Pass = Pass {
+    name: "expand-markdown-includes",
+    run: expand_markdown_includes,
+    description: "replaces `{{#include}}` directives by the content of the files they name",
+};
+
+fn expand_markdown_includes(krate: Crate, cx: &mut DocContext<'_>) -> Crate {
+    IncludeExpander { cx }.fold_crate(krate)
+}
+
+struct IncludeExpander<'a, 'tcx> {
+    cx: &'a DocContext<'tcx>,
+}
+
+impl<'a, 'tcx> DocFolder for IncludeExpander<'a, 'tcx> {
+    fn fold_item(&mut self, mut item: Item) -> Option<Item> {
+        // The docs of other crates are left as is: their links were resolved without the
+        // included files.
+        if item.item_id.is_local() {
+            let sess = self.cx.tcx.sess;
+            let includes = expand_fragment_includes(sess.source_map(), &mut item.attrs.doc_strings);
+            for (span, include) in includes {
+                match include.result {
+                    // Like with `include_str!`, the docs need to be rebuilt when the file changes.
+                    Ok(_) => {
+                        let file = Symbol::intern(&include.file.to_string_lossy());
+                        sess.parse_sess.file_depinfo.borrow_mut().insert(file);
+                    }
+                    Err(err) => sess.span_err(span, err),
+                }
+            }
+        }
+
+        Some(self.fold_item_recur(item))
+    }
+}
@@ -2,8 +2,12 @@
 //! efficiency and simplicity.
 
 mod bare_urls;
+mod broken_std_doc_anchors;
 mod check_code_block_syntax;
 mod html_tags;
+mod lazy_list_continuation;
+pub(super) mod malformed_external_urls;
+mod missing_image_alt_text;
 mod redundant_explicit_links;
 mod unescaped_backticks;
 
@@ -17,10 +21,23 @@
 
 struct Linter<'a, 'tcx> {
     cx: &'a mut DocContext<'tcx>,
+    /// The external URLs found so far, if `--external-url-manifest` was passed.
+    url_manifest: Option<Vec<malformed_external_urls::ManifestEntry>>,
+    /// The local copy of the standard library docs, if there is one.
+    std_docs: Option<broken_std_doc_anchors::StdDocs>,
 }
 
 pub(crate) fn run_lints(krate: Crate, cx: &mut DocContext<'_>) -> Crate {
-    Linter { cx }.visit_crate(&krate);
+    let manifest_path = cx.render_options.external_url_manifest.clone();
+    let std_docs = broken_std_doc_anchors::StdDocs::new(cx);
+    let mut linter =
+        Linter { cx, url_manifest: manifest_path.as_ref().map(|_| Vec::new()), std_docs };
+    linter.visit_crate(&krate);
+    if let Some(path) = manifest_path
+        && let Some(manifest) = linter.url_manifest
+    {
+        malformed_external_urls::write_manifest(linter.cx, &path, &manifest);
+    }
     krate
 }
 
@@ -31,6 +48,12 @@ fn visit_item(&mut self, item: &Item) {
         html_tags::visit_item(self.cx, item);
         unescaped_backticks::visit_item(self.cx, item);
         redundant_explicit_links::visit_item(self.cx, item);
+        lazy_list_continuation::visit_item(self.cx, item);
+        malformed_external_urls::visit_item(self.cx, item, self.url_manifest.as_mut());
+        missing_image_alt_text::visit_item(self.cx, item);
+        if let Some(std_docs) = &mut self.std_docs {
+            broken_std_doc_anchors::visit_item(self.cx, item, std_docs);
+        }
 
         self.visit_item_recur(item)
     }
@@ -0,0 +1,86 @@
+//! Checks the anchors of links to the standard library docs on `doc.rust-lang.org`, e.g.
+//! `[push_str](https://doc.rust-lang.org/std/string/struct.String.html#method.push_str)`,
+//! against a local copy of these docs, so that links to sections which were renamed or removed
+//! are caught without any network access.
+//!
+//! The local copy is the one installed in the sysroot with the `rust-docs` component, or the one
+//! passed with `--std-docs-dir`. Links to pages it doesn't have are not checked.
+
+use crate::clean::*;
+use crate::core::DocContext;
+use crate::html::markdown::std_doc_anchor_links;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_resolve::rustdoc::source_span_for_markdown_range;
+use rustc_session::lint::Level;
+use std::fs;
+use std::path::PathBuf;
+
+/// The local copy of the standard library docs.
+pub(super) struct StdDocs {
+    dir: PathBuf,
+    /// The IDs of the pages read so far, or `None` for the pages which couldn't be read.
+    pages: FxHashMap<String, Option<FxHashSet<String>>>,
+}
+
+impl StdDocs {
+    /// Returns the standard library docs to check the links against, if they are available.
+    pub(super) fn new(cx: &DocContext<'_>) -> Option<StdDocs> {
+        let dir = match &cx.render_options.std_docs_dir {
+            Some(dir) => dir.clone(),
+            None => cx.tcx.sess.sysroot.join("share/doc/rust/html"),
+        };
+        dir.is_dir().then(|| StdDocs { dir, pages: FxHashMap::default() })
+    }
+
+    /// Returns the IDs of the elements of `page`, a path like `std/string/struct.String.html`.
+    fn ids(&mut self, page: &str) -> Option<&FxHashSet<String>> {
+        let dir = &self.dir;
+        self.pages
+            .entry(page.to_owned())
+            .or_insert_with(|| {
+                let html = fs::read_to_string(dir.join(page)).ok()?;
+                Some(
+                    html.split(" id=\"")
+                        .skip(1)
+                        .filter_map(|rest| rest.split_once('"'))
+                        .map(|(id, _)| id.to_owned())
+                        .collect(),
+                )
+            })
+            .as_ref()
+    }
+}
+
+pub(super) fn visit_item(cx: &DocContext<'_>, item: &Item, std_docs: &mut StdDocs) {
+    let tcx = cx.tcx;
+    let Some(hir_id) = DocContext::as_local_hir_id(tcx, item.item_id) else {
+        // If non-local, no need to check anything.
+        return;
+    };
+    // Don't read the pages of the standard library docs if nothing would be reported.
+    if tcx.lint_level_at_node(crate::lint::BROKEN_STD_DOC_ANCHORS, hir_id).0 == Level::Allow {
+        return;
+    }
+    let dox = item.doc_value();
+
+    for link in std_doc_anchor_links(&dox) {
+        let Some(ids) = std_docs.ids(&link.page) else { continue };
+        if ids.contains(&link.anchor) {
+            continue;
+        }
+        let span = source_span_for_markdown_range(
+            tcx,
+            &dox,
+            link.range.inner_range(),
+            &item.attrs.doc_strings,
+        )
+        .unwrap_or_else(|| item.attr_span(tcx));
+        tcx.struct_span_lint_hir(
+            crate::lint::BROKEN_STD_DOC_ANCHORS,
+            hir_id,
+            span,
+            format!("`{}` has no section with the ID `{}`", link.page, link.anchor),
+            |lint| lint.help("the item it pointed to may have been renamed or removed"),
+        );
+    }
+}
@@ -1,4 +1,5 @@
-//! Detects invalid HTML (like an unclosed `<span>`) in doc comments.
+//! Detects invalid HTML (like an unclosed `<span>`) in doc comments, as well as the tags which
+//! aren't HTML elements or which break the layout of the documentation pages (like `<body>`).
 use crate::clean::*;
 use crate::core::DocContext;
 use crate::html::markdown::main_body_opts;
@@ -170,6 +171,135 @@ pub(crate) fn visit_item(cx: &DocContext<'_>, item: &Item) {
     "source", "track", "wbr",
 ];
 
+/// The HTML elements which can be used in the documentation, sorted.
+const KNOWN_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "address",
+    "area",
+    "article",
+    "aside",
+    "audio",
+    "b",
+    "bdi",
+    "bdo",
+    "blockquote",
+    "br",
+    "button",
+    "canvas",
+    "caption",
+    "cite",
+    "code",
+    "col",
+    "colgroup",
+    "data",
+    "datalist",
+    "dd",
+    "del",
+    "details",
+    "dfn",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "embed",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "i",
+    "iframe",
+    "img",
+    "input",
+    "ins",
+    "kbd",
+    "label",
+    "legend",
+    "li",
+    "map",
+    "mark",
+    "math",
+    "menu",
+    "meter",
+    "nav",
+    "noscript",
+    "object",
+    "ol",
+    "optgroup",
+    "option",
+    "output",
+    "p",
+    "picture",
+    "pre",
+    "progress",
+    "q",
+    "rp",
+    "rt",
+    "ruby",
+    "s",
+    "samp",
+    "script",
+    "search",
+    "section",
+    "select",
+    "slot",
+    "small",
+    "source",
+    "span",
+    "strong",
+    "style",
+    "sub",
+    "summary",
+    "sup",
+    "svg",
+    "table",
+    "tbody",
+    "td",
+    "template",
+    "textarea",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "tr",
+    "track",
+    "u",
+    "ul",
+    "var",
+    "video",
+    "wbr",
+];
+
+/// The HTML elements which only belong in the `<head>` of a document or which structure the whole
+/// page: in the documentation, they are dropped by the browser or conflict with rustdoc's layout.
+const LAYOUT_BREAKING_TAGS: &[&str] =
+    &["base", "body", "frame", "frameset", "head", "html", "link", "main", "meta", "title"];
+
+/// Returns `true` if `tag` (in lowercase) is neither an HTML element nor a custom element.
+fn is_unknown_tag(tag: &str) -> bool {
+    // https://html.spec.whatwg.org/#valid-custom-element-name
+    !tag.contains('-')
+        && KNOWN_TAGS.binary_search(&tag).is_err()
+        && !LAYOUT_BREAKING_TAGS.contains(&tag)
+}
+
+/// Returns `true` if the tags of the given stack are in an `<svg>` or `<math>` element, which have
+/// their own elements.
+fn is_in_foreign_content(tags: &[(String, Range<usize>)]) -> bool {
+    tags.iter().any(|(tag, _)| tag.eq_ignore_ascii_case("svg") || tag.eq_ignore_ascii_case("math"))
+}
+
 fn drop_tag(
     tags: &mut Vec<(String, Range<usize>)>,
     tag_name: String,
@@ -184,6 +314,11 @@ fn drop_tag(
             let at = at.to_lowercase();
             at == "script" || at == "style"
         });
+        // Unclosed tags are reported on their own, so only the balanced tags are checked.
+        if !should_not_warn && !is_in_foreign_content(&tags[..pos]) && is_unknown_tag(&tag_name_low)
+        {
+            f(format!("unknown HTML tag `{}`", tags[pos].0), &tags[pos].1, false);
+        }
         for (last_tag_name, last_tag_span) in tags.drain(pos + 1..) {
             if should_not_warn {
                 continue;
@@ -320,6 +455,17 @@ fn extract_html_tag(
                     }
                     drop_tag(tags, tag_name, r, f);
                 } else {
+                    let tag_name_low = tag_name.to_lowercase();
+                    let in_script = tags.iter().any(|(at, _)| {
+                        at.eq_ignore_ascii_case("script") || at.eq_ignore_ascii_case("style")
+                    });
+                    if !in_script && LAYOUT_BREAKING_TAGS.contains(&tag_name_low.as_str()) {
+                        f(
+                            format!("HTML tag `{tag_name}` breaks the layout of the documentation"),
+                            &r,
+                            false,
+                        );
+                    }
                     let mut is_self_closing = false;
                     let mut quote_pos = None;
                     if c != '>' {
@@ -364,10 +510,7 @@ fn extract_html_tag(
                     if is_self_closing {
                         // https://html.spec.whatwg.org/#parse-error-non-void-html-element-start-tag-with-trailing-solidus
                         let valid = ALLOWED_UNCLOSED.contains(&&tag_name[..])
-                            || tags.iter().take(pos + 1).any(|(at, _)| {
-                                let at = at.to_lowercase();
-                                at == "svg" || at == "math"
-                            });
+                            || is_in_foreign_content(tags);
                         if !valid {
                             f(format!("invalid self-closing HTML tag `{tag_name}`"), &r, false);
                         }
@@ -0,0 +1,96 @@
+//! Detects list items continued on lines which are not indented, for example:
+//!
+//! ```markdown
+//! - the first item,
+//! which continues here
+//! ```
+//!
+//! Markdown treats such "lazy" lines as part of the item, but they look like they start a
+//! separate paragraph. Suggests indenting them like the first line of the item.
+
+use crate::clean::*;
+use crate::core::DocContext;
+use crate::html::markdown::main_body_opts;
+use pulldown_cmark::{Event, Parser, Tag};
+use rustc_errors::Applicability;
+use rustc_resolve::rustdoc::source_span_for_markdown_range;
+use std::mem;
+
+pub(super) fn visit_item(cx: &DocContext<'_>, item: &Item) {
+    let tcx = cx.tcx;
+    let Some(hir_id) = DocContext::as_local_hir_id(tcx, item.item_id) else {
+        // If non-local, no need to check anything.
+        return;
+    };
+    let dox = item.doc_value();
+    if dox.is_empty() {
+        return;
+    }
+
+    // The column where the content of each list item we're in starts, if it is known.
+    let mut item_indents: Vec<Option<usize>> = Vec::new();
+    let mut item_start = None;
+    let mut after_line_break = false;
+    for (event, range) in Parser::new_ext(&dox, main_body_opts()).into_offset_iter() {
+        if let Some(item_start) = item_start.take() {
+            // The content is only aligned with the first line if it starts on the same line as
+            // the list marker.
+            let indent =
+                (!dox[item_start..range.start].contains('\n')).then(|| column(&dox, range.start));
+            item_indents.push(indent);
+        }
+
+        if mem::take(&mut after_line_break)
+            && let Some(&Some(indent)) = item_indents.last()
+            && column(&dox, range.start) < indent
+        {
+            let missing_indent = indent - column(&dox, range.start);
+            let line_end = dox[range.start..].find('\n').map_or(dox.len(), |i| range.start + i);
+            let span = source_span_for_markdown_range(
+                tcx,
+                &dox,
+                &(range.start..line_end),
+                &item.attrs.doc_strings,
+            )
+            .unwrap_or_else(|| item.attr_span(tcx));
+            let insert_span = source_span_for_markdown_range(
+                tcx,
+                &dox,
+                &(range.start..range.start),
+                &item.attrs.doc_strings,
+            );
+            tcx.struct_span_lint_hir(
+                crate::lint::LAZY_LIST_CONTINUATION,
+                hir_id,
+                span,
+                "list item continuation is not indented",
+                |lint| {
+                    lint.help("if this is supposed to be its own paragraph, add a blank line");
+                    if let Some(insert_span) = insert_span {
+                        lint.span_suggestion_verbose(
+                            insert_span,
+                            "indent this line to continue the list item",
+                            " ".repeat(missing_indent),
+                            Applicability::MachineApplicable,
+                        );
+                    }
+                    lint
+                },
+            );
+        }
+
+        match event {
+            Event::Start(Tag::Item) => item_start = Some(range.start),
+            Event::End(Tag::Item) => {
+                item_indents.pop();
+            }
+            Event::SoftBreak | Event::HardBreak => after_line_break = true,
+            _ => {}
+        }
+    }
+}
+
+/// Returns the column of the byte at `offset` in `dox`.
+fn column(dox: &str, offset: usize) -> usize {
+    offset - dox[..offset].rfind('\n').map_or(0, |i| i + 1)
+}
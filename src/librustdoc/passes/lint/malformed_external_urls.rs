@@ -0,0 +1,182 @@
+//! Checks the syntax of external URLs in doc comments, e.g. `[docs](htps://example.com)` or
+//! `[docs](https://example.com#)`.
+//!
+//! When `--external-url-manifest` is passed, the URLs are also collected into a JSON file so that
+//! their liveness can be checked without rustdoc.
+
+use crate::clean::*;
+use crate::core::DocContext;
+use crate::html::markdown::{markdown_links, MarkdownLinkRange};
+use rustc_errors::Applicability;
+use rustc_resolve::rustdoc::source_span_for_markdown_range;
+use serde::Serialize;
+use std::path::Path;
+
+/// The URL schemes that are expected in documentation.
+const KNOWN_SCHEMES: &[&str] = &["http", "https", "ftp", "mailto"];
+
+/// An external URL found in the documentation, as written in the manifest.
+#[derive(Serialize)]
+pub(super) struct ManifestEntry {
+    url: String,
+    /// The path of the item whose documentation contains the URL.
+    item: String,
+    file: String,
+    line: usize,
+}
+
+pub(super) fn visit_item(
+    cx: &DocContext<'_>,
+    item: &Item,
+    mut manifest: Option<&mut Vec<ManifestEntry>>,
+) {
+    let tcx = cx.tcx;
+    let Some(hir_id) = DocContext::as_local_hir_id(tcx, item.item_id) else {
+        // If non-local, no need to check anything.
+        return;
+    };
+    let dox = item.doc_value();
+    let links = markdown_links(&dox, |link| url_scheme(&link.link).is_some().then_some(link));
+
+    for link in links {
+        let span = source_span_for_markdown_range(
+            tcx,
+            &dox,
+            link.range.inner_range(),
+            &item.attrs.doc_strings,
+        )
+        .unwrap_or_else(|| item.attr_span(tcx));
+
+        if let Some(error) = check_url(&link.link) {
+            tcx.struct_span_lint_hir(
+                crate::lint::MALFORMED_EXTERNAL_URLS,
+                hir_id,
+                span,
+                error.message(),
+                |lint| {
+                    match &error {
+                        UrlError::UnknownScheme(_) => {
+                            lint.note(format!(
+                                "the known URL schemes are {}",
+                                KNOWN_SCHEMES
+                                    .iter()
+                                    .map(|scheme| format!("`{scheme}`"))
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            ));
+                        }
+                        UrlError::MissingHost => {}
+                        UrlError::EmptyFragment | UrlError::Whitespace => {
+                            // The suggestion replaces the destination, so it can only be made if
+                            // we know where it is.
+                            if let MarkdownLinkRange::Destination(range) = &link.range
+                                && dox[range.clone()].trim_start_matches('<').trim_end_matches('>')
+                                    == link.link
+                            {
+                                let (msg, fixed) = if let UrlError::EmptyFragment = error {
+                                    ("remove the `#`", link.link[..link.link.len() - 1].to_owned())
+                                } else {
+                                    ("percent-encode the whitespace", encode_whitespace(&link.link))
+                                };
+                                lint.span_suggestion(
+                                    span,
+                                    msg,
+                                    fixed,
+                                    Applicability::MachineApplicable,
+                                );
+                            }
+                        }
+                    }
+                    lint
+                },
+            );
+        }
+
+        if let Some(manifest) = &mut manifest {
+            let loc = tcx.sess.source_map().lookup_char_pos(span.lo());
+            manifest.push(ManifestEntry {
+                url: link.link,
+                item: item
+                    .item_id
+                    .as_def_id()
+                    .map(|def_id| tcx.def_path_str(def_id))
+                    .unwrap_or_default(),
+                file: loc.file.name.prefer_local().to_string(),
+                line: loc.line,
+            });
+        }
+    }
+}
+
+/// Writes the URLs collected by [`visit_item`] to `path`, as a JSON array.
+pub(super) fn write_manifest(cx: &DocContext<'_>, path: &Path, manifest: &[ManifestEntry]) {
+    let json = serde_json::to_string(manifest).expect("failed to serialize the URL manifest");
+    if let Err(err) = std::fs::write(path, json) {
+        cx.tcx.sess.err(format!(
+            "failed to write the external URL manifest to `{}`: {err}",
+            path.display()
+        ));
+    }
+}
+
+enum UrlError {
+    UnknownScheme(String),
+    MissingHost,
+    EmptyFragment,
+    Whitespace,
+}
+
+impl UrlError {
+    fn message(&self) -> String {
+        match self {
+            UrlError::UnknownScheme(scheme) => format!("unknown URL scheme `{scheme}`"),
+            UrlError::MissingHost => "this URL has no host".to_owned(),
+            UrlError::EmptyFragment => "this URL has an empty fragment".to_owned(),
+            UrlError::Whitespace => "this URL contains whitespace".to_owned(),
+        }
+    }
+}
+
+/// Returns the scheme `url` starts with, like `https` for `https://example.com`.
+pub(crate) fn url_scheme(url: &str) -> Option<&str> {
+    let (scheme, rest) = url.split_once(':')?;
+    let mut chars = scheme.chars();
+    let is_scheme = chars.next()?.is_ascii_alphabetic()
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        // Paths like `std::vec::Vec` are intra-doc links.
+        && !rest.starts_with(':');
+    is_scheme.then_some(scheme)
+}
+
+fn check_url(url: &str) -> Option<UrlError> {
+    let scheme = url_scheme(url)?;
+    if !KNOWN_SCHEMES.iter().any(|known| known.eq_ignore_ascii_case(scheme)) {
+        return Some(UrlError::UnknownScheme(scheme.to_owned()));
+    }
+    if !scheme.eq_ignore_ascii_case("mailto") {
+        let rest = &url[scheme.len() + 1..];
+        let host = rest.strip_prefix("//").and_then(|rest| rest.split(['/', '?', '#']).next());
+        if host.map_or(true, str::is_empty) {
+            return Some(UrlError::MissingHost);
+        }
+    }
+    if url.contains(|c: char| c.is_ascii_whitespace()) {
+        return Some(UrlError::Whitespace);
+    }
+    if url.ends_with('#') {
+        return Some(UrlError::EmptyFragment);
+    }
+    None
+}
+
+fn encode_whitespace(url: &str) -> String {
+    let mut encoded = String::with_capacity(url.len());
+    for c in url.chars() {
+        if c.is_ascii_whitespace() {
+            encoded.push_str(&format!("%{:02X}", c as u8));
+        } else {
+            encoded.push(c);
+        }
+    }
+    encoded
+}
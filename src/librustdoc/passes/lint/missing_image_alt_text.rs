@@ -0,0 +1,62 @@
+//! Detects images without alt text, for example:
+//!
+//! ```markdown
+//! ![](diagrams/layout.png)
+//! ```
+//!
+//! Without alt text, screen readers can only announce that there is an image, and nothing is
+//! shown in its place when it fails to load.
+
+use crate::clean::*;
+use crate::core::DocContext;
+use crate::html::markdown::main_body_opts;
+use pulldown_cmark::{Event, Parser, Tag};
+use rustc_resolve::rustdoc::source_span_for_markdown_range;
+use std::ops::Range;
+
+pub(super) fn visit_item(cx: &DocContext<'_>, item: &Item) {
+    let tcx = cx.tcx;
+    let Some(hir_id) = DocContext::as_local_hir_id(tcx, item.item_id) else {
+        // If non-local, no need to check anything.
+        return;
+    };
+    let dox = item.doc_value();
+    if dox.is_empty() {
+        return;
+    }
+
+    // The images we're in, with whether text was found in them so far. The text of the nested
+    // images is also the alt text of the outer ones.
+    let mut images: Vec<(Range<usize>, bool)> = Vec::new();
+    for (event, range) in Parser::new_ext(&dox, main_body_opts()).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Image(..)) => images.push((range, false)),
+            Event::Text(text) | Event::Code(text) if !text.trim().is_empty() => {
+                for (_, has_text) in &mut images {
+                    *has_text = true;
+                }
+            }
+            Event::End(Tag::Image(..)) => {
+                let Some((range, has_text)) = images.pop() else { continue };
+                if has_text {
+                    continue;
+                }
+                let span =
+                    source_span_for_markdown_range(tcx, &dox, &range, &item.attrs.doc_strings)
+                        .unwrap_or_else(|| item.attr_span(tcx));
+                tcx.struct_span_lint_hir(
+                    crate::lint::MISSING_IMAGE_ALT_TEXT,
+                    hir_id,
+                    span,
+                    "image has no alt text",
+                    |lint| {
+                        lint.help(
+                            "describe the image between the brackets, like `![A diagram](...)`",
+                        )
+                    },
+                );
+            }
+            _ => {}
+        }
+    }
+}
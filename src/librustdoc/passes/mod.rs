@@ -38,6 +38,12 @@
 mod check_custom_code_classes;
 pub(crate) use self::check_custom_code_classes::CHECK_CUSTOM_CODE_CLASSES;
 
+mod check_external_links;
+pub(crate) use self::check_external_links::CHECK_EXTERNAL_LINKS;
+
+mod expand_markdown_includes;
+pub(crate) use self::expand_markdown_includes::EXPAND_MARKDOWN_INCLUDES;
+
 /// A single pass over the cleaned documentation.
 ///
 /// Runs in the compiler context, so it has access to types and traits and the like.
@@ -65,10 +71,15 @@ pub(crate) enum Condition {
     WhenNotDocumentPrivate,
     /// When `--document-hidden-items` is not passed.
     WhenNotDocumentHidden,
+    /// When `--check-external-links` is passed.
+    WhenCheckExternalLinks,
+    /// When `--markdown-includes` is passed.
+    WhenMarkdownIncludes,
 }
 
 /// The full list of passes.
 pub(crate) const PASSES: &[Pass] = &[
+    EXPAND_MARKDOWN_INCLUDES,
     CHECK_CUSTOM_CODE_CLASSES,
     CHECK_DOC_TEST_VISIBILITY,
     STRIP_HIDDEN,
@@ -79,10 +90,12 @@ pub(crate) enum Condition {
     COLLECT_TRAIT_IMPLS,
     CALCULATE_DOC_COVERAGE,
     RUN_LINTS,
+    CHECK_EXTERNAL_LINKS,
 ];
 
 /// The list of passes run by default.
 pub(crate) const DEFAULT_PASSES: &[ConditionalPass] = &[
+    ConditionalPass::new(EXPAND_MARKDOWN_INCLUDES, WhenMarkdownIncludes),
     ConditionalPass::always(CHECK_CUSTOM_CODE_CLASSES),
     ConditionalPass::always(COLLECT_TRAIT_IMPLS),
     ConditionalPass::always(CHECK_DOC_TEST_VISIBILITY),
@@ -92,10 +105,12 @@ pub(crate) enum Condition {
     ConditionalPass::always(COLLECT_INTRA_DOC_LINKS),
     ConditionalPass::always(PROPAGATE_DOC_CFG),
     ConditionalPass::always(RUN_LINTS),
+    ConditionalPass::new(CHECK_EXTERNAL_LINKS, WhenCheckExternalLinks),
 ];
 
 /// The list of default passes run when `--doc-coverage` is passed to rustdoc.
 pub(crate) const COVERAGE_PASSES: &[ConditionalPass] = &[
+    ConditionalPass::new(EXPAND_MARKDOWN_INCLUDES, WhenMarkdownIncludes),
     ConditionalPass::new(STRIP_HIDDEN, WhenNotDocumentHidden),
     ConditionalPass::new(STRIP_PRIVATE, WhenNotDocumentPrivate),
     ConditionalPass::always(CALCULATE_DOC_COVERAGE),
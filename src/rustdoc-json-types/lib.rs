@@ -8,7 +8,7 @@
 use std::path::PathBuf;
 
 /// rustdoc format-version.
-pub const FORMAT_VERSION: u32 = 27;
+pub const FORMAT_VERSION: u32 = 28;
 
 /// A `Crate` is the root of the emitted JSON blob. It contains all type/documentation information
 /// about the language items in the local crate, as well as info about external items to allow
@@ -80,6 +80,12 @@ pub struct Item {
     pub docs: Option<String>,
     /// This mapping resolves [intra-doc links](https://github.com/rust-lang/rfcs/blob/master/text/1946-intra-rustdoc-links.md) from the docstring to their IDs
     pub links: FxHashMap<String, Id>,
+    /// A simplified structured representation of [`docs`](Self::docs), only present when rustdoc
+    /// is passed `--json-structured-docs`.
+    pub structured_docs: Option<Vec<DocBlock>>,
+    /// The summary of [`docs`](Self::docs), its first paragraph or heading shortened to about 60
+    /// characters, only present when rustdoc is passed `--json-structured-docs`.
+    pub summary: Option<Vec<SummarySpan>>,
     /// Stringified versions of the attributes on this item (e.g. `"#[inline]"`)
     pub attrs: Vec<String>,
     pub deprecation: Option<Deprecation>,
@@ -96,6 +102,74 @@ pub struct Span {
     pub end: (usize, usize),
 }
 
+/// A block of the documentation of an item, see [`Item::structured_docs`].
+///
+/// The blocks are listed in the order in which they appear in the documentation. Containers like
+/// lists, block quotes and tables are not represented: only the blocks they contain are.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DocBlock {
+    pub kind: DocBlockKind,
+    /// The byte range of the block in the [`docs`](Item::docs) of the item, as `(start, end)`.
+    pub range: (usize, usize),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DocBlockKind {
+    /// A heading, with its level from 1 (`#`) to 6 (`######`).
+    Heading {
+        level: u8,
+        /// The ID derived from the text of the heading, which the HTML output gives it when the
+        /// item is documented on a page of its own.
+        id: String,
+        content: Vec<DocInline>,
+    },
+    Paragraph(Vec<DocInline>),
+    CodeBlock {
+        /// The lang string of the code block, e.g. `"rust,ignore"`. Absent for indented code
+        /// blocks, and `Some("")` for fenced code blocks without one.
+        lang_string: Option<String>,
+        /// Whether the code block is Rust code, which rustdoc highlights and tests.
+        rust: bool,
+        /// The code, as written in the documentation (hidden lines of Rust code included).
+        code: String,
+    },
+}
+
+/// Inline content of a [`DocBlock`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DocInline {
+    Text(String),
+    /// Inline code, like `` `Vec<T>` ``.
+    Code(String),
+    Emphasis(Vec<DocInline>),
+    Strong(Vec<DocInline>),
+    Strikethrough(Vec<DocInline>),
+    Link {
+        content: Vec<DocInline>,
+        /// The destination of the link, as written in the documentation.
+        destination: String,
+        /// The item this link resolves to, if it is an intra-doc link. See [`Item::links`].
+        id: Option<Id>,
+    },
+    /// A line break inside a paragraph.
+    LineBreak,
+}
+
+/// A run of the text of the [`summary`](Item::summary) of an item with the same style and link.
+/// Emphasis is not represented.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SummarySpan {
+    pub text: String,
+    /// Whether the text is inline code, like `` `Vec<T>` ``.
+    pub code: bool,
+    /// The destination of the link the text is part of, as written in the documentation.
+    pub destination: Option<String>,
+    /// The item this link resolves to, if it is an intra-doc link. See [`Item::links`].
+    pub id: Option<Id>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Deprecation {
     pub since: Option<String>,
@@ -36,6 +36,8 @@ fn errors_on_missing_links() {
                 visibility: Visibility::Public,
                 docs: None,
                 links: FxHashMap::from_iter([("Not Found".to_owned(), id("1"))]),
+                structured_docs: None,
+                summary: None,
                 attrs: vec![],
                 deprecation: None,
                 inner: ItemEnum::Module(Module {
@@ -83,6 +85,8 @@ fn errors_on_local_in_paths_and_not_index() {
                     visibility: Visibility::Public,
                     docs: None,
                     links: FxHashMap::from_iter([(("prim@i32".to_owned(), id("0:1:1571")))]),
+                    structured_docs: None,
+                    summary: None,
                     attrs: Vec::new(),
                     deprecation: None,
                     inner: ItemEnum::Module(Module {
@@ -102,6 +106,8 @@ fn errors_on_local_in_paths_and_not_index() {
                     visibility: Visibility::Public,
                     docs: None,
                     links: FxHashMap::default(),
+                    structured_docs: None,
+                    summary: None,
                     attrs: Vec::new(),
                     deprecation: None,
                     inner: ItemEnum::Primitive(Primitive { name: "i32".to_owned(), impls: vec![] }),
@@ -146,6 +152,8 @@ fn checks_local_crate_id_is_correct() {
                 visibility: Visibility::Public,
                 docs: None,
                 links: FxHashMap::default(),
+                structured_docs: None,
+                summary: None,
                 attrs: Vec::new(),
                 deprecation: None,
                 inner: ItemEnum::Module(Module {
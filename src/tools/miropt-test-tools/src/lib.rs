@@ -91,7 +91,7 @@ pub fn files_for_miropt_test(
                 from_file = format!("{}.{}.mir", test_name, first_pass);
                 to_file = Some(second_file);
             } else {
-                let ext_re = regex::Regex::new(r#"(\.(mir|dot|html))$"#).unwrap();
+                let ext_re = regex::Regex::new(r#"(\.(mir|dot|html|cfg\.json))$"#).unwrap();
                 let cap = ext_re
                     .captures_iter(test_name)
                     .next()
@@ -103,10 +103,16 @@ pub fn files_for_miropt_test(
                 from_file = test_name.to_string();
                 assert!(test_names.next().is_none(), "two mir pass names specified for MIR dump");
                 to_file = None;
-                // the pass name is the third to last string in the test name
-                // this gets pushed into passes
+                // the pass name is the second to last string in the test name, once the
+                // extension is removed: this gets pushed into passes
                 passes.push(
-                    test_name.split('.').rev().nth(2).expect("invalid test format").to_string(),
+                    test_name
+                        .trim_end_matches(extension)
+                        .split('.')
+                        .rev()
+                        .nth(1)
+                        .expect("invalid test format")
+                        .to_string(),
                 );
             };
             if !expected_file.starts_with(&test_crate) {
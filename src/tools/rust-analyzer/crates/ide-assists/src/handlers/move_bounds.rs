@@ -67,7 +67,7 @@ pub(crate) fn move_bounds_to_where_clause(
                 };
                 if let Some(tbl) = type_param.type_bound_list() {
                     if let Some(predicate) = build_predicate(type_param) {
-                        where_clause.add_predicate(predicate)
+                        where_clause.merge_predicate(predicate)
                     }
                     tbl.remove()
                 }
@@ -97,6 +97,15 @@ fn move_bounds_to_where_clause_fn() {
         );
     }
 
+    #[test]
+    fn move_bounds_to_where_clause_merges_bounds() {
+        check_assist(
+            move_bounds_to_where_clause,
+            r#"fn foo<$0T: Clone + Copy, U: Copy>() where T: Copy, U: Clone {}"#,
+            r#"fn foo<T, U>() where T: Copy + Clone, U: Clone + Copy {}"#,
+        );
+    }
+
     #[test]
     fn move_bounds_to_where_clause_impl() {
         check_assist(
@@ -301,6 +301,76 @@ pub fn add_predicate(&self, predicate: ast::WherePred) {
         }
         ted::append_child(self.syntax(), predicate.syntax());
     }
+
+    /// Adds the bounds of `predicate` to the predicate bounding the same type or lifetime, if
+    /// there is one, skipping the bounds it already has. Otherwise `predicate` is added as is.
+    pub fn merge_predicate(&self, predicate: ast::WherePred) {
+        let same_bounded = |it: &ast::WherePred| {
+            it.generic_param_list().map(|it| it.to_string())
+                == predicate.generic_param_list().map(|it| it.to_string())
+                && it.lifetime().map(|it| it.to_string())
+                    == predicate.lifetime().map(|it| it.to_string())
+                && it.ty().map(|it| it.to_string()) == predicate.ty().map(|it| it.to_string())
+        };
+        let existing = self.predicates().find(same_bounded).and_then(|it| it.type_bound_list());
+        let (Some(existing), Some(new_bounds)) = (existing, predicate.type_bound_list()) else {
+            return self.add_predicate(predicate);
+        };
+        for bound in new_bounds.bounds() {
+            let bound_text = bound.syntax().to_string();
+            if existing.bounds().all(|it| it.syntax().to_string() != bound_text) {
+                existing.push_bound(bound.clone_subtree().clone_for_update());
+            }
+        }
+    }
+}
+
+impl Removable for ast::WherePred {
+    /// Removes the predicate and the comma separating it from its neighbour. The where clause is
+    /// removed too if it becomes empty.
+    fn remove(&self) {
+        let Some(where_clause) = self.syntax().parent().and_then(ast::WhereClause::cast) else {
+            return ted::remove(self.syntax());
+        };
+        if let Some(previous) = self.syntax().prev_sibling() {
+            if let Some(next_token) = previous.next_sibling_or_token() {
+                ted::remove_all(next_token..=self.syntax().clone().into());
+            }
+        } else if let Some(next) = self.syntax().next_sibling() {
+            if let Some(next_token) = next.prev_sibling_or_token() {
+                ted::remove_all(self.syntax().clone().into()..=next_token);
+            }
+        } else {
+            ted::remove(self.syntax());
+        }
+
+        if where_clause.predicates().next().is_none() {
+            if let Some(ws) = where_clause.syntax().prev_sibling_or_token() {
+                if ws.kind() == WHITESPACE {
+                    ted::remove(ws);
+                }
+            }
+            ted::remove(where_clause.syntax());
+        }
+    }
+}
+
+impl ast::TypeBoundList {
+    pub fn push_bound(&self, bound: ast::TypeBound) {
+        match self.bounds().last() {
+            Some(last_bound) => {
+                let position = Position::after(last_bound.syntax());
+                let elements = vec![
+                    make::tokens::single_space().into(),
+                    make::token(T![+]).into(),
+                    make::tokens::single_space().into(),
+                    bound.syntax().clone().into(),
+                ];
+                ted::insert_all(position, elements);
+            }
+            None => ted::append_child(self.syntax(), bound.syntax()),
+        }
+    }
 }
 
 impl ast::TypeParam {
@@ -867,6 +937,67 @@ fn check_create_gpl<N: GenericParamsOwnerEdit + fmt::Display>(before: &str, afte
         check_create_gpl::<ast::Enum>("enum E {", "enum E<> {");
     }
 
+    #[test]
+    fn test_merge_where_predicate() {
+        fn check_merge(before: &str, predicate: &str, after: &str) {
+            let fn_ = ast_mut_from_text::<ast::Fn>(before);
+            let predicate =
+                ast_mut_from_text::<ast::WherePred>(&format!("fn f() where {predicate} {{}}"));
+            let predicate = predicate.clone_subtree().clone_for_update();
+            fn_.get_or_create_where_clause().merge_predicate(predicate);
+            assert_eq!(fn_.to_string(), after);
+        }
+
+        check_merge("fn foo<T>() {}", "T: Copy", "fn foo<T>() where T: Copy {}");
+        check_merge(
+            "fn foo<T>() where T: Copy {}",
+            "T: Clone",
+            "fn foo<T>() where T: Copy + Clone {}",
+        );
+        check_merge(
+            "fn foo<T>() where T: Copy {}",
+            "T: Copy + Clone",
+            "fn foo<T>() where T: Copy + Clone {}",
+        );
+        check_merge(
+            "fn foo<T, U>() where T: Copy {}",
+            "U: Copy",
+            "fn foo<T, U>() where T: Copy, U: Copy {}",
+        );
+        check_merge(
+            "fn foo<'a, 'b>() where 'a: 'b {}",
+            "'a: 'static",
+            "fn foo<'a, 'b>() where 'a: 'b + 'static {}",
+        );
+        check_merge(
+            "fn foo<T>() where T: Copy {}",
+            "for<'a> T: Fn(&'a u8)",
+            "fn foo<T>() where T: Copy, for<'a> T: Fn(&'a u8) {}",
+        );
+    }
+
+    #[test]
+    fn test_remove_where_predicate() {
+        fn check_remove(before: &str, index: usize, after: &str) {
+            let fn_ = ast_mut_from_text::<ast::Fn>(before);
+            fn_.where_clause().unwrap().predicates().nth(index).unwrap().remove();
+            assert_eq!(fn_.to_string(), after);
+        }
+
+        check_remove(
+            "fn foo<T, U>() where T: Copy, U: Clone {}",
+            0,
+            "fn foo<T, U>() where U: Clone {}",
+        );
+        check_remove(
+            "fn foo<T, U>() where T: Copy, U: Clone {}",
+            1,
+            "fn foo<T, U>() where T: Copy {}",
+        );
+        check_remove("fn foo<T>() where T: Copy {}", 0, "fn foo<T>() {}");
+        check_remove("fn foo<T>() where T: Copy, {}", 0, "fn foo<T>() {}");
+    }
+
     #[test]
     fn test_increase_indent() {
         let arm_list = ast_mut_from_text::<ast::Fn>(
@@ -0,0 +1,49 @@
+// We specify incremental here because we want to test the partitioning for
+// incremental compilation
+// incremental
+// compile-flags:-Zprint-mono-items=lazy
+// compile-flags:-Ccodegen-units=2
+
+#![crate_type = "rlib"]
+#![feature(codegen_unit)]
+
+// This test makes sure that the items with the same `#[codegen_unit]` name are
+// placed in a single CGU, regardless of their module, and that they stay
+// together when CGUs are merged.
+//
+// The `hint.hot` CGU is the smallest, so it is merged into `ddd`, while `ccc`
+// is supposed to stay untouched.
+
+pub mod hot_a {
+    //~ MONO_ITEM fn hot_a::foo @@ codegen_unit_attr-ddd--codegen_unit_attr-hint.hot[External]
+    #[codegen_unit = "hot"]
+    pub fn foo(a: u64) -> u64 {
+        a
+    }
+
+    //~ MONO_ITEM static hot_a::HOT @@ codegen_unit_attr-ddd--codegen_unit_attr-hint.hot[External]
+    #[codegen_unit = "hot"]
+    pub static HOT: u64 = 0;
+}
+
+pub mod hot_b {
+    //~ MONO_ITEM fn hot_b::foo @@ codegen_unit_attr-ddd--codegen_unit_attr-hint.hot[External]
+    #[codegen_unit = "hot"]
+    pub fn foo(a: u64) -> u64 {
+        a
+    }
+}
+
+pub mod ccc {
+    //~ MONO_ITEM fn ccc::foo @@ codegen_unit_attr-ccc[External]
+    pub fn foo(a: u64, b: u64, c: u64, d: u64) -> u64 {
+        a + b + c + d + 1
+    }
+}
+
+pub mod ddd {
+    //~ MONO_ITEM fn ddd::foo @@ codegen_unit_attr-ddd--codegen_unit_attr-hint.hot[External]
+    pub fn foo(a: u64, b: u64, c: u64) -> u64 {
+        a + b + c + 1
+    }
+}
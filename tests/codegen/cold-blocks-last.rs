@@ -0,0 +1,24 @@
+// compile-flags: -C no-prepopulate-passes -C opt-level=0
+// Check that the blocks of the cold path are laid out after the hot ones.
+
+#![crate_type = "lib"]
+
+#[cold]
+#[inline(never)]
+#[no_mangle]
+pub fn cold_path() {}
+
+// CHECK-LABEL: @branch(
+#[no_mangle]
+pub fn branch(x: bool) -> u32 {
+    // CHECK: br i1
+    // CHECK-NOT: call void @cold_path()
+    // CHECK: ret i32
+    // CHECK: call void @cold_path()
+    if x {
+        cold_path();
+        1
+    } else {
+        2
+    }
+}
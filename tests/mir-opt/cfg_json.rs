@@ -0,0 +1,37 @@
+// Test the summary of the control-flow graph dumped with `-Z dump-mir-cfg-json`
+// compile-flags: -Z dump-mir-cfg-json
+
+#![feature(custom_mir, core_intrinsics)]
+
+extern crate core;
+use core::intrinsics::mir::*;
+
+// EMIT_MIR cfg_json.looping.built.after.cfg.json
+#[custom_mir(dialect = "built")]
+pub fn looping(b: bool) -> u32 {
+    mir!(
+        {
+            Goto(header)
+        }
+
+        header = {
+            match b {
+                true => body,
+                _ => exit,
+            }
+        }
+
+        body = {
+            Goto(header)
+        }
+
+        exit = {
+            RET = 0;
+            Return()
+        }
+    )
+}
+
+fn main() {
+    assert_eq!(looping(false), 0);
+}
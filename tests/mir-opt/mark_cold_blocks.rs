@@ -0,0 +1,33 @@
+// unit-test: MarkColdBlocks
+// compile-flags: -Zdump-mir-cold-blocks
+// needs-unwind
+#![crate_type = "lib"]
+
+#[cold]
+#[inline(never)]
+fn cold() {}
+
+#[inline(never)]
+fn exit() -> ! {
+    loop {}
+}
+
+pub struct Guard;
+
+impl Drop for Guard {
+    fn drop(&mut self) {}
+}
+
+// Only the block calling the `#[cold]` function is cold.
+// EMIT_MIR mark_cold_blocks.cold_branch.MarkColdBlocks.after.mir
+pub fn cold_branch(x: bool) {
+    if x {
+        cold();
+    }
+}
+
+// The unwind edge of a diverging call leads to cleanup blocks, which doesn't make the call cold.
+// EMIT_MIR mark_cold_blocks.exit_with_guard.MarkColdBlocks.after.mir
+pub fn exit_with_guard(_guard: Guard) -> ! {
+    exit()
+}
@@ -0,0 +1,17 @@
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+// compile-flags: -Zmir-pretty-variant-names
+
+// Check that the targets of switches on enum discriminants are annotated with the names of the
+// variants.
+
+// EMIT_MIR switch_variant_names.unwrap.SimplifyCfg-elaborate-drops.after.mir
+fn unwrap<T>(opt: Option<T>) -> T {
+    match opt {
+        Some(x) => x,
+        None => panic!(),
+    }
+}
+
+fn main() {
+    let _ = unwrap(Some(1i32));
+}
@@ -0,0 +1,16 @@
+// unit-test: UnreachablePropagation
+// only-x86_64
+
+// Check that inline assembly whose destination is unreachable is marked as diverging.
+
+#![crate_type = "lib"]
+
+use std::arch::asm;
+
+pub enum Empty {}
+
+// EMIT_MIR unreachable_asm.asm_before_never.UnreachablePropagation.diff
+pub unsafe fn asm_before_never(x: Empty) -> ! {
+    asm!("", options(nostack));
+    match x {}
+}
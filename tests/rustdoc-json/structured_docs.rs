@@ -0,0 +1,62 @@
+// compile-flags: -Z unstable-options --json-structured-docs
+
+// @set bar = "$.index[*][?(@.name=='Bar')].id"
+
+// @count "$.index[*][?(@.name=='foo')].structured_docs[*]" 3
+// @is "$.index[*][?(@.name=='foo')].structured_docs[0].kind.heading.level" 1
+// @is "$.index[*][?(@.name=='foo')].structured_docs[0].kind.heading.content[0].text" '"Title"'
+// @is "$.index[*][?(@.name=='foo')].structured_docs[0].kind.heading.id" '"title"'
+// @is "$.index[*][?(@.name=='foo')].structured_docs[1].kind.paragraph[0].text" '"Uses "'
+// @is "$.index[*][?(@.name=='foo')].structured_docs[1].kind.paragraph[1].link.destination" '"`Bar`"'
+// @is "$.index[*][?(@.name=='foo')].structured_docs[1].kind.paragraph[1].link.content[0].code" '"Bar"'
+// @is "$.index[*][?(@.name=='foo')].structured_docs[1].kind.paragraph[1].link.id" $bar
+// @is "$.index[*][?(@.name=='foo')].structured_docs[1].kind.paragraph[3].emphasis[0].text" '"emphasis"'
+// @is "$.index[*][?(@.name=='foo')].structured_docs[2].kind.code_block.lang_string" '"rust,ignore"'
+// @is "$.index[*][?(@.name=='foo')].structured_docs[2].kind.code_block.code" '"let x = 1;\n"'
+// @is "$.index[*][?(@.name=='foo')].structured_docs[2].kind.code_block.rust" true
+/// # Title
+///
+/// Uses [`Bar`] and *emphasis*.
+///
+/// ```rust,ignore
+/// let x = 1;
+/// ```
+pub fn foo() {}
+
+// @count "$.index[*][?(@.name=='Bar')].structured_docs[*]" 2
+// @is "$.index[*][?(@.name=='Bar')].structured_docs[0].kind.paragraph[0].text" '"first"'
+// @is "$.index[*][?(@.name=='Bar')].structured_docs[1].kind.paragraph[0].text" '"second"'
+/// - first
+/// - second
+pub struct Bar;
+
+// @is "$.index[*][?(@.name=='undocumented')].structured_docs" []
+pub fn undocumented() {}
+
+// @set foo = "$.index[*][?(@.name=='foo')].id"
+// @is "$.index[*][?(@.name=='summarized')].summary[0].text" '"Calls "'
+// @is "$.index[*][?(@.name=='summarized')].summary[0].code" false
+// @is "$.index[*][?(@.name=='summarized')].summary[0].destination" null
+// @is "$.index[*][?(@.name=='summarized')].summary[1].code" true
+// @is "$.index[*][?(@.name=='summarized')].summary[1].destination" '"`foo`"'
+// @is "$.index[*][?(@.name=='summarized')].summary[1].id" $foo
+// @is "$.index[*][?(@.name=='summarized')].summary[2].text" '" with emphasis."'
+// @count "$.index[*][?(@.name=='summarized')].summary[*]" 3
+/// Calls [`foo`] *with emphasis*.
+///
+/// More details.
+pub fn summarized() {}
+
+// The docs are parsed like for the HTML output.
+// @is "$.index[*][?(@.name=='rewritten')].structured_docs[0].kind.heading.id" '"implementations-1"'
+// @is "$.index[*][?(@.name=='rewritten')].structured_docs[1].kind.paragraph[1].link.content[0].text" '"foo"'
+// @is "$.index[*][?(@.name=='rewritten')].structured_docs[1].kind.paragraph[1].link.id" $foo
+// @is "$.index[*][?(@.name=='rewritten')].structured_docs[2].kind.code_block.rust" false
+/// # Implementations
+///
+/// Calls [fn@foo].
+///
+/// ```text
+/// foo
+/// ```
+pub fn rewritten() {}
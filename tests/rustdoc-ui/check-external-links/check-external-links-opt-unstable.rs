@@ -0,0 +1,6 @@
+// This test purpose is to check that the "--check-external-links"
+// option can only be used on nightly.
+
+// compile-flags: --check-external-links
+
+pub fn f() {}
@@ -0,0 +1,14 @@
+// Links without a host are reported without accessing the network, whether they use `http` or
+// `https`.
+
+// check-pass
+// compile-flags: -Z unstable-options --check-external-links
+
+/// See [the book](http:///book/) and [the reference].
+///
+/// [the reference]: https:///reference/
+pub fn f() {}
+
+/// See [the book](http:///book/) again.
+#[allow(rustdoc::unreachable_external_links)]
+pub fn g() {}
@@ -0,0 +1,29 @@
+// check-pass
+// compile-flags:-Z unstable-options --output-format json --show-coverage --show-coverage-details
+
+//! Parses things.
+
+/// Parses a number.
+///
+/// # Errors
+///
+/// Fails if the input isn't a number.
+///
+/// ```
+/// assert_eq!(details::parse("1"), Ok(1));
+/// ```
+pub fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+    input.parse()
+}
+
+/// Reads a file.
+pub fn read() -> Result<(), ()> {
+    Ok(())
+}
+
+/// Writes a file.
+pub fn write() -> std::io::Result<()> {
+    Ok(())
+}
+
+pub struct Undocumented;
@@ -0,0 +1,14 @@
+// compile-flags: --test --test-args=--test-threads=1 --cfg feature="std"
+// check-pass
+// normalize-stdout-test: "tests/rustdoc-ui/doctest" -> "$$DIR"
+// normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+
+#![feature(custom_code_classes_in_docs)]
+
+/// ```{required-features=std}
+/// let v = vec![1];
+/// ```
+/// ```{required-features="std,serde"}
+/// compile_error!("`serde` is not enabled");
+/// ```
+pub fn f() {}
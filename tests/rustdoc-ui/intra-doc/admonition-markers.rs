@@ -0,0 +1,12 @@
+// check-pass
+
+// Admonition markers are not intra-doc links.
+
+#![deny(rustdoc::broken_intra_doc_links)]
+
+/// > [!NOTE]
+/// > Useful information.
+///
+/// > [!warning]
+/// > Critical content.
+pub fn foo() {}
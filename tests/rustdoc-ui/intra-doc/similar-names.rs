@@ -0,0 +1,31 @@
+#![deny(rustdoc::broken_intra_doc_links)]
+//~^ NOTE lint level is defined
+
+/// [`Strukt::lenght`](Strukt::lenght)
+//~^ ERROR unresolved link
+//~| NOTE has no field or associated item named `lenght`
+//~| HELP an item with a similar name exists
+pub fn f() {}
+
+/// [Strukt::neww]
+//~^ ERROR unresolved link
+//~| NOTE has no field or associated item named `neww`
+//~| HELP an item with a similar name exists
+pub fn g() {}
+
+/// [Strukk]
+//~^ ERROR unresolved link
+//~| NOTE no item named `Strukk` in scope
+//~| HELP to escape
+//~| HELP an item with a similar name exists
+pub fn h() {}
+
+pub struct Strukt {
+    pub length: usize,
+}
+
+impl Strukt {
+    pub fn new() -> Self {
+        Strukt { length: 0 }
+    }
+}
@@ -0,0 +1,18 @@
+// compile-flags: -Z unstable-options --std-docs-dir {{src-base}}/lints/auxiliary/std-docs
+
+#![deny(rustdoc::broken_std_doc_anchors)]
+
+/// Use [`push_str`](https://doc.rust-lang.org/std/string/struct.String.html#method.push_str),
+/// see the [UTF-8 section](https://doc.rust-lang.org/nightly/std/string/struct.String.html#utf-8).
+///
+/// Not [`push_string`](https://doc.rust-lang.org/std/string/struct.String.html#method.push_string)
+//~^ ERROR `std/string/struct.String.html` has no section with the ID `method.push_string`
+/// nor [`with_len`].
+///
+/// [`with_len`]: https://doc.rust-lang.org/stable/std/string/struct.String.html#method.with_len
+//~^ ERROR `std/string/struct.String.html` has no section with the ID `method.with_len`
+///
+/// Pinned versions and pages which aren't available are not checked:
+/// [old](https://doc.rust-lang.org/1.0.0/std/string/struct.String.html#method.push_string),
+/// [`Vec::push`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.push_str).
+pub struct Buffer;
@@ -0,0 +1,31 @@
+#![deny(rustdoc::collapsed_heading_levels)]
+
+/// ##### Deepest
+///
+/// ###### Too deep
+//~^ ERROR this heading is rendered at the same level as its parent heading
+pub struct Deep;
+
+impl Deep {
+    /// # Errors
+    ///
+    /// ## Syntax errors
+    ///
+    /// ### Unclosed delimiters
+    //~^ ERROR this heading is rendered at the same level as its parent heading
+    pub fn parse(&self) {}
+}
+
+/// # Errors
+///
+/// ## Syntax errors
+///
+/// ### Unclosed delimiters
+pub fn shallow() {}
+
+impl Deep {
+    /// # Errors
+    ///
+    /// ### Alone at its level
+    pub fn alone(&self) {}
+}
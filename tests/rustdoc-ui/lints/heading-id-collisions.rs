@@ -0,0 +1,26 @@
+#![deny(rustdoc::heading_id_collisions)]
+
+/// # Examples
+///
+/// # Examples
+//~^ ERROR the ID `examples` of this heading is already used on this page
+pub fn repeated() {}
+
+/// # Implementations
+//~^ ERROR the ID `implementations` of this heading is already used on this page
+pub struct Reserved;
+
+/// # Examples
+///
+/// # Panics
+pub fn unique() {}
+
+pub struct Methods;
+
+impl Methods {
+    /// # Examples
+    pub fn first() {}
+
+    /// # Examples
+    pub fn second() {}
+}
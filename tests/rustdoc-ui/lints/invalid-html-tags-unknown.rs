@@ -0,0 +1,17 @@
+#![deny(rustdoc::invalid_html_tags)]
+
+/// <foo>bar</foo>
+//~^ ERROR unknown HTML tag `foo`
+/// <Span>ok</Span> <my-element>ok</my-element>
+pub fn unknown() {}
+
+/// <svg><rect></rect><path></path></svg>
+/// <math><mi>x</mi></math>
+/// <script><foo></foo></script>
+pub fn foreign_content() {}
+
+/// <title>The title</title>
+//~^ ERROR HTML tag `title` breaks the layout of the documentation
+/// <meta charset="utf-8">
+//~^ ERROR HTML tag `meta` breaks the layout of the documentation
+pub fn layout() {}
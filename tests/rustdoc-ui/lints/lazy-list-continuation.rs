@@ -0,0 +1,25 @@
+// run-rustfix
+
+#![deny(rustdoc::lazy_list_continuation)]
+
+/// - the first item,
+/// which continues here
+//~^ ERROR list item continuation is not indented
+/// - the second item
+pub fn lazy() {}
+
+/// 1. numbered items
+/// are indented more
+//~^ ERROR list item continuation is not indented
+///
+/// - nested:
+///   - inner item
+///   continued
+//~^ ERROR list item continuation is not indented
+pub fn nested() {}
+
+/// - a list item
+///   properly indented
+///
+/// A separate paragraph.
+pub fn indented() {}
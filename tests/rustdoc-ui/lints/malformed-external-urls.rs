@@ -0,0 +1,16 @@
+// run-rustfix
+
+#![deny(rustdoc::malformed_external_urls)]
+
+/// [unknown](htps://example.com)
+//~^ ERROR unknown URL scheme `htps`
+/// [no host](https:/example.com)
+//~^ ERROR this URL has no host
+/// [fragment](https://example.com/page#)
+//~^ ERROR this URL has an empty fragment
+/// [spaces](<https://example.com/a page>)
+//~^ ERROR this URL contains whitespace
+///
+/// These are fine: [web](https://example.com/page#section), [mail](mailto:someone@example.com),
+/// [intra-doc](std::vec::Vec) and <https://example.com>.
+pub fn f() {}
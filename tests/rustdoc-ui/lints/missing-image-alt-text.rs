@@ -0,0 +1,14 @@
+#![deny(rustdoc::missing_image_alt_text)]
+
+/// ![](layout.png)
+//~^ ERROR image has no alt text
+///
+/// ![  ](spaces.png "Spaces")
+//~^ ERROR image has no alt text
+///
+/// ![The layout](layout.png) and ![`Header`](header.png)
+pub struct Header;
+
+/// [![](https://example.com/badge.svg)](https://example.com/ci)
+//~^ ERROR image has no alt text
+pub struct Badge;
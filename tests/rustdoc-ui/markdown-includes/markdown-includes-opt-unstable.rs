@@ -0,0 +1,6 @@
+// This test purpose is to check that the "--markdown-includes"
+// option can only be used on nightly.
+
+// compile-flags: --markdown-includes
+
+pub fn f() {}
@@ -0,0 +1,16 @@
+// compile-flags: -Z unstable-options --markdown-includes
+// normalize-stderr-test: "`missing.md`: .*" -> "`missing.md`: FILE_NOT_FOUND"
+
+/// Parses the input.
+///
+/// {{#include missing.md}}
+//~^ ERROR cannot include `missing.md`
+pub fn parse() {}
+
+/// {{#include }}
+//~^ ERROR cannot include ``: missing path
+pub fn empty() {}
+
+/// Not a directive: {{#include missing.md
+/// }}
+pub fn multiline() {}
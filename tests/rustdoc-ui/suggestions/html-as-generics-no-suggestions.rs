@@ -56,7 +56,8 @@
 pub struct TagWithAttributes;
 
 /// This Vec<i32></i32> thing!
-// There should be no error, and no suggestion, since the tags are balanced.
+//~^ERROR unknown HTML tag `i32`
+// The tags are balanced, so there should be no suggestion, but `i32` isn't an HTML element.
 pub struct DoNotWarnOnMatchingTags;
 
 /// This Vec</i32> thing!
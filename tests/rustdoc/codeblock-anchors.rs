@@ -0,0 +1,24 @@
+#![crate_name = "foo"]
+
+// @has foo/fn.bar.html '//*[@id="fn.bar.example-1"]/a[@class="anchor example-anchor"]/@href' \
+//      '#fn.bar.example-1'
+// @has foo/fn.bar.html '//*[@id="fn.bar.example-2"]//code' 'plain text'
+
+/// ```
+/// bar();
+/// ```
+///
+/// ```text
+/// plain text
+/// ```
+pub fn bar() {}
+
+pub struct Foo;
+
+impl Foo {
+    // @has foo/struct.Foo.html '//*[@id="method.baz.example-1"]' 'Foo::baz();'
+    /// ```
+    /// Foo::baz();
+    /// ```
+    pub fn baz() {}
+}
@@ -0,0 +1,14 @@
+// compile-flags: -Z unstable-options --enable-per-target-ignores
+
+#![crate_name = "foo"]
+
+// @has foo/fn.bar.html
+// @has - '//*[@id="fn.bar.example-1"]//span[@class="example-badge ignore"]' \
+//   'ignored on wasm32, windows'
+// @has - '//*[@id="fn.bar.example-1"]//a[@class="tooltip"]/@title' \
+//   'This example is not tested on wasm32, windows'
+
+/// ```ignore-wasm32,ignore-windows
+/// bar();
+/// ```
+pub fn bar() {}
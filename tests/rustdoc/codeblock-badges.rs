@@ -0,0 +1,36 @@
+#![crate_name = "foo"]
+
+// @has foo/fn.bar.html
+// @has - '//*[@id="fn.bar.example-1"]//span[@class="example-badge ignore"]' 'ignored'
+// @has - '//*[@id="fn.bar.example-2"]//span[@class="example-badge compile_fail"]' 'compile_fail'
+// @count - '//*[@id="fn.bar.example-2"]//span[@class="example-badge no_run"]' 0
+// @has - '//*[@id="fn.bar.example-3"]//span[@class="example-badge no_run"]' 'no_run'
+// @has - '//*[@id="fn.bar.example-4"]//span[@class="example-badge should_panic"]' 'should_panic'
+// @has - '//*[@id="fn.bar.example-4"]//span[@class="example-badge edition"]' 'edition 2015'
+// @count - '//*[@id="fn.bar.example-5"]//*[@class="example-badges"]' 0
+// @count - '//*[@id="fn.bar.example-6"]//*[@class="example-badges"]' 0
+
+/// ```ignore
+/// bar();
+/// ```
+///
+/// ```compile_fail
+/// bar(1);
+/// ```
+///
+/// ```no_run
+/// bar();
+/// ```
+///
+/// ```should_panic,edition2015
+/// panic!();
+/// ```
+///
+/// ```
+/// bar();
+/// ```
+///
+/// ```ignore-wasm32,ignore-windows
+/// bar();
+/// ```
+pub fn bar() {}
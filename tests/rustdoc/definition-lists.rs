@@ -0,0 +1,20 @@
+#![crate_name = "foo"]
+
+// @has foo/index.html
+// @has - '//dl/dt[@id="borrow-checker"]/a[@class="anchor"]/@href' '#borrow-checker'
+// @has - '//dl/dt[@id="borrow-checker"]' 'Borrow checker'
+// @has - '//dl/dd' 'The part of the compiler which checks that references are valid.'
+// @has - '//dl/dt[@id="drop-glue"]/a[@href="struct.Foo.html"]' 'Drop glue'
+// @count - '//dl' 1
+// @count - '//dl/dd' 3
+
+//! Glossary
+//!
+//! Borrow checker
+//! : The part of the compiler which checks that references are valid.
+//!
+//! [Drop glue](Foo)
+//! : The code dropping the fields of a value.
+//! : It is generated by the compiler.
+
+pub struct Foo;
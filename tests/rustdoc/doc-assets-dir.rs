@@ -0,0 +1,19 @@
+// compile-flags: -Z unstable-options --doc-assets-dir {{src-base}}/auxiliary/doc-assets
+
+#![crate_name = "foo"]
+
+// @has foo/doc-assets/diagrams/layout.png
+// @has foo/struct.Header.html
+// @has - '//*[@class="docblock"]//img[@src="../foo/doc-assets/diagrams/layout.png"]/@alt' \
+//     'The layout of a header'
+// @has - '//img[@src="../foo/doc-assets/diagrams/layout.png"]/@width' '3'
+// @has - '//img[@src="../foo/doc-assets/diagrams/layout.png"]/@height' '2'
+// @has - '//img[@src="../foo/doc-assets/diagrams/layout.png"]/@title' 'Layout'
+// @has - '//img[@src="https://example.com/badge.svg"]/@alt' 'Badge'
+// @has - '//img[@src="missing.png"]/@alt' 'Missing'
+/// ![The layout of a header](./diagrams/layout.png "Layout")
+///
+/// ![Badge](https://example.com/badge.svg)
+///
+/// ![Missing](missing.png)
+pub struct Header;
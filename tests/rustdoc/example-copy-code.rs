@@ -0,0 +1,16 @@
+#![crate_name = "foo"]
+
+// @has foo/fn.foo.html
+// @has - '//*[@class="example-wrap"]/@data-code' 'assert_eq!(x, 1);'
+// @has - '//*[@class="example-wrap"]/@data-code' 'let x = 1;'
+// @!has - '//*[@class="example-wrap"]/@data-code' '# let x = 1;'
+// @count - '//*[@class="example-wrap"][@data-code]' 1
+/// ```
+/// # let x = 1;
+/// assert_eq!(x, 1);
+/// ```
+///
+/// ```text
+/// not rust
+/// ```
+pub fn foo() {}
@@ -0,0 +1,37 @@
+// compile-flags: -Zunstable-options --generate-link-to-definition
+
+#![crate_name = "foo"]
+
+pub struct Widget;
+
+impl Widget {
+    pub fn new() -> Widget {
+        Widget
+    }
+}
+
+// @has 'foo/fn.paint.html'
+// @has - '//pre//a[@href="../src/foo/example-src-links.rs.html#5"]' 'foo::Widget'
+// @has - '//pre//a[@href="../src/foo/example-src-links.rs.html#8-10"]' 'foo::Widget::new'
+// The docs don't link to `paint`.
+// @!has - '//pre//a' 'foo::paint'
+// Only the paths of the items the docs link to are linked, not the paths ending like them.
+// @!has - '//pre//a' 'foo::other::Widget'
+/// Paints a [`Widget`] built with [`Widget::new`]:
+///
+/// ```
+/// let widget: foo::Widget = foo::Widget::new();
+/// foo::paint(&widget);
+/// let _ = foo::other::Widget::new();
+/// ```
+pub fn paint(_: &Widget) {}
+
+pub mod other {
+    pub struct Widget;
+
+    impl Widget {
+        pub fn new() -> Widget {
+            Widget
+        }
+    }
+}
@@ -0,0 +1,17 @@
+// compile-flags: -Z unstable-options --expandable-hidden-lines
+
+#![crate_name = "foo"]
+
+// @has foo/fn.foo.html
+// @has - '//pre[@class="rust rust-example-rendered"]//span[@class="hidden-line"]' 'let x = 1;'
+// @has - '//*[@class="example-wrap"]/button[@class="hidden-lines-toggle"]' ''
+// @count - '//button[@class="hidden-lines-toggle"]' 1
+/// ```
+/// # let x = 1;
+/// assert_eq!(x, 1);
+/// ```
+///
+/// ```
+/// assert_eq!(1, 1);
+/// ```
+pub fn foo() {}
@@ -0,0 +1,17 @@
+// Every reference to a footnote gets its own anchor, and the footnote links back to all of them.
+
+#![crate_name = "foo"]
+
+// @has 'foo/struct.S.html'
+// @has - '//*[@class="docblock"]//sup[@id="fnref-a"]/a/@href' '#fn-a'
+// @has - '//*[@class="docblock"]//sup[@id="fnref-a-2"]/a/@href' '#fn-a'
+// @has - '//*[@class="docblock"]//sup[@id="fnref-b"]/a/@href' '#fn-b'
+// @has - '//li[@id="fn-a"]/p/a[@href="#fnref-a"]' '↩'
+// @has - '//li[@id="fn-a"]/p/a[@href="#fnref-a-2"]' '↩2'
+// @count - '//li[@id="fn-b"]//a' 1
+
+/// first [^a], second [^b], third [^a].
+///
+/// [^a]: referenced twice
+/// [^b]: referenced once
+pub struct S;
@@ -0,0 +1,23 @@
+// compile-flags: -Z unstable-options --markdown-includes
+
+#![crate_name = "foo"]
+
+// @has foo/fn.parse.html
+// @has - '//h2' 'Errors'
+// @has - '//*[@class="docblock"]/p' 'Returns an error if the input is empty'
+// @has - '//*[@class="docblock"]//a[@href="struct.Error.html"]' 'Error'
+// @!has - '//*[@class="docblock"]' '{{#include'
+/// Parses the input.
+///
+/// {{#include auxiliary/markdown-include.md}}
+pub fn parse(_input: &str) -> Result<(), Error> {
+    Ok(())
+}
+
+// The summary comes from the included file too.
+// @has foo/index.html '//*[@class="desc docblock-short"]' 'Checks the input.'
+// @hasraw search-index.js 'Checks the input.'
+/// {{#include auxiliary/markdown-include-summary.md}}
+pub fn check(_input: &str) {}
+
+pub struct Error;
@@ -0,0 +1,18 @@
+// compile-flags: -Z unstable-options --enable-math=https://example.com/katex/
+
+#![crate_name = "foo"]
+
+// @has foo/fn.hypot.html
+// @has - '//script[@src="https://example.com/katex/katex.min.js"]' ''
+// @has - '//link[@href="https://example.com/katex/katex.min.css"]' ''
+// @has - '//*[@class="docblock"]//span[@class="math"]' '\sqrt{a_1^2 + b_1^2}'
+// @has - '//*[@class="docblock"]//span[@class="math display"]' 'x < y'
+// @has - '//*[@class="docblock"]/p' 'It costs $5 or $10.'
+/// Returns $\sqrt{a_1^2 + b_1^2}$, which is at least $a_1$:
+///
+/// $$x < y$$
+///
+/// It costs $5 or $10.
+pub fn hypot(a_1: f64, b_1: f64) -> f64 {
+    (a_1 * a_1 + b_1 * b_1).sqrt()
+}
@@ -0,0 +1,23 @@
+// compile-flags: -Z unstable-options --nest-deep-headings
+
+#![crate_name = "foo"]
+
+// @has foo/struct.Deep.html
+// @has - '//div[@class="docblock"]/h6[@id="deepest"]' 'Deepest'
+// @has - '//div[@class="docblock"]/div[@role="heading"][@aria-level="7"][@id="too-deep"]' \
+//   'Too deep'
+// @has - '//div[@role="heading"][@aria-level="7"]/a[@href="#unclosed-delimiters"]' \
+//   'Unclosed delimiters'
+/// ##### Deepest
+///
+/// ###### Too deep
+pub struct Deep;
+
+impl Deep {
+    /// # Errors
+    ///
+    /// ## Syntax errors
+    ///
+    /// ### Unclosed delimiters
+    pub fn parse(&self) {}
+}
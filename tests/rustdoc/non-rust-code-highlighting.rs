@@ -0,0 +1,20 @@
+#![crate_name = "foo"]
+
+// @has foo/fn.f.html
+// @has - '//pre[@class="language-toml"]/code/span[@class="attr"]' '[dependencies]'
+// @has - '//pre[@class="language-toml"]/code/span[@class="string"]' '"1.0"'
+// @has - '//pre[@class="language-sh"]/code/span[@class="comment"]' '# build the docs'
+// @count - '//pre[@class="language-text"]/code/span' 0
+/// ```toml
+/// [dependencies]
+/// foo = "1.0"
+/// ```
+///
+/// ```sh
+/// cargo doc # build the docs
+/// ```
+///
+/// ```text
+/// [dependencies] # not highlighted
+/// ```
+pub fn f() {}
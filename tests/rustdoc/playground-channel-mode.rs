@@ -0,0 +1,23 @@
+// Checks that the `channel` and `mode` attributes of code blocks are forwarded to the playground.
+
+#![crate_name = "foo"]
+#![feature(custom_code_classes_in_docs)]
+#![doc(html_playground_url = "https://www.example.com/")]
+
+//! ```{channel=beta mode=release}
+//! println!("Hello, world!");
+//! ```
+//!
+//! ```{channel=stable}
+//! #![feature(something)]
+//! ```
+//!
+//! ```{mode=debug}
+//! #![feature(something)]
+//! ```
+
+// @count foo/index.html '//a[@class="test-arrow"]' 3
+// @has - '//a[@class="test-arrow"][contains(@href, "&version=beta&mode=release&edition=")]' "Run"
+// @has - '//a[@class="test-arrow"][contains(@href, "&version=stable&edition=")]' "Run"
+// Without a `channel` attribute, examples using unstable features are run on nightly.
+// @has - '//a[@class="test-arrow"][contains(@href, "&version=nightly&mode=debug&edition=")]' "Run"
@@ -0,0 +1,14 @@
+// compile-flags: -Z unstable-options --smart-link-titles
+
+#![crate_name = "foo"]
+
+// @has foo/struct.Stack.html
+// @has - '//a[@href="https://doc.rust-lang.org/std/vec/struct.Vec.html"]/code' 'std::vec::Vec'
+// @has - '//a[@href="https://docs.rs/regex/latest/regex/struct.Regex.html#method.new"]/code' \
+//   'regex::Regex::new'
+// @has - '//a[@href="https://example.com/"]' 'https://example.com/'
+/// Stores its elements in a <https://doc.rust-lang.org/std/vec/struct.Vec.html>, parsed with
+/// <https://docs.rs/regex/latest/regex/struct.Regex.html#method.new>.
+///
+/// More at <https://example.com/>.
+pub struct Stack;
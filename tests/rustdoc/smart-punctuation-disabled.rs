@@ -0,0 +1,21 @@
+// This test ensures that `#![doc(smart_punctuation = false)]` keeps `--` and quotes as written,
+// both in the documentation and in the summaries.
+
+#![crate_name = "foo"]
+#![feature(doc_smart_punctuation)]
+#![doc(smart_punctuation = false)]
+
+// @has 'foo/index.html' '//*[@class="desc docblock-short"]' 'Runs with --quiet.'
+// @has 'foo/fn.run.html' '//*[@class="docblock"]' 'Runs with --quiet.'
+// @has - '//*[@class="docblock"]' 'Pass --verbose to see "everything".'
+// @!has - '//*[@class="docblock"]' '–'
+// @hasraw search-index.js 'Runs with --quiet.'
+
+/// Runs with --quiet.
+///
+/// Pass --verbose to see "everything".
+pub fn run() {}
+
+// @has 'foo/fn.old.html' '//*[@class="stab deprecated"]' 'Deprecated: use run --quiet'
+#[deprecated(note = "use run --quiet")]
+pub fn old() {}
@@ -0,0 +1,11 @@
+// Items whose documentation starts with a table use its header row as summary.
+
+#![crate_name = "foo"]
+
+// @has foo/index.html '//*[@class="desc docblock-short"]' 'Name | Value'
+// @has foo/index.html '//*[@class="desc docblock-short"]//em' 'Value'
+// @hasraw search-index.js 'Name | '
+/// | Name | *Value* |
+/// |------|---------|
+/// | a    | b       |
+pub struct Table;
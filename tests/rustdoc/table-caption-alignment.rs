@@ -0,0 +1,15 @@
+#![crate_name = "foo"]
+
+// @has foo/fn.sizes.html
+// @has - '//*[@class="docblock"]//table/caption' 'The size of the integer types, in bytes.'
+// @has - '//*[@class="docblock"]//table//th[@class="align-right"]' 'Size'
+// @has - '//*[@class="docblock"]//table//td[@class="align-right"]' '8'
+// @has - '//*[@class="docblock"]//table//th[not(@class)]' 'Type'
+// @!hasraw - 'Table:'
+/// | Type  | Size |
+/// |-------|-----:|
+/// | `u8`  |    1 |
+/// | `u64` |    8 |
+///
+/// Table: The size of the integer types, in bytes.
+pub fn sizes() {}
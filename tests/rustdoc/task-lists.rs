@@ -1,13 +1,16 @@
-// ignore-tidy-linelength
-// FIXME: this doesn't test as much as I'd like; ideally it would have these query too:
-  // has task_lists/index.html '//li/input[@type="checkbox" and disabled]/following-sibling::text()' 'a'
-  // has task_lists/index.html '//li/input[@type="checkbox"]/following-sibling::text()' 'b'
-// Unfortunately that requires LXML, because the built-in xml module doesn't support all of xpath.
+#![crate_name = "foo"]
 
-// @has task_lists/index.html '//ul/li/input[@type="checkbox"]' ''
-// @has task_lists/index.html '//ul/li/input[@disabled]' ''
-// @has task_lists/index.html '//ul/li' 'a'
-// @has task_lists/index.html '//ul/li' 'b'
-//! This tests 'task list' support, a common markdown extension.
-//! - [ ] a
-//! - [x] b
+// @has foo/index.html
+// @has - '//*[@class="desc docblock-short"]' 'Release checklist'
+// @!has - '//*[@class="desc docblock-short"]//input'
+
+// @has foo/struct.Release.html
+// @has - '//li[@class="task-list-item"]/input[@type="checkbox"][@disabled][@checked]'
+// @has - '//li[@class="task-list-item"]/input[@aria-checked="true"]/..' 'Changelog'
+// @has - '//li[@class="task-list-item"]/input[@aria-checked="false"]/..' 'Announcement'
+// @count - '//li[@class="task-list-item"]/input[@checked]' 1
+/// Release checklist
+///
+/// - [x] Changelog
+/// - [ ] Announcement
+pub struct Release;
@@ -0,0 +1,87 @@
+// run-pass
+// Test that the optimized MIR records which families of optimizations modified it.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+
+use rustc_driver::Compilation;
+use rustc_hir::def::DefKind;
+use rustc_interface::{interface, Queries};
+use rustc_middle::mir::AppliedOptimizations;
+use std::io::Write;
+
+struct CheckOptimizations {
+    checked: usize,
+}
+
+impl rustc_driver::Callbacks for CheckOptimizations {
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            for def_id in tcx.hir().body_owners() {
+                if tcx.def_kind(def_id) != DefKind::Fn {
+                    continue;
+                }
+                let applied = tcx.optimized_mir(def_id).applied_optimizations;
+                match tcx.item_name(def_id.to_def_id()).as_str() {
+                    "caller" => assert!(applied.contains(AppliedOptimizations::INLINE)),
+                    "leaf" => assert_eq!(applied, AppliedOptimizations::empty()),
+                    _ => continue,
+                }
+                self.checked += 1;
+            }
+        });
+        Compilation::Stop
+    }
+}
+
+fn main() {
+    let path = "input_mir_applied_optimizations.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "-Copt-level=3".to_string(),
+        "-Zinline-mir".to_string(),
+        path.to_string(),
+    ];
+    let mut callbacks = CheckOptimizations { checked: 0 };
+    rustc_driver::catch_fatal_errors(|| {
+        rustc_driver::RunCompiler::new(&args, &mut callbacks).run().ok();
+    })
+    .unwrap();
+    assert_eq!(callbacks.checked, 2);
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    #[inline]
+    fn double(x: u32) -> u32 {{
+        x * 2
+    }}
+
+    pub fn caller(x: u32) -> u32 {{
+        double(x) + 1
+    }}
+
+    pub fn leaf() -> u32 {{
+        1
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,105 @@
+// run-pass
+// Test that the MIR unsafety checker records the unsafe operations of each body.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+
+use rustc_driver::Compilation;
+use rustc_hir::def::DefKind;
+use rustc_interface::{interface, Queries};
+use rustc_middle::mir::UnsafetyViolationDetails;
+use std::io::Write;
+
+struct CheckSummaries {
+    checked: usize,
+}
+
+impl rustc_driver::Callbacks for CheckSummaries {
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            for def_id in tcx.hir().body_owners() {
+                if tcx.def_kind(def_id) != DefKind::Fn {
+                    continue;
+                }
+                let summary = tcx.unsafety_summary(def_id);
+                let body_summary = tcx.optimized_mir(def_id).unsafety_summary.as_deref();
+                match tcx.item_name(def_id.to_def_id()).as_str() {
+                    "read" => {
+                        let has = |details: UnsafetyViolationDetails| {
+                            summary.operations_of(details).count() == 1
+                        };
+                        assert_eq!(summary.operations.len(), 2);
+                        assert!(has(UnsafetyViolationDetails::DerefOfRawPointer));
+                        assert!(has(UnsafetyViolationDetails::AccessToUnionField));
+                        assert_eq!(body_summary.unwrap().operations, summary.operations);
+                    }
+                    "caller" => {
+                        let calls: Vec<_> = summary
+                            .operations_of(UnsafetyViolationDetails::CallToUnsafeFunction)
+                            .map(|op| tcx.item_name(op.callee.unwrap()))
+                            .collect();
+                        assert_eq!(summary.operations.len(), 1);
+                        assert_eq!(calls[0].as_str(), "read");
+                    }
+                    "safe" => {
+                        assert!(summary.is_empty());
+                        assert!(body_summary.is_none());
+                    }
+                    _ => continue,
+                }
+                self.checked += 1;
+            }
+        });
+        Compilation::Stop
+    }
+}
+
+fn main() {
+    let path = "input_mir_unsafety_summary.rs";
+    generate_input(&path).unwrap();
+    let args = vec!["rustc".to_string(), "--crate-type=lib".to_string(), path.to_string()];
+    let mut callbacks = CheckSummaries { checked: 0 };
+    rustc_driver::catch_fatal_errors(|| {
+        rustc_driver::RunCompiler::new(&args, &mut callbacks).run().ok();
+    })
+    .unwrap();
+    assert_eq!(callbacks.checked, 3);
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub union U {{
+        x: u32,
+        y: f32,
+    }}
+
+    pub unsafe fn read(p: *const U) -> u32 {{
+        (*p).x
+    }}
+
+    pub fn caller(u: &U) -> u32 {{
+        unsafe {{ read(u) }}
+    }}
+
+    pub fn safe() -> u32 {{
+        1
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,138 @@
+// run-pass
+// Test that the kind, variants, fields and representation of ADTs can be inspected
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_hir;
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir;
+use rustc_smir::stable_mir::ty::{AdtDef, AdtKind, GenericArgs, IntTy, RigidTy, TyKind, UintTy};
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_adt(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let uses = get_item(tcx, &items, "uses").unwrap();
+    let adts: Vec<(AdtDef, GenericArgs)> = uses.body().locals[1..]
+        .iter()
+        .map(|local| match local.kind() {
+            TyKind::RigidTy(RigidTy::Adt(def, args)) => (def, args),
+            kind => panic!("unexpected type: {kind:?}"),
+        })
+        .collect();
+    let [(shape, _), (pair, pair_args), (bits, _), (aligned, _)] = &adts[..] else {
+        panic!("unexpected ADTs: {adts:?}");
+    };
+
+    // The variants of an enum and their discriminants.
+    assert_eq!(shape.kind(), AdtKind::Enum);
+    assert!(shape.fields().is_empty());
+    let variants = shape.variants();
+    let names: Vec<_> = variants.iter().map(|variant| variant.name.as_str()).collect();
+    assert_eq!(names, ["Circle", "Rect", "Empty"]);
+    assert_eq!(variants[1].fields.len(), 2);
+    assert_eq!(variants[1].fields[0].name, "0");
+    let discrs: Vec<_> =
+        (0..3).map(|variant| shape.discriminant_for_variant(variant).unwrap().val).collect();
+    assert_eq!(discrs, [0xff, 4, 5]);
+    let discr = shape.discriminant_for_variant(0).unwrap();
+    assert_matches!(discr.ty.kind(), TyKind::RigidTy(RigidTy::Int(IntTy::I8)));
+    assert!(shape.discriminant_for_variant(3).is_err());
+    let repr = shape.repr();
+    assert_matches!(repr.int.unwrap().kind(), TyKind::RigidTy(RigidTy::Int(IntTy::I8)));
+    assert!(!repr.c);
+
+    // The fields of a generic struct, in an instance of the struct.
+    assert_eq!(pair.kind(), AdtKind::Struct);
+    assert!(pair.discriminant_for_variant(0).is_err());
+    let fields = pair.fields();
+    let names: Vec<_> = fields.iter().map(|field| field.name.as_str()).collect();
+    assert_eq!(names, ["first", "second"]);
+    assert_matches!(fields[0].ty.kind(), TyKind::Param(_));
+    let first = fields[0].ty_with_args(pair_args).unwrap();
+    assert_matches!(first.kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::U8)));
+    assert!(pair.repr().c);
+
+    // Unions and the alignment attributes.
+    assert_eq!(bits.kind(), AdtKind::Union);
+    assert_eq!(bits.fields().len(), 2);
+    assert_eq!(aligned.repr().align, Some(16));
+    assert_eq!(aligned.repr().pack, None);
+    assert!(aligned.repr().int.is_none());
+
+    ControlFlow::Continue(())
+}
+
+// Use internal API to find a function in a crate.
+fn get_item<'a>(
+    tcx: TyCtxt,
+    items: &'a stable_mir::CrateItems,
+    name: &str,
+) -> Option<&'a stable_mir::CrateItem> {
+    items.iter().find(|crate_item| {
+        let def_id = rustc_internal::item_def_id(crate_item);
+        tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+    })
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_adt.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args, test_adt).run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    #[repr(i8)]
+    pub enum Shape {{
+        Circle(f32) = -1,
+        Rect(f32, f32) = 4,
+        Empty,
+    }}
+
+    #[repr(C)]
+    pub struct Pair<T> {{
+        pub first: T,
+        pub second: u32,
+    }}
+
+    pub union Bits {{
+        pub int: u32,
+        pub float: f32,
+    }}
+
+    #[repr(align(16))]
+    pub struct Aligned(pub u8);
+
+    pub fn uses(_: Shape, _: Pair<u8>, _: Bits, _: Aligned) {{}}"#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,115 @@
+// run-pass
+// Test the paths, attributes and visibilities of items
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_hir;
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir::{self, AttrArgs, Visibility};
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_attributes(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    let exported = get_item(tcx, &items, "module::exported").unwrap();
+    assert_eq!(exported.name(), "module::exported");
+    assert_eq!(exported.def_path(), "input::module::exported");
+    assert_eq!(exported.visibility(), Visibility::Public);
+    // The doc comment isn't an attribute.
+    let attrs = exported.attrs();
+    assert_eq!(attrs.len(), 2);
+    assert_eq!(attrs[0].path, ["no_mangle"]);
+    assert_eq!(attrs[0].args, AttrArgs::Empty);
+    assert_eq!(attrs[1].path, ["inline"]);
+    assert_eq!(attrs[1].args, AttrArgs::Delimited("never".to_string()));
+    assert_eq!(attrs[1].span.source_text().as_deref(), Some("#[inline(never)]"));
+
+    let skipped = get_item(tcx, &items, "module::skipped").unwrap();
+    assert_eq!(skipped.visibility(), Visibility::Restricted("input".to_string()));
+    let attrs = skipped.attrs();
+    assert_eq!(attrs.len(), 1);
+    assert_eq!(attrs[0].path, ["rustfmt", "skip"]);
+
+    let private = get_item(tcx, &items, "module::private").unwrap();
+    assert_eq!(private.visibility(), Visibility::Restricted("input::module".to_string()));
+    let attrs = private.attrs();
+    assert_eq!(attrs.len(), 1);
+    assert_eq!(attrs[0].path, ["export_name"]);
+    assert_eq!(attrs[0].args, AttrArgs::Eq("\"renamed\"".to_string()));
+
+    // Closures don't have a name, and are visible in their module.
+    let closure = items.iter().find(|item| item.def_path().ends_with("{closure#0}")).unwrap();
+    assert_eq!(closure.def_path(), "input::module::with_closure::{closure#0}");
+    assert_eq!(closure.visibility(), Visibility::Restricted("input::module".to_string()));
+    assert!(closure.attrs().is_empty());
+
+    ControlFlow::Continue(())
+}
+
+// Use internal API to find a function in a crate.
+fn get_item<'a>(
+    tcx: TyCtxt,
+    items: &'a stable_mir::CrateItems,
+    name: &str,
+) -> Option<&'a stable_mir::CrateItem> {
+    items.iter().find(|crate_item| {
+        let def_id = rustc_internal::item_def_id(crate_item);
+        tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+    })
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_attributes.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args, test_attributes).run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub mod module {{
+        /// An exported function.
+        #[no_mangle]
+        #[inline(never)]
+        pub extern "C" fn exported() {{}}
+
+        #[rustfmt::skip]
+        pub(crate) fn skipped() {{}}
+
+        #[export_name = "renamed"]
+        fn private() {{}}
+
+        pub fn with_closure() -> u32 {{
+            let closure = || 1;
+            closure()
+        }}
+    }}"#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,101 @@
+// run-pass
+// Test that the borrowck facts of a body can be retrieved through stable MIR
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_hir;
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir::{self, mir::BorrowKind};
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_borrowck_facts(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let facts = get_item(tcx, &items, "borrow").unwrap().borrowck_facts().unwrap();
+    assert!(!facts.cfg_edges.is_empty());
+    assert!(!facts.region_constraints.is_empty());
+
+    // The mutable borrow of `v`, which is `_1`.
+    let loan = facts
+        .loans
+        .iter()
+        .find(|loan| matches!(loan.kind, BorrowKind::Mut { .. }) && loan.borrowed_place.local == 1)
+        .unwrap();
+    assert!(loan.issued_at.mid);
+    assert!(loan.issued_at.block < facts.body.blocks.len());
+    assert!(!loan.out_of_scope_at.is_empty());
+
+    // `v` is moved to `s`.
+    let path = facts.move_paths.iter().find(|path| path.local == Some(1)).unwrap();
+    assert_eq!(path.parent, None);
+    assert!(!path.moved_at.is_empty());
+
+    ControlFlow::Continue(())
+}
+
+fn test_no_borrowck_facts(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    // The facts kept by a previous compilation are not visible to this one.
+    let items = stable_mir::all_local_items();
+    assert!(get_item(tcx, &items, "borrow").unwrap().borrowck_facts().is_err());
+    ControlFlow::Continue(())
+}
+
+// Use internal API to find a function in a crate.
+fn get_item<'a>(
+    tcx: TyCtxt,
+    items: &'a stable_mir::CrateItems,
+    name: &str,
+) -> Option<&'a stable_mir::CrateItem> {
+    items.iter().find(|crate_item| {
+        let def_id = rustc_internal::item_def_id(crate_item);
+        tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+    })
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_borrowck_facts.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args.clone(), test_borrowck_facts)
+        .with_borrowck_facts()
+        .run()
+        .unwrap();
+    rustc_internal::StableMir::new(args, test_no_borrowck_facts).run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn borrow(mut v: Vec<u8>) -> usize {{
+        let r = &mut v;
+        r.push(1);
+        let s = v;
+        s.len()
+    }}"#
+    )?;
+    Ok(())
+}
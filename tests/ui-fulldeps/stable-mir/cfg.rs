@@ -0,0 +1,160 @@
+// run-pass
+// Test the control-flow graph utilities of stable MIR bodies
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_hir;
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir;
+use rustc_smir::stable_mir::mir::{Body, TerminatorKind};
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_cfg(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    let body = get_item(tcx, &items, "sum").unwrap().body();
+    check_traversals(&body);
+    let dominators = body.dominators();
+    let switch = find_block(&body, |kind| matches!(kind, TerminatorKind::SwitchInt { .. }));
+    let ret = find_block(&body, |kind| matches!(kind, TerminatorKind::Return));
+    assert_eq!(dominators.immediate_dominator(0), None);
+    assert!(dominators.dominates(0, switch));
+    assert!(dominators.dominates(switch, ret));
+    assert!(!dominators.dominates(ret, switch));
+    assert!(dominators.dominates(switch, switch));
+    assert_eq!(dominators.dominators(ret).last(), Some(0));
+    assert!(dominators.dominators(ret).any(|block| block == switch));
+    // The end of the loop jumps back to a block dominating the condition of the loop.
+    assert!(body.blocks.iter().enumerate().any(|(index, block)| {
+        let successors = block.terminator.kind.successors();
+        successors
+            .into_iter()
+            .any(|succ| dominators.dominates(succ, index) && dominators.dominates(succ, switch))
+    }));
+
+    let body = get_item(tcx, &items, "choose").unwrap().body();
+    check_traversals(&body);
+    let dominators = body.dominators();
+    let switch = find_block(&body, |kind| matches!(kind, TerminatorKind::SwitchInt { .. }));
+    let ret = find_block(&body, |kind| matches!(kind, TerminatorKind::Return));
+    let TerminatorKind::SwitchInt { targets, otherwise, .. } = &body.blocks[switch].terminator.kind
+    else {
+        unreachable!()
+    };
+    let (then_block, else_block) = (targets[0].target, *otherwise);
+    // Neither branch dominates the other one, nor the block where they join.
+    assert_eq!(dominators.immediate_dominator(then_block), Some(switch));
+    assert_eq!(dominators.immediate_dominator(else_block), Some(switch));
+    assert!(!dominators.dominates(then_block, else_block));
+    assert!(!dominators.dominates(then_block, ret));
+    assert!(!dominators.dominates(else_block, ret));
+    assert!(dominators.dominates(switch, ret));
+
+    ControlFlow::Continue(())
+}
+
+/// Checks that the traversals visit each reachable block once, in an order consistent with the
+/// edges which aren't back edges, and that the predecessors match the successors.
+fn check_traversals(body: &Body) {
+    let preorder: Vec<usize> = body.preorder().map(|(index, _)| index).collect();
+    let postorder: Vec<usize> = body.postorder().map(|(index, _)| index).collect();
+    let reverse_postorder = body.reverse_postorder();
+    assert_eq!(preorder[0], 0);
+    assert_eq!(postorder.last(), Some(&0));
+    assert_eq!(reverse_postorder[0], 0);
+    assert_eq!(sorted(&preorder), sorted(&postorder));
+    assert_eq!(sorted(&postorder), sorted(&reverse_postorder));
+
+    let dominators = body.dominators();
+    let predecessors = body.predecessors();
+    for (index, block) in body.blocks.iter().enumerate() {
+        assert_eq!(dominators.is_reachable(index), preorder.contains(&index));
+        for successor in block.terminator.kind.successors() {
+            assert!(predecessors[successor].contains(&index));
+            let position = |order: &[usize], block| order.iter().position(|b| *b == block);
+            if dominators.is_reachable(index) && !dominators.dominates(successor, index) {
+                assert!(
+                    position(&reverse_postorder, index) < position(&reverse_postorder, successor)
+                );
+            }
+        }
+    }
+}
+
+fn sorted(blocks: &[usize]) -> Vec<usize> {
+    let mut blocks = blocks.to_vec();
+    blocks.sort();
+    // No block is visited twice.
+    assert!(blocks.windows(2).all(|pair| pair[0] != pair[1]));
+    blocks
+}
+
+/// Returns the index of the first block of `body` whose terminator matches `f`.
+fn find_block(body: &Body, f: impl Fn(&TerminatorKind) -> bool) -> usize {
+    body.blocks.iter().position(|block| f(&block.terminator.kind)).unwrap()
+}
+
+// Use internal API to find a function in a crate.
+fn get_item<'a>(
+    tcx: TyCtxt,
+    items: &'a stable_mir::CrateItems,
+    name: &str,
+) -> Option<&'a stable_mir::CrateItem> {
+    items.iter().find(|crate_item| {
+        let def_id = rustc_internal::item_def_id(crate_item);
+        tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+    })
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_cfg.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args, test_cfg).run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn sum(n: u32) -> u32 {{
+        let mut total = 0;
+        let mut i = 0;
+        while i < n {{
+            total += i;
+            i += 1;
+        }}
+        total
+    }}
+
+    pub fn choose(x: bool) -> u32 {{
+        let y = if x {{ 1 }} else {{ 2 }};
+        y + 1
+    }}"#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,155 @@
+// run-pass
+// Test that the captures of closures and the layout of generators can be retrieved
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_hir;
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir;
+use rustc_smir::stable_mir::mir::{BorrowKind, MutBorrowKind, Mutability, ProjectionElem};
+use rustc_smir::stable_mir::ty::{CaptureKind, ClosureDef, RigidTy, TyKind};
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_closures(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    let captures = closure(tcx, &items, "by_ref").captures().unwrap();
+    let [read] = &captures[..] else { panic!("unexpected captures: {captures:?}") };
+    assert_eq!(read.var_name, "counter");
+    assert!(read.projection.is_empty());
+    assert_matches!(read.kind, CaptureKind::ByRef(BorrowKind::Shared));
+    assert_matches!(read.mutability, Mutability::Not);
+
+    let captures = closure(tcx, &items, "by_mut").captures().unwrap();
+    let [bump] = &captures[..] else { panic!("unexpected captures: {captures:?}") };
+    assert_eq!(bump.var_name, "counter");
+    let CaptureKind::ByRef(BorrowKind::Mut { kind }) = &bump.kind else {
+        panic!("unexpected capture kind: {:?}", bump.kind);
+    };
+    assert_matches!(kind, MutBorrowKind::Default);
+    assert_matches!(bump.mutability, Mutability::Mut);
+
+    let captures = closure(tcx, &items, "by_value").captures().unwrap();
+    let [take] = &captures[..] else { panic!("unexpected captures: {captures:?}") };
+    assert_eq!(take.var_name, "name");
+    assert_matches!(take.kind, CaptureKind::ByValue);
+
+    // Only the field used by the closure is captured.
+    let captures = closure(tcx, &items, "field").captures().unwrap();
+    let [len] = &captures[..] else { panic!("unexpected captures: {captures:?}") };
+    assert_eq!(len.var_name, "pair");
+    assert_matches!(&len.projection[..], [ProjectionElem::Field(1, _)]);
+    assert_matches!(len.kind, CaptureKind::ByRef(BorrowKind::Shared));
+
+    // The body of an async function is a generator which captures the arguments, and saves the
+    // locals which are live across an `.await`.
+    let body = get_item(tcx, &items, "suspend").unwrap().body();
+    let TyKind::RigidTy(RigidTy::Generator(generator, ..)) = body.locals[0].kind() else {
+        panic!("unexpected return type: {:?}", body.locals[0]);
+    };
+    let captures = generator.captures().unwrap();
+    let [name] = &captures[..] else { panic!("unexpected captures: {captures:?}") };
+    assert_eq!(name.var_name, "name");
+    assert_matches!(name.kind, CaptureKind::ByValue);
+    let layout = generator.layout().unwrap();
+    assert_eq!(layout.variant_fields.len(), 4);
+    assert_eq!(layout.variant_spans.len(), 4);
+    assert!(layout.variant_fields[..3].iter().all(|fields| fields.is_empty()));
+    let saved_name = layout.variant_fields[3]
+        .iter()
+        .any(|&local| layout.saved_locals[local].name.as_deref() == Some("name"));
+    assert!(saved_name);
+
+    ControlFlow::Continue(())
+}
+
+/// Returns the closure defined in the function `name`.
+fn closure(tcx: TyCtxt<'_>, items: &stable_mir::CrateItems, name: &str) -> ClosureDef {
+    let body = get_item(tcx, items, name).unwrap().body();
+    body.locals
+        .iter()
+        .find_map(|local| match local.kind() {
+            TyKind::RigidTy(RigidTy::Closure(def, _)) => Some(def),
+            _ => None,
+        })
+        .unwrap()
+}
+
+// Use internal API to find a function in a crate.
+fn get_item<'a>(
+    tcx: TyCtxt,
+    items: &'a stable_mir::CrateItems,
+    name: &str,
+) -> Option<&'a stable_mir::CrateItem> {
+    items.iter().find(|crate_item| {
+        let def_id = rustc_internal::item_def_id(crate_item);
+        tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+    })
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_closures.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--edition=2021".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args, test_closures).run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn by_ref(counter: u32) -> u32 {{
+        let read = || counter + 1;
+        read()
+    }}
+
+    pub fn by_mut(mut counter: u32) -> u32 {{
+        let mut bump = || counter += 1;
+        bump();
+        counter
+    }}
+
+    pub fn by_value(name: String) -> usize {{
+        let take = move || name.len();
+        take()
+    }}
+
+    pub fn field(pair: (u32, String)) -> usize {{
+        let len = || pair.1.len();
+        len()
+    }}
+
+    pub async fn suspend(name: String) -> usize {{
+        std::future::ready(()).await;
+        name.len()
+    }}"#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,126 @@
+// run-pass
+// Test that the value of constants can be read from their allocation
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_hir;
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir;
+use rustc_smir::stable_mir::mir::visit::{Location, MirVisitor};
+use rustc_smir::stable_mir::mir::{Body, Constant};
+use rustc_smir::stable_mir::ty::{Const, ConstantKind, RigidTy, TyKind};
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_constants(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let item = get_item(tcx, &items, "constants").unwrap();
+    let env = item.param_env();
+    let constants: Vec<_> =
+        constants(&item.body()).iter().map(|cnst| env.eval_const(cnst).unwrap()).collect();
+    let [int, negative, boolean, size, table, reference] = &constants[..] else {
+        panic!("unexpected constants: {constants:?}");
+    };
+
+    // Scalars are read as a whole.
+    assert_eq!(int.try_to_bits().unwrap(), 0x0102_0304);
+    assert_eq!(negative.try_to_bits().unwrap(), 0xFE);
+    assert_eq!(boolean.try_to_bits().unwrap(), 1);
+    assert_eq!(size.eval_target_usize().unwrap(), 12);
+    assert!(int.eval_target_usize().is_err());
+
+    // The elements of an array are read from its allocation.
+    let ConstantKind::Allocated(alloc) = &table.literal else {
+        panic!("unexpected constant: {table:?}");
+    };
+    assert_eq!(alloc.read_scalar(0, 2).unwrap(), 1);
+    assert_eq!(alloc.read_scalar(4, 2).unwrap(), 0x0304);
+    assert!(alloc.read_scalar(4, 4).is_err());
+    assert!(alloc.read_scalar(0, 0).is_err());
+
+    // The bytes of a pointer aren't a scalar.
+    assert!(reference.try_to_bits().is_err());
+
+    ControlFlow::Continue(())
+}
+
+/// Returns the constants passed as arguments in `body`.
+fn constants(body: &Body) -> Vec<Const> {
+    struct Constants(Vec<Const>);
+
+    impl MirVisitor for Constants {
+        fn visit_constant(&mut self, constant: &Constant, _location: Location) {
+            match constant.literal.ty.kind() {
+                TyKind::RigidTy(RigidTy::FnDef(..) | RigidTy::Tuple(_)) => {}
+                _ => self.0.push(constant.literal.clone()),
+            }
+        }
+    }
+
+    let mut visitor = Constants(vec![]);
+    visitor.visit_body(body);
+    visitor.0
+}
+
+// Use internal API to find a function in a crate.
+fn get_item<'a>(
+    tcx: TyCtxt,
+    items: &'a stable_mir::CrateItems,
+    name: &str,
+) -> Option<&'a stable_mir::CrateItem> {
+    items.iter().find(|crate_item| {
+        let def_id = rustc_internal::item_def_id(crate_item);
+        tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+    })
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_constants.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args, test_constants).run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    const TABLE: [u16; 3] = [1, 2, 0x0304];
+    const REFERENCE: &u8 = &1;
+
+    pub fn take<T>(_: T) {{}}
+
+    pub fn constants() {{
+        take(0x0102_0304u32);
+        take(-2i8);
+        take(true);
+        take(12usize);
+        take(TABLE);
+        take(REFERENCE);
+    }}"#
+    )?;
+    Ok(())
+}
@@ -15,6 +15,7 @@
 extern crate rustc_smir;
 
 use rustc_hir::def::DefKind;
+use rustc_hir::Mutability;
 use rustc_middle::ty::TyCtxt;
 use rustc_smir::{
     rustc_internal,
@@ -28,6 +29,9 @@
 
 /// This function uses the Stable MIR APIs to get information about the test crate.
 fn test_stable_mir(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let version = stable_mir::compiler_interface_version();
+    assert!(stable_mir::SUPPORTED_INTERFACE_VERSIONS.contains(&version));
+
     // Get the local crate using stable_mir API.
     let local = stable_mir::local_crate();
     assert_eq!(&local.name, CRATE_NAME);
@@ -47,22 +51,35 @@ fn test_stable_mir(tcx: TyCtxt<'_>) -> ControlFlow<()> {
     assert_eq!(body.blocks.len(), 1);
     let block = &body.blocks[0];
     assert_eq!(block.statements.len(), 1);
-    match &block.statements[0] {
-        stable_mir::mir::Statement::Assign(..) => {}
+    match &block.statements[0].kind {
+        stable_mir::mir::StatementKind::Assign(..) => {}
         other => panic!("{other:?}"),
     }
-    match &block.terminator {
-        stable_mir::mir::Terminator::Return => {}
+    match &block.terminator.kind {
+        stable_mir::mir::TerminatorKind::Return => {}
         other => panic!("{other:?}"),
     }
+    let statement_span = format!("{:?}", block.statements[0].span);
+    assert!(statement_span.starts_with("input.rs:"), "{statement_span}");
+    assert_ne!(statement_span, format!("{:?}", block.terminator.span));
+    assert_ne!(statement_span, format!("{:?}", body.span));
 
     let foo_bar = get_item(tcx, &items, (DefKind::Fn, "foo_bar")).unwrap();
     let body = foo_bar.body();
     assert_eq!(body.locals.len(), 7);
     assert_eq!(body.blocks.len(), 4);
     let block = &body.blocks[0];
-    match &block.terminator {
-        stable_mir::mir::Terminator::Call { .. } => {}
+    match &block.terminator.kind {
+        stable_mir::mir::TerminatorKind::Call { .. } => {}
+        other => panic!("{other:?}"),
+    }
+    let names: Vec<_> = body.var_debug_info.iter().map(|info| info.name.as_str()).collect();
+    assert_eq!(names, ["x", "y", "x_64", "y_64"]);
+    let x = &body.var_debug_info[0];
+    assert_eq!(x.argument_index, Some(1));
+    assert!(x.composite.is_none());
+    match &x.value {
+        stable_mir::mir::VarDebugInfoContents::Place(place) => assert_eq!(place.local, 1),
         other => panic!("{other:?}"),
     }
 
@@ -100,8 +117,8 @@ fn test_stable_mir(tcx: TyCtxt<'_>) -> ControlFlow<()> {
     let body = drop.body();
     assert_eq!(body.blocks.len(), 2);
     let block = &body.blocks[0];
-    match &block.terminator {
-        stable_mir::mir::Terminator::Drop { .. } => {}
+    match &block.terminator.kind {
+        stable_mir::mir::TerminatorKind::Drop { .. } => {}
         other => panic!("{other:?}"),
     }
 
@@ -109,15 +126,15 @@ fn test_stable_mir(tcx: TyCtxt<'_>) -> ControlFlow<()> {
     let body = assert.body();
     assert_eq!(body.blocks.len(), 2);
     let block = &body.blocks[0];
-    match &block.terminator {
-        stable_mir::mir::Terminator::Assert { .. } => {}
+    match &block.terminator.kind {
+        stable_mir::mir::TerminatorKind::Assert { .. } => {}
         other => panic!("{other:?}"),
     }
 
     let monomorphic = get_item(tcx, &items, (DefKind::Fn, "monomorphic")).unwrap();
     for block in monomorphic.body().blocks {
-        match &block.terminator {
-            stable_mir::mir::Terminator::Call { func, .. } => match func {
+        match &block.terminator.kind {
+            stable_mir::mir::TerminatorKind::Call { func, .. } => match func {
                 stable_mir::mir::Operand::Constant(c) => match &c.literal.literal {
                     stable_mir::ty::ConstantKind::Allocated(alloc) => {
                         assert!(alloc.bytes.is_empty());
@@ -149,14 +166,55 @@ fn test_stable_mir(tcx: TyCtxt<'_>) -> ControlFlow<()> {
                 },
                 other => panic!("{other:?}"),
             },
-            stable_mir::mir::Terminator::Return => {}
+            stable_mir::mir::TerminatorKind::Return => {}
             other => panic!("{other:?}"),
         }
     }
 
+    let accessors = get_item(tcx, &items, (DefKind::Fn, "accessors")).unwrap();
+    let locals = accessors.body().locals;
+    let sig = locals[1].fn_ptr_sig().unwrap();
+    assert_eq!(sig.value.inputs_and_output.len(), 2);
+    assert_eq!(locals[2].tuple_fields().unwrap().len(), 2);
+    assert_eq!(locals[3].array_len(), Ok(4));
+    let err = locals[1].tuple_fields().unwrap_err();
+    assert!(err.to_string().starts_with("expected a tuple type, found"), "{err}");
+    let generic = get_item(tcx, &items, (DefKind::Fn, "generic")).unwrap();
+    assert!(generic.body().locals[0].array_len().is_err());
+
+    let drops = get_item(tcx, &items, (DefKind::Fn, "drops")).unwrap();
+    let locals = drops.body().locals;
+    assert_eq!(locals[1].needs_drop(), Ok(true));
+    assert_eq!(locals[3].needs_drop(), Ok(false));
+    let fields = locals[1].field_drops().unwrap();
+    assert_eq!(fields.iter().map(|field| field.needs_drop).collect::<Vec<_>>(), [true, false]);
+    let fields = locals[2].field_drops().unwrap();
+    assert_eq!(fields.len(), 1);
+    assert_eq!((fields[0].variant, fields[0].field, fields[0].needs_drop), (Some(1), 0, true));
+    assert!(locals[1].drop_glue_body().unwrap().is_some());
+    assert!(locals[3].drop_glue_body().unwrap().is_none());
+    assert!(locals[3].field_drops().is_err());
+    // `T` may need to be dropped, but its drop glue is unknown.
+    let t = generic.body().locals[1];
+    assert_eq!(t.needs_drop(), Ok(true));
+    assert!(t.drop_glue_body().is_err());
+
     let foo_const = get_item(tcx, &items, (DefKind::Const, "FOO")).unwrap();
     // Ensure we don't panic trying to get the body of a constant.
     foo_const.body();
+    assert_eq!(foo_const.linkage_info(), stable_mir::LinkageInfo::default());
+
+    let exported = get_item(tcx, &items, (DefKind::Fn, "exported")).unwrap().linkage_info();
+    assert!(exported.no_mangle);
+    assert_eq!(exported.export_level, Some(stable_mir::SymbolExportLevel::C));
+    assert_eq!(exported.symbol_name.as_deref(), Some("exported"));
+    let renamed = get_item(tcx, &items, (DefKind::Fn, "renamed")).unwrap().linkage_info();
+    assert!(!renamed.no_mangle);
+    assert_eq!(renamed.export_name.as_deref(), Some("renamed_symbol"));
+    assert_eq!(renamed.symbol_name.as_deref(), Some("renamed_symbol"));
+    let kept = get_item(tcx, &items, (DefKind::Static(Mutability::Not), "KEPT")).unwrap();
+    assert!(kept.linkage_info().used.is_some());
+    assert_eq!(generic.linkage_info().symbol_name, None);
 
     ControlFlow::Continue(())
 }
@@ -229,9 +287,22 @@ pub fn types(b: bool, _: char, _: i32, _: u64, _: f64) -> bool {{
 
     pub fn drop(_: String) {{}}
 
+    pub fn accessors(_: fn(u8) -> bool, _: (u8, char), _: [u16; 4]) {{}}
+
+    pub fn drops(_: (String, u8), _: Option<Vec<u8>>, _: u32) {{}}
+
     pub fn assert(x: i32) -> i32 {{
         x + 1
-    }}"#
+    }}
+
+    #[no_mangle]
+    pub extern "C" fn exported() {{}}
+
+    #[export_name = "renamed_symbol"]
+    pub fn renamed() {{}}
+
+    #[used]
+    static KEPT: u32 = 0;"#
     )?;
     Ok(())
 }
@@ -0,0 +1,209 @@
+// run-pass
+// Test that dataflow analyses can be run on stable MIR bodies
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_hir;
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir;
+use rustc_smir::stable_mir::mir::dataflow::{
+    Analysis, AnalysisDomain, BitSet, Direction, GenKill, GenKillAnalysis, JoinSemiLattice,
+    MaybeLiveLocals,
+};
+use rustc_smir::stable_mir::mir::visit::Location;
+use rustc_smir::stable_mir::mir::{
+    Body, Local, Statement, StatementKind, Terminator, TerminatorKind, VarDebugInfoContents,
+};
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_dataflow(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = get_item(tcx, &items, "sum").unwrap().body();
+    let ret = terminator_location(&body, |kind| matches!(kind, TerminatorKind::Return));
+    let switch =
+        terminator_location(&body, |kind| matches!(kind, TerminatorKind::SwitchInt { .. }));
+
+    // Only the argument is live at the start, and only the return place when returning.
+    let mut live = MaybeLiveLocals.iterate_to_fixpoint(&body);
+    assert_eq!(elems(&live.state_before(&body, Location::START)), [1]);
+    assert_eq!(elems(&live.state_before(&body, ret)), [0]);
+    assert!(live.state_after(&body, ret).is_empty());
+    // The argument and the variables are used by the next iterations of the loop.
+    let at_switch = live.state_before(&body, switch);
+    assert!(at_switch.contains(1));
+    assert!(at_switch.contains(variable(&body, "total")));
+    assert!(at_switch.contains(variable(&body, "i")));
+
+    let mut assigned = AssignedLocals.iterate_to_fixpoint(&body);
+    assert!(assigned.state_before(&body, Location::START).is_empty());
+    assert!(assigned.state_before(&body, ret).contains(0));
+    assert!(assigned.state_before(&body, switch).contains(variable(&body, "total")));
+
+    let body = get_item(tcx, &items, "calls").unwrap().body();
+    let ret = terminator_location(&body, |kind| matches!(kind, TerminatorKind::Return));
+    let mut called = MaybeCalled.iterate_to_fixpoint(&body);
+    assert_eq!(called.state_before(&body, Location::START), Called(false));
+    assert_eq!(called.state_before(&body, ret), Called(true));
+
+    ControlFlow::Continue(())
+}
+
+/// The locals which may have been assigned, a forward gen/kill analysis.
+struct AssignedLocals;
+
+impl AnalysisDomain for AssignedLocals {
+    type Domain = BitSet;
+
+    const DIRECTION: Direction = Direction::Forward;
+
+    fn bottom_value(&self, body: &Body) -> BitSet {
+        BitSet::new_empty(body.locals.len())
+    }
+}
+
+impl GenKillAnalysis for AssignedLocals {
+    fn statement_effect(&mut self, trans: &mut impl GenKill, statement: &Statement, _: Location) {
+        match &statement.kind {
+            StatementKind::Assign(place, _) if place.projection.is_empty() => {
+                trans.gen(place.local)
+            }
+            StatementKind::StorageDead(local) => trans.kill(*local),
+            _ => {}
+        }
+    }
+
+    fn terminator_effect(
+        &mut self,
+        trans: &mut impl GenKill,
+        terminator: &Terminator,
+        _: Location,
+    ) {
+        if let TerminatorKind::Call { destination, .. } = &terminator.kind {
+            trans.gen(destination.local);
+        }
+    }
+}
+
+/// Whether a function may have been called, an analysis with its own lattice.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Called(bool);
+
+impl JoinSemiLattice for Called {
+    fn join(&mut self, other: &Self) -> bool {
+        let changed = other.0 && !self.0;
+        self.0 |= other.0;
+        changed
+    }
+}
+
+struct MaybeCalled;
+
+impl AnalysisDomain for MaybeCalled {
+    type Domain = Called;
+
+    const DIRECTION: Direction = Direction::Forward;
+
+    fn bottom_value(&self, _: &Body) -> Called {
+        Called(false)
+    }
+}
+
+impl Analysis for MaybeCalled {
+    fn apply_statement_effect(&mut self, _: &mut Called, _: &Statement, _: Location) {}
+
+    fn apply_terminator_effect(
+        &mut self,
+        state: &mut Called,
+        terminator: &Terminator,
+        _: Location,
+    ) {
+        if let TerminatorKind::Call { .. } = terminator.kind {
+            state.0 = true;
+        }
+    }
+}
+
+fn elems(set: &BitSet) -> Vec<Local> {
+    set.iter().collect()
+}
+
+/// Returns the location of the first terminator of `body` matching `f`.
+fn terminator_location(body: &Body, f: impl Fn(&TerminatorKind) -> bool) -> Location {
+    let block = body.blocks.iter().position(|block| f(&block.terminator.kind)).unwrap();
+    Location { block, statement_index: body.blocks[block].statements.len() }
+}
+
+/// Returns the local of the variable `name`.
+fn variable(body: &Body, name: &str) -> Local {
+    body.var_debug_info
+        .iter()
+        .find_map(|info| match &info.value {
+            VarDebugInfoContents::Place(place) if info.name == name => Some(place.local),
+            _ => None,
+        })
+        .unwrap()
+}
+
+// Use internal API to find a function in a crate.
+fn get_item<'a>(
+    tcx: TyCtxt,
+    items: &'a stable_mir::CrateItems,
+    name: &str,
+) -> Option<&'a stable_mir::CrateItem> {
+    items.iter().find(|crate_item| {
+        let def_id = rustc_internal::item_def_id(crate_item);
+        tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+    })
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_dataflow.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args, test_dataflow).run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn sum(n: u32) -> u32 {{
+        let mut total = 0;
+        let mut i = 0;
+        while i < n {{
+            total += i;
+            i += 1;
+        }}
+        total
+    }}
+
+    pub fn calls(x: bool) -> u32 {{
+        if x {{ sum(3) }} else {{ 0 }}
+    }}"#
+    )?;
+    Ok(())
+}
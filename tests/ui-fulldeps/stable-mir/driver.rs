@@ -0,0 +1,78 @@
+// run-pass
+// Test the driver entry of the tools which replace rustc
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal::{self, ToolError};
+use rustc_smir::stable_mir;
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const CRATE_NAME: &str = "input";
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn count_items(_: TyCtxt<'_>) -> Result<(), Vec<ToolError>> {
+    CALLS.fetch_add(1, Ordering::Relaxed);
+    assert_eq!(stable_mir::all_local_items().len(), 2);
+    Ok(())
+}
+
+fn report_errors(_: TyCtxt<'_>) -> Result<(), Vec<ToolError>> {
+    CALLS.fetch_add(1, Ordering::Relaxed);
+    let item = &stable_mir::all_local_items()[0];
+    Err(vec![
+        ToolError { message: "found an item".to_string(), span: Some(item.span()) },
+        ToolError { message: "found a crate".to_string(), span: None },
+    ])
+}
+
+/// This test will generate a dummy crate, and compile it with the driver like cargo does when
+/// the tool is set as `RUSTC_WRAPPER`, i.e. with the path of `rustc` before its arguments.
+fn main() {
+    let path = "input_driver.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "tool".to_string(),
+        "/path/to/rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--emit=metadata".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+
+    assert_eq!(rustc_internal::run_with_tcx(args.clone(), count_items), 0);
+    assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+
+    // The errors of the tool make the compilation fail.
+    assert_eq!(rustc_internal::run_with_tcx(args.clone(), report_errors), 1);
+    assert_eq!(CALLS.load(Ordering::Relaxed), 2);
+
+    // The tool isn't called when nothing is compiled.
+    let version = vec!["tool".to_string(), "rustc".to_string(), "--version".to_string()];
+    assert_eq!(rustc_internal::run_with_tcx(version, report_errors), 0);
+    assert_eq!(CALLS.load(Ordering::Relaxed), 2);
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn first() {{}}
+
+    pub fn second() {{}}
+    "#
+    )?;
+    Ok(())
+}
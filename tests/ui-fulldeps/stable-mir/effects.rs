@@ -0,0 +1,149 @@
+// run-pass
+// Test the summaries of the side effects of instances
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_hir;
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir;
+use rustc_smir::stable_mir::mir::effects::EffectSummary;
+use rustc_smir::stable_mir::mir::mono::Instance;
+use rustc_smir::stable_mir::ty::GenericArgs;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_effects(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let summary = |name| {
+        let item = get_item(tcx, &items, name).unwrap();
+        let def = rustc_internal::fn_def(rustc_internal::item_def_id(item));
+        Instance::resolve(def, &GenericArgs(vec![])).unwrap().effect_summary()
+    };
+
+    assert_eq!(summary("flip"), EffectSummary::default());
+    // The overflow check may panic.
+    assert_eq!(summary("add"), EffectSummary { may_unwind: true, ..EffectSummary::default() });
+    assert_eq!(summary("read"), EffectSummary { reads_globals: true, ..EffectSummary::default() });
+    let write = summary("write");
+    assert!(write.reads_globals && write.writes_globals);
+    assert!(!write.calls_unknown && !write.may_allocate);
+
+    // The effects of the callees are propagated, including through recursive calls.
+    assert_eq!(summary("read_recursive"), summary("read"));
+    assert!(summary("ping").writes_globals);
+    assert!(summary("pong").writes_globals);
+    assert!(!summary("ping").calls_unknown);
+
+    // A panic of `add` aborts instead of unwinding out of an `extern "C"` function.
+    assert_eq!(summary("add_no_unwind"), EffectSummary::default());
+
+    // The function pointer may point to anything.
+    let call = summary("call_pointer");
+    assert!(call.calls_unknown);
+    assert!(call.reads_globals && call.writes_globals && call.may_unwind && call.may_allocate);
+
+    // The call graph of a function calling itself with ever larger type arguments is infinite.
+    let grow = summary("grow");
+    assert!(grow.calls_unknown);
+    assert!(grow.reads_globals && grow.writes_globals && grow.may_unwind && grow.may_allocate);
+
+    ControlFlow::Continue(())
+}
+
+// Use internal API to find a function in a crate.
+fn get_item<'a>(
+    tcx: TyCtxt,
+    items: &'a stable_mir::CrateItems,
+    name: &str,
+) -> Option<&'a stable_mir::CrateItem> {
+    items.iter().find(|crate_item| {
+        let def_id = rustc_internal::item_def_id(crate_item);
+        tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+    })
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_effects.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args, test_effects).run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    static COUNT: u32 = 3;
+    static mut TOTAL: u32 = 0;
+
+    pub fn flip(x: u32) -> u32 {{
+        x ^ 0xff
+    }}
+
+    pub fn add(x: u32) -> u32 {{
+        x + 1
+    }}
+
+    pub fn read() -> u32 {{
+        COUNT
+    }}
+
+    pub fn write(x: u32) {{
+        unsafe {{ TOTAL = TOTAL ^ x }}
+    }}
+
+    pub fn read_recursive(n: u32) -> u32 {{
+        if n == 0 {{ read() }} else {{ read_recursive(n ^ 1) }}
+    }}
+
+    pub fn ping(n: u32) {{
+        if n > 0 {{ pong(n ^ 1) }}
+    }}
+
+    pub fn pong(n: u32) {{
+        if n > 1 {{ write(n) }} else {{ ping(n ^ 1) }}
+    }}
+
+    pub extern "C" fn add_no_unwind(x: u32) -> u32 {{
+        add(x)
+    }}
+
+    pub fn call_pointer(f: fn()) {{
+        f()
+    }}
+
+    // Not public, so that it isn't monomorphized, which would hit the recursion limit.
+    #[allow(dead_code)]
+    fn grow() {{
+        nest::<u8>(3)
+    }}
+
+    fn nest<T>(n: u32) {{
+        if n > 0 {{ nest::<Vec<T>>(n - 1) }}
+    }}"#
+    )?;
+    Ok(())
+}
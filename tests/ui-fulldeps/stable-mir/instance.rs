@@ -0,0 +1,133 @@
+// run-pass
+// Test that functions can be resolved to instances, with monomorphized bodies and an ABI
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_hir;
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir;
+use rustc_smir::stable_mir::abi::{CallConvention, PassMode};
+use rustc_smir::stable_mir::mir::mono::{Instance, InstanceKind};
+use rustc_smir::stable_mir::mir::{Body, Operand, TerminatorKind};
+use rustc_smir::stable_mir::ty::{FnDef, GenericArgs, RigidTy, TyKind, UintTy};
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_instance(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    // The callee of `call` is `generic::<u64>`, whose body only refers to `u64`.
+    let body = get_item(tcx, &items, "call").unwrap().body();
+    let (def, args) = callees(&body).pop().unwrap();
+    let instance = Instance::resolve(def, &args).unwrap();
+    assert_eq!(instance.kind, InstanceKind::Item);
+    assert_eq!(instance, Instance::resolve(def, &args).unwrap());
+    let body = instance.body().unwrap();
+    assert_matches!(body.locals[0].kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::U64)));
+    let layout = body.locals[0].layout().unwrap();
+    assert_eq!(layout.size, 8);
+    assert_eq!(layout.align, std::mem::align_of::<u64>() as u64);
+    // The polymorphic body still refers to the generic parameter.
+    let generic = get_item(tcx, &items, "generic").unwrap().body();
+    assert_matches!(generic.locals[0].kind(), TyKind::Param(_));
+    assert!(generic.locals[0].layout().is_err());
+
+    // The call to `T::default()` is resolved to the implementation of `Default` for `u64`.
+    let (def, args) = callees(&body).pop().unwrap();
+    let default = Instance::resolve(def, &args).unwrap();
+    assert_eq!(default.kind, InstanceKind::Item);
+    assert_ne!(default.ty(), instance.ty());
+
+    let pair = get_item(tcx, &items, "pair").unwrap();
+    let def = rustc_internal::fn_def(rustc_internal::item_def_id(pair));
+    let instance = Instance::resolve(def, &GenericArgs(vec![])).unwrap();
+    let fn_abi = instance.fn_abi().unwrap();
+    assert_eq!(fn_abi.conv, CallConvention::Rust);
+    assert!(!fn_abi.c_variadic);
+    assert_eq!(fn_abi.args.len(), 1);
+    assert_eq!(fn_abi.args[0].mode, PassMode::Pair);
+    assert_eq!(fn_abi.args[0].layout.size, std::mem::size_of::<&[u8]>() as u64);
+    assert_eq!(fn_abi.ret.mode, PassMode::Direct);
+    assert!(fn_abi.ret.ty == instance.body().unwrap().locals[0]);
+
+    ControlFlow::Continue(())
+}
+
+/// Returns the functions called in `body`, with their generic arguments.
+fn callees(body: &Body) -> Vec<(FnDef, GenericArgs)> {
+    body.blocks
+        .iter()
+        .filter_map(|block| match &block.terminator.kind {
+            TerminatorKind::Call { func: Operand::Constant(func), .. } => {
+                match func.literal.ty.kind() {
+                    TyKind::RigidTy(RigidTy::FnDef(def, args)) => Some((def, args)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+// Use internal API to find a function in a crate.
+fn get_item<'a>(
+    tcx: TyCtxt,
+    items: &'a stable_mir::CrateItems,
+    name: &str,
+) -> Option<&'a stable_mir::CrateItem> {
+    items.iter().find(|crate_item| {
+        let def_id = rustc_internal::item_def_id(crate_item);
+        tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+    })
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_instance.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args, test_instance).run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn generic<T: Default>() -> T {{
+        T::default()
+    }}
+
+    pub fn call() -> u64 {{
+        generic::<u64>()
+    }}
+
+    pub fn pair(x: &[u8]) -> usize {{
+        x.len()
+    }}"#
+    )?;
+    Ok(())
+}
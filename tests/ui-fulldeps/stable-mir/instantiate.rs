@@ -0,0 +1,112 @@
+// run-pass
+// Test the instantiation of generic parameters with generic arguments
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_hir;
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir::mir::{Operand, TerminatorKind};
+use rustc_smir::stable_mir::ty::{EarlyBinder, GenericArgs, RigidTy, TyKind};
+use rustc_smir::stable_mir::{self, all_trait_impls, trait_impl};
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_instantiate(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    // The call to `wrap::<u8, 3>` in `caller`.
+    let body = get_item(tcx, &items, "caller").unwrap().body();
+    let TerminatorKind::Call { func: Operand::Constant(func), .. } = &body.blocks[0].terminator.kind
+    else {
+        unreachable!()
+    };
+    let TyKind::RigidTy(RigidTy::FnDef(wrap, args)) = func.literal.ty.kind() else {
+        unreachable!()
+    };
+    assert_eq!(args.0.len(), 2);
+
+    // The signature of `wrap` mentions `T` and `N`, which are instantiated by the compiler.
+    let sig = wrap.fn_sig().value;
+    let output = *sig.inputs_and_output.last().unwrap();
+    assert!(matches!(output.kind(), TyKind::RigidTy(RigidTy::Adt(..))));
+    assert_eq!(output.subst(&args).unwrap(), body.locals[0]);
+    let sig = EarlyBinder { value: sig }.instantiate(&args).unwrap();
+    assert_eq!(sig.inputs_and_output, [*args.0[0].expect_ty(), body.locals[0]]);
+
+    // The arguments must match the parameters.
+    assert!(output.subst(&GenericArgs(vec![])).is_err());
+    assert!(output.subst(&GenericArgs(vec![args.0[1].clone(), args.0[0].clone()])).is_err());
+
+    // The constant arguments of the trait are instantiated too.
+    let impls = all_trait_impls();
+    assert_eq!(impls.len(), 1);
+    let trait_ref = trait_impl(&impls[0]).instantiate(&args).unwrap();
+    assert_eq!(*trait_ref.args.0[0].expect_ty(), body.locals[0]);
+    assert_eq!(trait_ref.args.0[1].expect_const().eval_target_usize(), Ok(3));
+    assert!(trait_impl(&impls[0]).instantiate(&GenericArgs(vec![])).is_err());
+
+    ControlFlow::Continue(())
+}
+
+// Use internal API to find a function in a crate.
+fn get_item<'a>(
+    tcx: TyCtxt,
+    items: &'a stable_mir::CrateItems,
+    name: &str,
+) -> Option<&'a stable_mir::CrateItem> {
+    items.iter().find(|crate_item| {
+        let def_id = rustc_internal::item_def_id(crate_item);
+        tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+    })
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_instantiate.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args, test_instantiate).run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub struct Wrapper<T, const N: usize>(pub [T; N]);
+
+    pub trait Dims<const N: usize> {{}}
+
+    impl<T, const N: usize> Dims<N> for Wrapper<T, N> {{}}
+
+    pub fn wrap<T: Copy, const N: usize>(x: T) -> Wrapper<T, N> {{
+        Wrapper([x; N])
+    }}
+
+    pub fn caller() -> Wrapper<u8, 3> {{
+        wrap::<u8, 3>(1)
+    }}"#
+    )?;
+    Ok(())
+}
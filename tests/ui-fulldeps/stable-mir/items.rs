@@ -0,0 +1,117 @@
+// run-pass
+// Test that the items of a crate can be listed by kind, and the implementations of traits found
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir::{self, CrateItems, ItemKind};
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_items(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let names = |items: CrateItems| -> Vec<String> {
+        let mut names: Vec<_> =
+            items.iter().map(|item| tcx.def_path_str(rustc_internal::item_def_id(item))).collect();
+        names.sort();
+        names
+    };
+
+    // The required method of `Shape` doesn't have a body, so it isn't listed.
+    assert_eq!(
+        names(stable_mir::items_of_kind(ItemKind::Fn)),
+        [
+            "<Square as Shape>::sides",
+            "<Square as std::default::Default>::default",
+            "Square::new",
+            "area"
+        ]
+    );
+    assert_eq!(names(stable_mir::items_of_kind(ItemKind::Static)), ["ORIGIN"]);
+    assert_eq!(names(stable_mir::items_of_kind(ItemKind::Const)), ["Shape::CORNERS", "SIDE"]);
+    assert_eq!(names(stable_mir::items_of_kind(ItemKind::Trait)), ["Shape"]);
+    assert_eq!(stable_mir::items_of_kind(ItemKind::Impl).len(), 3);
+    // Every function listed has a body.
+    for item in stable_mir::items_of_kind(ItemKind::Fn) {
+        assert!(!item.body().blocks.is_empty());
+    }
+
+    // The implementations of a local trait.
+    let shape = stable_mir::all_trait_decls().pop().unwrap();
+    assert_eq!(stable_mir::trait_impls_of(&shape).len(), 1);
+
+    // The implementations of a trait of `core` include the local one.
+    let impls = stable_mir::all_trait_impls();
+    let default = impls
+        .iter()
+        .find(|impl_def| stable_mir::trait_impl(impl_def).value.def_id != shape)
+        .unwrap();
+    let trait_def = stable_mir::trait_impl(default).value.def_id;
+    let default_impls = stable_mir::trait_impls_of(&trait_def);
+    assert!(default_impls.contains(default));
+    assert!(default_impls.len() > 1);
+
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_items.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args, test_items).run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub static ORIGIN: (u32, u32) = (0, 0);
+    pub const SIDE: u32 = 2;
+
+    pub trait Shape {{
+        const CORNERS: u32 = 4;
+        fn sides(&self) -> u32;
+    }}
+
+    #[derive(Default)]
+    pub struct Square;
+
+    impl Square {{
+        pub fn new() -> Square {{
+            Square
+        }}
+    }}
+
+    impl Shape for Square {{
+        fn sides(&self) -> u32 {{
+            4
+        }}
+    }}
+
+    pub fn area() -> u32 {{
+        SIDE * SIDE
+    }}"#
+    )?;
+    Ok(())
+}
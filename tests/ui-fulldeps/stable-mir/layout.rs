@@ -0,0 +1,131 @@
+// run-pass
+// Test that the layouts of types can be retrieved through stable MIR
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_hir;
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir;
+use rustc_smir::stable_mir::abi::{
+    FieldsShape, Primitive, Scalar, TagEncoding, VariantsShape, WrappingRange,
+};
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_layout(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = get_item(tcx, &items, "types").unwrap().body();
+    let layout = |local: usize| body.locals[local].layout().unwrap();
+
+    // `#[repr(C)]` structs are laid out in the order of their fields.
+    let pair = layout(1);
+    assert_eq!((pair.size, pair.align), (8, 4));
+    assert_eq!(pair.fields, FieldsShape::Arbitrary { offsets: vec![0, 4] });
+    assert_eq!(pair.variants, VariantsShape::Single { index: 0 });
+
+    // References can't be null, so `None` is stored as the null pointer in `Option<&u8>`.
+    let reference = layout(2);
+    assert_eq!(reference.fields, FieldsShape::Primitive);
+    let niche = reference.largest_niche.unwrap();
+    assert_eq!(niche.offset, 0);
+    assert_eq!(niche.value, Primitive::Pointer);
+    assert!(!niche.valid_range.contains(0));
+    let option = layout(3);
+    assert_eq!(option.size, reference.size);
+    let VariantsShape::Multiple { tag_encoding, variants, .. } = option.variants else {
+        panic!("unexpected variants {:?}", option.variants)
+    };
+    assert_eq!(
+        tag_encoding,
+        TagEncoding::Niche { untagged_variant: 1, niche_variants: 0..=0, niche_start: 0 }
+    );
+    assert_eq!(variants.len(), 2);
+
+    // The discriminant of `Tagged` is stored in a `u8` before the fields of the variants.
+    let tagged = layout(4);
+    let VariantsShape::Multiple { tag, tag_encoding, tag_field, variants } = tagged.variants else {
+        panic!("unexpected variants {:?}", tagged.variants)
+    };
+    assert_eq!(tag_encoding, TagEncoding::Direct);
+    assert_eq!(tagged.fields, FieldsShape::Arbitrary { offsets: vec![0] });
+    assert_eq!(tag_field, 0);
+    assert_matches!(
+        tag,
+        Scalar::Initialized {
+            value: Primitive::Int { size: 1, signed: false },
+            valid_range: WrappingRange { start: 0, end: 1 },
+        }
+    );
+    assert_eq!(variants[0].fields, FieldsShape::Arbitrary { offsets: vec![4] });
+
+    let array = layout(5);
+    assert_eq!(array.fields, FieldsShape::Array { stride: 2, count: 3 });
+    assert_eq!(array.size, 6);
+
+    ControlFlow::Continue(())
+}
+
+// Use internal API to find a function in a crate.
+fn get_item<'a>(
+    tcx: TyCtxt,
+    items: &'a stable_mir::CrateItems,
+    name: &str,
+) -> Option<&'a stable_mir::CrateItem> {
+    items.iter().find(|crate_item| {
+        let def_id = rustc_internal::item_def_id(crate_item);
+        tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+    })
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_layout.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args, test_layout).run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    #[repr(C)]
+    pub struct Pair {{
+        pub a: u8,
+        pub b: u32,
+    }}
+
+    #[repr(u8)]
+    pub enum Tagged {{
+        A(u32),
+        B,
+    }}
+
+    pub fn types(_p: Pair, _r: &u8, _o: Option<&u8>, _t: Tagged, _a: [u16; 3]) {{}}"#
+    )?;
+    Ok(())
+}
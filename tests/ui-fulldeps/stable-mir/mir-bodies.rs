@@ -0,0 +1,305 @@
+// run-pass
+// Test that whole bodies can be walked through stable MIR, with typed places and terminators
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_hir;
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir;
+use rustc_smir::stable_mir::mir::{
+    Body, Operand, Place, ProjectionElem, Rvalue, StatementKind, TerminatorKind, UnwindAction,
+};
+use rustc_smir::stable_mir::ty::RegionKind;
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_bodies(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    // Every body, optimized or not, only refers to its own locals and blocks.
+    for item in items.iter() {
+        check_body(&item.body());
+        check_body(&item.borrowck_facts().unwrap().body);
+    }
+
+    let body = get_item(tcx, &items, "fields", DefKind::Fn).unwrap().body();
+    let field = body.blocks[0]
+        .statements
+        .iter()
+        .find_map(|statement| match &statement.kind {
+            StatementKind::Assign(_, Rvalue::Use(Operand::Copy(place))) => Some(place),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(field.local, 1);
+    assert_matches!(field.projection[..], [ProjectionElem::Deref, ProjectionElem::Field(1, _)]);
+    // The type of the field is the return type.
+    let ProjectionElem::Field(_, ty) = field.projection[1] else { unreachable!() };
+    assert!(ty == body.locals[0]);
+
+    let body =
+        get_item(tcx, &items, "variants", DefKind::Fn).unwrap().borrowck_facts().unwrap().body;
+    assert!(places(&body).iter().any(|place| matches!(
+        place.projection[..],
+        [ProjectionElem::Downcast(1), ProjectionElem::Field(0, _)]
+    )));
+    assert!(has_terminator(&body, |kind| matches!(kind, TerminatorKind::FalseEdge { .. })));
+
+    let body = get_item(tcx, &items, "slices", DefKind::Fn).unwrap().borrowck_facts().unwrap().body;
+    let projections: Vec<_> = places(&body).into_iter().map(|place| place.projection).collect();
+    assert!(projections.contains(&vec![
+        ProjectionElem::Deref,
+        ProjectionElem::ConstantIndex { offset: 0, min_length: 2, from_end: false },
+    ]));
+    assert!(projections.contains(&vec![
+        ProjectionElem::Deref,
+        ProjectionElem::ConstantIndex { offset: 1, min_length: 2, from_end: true },
+    ]));
+
+    let body = get_item(tcx, &items, "count", DefKind::Fn).unwrap().borrowck_facts().unwrap().body;
+    assert!(has_terminator(&body, |kind| matches!(
+        kind,
+        TerminatorKind::FalseUnwind { unwind: UnwindAction::Continue, .. }
+    )));
+    // The terminators only used by the borrow checker are gone after the optimizations.
+    let body = get_item(tcx, &items, "count", DefKind::Fn).unwrap().body();
+    assert!(!has_terminator(&body, |kind| matches!(kind, TerminatorKind::FalseUnwind { .. })));
+
+    // The regions of the references are the region variables of the borrowck facts, and are
+    // erased in the optimized body.
+    let item = get_item(tcx, &items, "borrow", DefKind::Fn).unwrap();
+    let facts = item.borrowck_facts().unwrap();
+    let region = ref_regions(&facts.body).pop().unwrap();
+    let RegionKind::ReVar(region) = region else { panic!("unexpected region {region:?}") };
+    assert!(facts.loans.iter().any(|loan| loan.region == region));
+    assert!(ref_regions(&item.body()).iter().all(|region| matches!(region, RegionKind::ReErased)));
+
+    let generator = items
+        .iter()
+        .find(|item| tcx.def_kind(rustc_internal::item_def_id(item)) == DefKind::Generator)
+        .unwrap();
+    let body = generator.borrowck_facts().unwrap().body;
+    assert!(has_terminator(&body, |kind| matches!(kind, TerminatorKind::Yield { .. })));
+
+    ControlFlow::Continue(())
+}
+
+fn check_body(body: &Body) {
+    for place in places(body) {
+        assert!(place.local < body.locals.len());
+        for elem in &place.projection {
+            if let ProjectionElem::Index(local) = elem {
+                assert!(*local < body.locals.len());
+            }
+        }
+    }
+    for block in &body.blocks {
+        for target in successors(&block.terminator.kind) {
+            assert!(target < body.blocks.len());
+        }
+    }
+}
+
+/// Returns the places written to and read by the assignments and terminators of `body`.
+fn places(body: &Body) -> Vec<Place> {
+    fn operand(operand: &Operand, places: &mut Vec<Place>) {
+        if let Operand::Copy(place) | Operand::Move(place) = operand {
+            places.push(place.clone());
+        }
+    }
+
+    let mut places = Vec::new();
+    for block in &body.blocks {
+        for statement in &block.statements {
+            match &statement.kind {
+                StatementKind::Assign(place, rvalue) => {
+                    places.push(place.clone());
+                    match rvalue {
+                        Rvalue::Use(value) | Rvalue::Repeat(value, _) => {
+                            operand(value, &mut places)
+                        }
+                        Rvalue::BinaryOp(_, lhs, rhs) | Rvalue::CheckedBinaryOp(_, lhs, rhs) => {
+                            operand(lhs, &mut places);
+                            operand(rhs, &mut places);
+                        }
+                        Rvalue::Ref(_, _, place)
+                        | Rvalue::AddressOf(_, place)
+                        | Rvalue::Len(place)
+                        | Rvalue::Discriminant(place)
+                        | Rvalue::CopyForDeref(place) => places.push(place.clone()),
+                        _ => {}
+                    }
+                }
+                StatementKind::FakeRead(_, place) | StatementKind::PlaceMention(place) => {
+                    places.push(place.clone())
+                }
+                _ => {}
+            }
+        }
+        match &block.terminator.kind {
+            TerminatorKind::Call { func, args, destination, .. } => {
+                operand(func, &mut places);
+                args.iter().for_each(|arg| operand(arg, &mut places));
+                places.push(destination.clone());
+            }
+            TerminatorKind::SwitchInt { discr, .. } => operand(discr, &mut places),
+            TerminatorKind::Drop { place, .. } => places.push(place.clone()),
+            TerminatorKind::Yield { value, resume_arg, .. } => {
+                operand(value, &mut places);
+                places.push(resume_arg.clone());
+            }
+            _ => {}
+        }
+    }
+    places
+}
+
+fn successors(kind: &TerminatorKind) -> Vec<usize> {
+    let unwind = |unwind: &UnwindAction| match unwind {
+        UnwindAction::Cleanup(target) => Some(*target),
+        _ => None,
+    };
+    match kind {
+        TerminatorKind::Goto { target } => vec![*target],
+        TerminatorKind::SwitchInt { targets, otherwise, .. } => {
+            targets.iter().map(|target| target.target).chain([*otherwise]).collect()
+        }
+        TerminatorKind::Drop { target, unwind: action, .. }
+        | TerminatorKind::Assert { target, unwind: action, .. } => {
+            [Some(*target), unwind(action)].into_iter().flatten().collect()
+        }
+        TerminatorKind::Call { target, unwind: action, .. } => {
+            [*target, unwind(action)].into_iter().flatten().collect()
+        }
+        TerminatorKind::Yield { resume, drop, .. } => {
+            [Some(*resume), *drop].into_iter().flatten().collect()
+        }
+        TerminatorKind::FalseEdge { real_target, imaginary_target } => {
+            vec![*real_target, *imaginary_target]
+        }
+        TerminatorKind::FalseUnwind { real_target, unwind: action } => {
+            [Some(*real_target), unwind(action)].into_iter().flatten().collect()
+        }
+        TerminatorKind::InlineAsm { destination, unwind: action, .. } => {
+            [*destination, unwind(action)].into_iter().flatten().collect()
+        }
+        TerminatorKind::Resume
+        | TerminatorKind::Abort
+        | TerminatorKind::Return
+        | TerminatorKind::Unreachable
+        | TerminatorKind::GeneratorDrop => vec![],
+    }
+}
+
+fn has_terminator(body: &Body, predicate: impl Fn(&TerminatorKind) -> bool) -> bool {
+    body.blocks.iter().any(|block| predicate(&block.terminator.kind))
+}
+
+fn ref_regions(body: &Body) -> Vec<RegionKind> {
+    body.blocks
+        .iter()
+        .flat_map(|block| &block.statements)
+        .filter_map(|statement| match &statement.kind {
+            StatementKind::Assign(_, Rvalue::Ref(region, ..)) => Some(region.kind.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+// Use internal API to find an item in a crate.
+fn get_item<'a>(
+    tcx: TyCtxt,
+    items: &'a stable_mir::CrateItems,
+    name: &str,
+    kind: DefKind,
+) -> Option<&'a stable_mir::CrateItem> {
+    items.iter().find(|crate_item| {
+        let def_id = rustc_internal::item_def_id(crate_item);
+        tcx.def_kind(def_id) == kind && tcx.def_path_str(def_id) == name
+    })
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_mir_bodies.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args, test_bodies).with_borrowck_facts().run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    #![feature(generators, generator_trait)]
+
+    pub struct Pair {{
+        pub a: u8,
+        pub b: u16,
+    }}
+
+    pub fn fields(p: &Pair) -> u16 {{
+        p.b
+    }}
+
+    pub fn variants(x: Option<Pair>) -> u8 {{
+        match x {{
+            Some(p) => p.a,
+            None => 0,
+        }}
+    }}
+
+    pub fn slices(v: &[u8]) -> u8 {{
+        match v {{
+            [first, .., last] => *first ^ *last,
+            _ => 0,
+        }}
+    }}
+
+    pub fn count(n: u8) -> u8 {{
+        let mut i = 0;
+        loop {{
+            if i == n {{
+                return i;
+            }}
+            i += 1;
+        }}
+    }}
+
+    pub fn borrow(v: &mut Vec<u8>) -> usize {{
+        let r = &*v;
+        r.len()
+    }}
+
+    pub fn generator() -> impl std::ops::Generator<Yield = u8, Return = ()> {{
+        || {{
+            yield 1;
+        }}
+    }}"#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,107 @@
+// run-pass
+// Test that the patterns of stable MIR find the idioms they describe
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_hir;
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir::{
+    self,
+    mir::pattern::{call, switch_int, terminator, BlockPattern},
+    mir::TerminatorKind,
+};
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_patterns(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    let body = get_item(tcx, &items, "unwrap").unwrap().body();
+    // Calls to `Option::unwrap`, by any suffix of their path.
+    assert_eq!(call().to("Option::<T>::unwrap").find(&body).len(), 1);
+    assert_eq!(call().to("unwrap").with_arg_count(1).find(&body).len(), 1);
+    assert!(call().to("Option::<T>::expect").find(&body).is_empty());
+    assert!(call().with_arg_count(2).find(&body).is_empty());
+    // The result of the call isn't matched on.
+    assert!(call().then(switch_int().on_discriminant()).find(&body).is_empty());
+
+    let body = get_item(tcx, &items, "first").unwrap().body();
+    // A call to `Iterator::next` followed by a switch on the discriminant of its result.
+    let next_then_match = call().to("Iterator::next").then(switch_int().on_discriminant());
+    assert_eq!(next_then_match.find(&body).len(), 1);
+
+    let body = get_item(tcx, &items, "compare").unwrap().body();
+    // Switching on a boolean isn't switching on a discriminant.
+    assert_eq!(switch_int().find(&body).len(), 1);
+    assert!(switch_int().on_discriminant().find(&body).is_empty());
+
+    // Patterns can also be written as closures.
+    let returns = terminator(|kind| matches!(kind, TerminatorKind::Return));
+    assert_eq!(returns.find(&body).len(), 1);
+
+    ControlFlow::Continue(())
+}
+
+// Use internal API to find a function in a crate.
+fn get_item<'a>(
+    tcx: TyCtxt,
+    items: &'a stable_mir::CrateItems,
+    name: &str,
+) -> Option<&'a stable_mir::CrateItem> {
+    items.iter().find(|crate_item| {
+        let def_id = rustc_internal::item_def_id(crate_item);
+        tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+    })
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_mir_patterns.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args, test_patterns).run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn unwrap(x: Option<u8>) -> u8 {{
+        x.unwrap()
+    }}
+
+    pub fn first(v: &[u8]) -> u8 {{
+        match v.iter().next() {{
+            Some(x) => *x,
+            None => 0,
+        }}
+    }}
+
+    pub fn compare(a: u8, b: u8) -> u8 {{
+        if a < b {{ a }} else {{ b }}
+    }}"#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,143 @@
+// run-pass
+// Test that the visitor of stable MIR walks whole bodies
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_hir;
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir;
+use rustc_smir::stable_mir::mir::visit::{Location, MirVisitor, PlaceContext, TyContext};
+use rustc_smir::stable_mir::mir::{Body, Constant, Local, Statement, Terminator};
+use rustc_smir::stable_mir::ty::Ty;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+#[derive(Default)]
+struct Counts {
+    statements: usize,
+    terminators: usize,
+    local_decls: usize,
+    constants: usize,
+    /// The locals used with the context of each use.
+    locals: Vec<(Local, PlaceContext)>,
+    terminator_locations: Vec<Location>,
+}
+
+impl MirVisitor for Counts {
+    fn visit_statement(&mut self, statement: &Statement, location: Location) {
+        self.statements += 1;
+        self.super_statement(statement, location)
+    }
+
+    fn visit_terminator(&mut self, terminator: &Terminator, location: Location) {
+        self.terminators += 1;
+        self.terminator_locations.push(location);
+        self.super_terminator(terminator, location)
+    }
+
+    fn visit_local(&mut self, local: &Local, ptx: PlaceContext, _location: Location) {
+        self.locals.push((*local, ptx));
+    }
+
+    fn visit_ty(&mut self, _ty: &Ty, context: TyContext) {
+        if let TyContext::LocalDecl(_) = context {
+            self.local_decls += 1;
+        }
+    }
+
+    fn visit_constant(&mut self, constant: &Constant, location: Location) {
+        self.constants += 1;
+        self.super_constant(constant, location)
+    }
+}
+
+fn test_visitor(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    let body = get_item(tcx, &items, "sum").unwrap().body();
+    let counts = visit(&body);
+    assert_eq!(counts.statements, body.blocks.iter().map(|block| block.statements.len()).sum());
+    assert_eq!(counts.terminators, body.blocks.len());
+    assert_eq!(counts.local_decls, body.locals.len());
+    for (block, location) in counts.terminator_locations.iter().enumerate() {
+        assert_eq!(location.block, block);
+        assert_eq!(location.statement_index, body.blocks[block].statements.len());
+    }
+    // The return place is written to, and the arguments are read.
+    assert!(counts.locals.contains(&(0, PlaceContext::MutatingUse)));
+    assert!(counts.locals.contains(&(1, PlaceContext::NonMutatingUse)));
+    assert!(counts.locals.contains(&(2, PlaceContext::NonMutatingUse)));
+    assert!(!counts.locals.contains(&(1, PlaceContext::MutatingUse)));
+
+    let body = get_item(tcx, &items, "push").unwrap().body();
+    let counts = visit(&body);
+    // The vector is borrowed mutably to call `Vec::push`, which is a constant.
+    assert!(counts.locals.contains(&(1, PlaceContext::MutatingUse)));
+    assert!(counts.constants >= 2);
+
+    ControlFlow::Continue(())
+}
+
+fn visit(body: &Body) -> Counts {
+    let mut counts = Counts::default();
+    counts.visit_body(body);
+    counts
+}
+
+// Use internal API to find a function in a crate.
+fn get_item<'a>(
+    tcx: TyCtxt,
+    items: &'a stable_mir::CrateItems,
+    name: &str,
+) -> Option<&'a stable_mir::CrateItem> {
+    items.iter().find(|crate_item| {
+        let def_id = rustc_internal::item_def_id(crate_item);
+        tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+    })
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_mir_visitor.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args, test_visitor).run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn sum(a: u32, b: u32) -> u32 {{
+        if a > b {{ a - b }} else {{ a + b }}
+    }}
+
+    pub fn push(mut v: Vec<u8>) -> Vec<u8> {{
+        v.push(1);
+        v
+    }}"#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,152 @@
+// run-pass
+// Test that types and constants can be normalized in the environment of an item
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_hir;
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir;
+use rustc_smir::stable_mir::mir::visit::{Location, MirVisitor};
+use rustc_smir::stable_mir::mir::{Body, Constant};
+use rustc_smir::stable_mir::ty::{
+    Const, ConstEvalError, ConstState, ConstantKind, RigidTy, TyKind, UintTy,
+};
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_normalize(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    let square = get_item(tcx, &items, "square").unwrap();
+    let env = square.param_env();
+    let body = square.borrowck_facts().unwrap().body;
+    // The type of the field is the associated type of the trait until it is normalized.
+    let side = body.locals[1].field_drops().unwrap()[0].ty;
+    assert_matches!(side.kind(), TyKind::Alias(..));
+    let side = env.normalize(side).unwrap();
+    assert_matches!(side.kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::U32)));
+    assert_eq!(env.normalize(side), Ok(side));
+    let sides = unevaluated(&body);
+    assert_eq!(sides.state(), ConstState::Unevaluated);
+    let sides = env.normalize_const(&sides).unwrap();
+    assert_eq!(sides.state(), ConstState::Known);
+    assert_eq!(sides.eval_target_usize().unwrap(), 4);
+    let evaluated = env.eval_const(&unevaluated(&body)).unwrap();
+    assert_eq!(evaluated.eval_target_usize().unwrap(), 4);
+    assert_eq!(env.eval_const(&evaluated).unwrap().state(), ConstState::Known);
+
+    // The where clauses of the item are used to normalize the types.
+    let bytes = get_item(tcx, &items, "bytes").unwrap();
+    let env = bytes.param_env();
+    assert_eq!(env, bytes.param_env());
+    let body = bytes.borrowck_facts().unwrap().body;
+    let side = body.locals[1].field_drops().unwrap()[0].ty;
+    let side = env.normalize(side).unwrap();
+    assert_matches!(side.kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::U8)));
+    // The constant depends on a generic parameter, so it can't be evaluated.
+    let sides = env.normalize_const(&unevaluated(&body)).unwrap();
+    assert_matches!(sides.literal, ConstantKind::Unevaluated(_));
+    assert_eq!(env.eval_const(&unevaluated(&body)).unwrap_err(), ConstEvalError::TooGeneric);
+
+    // The associated type of a generic parameter without a constraint stays an alias.
+    let any = get_item(tcx, &items, "any").unwrap();
+    let body = any.borrowck_facts().unwrap().body;
+    let side = body.locals[1].field_drops().unwrap()[0].ty;
+    assert_matches!(any.param_env().normalize(side).unwrap().kind(), TyKind::Alias(..));
+
+    ControlFlow::Continue(())
+}
+
+/// Returns the first unevaluated constant of `body`.
+fn unevaluated(body: &Body) -> Const {
+    struct Unevaluated(Option<Const>);
+
+    impl MirVisitor for Unevaluated {
+        fn visit_constant(&mut self, constant: &Constant, _location: Location) {
+            if let ConstantKind::Unevaluated(_) = constant.literal.literal {
+                self.0.get_or_insert_with(|| constant.literal.clone());
+            }
+        }
+    }
+
+    let mut visitor = Unevaluated(None);
+    visitor.visit_body(body);
+    visitor.0.unwrap()
+}
+
+// Use internal API to find a function in a crate.
+fn get_item<'a>(
+    tcx: TyCtxt,
+    items: &'a stable_mir::CrateItems,
+    name: &str,
+) -> Option<&'a stable_mir::CrateItem> {
+    items.iter().find(|crate_item| {
+        let def_id = rustc_internal::item_def_id(crate_item);
+        tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+    })
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_normalize.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args, test_normalize).with_borrowck_facts().run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub trait Shape {{
+        type Side;
+        const SIDES: usize;
+    }}
+
+    pub struct Square;
+
+    impl Shape for Square {{
+        type Side = u32;
+        const SIDES: usize = 4;
+    }}
+
+    pub struct Holder<T: Shape> {{
+        pub side: T::Side,
+    }}
+
+    pub fn square(_h: Holder<Square>) -> usize {{
+        Square::SIDES
+    }}
+
+    pub fn bytes<T: Shape<Side = u8>>(_h: Holder<T>) -> usize {{
+        T::SIDES
+    }}
+
+    pub fn any<T: Shape>(_h: Holder<T>) {{}}"#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,148 @@
+// run-pass
+// Test that the hidden types of opaque types can be retrieved
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_hir;
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir;
+use rustc_smir::stable_mir::ty::{
+    AliasKind, AliasTy, GenericArgKind, GenericArgs, RigidTy, Ty, TyKind, UintTy,
+};
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_opaque(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    // The hidden type of a type alias defined by `counter`.
+    let counter = opaque(&output(tcx, &items, "counter"));
+    let range = counter.hidden_type().unwrap();
+    let TyKind::RigidTy(RigidTy::Adt(_, args)) = range.kind() else {
+        panic!("unexpected hidden type: {range:?}");
+    };
+    let [GenericArgKind::Type(u32_ty)] = &args.0[..] else {
+        panic!("unexpected arguments: {args:?}");
+    };
+    assert_matches!(u32_ty.kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::U32)));
+
+    // The hidden type of a generic function returning `impl Sized`, which can be instantiated.
+    let wrap = opaque(&output(tcx, &items, "wrap"));
+    let option = wrap.hidden_type().unwrap();
+    let TyKind::RigidTy(RigidTy::Adt(option_def, args)) = option.kind() else {
+        panic!("unexpected hidden type: {option:?}");
+    };
+    let [GenericArgKind::Type(param)] = &args.0[..] else {
+        panic!("unexpected arguments: {args:?}");
+    };
+    assert_matches!(param.kind(), TyKind::Param(_));
+    let instantiated =
+        AliasTy { def_id: wrap.def_id, args: GenericArgs(vec![GenericArgKind::Type(*u32_ty)]) };
+    let TyKind::RigidTy(RigidTy::Adt(def, args)) = instantiated.hidden_type().unwrap().kind()
+    else {
+        panic!("unexpected hidden type of {instantiated:?}");
+    };
+    assert_eq!(def, option_def);
+    assert_matches!(&args.0[..], [GenericArgKind::Type(arg)] if arg == u32_ty);
+    let missing = AliasTy { def_id: wrap.def_id, args: GenericArgs(vec![]) };
+    assert!(missing.hidden_type().is_err());
+
+    // The hidden type of `nested` is the opaque type returned by `wrap`.
+    let nested = opaque(&output(tcx, &items, "nested")).hidden_type().unwrap();
+    let inner = opaque(&nested);
+    assert_eq!(inner.def_id, wrap.def_id);
+    let hidden = inner.hidden_type().unwrap();
+    assert_matches!(hidden.kind(), TyKind::RigidTy(RigidTy::Adt(def, _)) if def == option_def);
+
+    // Functions returning concrete types don't return an opaque type.
+    let plain = output(tcx, &items, "plain");
+    assert_matches!(plain.kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::U32)));
+
+    ControlFlow::Continue(())
+}
+
+/// Returns the return type of the function `name`.
+fn output(tcx: TyCtxt<'_>, items: &stable_mir::CrateItems, name: &str) -> Ty {
+    let item = get_item(tcx, items, name).unwrap();
+    let def = rustc_internal::fn_def(rustc_internal::item_def_id(item));
+    *def.fn_sig().value.inputs_and_output.last().unwrap()
+}
+
+/// Returns the alias of the opaque type `ty`.
+fn opaque(ty: &Ty) -> AliasTy {
+    match ty.kind() {
+        TyKind::Alias(AliasKind::Opaque, alias) => alias,
+        kind => panic!("expected an opaque type, found {kind:?}"),
+    }
+}
+
+// Use internal API to find a function in a crate.
+fn get_item<'a>(
+    tcx: TyCtxt,
+    items: &'a stable_mir::CrateItems,
+    name: &str,
+) -> Option<&'a stable_mir::CrateItem> {
+    items.iter().find(|crate_item| {
+        let def_id = rustc_internal::item_def_id(crate_item);
+        tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+    })
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_opaque.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args, test_opaque).run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    #![feature(type_alias_impl_trait)]
+
+    pub type Counter = impl Iterator<Item = u32>;
+
+    pub fn counter() -> Counter {{
+        0..10u32
+    }}
+
+    pub fn wrap<T>(value: T) -> impl Sized {{
+        Some(value)
+    }}
+
+    pub fn nested() -> impl Sized {{
+        wrap(1u8)
+    }}
+
+    pub fn plain() -> u32 {{
+        0
+    }}"#
+    )?;
+    Ok(())
+}
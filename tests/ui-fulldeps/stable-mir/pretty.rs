@@ -0,0 +1,95 @@
+// run-pass
+// Test the pretty-printing of stable MIR bodies
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_hir;
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_pretty(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    let body = get_item(tcx, &items, "add_one").unwrap().body();
+    let mut dump = String::new();
+    body.dump(&mut dump, "add_one").unwrap();
+    let lines: Vec<&str> = dump.lines().collect();
+    assert_eq!(lines[0], "fn add_one(_1: u32) -> u32 {");
+    assert!(lines.contains(&"    debug x => _1;"));
+    assert!(lines.contains(&"    let _0: u32;"));
+    assert!(lines.contains(&"    bb0: {"));
+    assert!(lines.contains(&"        return;"));
+    assert_eq!(lines.last(), Some(&"}"));
+    assert!(dump.contains("CheckedAdd(_1, const 1_u32)"));
+    assert!(dump.contains("\"attempt to compute `{} + {}`, which would overflow\""));
+    assert!(dump.contains("-> [success: bb1, unwind continue];"));
+    // Without a name, the body is printed as an anonymous function.
+    assert!(body.to_string().starts_with("fn(_1: u32) -> u32 {\n"));
+
+    let body = get_item(tcx, &items, "call").unwrap().body();
+    let dump = body.to_string();
+    assert!(dump.contains("    let _0: (u32, bool);\n    let _1: u32;\n"));
+    assert!(dump.contains("_1 = add_one(const 2_u32) -> [return: bb1, unwind continue];"));
+    assert!(dump.contains("_0 = (move _1, const true);"));
+
+    ControlFlow::Continue(())
+}
+
+// Use internal API to find a function in a crate.
+fn get_item<'a>(
+    tcx: TyCtxt,
+    items: &'a stable_mir::CrateItems,
+    name: &str,
+) -> Option<&'a stable_mir::CrateItem> {
+    items.iter().find(|crate_item| {
+        let def_id = rustc_internal::item_def_id(crate_item);
+        tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+    })
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_pretty.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args, test_pretty).run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn add_one(x: u32) -> u32 {{
+        x + 1
+    }}
+
+    pub fn call() -> (u32, bool) {{
+        (add_one(2), true)
+    }}"#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,126 @@
+// run-pass
+// Test that the MIR of items can be saved and loaded back without the compiler
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir;
+use rustc_smir::stable_mir::mir::{Operand, TerminatorKind};
+use rustc_smir::stable_mir::serialize::{ItemSnapshot, Snapshot, SNAPSHOT_FORMAT_VERSION};
+use rustc_smir::stable_mir::ty::{RigidTy, TyKind, UintTy};
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_serialize(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let bytes = stable_mir::serialize(&items);
+    let snapshot = stable_mir::deserialize(&bytes).unwrap();
+    assert_eq!(snapshot.crate_name, CRATE_NAME);
+    assert_eq!(snapshot.interface_version, stable_mir::compiler_interface_version());
+    assert_eq!(snapshot.items.len(), items.len());
+    for (item, saved) in items.iter().zip(&snapshot.items) {
+        assert_eq!(item.body().blocks.len(), saved.body.blocks.len());
+        assert_eq!(item.body().locals.len(), saved.body.locals.len());
+    }
+
+    // The types are resolved with the snapshot.
+    let add = get_item(&snapshot, "add");
+    assert_eq!(add.body.locals.len(), 3);
+    for &local in &add.body.locals {
+        assert_matches!(snapshot.ty_kind(local), TyKind::RigidTy(RigidTy::Uint(UintTy::U32)));
+    }
+    assert!(snapshot.span(add.span).contains("input_serialize.rs"));
+
+    // So are the definitions, like the callee of a call.
+    let call = get_item(&snapshot, "call");
+    let callees: Vec<_> = call
+        .body
+        .blocks
+        .iter()
+        .filter_map(|block| match &block.terminator.kind {
+            TerminatorKind::Call { func: Operand::Constant(func), .. } => {
+                match snapshot.ty_kind(func.literal.ty) {
+                    TyKind::RigidTy(RigidTy::FnDef(def, _)) => Some(snapshot.fn_path(*def)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .collect();
+    assert!(callees.contains(&"add"), "{callees:?}");
+
+    // Snapshots which are truncated, or saved by another version, are rejected.
+    assert!(stable_mir::deserialize(&bytes[..bytes.len() - 1]).is_err());
+    assert!(stable_mir::deserialize(b"not a snapshot").is_err());
+    let mut other_version = bytes.clone();
+    other_version[4..8].copy_from_slice(&(SNAPSHOT_FORMAT_VERSION + 1).to_le_bytes());
+    let error = stable_mir::deserialize(&other_version).unwrap_err();
+    assert!(error.to_string().contains("version"), "{error}");
+    let mut other_interface = bytes.clone();
+    other_interface[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(stable_mir::deserialize(&other_interface).is_err());
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert!(stable_mir::deserialize(&trailing).is_err());
+
+    // Only the items which are asked for are saved.
+    let only_add: Vec<_> = items
+        .into_iter()
+        .filter(|item| tcx.def_path_str(rustc_internal::item_def_id(item)) == "add")
+        .collect();
+    let snapshot = stable_mir::deserialize(&stable_mir::serialize(&only_add)).unwrap();
+    assert_eq!(snapshot.items.len(), 1);
+    assert_eq!(snapshot.items[0].name, "add");
+
+    ControlFlow::Continue(())
+}
+
+fn get_item<'a>(snapshot: &'a Snapshot, name: &str) -> &'a ItemSnapshot {
+    snapshot.items.iter().find(|item| item.name == name).unwrap()
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_serialize.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args, test_serialize).run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn add(a: u32, b: u32) -> u32 {{
+        a ^ b
+    }}
+
+    pub fn call(x: &[u8]) -> u32 {{
+        add(x.len() as u32, 1)
+    }}"#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,77 @@
+// run-pass
+// Test that spans can be resolved to their file, lines and source code
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir::ty::LineInfo;
+use rustc_smir::stable_mir::{self, CrateItem};
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_span(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    // The span of a function is its signature.
+    let add = get_item(tcx, &items, "add").unwrap();
+    let span = add.span();
+    assert_eq!(span.get_filename(), "input_span.rs");
+    assert_eq!(
+        span.get_lines(),
+        LineInfo { start_line: 2, start_col: 5, end_line: 2, end_col: 38 }
+    );
+    assert_eq!(span.source_text().unwrap(), "pub fn add(a: u32, b: u32) -> u32");
+
+    // The span of its body covers several lines.
+    let span = add.body().span;
+    let lines = span.get_lines();
+    assert_eq!((lines.start_line, lines.end_line, lines.end_col), (2, 4, 6));
+    let text = span.source_text().unwrap();
+    assert!(text.contains("a ^ b") && text.ends_with('}'), "{text}");
+
+    ControlFlow::Continue(())
+}
+
+fn get_item<'a>(tcx: TyCtxt<'_>, items: &'a [CrateItem], name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|item| tcx.def_path_str(rustc_internal::item_def_id(item)) == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_span.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args, test_span).run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn add(a: u32, b: u32) -> u32 {{
+        a ^ b
+    }}"#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,97 @@
+// run-pass
+// Test the types, initializers and allocations of statics
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir::mir::Mutability;
+use rustc_smir::stable_mir::ty::{RigidTy, TyKind, UintTy};
+use rustc_smir::stable_mir::{self, ItemKind};
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_statics(_tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::items_of_kind(ItemKind::Static);
+    assert_eq!(items.len(), 3);
+    let get_static = |name| {
+        let item = items.iter().find(|item| item.name() == name).unwrap();
+        item.static_def().unwrap()
+    };
+
+    let count = get_static("COUNT");
+    assert!(matches!(count.ty().kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::U32))));
+    // The initializer returns the value of the static.
+    let body = count.initializer_body().unwrap();
+    assert_eq!(body.locals[0], count.ty());
+    let alloc = count.allocation().unwrap();
+    assert_eq!(alloc.bytes.len(), 4);
+    assert_eq!(alloc.read_scalar(0, 4), Ok(7));
+    assert!(matches!(alloc.mutability, Mutability::Not));
+
+    // The allocation of a static of references points to the referenced memory.
+    let names = get_static("NAMES");
+    let alloc = names.allocation().unwrap();
+    assert_eq!(alloc.provenance.ptrs.len(), 2);
+    assert!(matches!(alloc.mutability, Mutability::Mut));
+
+    // The statics of `extern` blocks are only declared.
+    let external = get_static("EXTERNAL");
+    assert!(matches!(external.ty().kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::U8))));
+    assert!(external.initializer_body().is_err());
+    assert!(external.allocation().is_err());
+
+    // Only statics are statics.
+    let function = stable_mir::items_of_kind(ItemKind::Fn);
+    assert_eq!(function.len(), 1);
+    assert_eq!(function[0].static_def(), None);
+
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_statics.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args, test_statics).run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub static COUNT: u32 = 3 + 4;
+
+    pub static mut NAMES: [&str; 2] = ["first", "second"];
+
+    extern "C" {{
+        pub static EXTERNAL: u8;
+    }}
+
+    pub fn read() -> u32 {{
+        COUNT
+    }}"#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,180 @@
+// run-pass
+// Test that trait implementations, associated items and method calls can be resolved
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_hir;
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_smir::stable_mir;
+use rustc_smir::stable_mir::mir::{Body, Operand, TerminatorKind};
+use rustc_smir::stable_mir::ty::{
+    AssocKind, FnDef, GenericArgKind, GenericArgs, RigidTy, TraitDef, TyKind, UintTy,
+};
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_traits(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let shape = get_trait(tcx, "Shape");
+    let polygon = get_trait(tcx, "Polygon");
+    let copy = rustc_internal::trait_def(tcx.lang_items().copy_trait().unwrap());
+    let no_args = GenericArgs(vec![]);
+
+    // Whether the types of the arguments of `uses` implement the traits.
+    let locals = get_item(tcx, &items, "uses").unwrap().body().locals;
+    let (square, circle, param) = (locals[1], locals[2], locals[3]);
+    assert!(stable_mir::implements_trait(square, &shape, &no_args).unwrap());
+    assert!(stable_mir::implements_trait(square, &polygon, &no_args).unwrap());
+    assert!(stable_mir::implements_trait(square, &copy, &no_args).unwrap());
+    assert!(stable_mir::implements_trait(circle, &shape, &no_args).unwrap());
+    assert!(!stable_mir::implements_trait(circle, &polygon, &no_args).unwrap());
+    assert!(!stable_mir::implements_trait(circle, &copy, &no_args).unwrap());
+    assert!(stable_mir::implements_trait(param, &shape, &no_args).is_err());
+    let extra = GenericArgs(vec![GenericArgKind::Type(square)]);
+    assert!(stable_mir::implements_trait(square, &shape, &extra).is_err());
+
+    // The items of a trait, in the order of declaration.
+    let items_of_polygon = stable_mir::associated_items(&polygon);
+    let names: Vec<_> = items_of_polygon.iter().map(|item| item.name.as_str()).collect();
+    assert_eq!(names, ["SIDES", "Unit", "new"]);
+    assert_matches!(items_of_polygon[0].kind, AssocKind::Const(_));
+    assert_matches!(items_of_polygon[1].kind, AssocKind::Type(_));
+    assert_matches!(items_of_polygon[2].kind, AssocKind::Fn { has_self: false, .. });
+    let items_of_shape = stable_mir::associated_items(&shape);
+    assert_matches!(items_of_shape[0].kind, AssocKind::Fn { has_self: true, .. });
+    let defaults: Vec<_> = items_of_shape.iter().map(|item| item.has_default).collect();
+    assert_eq!(defaults, [false, true]);
+
+    // The calls of `calls`, to a default method, to an implemented method and to a method of a
+    // trait object.
+    let body = get_item(tcx, &items, "calls").unwrap().body();
+    let [(name, name_args), (area, area_args), (dyn_area, dyn_args)] = &callees(&body)[..] else {
+        panic!("unexpected calls in {body:?}");
+    };
+    assert_eq!(stable_mir::resolve_call(*name, name_args).unwrap(), Some(*name));
+    let resolved = stable_mir::resolve_call(*area, area_args).unwrap().unwrap();
+    assert_ne!(resolved, *area);
+    let body = resolved.body();
+    assert_matches!(body.locals[0].kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::U32)));
+    assert_eq!(stable_mir::resolve_call(*dyn_area, dyn_args).unwrap(), None);
+
+    ControlFlow::Continue(())
+}
+
+/// Returns the functions called in `body`, with their generic arguments.
+fn callees(body: &Body) -> Vec<(FnDef, GenericArgs)> {
+    body.blocks
+        .iter()
+        .filter_map(|block| match &block.terminator.kind {
+            TerminatorKind::Call { func: Operand::Constant(func), .. } => {
+                match func.literal.ty.kind() {
+                    TyKind::RigidTy(RigidTy::FnDef(def, args)) => Some((def, args)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+// Use internal API to find a trait in a crate.
+fn get_trait(tcx: TyCtxt<'_>, name: &str) -> TraitDef {
+    let def_id = tcx.all_traits().find(|def_id| tcx.def_path_str(*def_id) == name).unwrap();
+    rustc_internal::trait_def(def_id)
+}
+
+// Use internal API to find a function in a crate.
+fn get_item<'a>(
+    tcx: TyCtxt,
+    items: &'a stable_mir::CrateItems,
+    name: &str,
+) -> Option<&'a stable_mir::CrateItem> {
+    items.iter().find(|crate_item| {
+        let def_id = rustc_internal::item_def_id(crate_item);
+        tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+    })
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "input_traits.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::StableMir::new(args, test_traits).run().unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub trait Shape {{
+        fn area(&self) -> u32;
+        fn name(&self) -> &'static str {{
+            "shape"
+        }}
+    }}
+
+    pub trait Polygon: Shape {{
+        const SIDES: u32;
+        type Unit;
+        fn new() -> Self;
+    }}
+
+    #[derive(Clone, Copy)]
+    pub struct Square(pub u32);
+
+    impl Shape for Square {{
+        fn area(&self) -> u32 {{
+            self.0 * self.0
+        }}
+    }}
+
+    impl Polygon for Square {{
+        const SIDES: u32 = 4;
+        type Unit = u32;
+        fn new() -> Self {{
+            Square(1)
+        }}
+    }}
+
+    pub struct Circle;
+
+    impl Shape for Circle {{
+        fn area(&self) -> u32 {{
+            3
+        }}
+    }}
+
+    pub fn uses<T>(_: Square, _: Circle, _: T) {{}}
+
+    pub fn calls(square: Square, shape: &dyn Shape) -> u32 {{
+        square.name();
+        square.area() + shape.area()
+    }}"#
+    )?;
+    Ok(())
+}
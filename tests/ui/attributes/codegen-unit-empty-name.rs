@@ -0,0 +1,6 @@
+#![feature(codegen_unit)]
+#![crate_type = "lib"]
+
+#[codegen_unit = ""]
+//~^ ERROR `#[codegen_unit]` requires a non-empty name
+pub fn foo() {}
@@ -0,0 +1,5 @@
+#[codegen_unit = "hot"]
+//~^ ERROR the `#[codegen_unit]` attribute is an experimental feature
+fn foo() {}
+
+fn main() {}
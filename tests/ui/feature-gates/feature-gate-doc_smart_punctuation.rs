@@ -0,0 +1,3 @@
+#![doc(smart_punctuation = false)] //~ ERROR: `#[doc(smart_punctuation)]` is experimental
+
+fn main() {}
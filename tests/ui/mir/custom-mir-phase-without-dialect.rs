@@ -0,0 +1,18 @@
+// Check that a phase in `#[custom_mir]` without a dialect is reported as an error.
+
+#![feature(custom_mir, core_intrinsics)]
+
+extern crate core;
+use core::intrinsics::mir::*;
+
+#[custom_mir(phase = "optimized")]
+//~^ ERROR cannot specify the phase `optimized` without a dialect
+fn f() {
+    mir!({
+        Return()
+    })
+}
+
+fn main() {
+    f()
+}
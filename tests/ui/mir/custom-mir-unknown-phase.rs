@@ -0,0 +1,18 @@
+// Check that an unknown phase in `#[custom_mir]` is reported as an error listing the valid ones.
+
+#![feature(custom_mir, core_intrinsics)]
+
+extern crate core;
+use core::intrinsics::mir::*;
+
+#[custom_mir(dialect = "runtime", phase = "optimised")]
+//~^ ERROR unknown runtime phase `optimised`
+fn f() {
+    mir!({
+        Return()
+    })
+}
+
+fn main() {
+    f()
+}
@@ -0,0 +1,27 @@
+// run-pass
+// compile-flags: -O -Zmir-opt-fixpoint-fuel=8 -Zvalidate-mir
+// Iterating a group of passes must preserve the behavior of the program.
+
+fn copies(x: u32) -> u32 {
+    let a = x;
+    let b = a;
+    let c = !!(b == a);
+    if c { b + 1 } else { 0 }
+}
+
+fn nested(x: Option<(u32, u32)>) -> u32 {
+    let y = x;
+    match y {
+        Some((a, b)) => {
+            let c = a;
+            c * b
+        }
+        None => 0,
+    }
+}
+
+fn main() {
+    assert_eq!(copies(41), 42);
+    assert_eq!(nested(Some((6, 7))), 42);
+    assert_eq!(nested(None), 0);
+}
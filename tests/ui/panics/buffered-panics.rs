@@ -0,0 +1,22 @@
+// run-pass
+// needs-unwind
+// only-unix
+// ignore-emscripten
+
+#![feature(panic_buffer, rustc_private)]
+
+extern crate libc;
+
+use std::panic;
+
+fn main() {
+    // Without standard error, the output of the default hook is buffered.
+    assert_eq!(unsafe { libc::close(libc::STDERR_FILENO) }, 0);
+    let result = panic::catch_unwind(|| panic!("nobody is listening"));
+    assert!(result.is_err());
+
+    let output = panic::take_buffered_panics();
+    assert!(output.starts_with("thread 'main' panicked at"), "{output}");
+    assert!(output.contains("nobody is listening\n"), "{output}");
+    assert_eq!(panic::take_buffered_panics(), "");
+}
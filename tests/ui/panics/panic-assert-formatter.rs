@@ -0,0 +1,38 @@
+// run-pass
+// needs-unwind
+#![feature(panic_assert_formatter)]
+
+use std::panic;
+
+fn message(f: impl FnOnce() + panic::UnwindSafe) -> String {
+    let payload = panic::catch_unwind(f).unwrap_err();
+    *payload.downcast::<String>().unwrap()
+}
+
+fn main() {
+    panic::set_hook(Box::new(|_| {}));
+
+    panic::set_assert_formatter(Box::new(|failure, f| {
+        write!(f, "{:?} {} {:?}", failure.left(), failure.op(), failure.right())?;
+        if let Some(message) = failure.message() {
+            write!(f, " ({message})")?;
+        }
+        Ok(())
+    }));
+    assert_eq!(message(|| assert_eq!(1 + 1, 3)), "2 == 3");
+    let ids = "users";
+    assert_eq!(message(|| assert_ne!("a", "a", "ids of {ids}")), r#""a" != "a" (ids of users)"#);
+    // Other panics are left alone.
+    let payload = panic::catch_unwind(|| panic!("not an assertion")).unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"not an assertion"));
+
+    // The default message is used again once the formatter is taken.
+    assert!(panic::take_assert_formatter().is_some());
+    assert!(panic::take_assert_formatter().is_none());
+    assert_eq!(
+        message(|| assert_eq!(1 + 1, 3)),
+        "assertion `left == right` failed\n  left: 2\n right: 3"
+    );
+
+    let _ = panic::take_hook();
+}
@@ -0,0 +1,49 @@
+// run-pass
+// needs-unwind
+// ignore-android FIXME #17520
+// ignore-emscripten no backtrace support
+// ignore-openbsd no support for libbacktrace without filename
+// ignore-sgx no backtrace support
+// ignore-fuchsia Backtraces not symbolized
+// compile-flags:-g
+// compile-flags:-Cstrip=none
+
+#![feature(panic_backtrace_location)]
+
+use std::panic;
+use std::sync::Mutex;
+
+static LOCATION: Mutex<Option<String>> = Mutex::new(None);
+
+#[inline(never)]
+fn overflow() -> Vec<u8> {
+    // The capacity overflow panic is raised inside alloc, which isn't
+    // `#[track_caller]`.
+    Vec::with_capacity(usize::MAX)
+}
+
+fn panic_location() -> String {
+    assert!(panic::catch_unwind(overflow).is_err());
+    LOCATION.lock().unwrap().take().unwrap()
+}
+
+fn main() {
+    let here = panic::caller_from_frame().unwrap();
+    assert!(here.file().ends_with("panic-backtrace-location.rs"), "{here}");
+
+    panic::set_hook(Box::new(|info| {
+        *LOCATION.lock().unwrap() = Some(info.location().unwrap().file().to_owned());
+    }));
+
+    let location = panic_location();
+    assert!(!location.ends_with("panic-backtrace-location.rs"), "{location}");
+
+    panic::set_backtrace_location_fallback(true);
+    let location = panic_location();
+    assert!(location.ends_with("panic-backtrace-location.rs"), "{location}");
+
+    // Panics reported outside of std keep their location.
+    assert!(panic::catch_unwind(|| panic!()).is_err());
+    let location = LOCATION.lock().unwrap().take().unwrap();
+    assert!(location.ends_with("panic-backtrace-location.rs"), "{location}");
+}
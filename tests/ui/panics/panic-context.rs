@@ -0,0 +1,24 @@
+// run-fail
+// check-run-results
+// exec-env:RUST_BACKTRACE=0
+
+#![feature(panic_context)]
+
+use std::panic;
+
+fn parse(entry: &str) -> u32 {
+    let _guard = panic::push_context(format!("parsing entry `{entry}`"));
+    entry.parse().unwrap()
+}
+
+fn main() {
+    let _config = panic::push_context("loading config");
+    assert_eq!(parse("1"), 1);
+    {
+        // Contexts are popped when their guard is dropped.
+        let _popped = panic::push_context("popped");
+    }
+    // Only the most recent contexts are printed.
+    let _levels: Vec<_> = (0..10).map(|i| panic::push_context(format!("level {i}"))).collect();
+    parse("two");
+}
@@ -0,0 +1,63 @@
+// run-pass
+// needs-unwind
+// ignore-emscripten no threads support
+
+// Panics must keep running a valid hook while other threads replace it.
+
+#![feature(panic_update_hook)]
+
+use std::panic;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn main() {
+    let done = Arc::new(AtomicBool::new(false));
+
+    let writers: Vec<_> = (0..2)
+        .map(|_| {
+            let done = done.clone();
+            thread::spawn(move || {
+                while !done.load(Ordering::Relaxed) {
+                    // The hook owns some data which is freed when it is replaced.
+                    let data = vec![1usize; 16];
+                    panic::set_hook(Box::new(move |_| {
+                        CALLS.fetch_add(data.iter().sum::<usize>() / 16, Ordering::Relaxed);
+                    }));
+                    panic::update_hook(|prev, info| prev(info));
+                    let _ = panic::take_hook();
+                }
+            })
+        })
+        .collect();
+
+    let panickers: Vec<_> = (0..4)
+        .map(|_| {
+            thread::spawn(|| {
+                for _ in 0..500 {
+                    let _ = panic::catch_unwind(|| panic!("boom"));
+                }
+            })
+        })
+        .collect();
+
+    for panicker in panickers {
+        panicker.join().unwrap();
+    }
+    done.store(true, Ordering::Relaxed);
+    for writer in writers {
+        writer.join().unwrap();
+    }
+
+    // Not all panics ran the custom hook, but none ran a freed one.
+    assert!(CALLS.load(Ordering::Relaxed) <= 4 * 500);
+
+    // After the writers are done, the last hook is reliably used.
+    panic::set_hook(Box::new(|_| {
+        CALLS.store(usize::MAX, Ordering::Relaxed);
+    }));
+    let _ = panic::catch_unwind(|| panic!("boom"));
+    assert_eq!(CALLS.load(Ordering::Relaxed), usize::MAX);
+}
@@ -0,0 +1,39 @@
+// run-pass
+// needs-unwind
+// ignore-emscripten no threads support
+
+#![feature(internal_output_capture)]
+
+use std::io::{self, Write};
+use std::panic;
+use std::str;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+fn main() {
+    panic::set_hook(Box::new(|info| {
+        let message = format!("custom hook: {info}\n");
+        match io::output_capture() {
+            Some(sink) => sink.lock().unwrap().write_all(message.as_bytes()).unwrap(),
+            None => io::stderr().write_all(message.as_bytes()).unwrap(),
+        }
+    }));
+
+    let data = Arc::new(Mutex::new(Vec::new()));
+    let res = thread::spawn({
+        let data = data.clone();
+        move || {
+            assert!(!io::is_output_captured());
+            io::set_output_capture(Some(data));
+            assert!(io::is_output_captured());
+            panic!("Hello, world!")
+        }
+    })
+    .join();
+    assert!(res.is_err());
+
+    let output = data.lock().unwrap();
+    let output = str::from_utf8(&output).unwrap();
+    assert!(output.contains("custom hook: "));
+    assert!(output.contains("Hello, world!"));
+}
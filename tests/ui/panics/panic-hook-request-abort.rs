@@ -0,0 +1,21 @@
+// run-fail
+// check-run-results
+// needs-unwind
+// ignore-emscripten "RuntimeError" junk in output
+#![feature(panic_payload_as_str)]
+#![feature(panic_request_abort)]
+
+use std::panic;
+
+fn main() {
+    panic::set_hook(Box::new(|info| {
+        eprintln!("hook saw: {}", panic::payload_as_str(info.payload()).unwrap());
+        info.request_abort();
+    }));
+
+    // The hook turns this panic into an abort, so it is never caught.
+    let _ = panic::catch_unwind(|| {
+        panic!("caught?");
+    });
+    println!("This should never be printed.");
+}
@@ -0,0 +1,14 @@
+// run-fail
+// check-run-results
+// ignore-emscripten "RuntimeError" junk in output
+
+// Checks that a panic inside the panic hook reports both panics before aborting.
+
+use std::panic;
+
+fn main() {
+    panic::set_hook(Box::new(|_| {
+        panic!("panic in the hook");
+    }));
+    panic!("outer panic");
+}
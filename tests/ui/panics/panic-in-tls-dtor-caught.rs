@@ -0,0 +1,27 @@
+// run-pass
+// check-run-results
+// exec-env:RUST_BACKTRACE=0
+// needs-unwind
+// ignore-emscripten no threads support
+
+// Checks that a panic raised by the destructor of a thread-local and caught inside the destructor
+// doesn't claim that the process will abort.
+
+use std::panic;
+use std::thread;
+
+struct Bomb;
+
+impl Drop for Bomb {
+    fn drop(&mut self) {
+        let _ = panic::catch_unwind(|| panic!("BOOM"));
+    }
+}
+
+thread_local! {
+    static BOMB: Bomb = Bomb;
+}
+
+fn main() {
+    thread::spawn(|| BOMB.with(|_| {})).join().unwrap();
+}
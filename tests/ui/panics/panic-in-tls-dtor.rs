@@ -0,0 +1,26 @@
+// run-fail
+// check-run-results
+// exec-env:RUST_BACKTRACE=0
+// needs-unwind
+// ignore-emscripten no threads support
+
+// Checks that a panic raised by the destructor of a thread-local says so before the process
+// aborts.
+
+use std::thread;
+
+struct Bomb;
+
+impl Drop for Bomb {
+    fn drop(&mut self) {
+        panic!("BOOM");
+    }
+}
+
+thread_local! {
+    static BOMB: Bomb = Bomb;
+}
+
+fn main() {
+    thread::spawn(|| BOMB.with(|_| {})).join().unwrap();
+}
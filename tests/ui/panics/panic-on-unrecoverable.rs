@@ -0,0 +1,48 @@
+// run-fail
+// check-run-results
+// needs-unwind
+// only-unix
+// ignore-emscripten "RuntimeError" junk in output
+#![feature(panic_on_unrecoverable)]
+#![feature(panic_payload_as_str)]
+
+// Checks that the callback set with `on_unrecoverable` is only called for the panics which abort.
+
+use std::fs::File;
+use std::io::Write;
+use std::mem::ManuallyDrop;
+use std::os::fd::FromRawFd;
+use std::panic::{self, UnrecoverablePanic};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static PANIC_IN_HOOK: AtomicBool = AtomicBool::new(false);
+
+fn on_unrecoverable(info: &UnrecoverablePanic<'_>) {
+    // Write to the file descriptor directly, since the lock of `stderr()` may be held.
+    let mut stderr = ManuallyDrop::new(unsafe { File::from_raw_fd(2) });
+    let _ = writeln!(
+        stderr,
+        "unrecoverable: {:?} at line {}, {:?}",
+        info.reason(),
+        info.location().line(),
+        info.message()
+    );
+}
+
+fn main() {
+    panic::on_unrecoverable(on_unrecoverable);
+    panic::set_hook(Box::new(|info| {
+        eprintln!("hook saw: {}", panic::payload_as_str(info.payload()).unwrap());
+        if PANIC_IN_HOOK.load(Ordering::Relaxed) {
+            panic!("panic in the hook");
+        }
+    }));
+
+    // This panic is caught, so the callback isn't called.
+    let _ = panic::catch_unwind(|| {
+        panic!("recoverable");
+    });
+
+    PANIC_IN_HOOK.store(true, Ordering::Relaxed);
+    panic!("unrecoverable");
+}
@@ -0,0 +1,34 @@
+// run-pass
+// needs-unwind
+#![feature(panic_payload_kind)]
+
+use std::any::TypeId;
+use std::panic::{self, PanicPayloadKind};
+use std::sync::Mutex;
+
+static KINDS: Mutex<Vec<PanicPayloadKind>> = Mutex::new(Vec::new());
+
+fn main() {
+    panic::set_hook(Box::new(|info| {
+        KINDS.lock().unwrap().push(info.payload_kind());
+    }));
+
+    let x = 42;
+    let _ = panic::catch_unwind(|| panic!("static message"));
+    let _ = panic::catch_unwind(|| panic!("formatted message: {x}"));
+    let _ = panic::catch_unwind(|| panic::panic_any(x));
+    let _ = panic::catch_unwind(|| panic::panic_any(String::from("owned message")));
+    // Resumed panics don't run the hook.
+    let _ = panic::catch_unwind(|| panic::resume_unwind(Box::new(x)));
+
+    let _ = panic::take_hook();
+    assert_eq!(
+        *KINDS.lock().unwrap(),
+        [
+            PanicPayloadKind::StaticStr,
+            PanicPayloadKind::FormattedString,
+            PanicPayloadKind::Any(TypeId::of::<i32>()),
+            PanicPayloadKind::Any(TypeId::of::<String>()),
+        ]
+    );
+}
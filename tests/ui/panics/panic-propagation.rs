@@ -0,0 +1,34 @@
+// run-pass
+// needs-unwind
+// ignore-emscripten no threads support
+
+#![feature(panic_propagation)]
+
+use std::panic::{self, PanicPayload};
+use std::thread;
+
+fn main() {
+    panic::set_hook(Box::new(|_| {}));
+
+    // The location of the panic is kept across threads.
+    let line = line!() + 1;
+    let task = thread::spawn(|| PanicPayload::catch(|| panic!("task failed")));
+    let panic = task.join().unwrap().unwrap_err();
+    assert_eq!(panic.payload().downcast_ref::<&str>(), Some(&"task failed"));
+    let location = *panic.location().unwrap();
+    assert_eq!((location.file(), location.line()), (file!(), line));
+
+    // Resuming the panic keeps its payload and location.
+    let panic = PanicPayload::catch(|| {
+        PanicPayload::catch(|| panic!("{}", 42)).unwrap_err().resume_in_place()
+    })
+    .unwrap_err();
+    assert_eq!(panic.payload().downcast_ref::<String>().map(|s| &**s), Some("42"));
+    assert_eq!(panic.location().unwrap().file(), file!());
+    assert!(format!("{panic:?}").contains("\"42\""));
+
+    // Panics resumed with `resume_unwind` have no location.
+    let panic = PanicPayload::catch(|| panic::resume_unwind(Box::new(1))).unwrap_err();
+    assert_eq!(panic.location(), None);
+    assert_eq!(*panic.into_payload().downcast::<i32>().unwrap(), 1);
+}
@@ -0,0 +1,42 @@
+// run-pass
+// needs-unwind
+// exec-env:RUST_PANIC_TIMINGS=1
+
+#![feature(panic_timings)]
+
+use std::panic;
+
+struct Slow;
+
+impl Drop for Slow {
+    fn drop(&mut self) {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+}
+
+fn main() {
+    assert_eq!(panic::unwind_timings(), Default::default());
+
+    panic::set_hook(Box::new(|_| std::thread::sleep(std::time::Duration::from_millis(10))));
+    let result = panic::catch_unwind(|| {
+        let _slow = Slow;
+        panic!("measured");
+    });
+    let payload = result.unwrap_err();
+
+    let timings = panic::unwind_timings();
+    assert_eq!(timings.hooks, 1);
+    assert_eq!(timings.unwinds, 1);
+    assert!(timings.hook_time.as_millis() >= 10, "{timings:?}");
+    assert!(timings.unwind_time.as_millis() >= 10, "{timings:?}");
+
+    // Resumed panics don't run the hook, but are unwound.
+    assert!(panic::catch_unwind(|| panic::resume_unwind(payload)).is_err());
+    let resumed = panic::unwind_timings();
+    assert_eq!(resumed.hooks, 1);
+    assert_eq!(resumed.unwinds, 2);
+
+    // The timings are per thread.
+    let other = std::thread::spawn(panic::unwind_timings).join().unwrap();
+    assert_eq!(other.hooks, 0);
+}
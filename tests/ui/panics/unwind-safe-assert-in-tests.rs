@@ -0,0 +1,43 @@
+// run-pass
+// check-run-results
+// exec-env:RUST_BACKTRACE=0
+// needs-unwind
+// normalize-stderr-test "0x[0-9a-f]+" -> "$$ADDR"
+
+// Checks that the locks and `RefCell`s modified inside a `catch_unwind` closure that panicked
+// are reported once when they are used again.
+
+#![feature(unwind_safe_assert_in_tests)]
+
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Mutex;
+
+fn main() {
+    panic::unwind_safe_assert_in_tests(true);
+
+    let balance = Mutex::new(100);
+    let history = RefCell::new(Vec::new());
+    let read_only = RefCell::new(0);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        *balance.lock().unwrap() -= 30;
+        history.borrow_mut().push(-30);
+        let _ = *read_only.borrow();
+        panic!("failed to credit the other account");
+    }));
+    assert!(result.is_err());
+
+    assert_eq!(*balance.lock().unwrap(), 70);
+    assert_eq!(*balance.lock().unwrap(), 70);
+    assert_eq!(*history.borrow(), [-30]);
+    assert_eq!(*history.borrow(), [-30]);
+    assert_eq!(*read_only.borrow(), 0);
+
+    panic::unwind_safe_assert_in_tests(false);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        history.borrow_mut().clear();
+        panic!("not tracked anymore");
+    }));
+    assert!(result.is_err());
+    assert!(history.borrow().is_empty());
+}